@@ -0,0 +1,128 @@
+/*
+ * OpenSkills runtime C ABI.
+ *
+ * Options are passed and results returned as JSON strings. Every function
+ * that can fail takes a `char **error` out-parameter: on failure it returns
+ * NULL and, when `error` is not NULL, stores a message there. All strings
+ * returned by this library must be released with openskills_string_free().
+ *
+ * A runtime handle may be shared between threads; calls on the same handle
+ * are serialized.
+ */
+#ifndef OPENSKILLS_H
+#define OPENSKILLS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct OpenSkillsRuntime OpenSkillsRuntime;
+
+/* Library version, e.g. "0.1.0". Static; do not free. */
+const char *openskills_version(void);
+
+/*
+ * Optional runtime features this library was built with, as a JSON array,
+ * e.g. ["wasm"]. Static; do not free.
+ */
+const char *openskills_features(void);
+
+/* Free a string returned by this library. NULL is ignored. */
+void openskills_string_free(char *s);
+
+/*
+ * Create a runtime. `options_json` may be NULL or an object with
+ * "custom_directories", "use_standard_locations" (default true),
+ * "project_root", "workspace_dir", "permissions" (host policy:
+ * "trust_skill_allowed_tools", "fallback", "deny", "allow") and
+ * "strict_permissions".
+ */
+OpenSkillsRuntime *openskills_runtime_new(const char *options_json, char **error);
+
+/* Free a runtime. NULL is ignored. */
+void openskills_runtime_free(OpenSkillsRuntime *runtime);
+
+/*
+ * Answers a permission prompt (host policy "fallback": "prompt").
+ * `request_json` is an object with "skill_id", "tool", "description",
+ * "risk_level" ("low", "medium" or "high") and "context". Return 0 to deny,
+ * 1 to allow once, 2 to allow the tool for the skill from now on or 3 to
+ * allow once with writes confined to the workspace.
+ */
+typedef int (*openskills_permission_callback)(void *user_data, const char *request_json);
+
+/* Releases the user data of a permission callback. */
+typedef void (*openskills_free_user_data)(void *user_data);
+
+/*
+ * Install a permission callback, replacing any previous one. A NULL callback
+ * removes it (prompts are then approved). `free_user_data` (may be NULL) is
+ * called with `user_data` when the callback is replaced, the runtime is
+ * freed, or right away when `callback` is NULL. The callback runs on the
+ * thread executing a skill while the runtime is locked, so it must be
+ * thread-safe and must not call back into the same runtime.
+ */
+void openskills_runtime_set_permission_callback(OpenSkillsRuntime *runtime,
+                                                openskills_permission_callback callback,
+                                                void *user_data,
+                                                openskills_free_user_data free_user_data);
+
+/* Discover skills from the configured locations. Returns a JSON array. */
+char *openskills_discover_skills(OpenSkillsRuntime *runtime, char **error);
+
+/* Load skills from `dir`. Returns a JSON array of the skills found there. */
+char *openskills_load_from_directory(OpenSkillsRuntime *runtime, const char *dir, char **error);
+
+/* List loaded skills. Returns a JSON array. */
+char *openskills_list_skills(OpenSkillsRuntime *runtime, char **error);
+
+/* Load a skill's full instructions. Returns a JSON object. */
+char *openskills_activate_skill(OpenSkillsRuntime *runtime, const char *skill_id, char **error);
+
+/* List a skill's WASM modules and scripts. Returns a JSON array. */
+char *openskills_list_skill_targets(OpenSkillsRuntime *runtime, const char *skill_id, char **error);
+
+/*
+ * Execute a skill's entry point. `options_json` may be NULL or an object
+ * with "timeout_ms", "memory_mb", "cpu_quota", "max_fuel", "input",
+ * "read_paths", "write_paths", "allow_network", "env" and
+ * "effective_tools" (tools granted to the sandbox, replacing the host-policy
+ * resolution), "dry_run" (resolve the execution without running it;
+ * "output" is then the execution plan and the audit status "dry_run"),
+ * "retry" ({"max_attempts", "backoff_ms", "max_backoff_ms", "retry_on"}:
+ * run again on "timeout", "failure" and/or "error", each attempt audited
+ * with its "attempt" number) and "traceparent" (W3C trace context, kept in
+ * the audit and passed to the skill as TRACEPARENT). Returns a JSON object
+ * with "output", "stdout", "stderr", "audit" and "capabilities_needed"
+ * ([{"tool", "resource"}]: for a failed run, the tools to ask the user for
+ * before retrying, from what the sandbox denied).
+ */
+char *openskills_execute_skill(OpenSkillsRuntime *runtime, const char *skill_id,
+                               const char *options_json, char **error);
+
+/*
+ * Run one script or WASM module of a skill. `options_json` may be NULL or
+ * an object with "target_type" ("auto", "script" or "wasm"), "path",
+ * "args", "timeout_ms", "input", "workspace_dir", "read_paths",
+ * "write_paths", "allow_network" and "env". Returns the same JSON as
+ * openskills_execute_skill().
+ */
+char *openskills_run_skill_target(OpenSkillsRuntime *runtime, const char *skill_id,
+                                  const char *options_json, char **error);
+
+/*
+ * Run a shell command in the OS sandbox. `options_json` may be NULL or an
+ * object with "allow_network", "allowed_hosts", "allowed_ports",
+ * "allow_process", "allowed_programs", "read_paths", "write_paths", "env",
+ * "timeout_ms" (default 30000), "sandbox_mode", "backend" and "container_image".
+ * Returns a JSON object with "exit_code", "stdout", "stderr" and
+ * "timed_out".
+ */
+char *openskills_run_sandboxed_command(const char *command, const char *working_dir,
+                                       const char *options_json, char **error);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OPENSKILLS_H */
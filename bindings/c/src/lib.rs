@@ -0,0 +1,958 @@
+//! C ABI for the OpenSkills runtime, used by the Go binding (and any other
+//! language with a C FFI).
+//!
+//! Options go in and results come out as JSON strings. Every function that
+//! can fail takes a `char **error` out-parameter: on failure it returns
+//! `NULL` and, when `error` is not `NULL`, stores a message there. Strings
+//! returned by this library (results and errors) must be released with
+//! [`openskills_string_free`]. A runtime handle may be used from several
+//! threads; calls on the same handle are serialized.
+//!
+//! Hosts can answer permission prompts (`fallback: prompt` in the host
+//! policy) with [`openskills_runtime_set_permission_callback`].
+//!
+//! `include/openskills.h` declares the functions.
+
+use openskills_runtime::{
+    run_sandboxed_command, CapabilityDowngrade, CapabilityNeeded, CommandPermissions, ContainerConfig,
+    ExecutionBackend, ExecutionOptions, ExecutionTarget, HostCall, HostPolicy, NetworkRequest,
+    OpenSkillRuntime, PermissionCallback, PermissionRequest, PermissionResponse, PermissionsConfig,
+    RetryPolicy, RuntimeAuditRecord, RuntimeConfig, RuntimeError, RuntimeExecutionStatus,
+    SandboxMode, SandboxOverrides, SandboxViolation, SkillLocation,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Opaque runtime handle.
+pub struct OpenSkillsRuntime {
+    inner: Mutex<OpenSkillRuntime>,
+}
+
+/// Options for [`openskills_runtime_new`], mirroring `RuntimeConfig`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RuntimeOptions {
+    custom_directories: Vec<String>,
+    use_standard_locations: Option<bool>,
+    project_root: Option<String>,
+    workspace_dir: Option<String>,
+    /// Host policy (`trust_skill_allowed_tools`, `fallback`, `deny`, `allow`).
+    permissions: Option<PermissionsConfig>,
+    strict_permissions: bool,
+}
+
+/// Sandbox overrides shared by skill and target executions.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct OverrideOptions {
+    read_paths: Vec<String>,
+    write_paths: Vec<String>,
+    allow_network: bool,
+    env: BTreeMap<String, String>,
+}
+
+impl OverrideOptions {
+    fn into_overrides(self) -> SandboxOverrides {
+        SandboxOverrides {
+            read_paths: self.read_paths.into_iter().map(PathBuf::from).collect(),
+            write_paths: self.write_paths.into_iter().map(PathBuf::from).collect(),
+            allow_network: self.allow_network,
+            env: self.env,
+        }
+    }
+}
+
+/// Options for [`openskills_execute_skill`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ExecuteOptions {
+    timeout_ms: Option<u64>,
+    memory_mb: Option<u64>,
+    cpu_quota: Option<f64>,
+    max_fuel: Option<u64>,
+    input: Option<Value>,
+    effective_tools: Option<Vec<String>>,
+    dry_run: bool,
+    retry: Option<RetryOptions>,
+    traceparent: Option<String>,
+    #[serde(flatten)]
+    overrides: OverrideOptions,
+}
+
+/// `retry` in [`ExecuteOptions`]; see `RetryPolicy::from_millis`.
+#[derive(Debug, Deserialize)]
+struct RetryOptions {
+    max_attempts: u32,
+    #[serde(default)]
+    backoff_ms: u64,
+    max_backoff_ms: Option<u64>,
+    #[serde(default)]
+    retry_on: Vec<String>,
+}
+
+impl RetryOptions {
+    fn into_policy(self) -> Result<RetryPolicy, String> {
+        RetryPolicy::from_millis(
+            self.max_attempts,
+            self.backoff_ms,
+            self.max_backoff_ms,
+            &self.retry_on,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Options for [`openskills_run_skill_target`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TargetOptions {
+    /// `auto` (default), `script` or `wasm`.
+    target_type: Option<String>,
+    path: Option<String>,
+    args: Vec<String>,
+    timeout_ms: Option<u64>,
+    input: Option<Value>,
+    workspace_dir: Option<String>,
+    #[serde(flatten)]
+    overrides: OverrideOptions,
+}
+
+/// Options for [`openskills_run_sandboxed_command`].
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct CommandOptions {
+    allow_network: bool,
+    allowed_hosts: Vec<String>,
+    allowed_ports: Vec<u16>,
+    allow_process: bool,
+    allowed_programs: Vec<String>,
+    read_paths: Vec<String>,
+    write_paths: Vec<String>,
+    env: BTreeMap<String, String>,
+    timeout_ms: u64,
+    /// `enforce` (default) or `disabled`.
+    sandbox_mode: Option<String>,
+    /// `auto` (default), `native` or `container`.
+    backend: Option<String>,
+    container_image: Option<String>,
+}
+
+impl Default for CommandOptions {
+    fn default() -> Self {
+        Self {
+            allow_network: false,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
+            allow_process: false,
+            allowed_programs: Vec::new(),
+            read_paths: Vec::new(),
+            write_paths: Vec::new(),
+            env: BTreeMap::new(),
+            timeout_ms: 30_000,
+            sandbox_mode: None,
+            backend: None,
+            container_image: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Requires {
+    bins: Vec<String>,
+    env: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct LoadedSkill {
+    id: String,
+    version: String,
+    name: String,
+    description: String,
+    allowed_tools: Vec<String>,
+    model: Option<String>,
+    context: Option<String>,
+    agent: Option<String>,
+    user_invocable: bool,
+    location: SkillLocation,
+    instructions: String,
+    requires: Option<Requires>,
+    missing_dependencies: Option<Requires>,
+}
+
+#[derive(Serialize)]
+struct AuditRecord {
+    skill_id: String,
+    version: String,
+    input_hash: String,
+    output_hash: String,
+    start_time_ms: u64,
+    duration_ms: u64,
+    permissions_used: Vec<String>,
+    /// `success`, `timeout`, `permission_denied`, `instruction_only`,
+    /// `failed:<reason>`, `sandbox_violation:<reason>` or
+    /// `schema_violation:<reason>`.
+    exit_status: String,
+    stdout: String,
+    stderr: String,
+    sandbox_mode: String,
+    sandbox_violations: Vec<SandboxViolation>,
+    fuel_consumed: Option<u64>,
+    network_requests: Vec<NetworkRequest>,
+    host_calls: Vec<HostCall>,
+    capability_downgrades: Vec<CapabilityDowngrade>,
+    attempt: u32,
+    traceparent: Option<String>,
+}
+
+impl From<RuntimeAuditRecord> for AuditRecord {
+    fn from(audit: RuntimeAuditRecord) -> Self {
+        let exit_status = match audit.exit_status {
+            RuntimeExecutionStatus::Success => "success".to_string(),
+            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
+            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
+            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
+            RuntimeExecutionStatus::SandboxViolation(msg) => format!("sandbox_violation:{}", msg),
+            RuntimeExecutionStatus::SchemaViolation(msg) => format!("schema_violation:{}", msg),
+            RuntimeExecutionStatus::InstructionOnly => "instruction_only".to_string(),
+            RuntimeExecutionStatus::DryRun => "dry_run".to_string(),
+        };
+        Self {
+            sandbox_mode: audit.sandbox_mode.as_audit_str().to_string(),
+            skill_id: audit.skill_id,
+            version: audit.version,
+            input_hash: audit.input_hash,
+            output_hash: audit.output_hash,
+            start_time_ms: audit.start_time_ms,
+            duration_ms: audit.duration_ms,
+            permissions_used: audit.permissions_used,
+            exit_status,
+            stdout: audit.stdout,
+            stderr: audit.stderr,
+            sandbox_violations: audit.sandbox_violations,
+            fuel_consumed: audit.fuel_consumed,
+            network_requests: audit.network_requests,
+            host_calls: audit.host_calls,
+            capability_downgrades: audit.capability_downgrades,
+            attempt: audit.attempt,
+            traceparent: audit.traceparent,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExecutionResult {
+    output: Value,
+    stdout: String,
+    stderr: String,
+    audit: AuditRecord,
+    capabilities_needed: Vec<CapabilityNeeded>,
+}
+
+impl From<openskills_runtime::ExecutionResult> for ExecutionResult {
+    fn from(result: openskills_runtime::ExecutionResult) -> Self {
+        Self {
+            output: result.output,
+            stdout: result.stdout,
+            stderr: result.stderr,
+            audit: result.audit.into(),
+            capabilities_needed: result.capabilities_needed,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CommandResult {
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+    timed_out: bool,
+}
+
+/// Run `f`, turning its result into a JSON string or an error message.
+/// Panics are caught so they never unwind into the caller.
+///
+/// # Safety
+///
+/// `error` must be `NULL` or valid for writes.
+unsafe fn ffi_call<T: Serialize>(
+    error: *mut *mut c_char,
+    f: impl FnOnce() -> Result<T, String>,
+) -> *mut c_char {
+    let result = panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err("panic inside openskills".to_string()))
+        .and_then(|value| serde_json::to_string(&value).map_err(|e| e.to_string()));
+    match result {
+        Ok(json) => into_c_string(json),
+        Err(message) => {
+            if !error.is_null() {
+                *error = into_c_string(message);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+fn into_c_string(s: String) -> *mut c_char {
+    // Interior NULs cannot be represented; JSON escapes them anyway.
+    CString::new(s.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+/// # Safety
+///
+/// `ptr` must be `NULL` or a NUL-terminated string valid for `'a`.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, String> {
+    if ptr.is_null() {
+        return Err(format!("{} must not be NULL", name));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| format!("{} is not valid UTF-8", name))
+}
+
+/// Parse optional JSON options; `NULL` or an empty string means defaults.
+///
+/// # Safety
+///
+/// As for [`str_arg`].
+unsafe fn json_arg<T: DeserializeOwned + Default>(ptr: *const c_char, name: &str) -> Result<T, String> {
+    if ptr.is_null() {
+        return Ok(T::default());
+    }
+    let text = str_arg(ptr, name)?;
+    if text.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(text).map_err(|e| format!("invalid {}: {}", name, e))
+}
+
+/// # Safety
+///
+/// `runtime` must be `NULL` or a handle from [`openskills_runtime_new`] that
+/// has not been freed.
+unsafe fn runtime_arg<'a>(runtime: *mut OpenSkillsRuntime) -> Result<&'a OpenSkillsRuntime, String> {
+    runtime.as_ref().ok_or_else(|| "runtime must not be NULL".to_string())
+}
+
+/// Library version, e.g. `0.1.0`. The string is static; do not free it.
+#[no_mangle]
+pub extern "C" fn openskills_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Optional runtime features this library was built with, as a JSON array,
+/// e.g. `["wasm"]`. The string is static; do not free it.
+#[no_mangle]
+pub extern "C" fn openskills_features() -> *const c_char {
+    static FEATURES: OnceLock<CString> = OnceLock::new();
+    FEATURES
+        .get_or_init(|| {
+            let json = serde_json::to_string(&openskills_runtime::features()).unwrap_or_default();
+            CString::new(json).unwrap_or_default()
+        })
+        .as_ptr()
+}
+
+/// Free a string returned by this library. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a string returned by this library that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Create a runtime. `options_json` (may be `NULL`) is an object with
+/// `custom_directories`, `use_standard_locations` (default true),
+/// `project_root`, `workspace_dir`, `permissions` (the host policy) and
+/// `strict_permissions`.
+///
+/// # Safety
+///
+/// `options_json` must be `NULL` or a NUL-terminated string; `error` must be
+/// `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_runtime_new(
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut OpenSkillsRuntime {
+    let result = panic::catch_unwind(|| {
+        let options: RuntimeOptions = json_arg(options_json, "options")?;
+        let config = RuntimeConfig {
+            custom_directories: options.custom_directories.into_iter().map(PathBuf::from).collect(),
+            use_standard_locations: options.use_standard_locations.unwrap_or(true),
+            project_root: options.project_root.map(PathBuf::from),
+            workspace_dir: options.workspace_dir.map(PathBuf::from),
+            native_runner_config: None,
+        };
+        let mut runtime = OpenSkillRuntime::from_config(config);
+        if let Some(permissions) = options.permissions {
+            runtime.set_host_policy(HostPolicy::from_config(permissions));
+        }
+        if options.strict_permissions {
+            runtime.set_strict_permissions();
+        }
+        Ok::<_, String>(runtime)
+    })
+    .unwrap_or_else(|_| Err("panic inside openskills".to_string()));
+    match result {
+        Ok(runtime) => Box::into_raw(Box::new(OpenSkillsRuntime {
+            inner: Mutex::new(runtime),
+        })),
+        Err(message) => {
+            if !error.is_null() {
+                *error = into_c_string(message);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free a runtime. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `runtime` must be `NULL` or a handle from [`openskills_runtime_new`] that
+/// has not been freed and is not in use by another thread.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_runtime_free(runtime: *mut OpenSkillsRuntime) {
+    if !runtime.is_null() {
+        drop(Box::from_raw(runtime));
+    }
+}
+
+/// Answers a permission prompt. `request_json` is an object with
+/// `skill_id`, `tool`, `description`, `risk_level` (`low`, `medium` or
+/// `high`) and `context`. Return 0 to deny, 1 to allow once, 2 to allow
+/// the tool for the skill from now on or 3 to allow once with writes
+/// confined to the workspace.
+pub type OpenSkillsPermissionCallback =
+    unsafe extern "C" fn(user_data: *mut c_void, request_json: *const c_char) -> c_int;
+
+/// Releases the `user_data` of a permission callback.
+pub type OpenSkillsFreeUserData = unsafe extern "C" fn(user_data: *mut c_void);
+
+/// A [`PermissionCallback`] implemented by the host through the C ABI.
+struct ForeignPermissionCallback {
+    callback: OpenSkillsPermissionCallback,
+    user_data: *mut c_void,
+    free_user_data: Option<OpenSkillsFreeUserData>,
+}
+
+// The host promises that the callback and its user data may be used from
+// any thread (see `openskills_runtime_set_permission_callback`).
+unsafe impl Send for ForeignPermissionCallback {}
+unsafe impl Sync for ForeignPermissionCallback {}
+
+impl PermissionCallback for ForeignPermissionCallback {
+    fn request_permission(&self, request: &PermissionRequest) -> Result<PermissionResponse, RuntimeError> {
+        let request = CString::new(serde_json::to_string(request)?.replace('\0', ""))
+            .map_err(|e| RuntimeError::PermissionDenied(e.to_string()))?;
+        match unsafe { (self.callback)(self.user_data, request.as_ptr()) } {
+            0 => Ok(PermissionResponse::Deny),
+            1 => Ok(PermissionResponse::AllowOnce),
+            2 => Ok(PermissionResponse::AllowAlways),
+            3 => Ok(PermissionResponse::AllowInWorkspace),
+            other => Err(RuntimeError::PermissionDenied(format!(
+                "permission callback returned {}",
+                other
+            ))),
+        }
+    }
+}
+
+impl Drop for ForeignPermissionCallback {
+    fn drop(&mut self) {
+        if let Some(free_user_data) = self.free_user_data {
+            unsafe { free_user_data(self.user_data) };
+        }
+    }
+}
+
+/// Install a permission callback, replacing any previous one (whose
+/// `free_user_data` is then called). A `NULL` callback removes it, which
+/// auto-approves prompts. `free_user_data` (may be `NULL`) is called with
+/// `user_data` when the callback is replaced or the runtime is freed (right
+/// away when `callback` is `NULL`).
+///
+/// The callback runs on the thread that is executing a skill, while the
+/// runtime is locked: it must not call back into the same runtime.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle. `callback` and `free_user_data` must be
+/// safe to call with `user_data` from any thread until `free_user_data` is
+/// called.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_runtime_set_permission_callback(
+    runtime: *mut OpenSkillsRuntime,
+    callback: Option<OpenSkillsPermissionCallback>,
+    user_data: *mut c_void,
+    free_user_data: Option<OpenSkillsFreeUserData>,
+) {
+    let Some(runtime) = runtime.as_ref() else {
+        return;
+    };
+    if let (None, Some(free_user_data)) = (callback, free_user_data) {
+        free_user_data(user_data);
+    }
+    let callback = callback.map(|callback| {
+        Arc::new(ForeignPermissionCallback {
+            callback,
+            user_data,
+            free_user_data,
+        }) as Arc<dyn PermissionCallback>
+    });
+    let mut runtime = runtime.inner.lock().unwrap();
+    runtime.set_permission_callback(callback);
+}
+
+/// Discover skills from the configured locations; returns a JSON array of
+/// skill descriptors.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle; `error` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_discover_skills(
+    runtime: *mut OpenSkillsRuntime,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let mut runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        runtime.discover_skills().map_err(|e| e.to_string())
+    })
+}
+
+/// Load skills from `dir` in addition to those already loaded; returns a
+/// JSON array of the skill descriptors found there.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle, `dir` a NUL-terminated string; `error`
+/// must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_load_from_directory(
+    runtime: *mut OpenSkillsRuntime,
+    dir: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let dir = str_arg(dir, "dir")?;
+        let mut runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        runtime.load_from_directory(dir).map_err(|e| e.to_string())
+    })
+}
+
+/// List loaded skills; returns a JSON array of skill descriptors.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle; `error` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_list_skills(
+    runtime: *mut OpenSkillsRuntime,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        Ok(runtime.list_skills())
+    })
+}
+
+/// Load a skill's full instructions; returns a JSON object.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle, `skill_id` a NUL-terminated string;
+/// `error` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_activate_skill(
+    runtime: *mut OpenSkillsRuntime,
+    skill_id: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let skill_id = str_arg(skill_id, "skill_id")?;
+        let runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        let loaded = runtime.activate_skill(skill_id).map_err(|e| e.to_string())?;
+        Ok(LoadedSkill {
+            version: loaded.manifest.version().to_string(),
+            name: loaded.manifest.name.clone(),
+            description: loaded.manifest.description.clone(),
+            allowed_tools: loaded.manifest.get_allowed_tools(),
+            model: loaded.manifest.model.clone(),
+            context: loaded.manifest.context.clone(),
+            agent: loaded.manifest.agent.clone(),
+            user_invocable: loaded.manifest.is_user_invocable(),
+            requires: loaded.requires.map(|r| Requires {
+                bins: r.bins,
+                env: r.env,
+            }),
+            missing_dependencies: loaded.missing_dependencies.map(|m| Requires {
+                bins: m.bins,
+                env: m.env,
+            }),
+            id: loaded.id,
+            location: loaded.location,
+            instructions: loaded.instructions,
+        })
+    })
+}
+
+/// List a skill's WASM modules and scripts; returns a JSON array of
+/// `{"path", "kind", "sandbox"}` objects.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle, `skill_id` a NUL-terminated string;
+/// `error` must be `NULL` or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_list_skill_targets(
+    runtime: *mut OpenSkillsRuntime,
+    skill_id: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let skill_id = str_arg(skill_id, "skill_id")?;
+        let runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        runtime.list_skill_targets(skill_id).map_err(|e| e.to_string())
+    })
+}
+
+/// Execute a skill's entry point. `options_json` (may be `NULL`) takes
+/// `timeout_ms`, `memory_mb`, `cpu_quota`, `max_fuel`, `input`,
+/// `read_paths`, `write_paths`, `allow_network`, `env`, `effective_tools`,
+/// `dry_run`, `retry` and `traceparent`. Returns a JSON object with `output`,
+/// `stdout`, `stderr`, `audit` and `capabilities_needed`.
+///
+/// # Safety
+///
+/// `runtime` must be a live handle, `skill_id` a NUL-terminated string,
+/// `options_json` `NULL` or a NUL-terminated string; `error` must be `NULL`
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_execute_skill(
+    runtime: *mut OpenSkillsRuntime,
+    skill_id: *const c_char,
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let skill_id = str_arg(skill_id, "skill_id")?;
+        let options: ExecuteOptions = json_arg(options_json, "options")?;
+        let options = ExecutionOptions {
+            timeout_ms: options.timeout_ms,
+            memory_mb: options.memory_mb,
+            cpu_quota: options.cpu_quota.filter(|q| *q > 0.0),
+            max_fuel: options.max_fuel,
+            input: options.input,
+            sandbox_overrides: options.overrides.into_overrides(),
+            effective_tools: options.effective_tools,
+            dry_run: options.dry_run,
+            retry: options.retry.map(RetryOptions::into_policy).transpose()?,
+            traceparent: options.traceparent,
+        };
+        let mut runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        runtime
+            .execute_skill(skill_id, options)
+            .map(ExecutionResult::from)
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Run one script or WASM module of a skill. `options_json` (may be `NULL`)
+/// takes `target_type` (`auto`, `script` or `wasm`), `path`, `args`,
+/// `timeout_ms`, `input`, `workspace_dir`, `read_paths`, `write_paths`,
+/// `allow_network` and `env`. Returns the same JSON as
+/// [`openskills_execute_skill`].
+///
+/// # Safety
+///
+/// As for [`openskills_execute_skill`].
+#[no_mangle]
+pub unsafe extern "C" fn openskills_run_skill_target(
+    runtime: *mut OpenSkillsRuntime,
+    skill_id: *const c_char,
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let skill_id = str_arg(skill_id, "skill_id")?;
+        let options: TargetOptions = json_arg(options_json, "options")?;
+        let target = match (options.target_type.as_deref(), options.path) {
+            (Some("script"), Some(path)) => ExecutionTarget::Script {
+                path,
+                args: options.args,
+            },
+            (Some("wasm"), Some(path)) => ExecutionTarget::Wasm { path },
+            (Some("script" | "wasm"), None) => return Err("path is required for script and wasm targets".to_string()),
+            (Some("auto") | None, Some(path)) => ExecutionTarget::Path {
+                path,
+                args: options.args,
+            },
+            (Some("auto") | None, None) => ExecutionTarget::Auto,
+            (Some(other), _) => return Err(format!("unknown target_type: {}", other)),
+        };
+        let mut runtime = runtime_arg(runtime)?.inner.lock().unwrap();
+        runtime
+            .run_skill_target_with_overrides(
+                skill_id,
+                target,
+                options.timeout_ms,
+                options.input,
+                options.workspace_dir.map(PathBuf::from),
+                options.overrides.into_overrides(),
+            )
+            .map(ExecutionResult::from)
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Run a shell command in the OS sandbox. `options_json` (may be `NULL`)
+/// takes `allow_network`, `allowed_hosts`, `allowed_ports`,
+/// `allow_process`, `allowed_programs`, `read_paths`, `write_paths`, `env`,
+/// `timeout_ms`
+/// (default 30000), `sandbox_mode`, `backend` and `container_image`.
+/// Returns a JSON object with `exit_code`, `stdout`, `stderr` and
+/// `timed_out`.
+///
+/// # Safety
+///
+/// `command` and `working_dir` must be NUL-terminated strings,
+/// `options_json` `NULL` or a NUL-terminated string; `error` must be `NULL`
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn openskills_run_sandboxed_command(
+    command: *const c_char,
+    working_dir: *const c_char,
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    ffi_call(error, || {
+        let command = str_arg(command, "command")?;
+        let working_dir = str_arg(working_dir, "working_dir")?;
+        let options: CommandOptions = json_arg(options_json, "options")?;
+        let permissions = CommandPermissions {
+            allow_network: options.allow_network,
+            allowed_hosts: options.allowed_hosts,
+            allowed_ports: options.allowed_ports,
+            allow_process: options.allow_process,
+            allowed_programs: options.allowed_programs,
+            read_paths: options.read_paths.into_iter().map(PathBuf::from).collect(),
+            write_paths: options.write_paths.into_iter().map(PathBuf::from).collect(),
+            env_vars: options.env.into_iter().collect(),
+            timeout_ms: options.timeout_ms,
+            sandbox_mode: match options.sandbox_mode.as_deref() {
+                Some("disabled") => SandboxMode::Disabled,
+                _ => SandboxMode::Enforce,
+            },
+            backend: options
+                .backend
+                .as_deref()
+                .map(str::parse::<ExecutionBackend>)
+                .transpose()
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default(),
+            container: ContainerConfig {
+                image: options
+                    .container_image
+                    .unwrap_or_else(|| ContainerConfig::default().image),
+                ..Default::default()
+            },
+        };
+        let result = run_sandboxed_command(command, &PathBuf::from(working_dir), permissions)
+            .map_err(|e| e.to_string())?;
+        Ok(CommandResult {
+            exit_code: result.exit_code,
+            stdout: result.stdout,
+            stderr: result.stderr,
+            timed_out: result.timed_out,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Take ownership of a returned string.
+    unsafe fn take(s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let owned = CStr::from_ptr(s).to_string_lossy().into_owned();
+        openskills_string_free(s);
+        Some(owned)
+    }
+
+    fn skills_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let skill = dir.path().join("echo-skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(
+            skill.join("SKILL.md"),
+            "---\nname: echo-skill\ndescription: Echoes its arguments.\nallowed-tools: Bash\n---\nRun scripts/echo.sh.\n",
+        )
+        .unwrap();
+        fs::write(skill.join("scripts/echo.sh"), "echo \"$@\"\n").unwrap();
+        dir
+    }
+
+    fn runtime(dir: &TempDir) -> *mut OpenSkillsRuntime {
+        let options = CString::new(
+            serde_json::json!({
+                "custom_directories": [dir.path()],
+                "use_standard_locations": false,
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let mut error = ptr::null_mut();
+        let runtime = unsafe { openskills_runtime_new(options.as_ptr(), &mut error) };
+        assert!(!runtime.is_null(), "{:?}", unsafe { take(error) });
+        runtime
+    }
+
+    #[test]
+    fn test_discover_activate_and_list_targets() {
+        let dir = skills_dir();
+        let runtime = runtime(&dir);
+        let id = CString::new("echo-skill").unwrap();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let skills: Value =
+                serde_json::from_str(&take(openskills_discover_skills(runtime, &mut error)).unwrap()).unwrap();
+            assert_eq!(skills[0]["id"], "echo-skill");
+            assert_eq!(skills[0]["location"], "custom");
+
+            let loaded: Value =
+                serde_json::from_str(&take(openskills_activate_skill(runtime, id.as_ptr(), &mut error)).unwrap())
+                    .unwrap();
+            assert_eq!(loaded["instructions"], "Run scripts/echo.sh.");
+            assert_eq!(loaded["allowed_tools"], serde_json::json!(["Bash"]));
+
+            let targets: Value = serde_json::from_str(
+                &take(openskills_list_skill_targets(runtime, id.as_ptr(), &mut error)).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(targets[0]["path"], "scripts/echo.sh");
+            assert!(error.is_null());
+            openskills_runtime_free(runtime);
+        }
+    }
+
+    #[test]
+    fn test_run_skill_target() {
+        let dir = skills_dir();
+        let runtime = runtime(&dir);
+        let id = CString::new("echo-skill").unwrap();
+        let options = CString::new(r#"{"path": "scripts/echo.sh", "args": ["hello"]}"#).unwrap();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let result = take(openskills_run_skill_target(runtime, id.as_ptr(), options.as_ptr(), &mut error));
+            let result: Value = serde_json::from_str(&result.unwrap_or_else(|| take(error).unwrap())).unwrap();
+            assert_eq!(result["stdout"].as_str().unwrap().trim(), "hello");
+            assert_eq!(result["audit"]["skill_id"], "echo-skill");
+            assert_eq!(result["audit"]["exit_status"], "success");
+            openskills_runtime_free(runtime);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_reported_through_out_parameter() {
+        let dir = skills_dir();
+        let runtime = runtime(&dir);
+        let missing = CString::new("missing").unwrap();
+        let bad_json = CString::new("{not json").unwrap();
+        unsafe {
+            let mut error = ptr::null_mut();
+            assert!(openskills_activate_skill(runtime, missing.as_ptr(), &mut error).is_null());
+            assert!(take(error).unwrap().contains("skill not found"));
+
+            let mut error = ptr::null_mut();
+            assert!(openskills_execute_skill(runtime, missing.as_ptr(), bad_json.as_ptr(), &mut error).is_null());
+            assert!(take(error).unwrap().starts_with("invalid options"));
+
+            // A NULL error pointer is allowed.
+            assert!(openskills_activate_skill(runtime, ptr::null(), ptr::null_mut()).is_null());
+            openskills_runtime_free(runtime);
+        }
+    }
+
+    #[test]
+    fn test_permission_callback_answers_prompts() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        unsafe extern "C" fn deny(user_data: *mut c_void, request_json: *const c_char) -> c_int {
+            let request: Value = serde_json::from_str(CStr::from_ptr(request_json).to_str().unwrap()).unwrap();
+            assert_eq!(request["skill_id"], "echo-skill");
+            assert_eq!(request["tool"], "Bash");
+            assert_eq!(request["risk_level"], "high");
+            (*(user_data as *const AtomicUsize)).fetch_add(1, Ordering::SeqCst);
+            0
+        }
+        unsafe extern "C" fn free_counter(user_data: *mut c_void) {
+            (*(user_data as *const AtomicUsize)).fetch_add(100, Ordering::SeqCst);
+        }
+
+        let dir = skills_dir();
+        let options = CString::new(
+            serde_json::json!({
+                "custom_directories": [dir.path()],
+                "use_standard_locations": false,
+                "permissions": {"trust_skill_allowed_tools": false, "fallback": "prompt"},
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let id = CString::new("echo-skill").unwrap();
+        let target = CString::new(r#"{"path": "scripts/echo.sh"}"#).unwrap();
+        let calls = Box::leak(Box::new(AtomicUsize::new(0)));
+        unsafe {
+            let runtime = openskills_runtime_new(options.as_ptr(), ptr::null_mut());
+            openskills_runtime_set_permission_callback(
+                runtime,
+                Some(deny),
+                calls as *const AtomicUsize as *mut c_void,
+                Some(free_counter),
+            );
+            let mut error = ptr::null_mut();
+            take(openskills_run_skill_target(runtime, id.as_ptr(), target.as_ptr(), &mut error));
+            take(error);
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+            // Freeing the runtime releases the user data.
+            openskills_runtime_free(runtime);
+            assert_eq!(calls.load(Ordering::SeqCst), 101);
+        }
+    }
+
+    #[test]
+    fn test_version_is_static() {
+        let version = unsafe { CStr::from_ptr(openskills_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_features_are_static_json() {
+        let features = unsafe { CStr::from_ptr(openskills_features()) };
+        let features: Vec<String> = serde_json::from_str(features.to_str().unwrap()).unwrap();
+        assert!(features.iter().any(|f| f == "wasm"));
+        assert_eq!(openskills_features(), openskills_features());
+    }
+}
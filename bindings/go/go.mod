@@ -0,0 +1,3 @@
+module github.com/Geeksfino/openskills/bindings/go
+
+go 1.21
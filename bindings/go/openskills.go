@@ -0,0 +1,448 @@
+// Package openskills is a cgo wrapper around the OpenSkills runtime C ABI
+// (bindings/c). Build the C library first:
+//
+//	cargo build --release -p openskills_c
+//
+// The package links against target/release/libopenskills; set CGO_LDFLAGS
+// to link a library installed elsewhere.
+package openskills
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../c/include
+#cgo LDFLAGS: -L${SRCDIR}/../../target/release -lopenskills
+#cgo linux LDFLAGS: -lm -ldl -lpthread
+#include <stdlib.h>
+#include "openskills.h"
+*/
+import "C"
+
+import (
+	"encoding/json"
+	"errors"
+	"runtime"
+	"sync"
+	"unsafe"
+)
+
+// Options configures a Runtime. It mirrors the Rust RuntimeConfig.
+type Options struct {
+	CustomDirectories []string `json:"custom_directories,omitempty"`
+	// UseStandardLocations defaults to true when nil.
+	UseStandardLocations *bool  `json:"use_standard_locations,omitempty"`
+	ProjectRoot          string `json:"project_root,omitempty"`
+	WorkspaceDir         string `json:"workspace_dir,omitempty"`
+	StrictPermissions    bool   `json:"strict_permissions,omitempty"`
+}
+
+// SkillDescriptor is the progressive-disclosure view of a skill.
+type SkillDescriptor struct {
+	ID string `json:"id"`
+	// Version is semver, "0.0.0" when the skill declares none.
+	Version         string `json:"version"`
+	Description     string `json:"description"`
+	Location        string `json:"location"`
+	UserInvocable   bool   `json:"user_invocable"`
+	RequiresSummary string `json:"requires_summary,omitempty"`
+	// Kind is instruction_only, script, wasm or hybrid.
+	Kind string `json:"kind"`
+}
+
+// Requires lists binaries and environment variables.
+type Requires struct {
+	Bins []string `json:"bins"`
+	Env  []string `json:"env"`
+}
+
+// LoadedSkill is a skill with its full instructions.
+type LoadedSkill struct {
+	ID                  string    `json:"id"`
+	Version             string    `json:"version"`
+	Name                string    `json:"name"`
+	Description         string    `json:"description"`
+	AllowedTools        []string  `json:"allowed_tools"`
+	Model               *string   `json:"model"`
+	Context             *string   `json:"context"`
+	Agent               *string   `json:"agent"`
+	UserInvocable       bool      `json:"user_invocable"`
+	Location            string    `json:"location"`
+	Instructions        string    `json:"instructions"`
+	Requires            *Requires `json:"requires"`
+	MissingDependencies *Requires `json:"missing_dependencies"`
+}
+
+// SkillTarget is a script or WASM module inside a skill.
+type SkillTarget struct {
+	// Path is relative to the skill root.
+	Path string `json:"path"`
+	// Kind is wasm, python or shell.
+	Kind string `json:"kind"`
+	// Sandbox is wasm or native.
+	Sandbox string `json:"sandbox"`
+}
+
+// SandboxOverrides grants an execution more than the skill declares.
+type SandboxOverrides struct {
+	ReadPaths    []string          `json:"read_paths,omitempty"`
+	WritePaths   []string          `json:"write_paths,omitempty"`
+	AllowNetwork bool              `json:"allow_network,omitempty"`
+	Env          map[string]string `json:"env,omitempty"`
+}
+
+// ExecuteOptions configures ExecuteSkill.
+type ExecuteOptions struct {
+	TimeoutMs *uint64  `json:"timeout_ms,omitempty"`
+	MemoryMb  *uint64  `json:"memory_mb,omitempty"`
+	CPUQuota  *float64 `json:"cpu_quota,omitempty"`
+	MaxFuel   *uint64  `json:"max_fuel,omitempty"`
+	Input     any      `json:"input,omitempty"`
+	// DryRun resolves the execution without running it: Output is then the
+	// execution plan and the audit status dry_run.
+	DryRun bool `json:"dry_run,omitempty"`
+	// Retry runs timeouts and failures again; each attempt is audited.
+	Retry *RetryOptions `json:"retry,omitempty"`
+	// Traceparent is the caller's W3C trace context, kept in the audit and
+	// passed to the skill as TRACEPARENT.
+	Traceparent string `json:"traceparent,omitempty"`
+	SandboxOverrides
+}
+
+// RetryOptions configures ExecuteOptions.Retry.
+type RetryOptions struct {
+	// MaxAttempts is the total number of attempts, including the first.
+	MaxAttempts uint32 `json:"max_attempts"`
+	// BackoffMs is the delay before the first retry, doubled for each
+	// further one up to MaxBackoffMs (default 30000).
+	BackoffMs    uint64  `json:"backoff_ms,omitempty"`
+	MaxBackoffMs *uint64 `json:"max_backoff_ms,omitempty"`
+	// RetryOn lists timeout, failure and/or error (default: timeout, failure).
+	RetryOn []string `json:"retry_on,omitempty"`
+}
+
+// TargetOptions configures RunSkillTarget.
+type TargetOptions struct {
+	// TargetType is auto (default), script or wasm.
+	TargetType   string   `json:"target_type,omitempty"`
+	Path         string   `json:"path,omitempty"`
+	Args         []string `json:"args,omitempty"`
+	TimeoutMs    *uint64  `json:"timeout_ms,omitempty"`
+	Input        any      `json:"input,omitempty"`
+	WorkspaceDir string   `json:"workspace_dir,omitempty"`
+	SandboxOverrides
+}
+
+// SandboxViolation is one operation the sandbox blocked.
+type SandboxViolation struct {
+	Operation string `json:"operation"`
+	Path      string `json:"path,omitempty"`
+}
+
+// NetworkRequest is an outbound HTTP request made by a WASM skill.
+type NetworkRequest struct {
+	Method  string `json:"method"`
+	URL     string `json:"url"`
+	Host    string `json:"host"`
+	Port    uint16 `json:"port"`
+	Allowed bool   `json:"allowed"`
+}
+
+// HostCall is a host function call made by a WASM skill.
+type HostCall struct {
+	Function string `json:"function"`
+	Target   string `json:"target"`
+	Allowed  bool   `json:"allowed"`
+}
+
+// CapabilityDowngrade is a requested tool the sandbox did not (fully) grant.
+type CapabilityDowngrade struct {
+	Tool string `json:"tool"`
+	// Reason is policy_denied, permission_denied, unmapped or platform.
+	Reason string `json:"reason"`
+	Detail string `json:"detail"`
+}
+
+// AuditRecord describes one execution.
+type AuditRecord struct {
+	SkillID         string   `json:"skill_id"`
+	Version         string   `json:"version"`
+	InputHash       string   `json:"input_hash"`
+	OutputHash      string   `json:"output_hash"`
+	StartTimeMs     uint64   `json:"start_time_ms"`
+	DurationMs      uint64   `json:"duration_ms"`
+	PermissionsUsed []string `json:"permissions_used"`
+	// ExitStatus is success, timeout, permission_denied, instruction_only,
+	// dry_run, failed:<reason>, sandbox_violation:<reason> or
+	// schema_violation:<reason>.
+	ExitStatus           string                `json:"exit_status"`
+	Stdout               string                `json:"stdout"`
+	Stderr               string                `json:"stderr"`
+	SandboxMode          string                `json:"sandbox_mode"`
+	SandboxViolations    []SandboxViolation    `json:"sandbox_violations"`
+	FuelConsumed         *uint64               `json:"fuel_consumed"`
+	NetworkRequests      []NetworkRequest      `json:"network_requests"`
+	HostCalls            []HostCall            `json:"host_calls"`
+	CapabilityDowngrades []CapabilityDowngrade `json:"capability_downgrades"`
+	// Attempt is 1 unless the execution was retried.
+	Attempt uint32 `json:"attempt"`
+	// Traceparent is the caller's W3C trace context, if one was passed.
+	Traceparent *string `json:"traceparent"`
+}
+
+// ExecutionResult is the outcome of ExecuteSkill or RunSkillTarget.
+type ExecutionResult struct {
+	Output json.RawMessage `json:"output"`
+	Stdout string          `json:"stdout"`
+	Stderr string          `json:"stderr"`
+	Audit  AuditRecord     `json:"audit"`
+	// CapabilitiesNeeded lists, for a failed execution, the tools to ask
+	// the user for before retrying, from what the sandbox denied.
+	CapabilitiesNeeded []CapabilityNeeded `json:"capabilities_needed"`
+}
+
+// CapabilityNeeded is a capability a failed execution was denied.
+type CapabilityNeeded struct {
+	Tool string `json:"tool"`
+	// Resource is the path, host or URL the execution tried to reach.
+	Resource *string `json:"resource,omitempty"`
+}
+
+// CommandOptions configures RunSandboxedCommand.
+type CommandOptions struct {
+	AllowNetwork bool              `json:"allow_network,omitempty"`
+	AllowedHosts []string          `json:"allowed_hosts,omitempty"`
+	AllowedPorts []uint16          `json:"allowed_ports,omitempty"`
+	AllowProcess bool              `json:"allow_process,omitempty"`
+	ReadPaths    []string          `json:"read_paths,omitempty"`
+	WritePaths   []string          `json:"write_paths,omitempty"`
+	Env          map[string]string `json:"env,omitempty"`
+	// TimeoutMs defaults to 30000 when zero.
+	TimeoutMs uint64 `json:"timeout_ms,omitempty"`
+	// SandboxMode is enforce (default) or disabled.
+	SandboxMode string `json:"sandbox_mode,omitempty"`
+	// Backend is auto (default), native or container.
+	Backend        string `json:"backend,omitempty"`
+	ContainerImage string `json:"container_image,omitempty"`
+}
+
+// CommandResult is the outcome of RunSandboxedCommand.
+type CommandResult struct {
+	ExitCode int    `json:"exit_code"`
+	Stdout   string `json:"stdout"`
+	Stderr   string `json:"stderr"`
+	TimedOut bool   `json:"timed_out"`
+}
+
+// Runtime is a handle to an OpenSkills runtime. It is safe for concurrent
+// use; calls are serialized by the runtime.
+type Runtime struct {
+	mu     sync.RWMutex
+	handle *C.OpenSkillsRuntime
+}
+
+var errClosed = errors.New("openskills: runtime is closed")
+
+// Version returns the version of the linked C library.
+func Version() string {
+	return C.GoString(C.openskills_version())
+}
+
+// Features returns the optional runtime features the linked C library was
+// built with, e.g. "wasm".
+func Features() []string {
+	var features []string
+	// The library always returns a JSON array of strings.
+	_ = json.Unmarshal([]byte(C.GoString(C.openskills_features())), &features)
+	return features
+}
+
+// New creates a runtime. A nil opts uses the defaults.
+func New(opts *Options) (*Runtime, error) {
+	if opts == nil {
+		opts = &Options{}
+	}
+	cOpts, err := jsonCString(opts)
+	if err != nil {
+		return nil, err
+	}
+	defer C.free(unsafe.Pointer(cOpts))
+
+	var cErr *C.char
+	handle := C.openskills_runtime_new(cOpts, &cErr)
+	if handle == nil {
+		return nil, takeError(cErr)
+	}
+	r := &Runtime{handle: handle}
+	runtime.SetFinalizer(r, (*Runtime).Close)
+	return r, nil
+}
+
+// Close frees the runtime. It is safe to call more than once.
+func (r *Runtime) Close() error {
+	r.mu.Lock()
+	defer r.mu.Unlock()
+	if r.handle != nil {
+		C.openskills_runtime_free(r.handle)
+		r.handle = nil
+	}
+	runtime.SetFinalizer(r, nil)
+	return nil
+}
+
+// DiscoverSkills discovers skills from the configured locations.
+func (r *Runtime) DiscoverSkills() ([]SkillDescriptor, error) {
+	var skills []SkillDescriptor
+	err := r.call(&skills, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return C.openskills_discover_skills(h, cErr)
+	})
+	return skills, err
+}
+
+// LoadFromDirectory loads skills from dir in addition to those already
+// loaded and returns the ones found there.
+func (r *Runtime) LoadFromDirectory(dir string) ([]SkillDescriptor, error) {
+	cDir := C.CString(dir)
+	defer C.free(unsafe.Pointer(cDir))
+	var skills []SkillDescriptor
+	err := r.call(&skills, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return C.openskills_load_from_directory(h, cDir, cErr)
+	})
+	return skills, err
+}
+
+// ListSkills lists loaded skills.
+func (r *Runtime) ListSkills() ([]SkillDescriptor, error) {
+	var skills []SkillDescriptor
+	err := r.call(&skills, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return C.openskills_list_skills(h, cErr)
+	})
+	return skills, err
+}
+
+// ActivateSkill loads a skill's full instructions.
+func (r *Runtime) ActivateSkill(skillID string) (*LoadedSkill, error) {
+	cID := C.CString(skillID)
+	defer C.free(unsafe.Pointer(cID))
+	var loaded LoadedSkill
+	err := r.call(&loaded, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return C.openskills_activate_skill(h, cID, cErr)
+	})
+	if err != nil {
+		return nil, err
+	}
+	return &loaded, nil
+}
+
+// ListSkillTargets lists a skill's WASM modules and scripts.
+func (r *Runtime) ListSkillTargets(skillID string) ([]SkillTarget, error) {
+	cID := C.CString(skillID)
+	defer C.free(unsafe.Pointer(cID))
+	var targets []SkillTarget
+	err := r.call(&targets, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return C.openskills_list_skill_targets(h, cID, cErr)
+	})
+	return targets, err
+}
+
+// ExecuteSkill runs a skill's entry point. A nil opts uses the defaults.
+func (r *Runtime) ExecuteSkill(skillID string, opts *ExecuteOptions) (*ExecutionResult, error) {
+	if opts == nil {
+		opts = &ExecuteOptions{}
+	}
+	return r.execute(skillID, opts, func(h *C.OpenSkillsRuntime, id, o *C.char, cErr **C.char) *C.char {
+		return C.openskills_execute_skill(h, id, o, cErr)
+	})
+}
+
+// RunSkillTarget runs one script or WASM module of a skill. A nil opts
+// picks the skill's target automatically.
+func (r *Runtime) RunSkillTarget(skillID string, opts *TargetOptions) (*ExecutionResult, error) {
+	if opts == nil {
+		opts = &TargetOptions{}
+	}
+	return r.execute(skillID, opts, func(h *C.OpenSkillsRuntime, id, o *C.char, cErr **C.char) *C.char {
+		return C.openskills_run_skill_target(h, id, o, cErr)
+	})
+}
+
+// RunSandboxedCommand runs a shell command in the OS sandbox. A nil opts
+// uses the defaults.
+func RunSandboxedCommand(command, workingDir string, opts *CommandOptions) (*CommandResult, error) {
+	if opts == nil {
+		opts = &CommandOptions{}
+	}
+	cOpts, err := jsonCString(opts)
+	if err != nil {
+		return nil, err
+	}
+	defer C.free(unsafe.Pointer(cOpts))
+	cCommand := C.CString(command)
+	defer C.free(unsafe.Pointer(cCommand))
+	cDir := C.CString(workingDir)
+	defer C.free(unsafe.Pointer(cDir))
+
+	var cErr *C.char
+	out := C.openskills_run_sandboxed_command(cCommand, cDir, cOpts, &cErr)
+	var result CommandResult
+	if err := decode(out, cErr, &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+func (r *Runtime) execute(
+	skillID string,
+	opts any,
+	fn func(h *C.OpenSkillsRuntime, id, opts *C.char, cErr **C.char) *C.char,
+) (*ExecutionResult, error) {
+	cOpts, err := jsonCString(opts)
+	if err != nil {
+		return nil, err
+	}
+	defer C.free(unsafe.Pointer(cOpts))
+	cID := C.CString(skillID)
+	defer C.free(unsafe.Pointer(cID))
+
+	var result ExecutionResult
+	err = r.call(&result, func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char {
+		return fn(h, cID, cOpts, cErr)
+	})
+	if err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+// call invokes fn with the live handle and decodes its JSON result into out.
+func (r *Runtime) call(out any, fn func(h *C.OpenSkillsRuntime, cErr **C.char) *C.char) error {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	if r.handle == nil {
+		return errClosed
+	}
+	var cErr *C.char
+	return decode(fn(r.handle, &cErr), cErr, out)
+}
+
+// decode takes ownership of a result/error pair returned by the C ABI.
+func decode(result, cErr *C.char, out any) error {
+	if result == nil {
+		return takeError(cErr)
+	}
+	defer C.openskills_string_free(result)
+	return json.Unmarshal([]byte(C.GoString(result)), out)
+}
+
+func takeError(cErr *C.char) error {
+	if cErr == nil {
+		return errors.New("openskills: unknown error")
+	}
+	defer C.openskills_string_free(cErr)
+	return errors.New(C.GoString(cErr))
+}
+
+func jsonCString(v any) (*C.char, error) {
+	data, err := json.Marshal(v)
+	if err != nil {
+		return nil, err
+	}
+	return C.CString(string(data)), nil
+}
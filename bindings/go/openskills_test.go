@@ -0,0 +1,97 @@
+package openskills
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func newTestRuntime(t *testing.T) *Runtime {
+	t.Helper()
+	dir := t.TempDir()
+	skill := filepath.Join(dir, "echo-skill")
+	if err := os.MkdirAll(filepath.Join(skill, "scripts"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	manifest := "---\nname: echo-skill\ndescription: Echoes its arguments.\nallowed-tools: Bash\n---\nRun scripts/echo.sh.\n"
+	if err := os.WriteFile(filepath.Join(skill, "SKILL.md"), []byte(manifest), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(skill, "scripts", "echo.sh"), []byte("echo \"$@\"\n"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+
+	standard := false
+	r, err := New(&Options{CustomDirectories: []string{dir}, UseStandardLocations: &standard})
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { r.Close() })
+	return r
+}
+
+func TestDiscoverAndActivate(t *testing.T) {
+	r := newTestRuntime(t)
+	skills, err := r.DiscoverSkills()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(skills) != 1 || skills[0].ID != "echo-skill" || skills[0].Kind != "script" {
+		t.Fatalf("unexpected skills: %+v", skills)
+	}
+
+	loaded, err := r.ActivateSkill("echo-skill")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if loaded.Instructions != "Run scripts/echo.sh." {
+		t.Fatalf("unexpected instructions: %q", loaded.Instructions)
+	}
+
+	targets, err := r.ListSkillTargets("echo-skill")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(targets) != 1 || targets[0].Path != "scripts/echo.sh" {
+		t.Fatalf("unexpected targets: %+v", targets)
+	}
+}
+
+func TestRunSkillTarget(t *testing.T) {
+	r := newTestRuntime(t)
+	if _, err := r.DiscoverSkills(); err != nil {
+		t.Fatal(err)
+	}
+	result, err := r.RunSkillTarget("echo-skill", &TargetOptions{Path: "scripts/echo.sh", Args: []string{"hello"}})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if strings.TrimSpace(result.Stdout) != "hello" {
+		t.Fatalf("unexpected stdout: %q", result.Stdout)
+	}
+	if result.Audit.ExitStatus != "success" {
+		t.Fatalf("unexpected exit status: %q", result.Audit.ExitStatus)
+	}
+}
+
+func TestErrors(t *testing.T) {
+	r := newTestRuntime(t)
+	if _, err := r.ActivateSkill("missing"); err == nil || !strings.Contains(err.Error(), "skill not found") {
+		t.Fatalf("expected skill not found, got %v", err)
+	}
+	r.Close()
+	if _, err := r.ListSkills(); err != errClosed {
+		t.Fatalf("expected errClosed, got %v", err)
+	}
+}
+
+func TestRunSandboxedCommand(t *testing.T) {
+	result, err := RunSandboxedCommand("echo hi", t.TempDir(), &CommandOptions{SandboxMode: "disabled"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if result.ExitCode != 0 || strings.TrimSpace(result.Stdout) != "hi" {
+		t.Fatalf("unexpected result: %+v", result)
+	}
+}
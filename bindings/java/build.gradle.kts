@@ -0,0 +1,40 @@
+plugins {
+    `java-library`
+}
+
+group = "com.finogeek"
+version = "0.1.0"
+
+java {
+    toolchain {
+        languageVersion = JavaLanguageVersion.of(17)
+    }
+}
+
+repositories {
+    mavenCentral()
+}
+
+dependencies {
+    testImplementation(platform("org.junit:junit-bom:5.10.2"))
+    testImplementation("org.junit.jupiter:junit-jupiter")
+    testRuntimeOnly("org.junit.platform:junit-platform-launcher")
+}
+
+// The JNI library is built by cargo into the workspace target directory.
+val nativeProfile = providers.gradleProperty("openskills.profile").getOrElse("release")
+val nativeDir = rootDir.resolve("../../target/$nativeProfile")
+
+val cargoBuild by tasks.registering(Exec::class) {
+    workingDir = rootDir
+    commandLine(
+        listOf("cargo", "build", "-p", "openskills_java") +
+            if (nativeProfile == "release") listOf("--release") else emptyList()
+    )
+}
+
+tasks.test {
+    dependsOn(cargoBuild)
+    useJUnitPlatform()
+    systemProperty("java.library.path", nativeDir.absolutePath)
+}
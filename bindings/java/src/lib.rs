@@ -0,0 +1,296 @@
+//! JNI glue for the JVM binding (`com.finogeek.openskills`).
+//!
+//! Each native method of `NativeBridge` forwards to the C ABI in
+//! `bindings/c`, so Java sees the same JSON documents as the Go binding;
+//! the Java classes turn them into records. Errors are thrown as
+//! `OpenSkillsException`. A Java `PermissionBridge` is installed as the C
+//! ABI permission callback and called back on whatever thread executes the
+//! skill, attaching it to the JVM if needed.
+
+use jni::objects::{GlobalRef, JClass, JObject, JString, JValue};
+use jni::sys::{jlong, jstring};
+use jni::{JNIEnv, JavaVM};
+use openskills::*;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::ptr;
+
+const EXCEPTION_CLASS: &str = "com/finogeek/openskills/OpenSkillsException";
+
+/// Copy a Java string into a C string; `null` becomes `None`.
+fn c_string(env: &mut JNIEnv, s: &JString) -> Result<Option<CString>, String> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    let s: String = env.get_string(s).map_err(|e| e.to_string())?.into();
+    CString::new(s)
+        .map(Some)
+        .map_err(|_| "strings must not contain NUL characters".to_string())
+}
+
+fn opt_ptr(s: &Option<CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+fn throw(env: &mut JNIEnv, message: &str) {
+    // If throwing fails an exception is already pending.
+    let _ = env.throw_new(EXCEPTION_CLASS, message);
+}
+
+/// Turn a C ABI result/error pair into a Java string, throwing on error.
+///
+/// # Safety
+///
+/// `result` and `error` must come from one C ABI call.
+unsafe fn into_java(env: &mut JNIEnv, result: *mut c_char, error: *mut c_char) -> jstring {
+    if result.is_null() {
+        let message = if error.is_null() {
+            "unknown error".to_string()
+        } else {
+            let message = CStr::from_ptr(error).to_string_lossy().into_owned();
+            openskills_string_free(error);
+            message
+        };
+        throw(env, &message);
+        return ptr::null_mut();
+    }
+    let json = CStr::from_ptr(result).to_string_lossy().into_owned();
+    openskills_string_free(result);
+    match env.new_string(json) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            throw(env, &e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Convert the string arguments, then make a C ABI call that returns JSON.
+fn call(
+    env: &mut JNIEnv,
+    args: &[&JString],
+    f: impl FnOnce(&[Option<CString>], *mut *mut c_char) -> *mut c_char,
+) -> jstring {
+    let args: Result<Vec<Option<CString>>, String> = args.iter().map(|s| c_string(env, s)).collect();
+    match args {
+        Ok(args) => {
+            let mut error = ptr::null_mut();
+            let result = f(&args, &mut error);
+            unsafe { into_java(env, result, error) }
+        }
+        Err(message) => {
+            throw(env, &message);
+            ptr::null_mut()
+        }
+    }
+}
+
+fn handle(runtime: jlong) -> *mut OpenSkillsRuntime {
+    runtime as *mut OpenSkillsRuntime
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_version<'local>(
+    env: JNIEnv<'local>,
+    _class: JClass<'local>,
+) -> jstring {
+    let version = unsafe { CStr::from_ptr(openskills_version()) };
+    env.new_string(version.to_string_lossy())
+        .map_or(ptr::null_mut(), JString::into_raw)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_features<'local>(
+    env: JNIEnv<'local>,
+    _class: JClass<'local>,
+) -> jstring {
+    let features = unsafe { CStr::from_ptr(openskills_features()) };
+    env.new_string(features.to_string_lossy())
+        .map_or(ptr::null_mut(), JString::into_raw)
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_runtimeNew<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    options: JString<'local>,
+) -> jlong {
+    let options = match c_string(&mut env, &options) {
+        Ok(options) => options,
+        Err(message) => {
+            throw(&mut env, &message);
+            return 0;
+        }
+    };
+    let mut error = ptr::null_mut();
+    let runtime = unsafe { openskills_runtime_new(opt_ptr(&options), &mut error) };
+    if runtime.is_null() {
+        unsafe { into_java(&mut env, ptr::null_mut(), error) };
+        return 0;
+    }
+    runtime as jlong
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_runtimeFree<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+) {
+    unsafe { openskills_runtime_free(handle(runtime)) }
+}
+
+/// A Java `PermissionBridge` installed as the C ABI permission callback.
+struct JavaPermissionCallback {
+    vm: JavaVM,
+    bridge: GlobalRef,
+}
+
+impl JavaPermissionCallback {
+    /// Call `PermissionBridge.onRequest`; `None` if the JVM call failed.
+    fn request(&self, request_json: &str) -> Option<c_int> {
+        let mut env = self.vm.attach_current_thread().ok()?;
+        let request = env.new_string(request_json).ok()?;
+        let response = env.call_method(
+            &self.bridge,
+            "onRequest",
+            "(Ljava/lang/String;)I",
+            &[JValue::Object(&request)],
+        );
+        if env.exception_check().unwrap_or(true) {
+            let _ = env.exception_clear();
+            return None;
+        }
+        response.ok()?.i().ok()
+    }
+}
+
+unsafe extern "C" fn java_permission_callback(user_data: *mut c_void, request_json: *const c_char) -> c_int {
+    let callback = &*(user_data as *const JavaPermissionCallback);
+    let request_json = CStr::from_ptr(request_json).to_string_lossy();
+    // Deny when the JVM call fails.
+    callback.request(&request_json).unwrap_or(0)
+}
+
+unsafe extern "C" fn free_java_permission_callback(user_data: *mut c_void) {
+    drop(Box::from_raw(user_data as *mut JavaPermissionCallback));
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_setPermissionCallback<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    bridge: JObject<'local>,
+) {
+    if bridge.is_null() {
+        unsafe { openskills_runtime_set_permission_callback(handle(runtime), None, ptr::null_mut(), None) };
+        return;
+    }
+    let callback = env
+        .get_java_vm()
+        .and_then(|vm| Ok(JavaPermissionCallback { vm, bridge: env.new_global_ref(&bridge)? }));
+    match callback {
+        Ok(callback) => unsafe {
+            openskills_runtime_set_permission_callback(
+                handle(runtime),
+                Some(java_permission_callback),
+                Box::into_raw(Box::new(callback)) as *mut c_void,
+                Some(free_java_permission_callback),
+            )
+        },
+        Err(e) => throw(&mut env, &e.to_string()),
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_discoverSkills<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+) -> jstring {
+    call(&mut env, &[], |_, error| unsafe { openskills_discover_skills(handle(runtime), error) })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_loadFromDirectory<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    dir: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&dir], |args, error| unsafe {
+        openskills_load_from_directory(handle(runtime), opt_ptr(&args[0]), error)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_listSkills<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+) -> jstring {
+    call(&mut env, &[], |_, error| unsafe { openskills_list_skills(handle(runtime), error) })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_activateSkill<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    skill_id: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&skill_id], |args, error| unsafe {
+        openskills_activate_skill(handle(runtime), opt_ptr(&args[0]), error)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_listSkillTargets<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    skill_id: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&skill_id], |args, error| unsafe {
+        openskills_list_skill_targets(handle(runtime), opt_ptr(&args[0]), error)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_executeSkill<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    skill_id: JString<'local>,
+    options: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&skill_id, &options], |args, error| unsafe {
+        openskills_execute_skill(handle(runtime), opt_ptr(&args[0]), opt_ptr(&args[1]), error)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_runSkillTarget<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    runtime: jlong,
+    skill_id: JString<'local>,
+    options: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&skill_id, &options], |args, error| unsafe {
+        openskills_run_skill_target(handle(runtime), opt_ptr(&args[0]), opt_ptr(&args[1]), error)
+    })
+}
+
+#[no_mangle]
+pub extern "system" fn Java_com_finogeek_openskills_NativeBridge_runSandboxedCommand<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    command: JString<'local>,
+    working_dir: JString<'local>,
+    options: JString<'local>,
+) -> jstring {
+    call(&mut env, &[&command, &working_dir, &options], |args, error| unsafe {
+        openskills_run_sandboxed_command(opt_ptr(&args[0]), opt_ptr(&args[1]), opt_ptr(&args[2]), error)
+    })
+}
@@ -0,0 +1,91 @@
+package com.finogeek.openskills;
+
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Audit record of one execution.
+ *
+ * @param exitStatus {@code success}, {@code timeout}, {@code permission_denied},
+ *     {@code instruction_only}, {@code dry_run}, {@code failed:<reason>},
+ *     {@code sandbox_violation:<reason>} or {@code schema_violation:<reason>}
+ * @param sandboxMode effective OS sandbox mode ({@code enforce} or {@code disabled})
+ * @param fuelConsumed fuel a WASM execution consumed, or {@code null}
+ * @param capabilityDowngrades requested tools the sandbox did not (fully) grant
+ * @param attempt attempt number, 1 unless the execution was retried
+ * @param traceparent caller's W3C trace context, or {@code null}
+ */
+public record AuditRecord(
+        String skillId,
+        String version,
+        String inputHash,
+        String outputHash,
+        long startTimeMs,
+        long durationMs,
+        List<String> permissionsUsed,
+        String exitStatus,
+        String stdout,
+        String stderr,
+        String sandboxMode,
+        List<SandboxViolation> sandboxViolations,
+        Long fuelConsumed,
+        List<NetworkRequest> networkRequests,
+        List<HostCall> hostCalls,
+        List<CapabilityDowngrade> capabilityDowngrades,
+        int attempt,
+        String traceparent) {
+
+    /** An operation the OS sandbox blocked, recovered from stderr (best effort). */
+    public record SandboxViolation(String operation, String path) {}
+
+    /** An outbound HTTP request made by a WASM skill. */
+    public record NetworkRequest(String method, String url, String host, int port, boolean allowed) {}
+
+    /** A host function call made by a WASM skill. */
+    public record HostCall(String function, String target, boolean allowed) {}
+
+    /**
+     * A requested tool the sandbox did not (fully) grant.
+     *
+     * @param reason {@code policy_denied}, {@code permission_denied}, {@code unmapped} or
+     *     {@code platform}
+     */
+    public record CapabilityDowngrade(String tool, String reason, String detail) {}
+
+    static AuditRecord fromJson(Map<String, Object> json) {
+        return new AuditRecord(
+                Json.string(json, "skill_id"),
+                Json.string(json, "version"),
+                Json.string(json, "input_hash"),
+                Json.string(json, "output_hash"),
+                Json.number(json, "start_time_ms"),
+                Json.number(json, "duration_ms"),
+                Json.strings(json, "permissions_used"),
+                Json.string(json, "exit_status"),
+                Json.string(json, "stdout"),
+                Json.string(json, "stderr"),
+                Json.string(json, "sandbox_mode"),
+                Json.objects(json, "sandbox_violations").stream()
+                        .map(v -> new SandboxViolation(Json.string(v, "operation"), Json.string(v, "path")))
+                        .toList(),
+                Json.optionalNumber(json, "fuel_consumed"),
+                Json.objects(json, "network_requests").stream()
+                        .map(r -> new NetworkRequest(
+                                Json.string(r, "method"),
+                                Json.string(r, "url"),
+                                Json.string(r, "host"),
+                                (int) Json.number(r, "port"),
+                                Json.bool(r, "allowed")))
+                        .toList(),
+                Json.objects(json, "host_calls").stream()
+                        .map(c -> new HostCall(
+                                Json.string(c, "function"), Json.string(c, "target"), Json.bool(c, "allowed")))
+                        .toList(),
+                Json.objects(json, "capability_downgrades").stream()
+                        .map(d -> new CapabilityDowngrade(
+                                Json.string(d, "tool"), Json.string(d, "reason"), Json.string(d, "detail")))
+                        .toList(),
+                (int) Json.number(json, "attempt"),
+                Json.string(json, "traceparent"));
+    }
+}
@@ -0,0 +1,115 @@
+package com.finogeek.openskills;
+
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/** Options for {@link OpenSkillRuntime#runSandboxedCommand}. */
+public final class CommandOptions {
+    private final Map<String, Object> json;
+
+    private CommandOptions(Map<String, Object> json) {
+        this.json = json;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    String toJson() {
+        return Json.write(json);
+    }
+
+    public static final class Builder {
+        private final Map<String, Object> json = new LinkedHashMap<>();
+        private final List<String> allowedHosts = new ArrayList<>();
+        private final List<Integer> allowedPorts = new ArrayList<>();
+        private final List<String> allowedPrograms = new ArrayList<>();
+        private final List<String> readPaths = new ArrayList<>();
+        private final List<String> writePaths = new ArrayList<>();
+        private final Map<String, String> env = new LinkedHashMap<>();
+
+        private Builder() {}
+
+        public Builder allowNetwork(boolean allow) {
+            json.put("allow_network", allow);
+            return this;
+        }
+
+        /** Host reachable when network is allowed (subdomains match; none = any). */
+        public Builder allowedHost(String host) {
+            allowedHosts.add(host);
+            return this;
+        }
+
+        /** TCP port reachable when network is allowed (none = any). */
+        public Builder allowedPort(int port) {
+            allowedPorts.add(port);
+            return this;
+        }
+
+        /** Allow spawning subprocesses. */
+        public Builder allowProcess(boolean allow) {
+            json.put("allow_process", allow);
+            return this;
+        }
+
+        /** Program the command may execute: a name in PATH or a path (none = any). */
+        public Builder allowedProgram(String program) {
+            allowedPrograms.add(program);
+            return this;
+        }
+
+        public Builder readPath(String path) {
+            readPaths.add(path);
+            return this;
+        }
+
+        public Builder writePath(String path) {
+            writePaths.add(path);
+            return this;
+        }
+
+        /** Environment variable to pass through. */
+        public Builder env(String key, String value) {
+            env.put(key, value);
+            return this;
+        }
+
+        /** Timeout in milliseconds (default: 30000). */
+        public Builder timeoutMs(long timeoutMs) {
+            json.put("timeout_ms", timeoutMs);
+            return this;
+        }
+
+        /** OS sandbox mode: {@code enforce} (default) or {@code disabled}. */
+        public Builder sandboxMode(String sandboxMode) {
+            json.put("sandbox_mode", sandboxMode);
+            return this;
+        }
+
+        /** Sandbox backend: {@code auto} (default), {@code native} or {@code container}. */
+        public Builder backend(String backend) {
+            json.put("backend", backend);
+            return this;
+        }
+
+        /** Image for the container backend (default: {@code python:3-slim}). */
+        public Builder containerImage(String image) {
+            json.put("container_image", image);
+            return this;
+        }
+
+        public CommandOptions build() {
+            Map<String, Object> out = new LinkedHashMap<>(json);
+            out.put("allowed_hosts", List.copyOf(allowedHosts));
+            out.put("allowed_ports", List.copyOf(allowedPorts));
+            out.put("allowed_programs", List.copyOf(allowedPrograms));
+            out.put("read_paths", List.copyOf(readPaths));
+            out.put("write_paths", List.copyOf(writePaths));
+            out.put("env", Map.copyOf(env));
+            return new CommandOptions(out);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+package com.finogeek.openskills;
+
+import java.util.Map;
+
+/** Result of {@link OpenSkillRuntime#runSandboxedCommand}. */
+public record CommandResult(int exitCode, String stdout, String stderr, boolean timedOut) {
+
+    static CommandResult fromJson(Map<String, Object> json) {
+        return new CommandResult(
+                (int) Json.number(json, "exit_code"),
+                Json.string(json, "stdout"),
+                Json.string(json, "stderr"),
+                Json.bool(json, "timed_out"));
+    }
+}
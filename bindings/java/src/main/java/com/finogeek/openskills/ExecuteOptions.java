@@ -0,0 +1,118 @@
+package com.finogeek.openskills;
+
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/** Options for {@link OpenSkillRuntime#executeSkill(String, ExecuteOptions)}. */
+public final class ExecuteOptions {
+    private final Map<String, Object> json;
+
+    private ExecuteOptions(Map<String, Object> json) {
+        this.json = json;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    String toJson() {
+        return Json.write(json);
+    }
+
+    public static final class Builder {
+        private final Map<String, Object> json = new LinkedHashMap<>();
+        private final List<String> readPaths = new ArrayList<>();
+        private final List<String> writePaths = new ArrayList<>();
+        private final Map<String, String> env = new LinkedHashMap<>();
+
+        private Builder() {}
+
+        public Builder timeoutMs(long timeoutMs) {
+            json.put("timeout_ms", timeoutMs);
+            return this;
+        }
+
+        public Builder memoryMb(long memoryMb) {
+            json.put("memory_mb", memoryMb);
+            return this;
+        }
+
+        /** CPU cap for native scripts (Linux), as a fraction of one CPU. */
+        public Builder cpuQuota(double cpuQuota) {
+            json.put("cpu_quota", cpuQuota);
+            return this;
+        }
+
+        /** Fuel budget for WASM skills (roughly one unit per instruction). */
+        public Builder maxFuel(long maxFuel) {
+            json.put("max_fuel", maxFuel);
+            return this;
+        }
+
+        /** Input passed to the skill: a {@code Map}, {@code List}, string, number or boolean. */
+        public Builder input(Object input) {
+            json.put("input", input);
+            return this;
+        }
+
+        /** Extra path the skill may read, beyond its declared permissions. */
+        public Builder readPath(String path) {
+            readPaths.add(path);
+            return this;
+        }
+
+        /** Extra path the skill may write, beyond its declared permissions. */
+        public Builder writePath(String path) {
+            writePaths.add(path);
+            return this;
+        }
+
+        public Builder allowNetwork(boolean allow) {
+            json.put("allow_network", allow);
+            return this;
+        }
+
+        /** Environment variable for this run. */
+        public Builder env(String key, String value) {
+            env.put(key, value);
+            return this;
+        }
+
+        /**
+         * Resolve the execution without running it: the output is then the
+         * execution plan and the audit status {@code dry_run}.
+         */
+        public Builder dryRun(boolean dryRun) {
+            json.put("dry_run", dryRun);
+            return this;
+        }
+
+        /**
+         * Run timeouts and failures again, up to {@code maxAttempts} attempts in
+         * total, {@code backoffMs} apart and doubling. Each attempt is audited.
+         */
+        public Builder retry(int maxAttempts, long backoffMs) {
+            json.put("retry", Map.of("max_attempts", maxAttempts, "backoff_ms", backoffMs));
+            return this;
+        }
+
+        /**
+         * W3C trace context of the caller's span, kept in the audit and passed to
+         * the skill as {@code TRACEPARENT}.
+         */
+        public Builder traceparent(String traceparent) {
+            json.put("traceparent", traceparent);
+            return this;
+        }
+
+        public ExecuteOptions build() {
+            Map<String, Object> out = new LinkedHashMap<>(json);
+            out.put("read_paths", List.copyOf(readPaths));
+            out.put("write_paths", List.copyOf(writePaths));
+            out.put("env", Map.copyOf(env));
+            return new ExecuteOptions(out);
+        }
+    }
+}
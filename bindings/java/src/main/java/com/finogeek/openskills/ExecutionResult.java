@@ -0,0 +1,38 @@
+package com.finogeek.openskills;
+
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Result of running a skill.
+ *
+ * @param output parsed JSON output: a {@code Map}, {@code List}, {@code String},
+ *     {@code Long}, {@code Double}, {@code Boolean} or {@code null}
+ * @param capabilitiesNeeded for a failed execution, the tools to ask the user for
+ *     before retrying, from what the sandbox denied
+ */
+public record ExecutionResult(
+        Object output,
+        String stdout,
+        String stderr,
+        AuditRecord audit,
+        List<CapabilityNeeded> capabilitiesNeeded) {
+
+    /**
+     * A capability a failed execution was denied.
+     *
+     * @param resource path, host or URL the execution tried to reach, or {@code null}
+     */
+    public record CapabilityNeeded(String tool, String resource) {}
+
+    static ExecutionResult fromJson(Map<String, Object> json) {
+        return new ExecutionResult(
+                json.get("output"),
+                Json.string(json, "stdout"),
+                Json.string(json, "stderr"),
+                AuditRecord.fromJson(Json.object(json, "audit")),
+                Json.objects(json, "capabilities_needed").stream()
+                        .map(c -> new CapabilityNeeded(Json.string(c, "tool"), Json.string(c, "resource")))
+                        .toList());
+    }
+}
@@ -0,0 +1,41 @@
+package com.finogeek.openskills;
+
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Host-level tool permission policy (the {@code permissions} section of
+ * {@code .openskills/config.toml}).
+ *
+ * @param trustSkillAllowedTools approve the tools a skill lists in {@code allowed-tools}
+ * @param fallback what to do with tools not covered by {@code deny}, {@code allow}
+ *     or the skill's pre-approvals
+ * @param deny tools always denied
+ * @param allow tools always allowed
+ */
+public record HostPolicy(boolean trustSkillAllowedTools, Fallback fallback, List<String> deny, List<String> allow) {
+
+    /** Fallback for tools the policy does not cover. */
+    public enum Fallback {
+        ALLOW,
+        DENY,
+        /** Ask the {@link PermissionCallback}. */
+        PROMPT
+    }
+
+    public HostPolicy {
+        fallback = fallback == null ? Fallback.DENY : fallback;
+        deny = deny == null ? List.of() : List.copyOf(deny);
+        allow = allow == null ? List.of() : List.copyOf(allow);
+    }
+
+    Map<String, Object> toJson() {
+        Map<String, Object> json = new LinkedHashMap<>();
+        json.put("trust_skill_allowed_tools", trustSkillAllowedTools);
+        json.put("fallback", fallback.name().toLowerCase(java.util.Locale.ROOT));
+        json.put("deny", deny);
+        json.put("allow", allow);
+        return json;
+    }
+}
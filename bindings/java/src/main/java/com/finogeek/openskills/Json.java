@@ -0,0 +1,313 @@
+package com.finogeek.openskills;
+
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Minimal JSON reader/writer for the documents exchanged with the native
+ * library, so the binding has no third-party dependencies.
+ *
+ * <p>Objects parse to {@code Map<String, Object>}, arrays to
+ * {@code List<Object>}, integers to {@code Long}, other numbers to
+ * {@code Double}.
+ */
+final class Json {
+    private final String text;
+    private int pos;
+
+    private Json(String text) {
+        this.text = text;
+    }
+
+    static Object parse(String text) {
+        Json json = new Json(text);
+        json.skipWhitespace();
+        Object value = json.readValue();
+        json.skipWhitespace();
+        if (json.pos != text.length()) {
+            throw json.error("trailing characters");
+        }
+        return value;
+    }
+
+    @SuppressWarnings("unchecked")
+    static Map<String, Object> parseObject(String text) {
+        return (Map<String, Object>) parse(text);
+    }
+
+    @SuppressWarnings("unchecked")
+    static List<Map<String, Object>> parseArray(String text) {
+        return (List<Map<String, Object>>) parse(text);
+    }
+
+    static String write(Object value) {
+        StringBuilder out = new StringBuilder();
+        write(out, value);
+        return out.toString();
+    }
+
+    // Field accessors used by the records' fromJson factories.
+
+    static String string(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        return value == null ? null : value.toString();
+    }
+
+    static boolean bool(Map<String, Object> object, String key) {
+        return Boolean.TRUE.equals(object.get(key));
+    }
+
+    static long number(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        return value instanceof Number n ? n.longValue() : 0L;
+    }
+
+    static Long optionalNumber(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        return value instanceof Number n ? n.longValue() : null;
+    }
+
+    static List<String> strings(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        if (!(value instanceof List<?> list)) {
+            return List.of();
+        }
+        List<String> strings = new ArrayList<>(list.size());
+        for (Object item : list) {
+            strings.add(String.valueOf(item));
+        }
+        return Collections.unmodifiableList(strings);
+    }
+
+    @SuppressWarnings("unchecked")
+    static Map<String, Object> object(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        return value instanceof Map<?, ?> ? (Map<String, Object>) value : null;
+    }
+
+    @SuppressWarnings("unchecked")
+    static List<Map<String, Object>> objects(Map<String, Object> object, String key) {
+        Object value = object.get(key);
+        return value instanceof List<?> ? (List<Map<String, Object>>) value : List.of();
+    }
+
+    private Object readValue() {
+        if (pos >= text.length()) {
+            throw error("unexpected end of input");
+        }
+        char c = text.charAt(pos);
+        switch (c) {
+            case '{':
+                return readObject();
+            case '[':
+                return readArray();
+            case '"':
+                return readString();
+            case 't':
+                expect("true");
+                return Boolean.TRUE;
+            case 'f':
+                expect("false");
+                return Boolean.FALSE;
+            case 'n':
+                expect("null");
+                return null;
+            default:
+                return readNumber();
+        }
+    }
+
+    private Map<String, Object> readObject() {
+        Map<String, Object> object = new LinkedHashMap<>();
+        pos++;
+        skipWhitespace();
+        if (peek() == '}') {
+            pos++;
+            return object;
+        }
+        while (true) {
+            skipWhitespace();
+            if (peek() != '"') {
+                throw error("expected object key");
+            }
+            String key = readString();
+            skipWhitespace();
+            if (peek() != ':') {
+                throw error("expected ':'");
+            }
+            pos++;
+            skipWhitespace();
+            object.put(key, readValue());
+            skipWhitespace();
+            char c = next();
+            if (c == '}') {
+                return object;
+            }
+            if (c != ',') {
+                throw error("expected ',' or '}'");
+            }
+        }
+    }
+
+    private List<Object> readArray() {
+        List<Object> array = new ArrayList<>();
+        pos++;
+        skipWhitespace();
+        if (peek() == ']') {
+            pos++;
+            return array;
+        }
+        while (true) {
+            skipWhitespace();
+            array.add(readValue());
+            skipWhitespace();
+            char c = next();
+            if (c == ']') {
+                return array;
+            }
+            if (c != ',') {
+                throw error("expected ',' or ']'");
+            }
+        }
+    }
+
+    private String readString() {
+        StringBuilder out = new StringBuilder();
+        pos++;
+        while (true) {
+            char c = next();
+            if (c == '"') {
+                return out.toString();
+            }
+            if (c != '\\') {
+                out.append(c);
+                continue;
+            }
+            char escape = next();
+            switch (escape) {
+                case 'b' -> out.append('\b');
+                case 'f' -> out.append('\f');
+                case 'n' -> out.append('\n');
+                case 'r' -> out.append('\r');
+                case 't' -> out.append('\t');
+                case 'u' -> {
+                    if (pos + 4 > text.length()) {
+                        throw error("truncated unicode escape");
+                    }
+                    out.append((char) Integer.parseInt(text.substring(pos, pos + 4), 16));
+                    pos += 4;
+                }
+                default -> out.append(escape);
+            }
+        }
+    }
+
+    private Number readNumber() {
+        int start = pos;
+        while (pos < text.length() && "+-0123456789.eE".indexOf(text.charAt(pos)) >= 0) {
+            pos++;
+        }
+        String number = text.substring(start, pos);
+        if (number.isEmpty()) {
+            throw error("unexpected character");
+        }
+        try {
+            if (number.indexOf('.') < 0 && number.indexOf('e') < 0 && number.indexOf('E') < 0) {
+                return Long.parseLong(number);
+            }
+            return Double.parseDouble(number);
+        } catch (NumberFormatException e) {
+            // Integers beyond the long range (e.g. u64 counters).
+            return Double.parseDouble(number);
+        }
+    }
+
+    private void expect(String literal) {
+        if (!text.startsWith(literal, pos)) {
+            throw error("unexpected character");
+        }
+        pos += literal.length();
+    }
+
+    private char peek() {
+        return pos < text.length() ? text.charAt(pos) : '\0';
+    }
+
+    private char next() {
+        if (pos >= text.length()) {
+            throw error("unexpected end of input");
+        }
+        return text.charAt(pos++);
+    }
+
+    private void skipWhitespace() {
+        while (pos < text.length() && Character.isWhitespace(text.charAt(pos))) {
+            pos++;
+        }
+    }
+
+    private OpenSkillsException error(String message) {
+        return new OpenSkillsException("invalid JSON from native library: " + message + " at " + pos);
+    }
+
+    private static void write(StringBuilder out, Object value) {
+        if (value == null) {
+            out.append("null");
+        } else if (value instanceof String s) {
+            writeString(out, s);
+        } else if (value instanceof Boolean || value instanceof Number) {
+            out.append(value);
+        } else if (value instanceof Map<?, ?> map) {
+            out.append('{');
+            boolean first = true;
+            for (Map.Entry<?, ?> entry : map.entrySet()) {
+                if (!first) {
+                    out.append(',');
+                }
+                first = false;
+                writeString(out, String.valueOf(entry.getKey()));
+                out.append(':');
+                write(out, entry.getValue());
+            }
+            out.append('}');
+        } else if (value instanceof Iterable<?> items) {
+            out.append('[');
+            boolean first = true;
+            for (Object item : items) {
+                if (!first) {
+                    out.append(',');
+                }
+                first = false;
+                write(out, item);
+            }
+            out.append(']');
+        } else {
+            writeString(out, value.toString());
+        }
+    }
+
+    private static void writeString(StringBuilder out, String s) {
+        out.append('"');
+        for (int i = 0; i < s.length(); i++) {
+            char c = s.charAt(i);
+            switch (c) {
+                case '"' -> out.append("\\\"");
+                case '\\' -> out.append("\\\\");
+                case '\n' -> out.append("\\n");
+                case '\r' -> out.append("\\r");
+                case '\t' -> out.append("\\t");
+                default -> {
+                    if (c < 0x20) {
+                        out.append(String.format("\\u%04x", (int) c));
+                    } else {
+                        out.append(c);
+                    }
+                }
+            }
+        }
+        out.append('"');
+    }
+}
@@ -0,0 +1,42 @@
+package com.finogeek.openskills;
+
+import java.util.List;
+import java.util.Map;
+
+/**
+ * A fully loaded skill, returned by {@link OpenSkillRuntime#activateSkill}.
+ *
+ * @param missingDependencies requirements not met at activation, or {@code null}
+ */
+public record LoadedSkill(
+        String id,
+        String version,
+        String name,
+        String description,
+        List<String> allowedTools,
+        String model,
+        String context,
+        String agent,
+        boolean userInvocable,
+        String location,
+        String instructions,
+        Requires requires,
+        Requires missingDependencies) {
+
+    static LoadedSkill fromJson(Map<String, Object> json) {
+        return new LoadedSkill(
+                Json.string(json, "id"),
+                Json.string(json, "version"),
+                Json.string(json, "name"),
+                Json.string(json, "description"),
+                Json.strings(json, "allowed_tools"),
+                Json.string(json, "model"),
+                Json.string(json, "context"),
+                Json.string(json, "agent"),
+                Json.bool(json, "user_invocable"),
+                Json.string(json, "location"),
+                Json.string(json, "instructions"),
+                Requires.fromJson(Json.object(json, "requires")),
+                Requires.fromJson(Json.object(json, "missing_dependencies")));
+    }
+}
@@ -0,0 +1,46 @@
+package com.finogeek.openskills;
+
+/**
+ * Native methods implemented by {@code libopenskills_jni}. They mirror the C
+ * ABI ({@code bindings/c/include/openskills.h}): options go in and results
+ * come back as JSON, and failures throw {@link OpenSkillsException}.
+ */
+final class NativeBridge {
+    static {
+        String path = System.getProperty("openskills.library.path");
+        if (path != null && !path.isEmpty()) {
+            System.load(path);
+        } else {
+            System.loadLibrary("openskills_jni");
+        }
+    }
+
+    private NativeBridge() {}
+
+    static native String version();
+
+    static native String features();
+
+    static native long runtimeNew(String optionsJson);
+
+    static native void runtimeFree(long runtime);
+
+    /** Install {@code bridge} as the permission callback; {@code null} removes it. */
+    static native void setPermissionCallback(long runtime, PermissionBridge bridge);
+
+    static native String discoverSkills(long runtime);
+
+    static native String loadFromDirectory(long runtime, String dir);
+
+    static native String listSkills(long runtime);
+
+    static native String activateSkill(long runtime, String skillId);
+
+    static native String listSkillTargets(long runtime, String skillId);
+
+    static native String executeSkill(long runtime, String skillId, String optionsJson);
+
+    static native String runSkillTarget(long runtime, String skillId, String optionsJson);
+
+    static native String runSandboxedCommand(String command, String workingDir, String optionsJson);
+}
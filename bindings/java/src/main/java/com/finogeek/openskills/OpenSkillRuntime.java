@@ -0,0 +1,188 @@
+package com.finogeek.openskills;
+
+import java.util.List;
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.Executor;
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.Executors;
+import java.util.concurrent.atomic.AtomicInteger;
+import java.util.concurrent.locks.ReadWriteLock;
+import java.util.concurrent.locks.ReentrantReadWriteLock;
+import java.util.function.Supplier;
+
+/**
+ * OpenSkills runtime for JVM hosts.
+ *
+ * <p>Instances are thread-safe; calls on one runtime are serialized by the
+ * native library. Close the runtime (or use try-with-resources) to release
+ * it. The {@code *Async} methods run on the executor from
+ * {@link RuntimeOptions.Builder#executor}, or on a shared daemon pool.
+ *
+ * <pre>{@code
+ * try (OpenSkillRuntime runtime = OpenSkillRuntime.create(RuntimeOptions.builder()
+ *         .customDirectory("./skills")
+ *         .useStandardLocations(false)
+ *         .build())) {
+ *     runtime.discoverSkills();
+ *     ExecutionResult result = runtime.executeSkill("my-skill",
+ *             ExecuteOptions.builder().input(Map.of("query", "hello")).build());
+ * }
+ * }</pre>
+ */
+public final class OpenSkillRuntime implements AutoCloseable {
+    private static final ExecutorService DEFAULT_EXECUTOR = Executors.newCachedThreadPool(new DaemonThreadFactory());
+
+    private final ReadWriteLock lock = new ReentrantReadWriteLock();
+    private final Executor executor;
+    private long handle;
+
+    private OpenSkillRuntime(long handle, Executor executor) {
+        this.handle = handle;
+        this.executor = executor == null ? DEFAULT_EXECUTOR : executor;
+    }
+
+    /** Create a runtime scanning the standard skill locations. */
+    public static OpenSkillRuntime create() {
+        return create(RuntimeOptions.builder().build());
+    }
+
+    public static OpenSkillRuntime create(RuntimeOptions options) {
+        OpenSkillRuntime runtime = new OpenSkillRuntime(NativeBridge.runtimeNew(options.toJson()), options.executor());
+        if (options.permissionCallback() != null) {
+            runtime.setPermissionCallback(options.permissionCallback());
+        }
+        return runtime;
+    }
+
+    /** Version of the native library. */
+    public static String version() {
+        return NativeBridge.version();
+    }
+
+    /** Optional runtime features the native library was built with, e.g. {@code wasm}. */
+    public static List<String> features() {
+        return ((List<?>) Json.parse(NativeBridge.features())).stream().map(String::valueOf).toList();
+    }
+
+    /**
+     * Install the callback answering permission prompts, replacing any
+     * previous one. {@code null} removes it, which approves prompts.
+     */
+    public void setPermissionCallback(PermissionCallback callback) {
+        withHandle(handle -> {
+            NativeBridge.setPermissionCallback(handle, callback == null ? null : new PermissionBridge(callback));
+            return null;
+        });
+    }
+
+    /** Discover skills from the configured locations. */
+    public List<SkillDescriptor> discoverSkills() {
+        return descriptors(withHandle(NativeBridge::discoverSkills));
+    }
+
+    /** Load skills from {@code dir}; returns the skills found there. */
+    public List<SkillDescriptor> loadFromDirectory(String dir) {
+        return descriptors(withHandle(handle -> NativeBridge.loadFromDirectory(handle, dir)));
+    }
+
+    /** List loaded skills (the default version of each). */
+    public List<SkillDescriptor> listSkills() {
+        return descriptors(withHandle(NativeBridge::listSkills));
+    }
+
+    /** Load a skill's full instructions. {@code skillId} may carry a version constraint ({@code id@^1}). */
+    public LoadedSkill activateSkill(String skillId) {
+        return LoadedSkill.fromJson(Json.parseObject(withHandle(handle -> NativeBridge.activateSkill(handle, skillId))));
+    }
+
+    /** List a skill's WASM modules and scripts. */
+    public List<SkillTarget> listSkillTargets(String skillId) {
+        return Json.parseArray(withHandle(handle -> NativeBridge.listSkillTargets(handle, skillId))).stream()
+                .map(SkillTarget::fromJson)
+                .toList();
+    }
+
+    /** Execute a skill's entry point. */
+    public ExecutionResult executeSkill(String skillId, ExecuteOptions options) {
+        String optionsJson = options == null ? null : options.toJson();
+        return ExecutionResult.fromJson(
+                Json.parseObject(withHandle(handle -> NativeBridge.executeSkill(handle, skillId, optionsJson))));
+    }
+
+    public ExecutionResult executeSkill(String skillId) {
+        return executeSkill(skillId, null);
+    }
+
+    /** Run one script or WASM module of a skill. */
+    public ExecutionResult runSkillTarget(String skillId, TargetOptions options) {
+        String optionsJson = options == null ? null : options.toJson();
+        return ExecutionResult.fromJson(
+                Json.parseObject(withHandle(handle -> NativeBridge.runSkillTarget(handle, skillId, optionsJson))));
+    }
+
+    /** {@link #executeSkill(String, ExecuteOptions)} on the runtime's executor. */
+    public CompletableFuture<ExecutionResult> executeSkillAsync(String skillId, ExecuteOptions options) {
+        return async(() -> executeSkill(skillId, options));
+    }
+
+    /** {@link #runSkillTarget(String, TargetOptions)} on the runtime's executor. */
+    public CompletableFuture<ExecutionResult> runSkillTargetAsync(String skillId, TargetOptions options) {
+        return async(() -> runSkillTarget(skillId, options));
+    }
+
+    /** Run a shell command in the OS sandbox. */
+    public static CommandResult runSandboxedCommand(String command, String workingDir, CommandOptions options) {
+        String optionsJson = options == null ? null : options.toJson();
+        return CommandResult.fromJson(
+                Json.parseObject(NativeBridge.runSandboxedCommand(command, workingDir, optionsJson)));
+    }
+
+    /** Release the native runtime. Further calls throw {@link OpenSkillsException}. */
+    @Override
+    public void close() {
+        lock.writeLock().lock();
+        try {
+            if (handle != 0) {
+                NativeBridge.runtimeFree(handle);
+                handle = 0;
+            }
+        } finally {
+            lock.writeLock().unlock();
+        }
+    }
+
+    private interface HandleCall<T> {
+        T apply(long handle);
+    }
+
+    private <T> T withHandle(HandleCall<T> call) {
+        lock.readLock().lock();
+        try {
+            if (handle == 0) {
+                throw new OpenSkillsException("runtime is closed");
+            }
+            return call.apply(handle);
+        } finally {
+            lock.readLock().unlock();
+        }
+    }
+
+    private <T> CompletableFuture<T> async(Supplier<T> call) {
+        return CompletableFuture.supplyAsync(call, executor);
+    }
+
+    private static List<SkillDescriptor> descriptors(String json) {
+        return Json.parseArray(json).stream().map(SkillDescriptor::fromJson).toList();
+    }
+
+    private static final class DaemonThreadFactory implements java.util.concurrent.ThreadFactory {
+        private final AtomicInteger count = new AtomicInteger();
+
+        @Override
+        public Thread newThread(Runnable runnable) {
+            Thread thread = new Thread(runnable, "openskills-" + count.incrementAndGet());
+            thread.setDaemon(true);
+            return thread;
+        }
+    }
+}
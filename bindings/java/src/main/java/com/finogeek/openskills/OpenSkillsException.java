@@ -0,0 +1,14 @@
+package com.finogeek.openskills;
+
+/** Error reported by the OpenSkills runtime. */
+public class OpenSkillsException extends RuntimeException {
+    private static final long serialVersionUID = 1L;
+
+    public OpenSkillsException(String message) {
+        super(message);
+    }
+
+    public OpenSkillsException(String message, Throwable cause) {
+        super(message, cause);
+    }
+}
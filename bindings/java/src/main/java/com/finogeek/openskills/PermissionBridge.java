@@ -0,0 +1,22 @@
+package com.finogeek.openskills;
+
+/** Adapts a {@link PermissionCallback} to the native callback protocol. */
+final class PermissionBridge {
+    private final PermissionCallback callback;
+
+    PermissionBridge(PermissionCallback callback) {
+        this.callback = callback;
+    }
+
+    /** Called from native code with the request JSON; returns the C ABI response code. */
+    int onRequest(String requestJson) {
+        try {
+            PermissionResponse response = callback.requestPermission(
+                    PermissionRequest.fromJson(Json.parseObject(requestJson)));
+            return response == null ? PermissionResponse.DENY.code() : response.code();
+        } catch (Throwable t) {
+            // A failing callback denies the request rather than unwinding into native code.
+            return PermissionResponse.DENY.code();
+        }
+    }
+}
@@ -0,0 +1,14 @@
+package com.finogeek.openskills;
+
+/**
+ * Answers permission prompts raised when the host policy resolves a tool to
+ * {@code prompt} (see {@link RuntimeOptions.Builder#permissions}).
+ *
+ * <p>The callback runs on the thread executing the skill while the runtime
+ * is locked, so it must be thread-safe and must not call back into the same
+ * runtime. Exceptions deny the request.
+ */
+@FunctionalInterface
+public interface PermissionCallback {
+    PermissionResponse requestPermission(PermissionRequest request);
+}
@@ -0,0 +1,23 @@
+package com.finogeek.openskills;
+
+import java.util.LinkedHashMap;
+import java.util.Map;
+
+/** A tool use that needs the host's approval. */
+public record PermissionRequest(
+        String skillId, String tool, String description, RiskLevel riskLevel, Map<String, String> context) {
+
+    static PermissionRequest fromJson(Map<String, Object> json) {
+        Map<String, String> context = new LinkedHashMap<>();
+        Map<String, Object> raw = Json.object(json, "context");
+        if (raw != null) {
+            raw.forEach((key, value) -> context.put(key, String.valueOf(value)));
+        }
+        return new PermissionRequest(
+                Json.string(json, "skill_id"),
+                Json.string(json, "tool"),
+                Json.string(json, "description"),
+                RiskLevel.fromJson(Json.string(json, "risk_level")),
+                Map.copyOf(context));
+    }
+}
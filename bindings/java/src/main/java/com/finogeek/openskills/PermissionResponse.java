@@ -0,0 +1,21 @@
+package com.finogeek.openskills;
+
+/** Answer to a {@link PermissionRequest}. */
+public enum PermissionResponse {
+    /** Deny the tool. */
+    DENY(0),
+    /** Allow the tool for this request only. */
+    ALLOW_ONCE(1),
+    /** Allow the tool for this skill from now on. */
+    ALLOW_ALWAYS(2);
+
+    private final int code;
+
+    PermissionResponse(int code) {
+        this.code = code;
+    }
+
+    int code() {
+        return code;
+    }
+}
@@ -0,0 +1,12 @@
+package com.finogeek.openskills;
+
+import java.util.List;
+import java.util.Map;
+
+/** OpenClaw-compatible binaries and environment variables a skill needs. */
+public record Requires(List<String> bins, List<String> env) {
+
+    static Requires fromJson(Map<String, Object> json) {
+        return json == null ? null : new Requires(Json.strings(json, "bins"), Json.strings(json, "env"));
+    }
+}
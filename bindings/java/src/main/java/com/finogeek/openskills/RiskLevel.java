@@ -0,0 +1,15 @@
+package com.finogeek.openskills;
+
+/** Risk level of a {@link PermissionRequest}. */
+public enum RiskLevel {
+    /** Read operations. */
+    LOW,
+    /** Write operations. */
+    MEDIUM,
+    /** Bash, network and destructive operations. */
+    HIGH;
+
+    static RiskLevel fromJson(String value) {
+        return value == null ? HIGH : valueOf(value.toUpperCase(java.util.Locale.ROOT));
+    }
+}
@@ -0,0 +1,126 @@
+package com.finogeek.openskills;
+
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.concurrent.Executor;
+
+/** Options for {@link OpenSkillRuntime#create(RuntimeOptions)}. */
+public final class RuntimeOptions {
+    private final List<String> customDirectories;
+    private final Boolean useStandardLocations;
+    private final String projectRoot;
+    private final String workspaceDir;
+    private final HostPolicy permissions;
+    private final boolean strictPermissions;
+    private final PermissionCallback permissionCallback;
+    private final Executor executor;
+
+    private RuntimeOptions(Builder builder) {
+        this.customDirectories = List.copyOf(builder.customDirectories);
+        this.useStandardLocations = builder.useStandardLocations;
+        this.projectRoot = builder.projectRoot;
+        this.workspaceDir = builder.workspaceDir;
+        this.permissions = builder.permissions;
+        this.strictPermissions = builder.strictPermissions;
+        this.permissionCallback = builder.permissionCallback;
+        this.executor = builder.executor;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    PermissionCallback permissionCallback() {
+        return permissionCallback;
+    }
+
+    Executor executor() {
+        return executor;
+    }
+
+    String toJson() {
+        Map<String, Object> json = new LinkedHashMap<>();
+        json.put("custom_directories", customDirectories);
+        if (useStandardLocations != null) {
+            json.put("use_standard_locations", useStandardLocations);
+        }
+        if (projectRoot != null) {
+            json.put("project_root", projectRoot);
+        }
+        if (workspaceDir != null) {
+            json.put("workspace_dir", workspaceDir);
+        }
+        if (permissions != null) {
+            json.put("permissions", permissions.toJson());
+        }
+        json.put("strict_permissions", strictPermissions);
+        return Json.write(json);
+    }
+
+    public static final class Builder {
+        private final List<String> customDirectories = new ArrayList<>();
+        private Boolean useStandardLocations;
+        private String projectRoot;
+        private String workspaceDir;
+        private HostPolicy permissions;
+        private boolean strictPermissions;
+        private PermissionCallback permissionCallback;
+        private Executor executor;
+
+        private Builder() {}
+
+        /** Add a skill directory to scan besides (or instead of) the standard locations. */
+        public Builder customDirectory(String dir) {
+            customDirectories.add(dir);
+            return this;
+        }
+
+        /** Scan {@code ~/.claude/skills/}, {@code .claude/skills/} and nested ones (default: true). */
+        public Builder useStandardLocations(boolean use) {
+            this.useStandardLocations = use;
+            return this;
+        }
+
+        /** Project root for the standard locations (default: current directory). */
+        public Builder projectRoot(String projectRoot) {
+            this.projectRoot = projectRoot;
+            return this;
+        }
+
+        /** Workspace directory for skill I/O (default: a per-session directory). */
+        public Builder workspaceDir(String workspaceDir) {
+            this.workspaceDir = workspaceDir;
+            return this;
+        }
+
+        /** Host permission policy; use {@link HostPolicy.Fallback#PROMPT} to reach the permission callback. */
+        public Builder permissions(HostPolicy permissions) {
+            this.permissions = permissions;
+            return this;
+        }
+
+        /** Strict mode: nothing is pre-approved by skill declarations and other tools are denied. */
+        public Builder strictPermissions(boolean strict) {
+            this.strictPermissions = strict;
+            return this;
+        }
+
+        /** Callback answering permission prompts. */
+        public Builder permissionCallback(PermissionCallback callback) {
+            this.permissionCallback = callback;
+            return this;
+        }
+
+        /** Executor for the {@code *Async} methods (default: a shared daemon thread pool). */
+        public Builder executor(Executor executor) {
+            this.executor = executor;
+            return this;
+        }
+
+        public RuntimeOptions build() {
+            return new RuntimeOptions(this);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+package com.finogeek.openskills;
+
+import java.util.Map;
+
+/**
+ * Skill metadata returned by discovery and listing.
+ *
+ * @param version semver version ({@code "0.0.0"} when the skill declares none)
+ * @param location {@code personal}, {@code project}, {@code nested} or {@code custom}
+ * @param requiresSummary OpenClaw-compatible "bins, env" summary, or {@code null}
+ * @param kind {@code instruction_only}, {@code script}, {@code wasm} or {@code hybrid}
+ */
+public record SkillDescriptor(
+        String id,
+        String version,
+        String description,
+        String location,
+        boolean userInvocable,
+        String requiresSummary,
+        String kind) {
+
+    static SkillDescriptor fromJson(Map<String, Object> json) {
+        return new SkillDescriptor(
+                Json.string(json, "id"),
+                Json.string(json, "version"),
+                Json.string(json, "description"),
+                Json.string(json, "location"),
+                Json.bool(json, "user_invocable"),
+                Json.string(json, "requires_summary"),
+                Json.string(json, "kind"));
+    }
+}
@@ -0,0 +1,17 @@
+package com.finogeek.openskills;
+
+import java.util.Map;
+
+/**
+ * An executable artifact in a skill.
+ *
+ * @param path path relative to the skill root
+ * @param kind {@code wasm}, {@code python} or {@code shell}
+ * @param sandbox sandbox that runs it ({@code wasm} or {@code native})
+ */
+public record SkillTarget(String path, String kind, String sandbox) {
+
+    static SkillTarget fromJson(Map<String, Object> json) {
+        return new SkillTarget(Json.string(json, "path"), Json.string(json, "kind"), Json.string(json, "sandbox"));
+    }
+}
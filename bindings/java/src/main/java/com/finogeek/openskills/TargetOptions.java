@@ -0,0 +1,100 @@
+package com.finogeek.openskills;
+
+import java.util.ArrayList;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/** Options for {@link OpenSkillRuntime#runSkillTarget(String, TargetOptions)}. */
+public final class TargetOptions {
+    private final Map<String, Object> json;
+
+    private TargetOptions(Map<String, Object> json) {
+        this.json = json;
+    }
+
+    public static Builder builder() {
+        return new Builder();
+    }
+
+    String toJson() {
+        return Json.write(json);
+    }
+
+    public static final class Builder {
+        private final Map<String, Object> json = new LinkedHashMap<>();
+        private final List<String> args = new ArrayList<>();
+        private final List<String> readPaths = new ArrayList<>();
+        private final List<String> writePaths = new ArrayList<>();
+        private final Map<String, String> env = new LinkedHashMap<>();
+
+        private Builder() {}
+
+        /** {@code auto} (default: the skill's entry point), {@code script} or {@code wasm}. */
+        public Builder targetType(String targetType) {
+            json.put("target_type", targetType);
+            return this;
+        }
+
+        /** Script or WASM module path, relative to the skill root. */
+        public Builder path(String path) {
+            json.put("path", path);
+            return this;
+        }
+
+        /** Script argument. */
+        public Builder arg(String arg) {
+            args.add(arg);
+            return this;
+        }
+
+        public Builder timeoutMs(long timeoutMs) {
+            json.put("timeout_ms", timeoutMs);
+            return this;
+        }
+
+        /** Input passed to the target: a {@code Map}, {@code List}, string, number or boolean. */
+        public Builder input(Object input) {
+            json.put("input", input);
+            return this;
+        }
+
+        /** Workspace directory for this run (overrides the runtime default). */
+        public Builder workspaceDir(String workspaceDir) {
+            json.put("workspace_dir", workspaceDir);
+            return this;
+        }
+
+        /** Extra path the target may read, beyond the skill's declared permissions. */
+        public Builder readPath(String path) {
+            readPaths.add(path);
+            return this;
+        }
+
+        /** Extra path the target may write, beyond the skill's declared permissions. */
+        public Builder writePath(String path) {
+            writePaths.add(path);
+            return this;
+        }
+
+        public Builder allowNetwork(boolean allow) {
+            json.put("allow_network", allow);
+            return this;
+        }
+
+        /** Environment variable for this run. */
+        public Builder env(String key, String value) {
+            env.put(key, value);
+            return this;
+        }
+
+        public TargetOptions build() {
+            Map<String, Object> out = new LinkedHashMap<>(json);
+            out.put("args", List.copyOf(args));
+            out.put("read_paths", List.copyOf(readPaths));
+            out.put("write_paths", List.copyOf(writePaths));
+            out.put("env", Map.copyOf(env));
+            return new TargetOptions(out);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+package com.finogeek.openskills;
+
+import static org.junit.jupiter.api.Assertions.assertEquals;
+import static org.junit.jupiter.api.Assertions.assertFalse;
+import static org.junit.jupiter.api.Assertions.assertThrows;
+import static org.junit.jupiter.api.Assertions.assertTrue;
+
+import java.io.IOException;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.List;
+import java.util.Map;
+import java.util.concurrent.CopyOnWriteArrayList;
+import org.junit.jupiter.api.Test;
+import org.junit.jupiter.api.io.TempDir;
+
+class OpenSkillRuntimeTest {
+    @TempDir
+    Path skills;
+
+    private OpenSkillRuntime runtime(RuntimeOptions.Builder builder) throws IOException {
+        Path skill = skills.resolve("echo-skill");
+        Files.createDirectories(skill.resolve("scripts"));
+        Files.writeString(skill.resolve("SKILL.md"),
+                "---\nname: echo-skill\ndescription: Echoes its arguments.\nallowed-tools: Bash\n---\nRun scripts/echo.sh.\n");
+        Files.writeString(skill.resolve("scripts/echo.sh"), "echo \"$@\"\n");
+        OpenSkillRuntime runtime = OpenSkillRuntime.create(builder
+                .customDirectory(skills.toString())
+                .useStandardLocations(false)
+                .build());
+        runtime.discoverSkills();
+        return runtime;
+    }
+
+    @Test
+    void discoverActivateAndListTargets() throws IOException {
+        try (OpenSkillRuntime runtime = runtime(RuntimeOptions.builder())) {
+            List<SkillDescriptor> skills = runtime.listSkills();
+            assertEquals("echo-skill", skills.get(0).id());
+            assertEquals("custom", skills.get(0).location());
+
+            LoadedSkill loaded = runtime.activateSkill("echo-skill");
+            assertEquals("Run scripts/echo.sh.", loaded.instructions());
+            assertEquals(List.of("Bash"), loaded.allowedTools());
+
+            assertEquals("scripts/echo.sh", runtime.listSkillTargets("echo-skill").get(0).path());
+        }
+    }
+
+    @Test
+    void runSkillTargetSyncAndAsync() throws Exception {
+        try (OpenSkillRuntime runtime = runtime(RuntimeOptions.builder())) {
+            TargetOptions options = TargetOptions.builder().path("scripts/echo.sh").arg("hello").build();
+            ExecutionResult result = runtime.runSkillTarget("echo-skill", options);
+            assertEquals("hello", result.stdout().trim());
+            assertEquals("success", result.audit().exitStatus());
+
+            ExecutionResult async = runtime.runSkillTargetAsync("echo-skill", options).get();
+            assertEquals("hello", async.stdout().trim());
+        }
+    }
+
+    @Test
+    void permissionCallbackAnswersPrompts() throws IOException {
+        List<PermissionRequest> requests = new CopyOnWriteArrayList<>();
+        RuntimeOptions.Builder builder = RuntimeOptions.builder()
+                .permissions(new HostPolicy(false, HostPolicy.Fallback.PROMPT, null, null))
+                .permissionCallback(request -> {
+                    requests.add(request);
+                    return PermissionResponse.DENY;
+                });
+        try (OpenSkillRuntime runtime = runtime(builder)) {
+            TargetOptions options = TargetOptions.builder().path("scripts/echo.sh").build();
+            runtime.runSkillTarget("echo-skill", options);
+            assertEquals(1, requests.size());
+            assertEquals("Bash", requests.get(0).tool());
+            assertEquals(RiskLevel.HIGH, requests.get(0).riskLevel());
+        }
+    }
+
+    @Test
+    void errorsAreThrown() throws IOException {
+        OpenSkillRuntime runtime = runtime(RuntimeOptions.builder());
+        OpenSkillsException error =
+                assertThrows(OpenSkillsException.class, () -> runtime.activateSkill("missing"));
+        assertTrue(error.getMessage().contains("skill not found"), error.getMessage());
+
+        runtime.close();
+        assertThrows(OpenSkillsException.class, runtime::listSkills);
+    }
+
+    @Test
+    void jsonRoundTrip() {
+        Object value = Json.parse(Json.write(Map.of("text", "a\"b\n\u0001", "list", List.of(1L, 2.5, true))));
+        assertEquals(Map.of("text", "a\"b\n\u0001", "list", List.of(1L, 2.5, true)), value);
+        assertFalse(OpenSkillRuntime.version().isEmpty());
+    }
+}
@@ -0,0 +1,407 @@
+"""Type stubs for the OpenSkills runtime bindings."""
+
+from types import TracebackType
+from typing import Any, Iterator, Literal, Mapping, Sequence, TypedDict, final
+
+JsonValue = Any
+
+SkillLocationName = Literal["personal", "project", "nested", "custom"]
+
+class SandboxViolationDict(TypedDict):
+    operation: str
+    path: str | None
+
+class NetworkRequestDict(TypedDict):
+    method: str
+    url: str
+    host: str
+    port: int
+    allowed: bool
+
+class HostCallDict(TypedDict):
+    function: str
+    target: str
+    allowed: bool
+
+class CapabilityDowngradeDict(TypedDict):
+    tool: str
+    reason: Literal["policy_denied", "permission_denied", "unmapped", "platform", "workspace_only"]
+    detail: str
+
+class CapabilityNeededDict(TypedDict):
+    tool: str
+    resource: str | None
+
+class SkillActionDict(TypedDict):
+    skill_id: str
+    action_id: str
+    capabilities: list[str]
+    description: str | None
+    has_input_schema: bool
+
+class SkillCardExampleDict(TypedDict):
+    name: str | None
+    prompt: str
+    input: JsonValue | None
+
+class SkillCardDict(TypedDict):
+    id: str
+    version: str
+    description: str
+    author: str | None
+    license: str | None
+    homepage: str | None
+    tags: list[str]
+    location: SkillLocationName
+    kind: Literal["instruction_only", "script", "wasm", "hybrid"]
+    user_invocable: bool
+    examples: list[SkillCardExampleDict]
+    readme: str | None
+
+class SkillTargetDict(TypedDict):
+    path: str
+    kind: Literal["wasm", "python", "shell"]
+    sandbox: Literal["wasm", "native"]
+
+class EntrypointResolutionDict(TypedDict):
+    selected: str | None
+    args: list[str]
+    trace: list[str]
+
+class GcReportDict(TypedDict):
+    removed: list[str]
+    removed_sessions: list[str]
+    freed_bytes: int
+
+class ArtifactDict(TypedDict):
+    path: str
+    size: int
+    mime: str
+    sha256: str
+    converted_from: str | None
+
+class WorkspaceImportDict(TypedDict):
+    source: str | None
+    dest: str
+    path: str
+    size: int
+    sha256: str
+    timestamp: int
+
+class SandboxedCommandResultDict(TypedDict):
+    exit_code: int
+    stdout: str
+    stderr: str
+    timed_out: bool
+
+class RunSkillTargetOptions(TypedDict, total=False):
+    target_type: Literal["auto", "script", "wasm"]
+    path: str
+    args: Sequence[str]
+    timeout_ms: int
+    input: JsonValue
+    workspace_dir: str
+    read_paths: Sequence[str]
+    write_paths: Sequence[str]
+    allow_network: bool
+    env: Mapping[str, str]
+
+@final
+class SkillInfo:
+    """A skill in a listing; `skill["id"]` indexing is also supported."""
+
+    @property
+    def id(self) -> str: ...
+    @property
+    def version(self) -> str: ...
+    @property
+    def description(self) -> str: ...
+    @property
+    def location(self) -> SkillLocationName: ...
+    @property
+    def user_invocable(self) -> bool: ...
+    @property
+    def skill_kind(self) -> str: ...
+    @property
+    def project_root(self) -> str | None: ...
+    def __getitem__(self, key: str) -> Any: ...
+    def __contains__(self, key: str) -> bool: ...
+
+@final
+class LoadedSkill:
+    """A skill's full content, returned by `activate_skill`."""
+
+    @property
+    def id(self) -> str: ...
+    @property
+    def version(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def description(self) -> str: ...
+    @property
+    def allowed_tools(self) -> list[str]: ...
+    @property
+    def model(self) -> str | None: ...
+    @property
+    def context(self) -> str | None: ...
+    @property
+    def agent(self) -> str | None: ...
+    @property
+    def user_invocable(self) -> bool: ...
+    @property
+    def location(self) -> SkillLocationName: ...
+    @property
+    def instructions(self) -> str: ...
+    def __getitem__(self, key: str) -> Any: ...
+    def __contains__(self, key: str) -> bool: ...
+
+@final
+class AuditRecord:
+    """Audit record of one execution."""
+
+    @property
+    def skill_id(self) -> str: ...
+    @property
+    def version(self) -> str: ...
+    @property
+    def input_hash(self) -> str: ...
+    @property
+    def output_hash(self) -> str: ...
+    @property
+    def start_time_ms(self) -> int: ...
+    @property
+    def duration_ms(self) -> int: ...
+    @property
+    def permissions_used(self) -> list[str]: ...
+    @property
+    def exit_status(self) -> str:
+        """`success`, `timeout`, `permission_denied`, `instruction_only`,
+        `dry_run`, `failed:<reason>`, `sandbox_violation:<reason>` or
+        `schema_violation:<reason>`."""
+    @property
+    def stdout(self) -> str: ...
+    @property
+    def stderr(self) -> str: ...
+    @property
+    def sandbox_mode(self) -> str: ...
+    @property
+    def sandbox_violations(self) -> list[SandboxViolationDict]: ...
+    @property
+    def fuel_consumed(self) -> int | None: ...
+    @property
+    def network_requests(self) -> list[NetworkRequestDict]: ...
+    @property
+    def host_calls(self) -> list[HostCallDict]: ...
+    @property
+    def capability_downgrades(self) -> list[CapabilityDowngradeDict]: ...
+    @property
+    def attempt(self) -> int:
+        """1 unless the execution was retried."""
+    @property
+    def traceparent(self) -> str | None:
+        """Caller's W3C trace context, if one was passed."""
+    def __getitem__(self, key: str) -> Any: ...
+    def __contains__(self, key: str) -> bool: ...
+
+@final
+class ExecutionResult:
+    """Result of executing a skill."""
+
+    @property
+    def output(self) -> JsonValue: ...
+    @property
+    def stdout(self) -> str: ...
+    @property
+    def stderr(self) -> str: ...
+    @property
+    def audit(self) -> AuditRecord: ...
+    @property
+    def artifacts(self) -> list[ArtifactDict]: ...
+    @property
+    def capabilities_needed(self) -> list[CapabilityNeededDict]:
+        """For failed executions: tools to ask for before retrying."""
+    def __getitem__(self, key: str) -> Any: ...
+    def __contains__(self, key: str) -> bool: ...
+
+class ExecutionContextWrapper:
+    def __init__(self) -> None: ...
+    def fork(self) -> ExecutionContextWrapper: ...
+    def id(self) -> str: ...
+    def is_forked(self) -> bool: ...
+    def parent_id(self) -> str | None: ...
+    def summary(self) -> str | None: ...
+    def record_output(
+        self, output_type: Literal["stdout", "stderr", "toolcall", "tool_call", "tool", "result"], content: str
+    ) -> None: ...
+    def summarize(self) -> str: ...
+
+class SkillExecutionSessionWrapper:
+    def is_forked(self) -> bool: ...
+    def context_id(self) -> str | None: ...
+    def workspace_files(self) -> list[WorkspaceImportDict]: ...
+    def record_tool_call(self, tool: str, output: JsonValue) -> None: ...
+    def record_result(self, output: JsonValue) -> None: ...
+    def record_stdout(self, stdout: str) -> None: ...
+    def record_stderr(self, stderr: str) -> None: ...
+    def summarize(self) -> str: ...
+
+class OpenSkillRuntimeWrapper:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def with_project_root(project_root: str) -> OpenSkillRuntimeWrapper: ...
+    @staticmethod
+    def from_directory(skills_dir: str) -> OpenSkillRuntimeWrapper: ...
+    @staticmethod
+    def with_custom_directories(
+        custom_directories: Sequence[str],
+        *,
+        use_standard_locations: bool = True,
+        project_root: str | None = None,
+    ) -> OpenSkillRuntimeWrapper: ...
+    def __enter__(self) -> OpenSkillRuntimeWrapper: ...
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None = None,
+        exc_value: BaseException | None = None,
+        traceback: TracebackType | None = None,
+    ) -> bool: ...
+    def __iter__(self) -> Iterator[SkillInfo]: ...
+    def __len__(self) -> int: ...
+    def cleanup_workspace(self) -> None: ...
+    def discover_skills(self) -> list[SkillInfo]: ...
+    def add_project_root(self, root: str) -> None: ...
+    def load_from_directory(self, dir: str) -> list[SkillInfo]: ...
+    def list_skills(self) -> list[SkillInfo]: ...
+    def list_skill_actions(self) -> list[SkillActionDict]: ...
+    def get_skill_card(self, skill_id: str) -> SkillCardDict: ...
+    def find_skill_for_capability(self, capability: str) -> tuple[str, str] | None: ...
+    def find_skill_for_action(self, action_id: str) -> str | None: ...
+    def invoke_skill_action(self, skill_id: str, action_id: str, input: JsonValue) -> ExecutionResult: ...
+    def get_agent_system_prompt(self) -> str: ...
+    def activate_skill(self, skill_id: str) -> LoadedSkill: ...
+    def execute_skill(
+        self,
+        skill_id: str,
+        input: JsonValue | None = None,
+        timeout_ms: int | None = None,
+        memory_mb: int | None = None,
+        cpu_quota: float | None = None,
+        max_fuel: int | None = None,
+        *,
+        read_paths: Sequence[str] | None = None,
+        write_paths: Sequence[str] | None = None,
+        allow_network: bool = False,
+        env: Mapping[str, str] | None = None,
+        effective_tools: Sequence[str] | None = None,
+        dry_run: bool = False,
+        retry: int | None = None,
+        retry_backoff_ms: int = 500,
+        retry_on: Sequence[Literal["timeout", "failure", "error"]] | None = None,
+        traceparent: str | None = None,
+    ) -> ExecutionResult: ...
+    def resolve_effective_tools(self, skill_id: str) -> list[str]: ...
+    def is_tool_allowed(self, skill_id: str, tool: str) -> bool: ...
+    def start_skill_session(
+        self,
+        skill_id: str,
+        input: JsonValue | None = None,
+        parent_context: ExecutionContextWrapper | None = None,
+    ) -> SkillExecutionSessionWrapper: ...
+    def finish_skill_session(
+        self,
+        session: SkillExecutionSessionWrapper,
+        output: JsonValue,
+        *,
+        stdout: str = "",
+        stderr: str = "",
+        exit_status: str | None = None,
+    ) -> ExecutionResult: ...
+    def check_tool_permission(
+        self,
+        skill_id: str,
+        tool: str,
+        description: str | None = None,
+        argument: str | None = None,
+    ) -> bool: ...
+    def set_host_policy(
+        self,
+        trust_skill_allowed_tools: bool,
+        fallback: Literal["allow", "deny", "prompt"],
+        deny: Sequence[str] = ...,
+        allow: Sequence[str] = ...,
+        skill_sandbox: Literal["allow", "deny", "prompt"] | None = None,
+    ) -> None: ...
+    def set_permission_profile(
+        self, profile: Literal["strict", "standard", "permissive"]
+    ) -> None: ...
+    def set_output_redaction(self, enabled: bool) -> None: ...
+    def set_secret_redaction(
+        self, enabled: bool, patterns: Sequence[str] | None = None
+    ) -> None: ...
+    def set_tty_cleanup(self, enabled: bool) -> None: ...
+    def set_binary_output_diversion(self, enabled: bool) -> None: ...
+    def set_artifact_collection(self, enabled: bool) -> None: ...
+    def add_output_converter(
+        self,
+        from_type: str,
+        to: str,
+        skill_id: str,
+        run: str,
+        args: Sequence[str] | None = None,
+    ) -> None: ...
+    def get_session_id(self) -> str: ...
+    def set_session_id(self, session_id: str) -> None: ...
+    def set_entrypoint_config(
+        self,
+        precedence: Literal["wasm_first", "script_first"] | None = None,
+        wasm_candidates: Sequence[str] | None = None,
+        script_candidates: Sequence[str] | None = None,
+    ) -> None: ...
+    def explain_entrypoint(self, skill_id: str) -> EntrypointResolutionDict: ...
+    def set_max_output_bytes(self, max_bytes: int | None = None) -> None: ...
+    def set_artifact_retention(
+        self,
+        max_age_ms: int | None = None,
+        max_count: int | None = None,
+        max_bytes: int | None = None,
+        skill_id: str | None = None,
+    ) -> None: ...
+    def set_session_retention(self, max_age_ms: int | None = None) -> None: ...
+    def workspace_for(self, conversation_id: str) -> str: ...
+    def use_conversation(self, conversation_id: str) -> str: ...
+    def remove_conversation_workspace(self, conversation_id: str) -> bool: ...
+    def set_conversation_retention(self, max_age_ms: int | None = None) -> None: ...
+    def pin_artifact(self, path: str) -> str: ...
+    def unpin_artifact(self, path: str) -> str: ...
+    def gc_artifacts(self) -> GcReportDict: ...
+    def set_max_import_bytes(self, max_bytes: int) -> None: ...
+    def set_blob_store(self, enable: bool, dir: str | None = None) -> None: ...
+    def import_into_workspace(self, source: str | bytes, dest_rel: str) -> WorkspaceImportDict: ...
+    def get_import_audit(self) -> list[WorkspaceImportDict]: ...
+    def set_output_pipeline(self, processors: Sequence[str], skill_id: str | None = None) -> None: ...
+    def read_skill_file(self, skill_id: str, relative_path: str) -> str: ...
+    def list_skill_files(self, skill_id: str, subdir: str | None = None, recursive: bool = False) -> list[str]: ...
+    def list_skill_targets(self, skill_id: str) -> list[SkillTargetDict]: ...
+    def run_skill_target(self, skill_id: str, options: RunSkillTargetOptions | None = None) -> ExecutionResult: ...
+    def run_skill_example(self, skill_id: str, name: str) -> ExecutionResult: ...
+
+def features() -> list[str]: ...
+
+def run_sandboxed_shell_command(
+    command: str,
+    working_dir: str,
+    *,
+    allow_network: bool = False,
+    allowed_hosts: Sequence[str] | None = None,
+    allowed_ports: Sequence[int] | None = None,
+    allow_process: bool = False,
+    allowed_programs: Sequence[str] | None = None,
+    read_paths: Sequence[str] | None = None,
+    write_paths: Sequence[str] | None = None,
+    env_vars: Mapping[str, str] | None = None,
+    timeout_ms: int = 30000,
+    sandbox_mode: Literal["enforce", "disabled"] | None = None,
+    backend: Literal["auto", "native", "container"] | None = None,
+    container_image: str | None = None,
+) -> SandboxedCommandResultDict: ...
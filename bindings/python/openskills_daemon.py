@@ -0,0 +1,217 @@
+"""
+Client for the ``openskillsd`` daemon.
+
+The daemon keeps one runtime (discovered skills, compiled WASM modules,
+caches) warm and serves it over a Unix socket, so short-lived agent
+processes share it instead of each loading the native runtime. This module
+is pure Python and does not need the native extension.
+
+Usage:
+    from openskills_daemon import DaemonClient
+
+    with DaemonClient.connect() as client:
+        skills = client.list_skills()
+        result = client.execute_skill("pdf", {"input": {"file": "a.pdf"}})
+
+Requests and results use the daemon's JSON (snake_case keys), as dicts.
+"""
+
+import base64
+import json
+import os
+import socket
+import sys
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+SOCKET_NAME = "openskillsd.sock"
+
+
+def default_socket_path() -> str:
+    """
+    Socket the daemon listens on by default: ``$OPENSKILLS_SOCKET``, else
+    ``openskillsd.sock`` in ``$XDG_RUNTIME_DIR``, else in the OpenSkills
+    cache directory.
+    """
+    if os.environ.get("OPENSKILLS_SOCKET"):
+        return os.environ["OPENSKILLS_SOCKET"]
+    if sys.platform.startswith("linux") and os.environ.get("XDG_RUNTIME_DIR"):
+        return os.path.join(os.environ["XDG_RUNTIME_DIR"], SOCKET_NAME)
+    if sys.platform == "darwin":
+        cache_dir = Path.home() / "Library" / "Caches"
+    else:
+        cache_dir = Path(os.environ.get("XDG_CACHE_HOME") or Path.home() / ".cache")
+    return str(cache_dir / "openskills" / SOCKET_NAME)
+
+
+class DaemonError(Exception):
+    """Error reported by the daemon or the connection to it."""
+
+
+class DaemonClient:
+    """Connection to an ``openskillsd`` daemon. Not thread-safe; use one per thread."""
+
+    def __init__(self, sock: socket.socket):
+        self._sock = sock
+        self._reader = sock.makefile("r", encoding="utf-8")
+        self._next_id = 0
+
+    @classmethod
+    def connect(cls, socket_path: Optional[str] = None) -> "DaemonClient":
+        """Connect to the daemon at ``socket_path`` (default: ``default_socket_path()``)."""
+        path = socket_path or default_socket_path()
+        sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            sock.connect(path)
+        except OSError as e:
+            sock.close()
+            raise DaemonError(f"cannot connect to {path}: {e}") from e
+        return cls(sock)
+
+    def call(self, method: str, params: Optional[Dict[str, Any]] = None) -> Any:
+        """Call a daemon method with JSON params and return its result."""
+        self._next_id += 1
+        request = {"id": self._next_id, "method": method, "params": params}
+        try:
+            self._sock.sendall((json.dumps(request) + "\n").encode("utf-8"))
+            line = self._reader.readline()
+        except OSError as e:
+            raise DaemonError(str(e)) from e
+        if not line:
+            raise DaemonError("daemon closed the connection")
+        response = json.loads(line)
+        if "error" in response:
+            raise DaemonError(response["error"])
+        return response.get("result")
+
+    def status(self) -> Dict[str, Any]:
+        return self.call("status")
+
+    def discover_skills(self) -> List[Dict[str, Any]]:
+        return self.call("discover_skills")
+
+    def reload_skills(self) -> List[Dict[str, Any]]:
+        """Rediscover skills and return the added, modified and removed ones."""
+        return self.call("reload_skills")
+
+    def list_skills(self) -> List[Dict[str, Any]]:
+        return self.call("list_skills")
+
+    def activate_skill(self, skill_id: str) -> Dict[str, Any]:
+        return self.call("activate_skill", {"skill_id": skill_id})
+
+    def list_skill_targets(self, skill_id: str) -> List[Dict[str, Any]]:
+        return self.call("list_skill_targets", {"skill_id": skill_id})
+
+    def execute_skill(self, skill_id: str, options: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        return self.call("execute_skill", {"skill_id": skill_id, "options": options})
+
+    def run_skill_target(self, skill_id: str, options: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+        return self.call("run_skill_target", {"skill_id": skill_id, "options": options})
+
+    def start_session(
+        self, skill_id: str, input: Any = None, workspace_dir: Optional[str] = None
+    ) -> Dict[str, Any]:
+        """
+        Start an instruction-based session; it lives until finished, detached
+        or this connection closes. ``workspace_dir`` is handed to whoever
+        attaches it.
+        """
+        return self.call(
+            "start_session",
+            {"skill_id": skill_id, "input": input, "workspace_dir": workspace_dir},
+        )
+
+    def detach_session(self, session_id: str) -> Dict[str, Any]:
+        """Hand a session over to the daemon so another process can attach it."""
+        return self.call("detach_session", {"session_id": session_id})
+
+    def attach_session(self, session_id: str) -> Dict[str, Any]:
+        """Take over a detached session on this connection."""
+        return self.call("attach_session", {"session_id": session_id})
+
+    def list_sessions(self) -> List[Dict[str, Any]]:
+        """Sessions waiting to be attached."""
+        return self.call("list_sessions")
+
+    def record_tool_call(self, session_id: str, tool: str, output: Any = None) -> None:
+        self.call("record_tool_call", {"session_id": session_id, "tool": tool, "output": output})
+
+    def finish_session(
+        self,
+        session_id: str,
+        output: Any = None,
+        stdout: str = "",
+        stderr: str = "",
+        exit_status: str = "success",
+    ) -> Dict[str, Any]:
+        return self.call(
+            "finish_session",
+            {
+                "session_id": session_id,
+                "output": output,
+                "stdout": stdout,
+                "stderr": stderr,
+                "exit_status": exit_status,
+            },
+        )
+
+    def list_outputs(
+        self, session_id: Optional[str] = None, conversation_id: Optional[str] = None
+    ) -> List[Dict[str, Any]]:
+        """
+        Files in a workspace: a session's ``workspace_dir``, a conversation's
+        or, with neither, the runtime's.
+        """
+        return self.call(
+            "list_outputs", {"session_id": session_id, "conversation_id": conversation_id}
+        )
+
+    def read_output(
+        self,
+        path: str,
+        offset: int = 0,
+        length: Optional[int] = None,
+        session_id: Optional[str] = None,
+        conversation_id: Optional[str] = None,
+    ) -> Dict[str, Any]:
+        """
+        Read up to ``length`` bytes (at most 1 MiB) of an output file from
+        ``offset``. The chunk's ``data`` is base64-encoded.
+        """
+        return self.call(
+            "read_output",
+            {
+                "path": path,
+                "offset": offset,
+                "length": length,
+                "session_id": session_id,
+                "conversation_id": conversation_id,
+            },
+        )
+
+    def download_output(
+        self, path: str, session_id: Optional[str] = None, conversation_id: Optional[str] = None
+    ) -> bytes:
+        """Download a whole output file, one chunk at a time."""
+        data = bytearray()
+        while True:
+            chunk = self.read_output(path, len(data), None, session_id, conversation_id)
+            data.extend(base64.b64decode(chunk["data"]))
+            if chunk["eof"]:
+                return bytes(data)
+
+    def shutdown(self) -> None:
+        """Ask the daemon to stop accepting connections and exit."""
+        self.call("shutdown")
+
+    def close(self) -> None:
+        """Close the connection; sessions started on it are dropped."""
+        self._reader.close()
+        self._sock.close()
+
+    def __enter__(self) -> "DaemonClient":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        self.close()
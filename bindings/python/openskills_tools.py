@@ -344,10 +344,10 @@ def create_langchain_tools(runtime, workspace_dir: Optional[str] = None) -> List
         try:
             loaded = runtime.activate_skill(skill_id)
             return json.dumps({
-                "id": loaded['id'],
-                "name": loaded.get('name', loaded['id']),
-                "allowed_tools": loaded.get('allowed_tools', []),
-                "instructions": loaded['instructions'],
+                "id": loaded.id,
+                "name": loaded.name or loaded.id,
+                "allowed_tools": loaded.allowed_tools,
+                "instructions": loaded.instructions,
             })
         except Exception as e:
             return f"Error activating skill {skill_id}: {e}"
@@ -381,9 +381,9 @@ def create_langchain_tools(runtime, workspace_dir: Optional[str] = None) -> List
                 "timeout_ms": timeout_ms,
             })
             return json.dumps({
-                "stdout": result.get('stdout', ''),
-                "stderr": result.get('stderr', ''),
-                "output": result.get('output', {}),
+                "stdout": result.stdout,
+                "stderr": result.stderr,
+                "output": result.output,
             }, indent=2)
         except Exception as e:
             return f"Error running {script_path} from skill {skill_id}: {e}"
@@ -523,10 +523,10 @@ def create_simple_tools(runtime, workspace_dir: Optional[str] = None) -> Dict[st
     def activate_skill(skill_id: str) -> Dict:
         loaded = runtime.activate_skill(skill_id)
         return {
-            "id": loaded['id'],
-            "name": loaded.get('name', loaded['id']),
-            "allowed_tools": loaded.get('allowed_tools', []),
-            "instructions": loaded['instructions'],
+            "id": loaded.id,
+            "name": loaded.name or loaded.id,
+            "allowed_tools": loaded.allowed_tools,
+            "instructions": loaded.instructions,
         }
     
     def read_skill_file(skill_id: str, path: str) -> str:
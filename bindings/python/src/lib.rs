@@ -1,13 +1,16 @@
 use openskills_runtime::{
-    CommandPermissions, ExecutionContext, ExecutionOptions, ExecutionTarget, Fallback, HostPolicy,
-    OpenSkillRuntime, OutputType, PermissionsConfig, RuntimeConfig, RuntimeExecutionStatus,
-    SandboxMode, SkillExecutionSession, SkillLocation, run_sandboxed_command,
+    Artifact, CapabilityDowngrade, CapabilityNeeded, CommandPermissions, ContainerConfig, EntrypointConfig, EntrypointPrecedence, ExecutionBackend, ExecutionContext, ExecutionOptions, ExecutionResult, ExecutionTarget, Fallback, HostCall, HostPolicy,
+    NetworkRequest, OpenSkillRuntime, RuntimeAuditRecord, OutputConverter, OutputPipeline, RetentionPolicy, RetryPolicy, OutputType, PermissionProfile, PermissionsConfig, RuntimeConfig, RuntimeExecutionStatus,
+    SandboxMode, SandboxOverrides, SandboxViolation, SecretRedactor, SkillDescriptor, SkillExecutionSession, SkillLocation,
+    WorkspaceImport, run_sandboxed_command,
 };
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyList};
+use pyo3::types::{PyBytes, PyDict, PyIterator, PyList};
+use pyo3::IntoPyObjectExt;
 use serde_json::Value;
 use std::path::PathBuf;
 use std::sync::Mutex;
+use std::time::Duration;
 
 /// Safely convert timeout from i64 to u64, clamping negative values to 0.
 /// This matches the behavior of TypeScript's safe_timeout_ms function.
@@ -45,6 +48,241 @@ struct ExecutionContextWrapper {
     inner: Mutex<ExecutionContext>,
 }
 
+/// A skill in a listing. Fields are read-only attributes; `skill["id"]`
+/// still works for code written against the earlier dict listings.
+#[pyclass(name = "SkillInfo", frozen, get_all, eq)]
+#[derive(Clone, PartialEq)]
+struct SkillInfo {
+    id: String,
+    version: String,
+    description: String,
+    location: String,
+    user_invocable: bool,
+    skill_kind: String,
+    project_root: Option<String>,
+}
+
+impl From<SkillDescriptor> for SkillInfo {
+    fn from(s: SkillDescriptor) -> Self {
+        Self {
+            location: location_str(&s.location).to_string(),
+            id: s.id,
+            version: s.version,
+            description: s.description,
+            user_invocable: s.user_invocable,
+            skill_kind: s.kind.to_string(),
+            project_root: s.project_root.map(|p| p.display().to_string()),
+        }
+    }
+}
+
+impl SkillInfo {
+    const FIELDS: &'static [&'static str] = &[
+        "id",
+        "version",
+        "description",
+        "location",
+        "user_invocable",
+        "skill_kind",
+        "project_root",
+    ];
+}
+
+#[pymethods]
+impl SkillInfo {
+    fn __getitem__<'py>(slf: &Bound<'py, Self>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        field_item(slf.as_any(), Self::FIELDS, key)
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        Self::FIELDS.contains(&key)
+    }
+
+    fn __repr__(slf: &Bound<'_, Self>) -> PyResult<String> {
+        fields_repr(slf.as_any(), "SkillInfo", Self::FIELDS)
+    }
+}
+
+/// A skill's full content, returned by `activate_skill`.
+#[pyclass(name = "LoadedSkill", frozen, get_all)]
+struct PyLoadedSkill {
+    id: String,
+    version: String,
+    name: String,
+    description: String,
+    allowed_tools: Vec<String>,
+    model: Option<String>,
+    context: Option<String>,
+    agent: Option<String>,
+    user_invocable: bool,
+    location: String,
+    instructions: String,
+}
+
+impl PyLoadedSkill {
+    const FIELDS: &'static [&'static str] = &[
+        "id",
+        "version",
+        "name",
+        "description",
+        "allowed_tools",
+        "model",
+        "context",
+        "agent",
+        "user_invocable",
+        "location",
+        "instructions",
+    ];
+}
+
+#[pymethods]
+impl PyLoadedSkill {
+    fn __getitem__<'py>(slf: &Bound<'py, Self>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        field_item(slf.as_any(), Self::FIELDS, key)
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        Self::FIELDS.contains(&key)
+    }
+
+    fn __repr__(slf: &Bound<'_, Self>) -> PyResult<String> {
+        fields_repr(slf.as_any(), "LoadedSkill", Self::FIELDS)
+    }
+}
+
+/// Audit record of one execution.
+#[pyclass(name = "AuditRecord", frozen, get_all)]
+struct PyAuditRecord {
+    skill_id: String,
+    version: String,
+    input_hash: String,
+    output_hash: String,
+    start_time_ms: u64,
+    duration_ms: u64,
+    permissions_used: Vec<String>,
+    /// `success`, `timeout`, `permission_denied`, `instruction_only`,
+    /// `dry_run`, `failed:<reason>`, `sandbox_violation:<reason>` or
+    /// `schema_violation:<reason>`.
+    exit_status: String,
+    stdout: String,
+    stderr: String,
+    sandbox_mode: String,
+    sandbox_violations: Py<PyList>,
+    fuel_consumed: Option<u64>,
+    network_requests: Py<PyList>,
+    host_calls: Py<PyList>,
+    capability_downgrades: Py<PyList>,
+    /// 1 unless the execution was retried.
+    attempt: u32,
+    /// Caller's W3C trace context, if one was passed.
+    traceparent: Option<String>,
+}
+
+impl PyAuditRecord {
+    const FIELDS: &'static [&'static str] = &[
+        "skill_id",
+        "version",
+        "input_hash",
+        "output_hash",
+        "start_time_ms",
+        "duration_ms",
+        "permissions_used",
+        "exit_status",
+        "stdout",
+        "stderr",
+        "sandbox_mode",
+        "sandbox_violations",
+        "fuel_consumed",
+        "network_requests",
+        "host_calls",
+        "capability_downgrades",
+        "attempt",
+        "traceparent",
+    ];
+
+    fn new(py: Python<'_>, audit: RuntimeAuditRecord) -> PyResult<Self> {
+        let exit_status = match audit.exit_status {
+            RuntimeExecutionStatus::Success => "success".to_string(),
+            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
+            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
+            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
+            RuntimeExecutionStatus::SandboxViolation(msg) => format!("sandbox_violation:{}", msg),
+            RuntimeExecutionStatus::SchemaViolation(msg) => format!("schema_violation:{}", msg),
+            RuntimeExecutionStatus::InstructionOnly => "instruction_only".to_string(),
+            RuntimeExecutionStatus::DryRun => "dry_run".to_string(),
+        };
+        Ok(Self {
+            sandbox_mode: audit.sandbox_mode.as_audit_str().to_string(),
+            sandbox_violations: violations_to_py(py, &audit.sandbox_violations)?.unbind(),
+            network_requests: network_requests_to_py(py, &audit.network_requests)?.unbind(),
+            host_calls: host_calls_to_py(py, &audit.host_calls)?.unbind(),
+            capability_downgrades: downgrades_to_py(py, &audit.capability_downgrades)?.unbind(),
+            skill_id: audit.skill_id,
+            version: audit.version,
+            input_hash: audit.input_hash,
+            output_hash: audit.output_hash,
+            start_time_ms: audit.start_time_ms,
+            duration_ms: audit.duration_ms,
+            permissions_used: audit.permissions_used,
+            exit_status,
+            stdout: audit.stdout,
+            stderr: audit.stderr,
+            fuel_consumed: audit.fuel_consumed,
+            attempt: audit.attempt,
+            traceparent: audit.traceparent,
+        })
+    }
+}
+
+#[pymethods]
+impl PyAuditRecord {
+    fn __getitem__<'py>(slf: &Bound<'py, Self>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        field_item(slf.as_any(), Self::FIELDS, key)
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        Self::FIELDS.contains(&key)
+    }
+
+    fn __repr__(slf: &Bound<'_, Self>) -> PyResult<String> {
+        fields_repr(slf.as_any(), "AuditRecord", Self::FIELDS)
+    }
+}
+
+/// Result of executing a skill.
+#[pyclass(name = "ExecutionResult", frozen, get_all)]
+struct PyExecutionResult {
+    output: Py<PyAny>,
+    stdout: String,
+    stderr: String,
+    audit: Py<PyAuditRecord>,
+    /// Workspace files the execution created or modified.
+    artifacts: Py<PyList>,
+    /// For failed executions: `{"tool", "resource"}` dicts naming the tools
+    /// to ask the user for before retrying, from what the sandbox denied.
+    capabilities_needed: Py<PyList>,
+}
+
+impl PyExecutionResult {
+    const FIELDS: &'static [&'static str] =
+        &["output", "stdout", "stderr", "audit", "artifacts", "capabilities_needed"];
+}
+
+#[pymethods]
+impl PyExecutionResult {
+    fn __getitem__<'py>(slf: &Bound<'py, Self>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        field_item(slf.as_any(), Self::FIELDS, key)
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        Self::FIELDS.contains(&key)
+    }
+
+    fn __repr__(slf: &Bound<'_, Self>) -> PyResult<String> {
+        fields_repr(slf.as_any(), "ExecutionResult", Self::FIELDS)
+    }
+}
+
 #[pymethods]
 impl OpenSkillRuntimeWrapper {
     #[new]
@@ -68,6 +306,43 @@ impl OpenSkillRuntimeWrapper {
         }
     }
 
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    /// Remove the session workspace when leaving a `with` block. Exceptions
+    /// raised inside the block are not suppressed.
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &self,
+        _exc_type: Option<Bound<'_, PyAny>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.cleanup_workspace()?;
+        Ok(false)
+    }
+
+    /// Iterate over the discovered skills.
+    fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyIterator>> {
+        let skills = self.list_skills(py)?;
+        Ok(skills.bind(py).try_iter()?.unbind())
+    }
+
+    /// Number of discovered skills.
+    fn __len__(&self) -> usize {
+        self.inner.lock().unwrap().list_skills().len()
+    }
+
+    /// Remove the session workspace and everything in it.
+    fn cleanup_workspace(&self) -> PyResult<()> {
+        self.inner
+            .lock()
+            .unwrap()
+            .cleanup_workspace()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
+    }
+
     /// Create runtime with custom directories and configuration
     #[staticmethod]
     #[pyo3(signature = (custom_directories, *, use_standard_locations = true, project_root = None))]
@@ -97,26 +372,13 @@ impl OpenSkillRuntimeWrapper {
         let skills = runtime
             .discover_skills()
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        skill_infos(py, skills)
+    }
 
-        let list = PyList::empty(py);
-        for s in skills {
-            let item = PyDict::new(py);
-            item.set_item("id", s.id)?;
-            item.set_item("description", s.description)?;
-            item.set_item(
-                "location",
-                match s.location {
-                    SkillLocation::Personal => "personal",
-                    SkillLocation::Project => "project",
-                    SkillLocation::Nested => "nested",
-                    SkillLocation::Custom => "custom",
-                },
-            )?;
-            item.set_item("user_invocable", s.user_invocable)?;
-            list.append(item.as_any())?;
-        }
-
-        Ok(list.into())
+    /// Add a project root whose .claude/skills and nested skills later
+    /// discover_skills calls also load
+    fn add_project_root(&self, root: String) {
+        self.inner.lock().unwrap().add_project_root(root);
     }
 
     /// Load skills from a specific directory (additive - can be called multiple times)
@@ -125,52 +387,49 @@ impl OpenSkillRuntimeWrapper {
         let skills = runtime
             .load_from_directory(dir)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-
-        let list = PyList::empty(py);
-        for s in skills {
-            let item = PyDict::new(py);
-            item.set_item("id", s.id)?;
-            item.set_item("description", s.description)?;
-            item.set_item(
-                "location",
-                match s.location {
-                    SkillLocation::Personal => "personal",
-                    SkillLocation::Project => "project",
-                    SkillLocation::Nested => "nested",
-                    SkillLocation::Custom => "custom",
-                },
-            )?;
-            item.set_item("user_invocable", s.user_invocable)?;
-            list.append(item.as_any())?;
-        }
-
-        Ok(list.into())
+        skill_infos(py, skills)
     }
 
     /// List skills (progressive disclosure - descriptors only)
     fn list_skills(&self, py: Python) -> PyResult<Py<PyAny>> {
         let runtime = self.inner.lock().unwrap();
         let skills = runtime.list_skills();
+        skill_infos(py, skills)
+    }
 
-        let list = PyList::empty(py);
-        for s in skills {
+    /// Display metadata of a skill for skill pickers: description, author,
+    /// version, tags, example invocations and README.
+    fn get_skill_card(&self, py: Python, skill_id: String) -> PyResult<Py<PyAny>> {
+        let runtime = self.inner.lock().unwrap();
+        let card = runtime
+            .get_skill_card(&skill_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let json = py.import("json")?.getattr("loads")?;
+        let examples = PyList::empty(py);
+        for example in &card.examples {
             let item = PyDict::new(py);
-            item.set_item("id", s.id)?;
-            item.set_item("description", s.description)?;
-            item.set_item(
-                "location",
-                match s.location {
-                    SkillLocation::Personal => "personal",
-                    SkillLocation::Project => "project",
-                    SkillLocation::Nested => "nested",
-                    SkillLocation::Custom => "custom",
-                },
-            )?;
-            item.set_item("user_invocable", s.user_invocable)?;
-            list.append(item.as_any())?;
+            item.set_item("name", &example.name)?;
+            item.set_item("prompt", &example.prompt)?;
+            match &example.input {
+                Some(input) => item.set_item("input", json.call1((input.to_string(),))?)?,
+                None => item.set_item("input", py.None())?,
+            }
+            examples.append(item)?;
         }
-
-        Ok(list.into())
+        let dict = PyDict::new(py);
+        dict.set_item("id", card.id)?;
+        dict.set_item("version", card.version)?;
+        dict.set_item("description", card.description)?;
+        dict.set_item("author", card.author)?;
+        dict.set_item("license", card.license)?;
+        dict.set_item("homepage", card.homepage)?;
+        dict.set_item("tags", card.tags)?;
+        dict.set_item("location", location_str(&card.location))?;
+        dict.set_item("kind", card.kind.to_string())?;
+        dict.set_item("user_invocable", card.user_invocable)?;
+        dict.set_item("examples", examples)?;
+        dict.set_item("readme", card.readme)?;
+        dict.into_py_any(py)
     }
 
     /// List all declared actions from all skills (capability/action model).
@@ -221,31 +480,7 @@ impl OpenSkillRuntimeWrapper {
         let result = runtime
             .invoke_skill_action(&skill_id, &action_id, input_val)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-        let json_str = serde_json::to_string(&result.output)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{e}")))?;
-        let json_loads = json_module.getattr("loads")?;
-        let output: Py<PyAny> = json_loads.call1((json_str,))?.into();
-        let exit_status = match &result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-        let audit = PyDict::new(py);
-        audit.set_item("skill_id", result.audit.skill_id)?;
-        audit.set_item("exit_status", exit_status.as_str())?;
-        audit.set_item("stdout", result.audit.stdout)?;
-        audit.set_item("stderr", result.audit.stderr)?;
-        audit.set_item(
-            "sandbox_mode",
-            result.audit.sandbox_mode.as_audit_str(),
-        )?;
-        let out = PyDict::new(py);
-        out.set_item("output", output)?;
-        out.set_item("stdout", result.stdout)?;
-        out.set_item("stderr", result.stderr)?;
-        out.set_item("audit", audit)?;
-        Ok(out.into())
+        execution_result(py, result)
     }
 
     /// Get a complete skill-agnostic system prompt for agents.
@@ -261,31 +496,36 @@ impl OpenSkillRuntimeWrapper {
             .activate_skill(&skill_id)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
-        let skill = PyDict::new(py);
-        skill.set_item("id", loaded.id.clone())?;
-        skill.set_item("name", loaded.manifest.name.clone())?;
-        skill.set_item("description", loaded.manifest.description.clone())?;
-        skill.set_item("allowed_tools", loaded.manifest.get_allowed_tools())?;
-        skill.set_item("model", loaded.manifest.model.clone())?;
-        skill.set_item("context", loaded.manifest.context.clone())?;
-        skill.set_item("agent", loaded.manifest.agent.clone())?;
-        skill.set_item("user_invocable", loaded.manifest.is_user_invocable())?;
-        skill.set_item(
-            "location",
-            match loaded.location {
-                SkillLocation::Personal => "personal",
-                SkillLocation::Project => "project",
-                SkillLocation::Nested => "nested",
-                SkillLocation::Custom => "custom",
-            },
-        )?;
-        skill.set_item("instructions", loaded.instructions)?;
-
-        Ok(skill.into())
+        let skill = PyLoadedSkill {
+            version: loaded.manifest.version().to_string(),
+            name: loaded.manifest.name.clone(),
+            description: loaded.manifest.description.clone(),
+            allowed_tools: loaded.manifest.get_allowed_tools(),
+            model: loaded.manifest.model.clone(),
+            context: loaded.manifest.context.clone(),
+            agent: loaded.manifest.agent.clone(),
+            user_invocable: loaded.manifest.is_user_invocable(),
+            location: location_str(&loaded.location).to_string(),
+            id: loaded.id,
+            instructions: loaded.instructions,
+        };
+        skill.into_py_any(py)
     }
 
     /// Execute a skill's WASM module
-    #[pyo3(signature = (skill_id, input=None, timeout_ms=None, memory_mb=None))]
+    ///
+    /// `read_paths`, `write_paths`, `allow_network` and `env` widen the
+    /// sandbox for this run only (absolute paths; credential directories
+    /// and runtime-managed variables are rejected). `effective_tools`
+    /// replaces the host-policy resolution of the tools granted to the
+    /// sandbox (see `resolve_effective_tools`). With `dry_run`, nothing
+    /// runs: `output` is the execution plan and the audit status `dry_run`.
+    /// `retry` is the total number of attempts for timeouts and failures
+    /// (or the outcomes in `retry_on`), `retry_backoff_ms` apart and doubling;
+    /// each attempt is audited separately. `traceparent` (W3C trace context)
+    /// is kept in the audit and passed to the skill as `TRACEPARENT`.
+    #[pyo3(signature = (skill_id, input=None, timeout_ms=None, memory_mb=None, cpu_quota=None, max_fuel=None, *, read_paths=None, write_paths=None, allow_network=false, env=None, effective_tools=None, dry_run=false, retry=None, retry_backoff_ms=500, retry_on=None, traceparent=None))]
+    #[allow(clippy::too_many_arguments)]
     fn execute_skill(
         &self,
         py: Python<'_>,
@@ -293,6 +533,18 @@ impl OpenSkillRuntimeWrapper {
         input: Option<Bound<'_, PyAny>>,
         timeout_ms: Option<u64>,
         memory_mb: Option<u64>,
+        cpu_quota: Option<f64>,
+        max_fuel: Option<u64>,
+        read_paths: Option<Vec<String>>,
+        write_paths: Option<Vec<String>>,
+        allow_network: bool,
+        env: Option<&Bound<'_, PyDict>>,
+        effective_tools: Option<Vec<String>>,
+        dry_run: bool,
+        retry: Option<u32>,
+        retry_backoff_ms: u64,
+        retry_on: Option<Vec<String>>,
+        traceparent: Option<String>,
     ) -> PyResult<Py<PyAny>> {
         let mut runtime = self.inner.lock().unwrap();
 
@@ -310,55 +562,45 @@ impl OpenSkillRuntimeWrapper {
             None
         };
 
+        let retry = retry
+            .map(|max_attempts| {
+                RetryPolicy::from_millis(
+                    max_attempts,
+                    retry_backoff_ms,
+                    None,
+                    &retry_on.unwrap_or_default(),
+                )
+            })
+            .transpose()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+
         let options = ExecutionOptions {
             timeout_ms,
             memory_mb,
+            cpu_quota,
+            max_fuel,
             input: input_val,
+            sandbox_overrides: sandbox_overrides(read_paths, write_paths, allow_network, env),
+            effective_tools,
+            dry_run,
+            retry,
+            traceparent,
         };
 
         let result = runtime
             .execute_skill(&skill_id, options)
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
-        // Convert Value to JSON string, then parse to Python object
-        let json_str = serde_json::to_string(&result.output)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Serialization error: {e}"
-            )))?;
-            let json_module = py.import("json")?;
-            let json_loads = json_module.getattr("loads")?;
-            let output: Py<PyAny> = json_loads.call1((json_str,))?.into();
-
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
-        let audit = PyDict::new(py);
-        audit.set_item("skill_id", result.audit.skill_id)?;
-        audit.set_item("version", result.audit.version)?;
-        audit.set_item("input_hash", result.audit.input_hash)?;
-        audit.set_item("output_hash", result.audit.output_hash)?;
-        audit.set_item("start_time_ms", result.audit.start_time_ms)?;
-        audit.set_item("duration_ms", result.audit.duration_ms)?;
-        audit.set_item("permissions_used", result.audit.permissions_used)?;
-        audit.set_item("exit_status", exit_status)?;
-        audit.set_item("stdout", result.audit.stdout)?;
-        audit.set_item("stderr", result.audit.stderr)?;
-        audit.set_item(
-            "sandbox_mode",
-            result.audit.sandbox_mode.as_audit_str(),
-        )?;
-
-        let response = PyDict::new(py);
-        response.set_item("output", output)?;
-        response.set_item("stdout", result.stdout)?;
-        response.set_item("stderr", result.stderr)?;
-        response.set_item("audit", audit)?;
+        execution_result(py, result)
+    }
 
-        Ok(response.into())
+    /// Tools an execution of the skill would grant to the sandbox, resolved
+    /// through the host policy.
+    fn resolve_effective_tools(&self, skill_id: String) -> PyResult<Vec<String>> {
+        let runtime = self.inner.lock().unwrap();
+        runtime
+            .resolve_effective_tools(&skill_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
     /// Check if a tool is allowed for a skill
@@ -425,7 +667,7 @@ impl OpenSkillRuntimeWrapper {
             PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid JSON: {e}"))
         })?;
 
-        let status = parse_execution_status(exit_status);
+        let status = parse_execution_status(exit_status)?;
         let session = session.inner.lock().unwrap();
 
         let result = runtime
@@ -438,57 +680,23 @@ impl OpenSkillRuntimeWrapper {
             )
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
-        // Convert Value to JSON string, then parse to Python object
-        let result_json = serde_json::to_string(&result.output)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Serialization error: {e}"
-            )))?;
-        let json_loads = json_module.getattr("loads")?;
-        let output_obj: Py<PyAny> = json_loads.call1((result_json,))?.into();
-
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
-        let audit = PyDict::new(py);
-        audit.set_item("skill_id", result.audit.skill_id)?;
-        audit.set_item("version", result.audit.version)?;
-        audit.set_item("input_hash", result.audit.input_hash)?;
-        audit.set_item("output_hash", result.audit.output_hash)?;
-        audit.set_item("start_time_ms", result.audit.start_time_ms)?;
-        audit.set_item("duration_ms", result.audit.duration_ms)?;
-        audit.set_item("permissions_used", result.audit.permissions_used)?;
-        audit.set_item("exit_status", exit_status)?;
-        audit.set_item("stdout", result.audit.stdout)?;
-        audit.set_item("stderr", result.audit.stderr)?;
-        audit.set_item(
-            "sandbox_mode",
-            result.audit.sandbox_mode.as_audit_str(),
-        )?;
-
-        let response = PyDict::new(py);
-        response.set_item("output", output_obj)?;
-        response.set_item("stdout", result.stdout)?;
-        response.set_item("stderr", result.stderr)?;
-        response.set_item("audit", audit)?;
-
-        Ok(response.into())
+        execution_result(py, result)
     }
 
     /// Check if a tool call is permitted for a skill (ask-before-act for risky tools).
-    #[pyo3(signature = (skill_id, tool, description=None))]
+    /// `argument` is the call's command or path, matched against patterns
+    /// such as `Bash(npm run test:*)` in `allowed-tools`.
+    #[pyo3(signature = (skill_id, tool, description=None, argument=None))]
     fn check_tool_permission(
         &self,
         skill_id: String,
         tool: String,
         description: Option<String>,
+        argument: Option<String>,
     ) -> PyResult<bool> {
         let runtime = self.inner.lock().unwrap();
         runtime
-            .check_tool_permission(&skill_id, &tool, description, std::collections::HashMap::new())
+            .check_tool_permission_with_argument(&skill_id, &tool, argument.as_deref(), description, std::collections::HashMap::new())
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
@@ -496,36 +704,360 @@ impl OpenSkillRuntimeWrapper {
     ///
     /// This overrides the default host policy.
     /// The resolution algorithm is: deny > allow > skill trust > fallback.
-    #[pyo3(signature = (trust_skill_allowed_tools, fallback, deny=vec![], allow=vec![]))]
+    /// `skill_sandbox` decides SKILL.md `sandbox:` sections (default: "deny").
+    #[pyo3(signature = (trust_skill_allowed_tools, fallback, deny=vec![], allow=vec![], skill_sandbox=None))]
     fn set_host_policy(
         &self,
         trust_skill_allowed_tools: bool,
         fallback: String,
         deny: Vec<String>,
         allow: Vec<String>,
+        skill_sandbox: Option<String>,
     ) -> PyResult<()> {
-        let fallback = match fallback.as_str() {
-            "allow" => Fallback::Allow,
-            "deny" => Fallback::Deny,
-            "prompt" => Fallback::Prompt,
-            _ => {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                    "Invalid fallback: '{}'. Must be 'allow', 'deny', or 'prompt'.",
-                    fallback
-                )))
-            }
-        };
+        let fallback = parse_fallback(&fallback)?;
+        let skill_sandbox = parse_fallback(skill_sandbox.as_deref().unwrap_or("deny"))?;
         let policy = HostPolicy::from_config(PermissionsConfig {
             trust_skill_allowed_tools,
             fallback,
             deny,
             allow,
-        });
+        })
+        .with_skill_sandbox(skill_sandbox);
         let mut runtime = self.inner.lock().unwrap();
         runtime.set_host_policy(policy);
         Ok(())
     }
 
+    /// Answer permission requests with a built-in profile: "strict" denies
+    /// risky tools, "standard" asks only for high risk tools, "permissive"
+    /// also allows them without a permission callback.
+    fn set_permission_profile(&self, profile: String) -> PyResult<()> {
+        let profile = profile
+            .parse::<PermissionProfile>()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_permission_profile(profile);
+        Ok(())
+    }
+
+    /// Enable or disable redaction of host paths in returned outputs.
+    ///
+    /// When enabled, workspace, skill root and home directory paths in
+    /// output/stdout/stderr are replaced with `$SKILL_WORKSPACE`,
+    /// `$SKILL_ROOT` and `~`.
+    fn set_output_redaction(&self, enabled: bool) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_output_redaction(enabled);
+    }
+
+    /// Enable or disable redaction of API keys, tokens and other credentials
+    /// in output/stdout/stderr, before they reach the audit record.
+    ///
+    /// `patterns` are extra regexes to redact besides the built-in key
+    /// formats and entropy detection; a `secret` group limits the redaction
+    /// to that group.
+    #[pyo3(signature = (enabled, patterns=None))]
+    fn set_secret_redaction(&self, enabled: bool, patterns: Option<Vec<String>>) -> PyResult<()> {
+        let mut redactor = None;
+        if enabled {
+            let mut r = SecretRedactor::new();
+            for pattern in patterns.unwrap_or_default() {
+                r = r
+                    .with_pattern(&pattern)
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+            }
+            redactor = Some(r);
+        }
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_secret_redaction(redactor);
+        Ok(())
+    }
+
+    /// Enable or disable ANSI stripping and carriage-return collapsing of
+    /// captured output (enabled by default).
+    fn set_tty_cleanup(&self, enabled: bool) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_tty_cleanup(enabled);
+    }
+
+    /// Enable or disable writing binary stdout to a workspace file
+    /// (enabled by default). Binary stdout is never returned as lossy text.
+    fn set_binary_output_diversion(&self, enabled: bool) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_binary_output_diversion(enabled);
+    }
+
+    /// Enable or disable returning the workspace files each execution
+    /// created or modified in `ExecutionResult.artifacts` (enabled by default).
+    fn set_artifact_collection(&self, enabled: bool) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_artifact_collection(enabled);
+    }
+
+    /// Convert the files every skill produces from `from_type` (an
+    /// extension or MIME type) to `to` by running `run` in `skill_id`, in
+    /// addition to the skills' own `output-converters`.
+    #[pyo3(signature = (from_type, to, skill_id, run, args = None))]
+    fn add_output_converter(
+        &self,
+        from_type: String,
+        to: String,
+        skill_id: String,
+        run: String,
+        args: Option<Vec<String>>,
+    ) {
+        let converter = OutputConverter::new(from_type, to, skill_id, run).with_args(args.unwrap_or_default());
+        let mut runtime = self.inner.lock().unwrap();
+        let mut converters = runtime.output_converters().to_vec();
+        converters.push(converter);
+        runtime.set_output_converters(converters);
+    }
+
+    /// Session ID naming the default workspace (`session-<uuid>` unless set).
+    fn get_session_id(&self) -> String {
+        let runtime = self.inner.lock().unwrap();
+        runtime.get_session_id().to_string()
+    }
+
+    /// Use the host's own session ID, e.g. a conversation ID, so the
+    /// default workspace lines up with it. An empty ID is ignored.
+    fn set_session_id(&self, session_id: String) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_session_id(session_id);
+    }
+
+    /// Configure entry-point auto-detection for skills run without a target.
+    ///
+    /// `precedence` is "wasm_first" (default) or "script_first"; candidate
+    /// lists are patterns relative to the skill root (globs allowed) and
+    /// replace the defaults when given. SKILL.md `entrypoint:` always wins.
+    #[pyo3(signature = (precedence=None, wasm_candidates=None, script_candidates=None))]
+    fn set_entrypoint_config(
+        &self,
+        precedence: Option<String>,
+        wasm_candidates: Option<Vec<String>>,
+        script_candidates: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let precedence = match precedence.as_deref().unwrap_or("wasm_first") {
+            "wasm_first" => EntrypointPrecedence::WasmFirst,
+            "script_first" => EntrypointPrecedence::ScriptFirst,
+            other => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid precedence: '{}'. Must be 'wasm_first' or 'script_first'.",
+                    other
+                )))
+            }
+        };
+        let mut config = EntrypointConfig::default().with_precedence(precedence);
+        if let Some(candidates) = wasm_candidates {
+            config.wasm_candidates = candidates;
+        }
+        if let Some(candidates) = script_candidates {
+            config.script_candidates = candidates;
+        }
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_entrypoint_config(config);
+        Ok(())
+    }
+
+    /// Explain which file `execute_skill` would run for a skill.
+    ///
+    /// Returns `{"selected": path or None, "args": [str, ...], "trace": [str, ...]}`;
+    /// `args` are those declared with `entrypoint:` in SKILL.md.
+    fn explain_entrypoint(&self, py: Python, skill_id: String) -> PyResult<Py<PyAny>> {
+        let runtime = self.inner.lock().unwrap();
+        let resolution = runtime
+            .explain_entrypoint(&skill_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        let out = PyDict::new(py);
+        out.set_item("selected", resolution.selected)?;
+        out.set_item("args", resolution.args)?;
+        out.set_item("trace", resolution.trace)?;
+        Ok(out.into())
+    }
+
+    /// Limit captured stdout/stderr of native scripts to `max_bytes` each
+    /// (unbounded when unset). Larger output is saved in full to a workspace
+    /// file and returned as head + tail with a marker naming the file.
+    #[pyo3(signature = (max_bytes=None))]
+    fn set_max_output_bytes(&self, max_bytes: Option<usize>) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_max_output_bytes(max_bytes);
+    }
+
+    /// Configure retention of runtime-created workspace artifacts (spilled
+    /// output, binary stdout). Unset limits keep everything. With `skill_id`,
+    /// the policy replaces the default for that skill only.
+    #[pyo3(signature = (max_age_ms=None, max_count=None, max_bytes=None, skill_id=None))]
+    fn set_artifact_retention(
+        &self,
+        max_age_ms: Option<u64>,
+        max_count: Option<usize>,
+        max_bytes: Option<u64>,
+        skill_id: Option<String>,
+    ) {
+        let policy = RetentionPolicy {
+            max_age: max_age_ms.map(Duration::from_millis),
+            max_count,
+            max_bytes,
+        };
+        let mut runtime = self.inner.lock().unwrap();
+        match skill_id {
+            Some(id) => runtime.set_skill_artifact_retention(&id, Some(policy)),
+            None => runtime.set_artifact_retention(policy),
+        }
+    }
+
+    /// Remove other session workspaces idle for longer than `max_age_ms`
+    /// (disabled when unset).
+    #[pyo3(signature = (max_age_ms=None))]
+    fn set_session_retention(&self, max_age_ms: Option<u64>) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_session_retention(max_age_ms.map(Duration::from_millis));
+    }
+
+    /// Workspace of a host conversation, created on first use, so later
+    /// turns find the files earlier turns produced.
+    fn workspace_for(&self, conversation_id: String) -> PyResult<String> {
+        let runtime = self.inner.lock().unwrap();
+        runtime
+            .workspace_for(&conversation_id)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Run later executions in a conversation's workspace and return it.
+    fn use_conversation(&self, conversation_id: String) -> PyResult<String> {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime
+            .use_conversation(&conversation_id)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Remove a conversation's workspace. Returns whether it existed.
+    fn remove_conversation_workspace(&self, conversation_id: String) -> PyResult<bool> {
+        let runtime = self.inner.lock().unwrap();
+        runtime
+            .remove_conversation_workspace(&conversation_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Remove conversation workspaces unused for longer than `max_age_ms`
+    /// (disabled when unset).
+    #[pyo3(signature = (max_age_ms=None))]
+    fn set_conversation_retention(&self, max_age_ms: Option<u64>) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_conversation_retention(max_age_ms.map(Duration::from_millis));
+    }
+
+    /// Pin a workspace artifact so retention never removes it.
+    /// Returns the canonical path.
+    fn pin_artifact(&self, path: String) -> PyResult<String> {
+        let runtime = self.inner.lock().unwrap();
+        runtime
+            .pin_artifact(&path)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Unpin a workspace artifact. Returns the canonical path.
+    fn unpin_artifact(&self, path: String) -> PyResult<String> {
+        let runtime = self.inner.lock().unwrap();
+        runtime
+            .unpin_artifact(&path)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+
+    /// Enforce artifact and session retention now.
+    ///
+    /// Returns `{"removed": [...], "removed_sessions": [...], "freed_bytes": n}`.
+    fn gc_artifacts(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let report = {
+            let runtime = self.inner.lock().unwrap();
+            runtime
+                .gc_artifacts()
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+        };
+        let to_strings = |paths: Vec<PathBuf>| -> Vec<String> {
+            paths
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect()
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("removed", to_strings(report.removed))?;
+        dict.set_item("removed_sessions", to_strings(report.removed_sessions))?;
+        dict.set_item("freed_bytes", report.freed_bytes)?;
+        Ok(dict.into())
+    }
+
+    /// Limit the size of a single workspace import (default 100 MiB).
+    fn set_max_import_bytes(&self, max_bytes: u64) {
+        let mut runtime = self.inner.lock().unwrap();
+        runtime.set_max_import_bytes(max_bytes);
+    }
+
+    /// Deduplicate workspace imports through a content-addressed blob store
+    /// shared across sessions (`~/.cache/openskills/blobs/`, or `dir`).
+    #[pyo3(signature = (enable, dir=None))]
+    fn set_blob_store(&self, enable: bool, dir: Option<String>) {
+        let mut runtime = self.inner.lock().unwrap();
+        match dir {
+            Some(dir) if enable => runtime.set_blob_store_dir(Some(PathBuf::from(dir))),
+            _ => runtime.set_blob_store(enable),
+        }
+    }
+
+    /// Bring a user-provided document into the workspace at `dest_rel`.
+    ///
+    /// `source` is a host file path or `bytes`. Returns the audit entry
+    /// (`{"source", "dest", "path", "size", "sha256", "timestamp"}`).
+    fn import_into_workspace(
+        &self,
+        py: Python<'_>,
+        source: &Bound<'_, PyAny>,
+        dest_rel: String,
+    ) -> PyResult<Py<PyAny>> {
+        let entry = {
+            let runtime = self.inner.lock().unwrap();
+            match source.cast::<PyBytes>() {
+                Ok(bytes) => runtime.import_bytes_into_workspace(bytes.as_bytes(), &dest_rel),
+                Err(_) => runtime.import_into_workspace(source.extract::<PathBuf>()?, &dest_rel),
+            }
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+        };
+        Ok(import_to_py(py, &entry)?.into())
+    }
+
+    /// Files imported into the workspace, oldest first.
+    fn get_import_audit(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let entries = self.inner.lock().unwrap().get_import_audit();
+        let list = PyList::empty(py);
+        for entry in &entries {
+            list.append(import_to_py(py, entry)?)?;
+        }
+        Ok(list.into())
+    }
+
+    /// Configure the output post-processing pipeline.
+    ///
+    /// `processors` is an ordered list of names: `"strip-ansi"`, `"redact"`,
+    /// `"json-extract"`, `"truncate:<bytes>"`. With `skill_id`, the pipeline
+    /// replaces the default for that skill only.
+    #[pyo3(signature = (processors, skill_id=None))]
+    fn set_output_pipeline(&self, processors: Vec<String>, skill_id: Option<String>) -> PyResult<()> {
+        let pipeline = OutputPipeline::parse(&processors)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        let mut runtime = self.inner.lock().unwrap();
+        match skill_id {
+            Some(id) => runtime.set_skill_output_pipeline(&id, Some(pipeline)),
+            None => runtime.set_output_pipeline(pipeline),
+        }
+        Ok(())
+    }
+
     /// Read a file from a skill directory.
     ///
     /// This allows agents to read helper files (like `docx-js.md`) that skills
@@ -553,6 +1085,27 @@ impl OpenSkillRuntimeWrapper {
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
     }
 
+    /// List executable artifacts in a skill.
+    ///
+    /// Returns dicts with `path` (relative to the skill root), `kind`
+    /// ("wasm", "python" or "shell") and `sandbox` ("wasm" or "native").
+    fn list_skill_targets(&self, py: Python, skill_id: String) -> PyResult<Py<PyAny>> {
+        let runtime = self.inner.lock().unwrap();
+        let targets = runtime
+            .list_skill_targets(&skill_id)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for t in targets {
+            let item = PyDict::new(py);
+            item.set_item("path", t.path)?;
+            item.set_item("kind", t.kind.to_string())?;
+            item.set_item("sandbox", t.sandbox.to_string())?;
+            list.append(item.as_any())?;
+        }
+        Ok(list.into())
+    }
+
     /// Run a specific target (script/WASM) within a skill.
     ///
     /// This is designed for Claude Skills where SKILL.md instructions tell
@@ -567,7 +1120,7 @@ impl OpenSkillRuntimeWrapper {
         let mut runtime = self.inner.lock().unwrap();
 
         // Parse options from Python dict
-        let (target, timeout_ms, input_val, workspace_dir) = if let Some(opts) = options {
+        let (target, timeout_ms, input_val, workspace_dir, overrides) = if let Some(opts) = options {
             let target_type: Option<String> = opts.get_item("target_type")?
                 .and_then(|v| v.extract().ok());
             
@@ -625,54 +1178,46 @@ impl OpenSkillRuntimeWrapper {
                 .and_then(|v| v.extract::<String>().ok())
                 .map(std::path::PathBuf::from);
 
-            (target, timeout, input_val, workspace_dir)
+            let read_paths: Option<Vec<String>> = opts.get_item("read_paths")?
+                .and_then(|v| v.extract().ok());
+            let write_paths: Option<Vec<String>> = opts.get_item("write_paths")?
+                .and_then(|v| v.extract().ok());
+            let allow_network: bool = opts.get_item("allow_network")?
+                .and_then(|v| v.extract().ok())
+                .unwrap_or(false);
+            let env = opts.get_item("env")?;
+            let env = env.as_ref().and_then(|v| v.cast::<PyDict>().ok());
+            let overrides = sandbox_overrides(read_paths, write_paths, allow_network, env);
+
+            (target, timeout, input_val, workspace_dir, overrides)
         } else {
-            (ExecutionTarget::Auto, None, None, None)
+            (ExecutionTarget::Auto, None, None, None, SandboxOverrides::default())
         };
 
         let result = runtime
-            .run_skill_target(&skill_id, target, timeout_ms, input_val, workspace_dir)
+            .run_skill_target_with_overrides(
+                &skill_id,
+                target,
+                timeout_ms,
+                input_val,
+                workspace_dir,
+                overrides,
+            )
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
-        // Convert Value to JSON string, then parse to Python object
-        let json_str = serde_json::to_string(&result.output)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                "Serialization error: {e}"
-            )))?;
-        let json_module = py.import("json")?;
-        let json_loads = json_module.getattr("loads")?;
-        let output: Py<PyAny> = json_loads.call1((json_str,))?.into();
-
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
-        let audit = PyDict::new(py);
-        audit.set_item("skill_id", result.audit.skill_id)?;
-        audit.set_item("version", result.audit.version)?;
-        audit.set_item("input_hash", result.audit.input_hash)?;
-        audit.set_item("output_hash", result.audit.output_hash)?;
-        audit.set_item("start_time_ms", result.audit.start_time_ms)?;
-        audit.set_item("duration_ms", result.audit.duration_ms)?;
-        audit.set_item("permissions_used", result.audit.permissions_used)?;
-        audit.set_item("exit_status", exit_status)?;
-        audit.set_item("stdout", result.audit.stdout)?;
-        audit.set_item("stderr", result.audit.stderr)?;
-        audit.set_item(
-            "sandbox_mode",
-            result.audit.sandbox_mode.as_audit_str(),
-        )?;
-
-        let response = PyDict::new(py);
-        response.set_item("output", output)?;
-        response.set_item("stdout", result.stdout)?;
-        response.set_item("stderr", result.stderr)?;
-        response.set_item("audit", audit)?;
+        execution_result(py, result)
+    }
 
-        Ok(response.into())
+    /// Run the example named `name` from the skill's `examples:` list, as
+    /// a smoke test that the skill works on this machine.
+    fn run_skill_example(&self, py: Python<'_>, skill_id: String, name: String) -> PyResult<Py<PyAny>> {
+        let result = self
+            .inner
+            .lock()
+            .unwrap()
+            .run_skill_example(&skill_id, &name)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        execution_result(py, result)
     }
 }
 
@@ -691,6 +1236,15 @@ impl SkillExecutionSessionWrapper {
             .map(|id| id.to_string()))
     }
 
+    /// Template files (`workspace-files:`) the session copied into the workspace.
+    fn workspace_files(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let list = PyList::empty(py);
+        for entry in self.inner.lock().unwrap().workspace_files() {
+            list.append(import_to_py(py, entry)?)?;
+        }
+        Ok(list.into())
+    }
+
     fn record_tool_call(&self, py: Python<'_>, tool: String, output: Bound<'_, PyAny>) -> PyResult<()> {
         let json_module = py.import("json")?;
         let json_dumps = json_module.getattr("dumps")?;
@@ -791,28 +1345,39 @@ impl ExecutionContextWrapper {
 ///     command: Shell command to execute
 ///     working_dir: Working directory for the command
 ///     allow_network: Allow network access (default: False)
+///     allowed_hosts: Hosts reachable when network is allowed (subdomains match; default: any)
+///     allowed_ports: TCP ports reachable when network is allowed (default: any)
 ///     allow_process: Allow subprocess spawning (default: False)
+///     allowed_programs: Programs the command may execute (names in PATH or paths; default: any)
 ///     read_paths: List of paths the command can read from
 ///     write_paths: List of paths the command can write to
 ///     env_vars: Dict of environment variables to pass through
 ///     timeout_ms: Timeout in milliseconds (default: 30000)
 ///     sandbox_mode: OS sandbox mode - `"enforce"` (default) or `"disabled"`
+///     backend: Sandbox backend - `"auto"` (default), `"native"` or `"container"`
+///     container_image: Image for the container backend (default: `python:3-slim`)
 ///
 /// Returns:
 ///     Dict with exit_code, stdout, stderr, timed_out
 #[pyfunction]
-#[pyo3(signature = (command, working_dir, *, allow_network = false, allow_process = false, read_paths = None, write_paths = None, env_vars = None, timeout_ms = 30000, sandbox_mode = None))]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (command, working_dir, *, allow_network = false, allowed_hosts = None, allowed_ports = None, allow_process = false, allowed_programs = None, read_paths = None, write_paths = None, env_vars = None, timeout_ms = 30000, sandbox_mode = None, backend = None, container_image = None))]
 fn run_sandboxed_shell_command(
     py: Python<'_>,
     command: String,
     working_dir: String,
     allow_network: bool,
+    allowed_hosts: Option<Vec<String>>,
+    allowed_ports: Option<Vec<u16>>,
     allow_process: bool,
+    allowed_programs: Option<Vec<String>>,
     read_paths: Option<Vec<String>>,
     write_paths: Option<Vec<String>>,
     env_vars: Option<&Bound<'_, PyDict>>,
     timeout_ms: u64,
     sandbox_mode: Option<&str>,
+    backend: Option<&str>,
+    container_image: Option<String>,
 ) -> PyResult<Py<PyAny>> {
     // Convert env_vars from Python dict to Vec<(String, String)>
     let env_vec: Vec<(String, String)> = if let Some(env_dict) = env_vars {
@@ -830,7 +1395,10 @@ fn run_sandboxed_shell_command(
 
     let perms = CommandPermissions {
         allow_network,
+        allowed_hosts: allowed_hosts.unwrap_or_default(),
+        allowed_ports: allowed_ports.unwrap_or_default(),
         allow_process,
+        allowed_programs: allowed_programs.unwrap_or_default(),
         read_paths: read_paths
             .unwrap_or_default()
             .into_iter()
@@ -844,6 +1412,15 @@ fn run_sandboxed_shell_command(
         env_vars: env_vec,
         timeout_ms,
         sandbox_mode: parse_sandbox_mode(sandbox_mode),
+        backend: backend
+            .map(str::parse::<ExecutionBackend>)
+            .transpose()
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?
+            .unwrap_or_default(),
+        container: ContainerConfig {
+            image: container_image.unwrap_or_else(|| ContainerConfig::default().image),
+            ..Default::default()
+        },
     };
 
     let result = run_sandboxed_command(&command, &PathBuf::from(&working_dir), perms)
@@ -858,25 +1435,243 @@ fn run_sandboxed_shell_command(
     Ok(dict.into())
 }
 
+/// Optional runtime features this module was built with, e.g. `"wasm"`.
+#[pyfunction]
+fn features() -> Vec<&'static str> {
+    openskills_runtime::features()
+}
+
 #[pymodule]
 fn openskills(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<OpenSkillRuntimeWrapper>()?;
     m.add_class::<SkillExecutionSessionWrapper>()?;
     m.add_class::<ExecutionContextWrapper>()?;
+    m.add_class::<SkillInfo>()?;
+    m.add_class::<PyLoadedSkill>()?;
+    m.add_class::<PyAuditRecord>()?;
+    m.add_class::<PyExecutionResult>()?;
     m.add_function(wrap_pyfunction!(run_sandboxed_shell_command, m)?)?;
+    m.add_function(wrap_pyfunction!(features, m)?)?;
     Ok(())
 }
 
-fn parse_execution_status(status: Option<String>) -> openskills_runtime::RuntimeExecutionStatus {
-    match status.as_deref() {
-        Some("timeout") => openskills_runtime::RuntimeExecutionStatus::Timeout,
-        Some("permission_denied") => openskills_runtime::RuntimeExecutionStatus::PermissionDenied,
-        Some(s) if s.starts_with("failed:") => {
-            openskills_runtime::RuntimeExecutionStatus::Failed(
-                s.trim_start_matches("failed:").to_string(),
-            )
+fn location_str(location: &SkillLocation) -> &'static str {
+    match location {
+        SkillLocation::Personal => "personal",
+        SkillLocation::Project => "project",
+        SkillLocation::Nested => "nested",
+        SkillLocation::Custom => "custom",
+    }
+}
+
+/// An execution result as a [`PyExecutionResult`].
+fn execution_result(py: Python<'_>, result: ExecutionResult) -> PyResult<Py<PyAny>> {
+    let json_str = serde_json::to_string(&result.output).map_err(|e| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Serialization error: {e}"))
+    })?;
+    let output = py.import("json")?.getattr("loads")?.call1((json_str,))?.unbind();
+    let audit = Py::new(py, PyAuditRecord::new(py, result.audit)?)?;
+    PyExecutionResult {
+        output,
+        stdout: result.stdout,
+        stderr: result.stderr,
+        audit,
+        artifacts: artifacts_to_py(py, &result.artifacts)?.unbind(),
+        capabilities_needed: capabilities_needed_to_py(py, &result.capabilities_needed)?.unbind(),
+    }
+    .into_py_any(py)
+}
+
+/// `obj[key]` for result classes that used to be returned as dicts.
+fn field_item<'py>(
+    obj: &Bound<'py, PyAny>,
+    fields: &[&str],
+    key: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    if fields.contains(&key) {
+        obj.getattr(key)
+    } else {
+        Err(pyo3::exceptions::PyKeyError::new_err(key.to_string()))
+    }
+}
+
+/// `Name(field=value, ...)` with the fields' Python reprs.
+fn fields_repr(obj: &Bound<'_, PyAny>, name: &str, fields: &[&str]) -> PyResult<String> {
+    let mut parts = Vec::with_capacity(fields.len());
+    for field in fields {
+        parts.push(format!("{}={}", field, obj.getattr(*field)?.repr()?));
+    }
+    Ok(format!("{}({})", name, parts.join(", ")))
+}
+
+/// Skill listings as a list of [`SkillInfo`].
+fn skill_infos(py: Python<'_>, skills: Vec<SkillDescriptor>) -> PyResult<Py<PyAny>> {
+    let list = PyList::empty(py);
+    for s in skills {
+        list.append(SkillInfo::from(s))?;
+    }
+    Ok(list.into())
+}
+
+/// Sandbox violations as a list of `{"operation", "path"}` dicts.
+fn violations_to_py<'py>(
+    py: Python<'py>,
+    violations: &[SandboxViolation],
+) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for v in violations {
+        let item = PyDict::new(py);
+        item.set_item("operation", &v.operation)?;
+        item.set_item("path", v.path.as_deref())?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Audited `wasi:http` requests as a list of
+/// `{"method", "url", "host", "port", "allowed"}` dicts.
+fn network_requests_to_py<'py>(
+    py: Python<'py>,
+    requests: &[NetworkRequest],
+) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for r in requests {
+        let item = PyDict::new(py);
+        item.set_item("method", &r.method)?;
+        item.set_item("url", &r.url)?;
+        item.set_item("host", &r.host)?;
+        item.set_item("port", r.port)?;
+        item.set_item("allowed", r.allowed)?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Audited host function calls as a list of
+/// `{"function", "target", "allowed"}` dicts.
+fn host_calls_to_py<'py>(py: Python<'py>, calls: &[HostCall]) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for c in calls {
+        let item = PyDict::new(py);
+        item.set_item("function", &c.function)?;
+        item.set_item("target", &c.target)?;
+        item.set_item("allowed", c.allowed)?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Capability downgrades as a list of `{"tool", "reason", "detail"}` dicts.
+fn downgrades_to_py<'py>(
+    py: Python<'py>,
+    downgrades: &[CapabilityDowngrade],
+) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for d in downgrades {
+        let item = PyDict::new(py);
+        item.set_item("tool", &d.tool)?;
+        item.set_item("reason", d.reason.as_str())?;
+        item.set_item("detail", &d.detail)?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Denied capabilities as a list of `{"tool", "resource"}` dicts.
+fn capabilities_needed_to_py<'py>(
+    py: Python<'py>,
+    needed: &[CapabilityNeeded],
+) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for c in needed {
+        let item = PyDict::new(py);
+        item.set_item("tool", &c.tool)?;
+        item.set_item("resource", &c.resource)?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Produced files as a list of `{"path", "size", "mime", "sha256",
+/// "converted_from"}` dicts.
+fn artifacts_to_py<'py>(py: Python<'py>, artifacts: &[Artifact]) -> PyResult<Bound<'py, PyList>> {
+    let list = PyList::empty(py);
+    for a in artifacts {
+        let item = PyDict::new(py);
+        item.set_item("path", a.path.to_string_lossy().to_string())?;
+        item.set_item("size", a.size)?;
+        item.set_item("mime", &a.mime)?;
+        item.set_item("sha256", &a.sha256)?;
+        item.set_item(
+            "converted_from",
+            a.converted_from.as_ref().map(|p| p.to_string_lossy().to_string()),
+        )?;
+        list.append(item)?;
+    }
+    Ok(list)
+}
+
+/// Workspace import audit entry as a dict.
+fn import_to_py<'py>(py: Python<'py>, entry: &WorkspaceImport) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item(
+        "source",
+        entry.source.as_ref().map(|p| p.to_string_lossy().to_string()),
+    )?;
+    dict.set_item("dest", &entry.dest)?;
+    dict.set_item("path", entry.path.to_string_lossy().to_string())?;
+    dict.set_item("size", entry.size)?;
+    dict.set_item("sha256", &entry.sha256)?;
+    dict.set_item("timestamp", entry.timestamp)?;
+    Ok(dict)
+}
+
+/// Per-execution sandbox grants from keyword arguments / option dict keys.
+fn sandbox_overrides(
+    read_paths: Option<Vec<String>>,
+    write_paths: Option<Vec<String>>,
+    allow_network: bool,
+    env: Option<&Bound<'_, PyDict>>,
+) -> SandboxOverrides {
+    let mut overrides = SandboxOverrides {
+        read_paths: read_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect(),
+        write_paths: write_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect(),
+        allow_network,
+        ..Default::default()
+    };
+    if let Some(env) = env {
+        for (k, v) in env.iter() {
+            if let (Ok(key), Ok(value)) = (k.extract::<String>(), v.extract::<String>()) {
+                overrides.env.insert(key, value);
+            }
         }
-        _ => openskills_runtime::RuntimeExecutionStatus::Success,
+    }
+    overrides
+}
+
+fn parse_fallback(value: &str) -> PyResult<Fallback> {
+    match value {
+        "allow" => Ok(Fallback::Allow),
+        "deny" => Ok(Fallback::Deny),
+        "prompt" => Ok(Fallback::Prompt),
+        _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Invalid fallback: '{}'. Must be 'allow', 'deny', or 'prompt'.",
+            value
+        ))),
+    }
+}
+
+fn parse_execution_status(
+    status: Option<String>,
+) -> PyResult<openskills_runtime::RuntimeExecutionStatus> {
+    match status {
+        None => Ok(openskills_runtime::RuntimeExecutionStatus::Success),
+        Some(status) => openskills_runtime::RuntimeExecutionStatus::parse(&status).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid exit status: '{}'. Expected e.g. 'success' or 'failed:<reason>'.",
+                status
+            ))
+        }),
     }
 }
 
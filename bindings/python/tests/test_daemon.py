@@ -0,0 +1,76 @@
+import os
+import subprocess
+import time
+
+import pytest
+
+from openskills_daemon import DaemonClient, DaemonError
+
+
+def find_daemon():
+    # Built with `cargo build -p openskills-runtime --features daemon`.
+    root = os.path.dirname(os.path.dirname(os.path.dirname(os.path.dirname(os.path.abspath(__file__)))))
+    binary = os.environ.get("OPENSKILLSD_BIN") or os.path.join(root, "target", "debug", "openskillsd")
+    return binary if os.path.exists(binary) else None
+
+
+def connect_with_retry(socket_path):
+    for _ in range(50):
+        try:
+            return DaemonClient.connect(socket_path)
+        except DaemonError:
+            time.sleep(0.1)
+    raise RuntimeError(f"daemon did not start on {socket_path}")
+
+
+def test_daemon_client(tmp_path):
+    binary = find_daemon()
+    if binary is None:
+        pytest.skip("openskillsd not built")
+    skill_dir = tmp_path / "skills" / "alpha"
+    skill_dir.mkdir(parents=True)
+    (skill_dir / "SKILL.md").write_text(
+        "---\nname: alpha\ndescription: Test skill.\n---\nFollow these steps.\n"
+    )
+    socket_path = str(tmp_path / "openskillsd.sock")
+    daemon = subprocess.Popen(
+        [binary, "--socket", socket_path, "--dir", str(tmp_path / "skills"), "--no-standard-locations"],
+        stderr=subprocess.DEVNULL,
+    )
+    try:
+        with connect_with_retry(socket_path) as client:
+            assert client.status()["skills"] == 1
+            assert [s["id"] for s in client.list_skills()] == ["alpha"]
+            assert client.activate_skill("alpha")["instructions"].strip() == "Follow these steps."
+
+            session = client.start_session("alpha", {"doc": "a.pdf"})
+            client.record_tool_call(session["session_id"], "Read", "contents")
+            result = client.finish_session(session["session_id"], {"ok": True}, "done")
+            assert result["output"] == {"ok": True}
+            assert result["audit"]["exit_status"] == "success"
+
+            with pytest.raises(DaemonError):
+                client.activate_skill("missing")
+
+            # Hand a session to a second client, as an IDE would continue a CLI workflow.
+            started = client.start_session("alpha", None, str(tmp_path))
+            client.record_tool_call(started["session_id"], "Read", "contents")
+            client.detach_session(started["session_id"])
+            with connect_with_retry(socket_path) as ide:
+                assert [s["session_id"] for s in ide.list_sessions()] == [started["session_id"]]
+                attached = ide.attach_session(started["session_id"])
+                assert attached["permissions_used"] == ["Read"]
+                assert attached["workspace_dir"] == str(tmp_path)
+                (tmp_path / "report.md").write_text("# Report\n")
+                outputs = ide.list_outputs(session_id=started["session_id"])
+                assert {"path": "report.md", "mime": "text/markdown"}.items() <= outputs[0].items()
+                assert ide.download_output("report.md", session_id=started["session_id"]) == b"# Report\n"
+                with pytest.raises(DaemonError):
+                    client.attach_session(started["session_id"])
+                handed_off = ide.finish_session(started["session_id"])
+                assert handed_off["audit"]["permissions_used"] == ["Read"]
+
+            client.shutdown()
+        daemon.wait(timeout=10)
+    finally:
+        daemon.kill()
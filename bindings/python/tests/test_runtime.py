@@ -70,6 +70,48 @@ def test_execute_skill_placeholder_error():
             "not found", "No executable artifact", "execution"
         ]), f"Unexpected error: {error_msg}"
 
+def test_skill_listing_objects():
+    examples_dir = get_examples_dir()
+    runtime = OpenSkillRuntime.from_directory(examples_dir)
+    skills = runtime.discover_skills()
+
+    skill = next(s for s in skills if s.id == "explaining-code")
+    assert skill.location in ("personal", "project", "nested", "custom")
+    assert skill["description"] == skill.description
+    assert "SkillInfo(id='explaining-code'" in repr(skill)
+    with pytest.raises(KeyError):
+        skill["missing"]
+
+    assert len(runtime) == len(skills)
+    assert [s.id for s in runtime] == [s.id for s in runtime.list_skills()]
+
+def test_typed_results():
+    examples_dir = get_examples_dir()
+    runtime = OpenSkillRuntime.from_directory(examples_dir)
+    runtime.discover_skills()
+
+    skill = runtime.activate_skill("explaining-code")
+    assert skill.id == "explaining-code"
+    assert skill.instructions == skill["instructions"]
+    assert "instructions" in skill and "missing" not in skill
+
+    session = runtime.start_skill_session("explaining-code", None, None)
+    result = runtime.finish_skill_session(session, {"result": "done"}, stdout="out")
+    assert result.output == {"result": "done"}
+    assert result.stdout == "out"
+    assert result.audit.skill_id == "explaining-code"
+    assert result.audit.exit_status == "success"
+    assert result["audit"]["exit_status"] == "success"
+    assert repr(result.audit).startswith("AuditRecord(skill_id='explaining-code'")
+
+def test_runtime_context_manager():
+    with OpenSkillRuntime.from_directory(get_examples_dir()) as runtime:
+        runtime.discover_skills()
+        runtime.import_into_workspace(b"data", "notes.txt")
+        imported = runtime.get_import_audit()[0]["path"]
+        assert os.path.exists(imported)
+    assert not os.path.exists(imported)
+
 def test_runtime_config():
     examples_dir = get_examples_dir()
     runtime = OpenSkillRuntime.with_custom_directories(
@@ -0,0 +1,244 @@
+/**
+ * Client for the `openskillsd` daemon.
+ * @module daemon
+ */
+
+/** Reply to `status()`. */
+export interface DaemonStatus {
+  version: string;
+  /** Cargo features the daemon was built with, e.g. `["wasm", "daemon"]`. */
+  features: string[];
+  pid: number;
+  uptime_ms: number;
+  /** Loaded skills (each ID counts once). */
+  skills: number;
+}
+
+export interface DaemonSkillDescriptor {
+  id: string;
+  version: string;
+  description: string;
+  location: string;
+  user_invocable: boolean;
+  [key: string]: unknown;
+}
+
+export interface DaemonSkillChange {
+  skill_id: string;
+  version: string;
+  kind: 'added' | 'modified' | 'removed';
+  path: string;
+}
+
+export interface DaemonRequires {
+  bins: string[];
+  env: string[];
+}
+
+export interface DaemonLoadedSkill {
+  id: string;
+  version: string;
+  name: string;
+  description: string;
+  allowed_tools: string[];
+  model: string | null;
+  context: string | null;
+  agent: string | null;
+  user_invocable: boolean;
+  location: string;
+  instructions: string;
+  requires: DaemonRequires | null;
+  missing_dependencies: DaemonRequires | null;
+}
+
+export interface DaemonSkillTarget {
+  path: string;
+  kind: string;
+  [key: string]: unknown;
+}
+
+/** Sandbox overrides for one execution. */
+export interface DaemonOverrides {
+  read_paths?: string[];
+  write_paths?: string[];
+  allow_network?: boolean;
+  env?: Record<string, string>;
+}
+
+export interface DaemonExecuteOptions extends DaemonOverrides {
+  timeout_ms?: number;
+  memory_mb?: number;
+  cpu_quota?: number;
+  max_fuel?: number;
+  input?: unknown;
+  /** Resolve the execution without running it; `output` is the plan. */
+  dry_run?: boolean;
+  /** Retry timeouts and failures; each attempt is audited separately. */
+  retry?: DaemonRetryOptions;
+  /** W3C trace context, kept in the audit and passed as `TRACEPARENT`. */
+  traceparent?: string;
+}
+
+export interface DaemonRetryOptions {
+  /** Total attempts, including the first. */
+  max_attempts: number;
+  /** Delay before the first retry, doubled for each further one. */
+  backoff_ms?: number;
+  /** Cap on the delay (default 30000). */
+  max_backoff_ms?: number;
+  /** `timeout`, `failure` and/or `error` (default: timeouts and failures). */
+  retry_on?: string[];
+}
+
+export interface DaemonTargetOptions extends DaemonOverrides {
+  /** `auto` (default), `script` or `wasm`. */
+  target_type?: string;
+  path?: string;
+  args?: string[];
+  timeout_ms?: number;
+  input?: unknown;
+  workspace_dir?: string;
+}
+
+export interface DaemonAuditRecord {
+  skill_id: string;
+  version: string;
+  input_hash: string;
+  output_hash: string;
+  start_time_ms: number;
+  duration_ms: number;
+  permissions_used: string[];
+  /**
+   * `success`, `timeout`, `permission_denied`, `instruction_only`,
+   * `dry_run`, `failed:<reason>`, `sandbox_violation:<reason>` or
+   * `schema_violation:<reason>`.
+   */
+  exit_status: string;
+  stdout: string;
+  stderr: string;
+  sandbox_mode: string;
+  sandbox_violations: unknown[];
+  fuel_consumed: number | null;
+  network_requests: unknown[];
+  host_calls: unknown[];
+  capability_downgrades: unknown[];
+  /** Attempt number (1 unless the execution was retried). */
+  attempt: number;
+  /** Caller's W3C trace context, if one was passed. */
+  traceparent?: string;
+}
+
+export interface DaemonExecutionResult {
+  output: unknown;
+  stdout: string;
+  stderr: string;
+  audit: DaemonAuditRecord;
+  /** For failed executions: tools to ask for before retrying. */
+  capabilities_needed: { tool: string; resource?: string }[];
+}
+
+/** Reply to `startSession()`, `detachSession()`, `attachSession()` and `listSessions()`. */
+export interface DaemonSession {
+  session_id: string;
+  forked: boolean;
+  skill: DaemonLoadedSkill;
+  input: unknown;
+  /** Forked context the session records into. */
+  context_id: string | null;
+  /** Tools recorded so far. */
+  permissions_used: string[];
+  /** Working directory given to `startSession()`. */
+  workspace_dir: string | null;
+  elapsed_ms: number;
+}
+
+/**
+ * Workspace `listOutputs()` and `readOutput()` look in: a session's
+ * `workspace_dir`, a conversation workspace, or with neither the runtime's.
+ */
+export interface DaemonOutputScope {
+  session_id?: string;
+  conversation_id?: string;
+}
+
+/** A file in a workspace, listed by `listOutputs()`. */
+export interface DaemonOutputFile {
+  /** Path relative to the workspace. */
+  path: string;
+  size: number;
+  mime: string;
+  modified_ms: number;
+}
+
+/** Reply to `readOutput()`. */
+export interface DaemonOutputChunk {
+  path: string;
+  mime: string;
+  /** Size of the whole file. */
+  size: number;
+  offset: number;
+  /** The bytes read, base64-encoded. */
+  data: string;
+  /** Whether the chunk reaches the end of the file. */
+  eof: boolean;
+}
+
+/** Error reported by the daemon or the connection to it. */
+export declare class DaemonError extends Error {}
+
+/**
+ * Socket the daemon listens on by default: `$OPENSKILLS_SOCKET`, else
+ * `openskillsd.sock` in `$XDG_RUNTIME_DIR`, else in the OpenSkills cache
+ * directory.
+ */
+export declare function defaultSocketPath(): string;
+
+export declare class DaemonClient {
+  /** Connect to the daemon at `socketPath` (default: `defaultSocketPath()`). */
+  static connect(socketPath?: string): Promise<DaemonClient>;
+  /** Call a daemon method with JSON params and resolve with its result. */
+  call(method: string, params?: object | null): Promise<unknown>;
+  status(): Promise<DaemonStatus>;
+  discoverSkills(): Promise<DaemonSkillDescriptor[]>;
+  reloadSkills(): Promise<DaemonSkillChange[]>;
+  listSkills(): Promise<DaemonSkillDescriptor[]>;
+  activateSkill(skillId: string): Promise<DaemonLoadedSkill>;
+  listSkillTargets(skillId: string): Promise<DaemonSkillTarget[]>;
+  executeSkill(skillId: string, options?: DaemonExecuteOptions): Promise<DaemonExecutionResult>;
+  runSkillTarget(skillId: string, options?: DaemonTargetOptions): Promise<DaemonExecutionResult>;
+  /**
+   * Start an instruction-based session; it lives until finished, detached or
+   * this connection closes. `workspaceDir` is handed to whoever attaches it.
+   */
+  startSession(skillId: string, input?: unknown, workspaceDir?: string | null): Promise<DaemonSession>;
+  /** Hand a session over to the daemon so another process can attach it. */
+  detachSession(sessionId: string): Promise<DaemonSession>;
+  /** Take over a detached session on this connection. */
+  attachSession(sessionId: string): Promise<DaemonSession>;
+  /** Sessions waiting to be attached. */
+  listSessions(): Promise<DaemonSession[]>;
+  recordToolCall(sessionId: string, tool: string, output?: unknown): Promise<null>;
+  finishSession(
+    sessionId: string,
+    output?: unknown,
+    stdout?: string,
+    stderr?: string,
+    /** Audit status such as `success` (the default) or `failed:<reason>`. */
+    exitStatus?: string,
+  ): Promise<DaemonExecutionResult>;
+  /** Files in a workspace, e.g. documents a skill generated for the user. */
+  listOutputs(scope?: DaemonOutputScope): Promise<DaemonOutputFile[]>;
+  /** Read up to `length` bytes (at most 1 MiB) of an output file from `offset`. */
+  readOutput(
+    filePath: string,
+    offset?: number,
+    length?: number | null,
+    scope?: DaemonOutputScope,
+  ): Promise<DaemonOutputChunk>;
+  /** Download a whole output file, one chunk at a time. */
+  downloadOutput(filePath: string, scope?: DaemonOutputScope): Promise<Buffer>;
+  /** Ask the daemon to stop accepting connections and exit. */
+  shutdown(): Promise<null>;
+  /** Close the connection; sessions started on it are dropped. */
+  close(): void;
+}
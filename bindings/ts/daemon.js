@@ -0,0 +1,247 @@
+/**
+ * Client for the `openskillsd` daemon.
+ *
+ * The daemon keeps one runtime (discovered skills, compiled WASM modules,
+ * caches) warm and serves it over a Unix socket, so short-lived agent
+ * processes share it instead of each loading the native runtime. This
+ * client is plain Node.js and does not need the native addon.
+ *
+ * Usage:
+ * ```typescript
+ * import { DaemonClient } from '@finogeek/openskills/daemon';
+ *
+ * const client = await DaemonClient.connect();
+ * const skills = await client.listSkills();
+ * const result = await client.executeSkill('pdf', { input: { file: 'a.pdf' } });
+ * client.close();
+ * ```
+ *
+ * Requests and results use the daemon's JSON (snake_case keys).
+ *
+ * @module daemon
+ */
+
+const net = require('net');
+const os = require('os');
+const path = require('path');
+
+const SOCKET_NAME = 'openskillsd.sock';
+
+/**
+ * Socket the daemon listens on by default: `$OPENSKILLS_SOCKET`, else
+ * `openskillsd.sock` in `$XDG_RUNTIME_DIR`, else in the OpenSkills cache
+ * directory.
+ *
+ * @returns {string}
+ */
+function defaultSocketPath() {
+  if (process.env.OPENSKILLS_SOCKET) {
+    return process.env.OPENSKILLS_SOCKET;
+  }
+  if (process.platform === 'linux' && process.env.XDG_RUNTIME_DIR) {
+    return path.join(process.env.XDG_RUNTIME_DIR, SOCKET_NAME);
+  }
+  const cacheDir =
+    process.platform === 'darwin'
+      ? path.join(os.homedir(), 'Library', 'Caches')
+      : process.env.XDG_CACHE_HOME || path.join(os.homedir(), '.cache');
+  return path.join(cacheDir, 'openskills', SOCKET_NAME);
+}
+
+/** Error reported by the daemon or the connection to it. */
+class DaemonError extends Error {
+  constructor(message) {
+    super(message);
+    this.name = 'DaemonError';
+  }
+}
+
+class DaemonClient {
+  /**
+   * Connect to the daemon at `socketPath` (default: `defaultSocketPath()`).
+   *
+   * @param {string} [socketPath]
+   * @returns {Promise<DaemonClient>}
+   */
+  static connect(socketPath = defaultSocketPath()) {
+    return new Promise((resolve, reject) => {
+      const socket = net.createConnection(socketPath);
+      const onError = (err) => reject(new DaemonError(`cannot connect to ${socketPath}: ${err.message}`));
+      socket.once('error', onError);
+      socket.once('connect', () => {
+        socket.removeListener('error', onError);
+        resolve(new DaemonClient(socket));
+      });
+    });
+  }
+
+  constructor(socket) {
+    this._socket = socket;
+    this._nextId = 0;
+    this._pending = new Map();
+    this._buffer = '';
+    socket.setEncoding('utf8');
+    socket.on('data', (chunk) => this._onData(chunk));
+    socket.on('error', (err) => this._failAll(new DaemonError(err.message)));
+    socket.on('close', () => this._failAll(new DaemonError('daemon closed the connection')));
+  }
+
+  _onData(chunk) {
+    this._buffer += chunk;
+    let newline;
+    while ((newline = this._buffer.indexOf('\n')) >= 0) {
+      const line = this._buffer.slice(0, newline);
+      this._buffer = this._buffer.slice(newline + 1);
+      if (!line.trim()) continue;
+      let response;
+      try {
+        response = JSON.parse(line);
+      } catch (err) {
+        this._failAll(new DaemonError(`invalid response: ${err.message}`));
+        return;
+      }
+      const pending = this._pending.get(response.id);
+      if (!pending) continue;
+      this._pending.delete(response.id);
+      if (response.error !== undefined) {
+        pending.reject(new DaemonError(response.error));
+      } else {
+        pending.resolve(response.result === undefined ? null : response.result);
+      }
+    }
+  }
+
+  _failAll(err) {
+    for (const pending of this._pending.values()) {
+      pending.reject(err);
+    }
+    this._pending.clear();
+  }
+
+  /**
+   * Call a daemon method with JSON params and resolve with its result.
+   *
+   * @param {string} method
+   * @param {object} [params]
+   * @returns {Promise<any>}
+   */
+  call(method, params = null) {
+    const id = ++this._nextId;
+    return new Promise((resolve, reject) => {
+      this._pending.set(id, { resolve, reject });
+      this._socket.write(JSON.stringify({ id, method, params }) + '\n');
+    });
+  }
+
+  status() {
+    return this.call('status');
+  }
+
+  discoverSkills() {
+    return this.call('discover_skills');
+  }
+
+  reloadSkills() {
+    return this.call('reload_skills');
+  }
+
+  listSkills() {
+    return this.call('list_skills');
+  }
+
+  activateSkill(skillId) {
+    return this.call('activate_skill', { skill_id: skillId });
+  }
+
+  listSkillTargets(skillId) {
+    return this.call('list_skill_targets', { skill_id: skillId });
+  }
+
+  executeSkill(skillId, options = {}) {
+    return this.call('execute_skill', { skill_id: skillId, options });
+  }
+
+  runSkillTarget(skillId, options = {}) {
+    return this.call('run_skill_target', { skill_id: skillId, options });
+  }
+
+  /**
+   * Start an instruction-based session; it lives until finished, detached or
+   * this connection closes. `workspaceDir` is handed to whoever attaches it.
+   */
+  startSession(skillId, input = null, workspaceDir = null) {
+    return this.call('start_session', { skill_id: skillId, input, workspace_dir: workspaceDir });
+  }
+
+  /** Hand a session over to the daemon so another process can attach it. */
+  detachSession(sessionId) {
+    return this.call('detach_session', { session_id: sessionId });
+  }
+
+  /** Take over a detached session on this connection. */
+  attachSession(sessionId) {
+    return this.call('attach_session', { session_id: sessionId });
+  }
+
+  /** Sessions waiting to be attached. */
+  listSessions() {
+    return this.call('list_sessions');
+  }
+
+  recordToolCall(sessionId, tool, output = null) {
+    return this.call('record_tool_call', { session_id: sessionId, tool, output });
+  }
+
+  finishSession(sessionId, output = null, stdout = '', stderr = '', exitStatus = 'success') {
+    return this.call('finish_session', {
+      session_id: sessionId,
+      output,
+      stdout,
+      stderr,
+      exit_status: exitStatus,
+    });
+  }
+
+  /**
+   * Files in a workspace: a session's `workspace_dir` (`{ session_id }`), a
+   * conversation's (`{ conversation_id }`) or, by default, the runtime's.
+   */
+  listOutputs(scope = {}) {
+    return this.call('list_outputs', scope);
+  }
+
+  /**
+   * Read up to `length` bytes (at most 1 MiB) of an output file from
+   * `offset`. The chunk's `data` is base64-encoded.
+   */
+  readOutput(filePath, offset = 0, length = null, scope = {}) {
+    return this.call('read_output', { ...scope, path: filePath, offset, length });
+  }
+
+  /** Download a whole output file into a Buffer, one chunk at a time. */
+  async downloadOutput(filePath, scope = {}) {
+    const chunks = [];
+    let offset = 0;
+    for (;;) {
+      const chunk = await this.readOutput(filePath, offset, null, scope);
+      const bytes = Buffer.from(chunk.data, 'base64');
+      chunks.push(bytes);
+      offset += bytes.length;
+      if (chunk.eof) {
+        return Buffer.concat(chunks);
+      }
+    }
+  }
+
+  /** Ask the daemon to stop accepting connections and exit. */
+  shutdown() {
+    return this.call('shutdown');
+  }
+
+  /** Close the connection; sessions started on it are dropped. */
+  close() {
+    this._socket.end();
+  }
+}
+
+module.exports = { DaemonClient, DaemonError, defaultSocketPath };
@@ -5,11 +5,62 @@
 
 export interface SkillDescriptorJs {
   id: string
+  /** Semver version ("0.0.0" when the skill declares none). */
+  version: string
   description: string
   location: string
   userInvocable: boolean
   /** OpenClaw-compatible: "bins, env" summary (e.g. "git, GITHUB_TOKEN"). */
   requiresSummary?: string
+  /** "instruction_only", "script", "wasm" or "hybrid". */
+  skillKind: string
+  /** Project root that supplied the skill (project and nested skills). */
+  projectRoot?: string
+}
+/** Display metadata of a skill (from `getSkillCard`). */
+export interface SkillCardJs {
+  id: string
+  version: string
+  description: string
+  author?: string
+  license?: string
+  /** Homepage, or repository URL without one. */
+  homepage?: string
+  tags: Array<string>
+  location: string
+  /** "instruction_only", "script", "wasm" or "hybrid". */
+  skillKind: string
+  userInvocable: boolean
+  /** Example invocations, from `examples:` or EXAMPLES.md. */
+  examples: Array<SkillCardExampleJs>
+  /** README.md contents (up to 64 KiB). */
+  readme?: string
+}
+/** An example invocation on a skill card. */
+export interface SkillCardExampleJs {
+  /** Name to run the example by (see `runSkillExample`). */
+  name?: string
+  prompt: string
+  /** Example input as JSON, if given. */
+  inputJson?: string
+}
+/** Entry point chosen for a skill (from `explainEntrypoint`). */
+export interface EntrypointResolutionJs {
+  /** Selected file relative to the skill root; unset for instruction-only skills. */
+  selected?: string
+  /** Arguments declared with the SKILL.md entry point (empty otherwise). */
+  args: Array<string>
+  /** Each source and candidate considered, in order. */
+  trace: Array<string>
+}
+/** Executable artifact in a skill (from `listSkillTargets`). */
+export interface SkillTargetJs {
+  /** Path relative to the skill root. */
+  path: string
+  /** "wasm", "python" or "shell". */
+  kind: string
+  /** "wasm" or "native". */
+  sandbox: string
 }
 /** OpenClaw-compatible requires (bins/env) from SKILL.md frontmatter. */
 export interface RequiresJs {
@@ -23,6 +74,7 @@ export interface MissingDependenciesJs {
 }
 export interface LoadedSkillJs {
   id: string
+  version: string
   name: string
   description: string
   allowedTools: Array<string>
@@ -40,7 +92,50 @@ export interface LoadedSkillJs {
 export interface ExecutionOptionsJs {
   timeoutMs?: number
   memoryMb?: number
+  /** CPU cap for native scripts (Linux), as a fraction of one CPU. */
+  cpuQuota?: number
+  /** Fuel budget for WASM skills (roughly one unit per instruction). */
+  maxFuel?: number
   input?: string
+  /** Extra absolute paths the skill may read for this run. */
+  readPaths?: Array<string>
+  /** Extra absolute paths the skill may write for this run. */
+  writePaths?: Array<string>
+  /** Grant network access for this run. */
+  allowNetwork?: boolean
+  /** Environment variables for this run (array of ["KEY", "VALUE"] pairs). */
+  envVars?: Array<Array<string>>
+  /**
+   * Tools granted to the sandbox for this run, replacing the host-policy
+   * resolution (see `resolveEffectiveTools`).
+   */
+  effectiveTools?: Array<string>
+  /**
+   * Resolve the execution without running it: `outputJson` is then the
+   * execution plan and the audit status `dry_run`.
+   */
+  dryRun?: boolean
+  /** Retry timeouts and failures; each attempt is audited separately. */
+  retry?: RetryPolicyJs
+  /**
+   * W3C trace context of the caller's span, kept in the audit and passed
+   * to the skill as `TRACEPARENT`.
+   */
+  traceparent?: string
+}
+/** Retry policy for `ExecutionOptionsJs.retry`. */
+export interface RetryPolicyJs {
+  /** Total attempts, including the first. */
+  maxAttempts: number
+  /** Delay before the first retry, doubled for each further one. */
+  backoffMs?: number
+  /** Cap on the delay (default 30000). */
+  maxBackoffMs?: number
+  /**
+   * Outcomes to retry: `timeout`, `failure`, `error` (default: timeouts
+   * and failures).
+   */
+  retryOn?: Array<string>
 }
 /** Options for targeted skill execution. */
 export interface TargetExecutionOptionsJs {
@@ -56,13 +151,27 @@ export interface TargetExecutionOptionsJs {
   input?: string
   /** Workspace directory for script output (overrides runtime default) */
   workspaceDir?: string
+  /** Extra absolute paths the skill may read for this run. */
+  readPaths?: Array<string>
+  /** Extra absolute paths the skill may write for this run. */
+  writePaths?: Array<string>
+  /** Grant network access for this run. */
+  allowNetwork?: boolean
+  /** Environment variables for this run (array of ["KEY", "VALUE"] pairs). */
+  envVars?: Array<Array<string>>
 }
 /** Permissions for sandboxed command execution. */
 export interface CommandPermissionsJs {
   /** Allow network access. */
   allowNetwork?: boolean
+  /** Hosts reachable when network is allowed (subdomains match; empty = any). */
+  allowedHosts?: Array<string>
+  /** TCP ports reachable when network is allowed (empty = any). */
+  allowedPorts?: Array<number>
   /** Allow subprocess spawning. */
   allowProcess?: boolean
+  /** Programs the command may execute: names in `PATH` or paths (empty = any). */
+  allowedPrograms?: Array<string>
   /** Directories the command can read from. */
   readPaths?: Array<string>
   /** Directories the command can write to. */
@@ -73,6 +182,10 @@ export interface CommandPermissionsJs {
   timeoutMs?: number
   /** OS sandbox mode: `"enforce"` (default) or `"disabled"`. */
   sandboxMode?: string
+  /** Sandbox backend: `"auto"` (default), `"native"` or `"container"`. */
+  backend?: string
+  /** Image for the container backend (default: `python:3-slim`). */
+  containerImage?: string
 }
 /** Result from sandboxed command execution. */
 export interface CommandResultJs {
@@ -85,6 +198,62 @@ export interface CommandResultJs {
   /** Whether the command timed out. */
   timedOut: boolean
 }
+/** Result of an artifact retention pass. */
+export interface GcReportJs {
+  /** Artifact files removed. */
+  removed: Array<string>
+  /** Stale session workspaces removed. */
+  removedSessions: Array<string>
+  /** Bytes freed by removed artifacts. */
+  freedBytes: number
+}
+/** A file imported into the workspace. */
+export interface WorkspaceImportJs {
+  /** Host file the content came from; unset for in-memory bytes. */
+  source?: string
+  /** Destination as requested, relative to the workspace. */
+  dest: string
+  /** Absolute destination path. */
+  path: string
+  size: number
+  /** SHA-256 of the imported content. */
+  sha256: string
+  timestamp: number
+}
+/**
+ * Options for `new OpenSkillRuntimeWrapper(options)`, mirroring the Rust
+ * `RuntimeConfig` plus the builder options hosts set up front.
+ */
+export interface RuntimeOptionsJs {
+  /** Skill directories to scan besides (or instead of) the standard locations. */
+  customDirectories?: Array<string>
+  /** Scan `~/.claude/skills/`, `.claude/skills/` and nested ones (default: true). */
+  useStandardLocations?: boolean
+  /** Project root for relative path resolution. */
+  projectRoot?: string
+  /**
+   * Workspace directory for skill I/O (default: per-session directory
+   * under `~/.cache/openskills/workspace/`).
+   */
+  workspaceDir?: string
+  /**
+   * Trust no skill-declared tools and deny everything not allowed by the
+   * host policy (see `setStrictPermissions`).
+   */
+  strictPermissions?: boolean
+  /** Called with the audit record of every execution. */
+  onAudit?: (record: AuditRecord) => void
+  /** Python interpreter for native Python scripts (e.g. a venv binary). */
+  pythonInterpreter?: string
+  /** Let native Python scripts see user-site and venv packages. */
+  pythonAllowUserSite?: boolean
+  /** OS sandbox mode for native scripts: `"enforce"` (default) or `"disabled"`. */
+  sandboxMode?: string
+  /** Sandbox backend for native scripts: `"auto"` (default), `"native"` or `"container"`. */
+  backend?: string
+  /** Image for the container backend (default: `python:3-slim`). */
+  containerImage?: string
+}
 export interface AuditRecord {
   skillId: string
   version: string
@@ -100,13 +269,87 @@ export interface AuditRecord {
   stderr: string
   /** Effective OS sandbox mode (`enforce` or `disabled`). */
   sandboxMode: string
+  /** Operations the OS sandbox blocked, recovered from stderr (best effort). */
+  sandboxViolations: Array<SandboxViolationJs>
+  /** Fuel a WASM execution consumed, as decimal string (full u64 range). */
+  fuelConsumed?: string
+  /** Outbound `wasi:http` requests of a WASM execution, sent or denied. */
+  networkRequests: Array<NetworkRequestJs>
+  /** Host functions a WASM skill called, allowed or refused. */
+  hostCalls: Array<HostCallJs>
+  /** Requested tools the sandbox did not (fully) grant. */
+  capabilityDowngrades: Array<CapabilityDowngradeJs>
+  /** Attempt number (1 unless the execution was retried). */
+  attempt: number
+  /** Caller's W3C trace context, if one was passed. */
+  traceparent?: string
+}
+/** An operation the OS sandbox blocked. */
+export interface SandboxViolationJs {
+  /**
+   * Seatbelt operation (e.g. "file-write-create") or "file-read",
+   * "file-write", "file-access", "network", "syscall".
+   */
+  operation: string
+  path?: string
+}
+/** An outbound HTTP request made by a WASM skill. */
+export interface NetworkRequestJs {
+  method: string
+  url: string
+  host: string
+  port: number
+  /** Whether the host allowlist let the request through. */
+  allowed: boolean
+}
+/** A host function call made by a WASM skill. */
+export interface HostCallJs {
+  /** "read-workspace-file", "write-workspace-file", "log" or "http-fetch". */
+  function: string
+  /** Workspace path, URL or log level. */
+  target: string
+  allowed: boolean
+}
+/** A requested tool the sandbox did not (fully) grant. */
+export interface CapabilityDowngradeJs {
+  tool: string
+  /** "policy_denied", "permission_denied", "unmapped", "platform" or "workspace_only". */
+  reason: string
+  detail: string
+}
+/** A capability a failed execution was denied; ask for `tool` and retry. */
+export interface CapabilityNeededJs {
+  tool: string
+  /** Path, host or URL the execution tried to reach, when known. */
+  resource?: string
+}
+/** A workspace file an execution created or modified. */
+export interface ArtifactJs {
+  /** Absolute path of the file. */
+  path: string
+  size: number
+  /** MIME type guessed from the extension or content. */
+  mime: string
+  /** SHA-256 of the file content. */
+  sha256: string
+  /** The artifact this file was converted from, for converter outputs. */
+  convertedFrom?: string
 }
 export interface ExecutionResult {
   outputJson: string
   stdout: string
   stderr: string
   audit: AuditRecord
+  /** Workspace files the execution created or modified. */
+  artifacts: Array<ArtifactJs>
+  /**
+   * For failed executions: tools to ask the user for before retrying,
+   * from what the sandbox denied.
+   */
+  capabilitiesNeeded: Array<CapabilityNeededJs>
 }
+/** Optional runtime features this binding was built with, e.g. `wasm`. */
+export declare function features(): Array<string>
 /**
  * Run a shell command in a sandboxed environment (macOS only).
  *
@@ -117,6 +360,8 @@ export declare function runSandboxedShellCommand(command: string, workingDir: st
 export declare class SkillExecutionSessionWrapper {
   isForked(): boolean
   contextId(): string | null
+  /** Template files (`workspace-files:`) the session copied into the workspace. */
+  workspaceFiles(): Array<WorkspaceImportJs>
   recordToolCall(tool: string, outputJson: string): void
   recordResult(outputJson: string): void
   recordStdout(stdout: string): void
@@ -134,38 +379,206 @@ export declare class ExecutionContextWrapper {
   summarize(): string
 }
 export declare class OpenSkillRuntimeWrapper {
-  constructor()
+  constructor(options?: RuntimeOptionsJs | undefined | null)
   static withProjectRoot(projectRoot: string): OpenSkillRuntimeWrapper
   static fromDirectory(skillsDir: string): OpenSkillRuntimeWrapper
   /** Create runtime with custom directories and configuration */
   static withCustomDirectories(customDirectories: Array<string>, useStandardLocations?: boolean | undefined | null, projectRoot?: string | undefined | null): OpenSkillRuntimeWrapper
+  /**
+   * Set the workspace directory for skill I/O; `null` restores the
+   * per-session default.
+   */
+  setWorkspaceDir(dir?: string | undefined | null): void
+  /** Enable or disable standard locations for later `discoverSkills` calls. */
+  setStandardLocations(enable: boolean): void
+  /** Add a skill directory for later `discoverSkills` calls. */
+  addCustomDirectory(dir: string): void
+  /**
+   * Add a project root whose `.claude/skills` and nested skills later
+   * `discoverSkills` calls also load.
+   */
+  addProjectRoot(root: string): void
+  /**
+   * Switch to strict permissions: skill-declared tools are not trusted
+   * and everything the host policy does not allow is denied.
+   */
+  setStrictPermissions(): void
+  /**
+   * Answer permission requests with a built-in profile: "strict" denies
+   * risky tools, "standard" asks only for high risk tools, "permissive"
+   * also allows them without a permission callback.
+   */
+  setPermissionProfile(profile: 'strict' | 'standard' | 'permissive'): void
+  /**
+   * Call `callback` with the audit record of every execution; `null`
+   * stops delivery.
+   */
+  setAuditCallback(callback: ((record: AuditRecord) => void) | null): void
   /** Discover skills from standard locations (~/.claude/skills/, .claude/skills/, nested) */
   discoverSkills(): Array<SkillDescriptorJs>
   /** Load skills from a specific directory (additive - can be called multiple times) */
   loadFromDirectory(dir: string): Array<SkillDescriptorJs>
   /** List skills (progressive disclosure - descriptors only) */
   listSkills(): Array<SkillDescriptorJs>
+  /**
+   * Display metadata of a skill for skill pickers: description, author,
+   * version, tags, example invocations and README.
+   */
+  getSkillCard(skillId: string): SkillCardJs
   /** Get a complete skill-agnostic system prompt for agents. */
   getAgentSystemPrompt(): string
   /** Activate a skill (load full SKILL.md content) */
   activateSkill(skillId: string): LoadedSkillJs
   /** Execute a skill's WASM module */
   executeSkill(skillId: string, options?: ExecutionOptionsJs | undefined | null): ExecutionResult
+  /**
+   * Like `executeSkill`, but runs on the libuv thread pool and returns a
+   * Promise, so long script runs do not block the event loop. The runtime
+   * is only locked while the skill is resolved and audited, so other calls
+   * on it can be made while the skill runs.
+   */
+  executeSkillAsync(skillId: string, options?: ExecutionOptionsJs | undefined | null): Promise<ExecutionResult>
   /** Start an instruction-based skill session (for context: fork behavior). */
   startSkillSession(skillId: string, inputJson?: string | undefined | null, parentContext?: ExecutionContextWrapper | undefined | null): SkillExecutionSessionWrapper
   /** Finish a skill session and return an ExecutionResult. */
   finishSkillSession(session: SkillExecutionSessionWrapper, outputJson: string, stdout: string, stderr: string, exitStatus?: string | undefined | null): ExecutionResult
   /** Check if a tool is allowed for a skill */
   isToolAllowed(skillId: string, tool: string): boolean
-  /** Check if a tool call is permitted for a skill (ask-before-act for risky tools). */
-  checkToolPermission(skillId: string, tool: string, description?: string | undefined | null): boolean
+  /**
+   * Tools an execution of the skill would grant to the sandbox, resolved
+   * through the host policy.
+   */
+  resolveEffectiveTools(skillId: string): Array<string>
+  /**
+   * Check if a tool call is permitted for a skill (ask-before-act for risky tools).
+   * `argument` is the call's command or path, matched against patterns
+   * such as `Bash(npm run test:*)` in `allowed-tools`.
+   */
+  checkToolPermission(skillId: string, tool: string, description?: string | undefined | null, argument?: string | undefined | null): boolean
   /**
    * Set the host policy programmatically.
    *
    * This overrides the default host policy.
    * The resolution algorithm is: deny > allow > skill trust > fallback.
+   * `skillSandbox` decides SKILL.md `sandbox:` sections (default: "deny").
+   */
+  setHostPolicy(trustSkillAllowedTools: boolean, fallback: string, deny: Array<string>, allow: Array<string>, skillSandbox?: string | undefined | null): void
+  /**
+   * Enable or disable redaction of host paths in returned outputs.
+   *
+   * When enabled, workspace, skill root and home directory paths in
+   * output/stdout/stderr are replaced with `$SKILL_WORKSPACE`,
+   * `$SKILL_ROOT` and `~`.
+   */
+  setOutputRedaction(enabled: boolean): void
+  /**
+   * Enable or disable redaction of API keys, tokens and other credentials
+   * in output/stdout/stderr, before they reach the audit record.
+   *
+   * `patterns` are extra regexes to redact besides the built-in key
+   * formats and entropy detection; a `secret` group limits the redaction
+   * to that group.
+   */
+  setSecretRedaction(enabled: boolean, patterns?: Array<string> | undefined | null): void
+  /**
+   * Enable or disable ANSI stripping and carriage-return collapsing of
+   * captured output (enabled by default).
+   */
+  setTtyCleanup(enabled: boolean): void
+  /**
+   * Enable or disable writing binary stdout to a workspace file
+   * (enabled by default). Binary stdout is never returned as lossy text.
+   */
+  setBinaryOutputDiversion(enabled: boolean): void
+  /**
+   * Enable or disable returning the workspace files each execution
+   * created or modified in `artifacts` (enabled by default).
+   */
+  setArtifactCollection(enabled: boolean): void
+  /**
+   * Convert the files every skill produces from `fromType` (an extension
+   * or MIME type) to `to` by running `run` in `skillId`, in addition to
+   * the skills' own `output-converters`.
+   */
+  addOutputConverter(fromType: string, to: string, skillId: string, run: string, args?: Array<string> | undefined | null): void
+  /** Session ID naming the default workspace (`session-<uuid>` unless set). */
+  getSessionId(): string
+  /**
+   * Use the host's own session ID, e.g. a conversation ID, so the
+   * default workspace lines up with it. An empty ID is ignored.
+   */
+  setSessionId(sessionId: string): void
+  /**
+   * Configure entry-point auto-detection for skills run without a target.
+   *
+   * `precedence` is "wasm_first" (default) or "script_first"; candidate
+   * lists are patterns relative to the skill root (globs allowed) and
+   * replace the defaults when given. SKILL.md `entrypoint:` always wins.
+   */
+  setEntrypointConfig(precedence?: string | undefined | null, wasmCandidates?: Array<string> | undefined | null, scriptCandidates?: Array<string> | undefined | null): void
+  /** Explain which file `executeSkill` would run for a skill. */
+  explainEntrypoint(skillId: string): EntrypointResolutionJs
+  /**
+   * Limit captured stdout/stderr of native scripts to `max_bytes` each
+   * (unbounded when unset). Larger output is saved in full to a workspace
+   * file and returned as head + tail with a marker naming the file.
+   */
+  setMaxOutputBytes(maxBytes?: number | undefined | null): void
+  /**
+   * Configure retention of runtime-created workspace artifacts (spilled
+   * output, binary stdout). Unset limits keep everything. With `skill_id`,
+   * the policy replaces the default for that skill only.
+   */
+  setArtifactRetention(maxAgeMs?: number | undefined | null, maxCount?: number | undefined | null, maxBytes?: number | undefined | null, skillId?: string | undefined | null): void
+  /**
+   * Remove other session workspaces idle for longer than `max_age_ms`
+   * (disabled when unset).
+   */
+  setSessionRetention(maxAgeMs?: number | undefined | null): void
+  /**
+   * Workspace of a host conversation, created on first use, so later
+   * turns find the files earlier turns produced.
    */
-  setHostPolicy(trustSkillAllowedTools: boolean, fallback: string, deny: Array<string>, allow: Array<string>): void
+  workspaceFor(conversationId: string): string
+  /** Run later executions in a conversation's workspace and return it. */
+  useConversation(conversationId: string): string
+  /** Remove a conversation's workspace. Returns whether it existed. */
+  removeConversationWorkspace(conversationId: string): boolean
+  /**
+   * Remove conversation workspaces unused for longer than `max_age_ms`
+   * (disabled when unset).
+   */
+  setConversationRetention(maxAgeMs?: number | undefined | null): void
+  /**
+   * Pin a workspace artifact so retention never removes it.
+   * Returns the canonical path.
+   */
+  pinArtifact(path: string): string
+  /** Unpin a workspace artifact. Returns the canonical path. */
+  unpinArtifact(path: string): string
+  /** Enforce artifact and session retention now. */
+  gcArtifacts(): GcReportJs
+  /** Limit the size of a single workspace import (default 100 MiB). */
+  setMaxImportBytes(maxBytes: number): void
+  /**
+   * Deduplicate workspace imports through a content-addressed blob store
+   * shared across sessions (`~/.cache/openskills/blobs/`, or `dir`).
+   */
+  setBlobStore(enable: boolean, dir?: string | undefined | null): void
+  /** Copy a user-provided host file into the workspace at `dest_rel`. */
+  importIntoWorkspace(hostPath: string, destRel: string): WorkspaceImportJs
+  /** Write in-memory content into the workspace at `dest_rel`. */
+  importBytesIntoWorkspace(data: Buffer, destRel: string): WorkspaceImportJs
+  /** Files imported into the workspace, oldest first. */
+  getImportAudit(): Array<WorkspaceImportJs>
+  /**
+   * Configure the output post-processing pipeline.
+   *
+   * `processors` is an ordered list of names: `"strip-ansi"`, `"redact"`,
+   * `"json-extract"`, `"truncate:<bytes>"`. With `skill_id`, the pipeline
+   * replaces the default for that skill only.
+   */
+  setOutputPipeline(processors: Array<string>, skillId?: string | undefined | null): void
   /**
    * Set the permission mode for interactive prompting.
    *
@@ -182,6 +595,16 @@ export declare class OpenSkillRuntimeWrapper {
    * the agent which script to run (e.g., "run python ooxml/scripts/unpack.py").
    */
   runSkillTarget(skillId: string, options?: TargetExecutionOptionsJs | undefined | null): ExecutionResult
+  /**
+   * Run the example named `name` from the skill's `examples:` list, as
+   * a smoke test that the skill works on this machine.
+   */
+  runSkillExample(skillId: string, name: string): ExecutionResult
+  /**
+   * Like `runSkillTarget`, but runs on the libuv thread pool and returns
+   * a Promise (see `executeSkillAsync`).
+   */
+  runSkillTargetAsync(skillId: string, options?: TargetExecutionOptionsJs | undefined | null): Promise<ExecutionResult>
   /**
    * Read a file from a skill directory.
    *
@@ -195,6 +618,8 @@ export declare class OpenSkillRuntimeWrapper {
    * Returns relative paths from the skill root.
    */
   listSkillFiles(skillId: string, subdir?: string | undefined | null, recursive?: boolean | undefined | null): Array<string>
+  /** List executable artifacts (WASM modules, Python/shell scripts) in a skill. */
+  listSkillTargets(skillId: string): Array<SkillTargetJs>
   /** Get the root directory path for a skill. */
   getSkillRoot(skillId: string): string
 }
@@ -310,10 +310,11 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { SkillExecutionSessionWrapper, ExecutionContextWrapper, OpenSkillRuntimeWrapper, runSandboxedShellCommand } = nativeBinding
+const { SkillExecutionSessionWrapper, ExecutionContextWrapper, OpenSkillRuntimeWrapper, features, runSandboxedShellCommand } = nativeBinding
 
 module.exports.SkillExecutionSessionWrapper = SkillExecutionSessionWrapper
 module.exports.ExecutionContextWrapper = ExecutionContextWrapper
 module.exports.OpenSkillRuntimeWrapper = OpenSkillRuntimeWrapper
 module.exports.OpenSkillRuntime = OpenSkillRuntimeWrapper
+module.exports.features = features
 module.exports.runSandboxedShellCommand = runSandboxedShellCommand
@@ -1,22 +1,84 @@
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::JsFunction;
 use napi_derive::napi;
 use openskills_runtime::{
-    CliPermissionCallback, CommandPermissions, DenyAllCallback, ExecutionContext, ExecutionOptions,
-    ExecutionTarget, Fallback, HostPolicy, OpenSkillRuntime, OutputType, PermissionCallback,
-    PermissionsConfig, RuntimeConfig, RuntimeExecutionStatus, SandboxMode,
-    SkillExecutionSession, SkillLocation, run_sandboxed_command,
+    Artifact, AuditSink, CapabilityDowngrade, CapabilityNeeded, CliPermissionCallback, CommandPermissions, ContainerConfig, DenyAllCallback, EntrypointConfig, EntrypointPrecedence, ExecutionBackend, ExecutionContext, ExecutionOptions, RetentionPolicy, RetryPolicy,
+    ExecutionTarget, Fallback, HostCall, HostPolicy, NetworkRequest, OpenSkillRuntime, OutputConverter, OutputPipeline, OutputType, PermissionCallback,
+    NativeRunnerConfig, PermissionProfile, PermissionsConfig, RuntimeAuditRecord, RuntimeConfig, RuntimeExecutionStatus, SandboxMode, SandboxOverrides, SandboxViolation, SecretRedactor,
+    SkillExecutionSession, SkillLocation, WorkspaceImport, run_sandboxed_command, ExecutionStep, PendingExecution,
 };
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 #[napi(object)]
 pub struct SkillDescriptorJs {
     pub id: String,
+    /// Semver version ("0.0.0" when the skill declares none).
+    pub version: String,
     pub description: String,
     pub location: String,
     pub user_invocable: bool,
     /// OpenClaw-compatible: "bins, env" summary (e.g. "git, GITHUB_TOKEN").
     pub requires_summary: Option<String>,
+    /// "instruction_only", "script", "wasm" or "hybrid".
+    pub skill_kind: String,
+    /// Project root that supplied the skill (project and nested skills).
+    pub project_root: Option<String>,
+}
+
+/// Display metadata of a skill (from `getSkillCard`).
+#[napi(object)]
+pub struct SkillCardJs {
+    pub id: String,
+    pub version: String,
+    pub description: String,
+    pub author: Option<String>,
+    pub license: Option<String>,
+    /// Homepage, or repository URL without one.
+    pub homepage: Option<String>,
+    pub tags: Vec<String>,
+    pub location: String,
+    /// "instruction_only", "script", "wasm" or "hybrid".
+    pub skill_kind: String,
+    pub user_invocable: bool,
+    /// Example invocations, from `examples:` or EXAMPLES.md.
+    pub examples: Vec<SkillCardExampleJs>,
+    /// README.md contents (up to 64 KiB).
+    pub readme: Option<String>,
+}
+
+/// An example invocation on a skill card.
+#[napi(object)]
+pub struct SkillCardExampleJs {
+    /// Name to run the example by (see `runSkillExample`).
+    pub name: Option<String>,
+    pub prompt: String,
+    /// Example input as JSON, if given.
+    pub input_json: Option<String>,
+}
+
+/// Entry point chosen for a skill (from `explainEntrypoint`).
+#[napi(object)]
+pub struct EntrypointResolutionJs {
+    /// Selected file relative to the skill root; unset for instruction-only skills.
+    pub selected: Option<String>,
+    /// Arguments declared with the SKILL.md entry point (empty otherwise).
+    pub args: Vec<String>,
+    /// Each source and candidate considered, in order.
+    pub trace: Vec<String>,
+}
+
+/// Executable artifact in a skill (from `listSkillTargets`).
+#[napi(object)]
+pub struct SkillTargetJs {
+    /// Path relative to the skill root.
+    pub path: String,
+    /// "wasm", "python" or "shell".
+    pub kind: String,
+    /// "wasm" or "native".
+    pub sandbox: String,
 }
 
 /// Action descriptor (capability/action model).
@@ -46,6 +108,7 @@ pub struct MissingDependenciesJs {
 #[napi(object)]
 pub struct LoadedSkillJs {
     pub id: String,
+    pub version: String,
     pub name: String,
     pub description: String,
     pub allowed_tools: Vec<String>,
@@ -61,13 +124,146 @@ pub struct LoadedSkillJs {
     pub missing_dependencies: Option<MissingDependenciesJs>,
 }
 
+/// Options for `new OpenSkillRuntimeWrapper(options)`, mirroring the Rust
+/// `RuntimeConfig` plus the builder options hosts set up front.
+#[napi(object, object_to_js = false)]
+pub struct RuntimeOptionsJs {
+    /// Skill directories to scan besides (or instead of) the standard locations.
+    pub custom_directories: Option<Vec<String>>,
+    /// Scan `~/.claude/skills/`, `.claude/skills/` and nested ones (default: true).
+    pub use_standard_locations: Option<bool>,
+    /// Project root for relative path resolution.
+    pub project_root: Option<String>,
+    /// Workspace directory for skill I/O (default: per-session directory
+    /// under `~/.cache/openskills/workspace/`).
+    pub workspace_dir: Option<String>,
+    /// Trust no skill-declared tools and deny everything not allowed by the
+    /// host policy (see `setStrictPermissions`).
+    pub strict_permissions: Option<bool>,
+    /// Called with the audit record of every execution.
+    #[napi(ts_type = "(record: AuditRecord) => void")]
+    pub on_audit: Option<JsFunction>,
+    /// Python interpreter for native Python scripts (e.g. a venv binary).
+    pub python_interpreter: Option<String>,
+    /// Let native Python scripts see user-site and venv packages.
+    pub python_allow_user_site: Option<bool>,
+    /// OS sandbox mode for native scripts: `"enforce"` (default) or `"disabled"`.
+    pub sandbox_mode: Option<String>,
+    /// Sandbox backend for native scripts: `"auto"` (default), `"native"` or `"container"`.
+    pub backend: Option<String>,
+    /// Image for the container backend (default: `python:3-slim`).
+    pub container_image: Option<String>,
+}
+
+/// Forwards audit records to a JS callback. Records are delivered on the
+/// JS thread once the call that produced them has returned.
+struct JsAuditSink {
+    callback: ThreadsafeFunction<AuditRecord, ErrorStrategy::Fatal>,
+}
+
+impl JsAuditSink {
+    fn new(env: &Env, callback: JsFunction) -> Result<Self> {
+        let mut callback: ThreadsafeFunction<AuditRecord, ErrorStrategy::Fatal> =
+            callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        // Do not keep the process alive just for audit delivery.
+        callback.unref(env)?;
+        Ok(Self { callback })
+    }
+}
+
+impl AuditSink for JsAuditSink {
+    fn record(&self, record: &RuntimeAuditRecord) {
+        self.callback
+            .call(record.clone().into(), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+}
+
+/// Discards audit records (after `setAuditCallback(null)`).
+struct NoAuditSink;
+
+impl AuditSink for NoAuditSink {
+    fn record(&self, _record: &RuntimeAuditRecord) {}
+}
+
+/// Native runner config from runtime options, if any of its fields is set.
+fn native_runner_config(options: &RuntimeOptionsJs) -> Result<Option<NativeRunnerConfig>> {
+    if options.python_interpreter.is_none()
+        && options.python_allow_user_site.is_none()
+        && options.sandbox_mode.is_none()
+        && options.backend.is_none()
+        && options.container_image.is_none()
+    {
+        return Ok(None);
+    }
+    Ok(Some(NativeRunnerConfig {
+        python_interpreter: options.python_interpreter.as_ref().map(PathBuf::from),
+        python_allow_user_site: options.python_allow_user_site.unwrap_or(false),
+        sandbox_mode: parse_sandbox_mode(options.sandbox_mode.as_deref()),
+        backend: options
+            .backend
+            .as_deref()
+            .map(str::parse::<ExecutionBackend>)
+            .transpose()
+            .map_err(|e| Error::from_reason(e.to_string()))?
+            .unwrap_or_default(),
+        container: ContainerConfig {
+            image: options
+                .container_image
+                .clone()
+                .unwrap_or_else(|| ContainerConfig::default().image),
+            ..Default::default()
+        },
+        ..Default::default()
+    }))
+}
+
 #[napi(object)]
 pub struct ExecutionOptionsJs {
     #[napi(ts_type = "number")]
     pub timeout_ms: Option<i64>,
     #[napi(ts_type = "number")]
     pub memory_mb: Option<i64>,
+    /// CPU cap for native scripts (Linux), as a fraction of one CPU.
+    pub cpu_quota: Option<f64>,
+    /// Fuel budget for WASM skills (roughly one unit per instruction).
+    #[napi(ts_type = "number")]
+    pub max_fuel: Option<i64>,
     pub input: Option<String>, // JSON string
+    /// Extra absolute paths the skill may read for this run.
+    pub read_paths: Option<Vec<String>>,
+    /// Extra absolute paths the skill may write for this run.
+    pub write_paths: Option<Vec<String>>,
+    /// Grant network access for this run.
+    pub allow_network: Option<bool>,
+    /// Environment variables for this run (array of ["KEY", "VALUE"] pairs).
+    pub env_vars: Option<Vec<Vec<String>>>,
+    /// Tools granted to the sandbox for this run, replacing the host-policy
+    /// resolution (see `resolveEffectiveTools`).
+    pub effective_tools: Option<Vec<String>>,
+    /// Resolve the execution without running it: `outputJson` is then the
+    /// execution plan and the audit status `dry_run`.
+    pub dry_run: Option<bool>,
+    /// Retry timeouts and failures; each attempt is audited separately.
+    pub retry: Option<RetryPolicyJs>,
+    /// W3C trace context of the caller's span, kept in the audit and passed
+    /// to the skill as `TRACEPARENT`.
+    pub traceparent: Option<String>,
+}
+
+/// Retry policy for `ExecutionOptionsJs.retry`.
+#[napi(object)]
+pub struct RetryPolicyJs {
+    /// Total attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further one.
+    #[napi(ts_type = "number")]
+    pub backoff_ms: Option<i64>,
+    /// Cap on the delay (default 30000).
+    #[napi(ts_type = "number")]
+    pub max_backoff_ms: Option<i64>,
+    /// Outcomes to retry: `timeout`, `failure`, `error` (default: timeouts
+    /// and failures).
+    pub retry_on: Option<Vec<String>>,
 }
 
 /// Options for targeted skill execution.
@@ -86,15 +282,30 @@ pub struct TargetExecutionOptionsJs {
     pub input: Option<String>,
     /// Workspace directory for script output (overrides runtime default)
     pub workspace_dir: Option<String>,
+    /// Extra absolute paths the skill may read for this run.
+    pub read_paths: Option<Vec<String>>,
+    /// Extra absolute paths the skill may write for this run.
+    pub write_paths: Option<Vec<String>>,
+    /// Grant network access for this run.
+    pub allow_network: Option<bool>,
+    /// Environment variables for this run (array of ["KEY", "VALUE"] pairs).
+    pub env_vars: Option<Vec<Vec<String>>>,
 }
 
 /// Permissions for sandboxed command execution.
 #[napi(object)]
+#[derive(Default)]
 pub struct CommandPermissionsJs {
     /// Allow network access.
     pub allow_network: Option<bool>,
+    /// Hosts reachable when network is allowed (subdomains match; empty = any).
+    pub allowed_hosts: Option<Vec<String>>,
+    /// TCP ports reachable when network is allowed (empty = any).
+    pub allowed_ports: Option<Vec<u32>>,
     /// Allow subprocess spawning.
     pub allow_process: Option<bool>,
+    /// Programs the command may execute: names in `PATH` or paths (empty = any).
+    pub allowed_programs: Option<Vec<String>>,
     /// Directories the command can read from.
     pub read_paths: Option<Vec<String>>,
     /// Directories the command can write to.
@@ -106,6 +317,10 @@ pub struct CommandPermissionsJs {
     pub timeout_ms: Option<i64>,
     /// OS sandbox mode: `"enforce"` (default) or `"disabled"`.
     pub sandbox_mode: Option<String>,
+    /// Sandbox backend: `"auto"` (default), `"native"` or `"container"`.
+    pub backend: Option<String>,
+    /// Image for the container backend (default: `python:3-slim`).
+    pub container_image: Option<String>,
 }
 
 /// Result from sandboxed command execution.
@@ -121,6 +336,45 @@ pub struct CommandResultJs {
     pub timed_out: bool,
 }
 
+/// Result of an artifact retention pass.
+#[napi(object)]
+pub struct GcReportJs {
+    /// Artifact files removed.
+    pub removed: Vec<String>,
+    /// Stale session workspaces removed.
+    pub removed_sessions: Vec<String>,
+    /// Bytes freed by removed artifacts.
+    pub freed_bytes: i64,
+}
+
+/// A file imported into the workspace.
+#[napi(object)]
+pub struct WorkspaceImportJs {
+    /// Host file the content came from; unset for in-memory bytes.
+    pub source: Option<String>,
+    /// Destination as requested, relative to the workspace.
+    pub dest: String,
+    /// Absolute destination path.
+    pub path: String,
+    pub size: i64,
+    /// SHA-256 of the imported content.
+    pub sha256: String,
+    pub timestamp: i64,
+}
+
+impl From<WorkspaceImport> for WorkspaceImportJs {
+    fn from(entry: WorkspaceImport) -> Self {
+        Self {
+            source: entry.source.map(|p| p.to_string_lossy().to_string()),
+            dest: entry.dest,
+            path: entry.path.to_string_lossy().to_string(),
+            size: i64::try_from(entry.size).unwrap_or(i64::MAX),
+            sha256: entry.sha256,
+            timestamp: i64::try_from(entry.timestamp).unwrap_or(i64::MAX),
+        }
+    }
+}
+
 #[napi(object)]
 pub struct AuditRecord {
     pub skill_id: String,
@@ -139,6 +393,206 @@ pub struct AuditRecord {
     pub stderr: String,
     /// Effective OS sandbox mode (`enforce` or `disabled`).
     pub sandbox_mode: String,
+    /// Operations the OS sandbox blocked, recovered from stderr (best effort).
+    pub sandbox_violations: Vec<SandboxViolationJs>,
+    /// Fuel a WASM execution consumed, as decimal string (full u64 range).
+    #[napi(ts_type = "string")]
+    pub fuel_consumed: Option<String>,
+    /// Outbound `wasi:http` requests of a WASM execution, sent or denied.
+    pub network_requests: Vec<NetworkRequestJs>,
+    /// Host functions a WASM skill called, allowed or refused.
+    pub host_calls: Vec<HostCallJs>,
+    /// Requested tools the sandbox did not (fully) grant.
+    pub capability_downgrades: Vec<CapabilityDowngradeJs>,
+    /// Attempt number (1 unless the execution was retried).
+    pub attempt: u32,
+    /// Caller's W3C trace context, if one was passed.
+    pub traceparent: Option<String>,
+}
+
+impl From<RuntimeAuditRecord> for AuditRecord {
+    fn from(audit: RuntimeAuditRecord) -> Self {
+        let exit_status = match audit.exit_status {
+            RuntimeExecutionStatus::Success => "success".to_string(),
+            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
+            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
+            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
+            RuntimeExecutionStatus::SandboxViolation(msg) => format!("sandbox_violation:{}", msg),
+            RuntimeExecutionStatus::SchemaViolation(msg) => format!("schema_violation:{}", msg),
+            RuntimeExecutionStatus::InstructionOnly => "instruction_only".to_string(),
+            RuntimeExecutionStatus::DryRun => "dry_run".to_string(),
+        };
+        Self {
+            skill_id: audit.skill_id,
+            version: audit.version,
+            input_hash: audit.input_hash,
+            output_hash: audit.output_hash,
+            start_time_ms: u64_ms_to_audit_string(audit.start_time_ms),
+            duration_ms: u64_ms_to_audit_string(audit.duration_ms),
+            permissions_used: audit.permissions_used,
+            exit_status,
+            stdout: audit.stdout,
+            stderr: audit.stderr,
+            sandbox_mode: audit.sandbox_mode.as_audit_str().to_string(),
+            sandbox_violations: violations_to_js(audit.sandbox_violations),
+            fuel_consumed: audit.fuel_consumed.map(|f| f.to_string()),
+            network_requests: network_requests_to_js(audit.network_requests),
+            host_calls: host_calls_to_js(audit.host_calls),
+            capability_downgrades: downgrades_to_js(audit.capability_downgrades),
+            attempt: audit.attempt,
+            traceparent: audit.traceparent,
+        }
+    }
+}
+
+/// An operation the OS sandbox blocked.
+#[napi(object)]
+pub struct SandboxViolationJs {
+    /// Seatbelt operation (e.g. "file-write-create") or "file-read",
+    /// "file-write", "file-access", "network", "syscall".
+    pub operation: String,
+    pub path: Option<String>,
+}
+
+fn violations_to_js(violations: Vec<SandboxViolation>) -> Vec<SandboxViolationJs> {
+    violations
+        .into_iter()
+        .map(|v| SandboxViolationJs {
+            operation: v.operation,
+            path: v.path,
+        })
+        .collect()
+}
+
+/// An outbound HTTP request made by a WASM skill.
+#[napi(object)]
+pub struct NetworkRequestJs {
+    pub method: String,
+    pub url: String,
+    pub host: String,
+    pub port: u32,
+    /// Whether the host allowlist let the request through.
+    pub allowed: bool,
+}
+
+fn network_requests_to_js(requests: Vec<NetworkRequest>) -> Vec<NetworkRequestJs> {
+    requests
+        .into_iter()
+        .map(|r| NetworkRequestJs {
+            method: r.method,
+            url: r.url,
+            host: r.host,
+            port: u32::from(r.port),
+            allowed: r.allowed,
+        })
+        .collect()
+}
+
+/// A host function call made by a WASM skill.
+#[napi(object)]
+pub struct HostCallJs {
+    /// "read-workspace-file", "write-workspace-file", "log" or "http-fetch".
+    pub function: String,
+    /// Workspace path, URL or log level.
+    pub target: String,
+    pub allowed: bool,
+}
+
+fn host_calls_to_js(calls: Vec<HostCall>) -> Vec<HostCallJs> {
+    calls
+        .into_iter()
+        .map(|c| HostCallJs {
+            function: c.function,
+            target: c.target,
+            allowed: c.allowed,
+        })
+        .collect()
+}
+
+/// A requested tool the sandbox did not (fully) grant.
+#[napi(object)]
+pub struct CapabilityDowngradeJs {
+    pub tool: String,
+    /// "policy_denied", "permission_denied", "unmapped" or "platform".
+    pub reason: String,
+    pub detail: String,
+}
+
+/// A capability a failed execution was denied; ask for `tool` and retry.
+#[napi(object)]
+pub struct CapabilityNeededJs {
+    pub tool: String,
+    /// Path, host or URL the execution tried to reach, when known.
+    pub resource: Option<String>,
+}
+
+fn capabilities_needed_to_js(needed: Vec<CapabilityNeeded>) -> Vec<CapabilityNeededJs> {
+    needed
+        .into_iter()
+        .map(|c| CapabilityNeededJs {
+            tool: c.tool,
+            resource: c.resource,
+        })
+        .collect()
+}
+
+fn downgrades_to_js(downgrades: Vec<CapabilityDowngrade>) -> Vec<CapabilityDowngradeJs> {
+    downgrades
+        .into_iter()
+        .map(|d| CapabilityDowngradeJs {
+            tool: d.tool,
+            reason: d.reason.as_str().to_string(),
+            detail: d.detail,
+        })
+        .collect()
+}
+
+/// Per-execution sandbox grants from execution options.
+fn sandbox_overrides(
+    read_paths: Option<Vec<String>>,
+    write_paths: Option<Vec<String>>,
+    allow_network: Option<bool>,
+    env_vars: Option<Vec<Vec<String>>>,
+) -> SandboxOverrides {
+    SandboxOverrides {
+        read_paths: read_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect(),
+        write_paths: write_paths.unwrap_or_default().into_iter().map(PathBuf::from).collect(),
+        allow_network: allow_network.unwrap_or(false),
+        env: env_vars
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|pair| match pair.as_slice() {
+                [key, value, ..] => Some((key.clone(), value.clone())),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// A workspace file an execution created or modified.
+#[napi(object)]
+pub struct ArtifactJs {
+    /// Absolute path of the file.
+    pub path: String,
+    pub size: i64,
+    /// MIME type guessed from the extension or content.
+    pub mime: String,
+    /// SHA-256 of the file content.
+    pub sha256: String,
+    /// The artifact this file was converted from, for converter outputs.
+    pub converted_from: Option<String>,
+}
+
+impl From<Artifact> for ArtifactJs {
+    fn from(artifact: Artifact) -> Self {
+        Self {
+            path: artifact.path.to_string_lossy().to_string(),
+            size: i64::try_from(artifact.size).unwrap_or(i64::MAX),
+            mime: artifact.mime,
+            sha256: artifact.sha256,
+            converted_from: artifact.converted_from.map(|p| p.to_string_lossy().to_string()),
+        }
+    }
 }
 
 #[napi(object)]
@@ -147,6 +601,11 @@ pub struct ExecutionResult {
     pub stdout: String,
     pub stderr: String,
     pub audit: AuditRecord,
+    /// Workspace files the execution created or modified.
+    pub artifacts: Vec<ArtifactJs>,
+    /// For failed executions: tools to ask the user for before retrying,
+    /// from what the sandbox denied.
+    pub capabilities_needed: Vec<CapabilityNeededJs>,
 }
 
 // Helper functions used in impl blocks - must be defined before use
@@ -163,16 +622,29 @@ fn parse_output_type(value: &str) -> Result<OutputType> {
     }
 }
 
-fn parse_execution_status(status: Option<String>) -> openskills_runtime::RuntimeExecutionStatus {
-    match status.as_deref() {
-        Some("timeout") => openskills_runtime::RuntimeExecutionStatus::Timeout,
-        Some("permission_denied") => openskills_runtime::RuntimeExecutionStatus::PermissionDenied,
-        Some(s) if s.starts_with("failed:") => {
-            openskills_runtime::RuntimeExecutionStatus::Failed(
-                s.trim_start_matches("failed:").to_string(),
-            )
-        }
-        _ => openskills_runtime::RuntimeExecutionStatus::Success,
+fn parse_fallback(value: &str) -> Result<Fallback> {
+    match value {
+        "allow" => Ok(Fallback::Allow),
+        "deny" => Ok(Fallback::Deny),
+        "prompt" => Ok(Fallback::Prompt),
+        _ => Err(Error::from_reason(format!(
+            "Invalid fallback: '{}'. Must be 'allow', 'deny', or 'prompt'.",
+            value
+        ))),
+    }
+}
+
+fn parse_execution_status(
+    status: Option<String>,
+) -> Result<openskills_runtime::RuntimeExecutionStatus> {
+    match status {
+        None => Ok(openskills_runtime::RuntimeExecutionStatus::Success),
+        Some(status) => openskills_runtime::RuntimeExecutionStatus::parse(&status).ok_or_else(|| {
+            Error::from_reason(format!(
+                "Invalid exit status: '{}'. Expected e.g. 'success' or 'failed:<reason>'.",
+                status
+            ))
+        }),
     }
 }
 
@@ -205,6 +677,216 @@ fn parse_sandbox_mode(mode: Option<&str>) -> SandboxMode {
     }
 }
 
+fn execution_options(options: Option<ExecutionOptionsJs>) -> Result<ExecutionOptions> {
+    let Some(opts) = options else {
+        return Ok(ExecutionOptions::default());
+    };
+    let retry = opts
+        .retry
+        .map(|retry| {
+            RetryPolicy::from_millis(
+                retry.max_attempts,
+                safe_timeout_ms(retry.backoff_ms).unwrap_or(0),
+                safe_timeout_ms(retry.max_backoff_ms),
+                &retry.retry_on.unwrap_or_default(),
+            )
+        })
+        .transpose()
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(ExecutionOptions {
+        timeout_ms: safe_timeout_ms(opts.timeout_ms),
+        memory_mb: opts.memory_mb.map(|m| if m < 0 { 0 } else { m as u64 }),
+        cpu_quota: opts.cpu_quota.filter(|q| *q > 0.0),
+        max_fuel: opts.max_fuel.and_then(|f| u64::try_from(f).ok()),
+        input: opts.input.and_then(|s| serde_json::from_str(&s).ok()),
+        sandbox_overrides: sandbox_overrides(
+            opts.read_paths,
+            opts.write_paths,
+            opts.allow_network,
+            opts.env_vars,
+        ),
+        effective_tools: opts.effective_tools,
+        dry_run: opts.dry_run.unwrap_or(false),
+        retry,
+        traceparent: opts.traceparent,
+    })
+}
+
+fn execution_result(result: openskills_runtime::ExecutionResult) -> Result<ExecutionResult> {
+    let output_json =
+        serde_json::to_string(&result.output).map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(ExecutionResult {
+        output_json,
+        stdout: result.stdout,
+        stderr: result.stderr,
+        audit: result.audit.into(),
+        artifacts: result.artifacts.into_iter().map(ArtifactJs::from).collect(),
+        capabilities_needed: capabilities_needed_to_js(result.capabilities_needed),
+    })
+}
+
+/// A `runSkillTarget` call with its options converted.
+struct TargetRequest {
+    target: ExecutionTarget,
+    timeout_ms: Option<u64>,
+    input: Option<serde_json::Value>,
+    workspace_dir: Option<PathBuf>,
+    overrides: SandboxOverrides,
+}
+
+impl TargetRequest {
+    fn from_options(options: Option<TargetExecutionOptionsJs>) -> Result<Self> {
+        let Some(opts) = options else {
+            return Ok(Self {
+                target: ExecutionTarget::Auto,
+                timeout_ms: None,
+                input: None,
+                workspace_dir: None,
+                overrides: SandboxOverrides::default(),
+            });
+        };
+        let target = match opts.target_type.as_deref() {
+            Some("script") => {
+                let path = opts.path.ok_or_else(|| {
+                    Error::from_reason("path is required for script target".to_string())
+                })?;
+                ExecutionTarget::Script {
+                    path,
+                    args: opts.args.unwrap_or_default(),
+                }
+            }
+            Some("wasm") => {
+                let path = opts.path.ok_or_else(|| {
+                    Error::from_reason("path is required for wasm target".to_string())
+                })?;
+                ExecutionTarget::Wasm { path }
+            }
+            Some("auto") | None => {
+                // Auto-detect from path extension if path is provided
+                // Uses ExecutionTarget::Path for transparent WASM vs native sandbox selection
+                if let Some(path) = opts.path {
+                    ExecutionTarget::Path {
+                        path,
+                        args: opts.args.unwrap_or_default(),
+                    }
+                } else {
+                    ExecutionTarget::Auto
+                }
+            }
+            _ => ExecutionTarget::Auto,
+        };
+        Ok(Self {
+            target,
+            timeout_ms: safe_timeout_ms(opts.timeout_ms),
+            input: opts.input.and_then(|s| serde_json::from_str(&s).ok()),
+            workspace_dir: opts.workspace_dir.map(PathBuf::from),
+            overrides: sandbox_overrides(
+                opts.read_paths,
+                opts.write_paths,
+                opts.allow_network,
+                opts.env_vars,
+            ),
+        })
+    }
+
+    fn run(
+        self,
+        runtime: &mut OpenSkillRuntime,
+        skill_id: &str,
+    ) -> std::result::Result<openskills_runtime::ExecutionResult, openskills_runtime::RuntimeError> {
+        runtime.run_skill_target_with_overrides(
+            skill_id,
+            self.target,
+            self.timeout_ms,
+            self.input,
+            self.workspace_dir,
+            self.overrides,
+        )
+    }
+
+    fn begin(
+        self,
+        runtime: &mut OpenSkillRuntime,
+        skill_id: &str,
+    ) -> std::result::Result<PendingExecution, openskills_runtime::RuntimeError> {
+        runtime.begin_target_execution(
+            skill_id,
+            self.target,
+            self.timeout_ms,
+            self.input,
+            self.workspace_dir,
+            self.overrides,
+        )
+    }
+}
+
+enum ExecuteJob {
+    Skill {
+        skill_id: String,
+        options: ExecutionOptions,
+    },
+    Target {
+        skill_id: String,
+        request: TargetRequest,
+    },
+}
+
+/// Lock the runtime, or a session or context wrapping its state, failing
+/// instead of panicking across the N-API boundary if a panic poisoned it.
+fn lock_runtime<T>(state: &Mutex<T>) -> Result<MutexGuard<'_, T>> {
+    state
+        .lock()
+        .map_err(|_| Error::from_reason("runtime is unusable after a panic in another call".to_string()))
+}
+
+/// An execution run on the libuv thread pool by `executeSkillAsync` and
+/// `runSkillTargetAsync`.
+pub struct ExecuteTask {
+    runtime: Arc<Mutex<OpenSkillRuntime>>,
+    job: Option<ExecuteJob>,
+}
+
+impl Task for ExecuteTask {
+    type Output = ExecutionResult;
+    type JsValue = ExecutionResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let job = self
+            .job
+            .take()
+            .ok_or_else(|| Error::from_reason("execution already ran".to_string()))?;
+        // Only resolving and auditing hold the lock; the skill runs without
+        // it, so calls on the runtime meanwhile do not block the event loop.
+        let begun = match job {
+            ExecuteJob::Skill { skill_id, options } => {
+                lock_runtime(&self.runtime)?.begin_execution(&skill_id, options)
+            }
+            ExecuteJob::Target { skill_id, request } => {
+                request.begin(&mut *lock_runtime(&self.runtime)?, &skill_id)
+            }
+        };
+        let mut pending = begun.map_err(|e| Error::from_reason(e.to_string()))?;
+        loop {
+            pending.run();
+            let step = lock_runtime(&self.runtime)?.finish_pending_execution(pending);
+            match step {
+                ExecutionStep::Finished(result) => {
+                    return execution_result(result.map_err(|e| Error::from_reason(e.to_string()))?);
+                }
+                ExecutionStep::Retry { delay, next } => {
+                    std::thread::sleep(delay);
+                    let begun = lock_runtime(&self.runtime)?.begin_next_attempt(next);
+                    pending = begun.map_err(|e| Error::from_reason(e.to_string()))?;
+                }
+            }
+        }
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
 // Define all #[napi] structs before their impl blocks (required for NAPI macro expansion)
 // Each impl block MUST immediately follow its struct definition for NAPI-RS macro expansion
 #[napi]
@@ -215,24 +897,33 @@ pub struct SkillExecutionSessionWrapper {
 #[napi]
 impl SkillExecutionSessionWrapper {
     #[napi]
-    pub fn is_forked(&self) -> bool {
-        self.inner.lock().unwrap().is_forked()
+    pub fn is_forked(&self) -> Result<bool> {
+        Ok(lock_runtime(&self.inner)?.is_forked())
     }
 
     #[napi]
-    pub fn context_id(&self) -> Option<String> {
-        self.inner
-            .lock()
-            .unwrap()
+    pub fn context_id(&self) -> Result<Option<String>> {
+        Ok(lock_runtime(&self.inner)?
             .context_id()
-            .map(|id| id.to_string())
+            .map(|id| id.to_string()))
+    }
+
+    /// Template files (`workspace-files:`) the session copied into the workspace.
+    #[napi]
+    pub fn workspace_files(&self) -> Result<Vec<WorkspaceImportJs>> {
+        Ok(lock_runtime(&self.inner)?
+            .workspace_files()
+            .iter()
+            .cloned()
+            .map(WorkspaceImportJs::from)
+            .collect())
     }
 
     #[napi]
     pub fn record_tool_call(&self, tool: String, output_json: String) -> Result<()> {
         let output: serde_json::Value = serde_json::from_str(&output_json)
             .unwrap_or_else(|_| serde_json::json!({ "output": output_json }));
-        self.inner.lock().unwrap().record_tool_call(&tool, &output);
+        lock_runtime(&self.inner)?.record_tool_call(&tool, &output);
         Ok(())
     }
 
@@ -240,23 +931,25 @@ impl SkillExecutionSessionWrapper {
     pub fn record_result(&self, output_json: String) -> Result<()> {
         let output: serde_json::Value = serde_json::from_str(&output_json)
             .unwrap_or_else(|_| serde_json::json!({ "output": output_json }));
-        self.inner.lock().unwrap().record_result(&output);
+        lock_runtime(&self.inner)?.record_result(&output);
         Ok(())
     }
 
     #[napi]
-    pub fn record_stdout(&self, stdout: String) {
-        self.inner.lock().unwrap().record_stdout_if_present(&stdout);
+    pub fn record_stdout(&self, stdout: String) -> Result<()> {
+        lock_runtime(&self.inner)?.record_stdout_if_present(&stdout);
+        Ok(())
     }
 
     #[napi]
-    pub fn record_stderr(&self, stderr: String) {
-        self.inner.lock().unwrap().record_stderr_if_present(&stderr);
+    pub fn record_stderr(&self, stderr: String) -> Result<()> {
+        lock_runtime(&self.inner)?.record_stderr_if_present(&stderr);
+        Ok(())
     }
 
     #[napi]
-    pub fn summarize(&self) -> String {
-        self.inner.lock().unwrap().summarize_fork()
+    pub fn summarize(&self) -> Result<String> {
+        Ok(lock_runtime(&self.inner)?.summarize_fork())
     }
 }
 
@@ -275,82 +968,107 @@ impl ExecutionContextWrapper {
     }
 
     #[napi]
-    pub fn fork(&self) -> ExecutionContextWrapper {
-        let forked = self.inner.lock().unwrap().fork();
-        ExecutionContextWrapper {
+    pub fn fork(&self) -> Result<ExecutionContextWrapper> {
+        let forked = lock_runtime(&self.inner)?.fork();
+        Ok(ExecutionContextWrapper {
             inner: Mutex::new(forked),
-        }
+        })
     }
 
     #[napi]
-    pub fn id(&self) -> String {
-        self.inner.lock().unwrap().id().to_string()
+    pub fn id(&self) -> Result<String> {
+        Ok(lock_runtime(&self.inner)?.id().to_string())
     }
 
     #[napi]
-    pub fn is_forked(&self) -> bool {
-        self.inner.lock().unwrap().is_forked()
+    pub fn is_forked(&self) -> Result<bool> {
+        Ok(lock_runtime(&self.inner)?.is_forked())
     }
 
     #[napi]
-    pub fn parent_id(&self) -> Option<String> {
-        self.inner
-            .lock()
-            .unwrap()
+    pub fn parent_id(&self) -> Result<Option<String>> {
+        Ok(lock_runtime(&self.inner)?
             .parent_id()
-            .map(|id| id.to_string())
+            .map(|id| id.to_string()))
     }
 
     #[napi]
-    pub fn summary(&self) -> Option<String> {
-        self.inner
-            .lock()
-            .unwrap()
+    pub fn summary(&self) -> Result<Option<String>> {
+        Ok(lock_runtime(&self.inner)?
             .summary()
-            .map(|s| s.to_string())
+            .map(|s| s.to_string()))
     }
 
     #[napi]
     pub fn record_output(&self, output_type: String, content: String) -> Result<()> {
         let output_type = parse_output_type(&output_type)?;
-        self.inner
-            .lock()
-            .unwrap()
+        lock_runtime(&self.inner)?
             .record_output(output_type, content);
         Ok(())
     }
 
     #[napi]
-    pub fn summarize(&self) -> String {
-        self.inner.lock().unwrap().summarize()
+    pub fn summarize(&self) -> Result<String> {
+        Ok(lock_runtime(&self.inner)?.summarize())
+    }
+}
+
+impl Default for ExecutionContextWrapper {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[napi]
 pub struct OpenSkillRuntimeWrapper {
-    inner: Mutex<OpenSkillRuntime>,
+    /// Shared with `ExecuteTask`s running on the libuv thread pool.
+    inner: Arc<Mutex<OpenSkillRuntime>>,
 }
 
 #[napi]
 impl OpenSkillRuntimeWrapper {
+    /// Create a runtime; without options it uses the standard locations.
     #[napi(constructor)]
-    pub fn new() -> Self {
-        Self {
-            inner: Mutex::new(OpenSkillRuntime::new()),
+    pub fn new(env: Env, options: Option<RuntimeOptionsJs>) -> Result<Self> {
+        let Some(options) = options else {
+            return Ok(Self::default());
+        };
+        let config = RuntimeConfig {
+            custom_directories: options
+                .custom_directories
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            use_standard_locations: options.use_standard_locations.unwrap_or(true),
+            project_root: options.project_root.clone().map(PathBuf::from),
+            workspace_dir: options.workspace_dir.clone().map(PathBuf::from),
+            native_runner_config: native_runner_config(&options)?,
+        };
+        let mut runtime = OpenSkillRuntime::from_config(config);
+        if options.strict_permissions.unwrap_or(false) {
+            runtime.set_strict_permissions();
+        }
+        if let Some(callback) = options.on_audit {
+            runtime.set_audit_sink(Box::new(JsAuditSink::new(&env, callback)?));
         }
+        Ok(Self {
+            inner: Arc::new(Mutex::new(runtime)),
+        })
     }
 
     #[napi(factory)]
     pub fn with_project_root(project_root: String) -> Self {
         Self {
-            inner: Mutex::new(OpenSkillRuntime::with_project_root(project_root)),
+            inner: Arc::new(Mutex::new(OpenSkillRuntime::with_project_root(project_root))),
         }
     }
 
     #[napi(factory)]
     pub fn from_directory(skills_dir: String) -> Self {
         Self {
-            inner: Mutex::new(OpenSkillRuntime::from_directory(skills_dir)),
+            inner: Arc::new(Mutex::new(OpenSkillRuntime::from_directory(skills_dir))),
         }
     }
 
@@ -372,15 +1090,76 @@ impl OpenSkillRuntimeWrapper {
             native_runner_config: None,
         };
         Self {
-            inner: Mutex::new(OpenSkillRuntime::from_config(config)),
+            inner: Arc::new(Mutex::new(OpenSkillRuntime::from_config(config))),
         }
     }
 
+    /// Set the workspace directory for skill I/O; `null` restores the
+    /// per-session default.
+    #[napi]
+    pub fn set_workspace_dir(&self, dir: Option<String>) -> Result<()> {
+        lock_runtime(&self.inner)?.set_workspace_dir(dir.map(PathBuf::from));
+        Ok(())
+    }
+
+    /// Enable or disable standard locations for later `discoverSkills` calls.
+    #[napi]
+    pub fn set_standard_locations(&self, enable: bool) -> Result<()> {
+        lock_runtime(&self.inner)?.set_standard_locations(enable);
+        Ok(())
+    }
+
+    /// Add a skill directory for later `discoverSkills` calls.
+    #[napi]
+    pub fn add_custom_directory(&self, dir: String) -> Result<()> {
+        lock_runtime(&self.inner)?.add_custom_directory(dir);
+        Ok(())
+    }
+
+    /// Add a project root whose `.claude/skills` and nested skills later
+    /// `discoverSkills` calls also load.
+    #[napi]
+    pub fn add_project_root(&self, root: String) -> Result<()> {
+        lock_runtime(&self.inner)?.add_project_root(root);
+        Ok(())
+    }
+
+    /// Switch to strict permissions: skill-declared tools are not trusted
+    /// and everything the host policy does not allow is denied.
+    #[napi]
+    pub fn set_strict_permissions(&self) -> Result<()> {
+        lock_runtime(&self.inner)?.set_strict_permissions();
+        Ok(())
+    }
+
+    /// Answer permission requests with a built-in profile: "strict" denies
+    /// risky tools, "standard" asks only for high risk tools, "permissive"
+    /// also allows them without a permission callback.
+    #[napi(ts_args_type = "profile: 'strict' | 'standard' | 'permissive'")]
+    pub fn set_permission_profile(&self, profile: String) -> Result<()> {
+        let profile = profile
+            .parse::<PermissionProfile>()
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        lock_runtime(&self.inner)?.set_permission_profile(profile);
+        Ok(())
+    }
+
+    /// Call `callback` with the audit record of every execution; `null`
+    /// stops delivery.
+    #[napi(ts_args_type = "callback: ((record: AuditRecord) => void) | null")]
+    pub fn set_audit_callback(&self, env: Env, callback: Option<JsFunction>) -> Result<()> {
+        let sink: Box<dyn AuditSink + Send + Sync> = match callback {
+            Some(callback) => Box::new(JsAuditSink::new(&env, callback)?),
+            None => Box::new(NoAuditSink),
+        };
+        lock_runtime(&self.inner)?.set_audit_sink(sink);
+        Ok(())
+    }
 
     /// Discover skills from standard locations (~/.claude/skills/, .claude/skills/, nested)
     #[napi]
     pub fn discover_skills(&self) -> Result<Vec<SkillDescriptorJs>> {
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         let skills = runtime
             .discover_skills()
             .map_err(|e| Error::from_reason(e.to_string()))?;
@@ -389,6 +1168,7 @@ impl OpenSkillRuntimeWrapper {
             .into_iter()
             .map(|s| SkillDescriptorJs {
                 id: s.id,
+                version: s.version,
                 description: s.description,
                 location: match s.location {
                     SkillLocation::Personal => "personal".to_string(),
@@ -397,7 +1177,9 @@ impl OpenSkillRuntimeWrapper {
                     SkillLocation::Custom => "custom".to_string(),
                 },
                 user_invocable: s.user_invocable,
+                skill_kind: s.kind.to_string(),
                 requires_summary: s.requires_summary,
+                project_root: s.project_root.map(|p| p.display().to_string()),
             })
             .collect())
     }
@@ -405,7 +1187,7 @@ impl OpenSkillRuntimeWrapper {
     /// Load skills from a specific directory (additive - can be called multiple times)
     #[napi]
     pub fn load_from_directory(&self, dir: String) -> Result<Vec<SkillDescriptorJs>> {
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         let skills = runtime
             .load_from_directory(dir)
             .map_err(|e| Error::from_reason(e.to_string()))?;
@@ -414,6 +1196,7 @@ impl OpenSkillRuntimeWrapper {
             .into_iter()
             .map(|s| SkillDescriptorJs {
                 id: s.id,
+                version: s.version,
                 description: s.description,
                 location: match s.location {
                     SkillLocation::Personal => "personal".to_string(),
@@ -422,21 +1205,61 @@ impl OpenSkillRuntimeWrapper {
                     SkillLocation::Custom => "custom".to_string(),
                 },
                 user_invocable: s.user_invocable,
+                skill_kind: s.kind.to_string(),
                 requires_summary: s.requires_summary,
+                project_root: s.project_root.map(|p| p.display().to_string()),
             })
             .collect())
     }
 
+    /// Display metadata of a skill for skill pickers: description, author,
+    /// version, tags, example invocations and README.
+    #[napi]
+    pub fn get_skill_card(&self, skill_id: String) -> Result<SkillCardJs> {
+        let runtime = lock_runtime(&self.inner)?;
+        let card = runtime
+            .get_skill_card(&skill_id)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok(SkillCardJs {
+            id: card.id,
+            version: card.version,
+            description: card.description,
+            author: card.author,
+            license: card.license,
+            homepage: card.homepage,
+            tags: card.tags,
+            location: match card.location {
+                SkillLocation::Personal => "personal".to_string(),
+                SkillLocation::Project => "project".to_string(),
+                SkillLocation::Nested => "nested".to_string(),
+                SkillLocation::Custom => "custom".to_string(),
+            },
+            skill_kind: card.kind.to_string(),
+            user_invocable: card.user_invocable,
+            examples: card
+                .examples
+                .into_iter()
+                .map(|e| SkillCardExampleJs {
+                    name: e.name,
+                    prompt: e.prompt,
+                    input_json: e.input.map(|input| input.to_string()),
+                })
+                .collect(),
+            readme: card.readme,
+        })
+    }
+
     /// List skills (progressive disclosure - descriptors only)
     #[napi]
     pub fn list_skills(&self) -> Result<Vec<SkillDescriptorJs>> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         let skills = runtime.list_skills();
 
         Ok(skills
             .into_iter()
             .map(|s| SkillDescriptorJs {
                 id: s.id,
+                version: s.version,
                 description: s.description,
                 location: match s.location {
                     SkillLocation::Personal => "personal".to_string(),
@@ -445,16 +1268,18 @@ impl OpenSkillRuntimeWrapper {
                     SkillLocation::Custom => "custom".to_string(),
                 },
                 user_invocable: s.user_invocable,
+                skill_kind: s.kind.to_string(),
                 requires_summary: s.requires_summary,
+                project_root: s.project_root.map(|p| p.display().to_string()),
             })
             .collect())
     }
 
     /// List all declared actions from all skills (capability/action model).
     #[napi]
-    pub fn list_skill_actions(&self) -> Vec<SkillActionDescriptorJs> {
-        let runtime = self.inner.lock().unwrap();
-        runtime
+    pub fn list_skill_actions(&self) -> Result<Vec<SkillActionDescriptorJs>> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime
             .list_skill_actions()
             .into_iter()
             .map(|a| SkillActionDescriptorJs {
@@ -464,7 +1289,7 @@ impl OpenSkillRuntimeWrapper {
                 description: a.description,
                 has_input_schema: a.has_input_schema,
             })
-            .collect()
+            .collect())
     }
 
     /// Find (skill_id, action_id) that provides the given capability (e.g. "skill.scaffold").
@@ -472,18 +1297,18 @@ impl OpenSkillRuntimeWrapper {
     pub fn find_skill_for_capability(
         &self,
         capability: String,
-    ) -> Option<Vec<String>> {
-        let runtime = self.inner.lock().unwrap();
-        runtime
+    ) -> Result<Option<Vec<String>>> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime
             .find_skill_for_capability(&capability)
-            .map(|(sid, aid)| vec![sid, aid])
+            .map(|(sid, aid)| vec![sid, aid]))
     }
 
     /// Find skill_id that declares the given action id (e.g. "scaffold.create").
     #[napi]
-    pub fn find_skill_for_action(&self, action_id: String) -> Option<String> {
-        let runtime = self.inner.lock().unwrap();
-        runtime.find_skill_for_action(&action_id)
+    pub fn find_skill_for_action(&self, action_id: String) -> Result<Option<String>> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime.find_skill_for_action(&action_id))
     }
 
     /// Invoke a declared action by skill_id and action_id with validated input (JSON string).
@@ -496,50 +1321,34 @@ impl OpenSkillRuntimeWrapper {
     ) -> Result<ExecutionResult> {
         let input: serde_json::Value = serde_json::from_str(&input_json)
             .map_err(|e| Error::from_reason(format!("invalid input JSON: {}", e)))?;
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         let result = runtime
             .invoke_skill_action(&skill_id, &action_id, input)
             .map_err(|e| Error::from_reason(e.to_string()))?;
 
         let output_json = serde_json::to_string(&result.output)
             .map_err(|e| Error::from_reason(e.to_string()))?;
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
         Ok(ExecutionResult {
             output_json,
             stdout: result.stdout,
             stderr: result.stderr,
-            audit: AuditRecord {
-                skill_id: result.audit.skill_id,
-                version: result.audit.version,
-                input_hash: result.audit.input_hash,
-                output_hash: result.audit.output_hash,
-                start_time_ms: u64_ms_to_audit_string(result.audit.start_time_ms),
-                duration_ms: u64_ms_to_audit_string(result.audit.duration_ms),
-                permissions_used: result.audit.permissions_used,
-                exit_status,
-                stdout: result.audit.stdout,
-                stderr: result.audit.stderr,
-                sandbox_mode: result.audit.sandbox_mode.as_audit_str().to_string(),
-            },
+            audit: result.audit.into(),
+            artifacts: result.artifacts.into_iter().map(ArtifactJs::from).collect(),
+            capabilities_needed: capabilities_needed_to_js(result.capabilities_needed),
         })
     }
 
     /// Get a complete skill-agnostic system prompt for agents.
     #[napi]
-    pub fn get_agent_system_prompt(&self) -> String {
-        let runtime = self.inner.lock().unwrap();
-        runtime.get_agent_system_prompt()
+    pub fn get_agent_system_prompt(&self) -> Result<String> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime.get_agent_system_prompt())
     }
 
     /// Activate a skill (load full SKILL.md content)
     #[napi]
     pub fn activate_skill(&self, skill_id: String) -> Result<LoadedSkillJs> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         let loaded = runtime
             .activate_skill(&skill_id)
             .map_err(|e| Error::from_reason(e.to_string()))?;
@@ -555,6 +1364,7 @@ impl OpenSkillRuntimeWrapper {
 
         Ok(LoadedSkillJs {
             id: loaded.id.clone(),
+            version: loaded.manifest.version().to_string(),
             name: loaded.manifest.name.clone(),
             description: loaded.manifest.description.clone(),
             allowed_tools: loaded.manifest.get_allowed_tools(),
@@ -581,52 +1391,30 @@ impl OpenSkillRuntimeWrapper {
         skill_id: String,
         options: Option<ExecutionOptionsJs>,
     ) -> Result<ExecutionResult> {
-        let mut runtime = self.inner.lock().unwrap();
-
-        let exec_options = if let Some(opts) = options {
-            ExecutionOptions {
-                timeout_ms: safe_timeout_ms(opts.timeout_ms),
-                memory_mb: opts.memory_mb.map(|m| if m < 0 { 0 } else { m as u64 }),
-                input: opts.input.and_then(|s| {
-                    serde_json::from_str(&s).ok()
-                }),
-            }
-        } else {
-            ExecutionOptions::default()
-        };
-
+        let mut runtime = lock_runtime(&self.inner)?;
         let result = runtime
-            .execute_skill(&skill_id, exec_options)
-            .map_err(|e| Error::from_reason(e.to_string()))?;
-
-        let output_json = serde_json::to_string(&result.output)
+            .execute_skill(&skill_id, execution_options(options)?)
             .map_err(|e| Error::from_reason(e.to_string()))?;
+        execution_result(result)
+    }
 
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
-        Ok(ExecutionResult {
-            output_json,
-            stdout: result.stdout,
-            stderr: result.stderr,
-            audit: AuditRecord {
-                skill_id: result.audit.skill_id,
-                version: result.audit.version,
-                input_hash: result.audit.input_hash,
-                output_hash: result.audit.output_hash,
-                start_time_ms: u64_ms_to_audit_string(result.audit.start_time_ms),
-                duration_ms: u64_ms_to_audit_string(result.audit.duration_ms),
-                permissions_used: result.audit.permissions_used,
-                exit_status,
-                stdout: result.audit.stdout,
-                stderr: result.audit.stderr,
-                sandbox_mode: result.audit.sandbox_mode.as_audit_str().to_string(),
-            },
-        })
+    /// Like `executeSkill`, but runs on the libuv thread pool and returns a
+    /// Promise, so long script runs do not block the event loop. The runtime
+    /// is only locked while the skill is resolved and audited, so other calls
+    /// on it can be made while the skill runs.
+    #[napi(ts_return_type = "Promise<ExecutionResult>")]
+    pub fn execute_skill_async(
+        &self,
+        skill_id: String,
+        options: Option<ExecutionOptionsJs>,
+    ) -> Result<AsyncTask<ExecuteTask>> {
+        Ok(AsyncTask::new(ExecuteTask {
+            runtime: self.inner.clone(),
+            job: Some(ExecuteJob::Skill {
+                skill_id,
+                options: execution_options(options)?,
+            }),
+        }))
     }
 
     /// Start an instruction-based skill session (for context: fork behavior).
@@ -637,12 +1425,13 @@ impl OpenSkillRuntimeWrapper {
         input_json: Option<String>,
         parent_context: Option<&ExecutionContextWrapper>,
     ) -> Result<SkillExecutionSessionWrapper> {
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         let input = input_json
             .as_deref()
             .and_then(|s| serde_json::from_str(s).ok());
         let parent = parent_context
-            .map(|ctx| ctx.inner.lock().unwrap().clone());
+            .map(|ctx| lock_runtime(&ctx.inner).map(|c| c.clone()))
+            .transpose()?;
         let parent_ref = parent.as_ref();
 
         let session = runtime
@@ -664,12 +1453,12 @@ impl OpenSkillRuntimeWrapper {
         stderr: String,
         exit_status: Option<String>,
     ) -> Result<ExecutionResult> {
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         let output: serde_json::Value = serde_json::from_str(&output_json)
             .unwrap_or_else(|_| serde_json::json!({ "output": output_json }));
-        let status = parse_execution_status(exit_status);
+        let status = parse_execution_status(exit_status)?;
 
-        let session = session.inner.lock().unwrap();
+        let session = lock_runtime(&session.inner)?;
         let result = runtime
             .finish_skill_session(
                 session.clone(),
@@ -683,53 +1472,49 @@ impl OpenSkillRuntimeWrapper {
         let output_json = serde_json::to_string(&result.output)
             .map_err(|e| Error::from_reason(e.to_string()))?;
 
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
         Ok(ExecutionResult {
             output_json,
             stdout: result.stdout,
             stderr: result.stderr,
-            audit: AuditRecord {
-                skill_id: result.audit.skill_id,
-                version: result.audit.version,
-                input_hash: result.audit.input_hash,
-                output_hash: result.audit.output_hash,
-                start_time_ms: u64_ms_to_audit_string(result.audit.start_time_ms),
-                duration_ms: u64_ms_to_audit_string(result.audit.duration_ms),
-                permissions_used: result.audit.permissions_used,
-                exit_status,
-                stdout: result.audit.stdout,
-                stderr: result.audit.stderr,
-                sandbox_mode: result.audit.sandbox_mode.as_audit_str().to_string(),
-            },
+            audit: result.audit.into(),
+            artifacts: result.artifacts.into_iter().map(ArtifactJs::from).collect(),
+            capabilities_needed: capabilities_needed_to_js(result.capabilities_needed),
         })
     }
 
     /// Check if a tool is allowed for a skill
     #[napi]
     pub fn is_tool_allowed(&self, skill_id: String, tool: String) -> Result<bool> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         runtime
             .is_tool_allowed(&skill_id, &tool)
             .map_err(|e| Error::from_reason(e.to_string()))
     }
 
+    /// Tools an execution of the skill would grant to the sandbox, resolved
+    /// through the host policy.
+    #[napi]
+    pub fn resolve_effective_tools(&self, skill_id: String) -> Result<Vec<String>> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .resolve_effective_tools(&skill_id)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
     /// Check if a tool call is permitted for a skill (ask-before-act for risky tools).
+    /// `argument` is the call's command or path, matched against patterns
+    /// such as `Bash(npm run test:*)` in `allowed-tools`.
     #[napi]
     pub fn check_tool_permission(
         &self,
         skill_id: String,
         tool: String,
         description: Option<String>,
+        argument: Option<String>,
     ) -> Result<bool> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         runtime
-            .check_tool_permission(&skill_id, &tool, description, std::collections::HashMap::new())
+            .check_tool_permission_with_argument(&skill_id, &tool, argument.as_deref(), description, std::collections::HashMap::new())
             .map_err(|e| Error::from_reason(e.to_string()))
     }
 
@@ -737,6 +1522,7 @@ impl OpenSkillRuntimeWrapper {
     ///
     /// This overrides the default host policy.
     /// The resolution algorithm is: deny > allow > skill trust > fallback.
+    /// `skillSandbox` decides SKILL.md `sandbox:` sections (default: "deny").
     #[napi]
     pub fn set_host_policy(
         &self,
@@ -744,29 +1530,359 @@ impl OpenSkillRuntimeWrapper {
         fallback: String,
         deny: Vec<String>,
         allow: Vec<String>,
+        skill_sandbox: Option<String>,
     ) -> Result<()> {
-        let fallback = match fallback.as_str() {
-            "allow" => Fallback::Allow,
-            "deny" => Fallback::Deny,
-            "prompt" => Fallback::Prompt,
-            _ => {
-                return Err(Error::from_reason(format!(
-                    "Invalid fallback: '{}'. Must be 'allow', 'deny', or 'prompt'.",
-                    fallback
-                )))
-            }
-        };
+        let fallback = parse_fallback(&fallback)?;
+        let skill_sandbox = parse_fallback(skill_sandbox.as_deref().unwrap_or("deny"))?;
         let policy = HostPolicy::from_config(PermissionsConfig {
             trust_skill_allowed_tools,
             fallback,
             deny,
             allow,
-        });
-        let mut runtime = self.inner.lock().unwrap();
+        })
+        .with_skill_sandbox(skill_sandbox);
+        let mut runtime = lock_runtime(&self.inner)?;
         runtime.set_host_policy(policy);
         Ok(())
     }
 
+    /// Enable or disable redaction of host paths in returned outputs.
+    ///
+    /// When enabled, workspace, skill root and home directory paths in
+    /// output/stdout/stderr are replaced with `$SKILL_WORKSPACE`,
+    /// `$SKILL_ROOT` and `~`.
+    #[napi]
+    pub fn set_output_redaction(&self, enabled: bool) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_output_redaction(enabled);
+        Ok(())
+    }
+
+    /// Enable or disable redaction of API keys, tokens and other credentials
+    /// in output/stdout/stderr, before they reach the audit record.
+    ///
+    /// `patterns` are extra regexes to redact besides the built-in key
+    /// formats and entropy detection; a `secret` group limits the redaction
+    /// to that group.
+    #[napi]
+    pub fn set_secret_redaction(&self, enabled: bool, patterns: Option<Vec<String>>) -> Result<()> {
+        let mut redactor = None;
+        if enabled {
+            let mut r = SecretRedactor::new();
+            for pattern in patterns.unwrap_or_default() {
+                r = r
+                    .with_pattern(&pattern)
+                    .map_err(|e| Error::from_reason(e.to_string()))?;
+            }
+            redactor = Some(r);
+        }
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_secret_redaction(redactor);
+        Ok(())
+    }
+
+    /// Enable or disable ANSI stripping and carriage-return collapsing of
+    /// captured output (enabled by default).
+    #[napi]
+    pub fn set_tty_cleanup(&self, enabled: bool) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_tty_cleanup(enabled);
+        Ok(())
+    }
+
+    /// Enable or disable writing binary stdout to a workspace file
+    /// (enabled by default). Binary stdout is never returned as lossy text.
+    #[napi]
+    pub fn set_binary_output_diversion(&self, enabled: bool) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_binary_output_diversion(enabled);
+        Ok(())
+    }
+
+    /// Enable or disable returning the workspace files each execution
+    /// created or modified in `artifacts` (enabled by default).
+    #[napi]
+    pub fn set_artifact_collection(&self, enabled: bool) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_artifact_collection(enabled);
+        Ok(())
+    }
+
+    /// Convert the files every skill produces from `fromType` (an extension
+    /// or MIME type) to `to` by running `run` in `skillId`, in addition to
+    /// the skills' own `output-converters`.
+    #[napi]
+    pub fn add_output_converter(
+        &self,
+        from_type: String,
+        to: String,
+        skill_id: String,
+        run: String,
+        args: Option<Vec<String>>,
+    ) -> Result<()> {
+        let converter = OutputConverter::new(from_type, to, skill_id, run).with_args(args.unwrap_or_default());
+        let mut runtime = lock_runtime(&self.inner)?;
+        let mut converters = runtime.output_converters().to_vec();
+        converters.push(converter);
+        runtime.set_output_converters(converters);
+        Ok(())
+    }
+
+    /// Session ID naming the default workspace (`session-<uuid>` unless set).
+    #[napi]
+    pub fn get_session_id(&self) -> Result<String> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime.get_session_id().to_string())
+    }
+
+    /// Use the host's own session ID, e.g. a conversation ID, so the
+    /// default workspace lines up with it. An empty ID is ignored.
+    #[napi]
+    pub fn set_session_id(&self, session_id: String) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_session_id(session_id);
+        Ok(())
+    }
+
+    /// Configure entry-point auto-detection for skills run without a target.
+    ///
+    /// `precedence` is "wasm_first" (default) or "script_first"; candidate
+    /// lists are patterns relative to the skill root (globs allowed) and
+    /// replace the defaults when given. SKILL.md `entrypoint:` always wins.
+    #[napi]
+    pub fn set_entrypoint_config(
+        &self,
+        precedence: Option<String>,
+        wasm_candidates: Option<Vec<String>>,
+        script_candidates: Option<Vec<String>>,
+    ) -> Result<()> {
+        let precedence = match precedence.as_deref().unwrap_or("wasm_first") {
+            "wasm_first" => EntrypointPrecedence::WasmFirst,
+            "script_first" => EntrypointPrecedence::ScriptFirst,
+            other => {
+                return Err(Error::from_reason(format!(
+                    "Invalid precedence: '{}'. Must be 'wasm_first' or 'script_first'.",
+                    other
+                )))
+            }
+        };
+        let mut config = EntrypointConfig::default().with_precedence(precedence);
+        if let Some(candidates) = wasm_candidates {
+            config.wasm_candidates = candidates;
+        }
+        if let Some(candidates) = script_candidates {
+            config.script_candidates = candidates;
+        }
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_entrypoint_config(config);
+        Ok(())
+    }
+
+    /// Explain which file `executeSkill` would run for a skill.
+    #[napi]
+    pub fn explain_entrypoint(&self, skill_id: String) -> Result<EntrypointResolutionJs> {
+        let runtime = lock_runtime(&self.inner)?;
+        let resolution = runtime
+            .explain_entrypoint(&skill_id)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok(EntrypointResolutionJs {
+            selected: resolution.selected,
+            args: resolution.args,
+            trace: resolution.trace,
+        })
+    }
+
+    /// Limit captured stdout/stderr of native scripts to `max_bytes` each
+    /// (unbounded when unset). Larger output is saved in full to a workspace
+    /// file and returned as head + tail with a marker naming the file.
+    #[napi]
+    pub fn set_max_output_bytes(&self, max_bytes: Option<u32>) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_max_output_bytes(max_bytes.map(|n| n as usize));
+        Ok(())
+    }
+
+    /// Configure retention of runtime-created workspace artifacts (spilled
+    /// output, binary stdout). Unset limits keep everything. With `skill_id`,
+    /// the policy replaces the default for that skill only.
+    #[napi]
+    pub fn set_artifact_retention(
+        &self,
+        max_age_ms: Option<i64>,
+        max_count: Option<u32>,
+        max_bytes: Option<i64>,
+        skill_id: Option<String>,
+    ) -> Result<()> {
+        let policy = RetentionPolicy {
+            max_age: safe_timeout_ms(max_age_ms).map(Duration::from_millis),
+            max_count: max_count.map(|n| n as usize),
+            max_bytes: max_bytes.map(|n| n.max(0) as u64),
+        };
+        let mut runtime = lock_runtime(&self.inner)?;
+        match skill_id {
+            Some(id) => runtime.set_skill_artifact_retention(&id, Some(policy)),
+            None => runtime.set_artifact_retention(policy),
+        }
+        Ok(())
+    }
+
+    /// Remove other session workspaces idle for longer than `max_age_ms`
+    /// (disabled when unset).
+    #[napi]
+    pub fn set_session_retention(&self, max_age_ms: Option<i64>) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_session_retention(safe_timeout_ms(max_age_ms).map(Duration::from_millis));
+        Ok(())
+    }
+
+    /// Workspace of a host conversation, created on first use, so later
+    /// turns find the files earlier turns produced.
+    #[napi]
+    pub fn workspace_for(&self, conversation_id: String) -> Result<String> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .workspace_for(&conversation_id)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Run later executions in a conversation's workspace and return it.
+    #[napi]
+    pub fn use_conversation(&self, conversation_id: String) -> Result<String> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime
+            .use_conversation(&conversation_id)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Remove a conversation's workspace. Returns whether it existed.
+    #[napi]
+    pub fn remove_conversation_workspace(&self, conversation_id: String) -> Result<bool> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .remove_conversation_workspace(&conversation_id)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Remove conversation workspaces unused for longer than `max_age_ms`
+    /// (disabled when unset).
+    #[napi]
+    pub fn set_conversation_retention(&self, max_age_ms: Option<i64>) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_conversation_retention(safe_timeout_ms(max_age_ms).map(Duration::from_millis));
+        Ok(())
+    }
+
+    /// Pin a workspace artifact so retention never removes it.
+    /// Returns the canonical path.
+    #[napi]
+    pub fn pin_artifact(&self, path: String) -> Result<String> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .pin_artifact(&path)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Unpin a workspace artifact. Returns the canonical path.
+    #[napi]
+    pub fn unpin_artifact(&self, path: String) -> Result<String> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .unpin_artifact(&path)
+            .map(|p| p.to_string_lossy().to_string())
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Enforce artifact and session retention now.
+    #[napi]
+    pub fn gc_artifacts(&self) -> Result<GcReportJs> {
+        let runtime = lock_runtime(&self.inner)?;
+        let report = runtime
+            .gc_artifacts()
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        let to_strings = |paths: Vec<PathBuf>| {
+            paths
+                .into_iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect()
+        };
+        Ok(GcReportJs {
+            removed: to_strings(report.removed),
+            removed_sessions: to_strings(report.removed_sessions),
+            freed_bytes: i64::try_from(report.freed_bytes).unwrap_or(i64::MAX),
+        })
+    }
+
+    /// Limit the size of a single workspace import (default 100 MiB).
+    #[napi]
+    pub fn set_max_import_bytes(&self, max_bytes: i64) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        runtime.set_max_import_bytes(u64::try_from(max_bytes).unwrap_or(0));
+        Ok(())
+    }
+
+    /// Deduplicate workspace imports through a content-addressed blob store
+    /// shared across sessions (`~/.cache/openskills/blobs/`, or `dir`).
+    #[napi]
+    pub fn set_blob_store(&self, enable: bool, dir: Option<String>) -> Result<()> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        match dir {
+            Some(dir) if enable => runtime.set_blob_store_dir(Some(PathBuf::from(dir))),
+            _ => runtime.set_blob_store(enable),
+        }
+        Ok(())
+    }
+
+    /// Copy a user-provided host file into the workspace at `dest_rel`.
+    #[napi]
+    pub fn import_into_workspace(&self, host_path: String, dest_rel: String) -> Result<WorkspaceImportJs> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .import_into_workspace(&host_path, &dest_rel)
+            .map(WorkspaceImportJs::from)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Write in-memory content into the workspace at `dest_rel`.
+    #[napi]
+    pub fn import_bytes_into_workspace(&self, data: Buffer, dest_rel: String) -> Result<WorkspaceImportJs> {
+        let runtime = lock_runtime(&self.inner)?;
+        runtime
+            .import_bytes_into_workspace(&data, &dest_rel)
+            .map(WorkspaceImportJs::from)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Files imported into the workspace, oldest first.
+    #[napi]
+    pub fn get_import_audit(&self) -> Result<Vec<WorkspaceImportJs>> {
+        let runtime = lock_runtime(&self.inner)?;
+        Ok(runtime
+            .get_import_audit()
+            .into_iter()
+            .map(WorkspaceImportJs::from)
+            .collect())
+    }
+
+    /// Configure the output post-processing pipeline.
+    ///
+    /// `processors` is an ordered list of names: `"strip-ansi"`, `"redact"`,
+    /// `"json-extract"`, `"truncate:<bytes>"`. With `skill_id`, the pipeline
+    /// replaces the default for that skill only.
+    #[napi]
+    pub fn set_output_pipeline(&self, processors: Vec<String>, skill_id: Option<String>) -> Result<()> {
+        let pipeline = OutputPipeline::parse(&processors)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        let mut runtime = lock_runtime(&self.inner)?;
+        match skill_id {
+            Some(id) => runtime.set_skill_output_pipeline(&id, Some(pipeline)),
+            None => runtime.set_output_pipeline(pipeline),
+        }
+        Ok(())
+    }
+
     /// Set the permission mode for interactive prompting.
     ///
     /// Controls what happens when host policy returns `fallback: prompt`:
@@ -775,7 +1891,7 @@ impl OpenSkillRuntimeWrapper {
     /// - `"allow-all"`: Auto-approve all permission requests (default)
     #[napi]
     pub fn set_permission_mode(&self, mode: String) -> Result<()> {
-        let mut runtime = self.inner.lock().unwrap();
+        let mut runtime = lock_runtime(&self.inner)?;
         match mode.as_str() {
             "cli" => {
                 let cb: Arc<dyn PermissionCallback> = Arc::new(CliPermissionCallback);
@@ -808,79 +1924,40 @@ impl OpenSkillRuntimeWrapper {
         skill_id: String,
         options: Option<TargetExecutionOptionsJs>,
     ) -> Result<ExecutionResult> {
-        let mut runtime = self.inner.lock().unwrap();
-
-        let (target, timeout_ms, input, workspace_dir) = if let Some(opts) = options {
-            let target = match opts.target_type.as_deref() {
-                Some("script") => {
-                    let path = opts.path.ok_or_else(|| {
-                        Error::from_reason("path is required for script target".to_string())
-                    })?;
-                    ExecutionTarget::Script {
-                        path,
-                        args: opts.args.unwrap_or_default(),
-                    }
-                }
-                Some("wasm") => {
-                    let path = opts.path.ok_or_else(|| {
-                        Error::from_reason("path is required for wasm target".to_string())
-                    })?;
-                    ExecutionTarget::Wasm { path }
-                }
-                Some("auto") | None => {
-                    // Auto-detect from path extension if path is provided
-                    // Uses ExecutionTarget::Path for transparent WASM vs native sandbox selection
-                    if let Some(path) = opts.path {
-                        ExecutionTarget::Path {
-                            path,
-                            args: opts.args.unwrap_or_default(),
-                        }
-                    } else {
-                        ExecutionTarget::Auto
-                    }
-                }
-                _ => ExecutionTarget::Auto,
-            };
-            let timeout = safe_timeout_ms(opts.timeout_ms);
-            let input = opts.input.and_then(|s| serde_json::from_str(&s).ok());
-            let workspace = opts.workspace_dir.map(std::path::PathBuf::from);
-            (target, timeout, input, workspace)
-        } else {
-            (ExecutionTarget::Auto, None, None, None)
-        };
-
-        let result = runtime
-            .run_skill_target(&skill_id, target, timeout_ms, input, workspace_dir)
+        let request = TargetRequest::from_options(options)?;
+        let mut runtime = lock_runtime(&self.inner)?;
+        let result = request
+            .run(&mut runtime, &skill_id)
             .map_err(|e| Error::from_reason(e.to_string()))?;
+        execution_result(result)
+    }
 
-        let output_json = serde_json::to_string(&result.output)
+    /// Run the example named `name` from the skill's `examples:` list, as
+    /// a smoke test that the skill works on this machine.
+    #[napi]
+    pub fn run_skill_example(&self, skill_id: String, name: String) -> Result<ExecutionResult> {
+        let mut runtime = lock_runtime(&self.inner)?;
+        let result = runtime
+            .run_skill_example(&skill_id, &name)
             .map_err(|e| Error::from_reason(e.to_string()))?;
+        execution_result(result)
+    }
 
-        let exit_status = match result.audit.exit_status {
-            RuntimeExecutionStatus::Success => "success".to_string(),
-            RuntimeExecutionStatus::Timeout => "timeout".to_string(),
-            RuntimeExecutionStatus::PermissionDenied => "permission_denied".to_string(),
-            RuntimeExecutionStatus::Failed(msg) => format!("failed:{}", msg),
-        };
-
-        Ok(ExecutionResult {
-            output_json,
-            stdout: result.stdout,
-            stderr: result.stderr,
-            audit: AuditRecord {
-                skill_id: result.audit.skill_id,
-                version: result.audit.version,
-                input_hash: result.audit.input_hash,
-                output_hash: result.audit.output_hash,
-                start_time_ms: u64_ms_to_audit_string(result.audit.start_time_ms),
-                duration_ms: u64_ms_to_audit_string(result.audit.duration_ms),
-                permissions_used: result.audit.permissions_used,
-                exit_status,
-                stdout: result.audit.stdout,
-                stderr: result.audit.stderr,
-                sandbox_mode: result.audit.sandbox_mode.as_audit_str().to_string(),
-            },
-        })
+    /// Like `runSkillTarget`, but runs on the libuv thread pool and returns
+    /// a Promise (see `executeSkillAsync`).
+    #[napi(ts_return_type = "Promise<ExecutionResult>")]
+    pub fn run_skill_target_async(
+        &self,
+        skill_id: String,
+        options: Option<TargetExecutionOptionsJs>,
+    ) -> Result<AsyncTask<ExecuteTask>> {
+        Ok(AsyncTask::new(ExecuteTask {
+            runtime: self.inner.clone(),
+            job: Some(ExecuteJob::Target {
+                skill_id,
+                request: TargetRequest::from_options(options)?,
+            }),
+        }))
     }
 
     /// Read a file from a skill directory.
@@ -889,7 +1966,7 @@ impl OpenSkillRuntimeWrapper {
     /// reference in their SKILL.md instructions.
     #[napi]
     pub fn read_skill_file(&self, skill_id: String, relative_path: String) -> Result<String> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         runtime
             .read_skill_file(&skill_id, &relative_path)
             .map_err(|e| Error::from_reason(e.to_string()))
@@ -905,16 +1982,33 @@ impl OpenSkillRuntimeWrapper {
         subdir: Option<String>,
         recursive: Option<bool>,
     ) -> Result<Vec<String>> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         runtime
             .list_skill_files(&skill_id, subdir.as_deref(), recursive.unwrap_or(false))
             .map_err(|e| Error::from_reason(e.to_string()))
     }
 
+    /// List executable artifacts (WASM modules, Python/shell scripts) in a skill.
+    #[napi]
+    pub fn list_skill_targets(&self, skill_id: String) -> Result<Vec<SkillTargetJs>> {
+        let runtime = lock_runtime(&self.inner)?;
+        let targets = runtime
+            .list_skill_targets(&skill_id)
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        Ok(targets
+            .into_iter()
+            .map(|t| SkillTargetJs {
+                kind: t.kind.to_string(),
+                sandbox: t.sandbox.to_string(),
+                path: t.path,
+            })
+            .collect())
+    }
+
     /// Get the root directory path for a skill.
     #[napi]
     pub fn get_skill_root(&self, skill_id: String) -> Result<String> {
-        let runtime = self.inner.lock().unwrap();
+        let runtime = lock_runtime(&self.inner)?;
         let path = runtime
             .get_skill_root(&skill_id)
             .map_err(|e| Error::from_reason(e.to_string()))?;
@@ -922,10 +2016,24 @@ impl OpenSkillRuntimeWrapper {
     }
 }
 
+impl Default for OpenSkillRuntimeWrapper {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(OpenSkillRuntime::new())),
+        }
+    }
+}
+
 // ============================================================================
 // Standalone sandboxed command execution
 // ============================================================================
 
+/// Optional runtime features this binding was built with, e.g. `wasm`.
+#[napi]
+pub fn features() -> Vec<String> {
+    openskills_runtime::features().into_iter().map(String::from).collect()
+}
+
 /// Run a shell command in a sandboxed environment (macOS only).
 ///
 /// This provides Claude Code-like sandboxed bash execution for agents.
@@ -940,7 +2048,15 @@ pub fn run_sandboxed_shell_command(
     
     let rust_perms = CommandPermissions {
         allow_network: perms.allow_network.unwrap_or(false),
+        allowed_hosts: perms.allowed_hosts.unwrap_or_default(),
+        allowed_ports: perms
+            .allowed_ports
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|p| u16::try_from(p).ok())
+            .collect(),
         allow_process: perms.allow_process.unwrap_or(false),
+        allowed_programs: perms.allowed_programs.unwrap_or_default(),
         read_paths: perms
             .read_paths
             .unwrap_or_default()
@@ -967,6 +2083,19 @@ pub fn run_sandboxed_shell_command(
             .collect(),
         timeout_ms: safe_timeout_ms(perms.timeout_ms).unwrap_or(30000),
         sandbox_mode: parse_sandbox_mode(perms.sandbox_mode.as_deref()),
+        backend: perms
+            .backend
+            .as_deref()
+            .map(str::parse::<ExecutionBackend>)
+            .transpose()
+            .map_err(|e| Error::from_reason(e.to_string()))?
+            .unwrap_or_default(),
+        container: ContainerConfig {
+            image: perms
+                .container_image
+                .unwrap_or_else(|| ContainerConfig::default().image),
+            ..Default::default()
+        },
     };
 
     let result = run_sandboxed_command(&command, &PathBuf::from(&working_dir), rust_perms)
@@ -979,17 +2108,3 @@ pub fn run_sandboxed_shell_command(
         timed_out: result.timed_out,
     })
 }
-
-impl Default for CommandPermissionsJs {
-    fn default() -> Self {
-        Self {
-            allow_network: None,
-            allow_process: None,
-            read_paths: None,
-            write_paths: None,
-            env_vars: None,
-            timeout_ms: None,
-            sandbox_mode: None,
-        }
-    }
-}
@@ -0,0 +1,107 @@
+const { DaemonClient, DaemonError } = require("../daemon");
+const { spawn } = require("child_process");
+const assert = require("assert");
+const fs = require("fs");
+const os = require("os");
+const path = require("path");
+
+// Built with `cargo build -p openskills-runtime --features daemon`.
+function findDaemon() {
+  const binary = process.env.OPENSKILLSD_BIN ||
+    path.resolve(__dirname, "../../../target/debug/openskillsd");
+  return fs.existsSync(binary) ? binary : null;
+}
+
+async function connectWithRetry(socket) {
+  for (let attempt = 0; attempt < 50; attempt++) {
+    try {
+      return await DaemonClient.connect(socket);
+    } catch (err) {
+      await new Promise((resolve) => setTimeout(resolve, 100));
+    }
+  }
+  throw new Error(`daemon did not start on ${socket}`);
+}
+
+async function testDaemonClient() {
+  console.log("Running testDaemonClient...");
+  const binary = findDaemon();
+  if (!binary) {
+    console.log("testDaemonClient skipped (openskillsd not built)");
+    return;
+  }
+  const tmp = fs.mkdtempSync(path.join(os.tmpdir(), "openskillsd-"));
+  const skillDir = path.join(tmp, "skills", "alpha");
+  fs.mkdirSync(skillDir, { recursive: true });
+  fs.writeFileSync(
+    path.join(skillDir, "SKILL.md"),
+    "---\nname: alpha\ndescription: Test skill.\n---\nFollow these steps.\n"
+  );
+  const socket = path.join(tmp, "openskillsd.sock");
+  const daemon = spawn(binary, [
+    "--socket", socket,
+    "--dir", path.join(tmp, "skills"),
+    "--no-standard-locations",
+  ], { stdio: "ignore" });
+
+  try {
+    const client = await connectWithRetry(socket);
+    const status = await client.status();
+    assert.strictEqual(status.skills, 1);
+
+    const skills = await client.listSkills();
+    assert.deepStrictEqual(skills.map((s) => s.id), ["alpha"]);
+    const loaded = await client.activateSkill("alpha");
+    assert.strictEqual(loaded.instructions.trim(), "Follow these steps.");
+
+    const session = await client.startSession("alpha", { doc: "a.pdf" });
+    await client.recordToolCall(session.session_id, "Read", "contents");
+    const result = await client.finishSession(session.session_id, { ok: true }, "done");
+    assert.deepStrictEqual(result.output, { ok: true });
+    assert.strictEqual(result.audit.exit_status, "success");
+
+    await assert.rejects(client.activateSkill("missing"), DaemonError);
+
+    // Hand a session to a second client, as an IDE would continue a CLI workflow.
+    const started = await client.startSession("alpha", null, tmp);
+    await client.recordToolCall(started.session_id, "Read", "contents");
+    await client.detachSession(started.session_id);
+    const ide = await connectWithRetry(socket);
+    const waiting = await ide.listSessions();
+    assert.deepStrictEqual(waiting.map((s) => s.session_id), [started.session_id]);
+    const attached = await ide.attachSession(started.session_id);
+    assert.deepStrictEqual(attached.permissions_used, ["Read"]);
+    assert.strictEqual(attached.workspace_dir, tmp);
+    fs.writeFileSync(path.join(tmp, "report.md"), "# Report\n");
+    const scope = { session_id: started.session_id };
+    const outputs = await ide.listOutputs(scope);
+    assert.strictEqual(outputs[0].path, "report.md");
+    assert.strictEqual(outputs[0].mime, "text/markdown");
+    const report = await ide.downloadOutput("report.md", scope);
+    assert.strictEqual(report.toString(), "# Report\n");
+    await assert.rejects(client.attachSession(started.session_id), DaemonError);
+    const handedOff = await ide.finishSession(started.session_id);
+    assert.deepStrictEqual(handedOff.audit.permissions_used, ["Read"]);
+    ide.close();
+
+    await client.shutdown();
+    client.close();
+    await new Promise((resolve) => daemon.once("exit", resolve));
+  } finally {
+    daemon.kill();
+    fs.rmSync(tmp, { recursive: true, force: true });
+  }
+  console.log("testDaemonClient passed");
+}
+
+async function runTests() {
+  try {
+    await testDaemonClient();
+    console.log("All tests passed!");
+  } catch (err) {
+    console.error("Test failed:", err);
+    process.exit(1);
+  }
+}
+
+runTests();
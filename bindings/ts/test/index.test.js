@@ -172,6 +172,69 @@ async function testCheckToolPermission() {
   console.log("testCheckToolPermission passed");
 }
 
+async function testRuntimeOptions() {
+  console.log("Running testRuntimeOptions...");
+  const examplesDir = getExamplesDir();
+  const records = [];
+  const runtime = new OpenSkillRuntime({
+    customDirectories: [examplesDir],
+    useStandardLocations: false,
+    workspaceDir: path.join(require("os").tmpdir(), "openskills-ts-options-test"),
+    onAudit: (record) => records.push(record),
+  });
+
+  const skills = runtime.discoverSkills();
+  assert(skills.some(s => s.id === "explaining-code"), "Should find skills in customDirectories");
+
+  const result = runtime.executeSkill("explaining-code", {});
+  // Audit records are delivered once control returns to the event loop.
+  await new Promise(resolve => setImmediate(resolve));
+  assert.strictEqual(records.length, 1, "onAudit should receive one record");
+  assert.strictEqual(records[0].skillId, "explaining-code");
+  assert.strictEqual(records[0].exitStatus, result.audit.exitStatus);
+
+  runtime.setAuditCallback(null);
+  runtime.executeSkill("explaining-code", {});
+  await new Promise(resolve => setImmediate(resolve));
+  assert.strictEqual(records.length, 1, "No records after clearing the callback");
+
+  runtime.setStrictPermissions();
+  assert.throws(() => runtime.checkToolPermission("code-review", "Read"), /denied/);
+  console.log("testRuntimeOptions passed");
+}
+
+async function testRunSkillTargetAsync() {
+  console.log("Running testRunSkillTargetAsync...");
+  const fs = require("fs");
+  const os = require("os");
+  const skillsDir = fs.mkdtempSync(path.join(os.tmpdir(), "openskills-ts-async-"));
+  const skillDir = path.join(skillsDir, "slow-skill");
+  fs.mkdirSync(path.join(skillDir, "scripts"), { recursive: true });
+  fs.writeFileSync(
+    path.join(skillDir, "SKILL.md"),
+    "---\nname: slow-skill\ndescription: Sleeps, then prints.\n---\nRun scripts/slow.sh.\n"
+  );
+  fs.writeFileSync(path.join(skillDir, "scripts", "slow.sh"), "sleep 1\necho done\n");
+
+  const runtime = OpenSkillRuntime.fromDirectory(skillsDir);
+  runtime.discoverSkills();
+
+  // The event loop keeps running while the script sleeps.
+  let ticks = 0;
+  const timer = setInterval(() => ticks++, 50);
+  try {
+    const result = await runtime.runSkillTargetAsync("slow-skill", { path: "scripts/slow.sh" });
+    assert.strictEqual(result.stdout.trim(), "done");
+    assert(ticks >= 5, `event loop was blocked (${ticks} ticks)`);
+  } finally {
+    clearInterval(timer);
+    fs.rmSync(skillsDir, { recursive: true, force: true });
+  }
+
+  await assert.rejects(runtime.executeSkillAsync("missing-skill"), /not found/);
+  console.log("testRunSkillTargetAsync passed");
+}
+
 async function runTests() {
   try {
     await testDiscoverSkills();
@@ -181,6 +244,8 @@ async function runTests() {
     await testSkillSessionNonForked();
     await testExecutionContextFork();
     await testCheckToolPermission();
+    await testRuntimeOptions();
+    await testRunSkillTargetAsync();
     console.log("All tests passed!");
   } catch (err) {
     console.error("Test failed:", err);
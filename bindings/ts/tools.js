@@ -296,7 +296,7 @@ function createSkillTools(runtime, options = {}) {
       execute: async ({ skill_id, script_path, args, input, timeout_ms }) => {
         try {
           // Auto-detect sandbox type from file extension (handled by runtime)
-          const result = runtime.runSkillTarget(skill_id, {
+          const result = await runtime.runSkillTargetAsync(skill_id, {
             // targetType not specified = auto-detect from path extension
             path: script_path,
             args: args ?? [],
@@ -0,0 +1,171 @@
+//! Introspection and cancellation of running executions.
+//!
+//! Every skill execution is registered with its runtime's
+//! [`ExecutionMonitor`] while it runs. The monitor is cheap to clone and can
+//! be handed to another thread, so a dashboard or a "stop" button can list
+//! what is running and cancel it while the runtime itself is busy executing.
+//!
+//! Cancelling kills a native script's process, or interrupts a WASM
+//! component at its next epoch tick; the execution then finishes with
+//! `ExecutionStatus::Failed("cancelled")`.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Requests cancellation of one execution.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationHandle {
+    /// Ask the execution to stop. It finishes shortly afterwards with
+    /// `ExecutionStatus::Failed("cancelled")`.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether [`cancel`](Self::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// A skill execution that is currently running.
+#[derive(Debug, Clone)]
+pub struct ActiveExecution {
+    /// Identifier, unique within the runtime.
+    pub id: u64,
+    /// Skill being executed.
+    pub skill_id: String,
+    /// What runs: `auto` for the detected entry point, or the script or
+    /// WASM module path.
+    pub target: String,
+    /// Start time in milliseconds since the Unix epoch.
+    pub started_at_ms: u64,
+    /// Session ID of the runtime running it.
+    pub session_id: String,
+    /// Cancels the execution.
+    pub cancellation: CancellationHandle,
+}
+
+/// Running executions of a runtime; clones share the same view.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionMonitor {
+    running: Arc<Mutex<BTreeMap<u64, ActiveExecution>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl ExecutionMonitor {
+    /// Running executions, oldest first.
+    pub fn active_executions(&self) -> Vec<ActiveExecution> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// Cancel the running execution `id`. Returns `false` if it is not
+    /// running (anymore).
+    pub fn cancel(&self, id: u64) -> bool {
+        match self.lock().get(&id) {
+            Some(execution) => {
+                execution.cancellation.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Register an execution until the returned guard is dropped. It is
+    /// cancelled through `cancellation` if given, else through a new handle.
+    pub(crate) fn begin(
+        &self,
+        skill_id: &str,
+        target: String,
+        session_id: &str,
+        cancellation: Option<CancellationHandle>,
+    ) -> RunningExecution {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancellation = cancellation.unwrap_or_default();
+        let started_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.lock().insert(
+            id,
+            ActiveExecution {
+                id,
+                skill_id: skill_id.to_string(),
+                target,
+                started_at_ms,
+                session_id: session_id.to_string(),
+                cancellation: cancellation.clone(),
+            },
+        );
+        RunningExecution {
+            monitor: self.clone(),
+            id,
+            cancellation,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, ActiveExecution>> {
+        self.running.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Keeps an execution listed as running.
+pub(crate) struct RunningExecution {
+    monitor: ExecutionMonitor,
+    id: u64,
+    cancellation: CancellationHandle,
+}
+
+impl RunningExecution {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn cancellation(&self) -> CancellationHandle {
+        self.cancellation.clone()
+    }
+}
+
+impl Drop for RunningExecution {
+    fn drop(&mut self) {
+        self.monitor.lock().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_and_cancels_executions() {
+        let monitor = ExecutionMonitor::default();
+        let first = monitor.begin("a", "auto".to_string(), "session-1", None);
+        let token = CancellationHandle::default();
+        let second = monitor.begin("b", "scripts/run.py".to_string(), "session-1", Some(token.clone()));
+
+        let view = monitor.clone();
+        let active = view.active_executions();
+        let summary: Vec<_> = active
+            .iter()
+            .map(|e| (e.id, e.skill_id.as_str(), e.target.as_str()))
+            .collect();
+        assert_eq!(summary, vec![(1, "a", "auto"), (2, "b", "scripts/run.py")]);
+        assert!(active[0].started_at_ms > 0);
+
+        assert!(view.cancel(2));
+        assert!(second.cancellation().is_cancelled());
+        assert!(token.is_cancelled());
+        assert!(!first.cancellation().is_cancelled());
+
+        drop(second);
+        assert_eq!(view.active_executions().len(), 1);
+        assert!(!view.cancel(2));
+        drop(first);
+        assert!(view.active_executions().is_empty());
+    }
+}
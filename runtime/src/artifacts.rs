@@ -0,0 +1,541 @@
+//! Runtime-created workspace artifacts and their retention.
+//!
+//! Files the runtime writes on a skill's behalf (diverted binary stdout,
+//! spilled stdout/stderr) live under `<workspace>/.openskills/artifacts/<skill>/`,
+//! separate from anything the skill itself writes. The collector here only
+//! ever deletes files in that tree, according to a [`RetentionPolicy`]:
+//! per skill by age, count and total size, and per session by removing stale
+//! session and conversation workspaces under the default workspace root.
+//!
+//! Pinned artifacts are never collected. A pin is a `<file>.pin` sidecar, so
+//! it survives across runtime instances that share a workspace.
+
+use crate::errors::OpenSkillError;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Artifact tree, relative to the workspace.
+pub const ARTIFACTS_DIR: &str = ".openskills/artifacts";
+
+/// Conversation workspaces, relative to the default workspace root. The dot
+/// keeps it apart from session workspaces, whose names never contain one.
+pub const CONVERSATIONS_DIR: &str = ".conversations";
+
+const PIN_SUFFIX: &str = ".pin";
+
+/// How long runtime-created artifacts are kept.
+///
+/// All limits are optional; the default keeps everything. Limits apply to
+/// the unpinned artifacts of one skill in one workspace; the oldest go first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Remove artifacts last modified longer ago than this.
+    pub max_age: Option<Duration>,
+    /// Keep at most this many artifacts per skill.
+    pub max_count: Option<usize>,
+    /// Keep at most this many bytes of artifacts per skill.
+    pub max_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// True if this policy never removes anything.
+    pub fn is_keep_all(&self) -> bool {
+        self.max_age.is_none() && self.max_count.is_none() && self.max_bytes.is_none()
+    }
+}
+
+/// A runtime-created artifact in a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactInfo {
+    /// Absolute path of the artifact file.
+    pub path: PathBuf,
+    /// Skill the artifact belongs to (sanitized skill ID).
+    pub skill: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Last modification time.
+    pub modified: SystemTime,
+    /// Whether the artifact is pinned (exempt from collection).
+    pub pinned: bool,
+}
+
+/// What a collection pass removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Artifact files removed.
+    pub removed: Vec<PathBuf>,
+    /// Stale session and conversation workspaces removed.
+    pub removed_sessions: Vec<PathBuf>,
+    /// Bytes freed by removed artifacts (session workspaces not counted).
+    pub freed_bytes: u64,
+}
+
+impl GcReport {
+    fn merge(&mut self, other: GcReport) {
+        self.removed.extend(other.removed);
+        self.removed_sessions.extend(other.removed_sessions);
+        self.freed_bytes += other.freed_bytes;
+    }
+}
+
+/// Directory for a skill's artifacts in `workspace`.
+pub(crate) fn skill_dir(workspace: &Path, skill_id: &str) -> PathBuf {
+    workspace.join(ARTIFACTS_DIR).join(sanitize_skill_id(skill_id))
+}
+
+/// Make a skill ID safe for use as a single path component.
+pub(crate) fn sanitize_skill_id(skill_id: &str) -> String {
+    skill_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
+
+/// Workspace of session `session_id` under the default workspace `root`.
+pub(crate) fn session_dir(root: &Path, session_id: &str) -> PathBuf {
+    root.join(session_dir_name(session_id))
+}
+
+/// Directory name of a session workspace: the sanitized ID followed by a
+/// hash of the original, like [`conversation_dir`], so host IDs that
+/// sanitize alike (`a.b` and `a_b`) never share a workspace.
+fn session_dir_name(session_id: &str) -> String {
+    let mut name = sanitize_skill_id(session_id);
+    name.truncate(64);
+    let digest = hex::encode(Sha256::digest(session_id.as_bytes()));
+    format!("{}-{}", name, &digest[..16])
+}
+
+/// Whether `name` is the workspace of a generated `session-<uuid>` ID, not
+/// of an ID the host chose (which may look similar, like `session-foo`).
+fn is_generated_session_dir(name: &str) -> bool {
+    let Some((id, _)) = name.rsplit_once('-') else {
+        return false;
+    };
+    id.strip_prefix("session-")
+        .is_some_and(|uuid| uuid::Uuid::try_parse(uuid).is_ok())
+        && session_dir_name(id) == name
+}
+
+/// Workspace of host conversation `conversation_id` under the default
+/// workspace `root`.
+///
+/// The sanitized ID is followed by a hash of the original, so IDs that
+/// sanitize alike (`a/b` and `a_b`) still get separate workspaces.
+pub(crate) fn conversation_dir(root: &Path, conversation_id: &str) -> PathBuf {
+    let mut name = sanitize_skill_id(conversation_id);
+    name.truncate(64);
+    let digest = hex::encode(Sha256::digest(conversation_id.as_bytes()));
+    root.join(CONVERSATIONS_DIR).join(format!("{}-{}", name, &digest[..16]))
+}
+
+/// Mark `dir` as used now (best effort).
+pub(crate) fn touch(dir: &Path) {
+    let _ = std::fs::File::open(dir).and_then(|f| f.set_modified(SystemTime::now()));
+}
+
+/// List artifacts in `workspace`, optionally only those of one skill.
+pub(crate) fn list(workspace: &Path, skill_id: Option<&str>) -> std::io::Result<Vec<ArtifactInfo>> {
+    let root = workspace.join(ARTIFACTS_DIR);
+    let skills = match skill_id {
+        Some(id) => vec![sanitize_skill_id(id)],
+        None => subdirectory_names(&root)?,
+    };
+    let mut artifacts = Vec::new();
+    for skill in skills {
+        artifacts.extend(list_skill(&root.join(&skill), &skill)?);
+    }
+    Ok(artifacts)
+}
+
+fn list_skill(dir: &Path, skill: &str) -> std::io::Result<Vec<ArtifactInfo>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut artifacts = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.to_string_lossy().ends_with(PIN_SUFFIX) {
+            continue;
+        }
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        artifacts.push(ArtifactInfo {
+            pinned: pin_path(&path).exists(),
+            path,
+            skill: skill.to_string(),
+            size: metadata.len(),
+            modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        });
+    }
+    artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(artifacts)
+}
+
+fn subdirectory_names(dir: &Path) -> std::io::Result<Vec<String>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+fn pin_path(artifact: &Path) -> PathBuf {
+    let mut name = artifact.as_os_str().to_owned();
+    name.push(PIN_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Resolve `path` to an artifact file inside `workspace`'s artifact tree.
+fn resolve_artifact(workspace: &Path, path: &Path) -> Result<PathBuf, OpenSkillError> {
+    let root = workspace.join(ARTIFACTS_DIR);
+    let canonical = path.canonicalize().map_err(|_| {
+        OpenSkillError::InvalidConfig(format!("Artifact not found: {}", path.display()))
+    })?;
+    let inside = root
+        .canonicalize()
+        .map(|root| canonical.starts_with(root))
+        .unwrap_or(false);
+    if !inside || !canonical.is_file() {
+        return Err(OpenSkillError::InvalidConfig(format!(
+            "Not a workspace artifact: {}",
+            path.display()
+        )));
+    }
+    Ok(canonical)
+}
+
+/// Exempt an artifact from collection.
+pub(crate) fn pin(workspace: &Path, path: &Path) -> Result<PathBuf, OpenSkillError> {
+    let artifact = resolve_artifact(workspace, path)?;
+    std::fs::write(pin_path(&artifact), b"")?;
+    Ok(artifact)
+}
+
+/// Make a pinned artifact collectable again. Unpinning an unpinned artifact
+/// is a no-op.
+pub(crate) fn unpin(workspace: &Path, path: &Path) -> Result<PathBuf, OpenSkillError> {
+    let artifact = resolve_artifact(workspace, path)?;
+    match std::fs::remove_file(pin_path(&artifact)) {
+        Ok(()) => Ok(artifact),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(artifact),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Apply `policy_for(skill)` to every skill's artifacts in `workspace`.
+pub(crate) fn collect<'a>(
+    workspace: &Path,
+    policy_for: impl Fn(&str) -> &'a RetentionPolicy,
+    now: SystemTime,
+) -> std::io::Result<GcReport> {
+    let root = workspace.join(ARTIFACTS_DIR);
+    let mut report = GcReport::default();
+    for skill in subdirectory_names(&root)? {
+        let dir = root.join(&skill);
+        report.merge(collect_skill(&dir, &skill, policy_for(&skill), now)?);
+    }
+    Ok(report)
+}
+
+/// Apply `policy` to the artifacts of one skill.
+pub(crate) fn collect_skill(
+    dir: &Path,
+    skill: &str,
+    policy: &RetentionPolicy,
+    now: SystemTime,
+) -> std::io::Result<GcReport> {
+    let mut report = GcReport::default();
+    if policy.is_keep_all() {
+        return Ok(report);
+    }
+
+    let mut candidates: Vec<ArtifactInfo> = list_skill(dir, skill)?
+        .into_iter()
+        .filter(|a| !a.pinned)
+        .collect();
+    // Newest first: whatever is kept is a prefix of this list.
+    candidates.sort_by_key(|a| std::cmp::Reverse(a.modified));
+
+    let mut kept_bytes = 0u64;
+    for (index, artifact) in candidates.into_iter().enumerate() {
+        let expired = policy.max_age.is_some_and(|max_age| {
+            now.duration_since(artifact.modified)
+                .map(|age| age > max_age)
+                .unwrap_or(false)
+        });
+        let over_count = policy.max_count.is_some_and(|max| index >= max);
+        let over_bytes = policy
+            .max_bytes
+            .is_some_and(|max| kept_bytes + artifact.size > max);
+
+        if expired || over_count || over_bytes {
+            match std::fs::remove_file(&artifact.path) {
+                Ok(()) => {
+                    report.freed_bytes += artifact.size;
+                    report.removed.push(artifact.path);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        } else {
+            kept_bytes += artifact.size;
+        }
+    }
+    Ok(report)
+}
+
+/// Remove session workspaces under `root` not modified within `max_age`.
+///
+/// `current` (the running session) and sessions holding pinned artifacts
+/// are kept. Only workspaces of generated `session-<uuid>` IDs are touched;
+/// host-chosen session IDs are left to the host.
+pub(crate) fn collect_sessions(
+    root: &Path,
+    current: &str,
+    max_age: Duration,
+    now: SystemTime,
+) -> std::io::Result<GcReport> {
+    let mut report = GcReport::default();
+    let current = session_dir_name(current);
+    for name in subdirectory_names(root)? {
+        if name == current || !is_generated_session_dir(&name) {
+            continue;
+        }
+        remove_if_stale(root.join(&name), max_age, now, &mut report)?;
+    }
+    Ok(report)
+}
+
+/// Remove conversation workspaces under `root` not used within `max_age`.
+///
+/// `current` (the workspace in use) and conversations holding pinned
+/// artifacts are kept.
+pub(crate) fn collect_conversations(
+    root: &Path,
+    current: Option<&Path>,
+    max_age: Duration,
+    now: SystemTime,
+) -> std::io::Result<GcReport> {
+    let mut report = GcReport::default();
+    let root = root.join(CONVERSATIONS_DIR);
+    for name in subdirectory_names(&root)? {
+        let dir = root.join(&name);
+        if Some(dir.as_path()) != current {
+            remove_if_stale(dir, max_age, now, &mut report)?;
+        }
+    }
+    Ok(report)
+}
+
+/// Remove the workspace `dir` if nothing in it changed within `max_age` and
+/// it holds no pinned artifacts.
+fn remove_if_stale(
+    dir: PathBuf,
+    max_age: Duration,
+    now: SystemTime,
+    report: &mut GcReport,
+) -> std::io::Result<()> {
+    let stale = latest_modification(&dir)
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age > max_age);
+    if stale && !list(&dir, None)?.iter().any(|a| a.pinned) {
+        std::fs::remove_dir_all(&dir)?;
+        report.removed_sessions.push(dir);
+    }
+    Ok(())
+}
+
+/// Most recent modification time of `dir` or anything beneath it.
+fn latest_modification(dir: &Path) -> Option<SystemTime> {
+    let mut latest = std::fs::metadata(dir).ok()?.modified().ok()?;
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let modified = if file_type.is_dir() {
+            latest_modification(&entry.path())
+        } else {
+            entry.metadata().ok().and_then(|m| m.modified().ok())
+        };
+        if let Some(modified) = modified {
+            latest = latest.max(modified);
+        }
+    }
+    Some(latest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_artifact(workspace: &Path, skill: &str, name: &str, size: usize) -> PathBuf {
+        let dir = skill_dir(workspace, skill);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, vec![b'x'; size]).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_list_and_pin() {
+        let ws = TempDir::new().unwrap();
+        let a = write_artifact(ws.path(), "my/skill", "stdout-1.log", 3);
+        assert!(a.starts_with(ws.path().join(".openskills/artifacts/my_skill")));
+
+        let listed = list(ws.path(), None).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].skill, "my_skill");
+        assert!(!listed[0].pinned);
+
+        pin(ws.path(), &a).unwrap();
+        let listed = list(ws.path(), Some("my/skill")).unwrap();
+        assert_eq!(listed.len(), 1, "pin sidecar must not be listed");
+        assert!(listed[0].pinned);
+
+        unpin(ws.path(), &a).unwrap();
+        unpin(ws.path(), &a).unwrap();
+        assert!(!list(ws.path(), None).unwrap()[0].pinned);
+    }
+
+    #[test]
+    fn test_pin_rejects_paths_outside_artifacts() {
+        let ws = TempDir::new().unwrap();
+        let other = ws.path().join("result.txt");
+        std::fs::write(&other, "x").unwrap();
+        assert!(pin(ws.path(), &other).is_err());
+        assert!(pin(ws.path(), &ws.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_collect_by_age_and_count() {
+        let ws = TempDir::new().unwrap();
+        let old = write_artifact(ws.path(), "s", "a.log", 1);
+        let pinned = write_artifact(ws.path(), "s", "b.log", 1);
+        pin(ws.path(), &pinned).unwrap();
+
+        let keep_all = RetentionPolicy::default();
+        let report = collect(ws.path(), |_| &keep_all, SystemTime::now()).unwrap();
+        assert!(report.removed.is_empty());
+
+        // Everything is "old" an hour from now; only the pinned file survives.
+        let policy = RetentionPolicy {
+            max_age: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        let report = collect(ws.path(), |_| &policy, later).unwrap();
+        assert_eq!(report.removed, vec![old]);
+        assert!(pinned.exists());
+
+        for i in 0..3 {
+            write_artifact(ws.path(), "s", &format!("n{}.log", i), 10);
+        }
+        let policy = RetentionPolicy {
+            max_count: Some(1),
+            ..Default::default()
+        };
+        let report = collect(ws.path(), |_| &policy, SystemTime::now()).unwrap();
+        assert_eq!(report.removed.len(), 2);
+        assert_eq!(report.freed_bytes, 20);
+        assert_eq!(list(ws.path(), Some("s")).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_collect_by_bytes() {
+        let ws = TempDir::new().unwrap();
+        write_artifact(ws.path(), "s", "a.log", 60);
+        write_artifact(ws.path(), "s", "b.log", 60);
+        let policy = RetentionPolicy {
+            max_bytes: Some(100),
+            ..Default::default()
+        };
+        let report = collect(ws.path(), |_| &policy, SystemTime::now()).unwrap();
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.freed_bytes, 60);
+    }
+
+    #[test]
+    fn test_session_ids_that_sanitize_alike_get_separate_workspaces() {
+        let root = Path::new("/ws");
+        for (a, b) in [("a.b", "a_b"), ("x/y", "x_y")] {
+            let (dir_a, dir_b) = (session_dir(root, a), session_dir(root, b));
+            assert_ne!(dir_a, dir_b);
+            assert_eq!(dir_a.parent(), Some(root));
+            let name = dir_a.file_name().unwrap().to_str().unwrap().to_string();
+            assert!(name.starts_with(&format!("{}-", sanitize_skill_id(a))), "{name}");
+        }
+        assert_eq!(session_dir(root, "conv-1"), session_dir(root, "conv-1"));
+    }
+
+    #[test]
+    fn test_collect_sessions() {
+        let root = TempDir::new().unwrap();
+        let id = || format!("session-{}", uuid::Uuid::now_v7());
+        let current_id = id();
+        let current = session_dir(root.path(), &current_id);
+        let stale = session_dir(root.path(), &id());
+        let pinned = session_dir(root.path(), &id());
+        let unrelated = root.path().join("keep-me");
+        let host_chosen = session_dir(root.path(), "session-foo");
+        let malformed = root.path().join(format!("{}-0000000000000000", id()));
+        for dir in [&current, &stale, &pinned, &unrelated, &host_chosen, &malformed] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let artifact = write_artifact(&pinned, "s", "a.log", 1);
+        pin(&pinned, &artifact).unwrap();
+
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        let report =
+            collect_sessions(root.path(), &current_id, Duration::from_secs(60), later).unwrap();
+        assert_eq!(report.removed_sessions, vec![stale.clone()]);
+        assert!(!stale.exists());
+        assert!(current.exists() && pinned.exists() && unrelated.exists());
+        assert!(host_chosen.exists() && malformed.exists());
+
+        let report =
+            collect_sessions(root.path(), &current_id, Duration::from_secs(60), SystemTime::now())
+                .unwrap();
+        assert!(report.removed_sessions.is_empty());
+    }
+
+    #[test]
+    fn test_conversation_workspaces() {
+        let root = TempDir::new().unwrap();
+        let a = conversation_dir(root.path(), "chat/1");
+        assert_eq!(a, conversation_dir(root.path(), "chat/1"));
+        assert_ne!(a, conversation_dir(root.path(), "chat_1"));
+        assert!(a.starts_with(root.path().join(CONVERSATIONS_DIR)));
+        assert!(a.file_name().unwrap().to_string_lossy().starts_with("chat_1-"));
+
+        let b = conversation_dir(root.path(), "chat/2");
+        let c = conversation_dir(root.path(), "chat/3");
+        for dir in [&a, &b, &c] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let artifact = write_artifact(&c, "s", "a.log", 1);
+        pin(&c, &artifact).unwrap();
+
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        let report =
+            collect_conversations(root.path(), Some(&a), Duration::from_secs(60), later).unwrap();
+        assert_eq!(report.removed_sessions, vec![b.clone()]);
+        assert!(a.exists() && !b.exists() && c.exists());
+    }
+}
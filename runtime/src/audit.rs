@@ -1,5 +1,7 @@
 use crate::errors::OpenSkillError;
 use crate::sandbox_mode::SandboxMode;
+use crate::sandbox_violation::SandboxViolation;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 
@@ -9,6 +11,66 @@ pub enum ExecutionStatus {
     Failed(String),
     Timeout,
     PermissionDenied,
+    /// The OS sandbox terminated the process (e.g. a seccomp-blocked syscall).
+    SandboxViolation(String),
+    /// The execution succeeded but its output does not match the skill's
+    /// `output-schema`; the reason lists the violations.
+    SchemaViolation(String),
+    /// The skill has no auto-detected script or WASM entry point; the
+    /// caller should follow its instructions instead.
+    InstructionOnly,
+    /// Nothing ran: `ExecutionOptions::dry_run` was set and the output is
+    /// the execution plan.
+    DryRun,
+}
+
+impl ExecutionStatus {
+    /// `success`, `failed`, `timeout`, ... without the reason.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExecutionStatus::Success => "success",
+            ExecutionStatus::Failed(_) => "failed",
+            ExecutionStatus::Timeout => "timeout",
+            ExecutionStatus::PermissionDenied => "permission_denied",
+            ExecutionStatus::SandboxViolation(_) => "sandbox_violation",
+            ExecutionStatus::SchemaViolation(_) => "schema_violation",
+            ExecutionStatus::InstructionOnly => "instruction_only",
+            ExecutionStatus::DryRun => "dry_run",
+        }
+    }
+
+    /// The reason of a `Failed`, `SandboxViolation` or `SchemaViolation`.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            ExecutionStatus::Failed(reason)
+            | ExecutionStatus::SandboxViolation(reason)
+            | ExecutionStatus::SchemaViolation(reason) => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Parse a status written as its [`label`](Self::label), followed by
+    /// `:<reason>` for the statuses that carry one (`failed:<reason>`).
+    /// `None` for unknown labels and a missing or unexpected reason.
+    pub fn parse(status: &str) -> Option<Self> {
+        let (label, reason) = match status.split_once(':') {
+            Some((label, reason)) => (label, Some(reason)),
+            None => (status, None),
+        };
+        let r = reason.unwrap_or_default().to_string();
+        [
+            ExecutionStatus::Success,
+            ExecutionStatus::Failed(r.clone()),
+            ExecutionStatus::Timeout,
+            ExecutionStatus::PermissionDenied,
+            ExecutionStatus::SandboxViolation(r.clone()),
+            ExecutionStatus::SchemaViolation(r),
+            ExecutionStatus::InstructionOnly,
+            ExecutionStatus::DryRun,
+        ]
+        .into_iter()
+        .find(|status| status.label() == label && status.reason().is_some() == reason.is_some())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -25,8 +87,99 @@ pub struct AuditRecord {
     pub stderr: String,
     /// Effective OS sandbox mode for this execution (`enforce` or `disabled`).
     pub sandbox_mode: SandboxMode,
+    /// Operations the OS sandbox blocked, recovered from stderr (best effort).
+    pub sandbox_violations: Vec<SandboxViolation>,
+    /// Fuel a WASM execution consumed; `None` for native scripts.
+    pub fuel_consumed: Option<u64>,
+    /// Outbound HTTP requests a WASM skill made through `wasi:http`.
+    pub network_requests: Vec<NetworkRequest>,
+    /// Host functions a WASM skill called (`openskills:skill/host`).
+    pub host_calls: Vec<HostCall>,
+    /// Tools the skill asked for that the sandbox did not (fully) grant.
+    pub capability_downgrades: Vec<CapabilityDowngrade>,
+    /// 1 for the first run; higher when `ExecutionOptions::retry` ran the
+    /// skill again.
+    pub attempt: u32,
+    /// W3C `traceparent` the caller passed in `ExecutionOptions`, to find
+    /// the run in the embedding application's distributed traces.
+    pub traceparent: Option<String>,
+}
+
+/// An outbound HTTP request made by a WASM skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkRequest {
+    pub method: String,
+    pub url: String,
+    pub host: String,
+    pub port: u16,
+    /// Whether the host allowlist let the request through.
+    pub allowed: bool,
+}
+
+/// A host function call made by a WASM skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostCall {
+    /// `read-workspace-file`, `write-workspace-file`, `log` or `http-fetch`.
+    pub function: String,
+    /// Workspace path, URL or log level.
+    pub target: String,
+    /// Whether the skill's permissions allowed the call.
+    pub allowed: bool,
+}
+
+/// A requested tool that was stripped, or only partly granted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityDowngrade {
+    pub tool: String,
+    pub reason: DowngradeReason,
+    /// Human-readable explanation, e.g. what the platform could not provide.
+    pub detail: String,
+}
+
+/// Why a tool was not granted, or only partly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DowngradeReason {
+    /// The host policy denies it (deny list, or `fallback: deny`).
+    PolicyDenied,
+    /// The permission callback refused it.
+    PermissionDenied,
+    /// Approved, but the capability mapping grants nothing for it.
+    Unmapped,
+    /// Approved, but the execution mode or platform cannot provide part of
+    /// what it grants (e.g. subprocesses in WASM).
+    Platform,
+    /// Approved by the permission callback for writes inside the workspace
+    /// only; the sandbox gets no other writable paths.
+    WorkspaceOnly,
+}
+
+impl DowngradeReason {
+    /// `policy_denied`, `permission_denied`, `unmapped`, `platform` or
+    /// `workspace_only`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DowngradeReason::PolicyDenied => "policy_denied",
+            DowngradeReason::PermissionDenied => "permission_denied",
+            DowngradeReason::Unmapped => "unmapped",
+            DowngradeReason::Platform => "platform",
+            DowngradeReason::WorkspaceOnly => "workspace_only",
+        }
+    }
+}
+
+impl CapabilityDowngrade {
+    pub(crate) fn new(tool: &str, reason: DowngradeReason, detail: impl Into<String>) -> Self {
+        Self {
+            tool: tool.to_string(),
+            reason,
+            detail: detail.into(),
+        }
+    }
 }
 
+/// Receives the audit record of every execution (see
+/// `OpenSkillRuntime::with_audit_sink`).
 pub trait AuditSink {
     fn record(&self, record: &AuditRecord);
 }
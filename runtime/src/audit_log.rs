@@ -0,0 +1,286 @@
+//! Persistent audit log and per-skill usage statistics.
+//!
+//! [`AuditLog`] is an [`AuditSink`] that appends one JSON line per execution
+//! to a file, so the record of which skills ran, for how long and how they
+//! ended outlives the runtime. [`skill_usage`] turns such a log back into a
+//! table per skill (executions, failure rate, durations) for reviewing which
+//! skills are used and which keep failing; `openskills stats export` prints
+//! it as CSV or JSON.
+
+use crate::audit::{AuditRecord, AuditSink, ExecutionStatus};
+use crate::errors::OpenSkillError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One line of an audit log: the parts of an [`AuditRecord`] worth keeping.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    pub skill_id: String,
+    pub version: String,
+    pub start_time_ms: u64,
+    pub duration_ms: u64,
+    /// `success`, `failed`, `timeout`, `permission_denied`,
+    /// `sandbox_violation`, `schema_violation`, `instruction_only` or
+    /// `dry_run`.
+    pub status: String,
+    /// Why a `failed`, `sandbox_violation` or `schema_violation` execution
+    /// ended that way.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default = "first_attempt")]
+    pub attempt: u32,
+}
+
+fn first_attempt() -> u32 {
+    1
+}
+
+impl From<&AuditRecord> for AuditLogEntry {
+    fn from(record: &AuditRecord) -> Self {
+        let reason = match &record.exit_status {
+            ExecutionStatus::Failed(reason)
+            | ExecutionStatus::SandboxViolation(reason)
+            | ExecutionStatus::SchemaViolation(reason) => Some(reason.clone()),
+            _ => None,
+        };
+        Self {
+            skill_id: record.skill_id.clone(),
+            version: record.version.clone(),
+            start_time_ms: record.start_time_ms,
+            duration_ms: record.duration_ms,
+            status: record.exit_status.label().to_string(),
+            reason,
+            attempt: record.attempt,
+        }
+    }
+}
+
+/// Appends every execution's audit record to a JSON-lines file (see
+/// `OpenSkillRuntime::with_audit_sink`).
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Open `path` for appending, creating it and its directory if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, OpenSkillError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AuditSink for AuditLog {
+    fn record(&self, record: &AuditRecord) {
+        let mut line = match serde_json::to_vec(&AuditLogEntry::from(record)) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Warning: could not encode audit record for '{}': {}", record.skill_id, e);
+                return;
+            }
+        };
+        line.push(b'\n');
+        // One write per line, so concurrent runtimes appending to the same
+        // log do not interleave records.
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = file.write_all(&line) {
+            eprintln!(
+                "Warning: could not append to audit log {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// Read the entries of an audit log written by [`AuditLog`].
+pub fn read_audit_log(path: impl AsRef<Path>) -> Result<Vec<AuditLogEntry>, OpenSkillError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                OpenSkillError::InvalidConfig(format!(
+                    "{}:{}: invalid audit log entry: {}",
+                    path.display(),
+                    index + 1,
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Usage of one skill over an audit log (see [`skill_usage`]).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillUsage {
+    pub skill_id: String,
+    /// Version of the most recent execution.
+    pub version: String,
+    /// Execution attempts; a retried execution counts once per attempt.
+    pub executions: u64,
+    /// Attempts that did not succeed (timeouts included).
+    pub failures: u64,
+    pub timeouts: u64,
+    /// `failures / executions`.
+    pub failure_rate: f64,
+    pub mean_duration_ms: u64,
+    pub p95_duration_ms: u64,
+    /// Start of the most recent execution, in ms since the Unix epoch.
+    pub last_run_ms: u64,
+}
+
+/// Per-skill usage from `entries`, most executed skills first. Dry runs are
+/// left out, since nothing ran.
+pub fn skill_usage(entries: &[AuditLogEntry]) -> Vec<SkillUsage> {
+    let mut by_skill: BTreeMap<&str, Vec<&AuditLogEntry>> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.status != "dry_run") {
+        by_skill.entry(&entry.skill_id).or_default().push(entry);
+    }
+
+    let mut usage: Vec<SkillUsage> = by_skill
+        .into_iter()
+        .map(|(skill_id, runs)| {
+            let latest = runs.iter().max_by_key(|e| e.start_time_ms).expect("at least one run");
+            let failures = runs
+                .iter()
+                .filter(|e| !matches!(e.status.as_str(), "success" | "instruction_only"))
+                .count() as u64;
+            let timeouts = runs.iter().filter(|e| e.status == "timeout").count() as u64;
+            let mut durations: Vec<u64> = runs.iter().map(|e| e.duration_ms).collect();
+            durations.sort_unstable();
+            let executions = runs.len() as u64;
+            // Nearest-rank percentile.
+            let p95_index = (durations.len() * 95).div_ceil(100) - 1;
+            SkillUsage {
+                skill_id: skill_id.to_string(),
+                version: latest.version.clone(),
+                executions,
+                failures,
+                timeouts,
+                failure_rate: failures as f64 / executions as f64,
+                mean_duration_ms: durations.iter().sum::<u64>() / executions,
+                p95_duration_ms: durations[p95_index],
+                last_run_ms: latest.start_time_ms,
+            }
+        })
+        .collect();
+    usage.sort_by(|a, b| b.executions.cmp(&a.executions).then_with(|| a.skill_id.cmp(&b.skill_id)));
+    usage
+}
+
+/// `usage` as CSV, with a header row.
+pub fn skill_usage_csv(usage: &[SkillUsage]) -> String {
+    let mut csv = String::from(
+        "skill_id,version,executions,failures,timeouts,failure_rate,mean_duration_ms,p95_duration_ms,last_run_ms\n",
+    );
+    for row in usage {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{:.3},{},{},{}\n",
+            csv_field(&row.skill_id),
+            csv_field(&row.version),
+            row.executions,
+            row.failures,
+            row.timeouts,
+            row.failure_rate,
+            row.mean_duration_ms,
+            row.p95_duration_ms,
+            row.last_run_ms,
+        ));
+    }
+    csv
+}
+
+/// Quote `value` if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(skill_id: &str, status: &str, start_time_ms: u64, duration_ms: u64) -> AuditLogEntry {
+        AuditLogEntry {
+            skill_id: skill_id.to_string(),
+            version: format!("1.0.{start_time_ms}"),
+            start_time_ms,
+            duration_ms,
+            status: status.to_string(),
+            reason: None,
+            attempt: 1,
+        }
+    }
+
+    #[test]
+    fn test_skill_usage() {
+        let mut entries = vec![
+            entry("pdf", "success", 3, 100),
+            entry("pdf", "timeout", 1, 30_000),
+            entry("pdf", "success", 2, 200),
+            entry("pdf", "failed", 4, 300),
+            entry("pdf", "dry_run", 5, 1),
+            entry("docx", "instruction_only", 7, 0),
+        ];
+        entries.push(entry("xlsx", "success", 6, 50));
+
+        let usage = skill_usage(&entries);
+        assert_eq!(
+            usage.iter().map(|u| u.skill_id.as_str()).collect::<Vec<_>>(),
+            ["pdf", "docx", "xlsx"]
+        );
+        let pdf = &usage[0];
+        assert_eq!(pdf.version, "1.0.4");
+        assert_eq!((pdf.executions, pdf.failures, pdf.timeouts), (4, 2, 1));
+        assert_eq!(pdf.failure_rate, 0.5);
+        assert_eq!(pdf.mean_duration_ms, 7650);
+        assert_eq!(pdf.p95_duration_ms, 30_000);
+        assert_eq!(pdf.last_run_ms, 4);
+        assert_eq!(usage[1].failures, 0);
+
+        let csv = skill_usage_csv(&usage);
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("skill_id,version,executions,"));
+        assert_eq!(lines.next(), Some("pdf,1.0.4,4,2,1,0.500,7650,30000,4"));
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn test_audit_log_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs/audit.jsonl");
+        let entries = [entry("pdf", "success", 1, 10), entry("pdf", "failed", 2, 20)];
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let lines: Vec<String> = entries.iter().map(|e| serde_json::to_string(e).unwrap()).collect();
+        fs::write(&path, lines.join("\n") + "\n\n").unwrap();
+        assert_eq!(read_audit_log(&path).unwrap(), entries);
+
+        fs::write(&path, "{\"skill_id\": \"pdf\"}\n").unwrap();
+        let err = read_audit_log(&path).unwrap_err().to_string();
+        assert!(err.contains("audit.jsonl:1"), "{err}");
+    }
+}
@@ -3,11 +3,16 @@
 //! Claude Skills compatible runtime with WASM sandbox.
 
 use openskills_runtime::{
-    analyze_skill_tokens, validate_skill_path, ExecutionOptions,
-    OpenSkillRuntime,
+    analyze_skill_tokens, features, read_audit_log, skill_digest, skill_usage, skill_usage_csv,
+    validate_skill_path, verify_skill, AuditLog, CacheKind, CacheLimits, CacheManager,
+    CapabilityMapping, ExecutionOptions, HostPolicy, InstallSource, InstalledSkill,
+    OpenSkillRuntime, RetryPolicy, RuntimeExecutionStatus, SignatureStatus, SkillInstaller,
+    TrustedKey, LOCKFILE_NAME, UNVERSIONED,
 };
 #[cfg(feature = "build-tool")]
 use openskills_runtime::{build_skill, BuildConfig};
+#[cfg(feature = "oci")]
+use openskills_runtime::{package_skill, OciClient, OciReference};
 use serde_json::Value;
 use std::env;
 use std::fs;
@@ -19,33 +24,79 @@ fn print_usage() {
     eprintln!("OpenSkills Runtime - Claude Skills compatible with WASM sandbox");
     eprintln!();
     eprintln!("Usage:");
-    eprintln!("  openskills discover [--project-root <path>]");
+    eprintln!("  openskills discover [--project-root <path>]...");
     eprintln!("  openskills list [--dir <path>]");
     eprintln!("  openskills activate <skill-id> [--dir <path>]");
+    eprintln!("  openskills targets <skill-id> [--dir <path>]");
     eprintln!("  openskills execute <skill-id> [options]");
+    eprintln!("  openskills example <skill-id> [<name>] [--dir <path>] [--policy <file>]");
     eprintln!("  openskills build [<skill-path>] [options]");
     eprintln!("  openskills validate <skill-path> [options]");
+    eprintln!("  openskills verify <skill-path> [--trusted-key <file>]... [--digest] [--json]");
     eprintln!("  openskills analyze <skill-path> [options]");
+    eprintln!("  openskills lock [--check] [--project-root <path> | --dir <path>] [--lockfile <file>] [--json]");
+    eprintln!("  openskills cache ls|clear [<kind>] [--cache-dir <path>]");
+    eprintln!("  openskills cache gc [--max-size <size>] [--kind-max <kind>=<size>] [--cache-dir <path>]");
+    eprintln!("  openskills stats export --audit-log <file> [--format csv|json] [--output <file>]");
+    eprintln!("  openskills --features [--json]");
+    eprintln!("  openskills install <git-url> [--subdir <path>] [--ref <branch|tag>] [--name <skill-id>] [--dir <path>]");
+    eprintln!("  openskills update|uninstall <skill-id> [--dir <path>]");
+    eprintln!("  openskills package <skill-path> [--output <file>] [--oci <reference>] [--username <user> --password-stdin]");
+    eprintln!("  openskills pull <oci-reference> [--username <user> --password-stdin]");
     eprintln!();
     eprintln!("Commands:");
     eprintln!("  discover      Discover skills from standard locations (~/.claude/skills/, .claude/skills/)");
     eprintln!("  list          List skills from a specific directory");
     eprintln!("  activate      Load full skill content (SKILL.md instructions)");
+    eprintln!("  targets       List a skill's WASM modules and scripts with their sandbox");
     eprintln!("  execute       Execute a skill's WASM or native script in sandbox");
+    eprintln!("  example       Run a named example from a skill's `examples:` as a smoke test, or list them");
     eprintln!("  build         Compile TypeScript/JavaScript skill to WASM component");
     eprintln!("  validate      Validate a skill's format and structure");
+    eprintln!("  verify        Check a skill's signature, or print the digest to sign");
     eprintln!("  analyze       Analyze token usage for a skill");
+    eprintln!("  lock          Write openskills.lock for the discovered skills, or check them against it");
+    eprintln!("  cache         List, clear or size-cap the module/blob/venv/node_modules/registry/builtin caches");
+    eprintln!("  stats         Export per-skill usage, duration and failure rates from an audit log");
+    eprintln!("  install       Install a skill from a git repository into ~/.claude/skills/");
+    eprintln!("  update        Re-install an installed skill from its recorded source");
+    eprintln!("  uninstall     Remove a skill installed with `openskills install`");
+    eprintln!("  package       Pack a skill as a .tar.gz, or push it to an OCI registry with --oci");
+    eprintln!("  pull          Pull a skill pushed with `package --oci` into the registry cache");
     eprintln!();
     eprintln!("Options:");
-    eprintln!("  --project-root, -p   Project root for relative path resolution");
-    eprintln!("  --dir, -d            Skills directory (for list/activate)");
+    eprintln!("  --project-root, -p   Project root for relative path resolution (repeat for several roots with discover)");
+    eprintln!("  --dir, -d            Skills directory (for list/activate/targets/install/update/uninstall)");
     eprintln!("  --input, -i          Input JSON string (for execute)");
     eprintln!("  --input-file, -f     Input JSON file path (for execute)");
     eprintln!("  --timeout-ms, -t     Timeout in ms (for execute)");
+    eprintln!("  --max-fuel           Fuel budget for WASM skills (for execute)");
+    eprintln!("  --trusted-key        Minisign or PEM public key skills must be signed with (for execute/verify)");
+    eprintln!("  --capability-mapping TOML file mapping tools to sandbox grants (for execute)");
+    eprintln!("  --policy             Host policy TOML file, default ~/.config/openskills/policy.toml (for execute/example)");
+    eprintln!("  --dry-run            Print what would run and with which sandbox, without running it (for execute)");
+    eprintln!("  --retry              Attempts for timeouts and failures, with exponential backoff (for execute)");
+    eprintln!("  --traceparent        W3C trace context to audit and pass to the skill; defaults to $TRACEPARENT (for execute)");
+    eprintln!("  --audit-log          JSON-lines audit log to append to (for execute) or read (for stats)");
+    eprintln!("  --format             csv or json, default csv (for stats export)");
+    eprintln!("  --digest             Print the message a signature signs (for verify)");
+    eprintln!("  --check              Report drift from the lockfile instead of writing it (for lock)");
+    eprintln!("  --lockfile           Lockfile path, default <project-root>/openskills.lock (for lock)");
     eprintln!("  --force, -f          Force rebuild even if WASM is up to date (for build)");
-    eprintln!("  --verbose, -v        Verbose output (for build)");
+    eprintln!("  --verbose, -v        Verbose output (for build; sandbox setup timings for execute)");
     eprintln!("  --warnings           Show validation warnings");
+    eprintln!("  --max-size           Total cache size cap, e.g. 2G (for cache gc)");
+    eprintln!("  --kind-max           Cap for one cache, e.g. modules=500M (for cache gc)");
+    eprintln!("  --cache-dir          Cache root, default ~/.cache/openskills (for cache)");
+    eprintln!("  --subdir             Skill directory inside the repository (for install)");
+    eprintln!("  --ref                Branch or tag to install (for install)");
+    eprintln!("  --name               Skill ID to install as (for install)");
+    eprintln!("  --output, -o         Tarball path, default <skill-id>.tar.gz (for package); file to write (for stats export)");
+    eprintln!("  --oci                Push to this OCI reference, e.g. ghcr.io/acme/pdf:1.0.0 (for package)");
+    eprintln!("  --username           Registry user; the password is read from stdin (for package/pull)");
+    eprintln!("  --password-stdin     Read the registry password from stdin (for package/pull)");
     eprintln!("  --json               Output as JSON");
+    eprintln!("  --features           List the optional features this binary was built with");
     eprintln!("  --help, -h           Show help");
 }
 
@@ -63,10 +114,29 @@ fn main() {
         "discover" => cmd_discover(&args[2..]),
         "list" => cmd_list(&args[2..]),
         "activate" => cmd_activate(&args[2..]),
+        "targets" => cmd_targets(&args[2..]),
         "execute" => cmd_execute(&args[2..]),
+        "example" => cmd_example(&args[2..]),
         "build" => cmd_build(&args[2..]),
         "validate" => cmd_validate(&args[2..]),
+        "verify" => cmd_verify(&args[2..]),
         "analyze" => cmd_analyze(&args[2..]),
+        "lock" => cmd_lock(&args[2..]),
+        "cache" => cmd_cache(&args[2..]),
+        "stats" => cmd_stats(&args[2..]),
+        "install" => cmd_install(&args[2..]),
+        "update" | "uninstall" => cmd_update_or_uninstall(command, &args[2..]),
+        "package" => cmd_package(&args[2..]),
+        "pull" => cmd_pull(&args[2..]),
+        "--features" => {
+            if args.get(2).is_some_and(|a| a == "--json") {
+                println!("{}", serde_json::to_string_pretty(&features()).unwrap_or_default());
+            } else {
+                for feature in features() {
+                    println!("{}", feature);
+                }
+            }
+        }
         "--help" | "-h" => {
             print_usage();
         }
@@ -79,7 +149,7 @@ fn main() {
 }
 
 fn cmd_discover(args: &[String]) {
-    let mut project_root: Option<String> = None;
+    let mut project_roots: Vec<String> = Vec::new();
     let mut json_output = false;
 
     let mut i = 0;
@@ -87,7 +157,7 @@ fn cmd_discover(args: &[String]) {
         match args[i].as_str() {
             "--project-root" | "-p" => {
                 i += 1;
-                project_root = args.get(i).cloned();
+                project_roots.extend(args.get(i).cloned());
             }
             "--json" => {
                 json_output = true;
@@ -100,10 +170,13 @@ fn cmd_discover(args: &[String]) {
         i += 1;
     }
 
-    let mut runtime = match project_root {
-        Some(ref root) => OpenSkillRuntime::with_project_root(root),
+    let mut runtime = match project_roots.first() {
+        Some(root) => OpenSkillRuntime::with_project_root(root),
         None => OpenSkillRuntime::new(),
     };
+    for root in project_roots.iter().skip(1) {
+        runtime.add_project_root(root);
+    }
 
     match runtime.discover_skills() {
         Ok(skills) => {
@@ -120,7 +193,16 @@ fn cmd_discover(args: &[String]) {
                     println!("Discovered {} skill(s):", skills.len());
                     for s in skills {
                         let invocable = if s.user_invocable { "" } else { " [hidden]" };
-                        println!("  {} ({:?}){}", s.id, s.location, invocable);
+                        match (&s.project_root, project_roots.len() > 1) {
+                            (Some(root), true) => println!(
+                                "  {} ({:?}, {}){}",
+                                s.id,
+                                s.location,
+                                root.display(),
+                                invocable
+                            ),
+                            _ => println!("  {} ({:?}){}", s.id, s.location, invocable),
+                        }
                         println!("    {}", s.description);
                     }
                 }
@@ -169,9 +251,77 @@ fn cmd_list(args: &[String]) {
         } else {
             println!("Skills in {}:", dir);
             for s in skills {
-                println!("  {}: {}", s.id, s.description);
+                if s.version == UNVERSIONED {
+                    println!("  {}: {}", s.id, s.description);
+                } else {
+                    println!("  {}@{}: {}", s.id, s.version, s.description);
+                }
+            }
+        }
+    }
+}
+
+fn cmd_targets(args: &[String]) {
+    let mut skill_id: Option<String> = None;
+    let mut dir: Option<String> = None;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dir" | "-d" => {
+                i += 1;
+                dir = args.get(i).cloned();
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && skill_id.is_none() => {
+                skill_id = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let skill_id = skill_id.unwrap_or_else(|| {
+        eprintln!("Missing skill ID");
+        print_usage();
+        process::exit(1);
+    });
+
+    let mut runtime = match dir {
+        Some(ref d) => OpenSkillRuntime::from_directory(d),
+        None => OpenSkillRuntime::new(),
+    };
+
+    if dir.is_none() {
+        if let Err(e) = runtime.discover_skills() {
+            eprintln!("Error discovering skills: {}", e);
+            process::exit(1);
+        }
+    }
+
+    match runtime.list_skill_targets(&skill_id) {
+        Ok(targets) => {
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&targets).unwrap_or_default());
+            } else if targets.is_empty() {
+                println!("Skill '{}' is instruction-only (nothing to execute)", skill_id);
+            } else {
+                println!("Targets in {}:", skill_id);
+                for t in targets {
+                    println!("  {} ({}, {} sandbox)", t.path, t.kind, t.sandbox);
+                }
             }
         }
+        Err(err) => {
+            eprintln!("Error listing targets for '{}': {}", skill_id, err);
+            process::exit(1);
+        }
     }
 }
 
@@ -269,6 +419,15 @@ fn cmd_execute(args: &[String]) {
     let mut input_json: Option<String> = None;
     let mut input_file: Option<String> = None;
     let mut timeout_ms: Option<u64> = None;
+    let mut max_fuel: Option<u64> = None;
+    let mut trusted_keys: Vec<TrustedKey> = Vec::new();
+    let mut capability_mapping = CapabilityMapping::default();
+    let mut policy: Option<HostPolicy> = None;
+    let mut dry_run = false;
+    let mut retry: Option<RetryPolicy> = None;
+    let mut traceparent = std::env::var("TRACEPARENT").ok();
+    let mut audit_log: Option<String> = None;
+    let mut verbose = false;
 
     let mut i = 0;
     while i < args.len() {
@@ -289,6 +448,46 @@ fn cmd_execute(args: &[String]) {
                 i += 1;
                 timeout_ms = args.get(i).and_then(|v| v.parse().ok());
             }
+            "--max-fuel" => {
+                i += 1;
+                max_fuel = args.get(i).and_then(|v| v.parse().ok());
+            }
+            "--trusted-key" => {
+                i += 1;
+                trusted_keys.push(load_trusted_key(args.get(i)));
+            }
+            "--capability-mapping" => {
+                i += 1;
+                capability_mapping = load_capability_mapping(args.get(i));
+            }
+            "--policy" => {
+                i += 1;
+                policy = Some(load_policy(args.get(i)));
+            }
+            "--dry-run" => {
+                dry_run = true;
+            }
+            "--retry" => {
+                i += 1;
+                retry = args
+                    .get(i)
+                    .and_then(|v| v.parse().ok())
+                    .map(|max_attempts| RetryPolicy {
+                        max_attempts,
+                        ..Default::default()
+                    });
+            }
+            "--traceparent" => {
+                i += 1;
+                traceparent = args.get(i).cloned();
+            }
+            "--audit-log" => {
+                i += 1;
+                audit_log = args.get(i).cloned();
+            }
+            "--verbose" | "-v" => {
+                verbose = true;
+            }
             arg if !arg.starts_with('-') && skill_id.is_none() => {
                 skill_id = Some(arg.to_string());
             }
@@ -324,7 +523,25 @@ fn cmd_execute(args: &[String]) {
     let mut runtime = match dir {
         Some(ref d) => OpenSkillRuntime::from_directory(d),
         None => OpenSkillRuntime::new(),
-    };
+    }
+    .with_trusted_keys(trusted_keys)
+    .with_capability_mapping(capability_mapping);
+    let policy = policy.or_else(|| {
+        HostPolicy::load_default().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        })
+    });
+    if let Some(policy) = policy {
+        runtime.set_host_policy(policy);
+    }
+    if let Some(path) = audit_log {
+        let log = AuditLog::open(&path).unwrap_or_else(|e| {
+            eprintln!("Cannot open audit log {}: {}", path, e);
+            process::exit(1);
+        });
+        runtime.set_audit_sink(Box::new(log));
+    }
 
     // Discover if using standard locations
     if dir.is_none() {
@@ -337,7 +554,14 @@ fn cmd_execute(args: &[String]) {
     let options = ExecutionOptions {
         timeout_ms,
         memory_mb: None,
+        cpu_quota: None,
+        max_fuel,
         input: Some(input),
+        sandbox_overrides: Default::default(),
+        effective_tools: None,
+        dry_run,
+        retry,
+        traceparent,
     };
 
     match runtime.execute_skill(&skill_id, options) {
@@ -349,6 +573,17 @@ fn cmd_execute(args: &[String]) {
             if !result.stderr.is_empty() {
                 eprintln!("[stderr]\n{}", result.stderr);
             }
+            if verbose && !result.setup_timings.backend.is_empty() {
+                let timings = &result.setup_timings;
+                eprintln!(
+                    "[setup] {} backend, {:.1} ms",
+                    timings.backend,
+                    timings.total().as_secs_f64() * 1000.0
+                );
+                for phase in &timings.phases {
+                    eprintln!("  {:<20} {:>8.1} ms", phase.name, phase.duration.as_secs_f64() * 1000.0);
+                }
+            }
         }
         Err(err) => {
             eprintln!("Execution failed: {}", err);
@@ -357,6 +592,104 @@ fn cmd_execute(args: &[String]) {
     }
 }
 
+fn cmd_example(args: &[String]) {
+    let mut skill_id: Option<String> = None;
+    let mut name: Option<String> = None;
+    let mut dir: Option<String> = None;
+    let mut policy: Option<HostPolicy> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dir" | "-d" => {
+                i += 1;
+                dir = args.get(i).cloned();
+            }
+            "--policy" => {
+                i += 1;
+                policy = Some(load_policy(args.get(i)));
+            }
+            arg if !arg.starts_with('-') && skill_id.is_none() => {
+                skill_id = Some(arg.to_string());
+            }
+            arg if !arg.starts_with('-') && name.is_none() => {
+                name = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let skill_id = skill_id.unwrap_or_else(|| {
+        eprintln!("Missing skill ID");
+        print_usage();
+        process::exit(1);
+    });
+
+    let mut runtime = match dir {
+        Some(ref d) => OpenSkillRuntime::from_directory(d),
+        None => OpenSkillRuntime::new(),
+    };
+    let policy = policy.or_else(|| {
+        HostPolicy::load_default().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        })
+    });
+    if let Some(policy) = policy {
+        runtime.set_host_policy(policy);
+    }
+
+    if dir.is_none() {
+        if let Err(e) = runtime.discover_skills() {
+            eprintln!("Error discovering skills: {}", e);
+            process::exit(1);
+        }
+    }
+
+    let Some(name) = name else {
+        let card = runtime.get_skill_card(&skill_id).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        });
+        let named: Vec<_> = card.examples.iter().filter(|e| e.name.is_some()).collect();
+        if named.is_empty() {
+            println!("Skill '{}' has no named examples", skill_id);
+        } else {
+            println!("Examples in {}:", skill_id);
+            for example in named {
+                println!("  {:<20} {}", example.name.as_deref().unwrap_or_default(), example.prompt);
+            }
+        }
+        return;
+    };
+
+    match runtime.run_skill_example(&skill_id, &name) {
+        Ok(result) => {
+            println!("{}", serde_json::to_string_pretty(&result.output).unwrap_or_default());
+            if !result.stderr.is_empty() {
+                eprintln!("[stderr]\n{}", result.stderr);
+            }
+            if matches!(result.audit.exit_status, RuntimeExecutionStatus::Success) {
+                eprintln!("Example '{}' of {} passed", name, skill_id);
+            } else {
+                eprintln!(
+                    "Example '{}' of {} failed: {:?}",
+                    name, skill_id, result.audit.exit_status
+                );
+                process::exit(1);
+            }
+        }
+        Err(err) => {
+            eprintln!("Example '{}' of {} failed: {}", name, skill_id, err);
+            process::exit(1);
+        }
+    }
+}
+
 #[cfg(feature = "build-tool")]
 fn cmd_build(args: &[String]) {
     let mut skill_path: Option<String> = None;
@@ -365,6 +698,7 @@ fn cmd_build(args: &[String]) {
     let mut output_file: Option<String> = None;
     let mut plugin: Option<String> = None;
     let mut list_plugins = false;
+    let mut no_sandbox = false;
     let mut plugin_config: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
     let mut i = 0;
@@ -385,6 +719,7 @@ fn cmd_build(args: &[String]) {
                 eprintln!("  --plugin        Build plugin to use (default: auto-detect)");
                 eprintln!("  --list-plugins  List available build plugins and exit");
                 eprintln!("  --plugin-option Plugin option (key=value), can be repeated");
+                eprintln!("  --no-sandbox    Run compilers without the OS sandbox (trusted sources only)");
                 eprintln!("  --help, -h      Show this help message");
                 eprintln!();
                 eprintln!("Examples:");
@@ -432,6 +767,9 @@ fn cmd_build(args: &[String]) {
             "--list-plugins" => {
                 list_plugins = true;
             }
+            "--no-sandbox" => {
+                no_sandbox = true;
+            }
             "--plugin-option" => {
                 i += 1;
                 let raw = match args.get(i) {
@@ -502,6 +840,11 @@ fn cmd_build(args: &[String]) {
         verbose,
         plugin,
         plugin_config,
+        sandbox_mode: if no_sandbox {
+            openskills_runtime::SandboxMode::Disabled
+        } else {
+            openskills_runtime::SandboxMode::Enforce
+        },
     };
 
     match build_skill(config) {
@@ -612,13 +955,55 @@ fn cmd_validate(args: &[String]) {
     }
 }
 
-fn cmd_analyze(args: &[String]) {
+fn load_trusted_key(path: Option<&String>) -> TrustedKey {
+    let Some(path) = path else {
+        eprintln!("--trusted-key needs a key file");
+        process::exit(1);
+    };
+    TrustedKey::from_file(std::path::Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("Invalid trusted key: {}", e);
+        process::exit(1);
+    })
+}
+
+fn load_capability_mapping(path: Option<&String>) -> CapabilityMapping {
+    let Some(path) = path else {
+        eprintln!("--capability-mapping needs a TOML file");
+        process::exit(1);
+    };
+    CapabilityMapping::from_file(std::path::Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    })
+}
+
+fn load_policy(path: Option<&String>) -> HostPolicy {
+    let Some(path) = path else {
+        eprintln!("--policy needs a TOML file");
+        process::exit(1);
+    };
+    HostPolicy::from_file(std::path::Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    })
+}
+
+fn cmd_verify(args: &[String]) {
     let mut skill_path: Option<String> = None;
+    let mut trusted_keys: Vec<TrustedKey> = Vec::new();
+    let mut print_digest = false;
     let mut json_output = false;
 
     let mut i = 0;
     while i < args.len() {
         match args[i].as_str() {
+            "--trusted-key" => {
+                i += 1;
+                trusted_keys.push(load_trusted_key(args.get(i)));
+            }
+            "--digest" => {
+                print_digest = true;
+            }
             "--json" => {
                 json_output = true;
             }
@@ -638,37 +1023,790 @@ fn cmd_analyze(args: &[String]) {
         print_usage();
         process::exit(1);
     });
+    let path = std::path::Path::new(&skill_path);
 
-    let analysis = analyze_skill_tokens(std::path::Path::new(&skill_path));
+    if print_digest {
+        match skill_digest(path) {
+            Ok(digest) => print!("{}", digest),
+            Err(e) => {
+                eprintln!("Failed to digest skill: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+    if trusted_keys.is_empty() {
+        eprintln!("Missing --trusted-key");
+        process::exit(1);
+    }
 
+    let status = verify_skill(path, &trusted_keys);
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&analysis).unwrap_or_default());
+        let output = match &status {
+            SignatureStatus::Verified { key_id } => {
+                serde_json::json!({ "path": skill_path, "status": "verified", "key_id": key_id })
+            }
+            SignatureStatus::Unsigned => serde_json::json!({ "path": skill_path, "status": "unsigned" }),
+            SignatureStatus::Invalid(reason) => {
+                serde_json::json!({ "path": skill_path, "status": "invalid", "reason": reason })
+            }
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
     } else {
-        println!("Token Analysis: {}", skill_path);
-        println!();
-        if let Some(error) = analysis.error.as_ref() {
-            println!("Error: {}", error);
-            process::exit(1);
+        match &status {
+            SignatureStatus::Verified { key_id } => println!("Signature verified: {} (key {})", skill_path, key_id),
+            SignatureStatus::Unsigned => println!("Not signed: {}", skill_path),
+            SignatureStatus::Invalid(reason) => println!("Invalid signature: {}: {}", skill_path, reason),
         }
+    }
+    if !matches!(status, SignatureStatus::Verified { .. }) {
+        process::exit(1);
+    }
+}
 
-        println!("Tier 1 (Metadata):");
-        println!("  Name:        {} chars", analysis.name_len);
-        println!("  Description: {} chars", analysis.description_len);
-        println!("  Estimated:   ~{} tokens", analysis.tier1_tokens);
-        println!();
-        println!("Tier 2 (Instructions):");
-        println!("  Length:      {} chars", analysis.instructions_len);
-        println!("  Estimated:   ~{} tokens", analysis.tier2_tokens);
-        println!();
-        println!("Total:");
-        println!("  Estimated:   ~{} tokens", analysis.total_tokens);
+fn cmd_lock(args: &[String]) {
+    let mut project_root: Option<String> = None;
+    let mut dir: Option<String> = None;
+    let mut lockfile: Option<String> = None;
+    let mut check = false;
+    let mut json_output = false;
 
-        if analysis.tier1_tokens > 150 {
-            println!();
-            println!("Warning: Tier 1 is large, consider shortening description.");
-        }
-        if analysis.tier2_tokens > 1500 {
-            println!("Warning: Tier 2 is large, consider moving content to references.");
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--project-root" | "-p" => {
+                i += 1;
+                project_root = args.get(i).cloned();
+            }
+            "--dir" | "-d" => {
+                i += 1;
+                dir = args.get(i).cloned();
+            }
+            "--lockfile" => {
+                i += 1;
+                lockfile = args.get(i).cloned();
+            }
+            "--check" => {
+                check = true;
+            }
+            "--json" => {
+                json_output = true;
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
         }
+        i += 1;
+    }
+
+    let mut runtime = match (&dir, &project_root) {
+        (Some(d), _) => OpenSkillRuntime::from_directory(d),
+        (None, Some(root)) => OpenSkillRuntime::with_project_root(root),
+        (None, None) => OpenSkillRuntime::new(),
+    };
+    if let Err(e) = runtime.discover_skills() {
+        eprintln!("Error discovering skills: {}", e);
+        process::exit(1);
+    }
+    let lockfile = lockfile.unwrap_or_else(|| {
+        std::path::Path::new(project_root.as_deref().unwrap_or("."))
+            .join(LOCKFILE_NAME)
+            .to_string_lossy()
+            .into_owned()
+    });
+
+    if !check {
+        match runtime.write_lockfile(&lockfile) {
+            Ok(locked) => {
+                if json_output {
+                    println!("{}", serde_json::to_string_pretty(&locked).unwrap_or_default());
+                } else {
+                    println!("Locked {} skill(s) in {}", locked.skills.len(), lockfile);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write lockfile: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let drift = runtime.verify_lockfile(&lockfile).unwrap_or_else(|e| {
+        eprintln!("Failed to verify lockfile: {}", e);
+        process::exit(1);
+    });
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&drift).unwrap_or_default());
+    } else if drift.is_empty() {
+        println!("Skills match {}", lockfile);
+    } else {
+        println!("Skills drifted from {}:", lockfile);
+        for d in &drift {
+            println!("  {}", d);
+        }
+    }
+    if !drift.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn cmd_analyze(args: &[String]) {
+    let mut skill_path: Option<String> = None;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && skill_path.is_none() => {
+                skill_path = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let skill_path = skill_path.unwrap_or_else(|| {
+        eprintln!("Missing skill path");
+        print_usage();
+        process::exit(1);
+    });
+
+    let analysis = analyze_skill_tokens(std::path::Path::new(&skill_path));
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&analysis).unwrap_or_default());
+    } else {
+        println!("Token Analysis: {}", skill_path);
+        println!();
+        if let Some(error) = analysis.error.as_ref() {
+            println!("Error: {}", error);
+            process::exit(1);
+        }
+
+        println!("Tier 1 (Metadata):");
+        println!("  Name:        {} chars", analysis.name_len);
+        println!("  Description: {} chars", analysis.description_len);
+        println!("  Estimated:   ~{} tokens", analysis.tier1_tokens);
+        println!();
+        println!("Tier 2 (Instructions):");
+        println!("  Length:      {} chars", analysis.instructions_len);
+        println!("  Estimated:   ~{} tokens", analysis.tier2_tokens);
+        println!();
+        println!("Total:");
+        println!("  Estimated:   ~{} tokens", analysis.total_tokens);
+
+        if analysis.tier1_tokens > 150 {
+            println!();
+            println!("Warning: Tier 1 is large, consider shortening description.");
+        }
+        if analysis.tier2_tokens > 1500 {
+            println!("Warning: Tier 2 is large, consider moving content to references.");
+        }
+    }
+}
+
+fn cmd_cache(args: &[String]) {
+    let Some(action) = args.first() else {
+        eprintln!("Missing cache action (ls, clear or gc)");
+        print_usage();
+        process::exit(1);
+    };
+    let mut kind: Option<CacheKind> = None;
+    let mut cache_dir: Option<String> = None;
+    let mut limits = CacheLimits::default();
+    let mut json_output = false;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--cache-dir" => {
+                i += 1;
+                cache_dir = args.get(i).cloned();
+            }
+            "--max-size" => {
+                i += 1;
+                limits.max_bytes = Some(parse_size_arg(args.get(i)));
+            }
+            "--kind-max" => {
+                i += 1;
+                let (name, size) = args
+                    .get(i)
+                    .and_then(|v| v.split_once('='))
+                    .unwrap_or_else(|| {
+                        eprintln!("Expected <kind>=<size> for --kind-max");
+                        process::exit(1);
+                    });
+                limits
+                    .kind_max_bytes
+                    .insert(parse_cache_kind(name), parse_size_arg(Some(&size.to_string())));
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && kind.is_none() => {
+                kind = Some(parse_cache_kind(arg));
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let root = cache_dir
+        .map(Into::into)
+        .or_else(CacheManager::default_root)
+        .unwrap_or_else(|| {
+            eprintln!("Cannot determine cache directory; pass --cache-dir");
+            process::exit(1);
+        });
+    let cache = CacheManager::new(root);
+
+    let report = match action.as_str() {
+        "ls" => {
+            let entries = cache.list(kind).unwrap_or_else(|e| {
+                eprintln!("Error listing caches: {}", e);
+                process::exit(1);
+            });
+            if json_output {
+                let entries: Vec<Value> = entries
+                    .iter()
+                    .map(|e| {
+                        serde_json::json!({
+                            "kind": e.kind.dir_name(),
+                            "path": e.path,
+                            "size": e.size,
+                            "last_used": e.last_used
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .map(|d| d.as_secs())
+                                .unwrap_or(0),
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&entries).unwrap_or_default());
+            } else {
+                println!("Caches in {}:", cache.root().display());
+                for k in CacheKind::ALL {
+                    if kind.is_some_and(|only| only != k) {
+                        continue;
+                    }
+                    let of_kind: Vec<_> = entries.iter().filter(|e| e.kind == k).collect();
+                    let total: u64 = of_kind.iter().map(|e| e.size).sum();
+                    println!("  {}: {} entries, {}", k.dir_name(), of_kind.len(), format_size(total));
+                }
+            }
+            return;
+        }
+        "clear" => cache.clear(kind),
+        "gc" => {
+            if kind.is_some() {
+                eprintln!("cache gc takes no kind; use --kind-max <kind>=<size>");
+                process::exit(1);
+            }
+            if limits.is_unlimited() {
+                eprintln!("Nothing to enforce; pass --max-size or --kind-max");
+                process::exit(1);
+            }
+            cache.gc(&limits)
+        }
+        other => {
+            eprintln!("Unknown cache action: {}", other);
+            print_usage();
+            process::exit(1);
+        }
+    };
+
+    match report {
+        Ok(report) => {
+            if json_output {
+                let out = serde_json::json!({
+                    "removed": report.removed,
+                    "freed_bytes": report.freed_bytes,
+                });
+                println!("{}", serde_json::to_string_pretty(&out).unwrap_or_default());
+            } else {
+                println!(
+                    "Removed {} cache entries, freed {}",
+                    report.removed.len(),
+                    format_size(report.freed_bytes)
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Error cleaning caches: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn cmd_stats(args: &[String]) {
+    let Some(action) = args.first() else {
+        eprintln!("Missing stats action (export)");
+        print_usage();
+        process::exit(1);
+    };
+    if action != "export" {
+        eprintln!("Unknown stats action: {}", action);
+        print_usage();
+        process::exit(1);
+    }
+    let mut audit_log: Option<String> = None;
+    let mut format = "csv".to_string();
+    let mut output: Option<String> = None;
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--audit-log" => {
+                i += 1;
+                audit_log = args.get(i).cloned();
+            }
+            "--format" => {
+                i += 1;
+                format = args.get(i).cloned().unwrap_or_default();
+            }
+            "--output" | "-o" => {
+                i += 1;
+                output = args.get(i).cloned();
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let audit_log = audit_log.unwrap_or_else(|| {
+        eprintln!("Missing --audit-log");
+        process::exit(1);
+    });
+    let entries = read_audit_log(&audit_log).unwrap_or_else(|e| {
+        eprintln!("Error reading audit log: {}", e);
+        process::exit(1);
+    });
+    let usage = skill_usage(&entries);
+    let table = match format.as_str() {
+        "csv" => skill_usage_csv(&usage),
+        "json" => serde_json::to_string_pretty(&usage).unwrap_or_default() + "\n",
+        other => {
+            eprintln!("Unknown format: {} (expected csv or json)", other);
+            process::exit(1);
+        }
+    };
+    match output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, table) {
+                eprintln!("Failed to write {}: {}", path, e);
+                process::exit(1);
+            }
+        }
+        None => print!("{}", table),
+    }
+}
+
+fn cmd_install(args: &[String]) {
+    let mut source = InstallSource::default();
+    let mut dir: Option<String> = None;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--subdir" => {
+                i += 1;
+                source.subdir = args.get(i).cloned();
+            }
+            "--ref" => {
+                i += 1;
+                source.reference = args.get(i).cloned();
+            }
+            "--name" => {
+                i += 1;
+                source.name = args.get(i).cloned();
+            }
+            "--dir" | "-d" => {
+                i += 1;
+                dir = args.get(i).cloned();
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && source.url.is_empty() => {
+                source.url = arg.to_string();
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    if source.url.is_empty() {
+        eprintln!("Missing git URL");
+        print_usage();
+        process::exit(1);
+    }
+
+    match skill_installer(dir).install(&source) {
+        Ok(installed) => print_installed("Installed", &installed, json_output),
+        Err(err) => {
+            eprintln!("Error installing {}: {}", source.url, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn cmd_update_or_uninstall(command: &str, args: &[String]) {
+    let mut skill_id: Option<String> = None;
+    let mut dir: Option<String> = None;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--dir" | "-d" => {
+                i += 1;
+                dir = args.get(i).cloned();
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && skill_id.is_none() => {
+                skill_id = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let skill_id = skill_id.unwrap_or_else(|| {
+        eprintln!("Missing skill ID");
+        print_usage();
+        process::exit(1);
+    });
+    let installer = skill_installer(dir);
+
+    if command == "update" {
+        let previous = installer.provenance(&skill_id).ok().map(|p| p.commit);
+        match installer.update(&skill_id) {
+            Ok(installed) => {
+                if !json_output && previous.as_ref() == Some(&installed.provenance.commit) {
+                    println!("{} is up to date ({})", skill_id, short_sha(&installed.provenance.commit));
+                    return;
+                }
+                print_installed("Updated", &installed, json_output);
+            }
+            Err(err) => {
+                eprintln!("Error updating {}: {}", skill_id, err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    match installer.uninstall(&skill_id) {
+        Ok(provenance) => {
+            if json_output {
+                let output = serde_json::json!({ "id": skill_id, "provenance": provenance });
+                println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+            } else {
+                println!("Uninstalled {} (from {})", skill_id, provenance.source_url);
+            }
+        }
+        Err(err) => {
+            eprintln!("Error uninstalling {}: {}", skill_id, err);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "oci")]
+fn cmd_package(args: &[String]) {
+    let mut skill_path: Option<String> = None;
+    let mut output: Option<String> = None;
+    let mut oci: Option<String> = None;
+    let mut username: Option<String> = None;
+    let mut password_stdin = false;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--output" | "-o" => {
+                i += 1;
+                output = args.get(i).cloned();
+            }
+            "--oci" => {
+                i += 1;
+                oci = args.get(i).cloned();
+            }
+            "--username" => {
+                i += 1;
+                username = args.get(i).cloned();
+            }
+            "--password-stdin" => {
+                password_stdin = true;
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && skill_path.is_none() => {
+                skill_path = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let skill_path = std::path::PathBuf::from(skill_path.unwrap_or_else(|| ".".to_string()));
+
+    if let Some(oci) = oci {
+        let reference = OciReference::parse(&oci).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        });
+        match oci_client(username, password_stdin).push(&skill_path, &reference) {
+            Ok(pushed) => {
+                if json_output {
+                    let output = serde_json::json!({
+                        "id": pushed.id,
+                        "reference": reference.to_string(),
+                        "digest": pushed.digest,
+                        "layer_digest": pushed.layer_digest,
+                        "size": pushed.layer_size,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+                } else {
+                    println!(
+                        "Pushed {} to {} ({}, {})",
+                        pushed.id,
+                        reference,
+                        pushed.digest,
+                        format_size(pushed.layer_size)
+                    );
+                }
+            }
+            Err(err) => {
+                eprintln!("Error pushing {}: {}", skill_path.display(), err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let tarball = package_skill(&skill_path).unwrap_or_else(|e| {
+        eprintln!("Error packaging {}: {}", skill_path.display(), e);
+        process::exit(1);
+    });
+    let output = output.unwrap_or_else(|| {
+        let id = skill_path
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "skill".to_string());
+        format!("{}.tar.gz", id)
+    });
+    if let Err(e) = fs::write(&output, &tarball) {
+        eprintln!("Error writing {}: {}", output, e);
+        process::exit(1);
+    }
+    let sha256 = {
+        use sha2::Digest;
+        hex::encode(sha2::Sha256::digest(&tarball))
+    };
+    if json_output {
+        let output = serde_json::json!({ "path": output, "size": tarball.len(), "sha256": sha256 });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+    } else {
+        println!("Packaged {} ({})", output, format_size(tarball.len() as u64));
+        println!("  sha256: {}", sha256);
+    }
+}
+
+#[cfg(feature = "oci")]
+fn cmd_pull(args: &[String]) {
+    let mut reference: Option<String> = None;
+    let mut username: Option<String> = None;
+    let mut password_stdin = false;
+    let mut json_output = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--username" => {
+                i += 1;
+                username = args.get(i).cloned();
+            }
+            "--password-stdin" => {
+                password_stdin = true;
+            }
+            "--json" => {
+                json_output = true;
+            }
+            arg if !arg.starts_with('-') && reference.is_none() => {
+                reference = Some(arg.to_string());
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let reference = reference.unwrap_or_else(|| {
+        eprintln!("Missing OCI reference");
+        print_usage();
+        process::exit(1);
+    });
+    let reference = OciReference::parse(&reference).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    });
+    match oci_client(username, password_stdin).pull(&reference) {
+        Ok(skill) => {
+            if json_output {
+                let output = serde_json::json!({
+                    "id": skill.name,
+                    "version": skill.version,
+                    "path": skill.path,
+                    "cached": skill.cached,
+                });
+                println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+            } else {
+                let how = if skill.cached { "cached" } else { "pulled" };
+                println!("{} {} ({}) at {}", how, skill.name, skill.version, skill.path.display());
+            }
+        }
+        Err(err) => {
+            eprintln!("Error pulling {}: {}", reference, err);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "oci")]
+fn oci_client(username: Option<String>, password_stdin: bool) -> OciClient {
+    let client = OciClient::new();
+    let Some(username) = username else {
+        return client;
+    };
+    if !password_stdin {
+        eprintln!("--username requires --password-stdin");
+        process::exit(1);
+    }
+    let mut password = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut password) {
+        eprintln!("Error reading password: {}", e);
+        process::exit(1);
+    }
+    client.with_credentials(username, password.trim_end_matches(['\r', '\n']))
+}
+
+#[cfg(not(feature = "oci"))]
+fn cmd_package(_args: &[String]) {
+    eprintln!("Skill packaging is disabled in this binary. Rebuild with the `oci` feature enabled.");
+    process::exit(1);
+}
+
+#[cfg(not(feature = "oci"))]
+fn cmd_pull(_args: &[String]) {
+    eprintln!("OCI support is disabled in this binary. Rebuild with the `oci` feature enabled.");
+    process::exit(1);
+}
+
+fn skill_installer(dir: Option<String>) -> SkillInstaller {
+    let dir = dir
+        .map(Into::into)
+        .or_else(SkillInstaller::default_dir)
+        .unwrap_or_else(|| {
+            eprintln!("Cannot determine home directory; pass --dir");
+            process::exit(1);
+        });
+    SkillInstaller::new(dir)
+}
+
+fn print_installed(verb: &str, installed: &InstalledSkill, json_output: bool) {
+    if json_output {
+        let output = serde_json::json!({
+            "id": installed.id,
+            "path": installed.path,
+            "provenance": installed.provenance,
+            "warnings": installed.warnings,
+        });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+        return;
+    }
+    println!(
+        "{} {} at {} ({})",
+        verb,
+        installed.id,
+        installed.path.display(),
+        short_sha(&installed.provenance.commit)
+    );
+    for warn in &installed.warnings {
+        println!("  warning: {}", warn);
+    }
+}
+
+fn short_sha(commit: &str) -> &str {
+    commit.get(..12).unwrap_or(commit)
+}
+
+fn parse_cache_kind(name: &str) -> CacheKind {
+    CacheKind::from_dir_name(name).unwrap_or_else(|| {
+        let kinds: Vec<_> = CacheKind::ALL.iter().map(|k| k.dir_name()).collect();
+        eprintln!("Unknown cache '{}' (expected one of: {})", name, kinds.join(", "));
+        process::exit(1);
+    })
+}
+
+/// Parse a size such as `1048576`, `500M` or `2G` (binary units).
+fn parse_size_arg(value: Option<&String>) -> u64 {
+    let parsed = value.and_then(|v| {
+        let v = v.trim();
+        let (digits, shift) = match v.chars().last().map(|c| c.to_ascii_uppercase()) {
+            Some('K') => (&v[..v.len() - 1], 10),
+            Some('M') => (&v[..v.len() - 1], 20),
+            Some('G') => (&v[..v.len() - 1], 30),
+            _ => (v, 0),
+        };
+        digits.parse::<u64>().ok()?.checked_mul(1u64 << shift)
+    });
+    parsed.unwrap_or_else(|| {
+        eprintln!("Invalid size: {}", value.map(String::as_str).unwrap_or(""));
+        process::exit(1);
+    })
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
     }
 }
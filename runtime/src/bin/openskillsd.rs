@@ -0,0 +1,187 @@
+//! openskillsd - shared OpenSkills runtime daemon
+//!
+//! Keeps one runtime (discovered skills, compiled WASM modules, caches)
+//! warm and serves it to agent processes over a Unix socket.
+
+#[cfg(unix)]
+fn main() {
+    daemon::main();
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("openskillsd requires Unix domain sockets");
+    std::process::exit(1);
+}
+
+#[cfg(unix)]
+mod daemon {
+    use openskills_runtime::{
+        default_daemon_socket, DaemonServer, ExecutionJournal, HostPolicy, OpenSkillRuntime,
+        RuntimeConfig,
+    };
+    #[cfg(feature = "http-callback")]
+    use openskills_runtime::HttpPermissionCallback;
+    use std::env;
+    use std::path::PathBuf;
+    use std::process;
+
+    fn print_usage() {
+        eprintln!("openskillsd - shared OpenSkills runtime daemon");
+        eprintln!();
+        eprintln!("Usage:");
+        eprintln!("  openskillsd [--socket <path>] [--project-root <path>] [--dir <path>]... [--no-standard-locations] [--policy <path>] [--approval-url <url>] [--watch]");
+        eprintln!();
+        eprintln!("Options:");
+        eprintln!("  --socket, -s              Socket path, default $OPENSKILLS_SOCKET or $XDG_RUNTIME_DIR/openskillsd.sock");
+        eprintln!("  --project-root, -p        Project root for standard skill discovery");
+        eprintln!("  --dir, -d                 Extra skills directory (repeatable)");
+        eprintln!("  --no-standard-locations   Only scan --dir directories");
+        eprintln!("  --policy                  Host policy TOML file, default ~/.config/openskills/policy.toml");
+        eprintln!("  --approval-url            POST permission prompts to this approval service (needs the `http-callback` feature);");
+        eprintln!("                            $OPENSKILLS_APPROVAL_TOKEN is sent as a bearer token");
+        eprintln!("  --watch                   Reload skills when SKILL.md files change (needs the `watch` feature)");
+        eprintln!("  --help, -h                Show help");
+    }
+
+    fn value(args: &[String], i: usize, option: &str) -> String {
+        args.get(i).cloned().unwrap_or_else(|| {
+            eprintln!("Missing value for {}", option);
+            process::exit(1);
+        })
+    }
+
+    pub fn main() {
+        let args: Vec<String> = env::args().skip(1).collect();
+        let mut socket: Option<PathBuf> = None;
+        let mut config = RuntimeConfig {
+            custom_directories: Vec::new(),
+            use_standard_locations: true,
+            project_root: None,
+            workspace_dir: None,
+            native_runner_config: None,
+        };
+        let mut policy: Option<PathBuf> = None;
+        let mut approval_url: Option<String> = None;
+        let mut watch = false;
+
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--socket" | "-s" => {
+                    i += 1;
+                    socket = Some(PathBuf::from(value(&args, i, "--socket")));
+                }
+                "--project-root" | "-p" => {
+                    i += 1;
+                    config.project_root = Some(PathBuf::from(value(&args, i, "--project-root")));
+                }
+                "--dir" | "-d" => {
+                    i += 1;
+                    config.custom_directories.push(PathBuf::from(value(&args, i, "--dir")));
+                }
+                "--no-standard-locations" => config.use_standard_locations = false,
+                "--policy" => {
+                    i += 1;
+                    policy = Some(PathBuf::from(value(&args, i, "--policy")));
+                }
+                "--approval-url" => {
+                    i += 1;
+                    approval_url = Some(value(&args, i, "--approval-url"));
+                }
+                "--watch" => watch = true,
+                "--help" | "-h" => {
+                    print_usage();
+                    return;
+                }
+                other => {
+                    eprintln!("Unknown option: {}", other);
+                    print_usage();
+                    process::exit(1);
+                }
+            }
+            i += 1;
+        }
+
+        let mut runtime = OpenSkillRuntime::from_config(config)
+            .with_execution_journal(ExecutionJournal::default_dir());
+        let policy = match policy {
+            Some(path) => HostPolicy::from_file(&path).map(Some),
+            None => HostPolicy::load_default(),
+        };
+        match policy {
+            Ok(Some(policy)) => runtime.set_host_policy(policy),
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            }
+        }
+        #[cfg(feature = "http-callback")]
+        if let Some(url) = &approval_url {
+            let mut approver = match HttpPermissionCallback::new(url) {
+                Ok(approver) => approver,
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    process::exit(1);
+                }
+            };
+            if let Ok(token) = env::var("OPENSKILLS_APPROVAL_TOKEN") {
+                approver = approver.with_bearer_token(&token);
+            }
+            runtime.set_permission_callback(Some(std::sync::Arc::new(approver)));
+        }
+        #[cfg(not(feature = "http-callback"))]
+        if approval_url.is_some() {
+            eprintln!("--approval-url requires openskillsd built with the `http-callback` feature");
+            process::exit(1);
+        }
+        let aborted = runtime.recover_aborted_executions();
+        if !aborted.is_empty() {
+            eprintln!(
+                "openskillsd: cleaned up {} execution(s) aborted by a previous crash",
+                aborted.len()
+            );
+        }
+        match runtime.discover_skills() {
+            Ok(skills) => eprintln!("openskillsd: loaded {} skill(s)", skills.len()),
+            Err(err) => {
+                eprintln!("Error discovering skills: {}", err);
+                process::exit(1);
+            }
+        }
+
+        let socket = socket.unwrap_or_else(default_daemon_socket);
+        let server = match DaemonServer::bind(&socket, runtime) {
+            Ok(server) => server,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                process::exit(1);
+            }
+        };
+
+        #[cfg(feature = "watch")]
+        let _watcher = if watch {
+            match OpenSkillRuntime::watch_skills(server.runtime()) {
+                Ok(watcher) => Some(watcher),
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    process::exit(1);
+                }
+            }
+        } else {
+            None
+        };
+        #[cfg(not(feature = "watch"))]
+        if watch {
+            eprintln!("--watch requires openskillsd built with the `watch` feature");
+            process::exit(1);
+        }
+
+        eprintln!("openskillsd: listening on {}", server.socket_path().display());
+        if let Err(err) = server.serve() {
+            eprintln!("Error: {}", err);
+            process::exit(1);
+        }
+    }
+}
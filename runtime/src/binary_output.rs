@@ -0,0 +1,126 @@
+//! Binary stdout detection and diversion.
+//!
+//! Scripts occasionally write binary data (images, archives, pickles) to
+//! stdout. Decoding that with `from_utf8_lossy` silently corrupts it, so the
+//! runners keep the raw bytes instead and the runtime either diverts them to
+//! a file in the workspace or reports only their size and hash.
+
+use crate::artifacts;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Reference to binary stdout returned in place of lossy text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BinaryOutput {
+    /// File the bytes were written to (absent when not diverted).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// Size in bytes.
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the bytes.
+    pub sha256: String,
+}
+
+impl BinaryOutput {
+    /// JSON form used in `ExecutionResult.output`.
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Short human-readable placeholder used as the stdout text.
+    pub fn placeholder(&self) -> String {
+        match &self.path {
+            Some(path) => format!(
+                "[binary stdout: {} bytes, sha256 {}, saved to {}]",
+                self.size,
+                self.sha256,
+                path.display()
+            ),
+            None => format!("[binary stdout: {} bytes, sha256 {}]", self.size, self.sha256),
+        }
+    }
+}
+
+/// True if `bytes` look like binary data rather than text.
+///
+/// NUL bytes or invalid UTF-8 mark the stream as binary. A multi-byte
+/// character cut off at the very end (e.g. by a killed process) is still text.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Decode captured stdout: text is returned as a `String`, binary data is
+/// returned untouched as the second element (with empty text).
+pub(crate) fn decode_stdout(bytes: Vec<u8>) -> (String, Option<Vec<u8>>) {
+    if is_binary(&bytes) {
+        (String::new(), Some(bytes))
+    } else {
+        (String::from_utf8_lossy(&bytes).to_string(), None)
+    }
+}
+
+/// Describe binary stdout, writing it under `workspace` when given.
+///
+/// The file is the skill's artifact `stdout-<hash prefix>.bin` (see
+/// [`crate::artifacts`]), so identical output from repeated runs reuses the
+/// same file. Write failures fall back to a reference without a path.
+pub fn divert(bytes: &[u8], skill_id: &str, workspace: Option<&Path>) -> BinaryOutput {
+    let sha256 = hex::encode(Sha256::digest(bytes));
+    let path = workspace.and_then(|workspace| {
+        let dir = artifacts::skill_dir(workspace, skill_id);
+        let path = dir.join(format!("stdout-{}.bin", &sha256[..12]));
+        std::fs::create_dir_all(&dir).ok()?;
+        std::fs::write(&path, bytes).ok()?;
+        Some(path)
+    });
+    BinaryOutput {
+        path,
+        size: bytes.len() as u64,
+        sha256,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"hello\n"));
+        assert!(!is_binary("héllo".as_bytes()));
+        assert!(is_binary(b"PNG\x00\x01"));
+        assert!(is_binary(&[0xff, 0xfe, 0x41]));
+        // Truncated multi-byte sequence at the end is still text.
+        assert!(!is_binary(&[b'a', 0xc3]));
+    }
+
+    #[test]
+    fn test_divert_writes_file() {
+        let dir = TempDir::new().unwrap();
+        let bytes = [0u8, 159, 146, 150];
+        let out = divert(&bytes, "my/skill", Some(dir.path()));
+        let path = out.path.clone().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+        assert_eq!(out.size, 4);
+        assert!(path.starts_with(dir.path().join(".openskills/artifacts/my_skill")));
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("stdout-"));
+        assert!(out.placeholder().contains("saved to"));
+    }
+
+    #[test]
+    fn test_divert_without_workspace() {
+        let out = divert(&[0u8], "s", None);
+        assert!(out.path.is_none());
+        assert!(out.to_value().get("path").is_none());
+        assert_eq!(out.sha256.len(), 64);
+    }
+}
@@ -0,0 +1,155 @@
+//! Content-addressed blob store shared across sessions.
+//!
+//! Imported files are stored once under `~/.cache/openskills/blobs/`, named
+//! by their SHA-256, and materialized into each workspace as a reflink
+//! (copy-on-write clone, where the filesystem supports it) or a plain copy.
+//! With reflinks, the same large input imported in many sessions occupies
+//! disk space once.
+//!
+//! Workspace files are never hardlinked to a blob: a skill runs as the
+//! user owning the store, so it could `chmod` a shared inode and edit the
+//! blob, and every other session's copy, in place. Deleting the store never
+//! breaks a workspace, since every materialized file has its own data.
+
+use crate::cache::{CacheKind, CacheManager};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// How a blob was materialized at its destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Materialized {
+    Reflink,
+    Copy,
+}
+
+/// Blob store rooted at a directory.
+#[derive(Debug, Clone)]
+pub(crate) struct BlobStore {
+    root: PathBuf,
+}
+
+impl BlobStore {
+    pub(crate) fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Default location, shared by all sessions of the current user.
+    pub(crate) fn default_dir() -> Option<PathBuf> {
+        CacheManager::default_dir(CacheKind::Blobs)
+    }
+
+    /// Store `bytes`, returning their SHA-256 and the blob path. Content
+    /// already present is not written again.
+    pub(crate) fn put(&self, bytes: &[u8]) -> std::io::Result<(String, PathBuf)> {
+        let hash = hex::encode(Sha256::digest(bytes));
+        let path = self.root.join(&hash[..2]).join(&hash);
+        // A blob edited in place (mode bits don't stop its owner) is
+        // replaced rather than handed out.
+        let present = std::fs::read(&path).map(|stored| stored == bytes).unwrap_or(false);
+        if present {
+            CacheManager::touch(&path);
+        } else {
+            let dir = path.parent().unwrap_or(&self.root);
+            std::fs::create_dir_all(dir)?;
+            let tmp = dir.join(format!(".{hash}.{}", std::process::id()));
+            std::fs::write(&tmp, bytes)?;
+            set_readonly(&tmp, true)?;
+            if let Err(e) = std::fs::rename(&tmp, &path) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(e);
+            }
+        }
+        Ok((hash, path))
+    }
+
+    /// Create `dest` (which must not exist) with the content of `blob`.
+    pub(crate) fn materialize(&self, blob: &Path, dest: &Path) -> std::io::Result<Materialized> {
+        if reflink(blob, dest).is_ok() {
+            set_readonly(dest, false)?;
+            return Ok(Materialized::Reflink);
+        }
+        let _ = std::fs::remove_file(dest);
+        std::fs::copy(blob, dest)?;
+        set_readonly(dest, false)?;
+        Ok(Materialized::Copy)
+    }
+}
+
+fn set_readonly(path: &Path, readonly: bool) -> std::io::Result<()> {
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(if readonly { 0o444 } else { 0o644 });
+    }
+    #[cfg(not(unix))]
+    permissions.set_readonly(readonly);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Copy-on-write clone of `src` to a new file `dest` (Linux `FICLONE`).
+#[cfg(target_os = "linux")]
+fn reflink(src: &Path, dest: &Path) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+    let src = std::fs::File::open(src)?;
+    let dest = std::fs::OpenOptions::new().write(true).create_new(true).open(dest)?;
+    // SAFETY: both descriptors are open for the duration of the call.
+    if unsafe { libc::ioctl(dest.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reflink(_src: &Path, _dest: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_put_is_content_addressed() {
+        let temp = TempDir::new().unwrap();
+        let store = BlobStore::new(temp.path().join("blobs"));
+        let (hash, path) = store.put(b"report").unwrap();
+        assert_eq!(hash, hex::encode(Sha256::digest(b"report")));
+        assert_eq!(path, temp.path().join("blobs").join(&hash[..2]).join(&hash));
+        assert!(std::fs::metadata(&path).unwrap().permissions().readonly());
+
+        assert_eq!(store.put(b"report").unwrap().1, path);
+        assert_ne!(store.put(b"other").unwrap().1, path);
+
+        // A tampered blob is rewritten.
+        set_readonly(&path, false).unwrap();
+        std::fs::write(&path, b"tampered").unwrap();
+        store.put(b"report").unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"report");
+    }
+
+    #[test]
+    fn test_materialize_never_shares_the_blob() {
+        let temp = TempDir::new().unwrap();
+        let store = BlobStore::new(temp.path().join("blobs"));
+        let (_, blob) = store.put(b"report").unwrap();
+        let dest = temp.path().join("report.txt");
+
+        store.materialize(&blob, &dest).unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), b"report");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            assert_ne!(
+                std::fs::metadata(&blob).unwrap().ino(),
+                std::fs::metadata(&dest).unwrap().ino()
+            );
+        }
+        // Editing the workspace file leaves the blob alone.
+        assert!(!std::fs::metadata(&dest).unwrap().permissions().readonly());
+        std::fs::write(&dest, b"edited").unwrap();
+        assert_eq!(std::fs::read(&blob).unwrap(), b"report");
+    }
+}
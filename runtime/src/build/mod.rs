@@ -5,11 +5,15 @@
 //! - JavaScript (.js) → compile to WASM
 //!
 //! Build backends are plugin-based so developers can choose compilers.
+//! External compilers run in a [`sandbox::BuildSandbox`], so building an
+//! untrusted skill cannot touch the host beyond its own directory.
 
 use crate::errors::OpenSkillError;
 use crate::build::config::BuildConfigFile;
 use crate::build::plugin::PluginConfig;
 use crate::build::registry::PluginRegistry;
+use crate::build::sandbox::{BuildSandbox, NPM_REGISTRY_HOSTS};
+use crate::sandbox_mode::SandboxMode;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -18,6 +22,7 @@ pub mod config;
 pub mod plugin;
 pub mod plugins;
 pub mod registry;
+pub mod sandbox;
 
 /// List all available build plugins.
 pub fn list_build_plugins() -> Vec<plugin::PluginInfo> {
@@ -41,6 +46,8 @@ pub struct BuildConfig {
     pub plugin: Option<String>,
     /// Plugin-specific configuration.
     pub plugin_config: HashMap<String, String>,
+    /// OS sandbox for compiler commands (default: enforce).
+    pub sandbox_mode: SandboxMode,
 }
 
 impl Default for BuildConfig {
@@ -53,6 +60,7 @@ impl Default for BuildConfig {
             verbose: false,
             plugin: None,
             plugin_config: HashMap::new(),
+            sandbox_mode: SandboxMode::Enforce,
         }
     }
 }
@@ -80,12 +88,13 @@ pub fn detect_source_file(skill_dir: &Path) -> Result<PathBuf, OpenSkillError> {
     )))
 }
 
-/// Transpile TypeScript to JavaScript.
+/// Transpile TypeScript to JavaScript in `sandbox`.
 pub fn transpile_typescript(
     ts_file: &Path,
     output_js: &Path,
-    verbose: bool,
+    sandbox: &BuildSandbox,
 ) -> Result<(), OpenSkillError> {
+    let verbose = sandbox.verbose;
     // Validate input paths to prevent command injection
     if !ts_file.exists() {
         return Err(OpenSkillError::BuildError(format!(
@@ -102,33 +111,28 @@ pub fn transpile_typescript(
         OpenSkillError::BuildError("Invalid output file path encoding".to_string())
     })?;
 
-    // Try esbuild first (faster)
-    if Command::new("npx")
-        .args(["-y", "esbuild", "--version"])
-        .output()
+    // Try esbuild first (faster). Fetching it is the only step with network.
+    let esbuild_version = ["-y", "esbuild", "--version"].map(String::from);
+    if sandbox
+        .fetch("esbuild install", "npx", &esbuild_version, NPM_REGISTRY_HOSTS)
         .is_ok()
     {
         if verbose {
             eprintln!("Using esbuild for TypeScript transpilation");
         }
 
-        // Use Command with separate arguments instead of shell string to prevent injection
-        let status = Command::new("npx")
-            .arg("-y")
-            .arg("esbuild")
-            .arg(ts_file_str)
-            .arg("--bundle")
-            .arg("--format=esm")
-            .arg("--target=es2020")
-            .arg(format!("--outfile={}", output_js_str))
-            .status()
-            .map_err(|e| OpenSkillError::BuildError(format!("Failed to run esbuild: {}", e)))?;
-
-        if !status.success() {
-            return Err(OpenSkillError::BuildError(
-                "TypeScript transpilation failed with esbuild".to_string(),
-            ));
-        }
+        let args = [
+            "-y".to_string(),
+            "esbuild".to_string(),
+            ts_file_str.to_string(),
+            "--bundle".to_string(),
+            "--format=esm".to_string(),
+            "--target=es2020".to_string(),
+            format!("--outfile={}", output_js_str),
+        ];
+        sandbox.run("esbuild", "npx", &args).map_err(|e| {
+            OpenSkillError::BuildError(format!("TypeScript transpilation failed with esbuild: {}", e))
+        })?;
 
         return Ok(());
     }
@@ -157,18 +161,14 @@ pub fn transpile_typescript(
             })?;
         }
 
-        let status = Command::new("tsc")
-            .arg(ts_file.to_string_lossy().to_string())
-            .arg("--outDir")
-            .arg(output_js.parent().unwrap().to_string_lossy().to_string())
-            .status()
-            .map_err(|e| OpenSkillError::BuildError(format!("Failed to run tsc: {}", e)))?;
-
-        if !status.success() {
-            return Err(OpenSkillError::BuildError(
-                "TypeScript transpilation failed with tsc".to_string(),
-            ));
-        }
+        let args = [
+            ts_file.to_string_lossy().to_string(),
+            "--outDir".to_string(),
+            output_js.parent().unwrap().to_string_lossy().to_string(),
+        ];
+        sandbox.run("tsc", "tsc", &args).map_err(|e| {
+            OpenSkillError::BuildError(format!("TypeScript transpilation failed with tsc: {}", e))
+        })?;
 
         // tsc outputs to the same directory with .js extension
         let tsc_output = ts_file.with_extension("js");
@@ -230,6 +230,18 @@ pub fn build_skill(config: BuildConfig) -> Result<PathBuf, OpenSkillError> {
         }
     }
 
+    // Compilers run in the skill directory and may write only there, to
+    // the output directory and to the build caches.
+    let mut sandbox = BuildSandbox::new(&skill_dir);
+    sandbox.sandbox_mode = config.sandbox_mode;
+    sandbox.verbose = config.verbose;
+    if let Some(source_dir) = source_file.parent() {
+        sandbox = sandbox.allow_read(source_dir);
+    }
+    if let Some(output_dir) = output_wasm.parent() {
+        sandbox = sandbox.allow_write(output_dir);
+    }
+
     // Determine if we need TypeScript transpilation
     let js_file = if source_file.extension().and_then(|s| s.to_str()) == Some("ts") {
         if config.verbose {
@@ -238,7 +250,7 @@ pub fn build_skill(config: BuildConfig) -> Result<PathBuf, OpenSkillError> {
 
         // Create temporary JS file in same directory
         let temp_js = source_file.with_extension("js");
-        transpile_typescript(&source_file, &temp_js, config.verbose)?;
+        transpile_typescript(&source_file, &temp_js, &sandbox)?;
 
         temp_js
     } else {
@@ -310,6 +322,7 @@ pub fn build_skill(config: BuildConfig) -> Result<PathBuf, OpenSkillError> {
             merged.extend(config.plugin_config.clone());
             merged
         },
+        sandbox,
     };
 
     selected_plugin.compile(&js_file, &output_wasm, &plugin_config)?;
@@ -1,3 +1,4 @@
+use crate::build::sandbox::BuildSandbox;
 use crate::errors::OpenSkillError;
 use std::collections::HashMap;
 use std::path::Path;
@@ -29,6 +30,8 @@ pub struct PluginConfig {
     pub verbose: bool,
     pub force: bool,
     pub custom: HashMap<String, String>,
+    /// Sandbox external compiler commands must run in.
+    pub sandbox: BuildSandbox,
 }
 
 /// Display information for a plugin.
@@ -3,9 +3,9 @@
 #![allow(dead_code)] // Functions used by quickjs/assemblyscript plugins when features enabled
 
 use crate::build::plugin::PluginConfig;
+use crate::build::sandbox::GITHUB_RELEASE_HOSTS;
 use crate::errors::OpenSkillError;
 use std::path::PathBuf;
-use std::process::Command;
 
 /// Default adapter version to download.
 const DEFAULT_ADAPTER_VERSION: &str = "25.0.1";
@@ -52,7 +52,7 @@ pub fn find_adapter(config: &PluginConfig) -> Result<PathBuf, OpenSkillError> {
         .unwrap_or(false);
 
     if !auto_download {
-        if let Ok(path) = download_adapter(config.verbose) {
+        if let Ok(path) = download_adapter(config) {
             return Ok(path);
         }
     }
@@ -66,8 +66,10 @@ fn get_cache_adapter_path() -> Option<PathBuf> {
     dirs::cache_dir().map(|c| c.join("openskills/wasi_preview1_adapter.wasm"))
 }
 
-/// Download the WASI preview1 adapter to the cache directory.
-fn download_adapter(verbose: bool) -> Result<PathBuf, OpenSkillError> {
+/// Download the WASI preview1 adapter to the cache directory, in the
+/// build sandbox with network limited to GitHub releases.
+fn download_adapter(config: &PluginConfig) -> Result<PathBuf, OpenSkillError> {
+    let verbose = config.verbose;
     let cache_path = get_cache_adapter_path().ok_or_else(|| {
         OpenSkillError::BuildError("Cannot determine cache directory for adapter".to_string())
     })?;
@@ -92,46 +94,30 @@ fn download_adapter(verbose: bool) -> Result<PathBuf, OpenSkillError> {
     }
 
     // Try curl first, then wget
-    let result = if check_tool("curl") {
-        Command::new("curl")
-            .arg("-L")
-            .arg("-o")
-            .arg(&cache_path)
-            .arg(&url)
-            .output()
+    let cache_file = cache_path.display().to_string();
+    let (program, args) = if check_tool("curl") {
+        ("curl", vec!["-L".to_string(), "-f".to_string(), "-o".to_string(), cache_file, url])
     } else if check_tool("wget") {
-        Command::new("wget")
-            .arg("-O")
-            .arg(&cache_path)
-            .arg(&url)
-            .output()
+        ("wget", vec!["-O".to_string(), cache_file, url])
     } else {
         return Err(OpenSkillError::BuildError(
             "Neither curl nor wget available to download adapter".to_string(),
         ));
     };
 
-    match result {
-        Ok(output) if output.status.success() => {
-            if cache_path.exists() {
-                if verbose {
-                    eprintln!("✅ Adapter downloaded successfully");
-                }
-                Ok(cache_path)
-            } else {
-                Err(OpenSkillError::BuildError(
-                    "Download appeared to succeed but adapter file not found".to_string(),
-                ))
-            }
+    config
+        .sandbox
+        .fetch("adapter download", program, &args, GITHUB_RELEASE_HOSTS)
+        .map_err(|e| OpenSkillError::BuildError(format!("Failed to download adapter: {}", e)))?;
+    if cache_path.exists() {
+        if verbose {
+            eprintln!("✅ Adapter downloaded successfully");
         }
-        Ok(output) => Err(OpenSkillError::BuildError(format!(
-            "Failed to download adapter: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ))),
-        Err(e) => Err(OpenSkillError::BuildError(format!(
-            "Failed to run download command: {}",
-            e
-        ))),
+        Ok(cache_path)
+    } else {
+        Err(OpenSkillError::BuildError(
+            "Download appeared to succeed but adapter file not found".to_string(),
+        ))
     }
 }
 
@@ -100,6 +100,7 @@ mod javy_impl {
                 verbose: false,
                 force: false,
                 custom: std::collections::HashMap::new(),
+                sandbox: Default::default(),
             };
             Ok(self.locate_plugin_path(&config).is_some())
         }
@@ -4,7 +4,6 @@ use crate::build::plugin::{BuildPlugin, PluginConfig};
 use crate::build::plugins::adapter::{check_tool, find_adapter, generate_requirements};
 use crate::errors::OpenSkillError;
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub struct QuickJsBuildPlugin;
@@ -20,36 +19,18 @@ impl QuickJsBuildPlugin {
             .unwrap_or_default()
             .as_millis();
         let pid = std::process::id();
-        let mut path = std::env::temp_dir();
+        // Next to the output, which the build sandbox lets compilers write.
+        let mut path = output_wasm
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(std::env::temp_dir);
         let stem = output_wasm
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("skill");
-        path.push(format!("{}_core_{}_{}.wasm", stem, pid, ts));
+        path.push(format!(".{}_core_{}_{}.wasm", stem, pid, ts));
         path
     }
-
-    fn run_command(
-        &self,
-        label: &str,
-        mut command: Command,
-        verbose: bool,
-    ) -> Result<(), OpenSkillError> {
-        if verbose {
-            eprintln!("Running: {:?}", command);
-        }
-        let status = command.status().map_err(|e| {
-            OpenSkillError::BuildError(format!("Failed to run {}: {}", label, e))
-        })?;
-        if !status.success() {
-            return Err(OpenSkillError::BuildError(format!(
-                "{} failed with exit code {:?}",
-                label,
-                status.code()
-            )));
-        }
-        Ok(())
-    }
 }
 
 impl BuildPlugin for QuickJsBuildPlugin {
@@ -80,6 +61,10 @@ impl BuildPlugin for QuickJsBuildPlugin {
     ) -> Result<(), OpenSkillError> {
         // Find adapter with auto-detection
         let adapter_path = find_adapter(config)?;
+        let sandbox = match adapter_path.parent() {
+            Some(adapter_dir) => config.sandbox.clone().allow_read(adapter_dir),
+            None => config.sandbox.clone(),
+        };
 
         let javy_path = config
             .custom
@@ -108,13 +93,13 @@ impl BuildPlugin for QuickJsBuildPlugin {
         }
 
         // Step 1: Compile JS to core WASM module using javy CLI
-        let mut javy_cmd = Command::new(javy_path);
-        javy_cmd
-            .arg("build")
-            .arg(source_file)
-            .arg("-o")
-            .arg(&core_wasm);
-        self.run_command("javy build", javy_cmd, config.verbose)?;
+        let args = [
+            "build".to_string(),
+            source_file.display().to_string(),
+            "-o".to_string(),
+            core_wasm.display().to_string(),
+        ];
+        sandbox.run("javy build", javy_path, &args)?;
 
         if config.verbose {
             eprintln!(
@@ -124,22 +109,20 @@ impl BuildPlugin for QuickJsBuildPlugin {
         }
 
         // Step 2: Convert core module to WASI 0.3 component
-        let mut wasm_tools_cmd = Command::new(wasm_tools_path);
-        wasm_tools_cmd
-            .arg("component")
-            .arg("new")
-            .arg(&core_wasm)
-            .arg("--adapt")
-            .arg(format!(
-                "wasi_snapshot_preview1={}",
-                adapter_path.display()
-            ))
-            .arg("-o")
-            .arg(output_wasm);
-        self.run_command("wasm-tools component new", wasm_tools_cmd, config.verbose)?;
+        let args = [
+            "component".to_string(),
+            "new".to_string(),
+            core_wasm.display().to_string(),
+            "--adapt".to_string(),
+            format!("wasi_snapshot_preview1={}", adapter_path.display()),
+            "-o".to_string(),
+            output_wasm.display().to_string(),
+        ];
+        let converted = sandbox.run("wasm-tools component new", wasm_tools_path, &args);
 
         // Clean up intermediate file
         let _ = std::fs::remove_file(&core_wasm);
+        converted?;
 
         if config.verbose {
             eprintln!("QuickJS: build complete: {}", output_wasm.display());
@@ -0,0 +1,263 @@
+//! Sandboxed compiler invocations.
+//!
+//! Building a skill runs its sources through npx, tsc, javy, asc and
+//! wasm-tools, and npx may fetch (and run install scripts of) packages the
+//! skill names. Every such command goes through [`run_sandboxed_command`]:
+//!
+//! - [`BuildSandbox::fetch`] steps may reach the dependency hosts they name
+//!   (the npm registry, GitHub releases) and nothing else.
+//! - [`BuildSandbox::run`] steps (the compilers themselves) have no network
+//!   and npm runs offline from its cache.
+//! - Both can write only to the skill directory, the output directory and
+//!   the npm and OpenSkills caches; they read the tool's installation and
+//!   the system paths the OS sandbox always allows.
+
+use crate::errors::OpenSkillError;
+use crate::executor::{run_sandboxed_command, CommandPermissions, CommandResult};
+use crate::sandbox_mode::SandboxMode;
+use std::path::{Path, PathBuf};
+
+/// Hosts `npx` may download packages from.
+pub const NPM_REGISTRY_HOSTS: &[&str] = &["registry.npmjs.org"];
+
+/// Hosts the WASI adapter is downloaded from.
+pub const GITHUB_RELEASE_HOSTS: &[&str] = &[
+    "github.com",
+    "objects.githubusercontent.com",
+    "release-assets.githubusercontent.com",
+];
+
+/// Default limit for one build command.
+const DEFAULT_BUILD_TIMEOUT_MS: u64 = 300_000;
+
+/// Where build commands may read and write.
+#[derive(Debug, Clone)]
+pub struct BuildSandbox {
+    /// Directory the commands run in.
+    pub working_dir: PathBuf,
+    pub read_paths: Vec<PathBuf>,
+    pub write_paths: Vec<PathBuf>,
+    /// `Disabled` runs the compilers directly (the host owns the boundary).
+    pub sandbox_mode: SandboxMode,
+    pub timeout_ms: u64,
+    pub verbose: bool,
+}
+
+impl Default for BuildSandbox {
+    fn default() -> Self {
+        Self::new(&std::env::temp_dir())
+    }
+}
+
+impl BuildSandbox {
+    /// Sandbox for building the skill in `skill_dir`: it runs there and may
+    /// write to it and to the build caches.
+    pub fn new(skill_dir: &Path) -> Self {
+        let mut sandbox = Self {
+            working_dir: skill_dir.to_path_buf(),
+            read_paths: Vec::new(),
+            write_paths: Vec::new(),
+            sandbox_mode: SandboxMode::Enforce,
+            timeout_ms: DEFAULT_BUILD_TIMEOUT_MS,
+            verbose: false,
+        };
+        sandbox = sandbox.allow_write(skill_dir);
+        for cache in build_caches() {
+            sandbox = sandbox.allow_write(&cache);
+        }
+        sandbox
+    }
+
+    /// Also let commands read `path`.
+    pub fn allow_read(mut self, path: &Path) -> Self {
+        if !self.read_paths.iter().any(|p| p == path) {
+            self.read_paths.push(path.to_path_buf());
+        }
+        self
+    }
+
+    /// Also let commands read and write `path` (created if missing).
+    pub fn allow_write(mut self, path: &Path) -> Self {
+        let _ = std::fs::create_dir_all(path);
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self = self.allow_read(&path);
+        if !self.write_paths.contains(&path) {
+            self.write_paths.push(path);
+        }
+        self
+    }
+
+    /// Run a compiler step without network access.
+    pub fn run(&self, label: &str, program: &str, args: &[String]) -> Result<CommandResult, OpenSkillError> {
+        self.execute(label, program, args, &[])
+    }
+
+    /// Run a dependency fetch that may only reach `hosts`.
+    pub fn fetch(
+        &self,
+        label: &str,
+        program: &str,
+        args: &[String],
+        hosts: &[&str],
+    ) -> Result<CommandResult, OpenSkillError> {
+        self.execute(label, program, args, hosts)
+    }
+
+    fn execute(
+        &self,
+        label: &str,
+        program: &str,
+        args: &[String],
+        hosts: &[&str],
+    ) -> Result<CommandResult, OpenSkillError> {
+        let mut read_paths = self.read_paths.clone();
+        read_paths.extend(tool_paths(program));
+
+        let mut env_vars = vec![("npm_config_update_notifier".to_string(), "false".to_string())];
+        if let Some(home) = dirs::home_dir() {
+            env_vars.push(("HOME".to_string(), home.display().to_string()));
+        }
+        if let Some(npm_cache) = npm_cache_dir() {
+            env_vars.push(("npm_config_cache".to_string(), npm_cache.display().to_string()));
+        }
+        if hosts.is_empty() {
+            env_vars.push(("npm_config_offline".to_string(), "true".to_string()));
+        }
+
+        let command = std::iter::once(program)
+            .chain(args.iter().map(String::as_str))
+            .map(shell_quote)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.verbose {
+            eprintln!("Running (sandboxed): {}", command);
+        }
+        let permissions = CommandPermissions {
+            allow_network: !hosts.is_empty(),
+            allowed_hosts: hosts.iter().map(|h| h.to_string()).collect(),
+            allow_process: true,
+            read_paths,
+            write_paths: self.write_paths.clone(),
+            env_vars,
+            timeout_ms: self.timeout_ms,
+            sandbox_mode: self.sandbox_mode,
+            ..Default::default()
+        };
+        let result = run_sandboxed_command(&command, &self.working_dir, permissions)
+            .map_err(|e| OpenSkillError::BuildError(format!("Failed to run {}: {}", label, e)))?;
+        if self.verbose {
+            eprint!("{}{}", result.stdout, result.stderr);
+        }
+        if result.timed_out {
+            return Err(OpenSkillError::BuildError(format!(
+                "{} timed out after {}ms",
+                label, self.timeout_ms
+            )));
+        }
+        if result.exit_code != 0 {
+            return Err(OpenSkillError::BuildError(format!(
+                "{} failed with exit code {}: {}",
+                label,
+                result.exit_code,
+                result.stderr.trim()
+            )));
+        }
+        Ok(result)
+    }
+}
+
+/// npm's package cache (`$npm_config_cache`, else `~/.npm`).
+fn npm_cache_dir() -> Option<PathBuf> {
+    std::env::var_os("npm_config_cache")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".npm")))
+}
+
+fn build_caches() -> Vec<PathBuf> {
+    let mut caches: Vec<PathBuf> = npm_cache_dir().into_iter().collect();
+    if let Some(cache) = dirs::cache_dir() {
+        caches.push(cache.join("openskills"));
+    }
+    caches
+}
+
+/// The directory holding `program` and its installation's `lib` (where
+/// node keeps npm and global packages), following a symlinked binary too.
+fn tool_paths(program: &str) -> Vec<PathBuf> {
+    let Some(path) = find_program(program) else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    for binary in [path.clone(), path.canonicalize().unwrap_or(path)] {
+        let Some(bin_dir) = binary.parent() else {
+            continue;
+        };
+        if let Some(lib_dir) = bin_dir.parent().map(|prefix| prefix.join("lib")) {
+            if lib_dir.is_dir() && !paths.contains(&lib_dir) {
+                paths.push(lib_dir);
+            }
+        }
+        if !paths.iter().any(|p| p == bin_dir) {
+            paths.push(bin_dir.to_path_buf());
+        }
+    }
+    paths
+}
+
+fn find_program(program: &str) -> Option<PathBuf> {
+    if program.contains('/') {
+        return Some(PathBuf::from(program));
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Quote `arg` for `/bin/bash -c`.
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("--outfile=/tmp/a.js"), "--outfile=/tmp/a.js");
+        assert_eq!(shell_quote("my skill"), "'my skill'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote("$(rm -rf ~)"), "'$(rm -rf ~)'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_run_reports_failures() {
+        let skill = TempDir::new().unwrap();
+        let sandbox = BuildSandbox::new(skill.path());
+
+        let out = skill.path().join("out file.txt");
+        sandbox
+            .run("touch", "touch", &[out.display().to_string()])
+            .unwrap();
+        assert!(out.exists());
+
+        match sandbox.run("ls", "ls", &["missing; touch injected".to_string()]) {
+            Err(OpenSkillError::BuildError(message)) => {
+                assert!(message.starts_with("ls failed with exit code"), "{}", message)
+            }
+            other => panic!("expected a build error, got {:?}", other.map(|r| r.exit_code)),
+        }
+        assert!(!skill.path().join("injected").exists());
+    }
+}
@@ -0,0 +1,204 @@
+//! Skills compiled into the binary.
+//!
+//! A product shipping the runtime can guarantee a baseline set of skills
+//! without installing anything on disk first: [`builtin_skill!`] embeds a
+//! skill directory's files with `include_bytes!`, and
+//! `OpenSkillRuntime::with_embedded_skills` registers them next to the
+//! discovered ones. `OpenSkillRuntime::with_builtin_skills(true)` does the
+//! same for the runtime's own first-party skills (`code-review` and
+//! `explaining-code`).
+//!
+//! Skills run from real directories, so each one is unpacked on discovery
+//! under `~/.cache/openskills/builtin/<name>-<hash>/<name>/`, keyed by a
+//! hash of its files. Unpacking is staged and renamed into place, and an
+//! entry that already exists is reused, so several processes of the same
+//! binary share it and a new build with different files gets a new entry.
+
+use crate::cache::{CacheKind, CacheManager};
+use crate::errors::OpenSkillError;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// A skill embedded in the binary: its name (the skill ID) and its files,
+/// as paths relative to the skill directory with their contents. Build it
+/// with [`builtin_skill!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinSkill {
+    pub name: &'static str,
+    pub files: &'static [(&'static str, &'static [u8])],
+}
+
+impl BuiltinSkill {
+    pub const fn new(name: &'static str, files: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self { name, files }
+    }
+}
+
+/// Embed the skill directory `dir`, relative to the calling crate's
+/// `Cargo.toml`, as a [`BuiltinSkill`] named `name`. Every file to ship is
+/// listed, `SKILL.md` included:
+///
+/// ```ignore
+/// static SKILLS: &[BuiltinSkill] = &[
+///     builtin_skill!("pdf", "skills/pdf", ["SKILL.md", "scripts/extract.py"]),
+/// ];
+/// let runtime = OpenSkillRuntime::new().with_embedded_skills(SKILLS);
+/// ```
+#[macro_export]
+macro_rules! builtin_skill {
+    ($name:literal, $dir:literal, [$($file:literal),+ $(,)?]) => {
+        $crate::BuiltinSkill::new(
+            $name,
+            &[$((
+                $file,
+                include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $dir, "/", $file)) as &[u8],
+            )),+],
+        )
+    };
+}
+
+/// The runtime's first-party skills, registered by `with_builtin_skills(true)`.
+pub(crate) const BUILTIN_SKILLS: &[BuiltinSkill] = &[
+    builtin_skill!("code-review", "builtin-skills/code-review", ["SKILL.md"]),
+    builtin_skill!("explaining-code", "builtin-skills/explaining-code", ["SKILL.md"]),
+];
+
+/// Where built-in skills are unpacked: the `builtin` cache, or the temp
+/// directory on systems without a cache directory.
+pub(crate) fn default_unpack_dir() -> PathBuf {
+    CacheManager::default_dir(CacheKind::Builtin)
+        .unwrap_or_else(|| std::env::temp_dir().join("openskills").join("builtin"))
+}
+
+/// Unpack `skill` under `dir` unless an identical copy is there already,
+/// and return its skill directory.
+pub(crate) fn unpack(skill: &BuiltinSkill, dir: &Path) -> Result<PathBuf, OpenSkillError> {
+    check_relative(skill.name)?;
+    for (path, _) in skill.files {
+        check_relative(path)?;
+    }
+    if !skill.files.iter().any(|(path, _)| *path == "SKILL.md") {
+        return Err(OpenSkillError::InvalidManifest(format!(
+            "built-in skill '{}' has no SKILL.md",
+            skill.name
+        )));
+    }
+
+    let entry = dir.join(format!("{}-{}", skill.name, &content_hash(skill)[..16]));
+    let skill_dir = entry.join(skill.name);
+    if skill_dir.join("SKILL.md").is_file() {
+        CacheManager::touch(&skill_dir.join("SKILL.md"));
+        return Ok(skill_dir);
+    }
+
+    fs::create_dir_all(dir)?;
+    let staging = dir.join(format!(".unpack-{:08x}", rand::random::<u32>()));
+    let written = write_files(skill, &staging.join(skill.name));
+    let renamed = written.and_then(|()| fs::rename(&staging, &entry).map_err(Into::into));
+    if let Err(e) = renamed {
+        let _ = fs::remove_dir_all(&staging);
+        // Another process may have unpacked the same skill meanwhile.
+        if !skill_dir.join("SKILL.md").is_file() {
+            return Err(e);
+        }
+    }
+    Ok(skill_dir)
+}
+
+fn write_files(skill: &BuiltinSkill, skill_dir: &Path) -> Result<(), OpenSkillError> {
+    for (path, contents) in skill.files {
+        let target = skill_dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, contents)?;
+        // Embedding loses file modes; scripts are recognised by their shebang.
+        #[cfg(unix)]
+        if contents.starts_with(b"#!") {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(0o755))?;
+        }
+    }
+    Ok(())
+}
+
+fn content_hash(skill: &BuiltinSkill) -> String {
+    let mut files: Vec<_> = skill.files.iter().collect();
+    files.sort_by_key(|(path, _)| *path);
+    let mut hasher = Sha256::new();
+    for (path, contents) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(contents);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Reject paths that would leave the skill directory.
+fn check_relative(path: &str) -> Result<(), OpenSkillError> {
+    let normal = !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    if normal {
+        Ok(())
+    } else {
+        Err(OpenSkillError::InvalidManifest(format!(
+            "built-in skill path '{}' must be relative and stay inside the skill",
+            path
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SKILL: BuiltinSkill = BuiltinSkill::new(
+        "greeter",
+        &[
+            ("SKILL.md", b"---\nname: greeter\ndescription: Greets.\n---\nGreet.\n"),
+            ("scripts/greet.sh", b"#!/bin/bash\necho hi\n"),
+        ],
+    );
+
+    #[test]
+    fn test_unpack_reuses_identical_copies() {
+        let dir = TempDir::new().unwrap();
+        let skill_dir = unpack(&SKILL, dir.path()).unwrap();
+        assert_eq!(skill_dir.file_name().unwrap(), "greeter");
+        assert_eq!(fs::read(skill_dir.join("scripts/greet.sh")).unwrap(), b"#!/bin/bash\necho hi\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(skill_dir.join("scripts/greet.sh")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+        assert_eq!(unpack(&SKILL, dir.path()).unwrap(), skill_dir);
+
+        let changed = BuiltinSkill::new("greeter", &[("SKILL.md", b"---\nname: greeter\ndescription: Greets twice.\n---\n")]);
+        assert_ne!(unpack(&changed, dir.path()).unwrap(), skill_dir);
+        // No staging directories left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_unpack_rejects_escaping_paths() {
+        let dir = TempDir::new().unwrap();
+        for path in ["../evil", "/etc/passwd", "a/../../b", ""] {
+            let files: &'static [(&'static str, &'static [u8])] =
+                Box::leak(Box::new([("SKILL.md", b"---\nname: x\n---\n" as &[u8]), (path, b"" as &[u8])]));
+            let skill = BuiltinSkill::new("x", files);
+            assert!(
+                matches!(unpack(&skill, dir.path()), Err(OpenSkillError::InvalidManifest(_))),
+                "{path}"
+            );
+        }
+        let missing = BuiltinSkill::new("x", &[("README.md", b"")]);
+        assert!(unpack(&missing, dir.path()).is_err());
+        assert!(BUILTIN_SKILLS.iter().all(|s| unpack(s, dir.path()).is_ok()));
+    }
+}
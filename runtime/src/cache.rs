@@ -0,0 +1,409 @@
+//! Size-capped caches under `~/.cache/openskills/`.
+//!
+//! The runtime and the tools around it keep several caches next to each
+//! other: precompiled WASM components (`modules/`), the shared blob store
+//! (`blobs/`), and Python virtualenvs (`venvs/<key>/`) and Node.js
+//! dependency trees (`node_modules/<key>/`) that hosts or build steps
+//! provision for skills, skills downloaded from a remote registry
+//! (`registry/<name>/<version>/`) and built-in skills unpacked from the
+//! binary (`builtin/<name>-<hash>/`). Every cache is safe to delete: a
+//! missing entry is recompiled, re-imported, re-provisioned, re-downloaded
+//! or unpacked again.
+//!
+//! [`CacheManager`] treats each file (modules, blobs) or keyed directory
+//! (venvs, node_modules, registry versions, built-in skills) as one entry and evicts least recently used
+//! entries until the configured [`CacheLimits`] hold. A file was last used
+//! at the later of its modification and access time; a directory at the
+//! latest modification time in its tree, since listing it would bump its
+//! access times. Cache hits refresh an entry with [`CacheManager::touch`],
+//! as many filesystems update access times lazily or not at all.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A cache kept under the cache root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CacheKind {
+    /// Precompiled WASM components (`.cwasm`).
+    Modules,
+    /// Content-addressed blobs behind workspace imports.
+    Blobs,
+    /// Python virtualenvs, one directory per key.
+    Venvs,
+    /// Node.js dependency trees, one directory per key.
+    NodeModules,
+    /// Skills downloaded from a remote registry, one directory per version.
+    Registry,
+    /// Built-in skills unpacked from the binary, one directory per content hash.
+    Builtin,
+}
+
+impl CacheKind {
+    /// All cache kinds, in listing order.
+    pub const ALL: [CacheKind; 6] = [
+        CacheKind::Modules,
+        CacheKind::Blobs,
+        CacheKind::Venvs,
+        CacheKind::NodeModules,
+        CacheKind::Registry,
+        CacheKind::Builtin,
+    ];
+
+    /// Directory name under the cache root; also the CLI name.
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::Modules => "modules",
+            CacheKind::Blobs => "blobs",
+            CacheKind::Venvs => "venvs",
+            CacheKind::NodeModules => "node_modules",
+            CacheKind::Registry => "registry",
+            CacheKind::Builtin => "builtin",
+        }
+    }
+
+    /// Parse a directory name as returned by [`Self::dir_name`].
+    pub fn from_dir_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.dir_name() == name)
+    }
+
+    /// Depth of an entry below the kind's directory. Blobs are fanned out
+    /// by the first two hex digits of their hash, registry skills by name.
+    fn entry_depth(self) -> usize {
+        match self {
+            CacheKind::Blobs | CacheKind::Registry => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Size caps enforced by [`CacheManager::gc`].
+///
+/// The default is unlimited. Per-kind caps apply first, then `max_bytes`
+/// across all caches; least recently used entries are evicted first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// Total size of all caches.
+    pub max_bytes: Option<u64>,
+    /// Size of individual caches.
+    pub kind_max_bytes: BTreeMap<CacheKind, u64>,
+}
+
+impl CacheLimits {
+    /// True if these limits never evict anything.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_bytes.is_none() && self.kind_max_bytes.is_empty()
+    }
+}
+
+/// One cache entry: a file or a keyed directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    /// Cache the entry belongs to.
+    pub kind: CacheKind,
+    /// Absolute path of the entry.
+    pub path: PathBuf,
+    /// Size in bytes (recursive for directories).
+    pub size: u64,
+    /// Last use, as defined in the module docs.
+    pub last_used: SystemTime,
+}
+
+/// What a clear or collection pass removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheGcReport {
+    /// Entries removed.
+    pub removed: Vec<PathBuf>,
+    /// Bytes freed.
+    pub freed_bytes: u64,
+}
+
+/// Caches rooted at one directory.
+#[derive(Debug, Clone)]
+pub struct CacheManager {
+    root: PathBuf,
+}
+
+impl CacheManager {
+    /// Manage the caches under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Default cache root, `~/.cache/openskills/` (platform equivalent).
+    pub fn default_root() -> Option<PathBuf> {
+        dirs::cache_dir().map(|c| c.join("openskills"))
+    }
+
+    /// Default location of one cache.
+    pub fn default_dir(kind: CacheKind) -> Option<PathBuf> {
+        Self::default_root().map(|root| root.join(kind.dir_name()))
+    }
+
+    /// Cache root.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory of one cache.
+    pub fn dir(&self, kind: CacheKind) -> PathBuf {
+        self.root.join(kind.dir_name())
+    }
+
+    /// List entries, optionally of one cache, least recently used first.
+    pub fn list(&self, kind: Option<CacheKind>) -> std::io::Result<Vec<CacheEntry>> {
+        let kinds = match kind {
+            Some(kind) => vec![kind],
+            None => CacheKind::ALL.to_vec(),
+        };
+        let mut entries = Vec::new();
+        for kind in kinds {
+            collect_entries(kind, &self.dir(kind), kind.entry_depth(), &mut entries)?;
+        }
+        entries.sort_by(|a, b| a.last_used.cmp(&b.last_used).then_with(|| a.path.cmp(&b.path)));
+        Ok(entries)
+    }
+
+    /// Remove every entry, optionally of one cache only.
+    pub fn clear(&self, kind: Option<CacheKind>) -> std::io::Result<CacheGcReport> {
+        let mut report = CacheGcReport::default();
+        for entry in self.list(kind)? {
+            remove_entry(&entry, &mut report)?;
+        }
+        Ok(report)
+    }
+
+    /// Evict least recently used entries until `limits` hold.
+    pub fn gc(&self, limits: &CacheLimits) -> std::io::Result<CacheGcReport> {
+        let mut report = CacheGcReport::default();
+        if limits.is_unlimited() {
+            return Ok(report);
+        }
+
+        let entries = self.list(None)?;
+        let mut kind_totals: BTreeMap<CacheKind, u64> = BTreeMap::new();
+        for entry in &entries {
+            *kind_totals.entry(entry.kind).or_default() += entry.size;
+        }
+        let mut total: u64 = kind_totals.values().sum();
+        let mut kept = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let kind_total = kind_totals.entry(entry.kind).or_default();
+            let over_kind = limits
+                .kind_max_bytes
+                .get(&entry.kind)
+                .is_some_and(|max| *kind_total > *max);
+            if over_kind {
+                *kind_total -= entry.size;
+                total -= entry.size;
+                remove_entry(&entry, &mut report)?;
+            } else {
+                kept.push(entry);
+            }
+        }
+        if let Some(max) = limits.max_bytes {
+            for entry in &kept {
+                if total <= max {
+                    break;
+                }
+                total -= entry.size;
+                remove_entry(entry, &mut report)?;
+            }
+        }
+        Ok(report)
+    }
+
+    /// Mark a cache entry as just used (best effort).
+    pub fn touch(path: &Path) {
+        let now = SystemTime::now();
+        let times = std::fs::FileTimes::new().set_accessed(now).set_modified(now);
+        let _ = std::fs::File::open(path).and_then(|f| f.set_times(times));
+    }
+}
+
+/// Collect entries `depth` levels below `dir`.
+fn collect_entries(
+    kind: CacheKind,
+    dir: &Path,
+    depth: usize,
+    entries: &mut Vec<CacheEntry>,
+) -> std::io::Result<()> {
+    let read = match std::fs::read_dir(dir) {
+        Ok(read) => read,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for child in read {
+        let child = child?;
+        let path = child.path();
+        if is_in_flight(&path) {
+            continue;
+        }
+        let file_type = child.file_type()?;
+        if depth > 1 {
+            if file_type.is_dir() {
+                collect_entries(kind, &path, depth - 1, entries)?;
+            }
+            continue;
+        }
+        let metadata = child.metadata()?;
+        let (size, last_used) = if file_type.is_dir() {
+            tree_usage(&path)
+        } else {
+            (metadata.len(), last_used(&metadata))
+        };
+        entries.push(CacheEntry {
+            kind,
+            path,
+            size,
+            last_used,
+        });
+    }
+    Ok(())
+}
+
+/// Temporary files written before an atomic rename (`<key>.tmp<pid>`,
+/// `.<hash>.<pid>`); they belong to a concurrent writer.
+fn is_in_flight(path: &Path) -> bool {
+    let hidden = path
+        .file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with('.'));
+    let tmp = path
+        .extension()
+        .is_some_and(|e| e.to_string_lossy().starts_with("tmp"));
+    hidden || tmp
+}
+
+fn last_used(metadata: &std::fs::Metadata) -> SystemTime {
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let accessed = metadata.accessed().unwrap_or(SystemTime::UNIX_EPOCH);
+    modified.max(accessed)
+}
+
+/// Total size and latest modification of everything under `dir`.
+fn tree_usage(dir: &Path) -> (u64, SystemTime) {
+    let mut size = 0;
+    let mut latest = SystemTime::UNIX_EPOCH;
+    for entry in walkdir::WalkDir::new(dir).into_iter().flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_file() {
+            size += metadata.len();
+        }
+        latest = latest.max(metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+    }
+    (size, latest)
+}
+
+fn remove_entry(entry: &CacheEntry, report: &mut CacheGcReport) -> std::io::Result<()> {
+    let result = if entry.path.is_dir() {
+        std::fs::remove_dir_all(&entry.path)
+    } else {
+        std::fs::remove_file(&entry.path)
+    };
+    match result {
+        Ok(()) => {
+            report.freed_bytes += entry.size;
+            report.removed.push(entry.path.clone());
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    // Drop a blob fan-out directory once it is empty.
+    if entry.kind.entry_depth() > 1 {
+        if let Some(parent) = entry.path.parent() {
+            let _ = std::fs::remove_dir(parent);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn write_entry(path: &Path, size: usize, age_secs: u64) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; size]).unwrap();
+        set_age(path, age_secs);
+    }
+
+    fn set_age(path: &Path, age_secs: u64) {
+        let at = SystemTime::now() - Duration::from_secs(age_secs);
+        let times = std::fs::FileTimes::new().set_accessed(at).set_modified(at);
+        std::fs::File::open(path).unwrap().set_times(times).unwrap();
+    }
+
+    #[test]
+    fn test_list_entries_by_kind() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp.path());
+        write_entry(&cache.dir(CacheKind::Modules).join("a.cwasm"), 10, 30);
+        write_entry(&cache.dir(CacheKind::Modules).join("b.cwasm.tmp42"), 10, 0);
+        write_entry(&cache.dir(CacheKind::Blobs).join("ab").join("abcd"), 5, 20);
+        write_entry(&cache.dir(CacheKind::Blobs).join("ab").join(".abcd.42"), 5, 0);
+        let venv = cache.dir(CacheKind::Venvs).join("pdf");
+        write_entry(&venv.join("lib").join("site.py"), 7, 10);
+        write_entry(&venv.join("bin").join("python"), 3, 10);
+        set_age(&venv, 10);
+
+        let entries = cache.list(None).unwrap();
+        let summary: Vec<_> = entries.iter().map(|e| (e.kind, e.size)).collect();
+        assert_eq!(
+            summary,
+            vec![(CacheKind::Modules, 10), (CacheKind::Blobs, 5), (CacheKind::Venvs, 10)]
+        );
+        assert_eq!(cache.list(Some(CacheKind::NodeModules)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_gc_evicts_least_recently_used() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp.path());
+        let modules = cache.dir(CacheKind::Modules);
+        write_entry(&modules.join("old.cwasm"), 100, 300);
+        write_entry(&modules.join("mid.cwasm"), 100, 200);
+        write_entry(&modules.join("new.cwasm"), 100, 100);
+        let blob = cache.dir(CacheKind::Blobs).join("ff").join("ffff");
+        write_entry(&blob, 100, 250);
+
+        assert_eq!(cache.gc(&CacheLimits::default()).unwrap(), CacheGcReport::default());
+
+        // A hit makes the oldest module the most recently used.
+        CacheManager::touch(&modules.join("old.cwasm"));
+        let limits = CacheLimits {
+            kind_max_bytes: BTreeMap::from([(CacheKind::Modules, 200)]),
+            ..Default::default()
+        };
+        let report = cache.gc(&limits).unwrap();
+        assert_eq!(report.removed, vec![modules.join("mid.cwasm")]);
+        assert_eq!(report.freed_bytes, 100);
+
+        let limits = CacheLimits {
+            max_bytes: Some(150),
+            ..Default::default()
+        };
+        let report = cache.gc(&limits).unwrap();
+        assert_eq!(report.removed, vec![blob.clone(), modules.join("new.cwasm")]);
+        assert!(!blob.parent().unwrap().exists());
+        assert!(modules.join("old.cwasm").exists());
+    }
+
+    #[test]
+    fn test_clear() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp.path());
+        write_entry(&cache.dir(CacheKind::Modules).join("a.cwasm"), 1, 0);
+        write_entry(&cache.dir(CacheKind::NodeModules).join("k").join("index.js"), 2, 0);
+
+        let report = cache.clear(Some(CacheKind::NodeModules)).unwrap();
+        assert_eq!(report.removed, vec![cache.dir(CacheKind::NodeModules).join("k")]);
+        assert_eq!(report.freed_bytes, 2);
+        assert_eq!(cache.list(None).unwrap().len(), 1);
+        assert_eq!(cache.clear(None).unwrap().removed.len(), 1);
+        assert!(cache.list(None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Tool → sandbox capability mapping.
+//!
+//! Approved tools (Layer 2) become sandbox grants (Layer 3) through a
+//! [`CapabilityMapping`]. The default covers Claude Code's tools:
+//!
+//! | Tools | Grants |
+//! |-------|--------|
+//! | `Read`, `Grep`, `Glob`, `LS` | read `.` |
+//! | `Write`, `Edit`, `MultiEdit` | write `.` |
+//! | `Bash`, `Terminal` | read and write `.`, subprocesses |
+//! | `WebSearch`, `Fetch` | network |
+//!
+//! Hosts add their own tools (`Database`, `mcp__fs__write`, ...) or change
+//! the built-in ones with [`CapabilityMapping::with_tool`] or a TOML policy
+//! file:
+//!
+//! ```toml
+//! [tools.Database]
+//! network = true
+//!
+//! [tools.mcp__fs__write]
+//! write = ["output"]
+//! ```
+//!
+//! Paths are relative to the skill root. Tools without an entry grant
+//! nothing.
+
+use crate::errors::OpenSkillError;
+use crate::manifest::WasmConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// What approving one tool grants inside the sandbox.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolCapabilities {
+    /// Readable paths, relative to the skill root.
+    pub read: Vec<String>,
+    /// Writable paths, relative to the skill root.
+    pub write: Vec<String>,
+    /// Outbound network access.
+    pub network: bool,
+    /// Spawning subprocesses from native scripts.
+    pub process: bool,
+}
+
+/// Tool name → [`ToolCapabilities`]; see the module docs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityMapping {
+    #[serde(default)]
+    tools: BTreeMap<String, ToolCapabilities>,
+}
+
+impl Default for CapabilityMapping {
+    fn default() -> Self {
+        let read = ToolCapabilities {
+            read: vec![".".to_string()],
+            ..Default::default()
+        };
+        let write = ToolCapabilities {
+            write: vec![".".to_string()],
+            ..Default::default()
+        };
+        let shell = ToolCapabilities {
+            read: vec![".".to_string()],
+            write: vec![".".to_string()],
+            process: true,
+            ..Default::default()
+        };
+        let network = ToolCapabilities {
+            network: true,
+            ..Default::default()
+        };
+        let mut mapping = Self::empty();
+        for (tools, capabilities) in [
+            (&["Read", "Grep", "Glob", "LS"][..], read),
+            (&["Write", "Edit", "MultiEdit"][..], write),
+            (&["Bash", "Terminal"][..], shell),
+            (&["WebSearch", "Fetch"][..], network),
+        ] {
+            for tool in tools {
+                mapping = mapping.with_tool(tool, capabilities.clone());
+            }
+        }
+        mapping
+    }
+}
+
+impl CapabilityMapping {
+    /// A mapping where no tool grants anything.
+    pub fn empty() -> Self {
+        Self {
+            tools: BTreeMap::new(),
+        }
+    }
+
+    /// Set what `tool` grants, replacing any previous entry.
+    pub fn with_tool(mut self, tool: &str, capabilities: ToolCapabilities) -> Self {
+        self.tools.insert(tool.to_string(), capabilities);
+        self
+    }
+
+    /// The default mapping with the `[tools.<name>]` tables of a TOML
+    /// policy file added (replacing built-in entries of the same name).
+    pub fn from_toml(content: &str) -> Result<Self, OpenSkillError> {
+        let file: CapabilityMapping = toml::from_str(content).map_err(|e| {
+            OpenSkillError::InvalidConfig(format!("invalid capability mapping: {e}"))
+        })?;
+        Ok(file
+            .tools
+            .into_iter()
+            .fold(Self::default(), |mapping, (tool, capabilities)| {
+                mapping.with_tool(&tool, capabilities)
+            }))
+    }
+
+    /// [`Self::from_toml`] on the contents of `path`.
+    pub fn from_file(path: &Path) -> Result<Self, OpenSkillError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            OpenSkillError::InvalidConfig(format!(
+                "cannot read capability mapping {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    /// What `tool` grants, if it has an entry.
+    pub fn get(&self, tool: &str) -> Option<&ToolCapabilities> {
+        self.tools.get(tool)
+    }
+
+    /// WASI capabilities for the approved `tools`.
+    pub(crate) fn wasm_config(&self, tools: &[String]) -> WasmConfig {
+        let mut config = WasmConfig::default();
+        for capabilities in tools.iter().filter_map(|tool| self.get(tool)) {
+            for path in &capabilities.read {
+                if !config.filesystem.read.contains(path) {
+                    config.filesystem.read.push(path.clone());
+                }
+            }
+            for path in &capabilities.write {
+                if !config.filesystem.write.contains(path) {
+                    config.filesystem.write.push(path.clone());
+                }
+            }
+            if capabilities.network && !config.network.allow.iter().any(|h| h == "*") {
+                config.network.allow.push("*".to_string());
+            }
+        }
+        config
+    }
+
+    /// The approved `tools` that let native scripts spawn subprocesses.
+    pub(crate) fn process_tools(&self, tools: &[String]) -> Vec<String> {
+        tools
+            .iter()
+            .filter(|tool| self.get(tool).is_some_and(|capabilities| capabilities.process))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let mapping = CapabilityMapping::default();
+        let config = mapping.wasm_config(&tools(&["Read", "Bash", "Fetch", "Unknown"]));
+        assert_eq!(config.filesystem.read, ["."]);
+        assert_eq!(config.filesystem.write, ["."]);
+        assert_eq!(config.network.allow, ["*"]);
+        assert_eq!(mapping.process_tools(&tools(&["Read", "Terminal"])), ["Terminal"]);
+        assert!(mapping.process_tools(&tools(&["Read", "Write"])).is_empty());
+    }
+
+    #[test]
+    fn test_from_toml_adds_and_replaces_tools() {
+        let mapping = CapabilityMapping::from_toml(
+            r#"
+            [tools.Database]
+            network = true
+
+            [tools.mcp__fs__write]
+            write = ["output"]
+
+            [tools.Bash]
+            read = ["."]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(mapping.wasm_config(&tools(&["Database"])).network.allow, ["*"]);
+        assert_eq!(
+            mapping.wasm_config(&tools(&["mcp__fs__write"])).filesystem.write,
+            ["output"]
+        );
+        assert!(mapping.process_tools(&tools(&["Bash"])).is_empty());
+        assert!(mapping.get("Read").is_some());
+
+        assert!(matches!(
+            CapabilityMapping::from_toml("[tools.Database]\nnetwrok = true\n"),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+    }
+}
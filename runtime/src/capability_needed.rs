@@ -0,0 +1,207 @@
+//! What a failed execution would have needed, in a form agents can act on.
+//!
+//! Sandbox violations and blocked WASM requests say what was denied
+//! (`file-write-create /srv/out`, `network-outbound 93.184.216.34:443`), not
+//! which tool would have allowed it. This module names the tool: one the
+//! skill asked for whose capability mapping grants the operation, otherwise
+//! the built-in tool for it (`Read`, `Write`, `Bash`, `Fetch`). An agent can
+//! then ask the user for exactly that permission and run the skill again.
+
+use crate::audit::{HostCall, NetworkRequest};
+use crate::capability_mapping::{CapabilityMapping, ToolCapabilities};
+use crate::sandbox_violation::SandboxViolation;
+use serde::{Deserialize, Serialize};
+
+/// A capability a failed execution was denied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityNeeded {
+    /// Tool to ask the user for, e.g. `Write` or `Fetch`.
+    pub tool: String,
+    /// Path, host or URL the execution tried to reach, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+}
+
+/// Kind of access a denied operation needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Write,
+    Network,
+    Process,
+}
+
+impl Access {
+    fn granted_by(self, capabilities: &ToolCapabilities) -> bool {
+        match self {
+            Access::Read => !capabilities.read.is_empty(),
+            Access::Write => !capabilities.write.is_empty(),
+            Access::Network => capabilities.network,
+            Access::Process => capabilities.process,
+        }
+    }
+
+    /// Built-in tool granting this access in the default mapping.
+    fn default_tool(self) -> &'static str {
+        match self {
+            Access::Read => "Read",
+            Access::Write => "Write",
+            Access::Network => "Fetch",
+            Access::Process => "Bash",
+        }
+    }
+}
+
+/// The capabilities behind `violations`, `network_requests` and `host_calls`
+/// that were denied, each named after a tool from `requested_tools` when
+/// `mapping` says one grants it.
+pub(crate) fn capabilities_needed(
+    violations: &[SandboxViolation],
+    network_requests: &[NetworkRequest],
+    host_calls: &[HostCall],
+    requested_tools: &[String],
+    mapping: &CapabilityMapping,
+) -> Vec<CapabilityNeeded> {
+    let denied = violations
+        .iter()
+        .filter_map(|v| violation_access(&v.operation).map(|access| (access, v.path.clone())))
+        .chain(
+            network_requests
+                .iter()
+                .filter(|r| !r.allowed)
+                .map(|r| (Access::Network, Some(r.host.clone()))),
+        )
+        .chain(
+            host_calls
+                .iter()
+                .filter(|c| !c.allowed)
+                .filter_map(|c| host_call_access(&c.function).map(|access| (access, Some(c.target.clone())))),
+        );
+
+    let mut needed: Vec<CapabilityNeeded> = Vec::new();
+    for (access, resource) in denied {
+        let tool = requested_tools
+            .iter()
+            .find(|tool| mapping.get(tool).is_some_and(|c| access.granted_by(c)))
+            .map_or_else(|| access.default_tool().to_string(), Clone::clone);
+        let capability = CapabilityNeeded { tool, resource };
+        if !needed.contains(&capability) {
+            needed.push(capability);
+        }
+    }
+    needed
+}
+
+/// `file-write-create` → write, `network-outbound` → network, ...; `None`
+/// for operations no tool grants (e.g. seccomp-blocked syscalls). A
+/// `file-access` denial does not say whether it was a read or a write, so it
+/// asks for write access, under which the path is readable too.
+fn violation_access(operation: &str) -> Option<Access> {
+    if operation.starts_with("file-write") || operation == "file-access" {
+        Some(Access::Write)
+    } else if operation.starts_with("file-read") {
+        Some(Access::Read)
+    } else if operation.starts_with("network") {
+        Some(Access::Network)
+    } else if operation.starts_with("process-") {
+        Some(Access::Process)
+    } else {
+        None
+    }
+}
+
+fn host_call_access(function: &str) -> Option<Access> {
+    match function {
+        "read-workspace-file" => Some(Access::Read),
+        "write-workspace-file" => Some(Access::Write),
+        "http-fetch" => Some(Access::Network),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(operation: &str, path: Option<&str>) -> SandboxViolation {
+        SandboxViolation {
+            operation: operation.to_string(),
+            path: path.map(String::from),
+        }
+    }
+
+    fn needed(tool: &str, resource: Option<&str>) -> CapabilityNeeded {
+        CapabilityNeeded {
+            tool: tool.to_string(),
+            resource: resource.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_violations_name_a_requested_tool() {
+        let violations = [
+            violation("file-write-create", Some("/srv/out.txt")),
+            violation("network-outbound", Some("93.184.216.34:443")),
+            violation("file-read", Some("/etc/shadow")),
+            violation("file-access", Some("/srv/data")),
+            violation("syscall", None),
+            violation("file-write", Some("/srv/out.txt")),
+        ];
+        let requested = vec!["Edit".to_string(), "WebSearch".to_string()];
+        assert_eq!(
+            capabilities_needed(&violations, &[], &[], &requested, &CapabilityMapping::default()),
+            vec![
+                needed("Edit", Some("/srv/out.txt")),
+                needed("WebSearch", Some("93.184.216.34:443")),
+                needed("Read", Some("/etc/shadow")),
+                needed("Edit", Some("/srv/data")),
+            ]
+        );
+        assert_eq!(
+            capabilities_needed(&violations[..2], &[], &[], &[], &CapabilityMapping::default()),
+            vec![
+                needed("Write", Some("/srv/out.txt")),
+                needed("Fetch", Some("93.184.216.34:443")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocked_wasm_requests() {
+        let requests = [
+            NetworkRequest {
+                method: "GET".to_string(),
+                url: "https://api.example.com/v1".to_string(),
+                host: "api.example.com".to_string(),
+                port: 443,
+                allowed: false,
+            },
+            NetworkRequest {
+                method: "GET".to_string(),
+                url: "https://ok.example.com/".to_string(),
+                host: "ok.example.com".to_string(),
+                port: 443,
+                allowed: true,
+            },
+        ];
+        let calls = [
+            HostCall {
+                function: "write-workspace-file".to_string(),
+                target: "report.md".to_string(),
+                allowed: false,
+            },
+            HostCall {
+                function: "log".to_string(),
+                target: "info".to_string(),
+                allowed: false,
+            },
+        ];
+        assert_eq!(
+            capabilities_needed(&[], &requests, &calls, &[], &CapabilityMapping::default()),
+            vec![
+                needed("Fetch", Some("api.example.com")),
+                needed("Write", Some("report.md")),
+            ]
+        );
+    }
+}
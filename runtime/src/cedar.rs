@@ -0,0 +1,331 @@
+//! Permission decisions from Cedar policies.
+//!
+//! On servers there is nobody to answer a prompt, so instead of an
+//! interactive callback a host can install a [`CedarPolicyCallback`] and
+//! decide permission requests with org-wide [Cedar](https://www.cedarpolicy.com)
+//! policies. Each request is evaluated as:
+//!
+//! - principal `Skill::"<skill id>"`, a member of `Location::"<location>"`
+//!   (`personal`, `project`, `nested`, `custom`), with a `location` attribute;
+//! - action `Action::"<tool>"`, e.g. `Action::"Bash"`;
+//! - resource `Tool::"<tool>"`, a member of `Risk::"<risk>"` (`low`,
+//!   `medium`, `high`), with a `risk` attribute;
+//! - context with `risk`, `description`, every entry of the request's
+//!   context as a string (e.g. `argument`, `command`, `skill_root`), and
+//!   `paths`, the set of paths the request names.
+//!
+//! ```cedar
+//! // Project skills may run shell commands; nothing else may.
+//! permit(principal in Location::"project", action == Action::"Bash", resource);
+//!
+//! // Writes are fine outside of /etc.
+//! permit(principal, action == Action::"Write", resource)
+//!     unless { context.argument like "/etc/*" };
+//!
+//! forbid(principal, action, resource in Risk::"high")
+//!     when { context.paths.contains("/") };
+//! ```
+//!
+//! Cedar denies what no policy permits. With a fallback callback, requests
+//! no policy decides go to it instead.
+
+use crate::errors::OpenSkillError;
+use crate::permission_callback::{
+    PermissionCallback, PermissionRequest, PermissionResponse, RiskLevel,
+};
+use cedar_policy::{
+    Authorizer, Context, Decision, Entities, Entity, EntityId, EntityTypeName, EntityUid,
+    PolicySet, Request, RestrictedExpression,
+};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Answers permission requests by evaluating Cedar policies; see the module
+/// docs for the entities a request is evaluated with.
+pub struct CedarPolicyCallback {
+    policies: PolicySet,
+    authorizer: Authorizer,
+    fallback: Option<Arc<dyn PermissionCallback>>,
+}
+
+impl std::fmt::Debug for CedarPolicyCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CedarPolicyCallback")
+            .field("policies", &self.policies.policies().count())
+            .field("has_fallback", &self.fallback.is_some())
+            .finish()
+    }
+}
+
+impl CedarPolicyCallback {
+    /// Parse Cedar policies.
+    pub fn from_policies(policies: &str) -> Result<Self, OpenSkillError> {
+        let policies = PolicySet::from_str(policies)
+            .map_err(|e| OpenSkillError::InvalidConfig(format!("invalid Cedar policies: {e}")))?;
+        Ok(Self {
+            policies,
+            authorizer: Authorizer::new(),
+            fallback: None,
+        })
+    }
+
+    /// [`Self::from_policies`] on the contents of `path`.
+    pub fn from_file(path: &Path) -> Result<Self, OpenSkillError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            OpenSkillError::InvalidConfig(format!(
+                "cannot read Cedar policies {}: {e}",
+                path.display()
+            ))
+        })?;
+        Self::from_policies(&content)
+    }
+
+    /// Ask `fallback` about requests no policy permits or forbids, instead
+    /// of denying them.
+    pub fn with_fallback(mut self, fallback: Arc<dyn PermissionCallback>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Evaluate `request`: `Some(true)` if permitted, `Some(false)` if
+    /// forbidden or denied by default, `None` if no policy applied and there
+    /// is a fallback.
+    fn decide(&self, request: &PermissionRequest) -> Result<Option<bool>, OpenSkillError> {
+        let risk = risk_name(request.risk_level);
+        let location = request.context.get("location").map(String::as_str);
+
+        let skill = uid("Skill", &request.skill_id)?;
+        let mut skill_parents = HashSet::new();
+        if let Some(location) = location {
+            skill_parents.insert(uid("Location", location)?);
+        }
+        let skill_attrs = HashMap::from([(
+            "location".to_string(),
+            RestrictedExpression::new_string(location.unwrap_or_default().to_string()),
+        )]);
+        let tool = uid("Tool", &request.tool)?;
+        let tool_attrs = HashMap::from([(
+            "risk".to_string(),
+            RestrictedExpression::new_string(risk.to_string()),
+        )]);
+        let entities = Entities::from_entities([
+            Entity::new(skill.clone(), skill_attrs, skill_parents),
+            Entity::new(
+                tool.clone(),
+                tool_attrs,
+                HashSet::from([uid("Risk", risk)?]),
+            ),
+        ])
+        .map_err(|e| OpenSkillError::InvalidConfig(format!("invalid Cedar entities: {e}")))?;
+
+        let mut context: Vec<(String, RestrictedExpression)> = request
+            .context
+            .iter()
+            .map(|(key, value)| (key.clone(), RestrictedExpression::new_string(value.clone())))
+            .collect();
+        context.push((
+            "risk".to_string(),
+            RestrictedExpression::new_string(risk.to_string()),
+        ));
+        context.push((
+            "description".to_string(),
+            RestrictedExpression::new_string(request.description.clone()),
+        ));
+        context.push((
+            "paths".to_string(),
+            RestrictedExpression::new_set(
+                request_paths(request)
+                    .into_iter()
+                    .map(RestrictedExpression::new_string),
+            ),
+        ));
+
+        let cedar_request = Request::new(
+            Some(skill),
+            Some(uid("Action", &request.tool)?),
+            Some(tool),
+            Context::from_pairs(context),
+        );
+        let response = self
+            .authorizer
+            .is_authorized(&cedar_request, &self.policies, &entities);
+        let decided = response.diagnostics().reason().next().is_some();
+        Ok(match response.decision() {
+            Decision::Allow => Some(true),
+            Decision::Deny if decided || self.fallback.is_none() => Some(false),
+            Decision::Deny => None,
+        })
+    }
+}
+
+impl PermissionCallback for CedarPolicyCallback {
+    fn request_permission(
+        &self,
+        request: &PermissionRequest,
+    ) -> Result<PermissionResponse, OpenSkillError> {
+        match (self.decide(request)?, &self.fallback) {
+            (Some(true), _) => Ok(PermissionResponse::AllowOnce),
+            (None, Some(fallback)) => fallback.request_permission(request),
+            _ => Ok(PermissionResponse::Deny),
+        }
+    }
+}
+
+fn uid(kind: &str, id: &str) -> Result<EntityUid, OpenSkillError> {
+    let invalid = |e: cedar_policy::ParseErrors| {
+        OpenSkillError::InvalidConfig(format!("invalid Cedar entity {kind}::{id:?}: {e}"))
+    };
+    Ok(EntityUid::from_type_name_and_id(
+        EntityTypeName::from_str(kind).map_err(invalid)?,
+        EntityId::from_str(id).map_err(invalid)?,
+    ))
+}
+
+fn risk_name(risk: RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Low => "low",
+        RiskLevel::Medium => "medium",
+        RiskLevel::High => "high",
+    }
+}
+
+/// Paths named by the request's context: `argument`s that look like paths,
+/// the `workspace` and entries whose key ends in `path`, `paths` or `root`
+/// (comma-separated).
+fn request_paths(request: &PermissionRequest) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for (key, value) in &request.context {
+        let listed = key.ends_with("path")
+            || key.ends_with("paths")
+            || key.ends_with("root")
+            || key == "workspace";
+        let path_argument = key == "argument" && (value.starts_with('/') || value.starts_with('.'));
+        if listed || path_argument {
+            paths.extend(
+                value
+                    .split(", ")
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_string),
+            );
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(
+        skill: &str,
+        tool: &str,
+        risk: RiskLevel,
+        context: &[(&str, &str)],
+    ) -> PermissionRequest {
+        PermissionRequest {
+            skill_id: skill.to_string(),
+            tool: tool.to_string(),
+            description: format!("Execute {} operations", tool),
+            risk_level: risk,
+            context: context
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    const POLICIES: &str = r#"
+permit(principal in Location::"project", action == Action::"Bash", resource);
+permit(principal, action == Action::"Write", resource)
+    unless { context.argument like "/etc/*" };
+forbid(principal == Skill::"untrusted", action, resource in Risk::"high");
+"#;
+
+    #[test]
+    fn test_cedar_policies_decide_requests() {
+        let callback = CedarPolicyCallback::from_policies(POLICIES).unwrap();
+        let allowed = |request: PermissionRequest| {
+            matches!(
+                callback.request_permission(&request).unwrap(),
+                PermissionResponse::AllowOnce
+            )
+        };
+
+        assert!(allowed(request(
+            "build",
+            "Bash",
+            RiskLevel::High,
+            &[("location", "project")]
+        )));
+        assert!(!allowed(request(
+            "notes",
+            "Bash",
+            RiskLevel::High,
+            &[("location", "personal")]
+        )));
+        assert!(allowed(request(
+            "docs",
+            "Write",
+            RiskLevel::Medium,
+            &[("argument", "/tmp/out.md")]
+        )));
+        assert!(!allowed(request(
+            "docs",
+            "Write",
+            RiskLevel::Medium,
+            &[("argument", "/etc/hosts")]
+        )));
+        assert!(!allowed(request(
+            "untrusted",
+            "Bash",
+            RiskLevel::High,
+            &[("location", "project")]
+        )));
+        // Nothing permits Fetch: denied by default
+        assert!(!allowed(request("build", "Fetch", RiskLevel::High, &[])));
+
+        assert!(CedarPolicyCallback::from_policies("permit(principal, action").is_err());
+    }
+
+    #[test]
+    fn test_undecided_requests_go_to_the_fallback() {
+        struct AllowAll;
+        impl PermissionCallback for AllowAll {
+            fn request_permission(
+                &self,
+                _request: &PermissionRequest,
+            ) -> Result<PermissionResponse, OpenSkillError> {
+                Ok(PermissionResponse::AllowAlways)
+            }
+        }
+
+        let callback = CedarPolicyCallback::from_policies(POLICIES)
+            .unwrap()
+            .with_fallback(Arc::new(AllowAll));
+        let fetch = request("build", "Fetch", RiskLevel::High, &[]);
+        assert!(matches!(
+            callback.request_permission(&fetch).unwrap(),
+            PermissionResponse::AllowAlways
+        ));
+        // A forbid still wins over the fallback
+        let bash = request("untrusted", "Bash", RiskLevel::High, &[]);
+        assert!(matches!(
+            callback.request_permission(&bash).unwrap(),
+            PermissionResponse::Deny
+        ));
+
+        let write = request(
+            "docs",
+            "Write",
+            RiskLevel::Medium,
+            &[("skill_root", "/skills/docs")],
+        );
+        assert_eq!(
+            request_paths(&write),
+            BTreeSet::from(["/skills/docs".to_string()])
+        );
+    }
+}
@@ -0,0 +1,1130 @@
+//! `openskillsd`: one long-running runtime shared by many agent processes.
+//!
+//! A [`DaemonServer`] owns an [`OpenSkillRuntime`] (and with it the
+//! discovered skills, compiled WASM modules and warm caches) and serves it
+//! on a Unix socket, so short-lived agent processes connect with a
+//! [`DaemonClient`] instead of each paying for discovery and compilation.
+//!
+//! The protocol is newline-delimited JSON. Each request is
+//! `{"id": 1, "method": "list_skills", "params": {...}}` and gets one
+//! response line, `{"id": 1, "result": ...}` or `{"id": 1, "error": "..."}`.
+//! Methods:
+//!
+//! - `status`: daemon version, compiled-in features, pid, uptime and
+//!   number of loaded skills
+//! - `discover_skills`, `reload_skills`, `list_skills`
+//! - `activate_skill` and `list_skill_targets` (`skill_id`)
+//! - `execute_skill` and `run_skill_target` (`skill_id`, `options`)
+//! - `start_session` (`skill_id`, `input`, `workspace_dir`),
+//!   `record_tool_call` (`session_id`, `tool`, `output`) and
+//!   `finish_session` (`session_id`, `output`, `stdout`, `stderr`,
+//!   `exit_status`); sessions belong to the connection that started them and
+//!   are dropped when it closes
+//! - `detach_session` (`session_id`) hands a session over to the daemon so
+//!   it outlives the connection; `list_sessions` shows detached sessions and
+//!   `attach_session` (`session_id`) claims one on another connection, e.g.
+//!   an IDE extension continuing a workflow a CLI started
+//! - `list_outputs` and `read_output` (`path`, `offset`, `length`) let remote
+//!   frontends fetch the files skills wrote, in ranges of at most
+//!   [`MAX_OUTPUT_CHUNK`] bytes; both look in the workspace of a session
+//!   (`session_id`, its `workspace_dir`), a conversation (`conversation_id`,
+//!   see `OpenSkillRuntime::workspace_for`) or, by default, the runtime
+//! - `shutdown`
+//!
+//! Results use the same JSON as the C ABI. Calls from all connections are
+//! serialized on the shared runtime, like calls on one binding handle.
+
+use crate::errors::OpenSkillError;
+use crate::workspace_snapshot::{self, MAX_FILES};
+use crate::{
+    CapabilityDowngrade, CapabilityNeeded, ExecutionOptions, ExecutionTarget, HostCall, NetworkRequest, OpenSkillRuntime,
+    RetryPolicy, RuntimeAuditRecord, RuntimeExecutionStatus, SandboxOverrides, SandboxViolation,
+    SkillChange, SkillDescriptor, SkillExecutionSession, SkillLocation, SkillTarget,
+};
+use base64::Engine as _;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs::{self, File, Permissions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Instant, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// Environment variable overriding the default daemon socket.
+pub const DAEMON_SOCKET_ENV: &str = "OPENSKILLS_SOCKET";
+
+const SOCKET_NAME: &str = "openskillsd.sock";
+
+/// Most bytes one `read_output` call returns.
+pub const MAX_OUTPUT_CHUNK: u64 = 1024 * 1024;
+
+/// Socket the daemon listens on and clients connect to by default:
+/// `$OPENSKILLS_SOCKET`, else `openskillsd.sock` in the user's runtime
+/// directory (`$XDG_RUNTIME_DIR`), else in `~/.cache/openskills/`.
+pub fn default_daemon_socket() -> PathBuf {
+    if let Some(path) = env::var_os(DAEMON_SOCKET_ENV).filter(|p| !p.is_empty()) {
+        return PathBuf::from(path);
+    }
+    dirs::runtime_dir()
+        .or_else(|| dirs::cache_dir().map(|dir| dir.join("openskills")))
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join(SOCKET_NAME)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Request {
+    id: u64,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Response {
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Reply to `status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// Runtime version of the daemon.
+    pub version: String,
+    /// Cargo features the daemon was built with (see [`crate::features`]).
+    #[serde(default)]
+    pub features: Vec<String>,
+    pub pid: u32,
+    pub uptime_ms: u64,
+    /// Loaded skills (each ID counts once).
+    pub skills: usize,
+}
+
+/// Options for `execute_skill`, mirroring [`ExecutionOptions`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonExecuteOptions {
+    pub timeout_ms: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub cpu_quota: Option<f64>,
+    pub max_fuel: Option<u64>,
+    pub input: Option<Value>,
+    pub read_paths: Vec<String>,
+    pub write_paths: Vec<String>,
+    pub allow_network: bool,
+    pub env: BTreeMap<String, String>,
+    pub dry_run: bool,
+    pub retry: Option<DaemonRetryOptions>,
+    /// W3C trace context; see `ExecutionOptions::traceparent`.
+    pub traceparent: Option<String>,
+}
+
+/// [`RetryPolicy`](crate::RetryPolicy) for `execute_skill`; see
+/// `RetryPolicy::from_millis`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonRetryOptions {
+    pub max_attempts: u32,
+    #[serde(default)]
+    pub backoff_ms: u64,
+    #[serde(default)]
+    pub max_backoff_ms: Option<u64>,
+    /// `timeout`, `failure` and/or `error`; empty = timeouts and failures.
+    #[serde(default)]
+    pub retry_on: Vec<String>,
+}
+
+/// Options for `run_skill_target`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonTargetOptions {
+    /// `auto` (default), `script` or `wasm`.
+    pub target_type: Option<String>,
+    pub path: Option<String>,
+    pub args: Vec<String>,
+    pub timeout_ms: Option<u64>,
+    pub input: Option<Value>,
+    pub workspace_dir: Option<String>,
+    pub read_paths: Vec<String>,
+    pub write_paths: Vec<String>,
+    pub allow_network: bool,
+    pub env: BTreeMap<String, String>,
+}
+
+fn overrides(
+    read_paths: Vec<String>,
+    write_paths: Vec<String>,
+    allow_network: bool,
+    env: BTreeMap<String, String>,
+) -> SandboxOverrides {
+    SandboxOverrides {
+        read_paths: read_paths.into_iter().map(PathBuf::from).collect(),
+        write_paths: write_paths.into_iter().map(PathBuf::from).collect(),
+        allow_network,
+        env,
+    }
+}
+
+/// Declared or missing `bins` and `env` of a skill.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonRequires {
+    pub bins: Vec<String>,
+    pub env: Vec<String>,
+}
+
+/// Reply to `activate_skill`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonLoadedSkill {
+    pub id: String,
+    pub version: String,
+    pub name: String,
+    pub description: String,
+    pub allowed_tools: Vec<String>,
+    pub model: Option<String>,
+    pub context: Option<String>,
+    pub agent: Option<String>,
+    pub user_invocable: bool,
+    pub location: SkillLocation,
+    pub instructions: String,
+    pub requires: Option<DaemonRequires>,
+    pub missing_dependencies: Option<DaemonRequires>,
+}
+
+impl From<crate::LoadedSkill> for DaemonLoadedSkill {
+    fn from(loaded: crate::LoadedSkill) -> Self {
+        Self {
+            version: loaded.manifest.version().to_string(),
+            name: loaded.manifest.name.clone(),
+            description: loaded.manifest.description.clone(),
+            allowed_tools: loaded.manifest.get_allowed_tools(),
+            model: loaded.manifest.model.clone(),
+            context: loaded.manifest.context.clone(),
+            agent: loaded.manifest.agent.clone(),
+            user_invocable: loaded.manifest.is_user_invocable(),
+            requires: loaded.requires.map(|r| DaemonRequires {
+                bins: r.bins,
+                env: r.env,
+            }),
+            missing_dependencies: loaded.missing_dependencies.map(|m| DaemonRequires {
+                bins: m.bins,
+                env: m.env,
+            }),
+            id: loaded.id,
+            location: loaded.location,
+            instructions: loaded.instructions,
+        }
+    }
+}
+
+/// Audit record of a daemon execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonAuditRecord {
+    pub skill_id: String,
+    pub version: String,
+    pub input_hash: String,
+    pub output_hash: String,
+    pub start_time_ms: u64,
+    pub duration_ms: u64,
+    pub permissions_used: Vec<String>,
+    /// `success`, `timeout`, `permission_denied`, `instruction_only`,
+    /// `dry_run`, `failed:<reason>`, `sandbox_violation:<reason>` or
+    /// `schema_violation:<reason>`.
+    pub exit_status: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub sandbox_mode: String,
+    pub sandbox_violations: Vec<SandboxViolation>,
+    pub fuel_consumed: Option<u64>,
+    pub network_requests: Vec<NetworkRequest>,
+    pub host_calls: Vec<HostCall>,
+    pub capability_downgrades: Vec<CapabilityDowngrade>,
+    pub attempt: u32,
+    pub traceparent: Option<String>,
+}
+
+impl From<RuntimeAuditRecord> for DaemonAuditRecord {
+    fn from(audit: RuntimeAuditRecord) -> Self {
+        let exit_status = match audit.exit_status.reason() {
+            Some(reason) => format!("{}:{}", audit.exit_status.label(), reason),
+            None => audit.exit_status.label().to_string(),
+        };
+        Self {
+            sandbox_mode: audit.sandbox_mode.as_audit_str().to_string(),
+            skill_id: audit.skill_id,
+            version: audit.version,
+            input_hash: audit.input_hash,
+            output_hash: audit.output_hash,
+            start_time_ms: audit.start_time_ms,
+            duration_ms: audit.duration_ms,
+            permissions_used: audit.permissions_used,
+            exit_status,
+            stdout: audit.stdout,
+            stderr: audit.stderr,
+            sandbox_violations: audit.sandbox_violations,
+            fuel_consumed: audit.fuel_consumed,
+            network_requests: audit.network_requests,
+            host_calls: audit.host_calls,
+            capability_downgrades: audit.capability_downgrades,
+            attempt: audit.attempt,
+            traceparent: audit.traceparent,
+        }
+    }
+}
+
+/// Parse an `exit_status` string as [`DaemonAuditRecord`] writes it. Unknown
+/// or malformed statuses are an error, never a success.
+fn parse_exit_status(status: &str) -> Result<RuntimeExecutionStatus, OpenSkillError> {
+    RuntimeExecutionStatus::parse(status)
+        .ok_or_else(|| OpenSkillError::DaemonError(format!("invalid exit_status: {status}")))
+}
+
+/// Reply to `execute_skill`, `run_skill_target` and `finish_session`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonExecutionResult {
+    pub output: Value,
+    pub stdout: String,
+    pub stderr: String,
+    pub audit: DaemonAuditRecord,
+    /// See `ExecutionResult::capabilities_needed`.
+    #[serde(default)]
+    pub capabilities_needed: Vec<CapabilityNeeded>,
+}
+
+impl From<crate::ExecutionResult> for DaemonExecutionResult {
+    fn from(result: crate::ExecutionResult) -> Self {
+        Self {
+            output: result.output,
+            stdout: result.stdout,
+            stderr: result.stderr,
+            audit: result.audit.into(),
+            capabilities_needed: result.capabilities_needed,
+        }
+    }
+}
+
+/// Reply to `start_session`, `detach_session`, `attach_session` and
+/// `list_sessions`: a session's state as the next holder needs it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonSession {
+    pub session_id: String,
+    pub forked: bool,
+    pub skill: DaemonLoadedSkill,
+    #[serde(default)]
+    pub input: Value,
+    /// Forked context the session records into.
+    #[serde(default)]
+    pub context_id: Option<String>,
+    /// Tools recorded so far.
+    #[serde(default)]
+    pub permissions_used: Vec<String>,
+    /// Working directory given to `start_session`.
+    #[serde(default)]
+    pub workspace_dir: Option<String>,
+    #[serde(default)]
+    pub elapsed_ms: u64,
+}
+
+/// A session held by a connection or, once detached, by the daemon.
+struct HostedSession {
+    session: SkillExecutionSession,
+    workspace_dir: Option<String>,
+}
+
+impl HostedSession {
+    fn describe(&self, session_id: &str) -> DaemonSession {
+        DaemonSession {
+            session_id: session_id.to_string(),
+            forked: self.session.is_forked(),
+            skill: self.session.skill().clone().into(),
+            input: self.session.input().clone(),
+            context_id: self.session.context_id().map(str::to_string),
+            permissions_used: self.session.permissions_used().to_vec(),
+            workspace_dir: self.workspace_dir.clone(),
+            elapsed_ms: self.session.elapsed_ms(),
+        }
+    }
+}
+
+/// Which workspace `list_outputs` and `read_output` look in: the
+/// `workspace_dir` of a session, a conversation workspace, or with neither
+/// the runtime's workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonOutputScope {
+    pub session_id: Option<String>,
+    pub conversation_id: Option<String>,
+}
+
+/// A file in a workspace, listed by `list_outputs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonOutputFile {
+    /// Path relative to the workspace.
+    pub path: String,
+    pub size: u64,
+    pub mime: String,
+    pub modified_ms: u64,
+}
+
+/// Reply to `read_output`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonOutputChunk {
+    pub path: String,
+    pub mime: String,
+    /// Size of the whole file.
+    pub size: u64,
+    pub offset: u64,
+    /// The bytes read, base64-encoded.
+    pub data: String,
+    /// Whether the chunk reaches the end of the file.
+    pub eof: bool,
+}
+
+impl DaemonOutputChunk {
+    /// The bytes read.
+    pub fn bytes(&self) -> Result<Vec<u8>, OpenSkillError> {
+        base64::engine::general_purpose::STANDARD
+            .decode(&self.data)
+            .map_err(|e| OpenSkillError::DaemonError(format!("invalid output chunk: {}", e)))
+    }
+}
+
+#[derive(Deserialize)]
+struct SkillParams {
+    skill_id: String,
+}
+
+#[derive(Deserialize)]
+struct ExecuteParams<T> {
+    skill_id: String,
+    #[serde(default)]
+    options: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct StartSessionParams {
+    skill_id: String,
+    #[serde(default)]
+    input: Option<Value>,
+    #[serde(default)]
+    workspace_dir: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SessionParams {
+    session_id: String,
+}
+
+#[derive(Deserialize)]
+struct ToolCallParams {
+    session_id: String,
+    tool: String,
+    #[serde(default)]
+    output: Value,
+}
+
+#[derive(Deserialize)]
+struct FinishSessionParams {
+    session_id: String,
+    #[serde(default)]
+    output: Value,
+    #[serde(default)]
+    stdout: String,
+    #[serde(default)]
+    stderr: String,
+    exit_status: String,
+}
+
+#[derive(Deserialize)]
+struct ReadOutputParams {
+    #[serde(flatten)]
+    scope: DaemonOutputScope,
+    path: String,
+    #[serde(default)]
+    offset: u64,
+    #[serde(default)]
+    length: Option<u64>,
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, OpenSkillError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params)
+        .map_err(|e| OpenSkillError::DaemonError(format!("invalid params: {}", e)))
+}
+
+fn to_value<T: Serialize>(value: T) -> Result<Value, OpenSkillError> {
+    Ok(serde_json::to_value(value)?)
+}
+
+fn daemon_error(err: std::io::Error) -> OpenSkillError {
+    OpenSkillError::DaemonError(err.to_string())
+}
+
+/// State shared by the daemon's connections.
+struct DaemonState {
+    runtime: Arc<Mutex<OpenSkillRuntime>>,
+    socket_path: PathBuf,
+    started: Instant,
+    next_session: AtomicU64,
+    /// Sessions handed over with `detach_session`, waiting to be attached.
+    detached: Mutex<HashMap<String, HostedSession>>,
+    stopping: AtomicBool,
+}
+
+impl DaemonState {
+    fn runtime(&self) -> MutexGuard<'_, OpenSkillRuntime> {
+        self.runtime.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn detached(&self) -> MutexGuard<'_, HashMap<String, HostedSession>> {
+        self.detached.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn handle_connection(&self, stream: UnixStream) {
+        let Ok(reader) = stream.try_clone() else {
+            return;
+        };
+        let mut writer = stream;
+        let mut sessions = HashMap::new();
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Request>(&line) {
+                Ok(request) => match self.dispatch(&request.method, request.params, &mut sessions) {
+                    Ok(result) => Response {
+                        id: request.id,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(e) => Response {
+                        id: request.id,
+                        result: None,
+                        error: Some(e.to_string()),
+                    },
+                },
+                Err(e) => Response {
+                    id: 0,
+                    result: None,
+                    error: Some(format!("invalid request: {}", e)),
+                },
+            };
+            let Ok(mut line) = serde_json::to_string(&response) else {
+                break;
+            };
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).is_err() || self.stopping.load(Ordering::SeqCst) {
+                break;
+            }
+        }
+    }
+
+    fn dispatch(
+        &self,
+        method: &str,
+        raw: Value,
+        sessions: &mut HashMap<String, HostedSession>,
+    ) -> Result<Value, OpenSkillError> {
+        match method {
+            "status" => to_value(DaemonStatus {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                features: crate::features().into_iter().map(String::from).collect(),
+                pid: std::process::id(),
+                uptime_ms: self.started.elapsed().as_millis() as u64,
+                skills: self.runtime().list_skills().len(),
+            }),
+            "discover_skills" => to_value(self.runtime().discover_skills()?),
+            "reload_skills" => to_value(self.runtime().reload_skills()?),
+            "list_skills" => to_value(self.runtime().list_skills()),
+            "activate_skill" => {
+                let p: SkillParams = params(raw)?;
+                to_value(DaemonLoadedSkill::from(self.runtime().activate_skill(&p.skill_id)?))
+            }
+            "list_skill_targets" => {
+                let p: SkillParams = params(raw)?;
+                to_value(self.runtime().list_skill_targets(&p.skill_id)?)
+            }
+            "execute_skill" => {
+                let p: ExecuteParams<DaemonExecuteOptions> = params(raw)?;
+                let o = p.options.unwrap_or_default();
+                let options = ExecutionOptions {
+                    timeout_ms: o.timeout_ms,
+                    memory_mb: o.memory_mb,
+                    cpu_quota: o.cpu_quota.filter(|q| *q > 0.0),
+                    max_fuel: o.max_fuel,
+                    input: o.input,
+                    sandbox_overrides: overrides(o.read_paths, o.write_paths, o.allow_network, o.env),
+                    effective_tools: None,
+                    dry_run: o.dry_run,
+                    retry: o
+                        .retry
+                        .map(|r| {
+                            RetryPolicy::from_millis(
+                                r.max_attempts,
+                                r.backoff_ms,
+                                r.max_backoff_ms,
+                                &r.retry_on,
+                            )
+                        })
+                        .transpose()?,
+                    traceparent: o.traceparent,
+                };
+                let result = self.runtime().execute_skill(&p.skill_id, options)?;
+                to_value(DaemonExecutionResult::from(result))
+            }
+            "run_skill_target" => {
+                let p: ExecuteParams<DaemonTargetOptions> = params(raw)?;
+                let o = p.options.unwrap_or_default();
+                let target = match (o.target_type.as_deref(), o.path) {
+                    (Some("script"), Some(path)) => ExecutionTarget::Script { path, args: o.args },
+                    (Some("wasm"), Some(path)) => ExecutionTarget::Wasm { path },
+                    (Some("script" | "wasm"), None) => {
+                        return Err(OpenSkillError::DaemonError(
+                            "path is required for script and wasm targets".to_string(),
+                        ))
+                    }
+                    (Some("auto") | None, Some(path)) => ExecutionTarget::Path { path, args: o.args },
+                    (Some("auto") | None, None) => ExecutionTarget::Auto,
+                    (Some(other), _) => {
+                        return Err(OpenSkillError::DaemonError(format!("unknown target_type: {}", other)))
+                    }
+                };
+                let result = self.runtime().run_skill_target_with_overrides(
+                    &p.skill_id,
+                    target,
+                    o.timeout_ms,
+                    o.input,
+                    o.workspace_dir.map(PathBuf::from),
+                    overrides(o.read_paths, o.write_paths, o.allow_network, o.env),
+                )?;
+                to_value(DaemonExecutionResult::from(result))
+            }
+            "start_session" => {
+                let p: StartSessionParams = params(raw)?;
+                let session = self.runtime().start_skill_session(&p.skill_id, p.input, None)?;
+                let session_id = format!("session-{}", self.next_session.fetch_add(1, Ordering::SeqCst) + 1);
+                let hosted = HostedSession {
+                    session,
+                    workspace_dir: p.workspace_dir,
+                };
+                let reply = hosted.describe(&session_id);
+                sessions.insert(session_id, hosted);
+                to_value(reply)
+            }
+            "record_tool_call" => {
+                let p: ToolCallParams = params(raw)?;
+                let session = sessions
+                    .get_mut(&p.session_id)
+                    .ok_or_else(|| unknown_session(&p.session_id))?;
+                session.session.record_tool_call(&p.tool, &p.output);
+                Ok(Value::Null)
+            }
+            "finish_session" => {
+                let p: FinishSessionParams = params(raw)?;
+                let exit_status = parse_exit_status(&p.exit_status)?;
+                let session = sessions
+                    .remove(&p.session_id)
+                    .ok_or_else(|| unknown_session(&p.session_id))?;
+                let result = self.runtime().finish_skill_session(
+                    session.session,
+                    p.output,
+                    p.stdout,
+                    p.stderr,
+                    exit_status,
+                )?;
+                to_value(DaemonExecutionResult::from(result))
+            }
+            "detach_session" => {
+                let p: SessionParams = params(raw)?;
+                let session = sessions
+                    .remove(&p.session_id)
+                    .ok_or_else(|| unknown_session(&p.session_id))?;
+                let reply = session.describe(&p.session_id);
+                self.detached().insert(p.session_id, session);
+                to_value(reply)
+            }
+            "attach_session" => {
+                let p: SessionParams = params(raw)?;
+                let session = self.detached().remove(&p.session_id).ok_or_else(|| {
+                    OpenSkillError::DaemonError(format!("no detached session: {}", p.session_id))
+                })?;
+                let reply = session.describe(&p.session_id);
+                sessions.insert(p.session_id, session);
+                to_value(reply)
+            }
+            "list_sessions" => {
+                let mut detached: Vec<DaemonSession> = self
+                    .detached()
+                    .iter()
+                    .map(|(id, session)| session.describe(id))
+                    .collect();
+                // Oldest first.
+                detached.sort_by_key(|s| std::cmp::Reverse(s.elapsed_ms));
+                to_value(detached)
+            }
+            "list_outputs" => {
+                let scope: DaemonOutputScope = params(raw)?;
+                let workspace = self.output_workspace(&scope, sessions)?;
+                to_value(list_outputs(&workspace))
+            }
+            "read_output" => {
+                let p: ReadOutputParams = params(raw)?;
+                let workspace = self.output_workspace(&p.scope, sessions)?;
+                to_value(read_output(&workspace, &p.path, p.offset, p.length)?)
+            }
+            "shutdown" => {
+                self.stopping.store(true, Ordering::SeqCst);
+                // Wake the accept loop so it sees the flag.
+                let _ = UnixStream::connect(&self.socket_path);
+                Ok(Value::Null)
+            }
+            other => Err(OpenSkillError::DaemonError(format!("unknown method: {}", other))),
+        }
+    }
+
+    fn output_workspace(
+        &self,
+        scope: &DaemonOutputScope,
+        sessions: &HashMap<String, HostedSession>,
+    ) -> Result<PathBuf, OpenSkillError> {
+        match (&scope.session_id, &scope.conversation_id) {
+            (Some(_), Some(_)) => Err(OpenSkillError::DaemonError(
+                "give session_id or conversation_id, not both".to_string(),
+            )),
+            (Some(id), None) => {
+                let workspace_dir = match sessions.get(id) {
+                    Some(session) => session.workspace_dir.clone(),
+                    None => self
+                        .detached()
+                        .get(id)
+                        .ok_or_else(|| unknown_session(id))?
+                        .workspace_dir
+                        .clone(),
+                };
+                workspace_dir.map(PathBuf::from).ok_or_else(|| {
+                    OpenSkillError::DaemonError(format!("session {} has no workspace_dir", id))
+                })
+            }
+            (None, Some(id)) => self.runtime().workspace_for(id),
+            (None, None) => self.runtime().get_workspace_dir(),
+        }
+    }
+}
+
+fn unknown_session(session_id: &str) -> OpenSkillError {
+    OpenSkillError::DaemonError(format!("unknown session: {}", session_id))
+}
+
+/// Regular files in `workspace` (symlinks not followed), by path.
+fn list_outputs(workspace: &Path) -> Vec<DaemonOutputFile> {
+    WalkDir::new(workspace)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .take(MAX_FILES)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let path = entry.path().strip_prefix(workspace).ok()?;
+            Some(DaemonOutputFile {
+                path: path.to_string_lossy().to_string(),
+                size: metadata.len(),
+                mime: workspace_snapshot::guess_mime(entry.path()),
+                modified_ms: metadata
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_millis() as u64),
+            })
+        })
+        .collect()
+}
+
+/// Read up to `length` bytes (at most [`MAX_OUTPUT_CHUNK`]) of the file at
+/// `path`, relative to `workspace`, from `offset`. Paths leaving the
+/// workspace, also through symlinks, are refused.
+fn read_output(
+    workspace: &Path,
+    path: &str,
+    offset: u64,
+    length: Option<u64>,
+) -> Result<DaemonOutputChunk, OpenSkillError> {
+    let relative = Path::new(path);
+    if !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(OpenSkillError::DaemonError(format!("invalid output path: {}", path)));
+    }
+    let root = workspace.canonicalize()?;
+    let file_path = root.join(relative).canonicalize()?;
+    if !file_path.starts_with(&root) || !file_path.is_file() {
+        return Err(OpenSkillError::DaemonError(format!("not an output file: {}", path)));
+    }
+    let mut file = File::open(&file_path)?;
+    let size = file.metadata()?.len();
+    let offset = offset.min(size);
+    let length = length.unwrap_or(MAX_OUTPUT_CHUNK).min(MAX_OUTPUT_CHUNK);
+    file.seek(SeekFrom::Start(offset))?;
+    let mut data = Vec::new();
+    file.take(length).read_to_end(&mut data)?;
+    Ok(DaemonOutputChunk {
+        path: path.to_string(),
+        mime: workspace_snapshot::guess_mime(&file_path),
+        size,
+        offset,
+        eof: offset + data.len() as u64 >= size,
+        data: base64::engine::general_purpose::STANDARD.encode(&data),
+    })
+}
+
+/// Serves one runtime on a Unix socket (see the module docs).
+///
+/// The socket is created with mode `0600`, so only its owner can connect,
+/// and removed when the server is dropped.
+pub struct DaemonServer {
+    listener: UnixListener,
+    state: Arc<DaemonState>,
+}
+
+/// Bind a socket only its owner can connect to. It is bound in a fresh 0700
+/// directory next to `socket_path`, made 0600 and then renamed into place,
+/// so no other user can connect before the mode is set.
+fn bind_private(socket_path: &Path) -> std::io::Result<UnixListener> {
+    use std::os::unix::fs::DirBuilderExt;
+
+    let parent = match socket_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)?;
+    if fs::symlink_metadata(socket_path).is_ok() {
+        return Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists));
+    }
+    let staging = parent.join(format!(".openskills-daemon-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+    fs::DirBuilder::new().mode(0o700).create(&staging)?;
+    let staged = staging.join("socket");
+    let bound = UnixListener::bind(&staged).and_then(|listener| {
+        fs::set_permissions(&staged, Permissions::from_mode(0o600))?;
+        fs::rename(&staged, socket_path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    bound
+}
+
+impl DaemonServer {
+    /// Listen on `socket_path`, replacing a stale socket left by a daemon
+    /// that died. Fails if another daemon is listening there.
+    pub fn bind<P: AsRef<Path>>(socket_path: P, runtime: OpenSkillRuntime) -> Result<Self, OpenSkillError> {
+        let socket_path = socket_path.as_ref().to_path_buf();
+        if UnixStream::connect(&socket_path).is_ok() {
+            return Err(OpenSkillError::DaemonError(format!(
+                "a daemon is already listening on {}",
+                socket_path.display()
+            )));
+        }
+        if fs::symlink_metadata(&socket_path).is_ok_and(|m| m.file_type().is_socket()) {
+            fs::remove_file(&socket_path)?;
+        }
+        let listener = bind_private(&socket_path).map_err(|e| {
+            OpenSkillError::DaemonError(format!("cannot listen on {}: {}", socket_path.display(), e))
+        })?;
+        Ok(Self {
+            listener,
+            state: Arc::new(DaemonState {
+                runtime: Arc::new(Mutex::new(runtime)),
+                socket_path,
+                started: Instant::now(),
+                next_session: AtomicU64::new(0),
+                detached: Mutex::new(HashMap::new()),
+                stopping: AtomicBool::new(false),
+            }),
+        })
+    }
+
+    /// The socket this server listens on.
+    pub fn socket_path(&self) -> &Path {
+        &self.state.socket_path
+    }
+
+    /// The shared runtime, e.g. for `OpenSkillRuntime::watch_skills`.
+    pub fn runtime(&self) -> &Arc<Mutex<OpenSkillRuntime>> {
+        &self.state.runtime
+    }
+
+    /// Accept connections, each on its own thread, until a client sends
+    /// `shutdown`.
+    pub fn serve(&self) -> Result<(), OpenSkillError> {
+        for stream in self.listener.incoming() {
+            if self.state.stopping.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let state = self.state.clone();
+            thread::Builder::new()
+                .name("openskillsd-connection".to_string())
+                .spawn(move || state.handle_connection(stream))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DaemonServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.state.socket_path);
+    }
+}
+
+/// Connection to an `openskillsd` daemon.
+///
+/// Methods mirror [`OpenSkillRuntime`]'s and run on the daemon's shared
+/// runtime; daemon-side failures come back as `DaemonError`.
+pub struct DaemonClient {
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+    next_id: u64,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon listening on `socket_path`.
+    pub fn connect<P: AsRef<Path>>(socket_path: P) -> Result<Self, OpenSkillError> {
+        let socket_path = socket_path.as_ref();
+        let writer = UnixStream::connect(socket_path).map_err(|e| {
+            OpenSkillError::DaemonError(format!("cannot connect to {}: {}", socket_path.display(), e))
+        })?;
+        let reader = BufReader::new(writer.try_clone().map_err(daemon_error)?);
+        Ok(Self {
+            reader,
+            writer,
+            next_id: 0,
+        })
+    }
+
+    /// Connect to the daemon at [`default_daemon_socket`].
+    pub fn connect_default() -> Result<Self, OpenSkillError> {
+        Self::connect(default_daemon_socket())
+    }
+
+    /// Call a daemon method with JSON params and return its JSON result.
+    pub fn call(&mut self, method: &str, params: Value) -> Result<Value, OpenSkillError> {
+        self.next_id += 1;
+        let request = Request {
+            id: self.next_id,
+            method: method.to_string(),
+            params,
+        };
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).map_err(daemon_error)?;
+
+        let mut reply = String::new();
+        if self.reader.read_line(&mut reply).map_err(daemon_error)? == 0 {
+            return Err(OpenSkillError::DaemonError("daemon closed the connection".to_string()));
+        }
+        let response: Response = serde_json::from_str(&reply)
+            .map_err(|e| OpenSkillError::DaemonError(format!("invalid response: {}", e)))?;
+        match response.error {
+            Some(message) => Err(OpenSkillError::DaemonError(message)),
+            None => Ok(response.result.unwrap_or(Value::Null)),
+        }
+    }
+
+    fn call_as<T: DeserializeOwned>(&mut self, method: &str, params: Value) -> Result<T, OpenSkillError> {
+        serde_json::from_value(self.call(method, params)?)
+            .map_err(|e| OpenSkillError::DaemonError(format!("invalid {} result: {}", method, e)))
+    }
+
+    pub fn status(&mut self) -> Result<DaemonStatus, OpenSkillError> {
+        self.call_as("status", Value::Null)
+    }
+
+    /// Rediscover skills on the daemon.
+    pub fn discover_skills(&mut self) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
+        self.call_as("discover_skills", Value::Null)
+    }
+
+    /// Rediscover skills and report what changed (see
+    /// `OpenSkillRuntime::reload_skills`).
+    pub fn reload_skills(&mut self) -> Result<Vec<SkillChange>, OpenSkillError> {
+        self.call_as("reload_skills", Value::Null)
+    }
+
+    pub fn list_skills(&mut self) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
+        self.call_as("list_skills", Value::Null)
+    }
+
+    pub fn activate_skill(&mut self, skill_id: &str) -> Result<DaemonLoadedSkill, OpenSkillError> {
+        self.call_as("activate_skill", json!({ "skill_id": skill_id }))
+    }
+
+    pub fn list_skill_targets(&mut self, skill_id: &str) -> Result<Vec<SkillTarget>, OpenSkillError> {
+        self.call_as("list_skill_targets", json!({ "skill_id": skill_id }))
+    }
+
+    pub fn execute_skill(
+        &mut self,
+        skill_id: &str,
+        options: &DaemonExecuteOptions,
+    ) -> Result<DaemonExecutionResult, OpenSkillError> {
+        self.call_as("execute_skill", json!({ "skill_id": skill_id, "options": options }))
+    }
+
+    pub fn run_skill_target(
+        &mut self,
+        skill_id: &str,
+        options: &DaemonTargetOptions,
+    ) -> Result<DaemonExecutionResult, OpenSkillError> {
+        self.call_as("run_skill_target", json!({ "skill_id": skill_id, "options": options }))
+    }
+
+    /// Start an instruction-based skill session on the daemon; it lives
+    /// until finished, detached or until this connection closes.
+    /// `workspace_dir` is handed to whoever attaches the session later.
+    pub fn start_session(
+        &mut self,
+        skill_id: &str,
+        input: Option<Value>,
+        workspace_dir: Option<&Path>,
+    ) -> Result<DaemonSession, OpenSkillError> {
+        self.call_as(
+            "start_session",
+            json!({ "skill_id": skill_id, "input": input, "workspace_dir": workspace_dir }),
+        )
+    }
+
+    /// Hand a session over to the daemon so another process can
+    /// [`attach`](Self::attach_session) it; this connection can no longer
+    /// use it.
+    pub fn detach_session(&mut self, session_id: &str) -> Result<DaemonSession, OpenSkillError> {
+        self.call_as("detach_session", json!({ "session_id": session_id }))
+    }
+
+    /// Take over a detached session, with its context and recorded tool
+    /// calls, on this connection.
+    pub fn attach_session(&mut self, session_id: &str) -> Result<DaemonSession, OpenSkillError> {
+        self.call_as("attach_session", json!({ "session_id": session_id }))
+    }
+
+    /// Sessions waiting to be attached.
+    pub fn list_sessions(&mut self) -> Result<Vec<DaemonSession>, OpenSkillError> {
+        self.call_as("list_sessions", Value::Null)
+    }
+
+    pub fn record_tool_call(&mut self, session_id: &str, tool: &str, output: &Value) -> Result<(), OpenSkillError> {
+        self.call(
+            "record_tool_call",
+            json!({ "session_id": session_id, "tool": tool, "output": output }),
+        )?;
+        Ok(())
+    }
+
+    /// Finish a session. `exit_status` is an audit status string such as
+    /// `success` (sent for `None`) or `failed:<reason>`.
+    pub fn finish_session(
+        &mut self,
+        session_id: &str,
+        output: Value,
+        stdout: &str,
+        stderr: &str,
+        exit_status: Option<&str>,
+    ) -> Result<DaemonExecutionResult, OpenSkillError> {
+        self.call_as(
+            "finish_session",
+            json!({
+                "session_id": session_id,
+                "output": output,
+                "stdout": stdout,
+                "stderr": stderr,
+                "exit_status": exit_status.unwrap_or("success"),
+            }),
+        )
+    }
+
+    /// Files in a workspace, e.g. documents a skill generated for the user.
+    pub fn list_outputs(&mut self, scope: &DaemonOutputScope) -> Result<Vec<DaemonOutputFile>, OpenSkillError> {
+        self.call_as("list_outputs", to_value(scope)?)
+    }
+
+    /// Read up to `length` bytes (at most [`MAX_OUTPUT_CHUNK`]) of an
+    /// output file from `offset`.
+    pub fn read_output(
+        &mut self,
+        scope: &DaemonOutputScope,
+        path: &str,
+        offset: u64,
+        length: Option<u64>,
+    ) -> Result<DaemonOutputChunk, OpenSkillError> {
+        let mut params = to_value(scope)?;
+        params["path"] = json!(path);
+        params["offset"] = json!(offset);
+        params["length"] = json!(length);
+        self.call_as("read_output", params)
+    }
+
+    /// Download a whole output file, one chunk at a time.
+    pub fn download_output(&mut self, scope: &DaemonOutputScope, path: &str) -> Result<Vec<u8>, OpenSkillError> {
+        let mut bytes = Vec::new();
+        loop {
+            let chunk = self.read_output(scope, path, bytes.len() as u64, None)?;
+            bytes.extend(chunk.bytes()?);
+            if chunk.eof {
+                return Ok(bytes);
+            }
+        }
+    }
+
+    /// Ask the daemon to stop accepting connections and exit.
+    pub fn shutdown(&mut self) -> Result<(), OpenSkillError> {
+        self.call("shutdown", Value::Null)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exit_status() {
+        assert!(matches!(parse_exit_status("success"), Ok(RuntimeExecutionStatus::Success)));
+        assert!(matches!(
+            parse_exit_status("failed:boom: at line 2"),
+            Ok(RuntimeExecutionStatus::Failed(reason)) if reason == "boom: at line 2"
+        ));
+        assert!(matches!(
+            parse_exit_status("sandbox_violation:exec"),
+            Ok(RuntimeExecutionStatus::SandboxViolation(reason)) if reason == "exec"
+        ));
+        assert!(matches!(parse_exit_status("dry_run"), Ok(RuntimeExecutionStatus::DryRun)));
+        // Unknown, misspelled or malformed statuses never read as success.
+        for status in ["", "sucess", "Success", "failed", "timeout:late", "ok"] {
+            assert!(
+                matches!(parse_exit_status(status), Err(OpenSkillError::DaemonError(_))),
+                "{status}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_params_accept_null_for_optional_fields() {
+        let p: ExecuteParams<DaemonExecuteOptions> = params(json!({ "skill_id": "pdf" })).unwrap();
+        assert_eq!(p.skill_id, "pdf");
+        assert!(p.options.is_none());
+        assert!(matches!(
+            params::<SkillParams>(Value::Null),
+            Err(OpenSkillError::DaemonError(_))
+        ));
+    }
+}
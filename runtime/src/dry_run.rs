@@ -0,0 +1,106 @@
+//! Dry runs: what an execution would do, resolved without running it.
+//!
+//! With `ExecutionOptions::dry_run` set, the runtime resolves permissions,
+//! the entry point, the interpreter and the sandbox exactly as for a real
+//! run, then stops before spawning anything. The [`ExecutionPlan`] is
+//! returned as the execution's `output`, with status
+//! `ExecutionStatus::DryRun`, so an untrusted skill can be reviewed before
+//! it is allowed to run.
+
+use crate::audit::CapabilityDowngrade;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Everything an execution would do, as returned by a dry run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    pub skill_id: String,
+    pub version: String,
+    /// `wasm`, `native` or `instruction_only`.
+    pub mode: String,
+    /// Script or WASM module that would run, relative to the skill root.
+    pub entrypoint: Option<String>,
+    /// Native scripts: interpreter, script and arguments. Python on the
+    /// interpreter component: the guest's `argv`.
+    pub command: Vec<String>,
+    /// Backend that would run the skill: `wasm`, `seatbelt`, `landlock`,
+    /// `bubblewrap`, `nsjail`, `container` or `direct` (unsandboxed).
+    /// `None` for instruction-only skills.
+    pub backend: Option<String>,
+    /// Tools the sandbox would be granted.
+    pub effective_tools: Vec<String>,
+    /// Requested tools that would be stripped or only partly granted.
+    pub capability_downgrades: Vec<CapabilityDowngrade>,
+    pub timeout_ms: u64,
+    pub memory_mb: Option<u64>,
+    pub max_fuel: Option<u64>,
+    /// What the sandbox would allow; `None` when nothing is sandboxed.
+    pub sandbox: Option<SandboxProfile>,
+}
+
+/// Access a sandbox would grant.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxProfile {
+    /// Paths readable (or mounted read-only).
+    pub read_only: Vec<PathBuf>,
+    /// Paths writable (or mounted read-write).
+    pub read_write: Vec<PathBuf>,
+    /// Paths hidden even where reads are otherwise broad.
+    pub deny: Vec<PathBuf>,
+    /// Outbound network access.
+    pub allow_network: bool,
+    /// Hosts the network is narrowed to (empty = any).
+    pub allowed_hosts: Vec<String>,
+    /// Ports the network is narrowed to (empty = any).
+    pub allowed_ports: Vec<u16>,
+    /// Spawning subprocesses.
+    pub allow_process: bool,
+    /// The generated Seatbelt profile (macOS), or the bubblewrap/nsjail
+    /// command line, when the backend has one.
+    pub generated: Option<String>,
+}
+
+/// How a native script would run; filled in by the platform's native runner.
+#[derive(Debug, Clone)]
+pub(crate) struct NativePlan {
+    pub command: Vec<String>,
+    pub backend: &'static str,
+    pub sandbox: Option<SandboxProfile>,
+}
+
+/// Render a command line for review, quoting arguments that need it.
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+pub(crate) fn command_line(command: &std::process::Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./=:,@+".contains(c))
+            {
+                arg.into_owned()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn test_command_line_quotes_arguments() {
+        let mut command = Command::new("/usr/bin/bwrap");
+        command.args(["--ro-bind", "/my docs", "/my docs", "--", "it's", ""]);
+        assert_eq!(
+            command_line(&command),
+            "/usr/bin/bwrap --ro-bind '/my docs' '/my docs' -- 'it'\\''s' ''"
+        );
+    }
+}
@@ -0,0 +1,558 @@
+//! Network egress allowlisting for native sandboxes.
+//!
+//! `allow_network` is all-or-nothing at the OS level. When a skill or
+//! command also declares `allowed_hosts`/`allowed_ports`, outbound TCP is
+//! narrowed further:
+//!
+//! - **Ports only**: the sandbox allows TCP connects to those ports
+//!   (Seatbelt `remote tcp "*:PORT"` on macOS, Landlock `ConnectTcp` on Linux).
+//! - **Hosts**: a filtering HTTP proxy is started on loopback for the
+//!   duration of the run and exported via `HTTP(S)_PROXY`/`ALL_PROXY`. The
+//!   sandbox only allows connecting to `localhost:PROXY_PORT`, and the proxy
+//!   only forwards `CONNECT` tunnels and plain HTTP requests to allowed
+//!   `host:port` pairs.
+//!
+//! A restricted policy fails closed (no network) where the sandbox cannot
+//! enforce it: ports need a [`ConnectFilter::Ports`] sandbox; hosts need
+//! [`ConnectFilter::AddressesAndPorts`], because a port-only rule would let
+//! the script reach any remote host on the proxy's port number. Landlock
+//! filters TCP by port only (Linux 6.7+), so on Linux only `allowed_ports`
+//! is enforceable and UDP is not filtered.
+//!
+//! WASM skills use the same [`EgressPolicy`] to check each `wasi:http`
+//! request, which is why the policy is also built on non-Unix hosts.
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use crate::errors::OpenSkillError;
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const MAX_REQUEST_HEAD: usize = 64 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+const HEAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Hosts and ports a sandboxed process may connect to.
+///
+/// Empty lists mean "no restriction" for that dimension. A host entry
+/// matches itself and its subdomains (`example.com` matches
+/// `api.example.com`); a leading `*.` is accepted and `*` matches any host.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct EgressPolicy {
+    pub hosts: Vec<String>,
+    pub ports: Vec<u16>,
+}
+
+impl EgressPolicy {
+    pub(crate) fn new(hosts: &[String], ports: &[u16]) -> Self {
+        Self {
+            hosts: hosts
+                .iter()
+                .map(|h| normalize_host(h.trim_start_matches("*.")))
+                .filter(|h| !h.is_empty())
+                .collect(),
+            ports: ports.to_vec(),
+        }
+    }
+
+    /// True if this policy does not narrow `allow_network` at all.
+    pub(crate) fn is_unrestricted(&self) -> bool {
+        self.ports.is_empty() && (self.hosts.is_empty() || self.hosts.iter().any(|h| h == "*"))
+    }
+
+    /// How finely a sandbox must filter connects to enforce this policy.
+    pub(crate) fn required_filter(&self) -> ConnectFilter {
+        if self.is_unrestricted() {
+            ConnectFilter::None
+        } else if self.hosts.is_empty() {
+            ConnectFilter::Ports
+        } else {
+            ConnectFilter::AddressesAndPorts
+        }
+    }
+
+    /// Whether a connection to `host:port` is allowed.
+    pub(crate) fn allows(&self, host: &str, port: u16) -> bool {
+        if !self.ports.is_empty() && !self.ports.contains(&port) {
+            return false;
+        }
+        if self.hosts.is_empty() {
+            return true;
+        }
+        let host = normalize_host(host);
+        self.hosts.iter().any(|allowed| {
+            allowed == "*" || host == *allowed || host.ends_with(&format!(".{}", allowed))
+        })
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// How finely a sandbox can restrict outbound connections, coarsest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ConnectFilter {
+    /// Network is all-or-nothing (container, Bubblewrap, old kernels).
+    None,
+    /// TCP connects by port, to any address (Landlock ABI v4).
+    Ports,
+    /// TCP connects by address and port (Seatbelt).
+    AddressesAndPorts,
+}
+
+/// Outbound network plan for one sandboxed process.
+#[derive(Debug)]
+pub(crate) struct Egress {
+    /// Whether the sandbox allows network sockets at all.
+    pub allow_network: bool,
+    /// TCP ports the sandbox may connect to (`None` = any).
+    pub connect_ports: Option<Vec<u16>>,
+    proxy: Option<EgressProxy>,
+}
+
+impl Egress {
+    /// Resolve `policy` for a sandbox that can restrict connects as finely
+    /// as `filter`. A policy the sandbox cannot enforce disables the
+    /// network instead.
+    pub(crate) fn start(
+        allow_network: bool,
+        policy: &EgressPolicy,
+        filter: ConnectFilter,
+    ) -> Result<Self, OpenSkillError> {
+        let unrestricted = Self {
+            allow_network,
+            connect_ports: None,
+            proxy: None,
+        };
+        if !allow_network || policy.is_unrestricted() {
+            return Ok(unrestricted);
+        }
+        if filter < policy.required_filter() {
+            return Ok(Self {
+                allow_network: false,
+                ..unrestricted
+            });
+        }
+        if policy.hosts.is_empty() {
+            return Ok(Self {
+                connect_ports: Some(policy.ports.clone()),
+                ..unrestricted
+            });
+        }
+        let proxy = EgressProxy::start(policy.clone())?;
+        Ok(Self {
+            connect_ports: Some(vec![proxy.port()]),
+            proxy: Some(proxy),
+            ..unrestricted
+        })
+    }
+
+    /// Proxy environment variables for the sandboxed process (empty without
+    /// a proxy).
+    pub(crate) fn env_vars(&self) -> Vec<(String, String)> {
+        let Some(proxy) = &self.proxy else {
+            return Vec::new();
+        };
+        let url = format!("http://127.0.0.1:{}", proxy.port());
+        let mut vars = Vec::new();
+        for key in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+            vars.push((key.to_string(), url.clone()));
+            vars.push((key.to_ascii_lowercase(), url.clone()));
+        }
+        vars.push(("NO_PROXY".to_string(), String::new()));
+        vars.push(("no_proxy".to_string(), String::new()));
+        vars
+    }
+
+    /// Port of the filtering proxy, if one is running.
+    pub(crate) fn proxy_port(&self) -> Option<u16> {
+        self.proxy.as_ref().map(EgressProxy::port)
+    }
+
+    /// Seatbelt network rules for this plan.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub(crate) fn seatbelt_rules(&self) -> String {
+        if !self.allow_network {
+            return String::new();
+        }
+        let Some(ports) = &self.connect_ports else {
+            return "(allow network*)\n".to_string();
+        };
+        let mut rules = String::from("(allow system-socket)\n");
+        if let Some(port) = self.proxy_port() {
+            rules.push_str(&format!(
+                "(allow network-outbound (remote tcp \"localhost:{}\"))\n",
+                port
+            ));
+            return rules;
+        }
+        for port in ports {
+            rules.push_str(&format!(
+                "(allow network-outbound (remote tcp \"*:{}\"))\n",
+                port
+            ));
+        }
+        // Name resolution for direct connections.
+        rules.push_str("(allow network-outbound (remote udp \"*:53\"))\n");
+        rules.push_str("(allow network-outbound (literal \"/private/var/run/mDNSResponder\"))\n");
+        rules
+    }
+}
+
+/// How Landlock can restrict connects on this kernel: by TCP port with
+/// ABI v4 (Linux 6.7+), otherwise not at all.
+#[cfg(target_os = "linux")]
+pub(crate) fn landlock_connect_filter() -> ConnectFilter {
+    // LANDLOCK_CREATE_RULESET_VERSION returns the supported ABI version.
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1;
+    // SAFETY: querying the ABI version takes no pointers.
+    let version = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    if version >= 4 {
+        ConnectFilter::Ports
+    } else {
+        ConnectFilter::None
+    }
+}
+
+/// Loopback HTTP proxy that forwards only allowed destinations.
+///
+/// Runs until dropped.
+#[derive(Debug)]
+pub(crate) struct EgressProxy {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl EgressProxy {
+    pub(crate) fn start(policy: EgressPolicy) -> Result<Self, OpenSkillError> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&shutdown);
+        let policy = Arc::new(policy);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let policy = Arc::clone(&policy);
+                thread::spawn(move || {
+                    let _ = handle_client(stream, &policy);
+                });
+            }
+        });
+        Ok(Self { addr, shutdown })
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.addr.port()
+    }
+}
+
+impl Drop for EgressProxy {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag.
+        let _ = TcpStream::connect_timeout(&self.addr, Duration::from_millis(200));
+    }
+}
+
+/// Serve one proxy client: a `CONNECT` tunnel or one plain HTTP request.
+fn handle_client(mut client: TcpStream, policy: &EgressPolicy) -> std::io::Result<()> {
+    client.set_read_timeout(Some(HEAD_TIMEOUT))?;
+    let Some((head, rest)) = read_request_head(&mut client)? else {
+        return respond(&mut client, 400, "Bad Request", "malformed proxy request");
+    };
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return respond(&mut client, 400, "Bad Request", "malformed request line");
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let Some((host, port)) = split_authority(target, 443) else {
+            return respond(&mut client, 400, "Bad Request", "malformed CONNECT target");
+        };
+        if !policy.allows(&host, port) {
+            return deny(&mut client, &host, port);
+        }
+        let Some(mut upstream) = connect(&host, port) else {
+            return respond(&mut client, 502, "Bad Gateway", "upstream connection failed");
+        };
+        client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")?;
+        upstream.write_all(&rest)?;
+        return tunnel(client, upstream);
+    }
+
+    let Ok(url) = url::Url::parse(target) else {
+        return respond(&mut client, 400, "Bad Request", "proxy requests need an absolute URL");
+    };
+    if url.scheme() != "http" {
+        return respond(&mut client, 400, "Bad Request", "use CONNECT for non-HTTP schemes");
+    }
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    if !policy.allows(&host, port) {
+        return deny(&mut client, &host, port);
+    }
+    let Some(mut upstream) = connect(&host, port) else {
+        return respond(&mut client, 502, "Bad Gateway", "upstream connection failed");
+    };
+
+    // Origin-form request; one request per connection so every request is checked.
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    let mut forwarded = format!("{} {} {}\r\n", method, path, version);
+    for line in lines.filter(|l| !l.is_empty()) {
+        let name = line.split(':').next().unwrap_or_default().trim();
+        if ["proxy-connection", "proxy-authorization", "connection", "keep-alive"]
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+        {
+            continue;
+        }
+        forwarded.push_str(line);
+        forwarded.push_str("\r\n");
+    }
+    forwarded.push_str("Connection: close\r\n\r\n");
+    upstream.write_all(forwarded.as_bytes())?;
+    upstream.write_all(&rest)?;
+    tunnel(client, upstream)
+}
+
+/// Read up to the end of the request head; returns the head and any bytes
+/// already read past it.
+fn read_request_head(client: &mut TcpStream) -> std::io::Result<Option<(String, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = client.read(&mut chunk)?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            buf.truncate(end);
+            return Ok(String::from_utf8(buf).ok().map(|head| (head, rest)));
+        }
+        if buf.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+    }
+}
+
+/// Split `host:port` (IPv6 in brackets) with a default port.
+fn split_authority(authority: &str, default_port: u16) -> Option<(String, u16)> {
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']')?;
+        let port = match tail.strip_prefix(':') {
+            Some(p) => p.parse().ok()?,
+            None => default_port,
+        };
+        return Some((host.to_string(), port));
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((authority.to_string(), default_port)),
+    }
+    .filter(|(host, _)| !host.is_empty())
+}
+
+fn connect(host: &str, port: u16) -> Option<TcpStream> {
+    let addrs = (host, port).to_socket_addrs().ok()?;
+    addrs
+        .into_iter()
+        .find_map(|addr| TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).ok())
+}
+
+/// Copy bytes both ways until either side closes.
+fn tunnel(client: TcpStream, upstream: TcpStream) -> std::io::Result<()> {
+    client.set_read_timeout(None)?;
+    let mut client_read = client.try_clone()?;
+    let mut upstream_write = upstream.try_clone()?;
+    let upload = thread::spawn(move || {
+        let _ = std::io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+    let (mut upstream_read, mut client_write) = (upstream, client);
+    let _ = std::io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = upload.join();
+    Ok(())
+}
+
+fn deny(client: &mut TcpStream, host: &str, port: u16) -> std::io::Result<()> {
+    let message = format!("openskills: egress to {}:{} is not allowed", host, port);
+    respond(client, 403, "Forbidden", &message)
+}
+
+fn respond(client: &mut TcpStream, code: u16, reason: &str, body: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}\n",
+        code,
+        reason,
+        body.len() + 1,
+        body
+    );
+    client.write_all(response.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(hosts: &[&str], ports: &[u16]) -> EgressPolicy {
+        let hosts: Vec<String> = hosts.iter().map(|h| h.to_string()).collect();
+        EgressPolicy::new(&hosts, ports)
+    }
+
+    #[test]
+    fn test_policy_matching() {
+        let p = policy(&["Example.com", "*.api.test"], &[]);
+        assert!(p.allows("example.com", 443));
+        assert!(p.allows("www.example.com.", 80));
+        assert!(p.allows("v1.api.test", 443));
+        assert!(!p.allows("notexample.com", 443));
+        assert!(!p.allows("example.org", 443));
+
+        let p = policy(&["example.com"], &[443]);
+        assert!(p.allows("example.com", 443));
+        assert!(!p.allows("example.com", 80));
+
+        assert!(policy(&[], &[]).is_unrestricted());
+        assert!(policy(&["*"], &[]).is_unrestricted());
+        assert!(!policy(&[], &[443]).is_unrestricted());
+    }
+
+    #[test]
+    fn test_split_authority() {
+        assert_eq!(split_authority("a.com:8443", 443), Some(("a.com".into(), 8443)));
+        assert_eq!(split_authority("a.com", 443), Some(("a.com".into(), 443)));
+        assert_eq!(split_authority("[::1]:80", 443), Some(("::1".into(), 80)));
+        assert_eq!(split_authority(":80", 443), None);
+    }
+
+    #[test]
+    fn test_egress_plan() {
+        let restricted = policy(&["example.com"], &[]);
+        let e = Egress::start(false, &restricted, ConnectFilter::AddressesAndPorts).unwrap();
+        assert!(!e.allow_network && e.env_vars().is_empty());
+
+        let e = Egress::start(true, &policy(&[], &[]), ConnectFilter::AddressesAndPorts).unwrap();
+        assert!(e.allow_network && e.connect_ports.is_none());
+
+        let e = Egress::start(true, &policy(&[], &[443]), ConnectFilter::AddressesAndPorts).unwrap();
+        assert_eq!(e.connect_ports, Some(vec![443]));
+        assert!(e.proxy_port().is_none());
+
+        // Cannot filter: fail closed.
+        let e = Egress::start(true, &restricted, ConnectFilter::None).unwrap();
+        assert!(!e.allow_network);
+
+        // Port-only filtering cannot pin the proxy's address: fail closed
+        // for hosts, enforce ports.
+        let e = Egress::start(true, &restricted, ConnectFilter::Ports).unwrap();
+        assert!(!e.allow_network && e.proxy_port().is_none() && e.env_vars().is_empty());
+        let e = Egress::start(true, &policy(&["example.com"], &[443]), ConnectFilter::Ports).unwrap();
+        assert!(!e.allow_network);
+        let e = Egress::start(true, &policy(&[], &[443]), ConnectFilter::Ports).unwrap();
+        assert!(e.allow_network);
+        assert_eq!(e.connect_ports, Some(vec![443]));
+
+        let e = Egress::start(true, &restricted, ConnectFilter::AddressesAndPorts).unwrap();
+        let port = e.proxy_port().unwrap();
+        assert_eq!(e.connect_ports, Some(vec![port]));
+        assert!(e
+            .env_vars()
+            .contains(&("HTTPS_PROXY".to_string(), format!("http://127.0.0.1:{}", port))));
+    }
+
+    #[test]
+    fn test_seatbelt_rules() {
+        let none = Egress::start(false, &policy(&[], &[]), ConnectFilter::AddressesAndPorts).unwrap();
+        assert_eq!(none.seatbelt_rules(), "");
+        let all = Egress::start(true, &policy(&[], &[]), ConnectFilter::AddressesAndPorts).unwrap();
+        assert_eq!(all.seatbelt_rules(), "(allow network*)\n");
+
+        let ports = Egress::start(true, &policy(&[], &[443]), ConnectFilter::AddressesAndPorts).unwrap();
+        let rules = ports.seatbelt_rules();
+        assert!(rules.contains("(remote tcp \"*:443\")"));
+        assert!(!rules.contains("network*"));
+
+        let hosts = Egress::start(true, &policy(&["example.com"], &[]), ConnectFilter::AddressesAndPorts).unwrap();
+        let rules = hosts.seatbelt_rules();
+        let port = hosts.proxy_port().unwrap();
+        assert!(rules.contains(&format!("(remote tcp \"localhost:{}\")", port)));
+        assert!(!rules.contains("*:"));
+    }
+
+    fn proxy_roundtrip(proxy: &EgressProxy, request: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", proxy.port())).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        let _ = stream.read_to_string(&mut response);
+        response
+    }
+
+    #[test]
+    fn test_proxy_filters_destinations() {
+        // Local upstream standing in for an allowed server.
+        let upstream = TcpListener::bind("127.0.0.1:0").unwrap();
+        let upstream_port = upstream.local_addr().unwrap().port();
+        thread::spawn(move || {
+            for mut stream in upstream.incoming().flatten() {
+                let mut buf = [0u8; 1024];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                let first = request.lines().next().unwrap_or_default().to_string();
+                let _ = write!(stream, "HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n{}", first);
+            }
+        });
+
+        let proxy = EgressProxy::start(policy(&["127.0.0.1"], &[upstream_port])).unwrap();
+
+        let ok = proxy_roundtrip(
+            &proxy,
+            &format!("GET http://127.0.0.1:{}/x?y=1 HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n", upstream_port),
+        );
+        assert!(ok.starts_with("HTTP/1.1 200"), "{}", ok);
+        assert!(ok.ends_with("GET /x?y=1 HTTP/1.1"), "{}", ok);
+
+        let tunnel = proxy_roundtrip(
+            &proxy,
+            &format!("CONNECT 127.0.0.1:{} HTTP/1.1\r\n\r\nPING /t HTTP/1.1\r\n\r\n", upstream_port),
+        );
+        assert!(tunnel.starts_with("HTTP/1.1 200 Connection established"), "{}", tunnel);
+        assert!(tunnel.ends_with("PING /t HTTP/1.1"), "{}", tunnel);
+
+        let denied = proxy_roundtrip(&proxy, "CONNECT example.com:443 HTTP/1.1\r\n\r\n");
+        assert!(denied.starts_with("HTTP/1.1 403"), "{}", denied);
+        assert!(denied.contains("example.com:443"));
+
+        let wrong_port = proxy_roundtrip(&proxy, "GET http://127.0.0.1:1/ HTTP/1.1\r\n\r\n");
+        assert!(wrong_port.starts_with("HTTP/1.1 403"), "{}", wrong_port);
+    }
+}
@@ -0,0 +1,374 @@
+//! Entry-point resolution for auto-detected skill execution.
+//!
+//! When a skill is executed without an explicit target, the file to run is
+//! chosen in this order:
+//! 1. The caller's `wasm_module` override
+//! 2. `entrypoint:` in SKILL.md
+//! 3. The runtime's [`EntrypointConfig`]: WASM and script candidate
+//!    patterns, tried in the configured precedence
+//!
+//! Every step is recorded in an [`EntrypointResolution`] trace so callers
+//! can explain why a particular file ran.
+
+use crate::errors::OpenSkillError;
+use crate::manifest::SkillEntrypoint;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const DEFAULT_WASM_CANDIDATES: &[&str] = &[
+    "skill.wasm",
+    "wasm/skill.wasm",
+    "module.wasm",
+    "main.wasm",
+    "*.wasm",
+];
+
+const DEFAULT_SCRIPT_CANDIDATES: &[&str] = &[
+    "script.py",
+    "main.py",
+    "src/main.py",
+    "index.py",
+    "src/index.py",
+    "script.sh",
+    "main.sh",
+    "src/main.sh",
+    "index.sh",
+    "src/index.sh",
+    "script.bash",
+    "main.bash",
+    "src/main.bash",
+    "index.bash",
+    "src/index.bash",
+    "*.py",
+    "*.sh",
+    "*.bash",
+    "src/*.py",
+    "src/*.sh",
+    "src/*.bash",
+    "scripts/*.py",
+    "scripts/*.sh",
+    "scripts/*.bash",
+];
+
+/// Which kind of candidate is tried first when neither the caller nor the
+/// manifest names an entry point.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntrypointPrecedence {
+    /// WASM modules before scripts (the historical behavior).
+    #[default]
+    WasmFirst,
+    /// Scripts before WASM modules.
+    ScriptFirst,
+}
+
+/// Candidate patterns for entry-point auto-detection.
+///
+/// Patterns are relative to the skill root and may use glob syntax
+/// (`scripts/*.py`); glob matches are tried in sorted order and hidden
+/// files are never matched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntrypointConfig {
+    #[serde(default)]
+    pub precedence: EntrypointPrecedence,
+    #[serde(default = "default_wasm_candidates")]
+    pub wasm_candidates: Vec<String>,
+    #[serde(default = "default_script_candidates")]
+    pub script_candidates: Vec<String>,
+}
+
+fn default_wasm_candidates() -> Vec<String> {
+    DEFAULT_WASM_CANDIDATES.iter().map(|s| s.to_string()).collect()
+}
+
+fn default_script_candidates() -> Vec<String> {
+    DEFAULT_SCRIPT_CANDIDATES.iter().map(|s| s.to_string()).collect()
+}
+
+impl Default for EntrypointConfig {
+    fn default() -> Self {
+        Self {
+            precedence: EntrypointPrecedence::default(),
+            wasm_candidates: default_wasm_candidates(),
+            script_candidates: default_script_candidates(),
+        }
+    }
+}
+
+impl EntrypointConfig {
+    /// Set whether WASM modules or scripts are tried first.
+    pub fn with_precedence(mut self, precedence: EntrypointPrecedence) -> Self {
+        self.precedence = precedence;
+        self
+    }
+
+    /// Replace the WASM candidate patterns.
+    pub fn with_wasm_candidates<I, S>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.wasm_candidates = candidates.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replace the script candidate patterns.
+    pub fn with_script_candidates<I, S>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.script_candidates = candidates.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// The entry point chosen for a skill and how it was found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntrypointResolution {
+    /// Selected file relative to the skill root; `None` when the skill is
+    /// instruction-only.
+    pub selected: Option<String>,
+    /// Arguments declared with the SKILL.md entry point (empty otherwise).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Each source and candidate considered, in order.
+    pub trace: Vec<String>,
+}
+
+/// Resolve the file `execute_skill` would run for a skill.
+pub(crate) fn resolve_entrypoint(
+    skill_root: &Path,
+    manifest_entrypoint: Option<&SkillEntrypoint>,
+    wasm_override: Option<&str>,
+    config: &EntrypointConfig,
+) -> Result<EntrypointResolution, OpenSkillError> {
+    let mut trace = Vec::new();
+
+    if let Some(wasm_module) = wasm_override {
+        trace.push(format!("wasm_module option: {wasm_module} (selected)"));
+        return Ok(EntrypointResolution {
+            selected: Some(wasm_module.to_string()),
+            args: Vec::new(),
+            trace,
+        });
+    }
+
+    if let Some(entrypoint) = manifest_entrypoint {
+        check_manifest_entrypoint(skill_root, entrypoint)?;
+        let path = entrypoint.path();
+        let args = entrypoint.args();
+        if args.is_empty() {
+            trace.push(format!("SKILL.md entrypoint: {path} (selected)"));
+        } else {
+            trace.push(format!(
+                "SKILL.md entrypoint: {path} {} (selected)",
+                args.join(" ")
+            ));
+        }
+        return Ok(EntrypointResolution {
+            selected: Some(path.to_string()),
+            args: args.to_vec(),
+            trace,
+        });
+    }
+    trace.push("SKILL.md entrypoint: not set".to_string());
+
+    let order: [(&str, &[String]); 2] = match config.precedence {
+        EntrypointPrecedence::WasmFirst => [
+            ("wasm", &config.wasm_candidates),
+            ("script", &config.script_candidates),
+        ],
+        EntrypointPrecedence::ScriptFirst => [
+            ("script", &config.script_candidates),
+            ("wasm", &config.wasm_candidates),
+        ],
+    };
+    for (kind, candidates) in order {
+        for pattern in candidates {
+            match first_match(skill_root, pattern) {
+                Some(path) => {
+                    trace.push(format!("{kind} candidate {pattern}: {path} (selected)"));
+                    return Ok(EntrypointResolution {
+                        selected: Some(path),
+                        args: Vec::new(),
+                        trace,
+                    });
+                }
+                None => trace.push(format!("{kind} candidate {pattern}: no match")),
+            }
+        }
+    }
+
+    trace.push("no entry point found; skill is instruction-only".to_string());
+    Ok(EntrypointResolution {
+        selected: None,
+        args: Vec::new(),
+        trace,
+    })
+}
+
+/// `entrypoint:` must name an existing `.wasm`, `.py` or `.sh`/`.bash` file
+/// inside the skill directory. Arguments are only accepted for scripts.
+pub(crate) fn check_manifest_entrypoint(
+    skill_root: &Path,
+    entrypoint: &SkillEntrypoint,
+) -> Result<(), OpenSkillError> {
+    let entrypoint_args = entrypoint.args();
+    let entrypoint = entrypoint.path();
+    let path = skill_root.join(entrypoint);
+    let resolved = path.canonicalize().map_err(|_| {
+        OpenSkillError::InvalidManifest(format!("entrypoint '{entrypoint}' not found"))
+    })?;
+    let root = skill_root
+        .canonicalize()
+        .unwrap_or_else(|_| skill_root.to_path_buf());
+    if !resolved.starts_with(&root) || !resolved.is_file() {
+        return Err(OpenSkillError::InvalidManifest(format!(
+            "entrypoint '{entrypoint}' must be a file inside the skill directory"
+        )));
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if !matches!(ext.as_str(), "wasm" | "py" | "sh" | "bash") {
+        return Err(OpenSkillError::InvalidManifest(format!(
+            "entrypoint '{entrypoint}' must be a .wasm, .py, .sh or .bash file"
+        )));
+    }
+    if ext == "wasm" && !entrypoint_args.is_empty() {
+        return Err(OpenSkillError::InvalidManifest(format!(
+            "entrypoint '{entrypoint}' is a WASM module and cannot take args"
+        )));
+    }
+    Ok(())
+}
+
+/// First file matching `pattern` under `skill_root`, relative to the root.
+fn first_match(skill_root: &Path, pattern: &str) -> Option<String> {
+    if !pattern.contains(['*', '?', '[']) {
+        return skill_root
+            .join(pattern)
+            .is_file()
+            .then(|| pattern.to_string());
+    }
+
+    let root = skill_root.to_str()?;
+    let full = format!("{}/{}", glob::Pattern::escape(root), pattern);
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+    };
+    glob::glob_with(&full, options)
+        .ok()?
+        .flatten()
+        .filter(|p| p.is_file())
+        .find_map(|p| {
+            let relative = p.strip_prefix(skill_root).ok()?;
+            Some(relative.to_string_lossy().replace('\\', "/"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skill_with(files: &[&str]) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        for file in files {
+            let path = temp.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_default_prefers_wasm() {
+        let skill = skill_with(&["main.py", "skill.wasm"]);
+        let resolution =
+            resolve_entrypoint(skill.path(), None, None, &EntrypointConfig::default()).unwrap();
+        assert_eq!(resolution.selected.as_deref(), Some("skill.wasm"));
+        assert_eq!(
+            resolution.trace,
+            vec![
+                "SKILL.md entrypoint: not set",
+                "wasm candidate skill.wasm: skill.wasm (selected)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_script_first_and_glob_candidates() {
+        let skill = skill_with(&["skill.wasm", "scripts/b.py", "scripts/a.py", "scripts/.hidden.py"]);
+        let config = EntrypointConfig::default()
+            .with_precedence(EntrypointPrecedence::ScriptFirst)
+            .with_script_candidates(["run.py", "scripts/*.py"]);
+        let resolution = resolve_entrypoint(skill.path(), None, None, &config).unwrap();
+        assert_eq!(resolution.selected.as_deref(), Some("scripts/a.py"));
+        assert_eq!(resolution.trace[1], "script candidate run.py: no match");
+    }
+
+    fn path(p: &str) -> SkillEntrypoint {
+        SkillEntrypoint::Path(p.to_string())
+    }
+
+    #[test]
+    fn test_manifest_entrypoint_wins() {
+        let skill = skill_with(&["skill.wasm", "tools/convert.py"]);
+        let config = EntrypointConfig::default();
+        let convert = path("tools/convert.py");
+        let resolution = resolve_entrypoint(skill.path(), Some(&convert), None, &config).unwrap();
+        assert_eq!(resolution.selected.as_deref(), Some("tools/convert.py"));
+
+        // The caller's explicit module still beats the manifest.
+        let resolution =
+            resolve_entrypoint(skill.path(), Some(&convert), Some("skill.wasm"), &config).unwrap();
+        assert_eq!(resolution.selected.as_deref(), Some("skill.wasm"));
+
+        for bad in ["missing.py", "../escape.py", "SKILL.md"] {
+            assert!(matches!(
+                resolve_entrypoint(skill.path(), Some(&path(bad)), None, &config),
+                Err(OpenSkillError::InvalidManifest(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_manifest_entrypoint_args() {
+        let skill = skill_with(&["skill.wasm", "tools/convert.py"]);
+        let config = EntrypointConfig::default();
+        let with_args = SkillEntrypoint::WithArgs {
+            path: "tools/convert.py".to_string(),
+            args: vec!["--format".to_string(), "json".to_string()],
+        };
+        let resolution = resolve_entrypoint(skill.path(), Some(&with_args), None, &config).unwrap();
+        assert_eq!(resolution.args, vec!["--format", "json"]);
+        assert_eq!(
+            resolution.trace,
+            vec!["SKILL.md entrypoint: tools/convert.py --format json (selected)"]
+        );
+
+        let wasm_with_args = SkillEntrypoint::WithArgs {
+            path: "skill.wasm".to_string(),
+            args: vec!["-v".to_string()],
+        };
+        assert!(matches!(
+            check_manifest_entrypoint(skill.path(), &wasm_with_args),
+            Err(OpenSkillError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn test_instruction_only() {
+        let skill = skill_with(&["reference.md"]);
+        let resolution =
+            resolve_entrypoint(skill.path(), None, None, &EntrypointConfig::default()).unwrap();
+        assert!(resolution.selected.is_none());
+        assert!(resolution.trace.last().unwrap().contains("instruction-only"));
+    }
+}
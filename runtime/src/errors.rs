@@ -21,6 +21,14 @@ pub enum OpenSkillError {
     #[error("execution timeout")]
     Timeout,
 
+    /// WASM execution used up its fuel budget.
+    #[error("fuel exhausted")]
+    FuelExhausted,
+
+    /// The host cancelled the execution.
+    #[error("cancelled")]
+    Cancelled,
+
     /// Tool not allowed by skill configuration.
     #[error("tool not allowed: {0}")]
     ToolNotAllowed(String),
@@ -72,4 +80,42 @@ pub enum OpenSkillError {
     /// Action input validation failed.
     #[error("invalid action input: {0}")]
     InvalidActionInput(String),
+
+    /// Invalid runtime configuration value.
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
+
+    /// Installing, updating or uninstalling a skill failed.
+    #[error("install failed: {0}")]
+    InstallError(String),
+
+    /// Resolving or downloading a skill from a remote registry failed.
+    #[error("registry error: {0}")]
+    RegistryError(String),
+
+    /// A skill's signature is missing or does not verify against the
+    /// trusted keys.
+    #[error("signature verification failed: {0}")]
+    SignatureError(String),
+
+    /// A skill version or `id@constraint` reference is not valid semver.
+    #[error("invalid version: {0}")]
+    InvalidVersion(String),
+
+    /// Reading or writing `openskills.lock` failed.
+    #[error("lockfile error: {0}")]
+    LockfileError(String),
+
+    /// Watching skill directories for changes failed.
+    #[error("watch error: {0}")]
+    WatchError(String),
+
+    /// Talking to the `openskillsd` daemon failed, or the daemon reported
+    /// an error.
+    #[error("daemon error: {0}")]
+    DaemonError(String),
+
+    /// An `ExecutionQueue` rejected a job or does not know it.
+    #[error("queue error: {0}")]
+    QueueError(String),
 }
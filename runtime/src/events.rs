@@ -0,0 +1,299 @@
+//! Typed runtime events for embedding hosts.
+//!
+//! A host registers a [`RuntimeEventHandler`] with
+//! `OpenSkillRuntime::with_event_handler` to follow what the runtime does:
+//! discovery, activation, executions, permission prompts and hooks. Handlers
+//! are called synchronously on the thread doing the work, so they should
+//! hand events off (e.g. to a channel) rather than block.
+//!
+//! A single run can also stream its own progress as [`ExecutionEvent`]s
+//! (see `OpenSkillRuntime::run_skill_target_with_events`), including the
+//! script's output as it is written. With secret redaction on, output
+//! chunks pass through a [`StreamRedactor`] first and arrive a line at a
+//! time.
+
+use crate::audit::ExecutionStatus;
+use crate::output_pipeline::StreamRedactor;
+use crate::permission_callback::{PermissionRequest, PermissionResponse};
+use crate::redact::OutputRedactor;
+use crate::watch::SkillChangeKind;
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Something the runtime did.
+#[derive(Debug, Clone)]
+pub enum RuntimeEvent {
+    /// Skills were (re)discovered.
+    DiscoveryCompleted {
+        /// Skills found.
+        skills: usize,
+        /// Skills that failed to load.
+        errors: usize,
+    },
+    /// A skill's full instructions were loaded.
+    SkillActivated { skill_id: String },
+    /// An execution started; `execution_id` matches
+    /// [`ActiveExecution::id`](crate::ActiveExecution::id).
+    ExecutionStarted {
+        execution_id: u64,
+        skill_id: String,
+        /// `auto`, or the script or WASM module path.
+        target: String,
+    },
+    /// An execution finished. Executions that failed before producing a
+    /// result report `Failed` with the error message.
+    ExecutionFinished {
+        execution_id: u64,
+        skill_id: String,
+        status: ExecutionStatus,
+        duration_ms: u64,
+    },
+    /// The permission callback is being asked to approve a tool.
+    PermissionRequested { request: PermissionRequest },
+    /// The permission callback answered.
+    PermissionResolved {
+        skill_id: String,
+        tool: String,
+        response: PermissionResponse,
+    },
+    /// A skill hook command ran.
+    HookExecuted {
+        skill_id: String,
+        /// `PreToolUse`, `PostToolUse` or `Stop`.
+        event: String,
+        exit_code: i32,
+        timed_out: bool,
+    },
+    /// A skill failed signature verification but runs anyway because the
+    /// runtime is in `SignatureMode::Warn`.
+    SignatureRejected { skill_id: String, reason: String },
+    /// A skill version was added, modified or removed by
+    /// `OpenSkillRuntime::reload_skills`.
+    SkillChanged {
+        skill_id: String,
+        version: String,
+        kind: SkillChangeKind,
+    },
+}
+
+/// Receives [`RuntimeEvent`]s.
+pub trait RuntimeEventHandler: Send + Sync {
+    fn on_event(&self, event: &RuntimeEvent);
+}
+
+/// Where a runtime sends its events; does nothing without a handler.
+#[derive(Clone, Default)]
+pub(crate) struct EventSink {
+    handler: Option<Arc<dyn RuntimeEventHandler>>,
+}
+
+impl EventSink {
+    pub(crate) fn new(handler: Box<dyn RuntimeEventHandler>) -> Self {
+        Self {
+            handler: Some(Arc::from(handler)),
+        }
+    }
+
+    /// Send the event built by `event`, which is only called if a handler
+    /// is registered.
+    pub(crate) fn emit(&self, event: impl FnOnce() -> RuntimeEvent) {
+        if let Some(handler) = &self.handler {
+            handler.on_event(&event());
+        }
+    }
+}
+
+impl std::fmt::Debug for EventSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSink")
+            .field("has_handler", &self.handler.is_some())
+            .finish()
+    }
+}
+
+/// Progress of one execution, in order: any permission prompts, `Started`,
+/// output chunks, then `Finished`.
+#[derive(Debug, Clone)]
+pub enum ExecutionEvent {
+    /// The execution was registered; `execution_id` matches
+    /// [`ActiveExecution::id`](crate::ActiveExecution::id).
+    Started {
+        execution_id: u64,
+        skill_id: String,
+        /// `auto`, or the script or WASM module path.
+        target: String,
+    },
+    /// Bytes the skill wrote to stdout. Chunks may split UTF-8 sequences,
+    /// except with secret redaction on, when they end on a line break.
+    StdoutChunk(Vec<u8>),
+    /// Bytes the skill wrote to stderr.
+    StderrChunk(Vec<u8>),
+    /// The permission callback is being asked to approve a tool.
+    PermissionRequested { request: PermissionRequest },
+    /// The execution finished; the result is also returned to the caller.
+    Finished {
+        execution_id: u64,
+        status: ExecutionStatus,
+        duration_ms: u64,
+    },
+}
+
+/// Sending half of an [`ExecutionEvent`] channel.
+pub type ExecutionEventSender = mpsc::UnboundedSender<ExecutionEvent>;
+
+/// Receiving half of an [`ExecutionEvent`] channel. Use `recv().await` in
+/// async code and `blocking_recv()` on a plain thread.
+pub type ExecutionEventReceiver = mpsc::UnboundedReceiver<ExecutionEvent>;
+
+/// A channel for [`ExecutionEvent`]s.
+pub fn execution_event_channel() -> (ExecutionEventSender, ExecutionEventReceiver) {
+    mpsc::unbounded_channel()
+}
+
+/// Where one execution sends its [`ExecutionEvent`]s; does nothing
+/// without a sender. Events are dropped once the receiver is gone.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExecutionEvents {
+    sender: Option<ExecutionEventSender>,
+    // Redacts output chunks before they are sent: stdout, stderr
+    redaction: Option<Arc<Mutex<[StreamRedactor; 2]>>>,
+}
+
+impl ExecutionEvents {
+    pub(crate) fn new(sender: Option<ExecutionEventSender>) -> Self {
+        Self {
+            sender,
+            redaction: None,
+        }
+    }
+
+    /// Pass output chunks through `redactor` before sending them.
+    pub(crate) fn with_redactor(mut self, redactor: Option<OutputRedactor>) -> Self {
+        self.redaction = redactor.filter(|_| self.sender.is_some()).map(|redactor| {
+            let stream = StreamRedactor::new(redactor, 0);
+            Arc::new(Mutex::new([stream.clone(), stream]))
+        });
+        self
+    }
+
+    /// Send the event built by `event`, which is only called if there is
+    /// a sender.
+    pub(crate) fn emit(&self, event: impl FnOnce() -> ExecutionEvent) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(event());
+        }
+    }
+
+    /// Send `data` as a stdout or stderr chunk. With a redactor, only the
+    /// whole lines read so far are sent; [`Self::finish_output`] sends the
+    /// rest.
+    pub(crate) fn output(&self, stream: OutputStream, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        match &self.redaction {
+            Some(redaction) => {
+                let ready = match redaction.lock() {
+                    Ok(mut redaction) => redaction[stream as usize].push(data),
+                    Err(_) => None,
+                };
+                if let Some(text) = ready {
+                    self.chunk(stream, text.into_bytes());
+                }
+            }
+            None => self.chunk(stream, data.to_vec()),
+        }
+    }
+
+    /// Send what the redactor still holds back at the end of `stream`.
+    pub(crate) fn finish_output(&self, stream: OutputStream) {
+        let Some(redaction) = &self.redaction else {
+            return;
+        };
+        let rest = match redaction.lock() {
+            Ok(mut redaction) => redaction[stream as usize].finish(),
+            Err(_) => None,
+        };
+        if let Some(text) = rest {
+            self.chunk(stream, text.into_bytes());
+        }
+    }
+
+    fn chunk(&self, stream: OutputStream, data: Vec<u8>) {
+        if data.is_empty() {
+            return;
+        }
+        self.emit(|| match stream {
+            OutputStream::Stdout => ExecutionEvent::StdoutChunk(data),
+            OutputStream::Stderr => ExecutionEvent::StderrChunk(data),
+        });
+    }
+
+    /// `reader`, sending what is read from it as output chunks.
+    pub(crate) fn forward<R: Read>(
+        &self,
+        reader: Option<R>,
+        stream: OutputStream,
+    ) -> Option<ForwardOutput<R>> {
+        reader.map(|inner| ForwardOutput {
+            inner,
+            events: self.clone(),
+            stream,
+        })
+    }
+
+    /// `sink` with permission prompts also sent to this execution.
+    pub(crate) fn with_permission_prompts(&self, sink: EventSink) -> EventSink {
+        if self.sender.is_none() {
+            return sink;
+        }
+        EventSink::new(Box::new(PermissionForwarder {
+            events: self.clone(),
+            inner: sink,
+        }))
+    }
+}
+
+/// Which output stream a chunk came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OutputStream {
+    Stdout = 0,
+    Stderr = 1,
+}
+
+/// A reader that sends everything read through it as output chunks.
+pub(crate) struct ForwardOutput<R> {
+    inner: R,
+    events: ExecutionEvents,
+    stream: OutputStream,
+}
+
+impl<R: Read> Read for ForwardOutput<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            self.events.finish_output(self.stream);
+        }
+        self.events.output(self.stream, &buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Passes runtime events on to the runtime's handler, copying permission
+/// prompts to one execution.
+struct PermissionForwarder {
+    events: ExecutionEvents,
+    inner: EventSink,
+}
+
+impl RuntimeEventHandler for PermissionForwarder {
+    fn on_event(&self, event: &RuntimeEvent) {
+        if let RuntimeEvent::PermissionRequested { request } = event {
+            self.events.emit(|| ExecutionEvent::PermissionRequested {
+                request: request.clone(),
+            });
+        }
+        self.inner.emit(|| event.clone());
+    }
+}
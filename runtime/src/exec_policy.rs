@@ -0,0 +1,198 @@
+//! Exec allowlisting for sandboxed commands.
+//!
+//! `allow_process` lets a command spawn subprocesses, and they inherit the
+//! whole sandbox, but it says nothing about *what* they run. When
+//! [`crate::CommandPermissions::allowed_programs`] is non-empty, only those
+//! binaries (and the `/bin/bash` running the command) can be executed:
+//!
+//! - **macOS**: the Seatbelt profile allows `process-exec` on those paths
+//!   instead of everywhere.
+//! - **Linux**: Landlock's `Execute` right is dropped from every path rule
+//!   and granted on the allowed binaries alone. seccomp cannot read the path
+//!   an `execve` points to, so the check lives in Landlock; a denied exec
+//!   fails with "Permission denied". The allowlist needs Landlock ABI 1 or
+//!   later: where the ruleset cannot be enforced the command does not start.
+//!
+//! Names are resolved through the command's `PATH`, relative paths against
+//! its working directory, and symlinks to their target as well. A script
+//! run directly (`./build.sh`, a `#!/usr/bin/env node` shebang) needs the
+//! script and each interpreter in the chain listed.
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// The shell every sandboxed command runs under.
+pub(crate) const COMMAND_SHELL: &str = "/bin/bash";
+
+/// Binaries a sandboxed command may execute.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ExecPolicy {
+    programs: Vec<PathBuf>,
+}
+
+impl ExecPolicy {
+    /// Policy allowing `programs` and the command shell, or `None` when
+    /// `programs` is empty (any program may run). Programs that cannot be
+    /// found are left out: they could not be executed anyway.
+    pub(crate) fn new(programs: &[String], search_path: Option<&OsStr>, working_dir: &Path) -> Option<Self> {
+        if programs.is_empty() {
+            return None;
+        }
+        let mut policy = Self::default();
+        policy.add(Path::new(COMMAND_SHELL));
+        for program in programs {
+            if let Some(path) = find_program(program, search_path, working_dir) {
+                policy.add(&path);
+            }
+        }
+        Some(policy)
+    }
+
+    /// Allowed binaries, each as found and as its symlink target.
+    pub(crate) fn programs(&self) -> &[PathBuf] {
+        &self.programs
+    }
+
+    /// Allow `path`, its symlink target and, for a dynamically linked
+    /// binary, its loader (Landlock checks the loader like an exec).
+    fn add(&mut self, path: &Path) {
+        let canonical = path.canonicalize().ok();
+        for path in std::iter::once(path.to_path_buf()).chain(canonical) {
+            if !self.programs.contains(&path) {
+                self.programs.push(path);
+            }
+        }
+        if let Some(loader) = elf_interpreter(path) {
+            if !self.programs.contains(&loader) {
+                self.add(&loader);
+            }
+        }
+    }
+}
+
+/// The `PT_INTERP` path of an ELF executable.
+fn elf_interpreter(path: &Path) -> Option<PathBuf> {
+    use std::io::Read;
+
+    const PT_INTERP: u32 = 3;
+    let mut head = Vec::new();
+    std::fs::File::open(path).ok()?.take(64 * 1024).read_to_end(&mut head).ok()?;
+    if head.get(..4)? != b"\x7fELF" {
+        return None;
+    }
+    let little = *head.get(5)? == 1;
+    let int = |offset: usize, len: usize| -> Option<usize> {
+        let bytes = head.get(offset..offset + len)?;
+        let fold = |acc: u64, b: &u8| acc << 8 | u64::from(*b);
+        let value = if little {
+            bytes.iter().rev().fold(0, fold)
+        } else {
+            bytes.iter().fold(0, fold)
+        };
+        usize::try_from(value).ok()
+    };
+    // Header field offsets and word size for ELF32 / ELF64.
+    let (phoff, phentsize, phnum, p_offset, p_filesz, word) = match head.get(4)? {
+        1 => (0x1c, 0x2a, 0x2c, 4, 16, 4),
+        2 => (0x20, 0x36, 0x38, 8, 32, 8),
+        _ => return None,
+    };
+    let (table, entry_size) = (int(phoff, word)?, int(phentsize, 2)?);
+    (0..int(phnum, 2)?).find_map(|i| {
+        let header = table + i * entry_size;
+        if int(header, 4)? != PT_INTERP as usize {
+            return None;
+        }
+        let start = int(header + p_offset, word)?;
+        let interp = head.get(start..start + int(header + p_filesz, word)?)?;
+        let interp = interp.split(|&b| b == 0).next()?;
+        Some(PathBuf::from(std::str::from_utf8(interp).ok()?))
+    })
+}
+
+fn find_program(program: &str, search_path: Option<&OsStr>, working_dir: &Path) -> Option<PathBuf> {
+    if program.contains('/') {
+        let path = working_dir.join(program);
+        return path.is_file().then_some(path);
+    }
+    std::env::split_paths(search_path?)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Programs a shell command starts itself: the first word of each simple
+/// command, after any `NAME=value` assignments. `cd out && node build.js |
+/// tee log` gives `["cd", "node", "tee"]`; builtins like `cd` are harmless
+/// since they resolve to nothing. Programs those start in turn (a package
+/// manager's scripts, an `env` shebang) must be added by the caller.
+pub fn command_programs(command: &str) -> Vec<String> {
+    let mut programs: Vec<String> = Vec::new();
+    for segment in command.split(['&', '|', ';', '\n', '(', ')']) {
+        let program = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(['\'', '"']))
+            .find(|word| !word.is_empty() && !is_assignment(word));
+        if let Some(program) = program {
+            if !programs.iter().any(|p| p == program) {
+                programs.push(program.to_string());
+            }
+        }
+    }
+    programs
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_programs() {
+        assert_eq!(command_programs("node script.js"), ["node"]);
+        assert_eq!(
+            command_programs("cd out && NODE_ENV=production node build.js | tee log; node x.js"),
+            ["cd", "node", "tee"]
+        );
+        assert_eq!(command_programs("(\"./run.sh\" --fast) || exit 1"), ["./run.sh", "exit"]);
+        assert!(command_programs("  ").is_empty());
+    }
+
+    #[test]
+    fn test_policy_resolves_programs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let script = dir.path().join("run.sh");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+
+        assert_eq!(ExecPolicy::new(&[], None, dir.path()), None);
+
+        let path = std::env::join_paths([dir.path()]).unwrap();
+        let policy = ExecPolicy::new(
+            &["run.sh".to_string(), "./run.sh".to_string(), "missing".to_string()],
+            Some(&path),
+            dir.path(),
+        )
+        .unwrap();
+        let programs = policy.programs();
+        assert!(programs.contains(&PathBuf::from(COMMAND_SHELL)));
+        assert!(programs.contains(&script));
+        assert!(programs.contains(&dir.path().join("./run.sh")));
+        assert!(!programs.iter().any(|p| p.ends_with("missing")));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_policy_allows_the_loader() {
+        let Some(loader) = elf_interpreter(Path::new(COMMAND_SHELL)) else {
+            return; // statically linked shell
+        };
+        assert!(loader.is_absolute());
+        let policy = ExecPolicy::new(&["true".to_string()], None, Path::new("/")).unwrap();
+        assert!(policy.programs().contains(&loader));
+    }
+}
@@ -0,0 +1,186 @@
+//! Per-skill limit suggestions from execution history.
+//!
+//! The runtime remembers how long each skill's recent executions took and
+//! whether they hit their timeout or memory limit. A skill that reliably
+//! needs 90s should not keep running into the 30s default; from that history
+//! [`LimitSuggestion`] proposes a timeout with headroom over the slow end of
+//! past runs, and doubles any limit an execution ran out of. Suggestions only
+//! ever raise limits above the defaults, never lower them.
+
+use crate::audit::ExecutionStatus;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Executions remembered per skill.
+const HISTORY_LEN: usize = 50;
+
+/// Completed executions needed before durations are used.
+const MIN_SAMPLES: usize = 3;
+
+/// Upper bound for suggested timeouts (30 minutes).
+const MAX_TIMEOUT_MS: u64 = 30 * 60 * 1000;
+
+/// Upper bound for suggested memory limits (16 GiB).
+const MAX_MEMORY_MB: u64 = 16 * 1024;
+
+/// Limits suggested for a skill by [`suggest_limits`](crate::OpenSkillRuntime::suggest_limits).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimitSuggestion {
+    /// Skill the suggestion is for.
+    pub skill_id: String,
+    /// Recorded executions it is based on.
+    pub samples: usize,
+    /// Timeout to run the skill with; `None` when the default is enough.
+    pub timeout_ms: Option<u64>,
+    /// Memory limit to run the skill with; `None` when no execution ran out
+    /// of memory.
+    pub memory_mb: Option<u64>,
+}
+
+/// One finished execution.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExecutionSample {
+    pub duration_ms: u64,
+    /// Timeout the execution ran with.
+    pub timeout_ms: u64,
+    /// Memory limit the execution ran with, if any.
+    pub memory_mb: Option<u64>,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Finished,
+    TimedOut,
+    OutOfMemory,
+}
+
+impl Outcome {
+    /// `None` for executions that did not run anything.
+    pub(crate) fn from_status(status: &ExecutionStatus) -> Option<Self> {
+        match status {
+            ExecutionStatus::InstructionOnly | ExecutionStatus::DryRun => None,
+            ExecutionStatus::Timeout => Some(Self::TimedOut),
+            ExecutionStatus::Failed(reason) if reason == "oom" => Some(Self::OutOfMemory),
+            _ => Some(Self::Finished),
+        }
+    }
+}
+
+/// Recent executions per skill, kept by a runtime.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionHistory {
+    skills: Mutex<HashMap<String, VecDeque<ExecutionSample>>>,
+}
+
+impl ExecutionHistory {
+    pub(crate) fn record(&self, skill_id: &str, sample: ExecutionSample) {
+        let mut skills = self.skills.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let samples = skills.entry(skill_id.to_string()).or_default();
+        if samples.len() == HISTORY_LEN {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Suggest limits for `skill_id` given the default timeout.
+    pub(crate) fn suggest(&self, skill_id: &str, default_timeout_ms: u64) -> LimitSuggestion {
+        let skills = self.skills.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let samples: Vec<ExecutionSample> = skills
+            .get(skill_id)
+            .map(|samples| samples.iter().copied().collect())
+            .unwrap_or_default();
+        drop(skills);
+
+        let mut durations: Vec<u64> = samples
+            .iter()
+            .filter(|s| s.outcome != Outcome::TimedOut)
+            .map(|s| s.duration_ms)
+            .collect();
+        durations.sort_unstable();
+        // 50% headroom over the 95th percentile, in whole seconds.
+        let from_durations = (durations.len() >= MIN_SAMPLES).then(|| {
+            let p95 = durations[(durations.len() * 95).div_ceil(100) - 1];
+            (p95 + p95 / 2).div_ceil(1000) * 1000
+        });
+        let from_timeouts = samples
+            .iter()
+            .filter(|s| s.outcome == Outcome::TimedOut)
+            .map(|s| s.timeout_ms.saturating_mul(2))
+            .max();
+        let timeout_ms = from_durations
+            .into_iter()
+            .chain(from_timeouts)
+            .max()
+            .map(|ms| ms.min(MAX_TIMEOUT_MS))
+            .filter(|&ms| ms > default_timeout_ms);
+
+        let memory_mb = samples
+            .iter()
+            .filter(|s| s.outcome == Outcome::OutOfMemory)
+            .filter_map(|s| s.memory_mb)
+            .map(|mb| mb.saturating_mul(2).min(MAX_MEMORY_MB))
+            .max();
+
+        LimitSuggestion {
+            skill_id: skill_id.to_string(),
+            samples: samples.len(),
+            timeout_ms,
+            memory_mb,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(duration_ms: u64, outcome: Outcome) -> ExecutionSample {
+        ExecutionSample {
+            duration_ms,
+            timeout_ms: 30_000,
+            memory_mb: Some(256),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_suggests_timeout_from_durations() {
+        let history = ExecutionHistory::default();
+        history.record("slow", sample(88_000, Outcome::Finished));
+        history.record("slow", sample(90_000, Outcome::Finished));
+        assert_eq!(history.suggest("slow", 30_000).timeout_ms, None);
+
+        history.record("slow", sample(91_500, Outcome::Finished));
+        let suggestion = history.suggest("slow", 30_000);
+        assert_eq!(suggestion.samples, 3);
+        assert_eq!(suggestion.timeout_ms, Some(138_000));
+        assert_eq!(suggestion.memory_mb, None);
+
+        for _ in 0..HISTORY_LEN {
+            history.record("fast", sample(200, Outcome::Finished));
+        }
+        let fast = history.suggest("fast", 30_000);
+        assert_eq!(fast.samples, HISTORY_LEN);
+        assert_eq!(fast.timeout_ms, None);
+        assert_eq!(history.suggest("unknown", 30_000), LimitSuggestion {
+            skill_id: "unknown".to_string(),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_raises_exhausted_limits() {
+        let history = ExecutionHistory::default();
+        history.record("skill", sample(30_000, Outcome::TimedOut));
+        history.record("skill", sample(1_000, Outcome::OutOfMemory));
+        let suggestion = history.suggest("skill", 30_000);
+        assert_eq!(suggestion.timeout_ms, Some(60_000));
+        assert_eq!(suggestion.memory_mb, Some(512));
+        assert_eq!(Outcome::from_status(&ExecutionStatus::InstructionOnly), None);
+        assert_eq!(
+            Outcome::from_status(&ExecutionStatus::Failed("oom".to_string())),
+            Some(Outcome::OutOfMemory)
+        );
+    }
+}
@@ -0,0 +1,570 @@
+//! Job queue for servers multiplexing many agent sessions onto one runtime.
+//!
+//! An [`ExecutionQueue`] shares an [`OpenSkillRuntime`] with the rest of the
+//! host (the same `Arc<Mutex<_>>` a `DaemonServer` uses) and runs submitted
+//! jobs on its own worker threads. The runtime is locked only to resolve a
+//! job and to record its result; the sandboxed process or WASM component
+//! runs unlocked, so jobs run concurrently up to the configured limits.
+//!
+//! Jobs start in priority order (higher first, then in submission order),
+//! subject to a global and a per-skill concurrency limit and optional
+//! start-rate limits. A job that is held back by its skill's limits does not
+//! block jobs of other skills behind it.
+
+use crate::active_executions::CancellationHandle;
+use crate::audit::ExecutionStatus;
+use crate::context::ExecutionContext;
+use crate::errors::OpenSkillError;
+use crate::pending_execution::{ExecutionStep, NextAttempt};
+use crate::{ExecutionOptions, ExecutionResult, OpenSkillRuntime};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// At most `max_starts` jobs start within any window of `per`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_starts: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(max_starts: u32, per: Duration) -> Self {
+        Self { max_starts, per }
+    }
+}
+
+/// Limits an [`ExecutionQueue`] enforces.
+#[derive(Debug, Clone)]
+pub struct QueueConfig {
+    /// Jobs running at once; also the number of worker threads. `0` means
+    /// the number of available cores.
+    pub max_concurrent: usize,
+    /// Jobs of one skill running at once, unless `skill_concurrency` sets
+    /// a limit for it. `None` = only the global limit applies.
+    pub max_concurrent_per_skill: Option<usize>,
+    /// Per-skill concurrency limits, by skill ID.
+    pub skill_concurrency: HashMap<String, usize>,
+    /// Start rate across all skills.
+    pub rate_limit: Option<RateLimit>,
+    /// Per-skill start rates, by skill ID.
+    pub skill_rate_limits: HashMap<String, RateLimit>,
+    /// `submit` fails once this many jobs are waiting. `None` = unbounded.
+    pub max_queued: Option<usize>,
+    /// Finished jobs whose result nobody collected are kept for `status`
+    /// and `wait` up to this many; the oldest are dropped first.
+    pub max_finished: usize,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 0,
+            max_concurrent_per_skill: None,
+            skill_concurrency: HashMap::new(),
+            rate_limit: None,
+            skill_rate_limits: HashMap::new(),
+            max_queued: None,
+            max_finished: 1000,
+        }
+    }
+}
+
+impl QueueConfig {
+    fn skill_concurrency(&self, skill_id: &str) -> Option<usize> {
+        self.skill_concurrency
+            .get(skill_id)
+            .copied()
+            .or(self.max_concurrent_per_skill)
+    }
+}
+
+/// Where a job is in its lifecycle.
+#[derive(Debug, Clone)]
+pub enum JobStatus {
+    /// Waiting for a worker or for its limits.
+    Queued,
+    /// Executing; `execution_id` is its ID in `active_executions`, once
+    /// the skill is resolved.
+    Running { execution_id: Option<u64> },
+    /// Ran to completion with this status (which may itself be a failure,
+    /// e.g. a non-zero exit or a timeout).
+    Finished(ExecutionStatus),
+    /// Could not run, e.g. the skill was not found or its sandbox failed.
+    Failed(String),
+    /// Cancelled before or while running.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Whether the job will not change state anymore.
+    pub fn is_done(&self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running { .. })
+    }
+}
+
+/// A job submitted to an [`ExecutionQueue`].
+#[derive(Debug, Clone)]
+pub struct QueuedJob {
+    /// Identifier, unique within the queue.
+    pub id: u64,
+    pub skill_id: String,
+    pub priority: i32,
+    pub status: JobStatus,
+    /// Times in milliseconds since the Unix epoch.
+    pub submitted_at_ms: u64,
+    pub started_at_ms: Option<u64>,
+    pub finished_at_ms: Option<u64>,
+}
+
+struct Job {
+    info: QueuedJob,
+    options: ExecutionOptions,
+    cancellation: CancellationHandle,
+    result: Option<Result<ExecutionResult, OpenSkillError>>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    jobs: HashMap<u64, Job>,
+    /// Queued job IDs, highest priority first, then oldest first.
+    pending: Vec<u64>,
+    /// Finished job IDs, oldest first, for `max_finished`.
+    finished: VecDeque<u64>,
+    running: HashMap<String, usize>,
+    running_total: usize,
+    /// Recent start times, for the rate limits.
+    starts: VecDeque<Instant>,
+    skill_starts: HashMap<String, VecDeque<Instant>>,
+    next_id: u64,
+    shutdown: bool,
+}
+
+/// What a worker should do next.
+enum Next {
+    Run(u64),
+    /// Nothing may start before this instant, or until woken.
+    Wait(Option<Instant>),
+}
+
+impl QueueState {
+    fn next(&mut self, config: &QueueConfig, max_concurrent: usize, now: Instant) -> Next {
+        if self.running_total >= max_concurrent || self.pending.is_empty() {
+            return Next::Wait(None);
+        }
+        if let Some(ready) = rate_limited(&mut self.starts, config.rate_limit, now) {
+            return Next::Wait(Some(ready));
+        }
+        let mut wake = None;
+        let mut runnable = None;
+        for (index, id) in self.pending.iter().enumerate() {
+            let skill_id = &self.jobs[id].info.skill_id;
+            let running = self.running.get(skill_id).copied().unwrap_or(0);
+            if config
+                .skill_concurrency(skill_id)
+                .is_some_and(|limit| running >= limit)
+            {
+                continue;
+            }
+            let starts = self.skill_starts.entry(skill_id.clone()).or_default();
+            let limit = config.skill_rate_limits.get(skill_id).copied();
+            if let Some(ready) = rate_limited(starts, limit, now) {
+                wake = Some(wake.map_or(ready, |wake: Instant| wake.min(ready)));
+                continue;
+            }
+            runnable = Some(index);
+            break;
+        }
+        match runnable {
+            Some(index) => Next::Run(self.pending.remove(index)),
+            None => Next::Wait(wake),
+        }
+    }
+
+    fn start(&mut self, id: u64, now: Instant) {
+        let skill_id = self.jobs[&id].info.skill_id.clone();
+        self.starts.push_back(now);
+        self.skill_starts.entry(skill_id.clone()).or_default().push_back(now);
+        *self.running.entry(skill_id).or_default() += 1;
+        self.running_total += 1;
+    }
+
+    fn finish(
+        &mut self,
+        id: u64,
+        result: Result<ExecutionResult, OpenSkillError>,
+        max_finished: usize,
+    ) {
+        let Some(job) = self.jobs.get_mut(&id) else {
+            return;
+        };
+        job.info.status = match &result {
+            _ if job.cancellation.is_cancelled() => JobStatus::Cancelled,
+            Ok(result) => JobStatus::Finished(result.audit.exit_status.clone()),
+            Err(OpenSkillError::Cancelled) => JobStatus::Cancelled,
+            Err(err) => JobStatus::Failed(err.to_string()),
+        };
+        job.info.finished_at_ms = Some(now_ms());
+        job.result = Some(result);
+        self.finished.push_back(id);
+        while self.finished.len() > max_finished {
+            if let Some(oldest) = self.finished.pop_front() {
+                self.jobs.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// `None` if a job may start now under `limit`, else when one may.
+fn rate_limited(
+    starts: &mut VecDeque<Instant>,
+    limit: Option<RateLimit>,
+    now: Instant,
+) -> Option<Instant> {
+    let limit = limit?;
+    while starts
+        .front()
+        .is_some_and(|start| now.duration_since(*start) >= limit.per)
+    {
+        starts.pop_front();
+    }
+    if starts.len() < limit.max_starts as usize {
+        return None;
+    }
+    starts.front().map(|oldest| *oldest + limit.per)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+struct Shared {
+    runtime: Arc<Mutex<OpenSkillRuntime>>,
+    config: QueueConfig,
+    max_concurrent: usize,
+    state: Mutex<QueueState>,
+    /// Signalled when a job is queued, finishes or a limit may have lifted.
+    changed: Condvar,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn runtime(&self) -> MutexGuard<'_, OpenSkillRuntime> {
+        self.runtime.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn work(&self) {
+        while let Some((id, skill_id, options, cancellation)) = self.take_next() {
+            let result = self.run(id, &skill_id, &options, &cancellation);
+            let mut state = self.state();
+            if let Some(running) = state.running.get_mut(&skill_id) {
+                *running -= 1;
+            }
+            state.running_total -= 1;
+            state.finish(id, result, self.config.max_finished);
+            drop(state);
+            self.changed.notify_all();
+        }
+    }
+
+    /// Block until a job may start, or return `None` on shutdown.
+    fn take_next(&self) -> Option<(u64, String, ExecutionOptions, CancellationHandle)> {
+        let mut state = self.state();
+        loop {
+            if state.shutdown {
+                return None;
+            }
+            let now = Instant::now();
+            match state.next(&self.config, self.max_concurrent, now) {
+                Next::Run(id) => {
+                    state.start(id, now);
+                    let job = state.jobs.get_mut(&id)?;
+                    job.info.status = JobStatus::Running { execution_id: None };
+                    job.info.started_at_ms = Some(now_ms());
+                    return Some((
+                        id,
+                        job.info.skill_id.clone(),
+                        job.options.clone(),
+                        job.cancellation.clone(),
+                    ));
+                }
+                Next::Wait(Some(until)) => {
+                    let timeout = until.saturating_duration_since(now);
+                    state = self
+                        .changed
+                        .wait_timeout(state, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+                Next::Wait(None) => {
+                    state = self
+                        .changed
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+    }
+
+    /// Run job `id` with its retries, holding the runtime lock only to
+    /// resolve and record each attempt.
+    fn run(
+        &self,
+        id: u64,
+        skill_id: &str,
+        options: &ExecutionOptions,
+        cancellation: &CancellationHandle,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let mut next = NextAttempt::first(skill_id, options.clone(), &ExecutionContext::new())
+            .with_cancellation(cancellation.clone());
+        loop {
+            if cancellation.is_cancelled() {
+                return Err(OpenSkillError::Cancelled);
+            }
+            let mut pending = self.runtime().begin_next_attempt(next)?;
+            if let Some(job) = self.state().jobs.get_mut(&id) {
+                job.info.status = JobStatus::Running {
+                    execution_id: Some(pending.execution_id()),
+                };
+            }
+            pending.run();
+            match self.runtime().finish_pending_execution(pending) {
+                ExecutionStep::Finished(result) => return result,
+                ExecutionStep::Retry { delay, next: retry } => {
+                    thread::sleep(delay);
+                    next = retry;
+                }
+            }
+        }
+    }
+}
+
+/// Runs skill executions submitted by many callers on one runtime, within
+/// concurrency and rate limits. Dropping the queue cancels queued jobs and
+/// waits for running ones to finish.
+pub struct ExecutionQueue {
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ExecutionQueue {
+    /// Start a queue running jobs on `runtime`.
+    pub fn new(runtime: Arc<Mutex<OpenSkillRuntime>>, config: QueueConfig) -> Self {
+        let max_concurrent = match config.max_concurrent {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        let shared = Arc::new(Shared {
+            runtime,
+            config,
+            max_concurrent,
+            state: Mutex::new(QueueState::default()),
+            changed: Condvar::new(),
+        });
+        let workers = (0..max_concurrent)
+            .filter_map(|index| {
+                let shared = shared.clone();
+                thread::Builder::new()
+                    .name(format!("openskills-queue-{index}"))
+                    .spawn(move || shared.work())
+                    .ok()
+            })
+            .collect();
+        Self { shared, workers }
+    }
+
+    /// Runtime the jobs run on.
+    pub fn runtime(&self) -> &Arc<Mutex<OpenSkillRuntime>> {
+        &self.shared.runtime
+    }
+
+    /// Queue an execution of `skill_id`; jobs with a higher `priority`
+    /// start first. Returns the job ID.
+    pub fn submit(
+        &self,
+        skill_id: &str,
+        options: ExecutionOptions,
+        priority: i32,
+    ) -> Result<u64, OpenSkillError> {
+        let mut state = self.shared.state();
+        if state.shutdown {
+            return Err(OpenSkillError::QueueError("queue is shut down".to_string()));
+        }
+        if let Some(max) = self.shared.config.max_queued {
+            if state.pending.len() >= max {
+                return Err(OpenSkillError::QueueError(format!(
+                    "queue is full ({max} jobs waiting)"
+                )));
+            }
+        }
+        state.next_id += 1;
+        let id = state.next_id;
+        let position = state
+            .pending
+            .partition_point(|queued| state.jobs[queued].info.priority >= priority);
+        state.pending.insert(position, id);
+        state.jobs.insert(
+            id,
+            Job {
+                info: QueuedJob {
+                    id,
+                    skill_id: skill_id.to_string(),
+                    priority,
+                    status: JobStatus::Queued,
+                    submitted_at_ms: now_ms(),
+                    started_at_ms: None,
+                    finished_at_ms: None,
+                },
+                options,
+                cancellation: CancellationHandle::default(),
+                result: None,
+            },
+        );
+        drop(state);
+        self.shared.changed.notify_all();
+        Ok(id)
+    }
+
+    /// Job `id`, or `None` if it is unknown or its result was collected.
+    pub fn status(&self, id: u64) -> Option<QueuedJob> {
+        self.shared.state().jobs.get(&id).map(|job| job.info.clone())
+    }
+
+    /// All known jobs, in submission order.
+    pub fn jobs(&self) -> Vec<QueuedJob> {
+        let mut jobs: Vec<QueuedJob> = self
+            .shared
+            .state()
+            .jobs
+            .values()
+            .map(|job| job.info.clone())
+            .collect();
+        jobs.sort_by_key(|job| job.id);
+        jobs
+    }
+
+    /// Jobs waiting to start.
+    pub fn queued_len(&self) -> usize {
+        self.shared.state().pending.len()
+    }
+
+    /// Jobs running now.
+    pub fn running_len(&self) -> usize {
+        self.shared.state().running_total
+    }
+
+    /// Cancel job `id`: a queued job never starts, a running one is killed.
+    /// Returns `false` if the job is unknown or already done.
+    pub fn cancel(&self, id: u64) -> bool {
+        let mut state = self.shared.state();
+        let Some(job) = state.jobs.get(&id) else {
+            return false;
+        };
+        if job.info.status.is_done() {
+            return false;
+        }
+        job.cancellation.cancel();
+        if let Some(index) = state.pending.iter().position(|queued| *queued == id) {
+            state.pending.remove(index);
+            state.finish(id, Err(OpenSkillError::Cancelled), self.shared.config.max_finished);
+        }
+        drop(state);
+        self.shared.changed.notify_all();
+        true
+    }
+
+    /// Wait until job `id` is done and collect its result, removing the job.
+    /// Fails with `OpenSkillError::Timeout` if it is not done within
+    /// `timeout` (`None` = wait indefinitely); the job then stays queued.
+    pub fn wait(
+        &self,
+        id: u64,
+        timeout: Option<Duration>,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut state = self.shared.state();
+        loop {
+            match state.jobs.get(&id) {
+                None => return Err(OpenSkillError::QueueError(format!("unknown job: {id}"))),
+                Some(job) if job.result.is_some() => break,
+                Some(_) => {}
+            }
+            state = match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(OpenSkillError::Timeout);
+                    }
+                    self.shared
+                        .changed
+                        .wait_timeout(state, deadline - now)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .shared
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+        state.finished.retain(|finished| *finished != id);
+        let job = state.jobs.remove(&id).expect("job checked above");
+        job.result.expect("job checked above")
+    }
+}
+
+impl Drop for ExecutionQueue {
+    fn drop(&mut self) {
+        let mut state = self.shared.state();
+        state.shutdown = true;
+        for id in std::mem::take(&mut state.pending) {
+            if let Some(job) = state.jobs.get(&id) {
+                job.cancellation.cancel();
+            }
+            state.finish(id, Err(OpenSkillError::Cancelled), usize::MAX);
+        }
+        drop(state);
+        self.shared.changed.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl std::fmt::Debug for ExecutionQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionQueue")
+            .field("config", &self.shared.config)
+            .field("queued", &self.queued_len())
+            .field("running", &self.running_len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limited_until_oldest_start_expires() {
+        let now = Instant::now();
+        let limit = Some(RateLimit::new(2, Duration::from_secs(10)));
+        let mut starts = VecDeque::from([now - Duration::from_secs(12), now - Duration::from_secs(4)]);
+        assert_eq!(rate_limited(&mut starts, limit, now), None);
+
+        starts.push_back(now);
+        assert_eq!(
+            rate_limited(&mut starts, limit, now),
+            Some(now + Duration::from_secs(6))
+        );
+        assert_eq!(rate_limited(&mut starts, None, now), None);
+    }
+}
@@ -10,16 +10,42 @@
 //!
 //! Both modes use the same permission model and sandbox.
 
-use crate::audit::ExecutionStatus;
+use crate::active_executions::CancellationHandle;
+use crate::audit::{CapabilityDowngrade, ExecutionStatus, HostCall, NetworkRequest};
+use crate::capability_mapping::CapabilityMapping;
+use crate::dry_run::{ExecutionPlan, SandboxProfile};
+use crate::entrypoint::{resolve_entrypoint, EntrypointConfig};
 use crate::errors::OpenSkillError;
-use crate::native_runner::{detect_script_type, execute_native, NativeRunnerConfig, ScriptType};
+use crate::events::ExecutionEventSender;
+#[cfg(unix)]
+use crate::egress::{ConnectFilter, Egress, EgressPolicy};
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::exec_policy::ExecPolicy;
+use crate::io_pool;
+use crate::journal::JournalRecord;
+use crate::metrics::SetupTimings;
+use crate::native_runner::{
+    detect_script_type, execute_native, plan_native, NativeRunnerConfig, ResourceLimits,
+    ScriptType,
+};
+use crate::sandbox_backend::{ContainerConfig, ExecutionBackend};
 use crate::sandbox_mode::SandboxMode;
-use crate::permissions::{map_tools_to_capabilities, PermissionEnforcer};
+use crate::sandbox_violation::SandboxViolation;
+use crate::permissions::PermissionEnforcer;
+use crate::python_wasm::{PythonWasm, INTERPRETER_MODULE};
+use crate::redact::OutputRedactor;
+use crate::sandbox_overrides::SandboxOverrides;
 use crate::registry::Skill;
+use crate::script_session::ScriptStdin;
+use crate::secret_redaction::SecretRedactor;
+#[cfg(target_os = "macos")]
+use crate::temp_artifacts::TempArtifact;
+use crate::wasm_engine::WasmEngineHandle;
 #[cfg(feature = "wasm")]
-use crate::wasm_runner::execute_wasm;
+use crate::wasm_runner::{execute_wasm, precompile_wasm};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
@@ -38,6 +64,20 @@ pub struct ExecutionArtifacts {
     /// Effective OS sandbox mode for this execution (public API for callers/bindings).
     #[allow(dead_code)]
     pub sandbox_mode: SandboxMode,
+    /// Raw stdout when it was detected as binary (`stdout` is then empty).
+    pub binary_stdout: Option<Vec<u8>>,
+    /// Operations the OS sandbox blocked (native scripts only).
+    pub sandbox_violations: Vec<SandboxViolation>,
+    /// Fuel consumed (WASM only).
+    pub fuel_consumed: Option<u64>,
+    /// Outbound `wasi:http` requests (WASM only).
+    pub network_requests: Vec<NetworkRequest>,
+    /// Host function calls (WASM only).
+    pub host_calls: Vec<HostCall>,
+    /// Tools the execution mode could not fully grant.
+    pub capability_downgrades: Vec<CapabilityDowngrade>,
+    /// Sandbox setup latency, by phase.
+    pub setup_timings: SetupTimings,
 }
 
 /// Options for skill execution.
@@ -47,6 +87,10 @@ pub struct ExecutionOptions {
     pub timeout_ms: Option<u64>,
     /// Override memory limit in MB.
     pub memory_mb: Option<u64>,
+    /// CPU cap for native scripts, as a fraction of one CPU (Linux).
+    pub cpu_quota: Option<f64>,
+    /// Fuel budget for WASM execution; unmetered when `None`.
+    pub max_fuel: Option<u64>,
     /// Input data for WASM execution.
     pub input: Option<Value>,
     /// WASM module path override (relative to skill root).
@@ -59,14 +103,33 @@ pub struct ExecutionOptions {
     /// Set by OpenSkillRuntime after resolving the skill manifest's allowed-tools
     /// against the host policy. Defaults to empty (no tools approved).
     pub effective_tools: Vec<String>,
+    /// Translates `effective_tools` into sandbox grants.
+    pub capability_mapping: Arc<CapabilityMapping>,
     /// Optional native runner config (interpreter overrides, Python site visibility).
     pub native_runner_config: Option<NativeRunnerConfig>,
+    /// Candidate patterns and precedence for entry-point auto-detection.
+    pub entrypoint_config: EntrypointConfig,
+    /// Extra paths, network and env vars granted by the host for this run.
+    pub sandbox_overrides: SandboxOverrides,
+    /// Stops the execution when cancelled.
+    pub cancellation: Option<CancellationHandle>,
+    /// Resolve the execution without running it; see [`crate::dry_run`].
+    pub dry_run: bool,
+    /// Only `workspace_dir` is writable in the sandbox, as a permission
+    /// callback answered `AllowInWorkspace`.
+    pub workspace_only_writes: bool,
+    /// Journal entry to record the spawned process in.
+    pub journal: Option<JournalRecord>,
+    /// Redacts streamed output chunks and output spilled to disk. Set by
+    /// OpenSkillRuntime from `with_secret_redaction`.
+    pub secret_redactor: Option<SecretRedactor>,
 }
 
 /// Target for skill execution (what to run within a skill).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum ExecutionTarget {
     /// Auto-detect: find entry point (script.py, main.py, skill.wasm, etc.)
+    #[default]
     Auto,
     /// Run a specific file - auto-detect type from extension (.wasm = WASM, .py/.sh = native).
     /// This is the recommended variant for transparent sandbox selection.
@@ -87,9 +150,15 @@ pub enum ExecutionTarget {
     },
 }
 
-impl Default for ExecutionTarget {
-    fn default() -> Self {
-        Self::Auto
+impl ExecutionTarget {
+    /// `auto`, or the path of the file to run.
+    pub(crate) fn label(&self) -> String {
+        match self {
+            ExecutionTarget::Auto => "auto".to_string(),
+            ExecutionTarget::Path { path, .. }
+            | ExecutionTarget::Script { path, .. }
+            | ExecutionTarget::Wasm { path } => path.clone(),
+        }
     }
 }
 
@@ -102,6 +171,10 @@ pub struct TargetExecutionOptions {
     pub timeout_ms: Option<u64>,
     /// Override memory limit in MB.
     pub memory_mb: Option<u64>,
+    /// CPU cap for native scripts, as a fraction of one CPU (Linux).
+    pub cpu_quota: Option<f64>,
+    /// Fuel budget for WASM targets; unmetered when `None`.
+    pub max_fuel: Option<u64>,
     /// Input data (JSON).
     pub input: Option<Value>,
     /// Working directory override (relative to skill root).
@@ -114,14 +187,38 @@ pub struct TargetExecutionOptions {
     /// Set by OpenSkillRuntime after resolving the skill manifest's allowed-tools
     /// against the host policy. Defaults to empty (no tools approved).
     pub effective_tools: Vec<String>,
+    /// Translates `effective_tools` into sandbox grants.
+    pub capability_mapping: Arc<CapabilityMapping>,
     /// Optional native runner config (interpreter overrides, Python site visibility).
     pub native_runner_config: Option<NativeRunnerConfig>,
+    /// Candidate patterns and precedence for entry-point auto-detection.
+    pub entrypoint_config: EntrypointConfig,
+    /// Extra paths, network and env vars granted by the host for this run.
+    pub sandbox_overrides: SandboxOverrides,
+    /// Stops the execution when cancelled.
+    pub cancellation: Option<CancellationHandle>,
+    /// Receives stdout and stderr chunks as the target writes them.
+    pub events: Option<ExecutionEventSender>,
+    /// Messages streamed to a native script's stdin (set by
+    /// `OpenSkillRuntime::start_script_session`).
+    pub stdin: Option<ScriptStdin>,
+    /// Journal entry to record the spawned process in.
+    pub journal: Option<JournalRecord>,
+    /// Only `workspace_dir` is writable in the sandbox, as a permission
+    /// callback answered `AllowInWorkspace`.
+    pub workspace_only_writes: bool,
+    /// Redacts streamed output chunks and output spilled to disk. Set by
+    /// OpenSkillRuntime from `with_secret_redaction`.
+    pub secret_redactor: Option<SecretRedactor>,
 }
 
 #[derive(Debug)]
 enum ExecutionMode {
-    Wasm { wasm_module: String },
-    Native { script_path: PathBuf, script_type: ScriptType },
+    /// `python` is set when a Python script runs on the skill's
+    /// interpreter component.
+    Wasm { wasm_module: String, python: Option<PythonWasm> },
+    Native { script_path: PathBuf, script_type: ScriptType, args: Vec<String> },
+    InstructionOnly,
 }
 
 /// Execute a skill's WASM module or native script in a sandbox.
@@ -132,9 +229,14 @@ enum ExecutionMode {
 pub fn execute_skill(
     skill: &Skill,
     options: ExecutionOptions,
+    wasm_engine: &WasmEngineHandle,
 ) -> Result<ExecutionArtifacts, OpenSkillError> {
+    #[cfg(not(feature = "wasm"))]
+    let _ = wasm_engine;
     let allowed_tools = options.effective_tools.clone();
-    let mut wasm_config = map_tools_to_capabilities(&allowed_tools);
+    let mut wasm_config = options.capability_mapping.wasm_config(&allowed_tools);
+    options.sandbox_overrides.check()?;
+    options.sandbox_overrides.apply(&mut wasm_config);
 
     // Apply option overrides
     if let Some(timeout) = options.timeout_ms.filter(|&timeout| timeout > 0) {
@@ -143,12 +245,32 @@ pub fn execute_skill(
     if let Some(memory) = options.memory_mb {
         wasm_config.memory_mb = memory;
     }
+    // Native scripts are only capped when the caller asks for it.
+    let limits = ResourceLimits {
+        memory_mb: options.memory_mb,
+        cpu_quota: options.cpu_quota,
+    };
 
     let enforcer = PermissionEnforcer::new(
         allowed_tools.clone(),
         wasm_config.clone(),
         skill.root.clone(),
-    );
+    )
+    .with_process_tools(options.capability_mapping.process_tools(&allowed_tools))
+    .with_env(options.sandbox_overrides.env.clone())
+    .with_cancellation(options.cancellation.clone())
+    .with_journal(options.journal.clone())
+    .with_workspace_only_writes(options.workspace_only_writes);
+
+    let mode = detect_execution_mode(
+        skill,
+        options.wasm_module.as_deref(),
+        &options.entrypoint_config,
+    )?;
+    if options.dry_run {
+        let plan = plan_execution(skill, mode, &options, &enforcer, limits)?;
+        return dry_run_artifacts(plan);
+    }
 
     // Prepare input
     let input = options.input.unwrap_or_else(|| {
@@ -158,8 +280,9 @@ pub fn execute_skill(
         })
     });
 
-    match detect_execution_mode(&skill.root, options.wasm_module)? {
-        ExecutionMode::Wasm { wasm_module } => {
+    match mode {
+        #[cfg_attr(not(feature = "wasm"), allow(unused_variables))]
+        ExecutionMode::Wasm { wasm_module, python } => {
             let wasm_path = skill.root.join(&wasm_module);
             if !wasm_path.exists() {
                 return Err(OpenSkillError::WasmError(format!(
@@ -169,12 +292,15 @@ pub fn execute_skill(
             }
             #[cfg(feature = "wasm")]
             return execute_wasm(
+                &*wasm_engine.get()?,
                 skill,
                 &wasm_module,
                 input,
                 wasm_config.timeout_ms,
+                options.max_fuel,
                 &enforcer,
                 options.workspace_dir.as_deref(),
+                python.as_ref(),
             );
             #[cfg(not(feature = "wasm"))]
             return Err(OpenSkillError::WasmDisabled);
@@ -182,6 +308,7 @@ pub fn execute_skill(
         ExecutionMode::Native {
             script_path,
             script_type,
+            args,
         } => execute_native(
             skill,
             &script_path,
@@ -189,14 +316,120 @@ pub fn execute_skill(
             input,
             wasm_config.timeout_ms,
             &enforcer,
-            &allowed_tools,
             options.workspace_dir.as_deref(),
-            &[], // No user-provided args for auto-detected scripts
+            &args, // Declared with `entrypoint:` in SKILL.md, if any
             options.native_runner_config.as_ref(),
+            options.secret_redactor.as_ref(),
+            limits,
         ),
+        ExecutionMode::InstructionOnly => Ok(instruction_only_artifacts(skill)),
     }
 }
 
+/// Resolve what [`execute_skill`] would run for `mode`, without running it.
+fn plan_execution(
+    skill: &Skill,
+    mode: ExecutionMode,
+    options: &ExecutionOptions,
+    enforcer: &PermissionEnforcer,
+    limits: ResourceLimits,
+) -> Result<ExecutionPlan, OpenSkillError> {
+    let mut plan = ExecutionPlan {
+        skill_id: skill.id.clone(),
+        version: skill.manifest.version().to_string(),
+        mode: "instruction_only".to_string(),
+        entrypoint: None,
+        command: Vec::new(),
+        backend: None,
+        effective_tools: options.effective_tools.clone(),
+        capability_downgrades: Vec::new(),
+        timeout_ms: enforcer.timeout_ms(),
+        memory_mb: options.memory_mb,
+        max_fuel: options.max_fuel,
+        sandbox: None,
+    };
+    match mode {
+        ExecutionMode::Wasm { wasm_module, python } => {
+            let wasm_path = skill.root.join(&wasm_module);
+            if !wasm_path.exists() {
+                return Err(OpenSkillError::WasmError(format!(
+                    "WASM module not found: {}",
+                    wasm_path.display()
+                )));
+            }
+            if cfg!(not(feature = "wasm")) {
+                return Err(OpenSkillError::WasmDisabled);
+            }
+            plan.mode = "wasm".to_string();
+            plan.backend = Some("wasm".to_string());
+            plan.memory_mb = Some(enforcer.memory_mb());
+            match python {
+                Some(python) => {
+                    plan.entrypoint = python.script.clone();
+                    plan.command = python.argv();
+                }
+                None => plan.entrypoint = Some(wasm_module),
+            }
+            #[cfg(feature = "wasm")]
+            {
+                plan.capability_downgrades = crate::wasm_runner::process_downgrades(enforcer);
+            }
+            plan.sandbox = Some(SandboxProfile {
+                read_only: enforcer.filesystem_read_paths(),
+                read_write: enforcer.filesystem_write_paths(),
+                allow_network: enforcer.network_enabled(),
+                allowed_hosts: enforcer.network_hosts().to_vec(),
+                ..SandboxProfile::default()
+            });
+        }
+        ExecutionMode::Native {
+            script_path,
+            script_type,
+            args,
+        } => {
+            let native = plan_native(
+                skill,
+                &script_path,
+                script_type,
+                enforcer,
+                options.workspace_dir.as_deref(),
+                &args,
+                options.native_runner_config.as_ref(),
+                limits,
+            )?;
+            plan.mode = "native".to_string();
+            plan.entrypoint = script_path
+                .strip_prefix(&skill.root)
+                .ok()
+                .map(|p| p.to_string_lossy().to_string());
+            plan.command = native.command;
+            plan.backend = Some(native.backend.to_string());
+            plan.sandbox = native.sandbox;
+        }
+        ExecutionMode::InstructionOnly => {}
+    }
+    Ok(plan)
+}
+
+/// Artifacts for a dry run: the plan as output, nothing executed.
+fn dry_run_artifacts(plan: ExecutionPlan) -> Result<ExecutionArtifacts, OpenSkillError> {
+    Ok(ExecutionArtifacts {
+        output: serde_json::to_value(&plan)?,
+        stdout: String::new(),
+        stderr: String::new(),
+        permissions_used: Vec::new(),
+        exit_status: ExecutionStatus::DryRun,
+        sandbox_mode: SandboxMode::Enforce,
+        binary_stdout: None,
+        sandbox_violations: Vec::new(),
+        fuel_consumed: None,
+        network_requests: Vec::new(),
+        host_calls: Vec::new(),
+        capability_downgrades: plan.capability_downgrades,
+        setup_timings: SetupTimings::default(),
+    })
+}
+
 /// Execute a specific target within a skill.
 ///
 /// Unlike `execute_skill()` which auto-detects the entry point, this function
@@ -208,6 +441,7 @@ pub fn execute_skill(
 ///
 /// * `skill` - The skill containing the target
 /// * `options` - Target execution options including what to run
+/// * `wasm_engine` - Engine for WASM targets
 ///
 /// # Example
 ///
@@ -221,15 +455,20 @@ pub fn execute_skill(
 ///     timeout_ms: Some(30000),
 ///     ..Default::default()
 /// };
-/// let result = run_skill_target(&skill, options)?;
+/// let result = run_skill_target(&skill, options, &wasm_engine)?;
 /// ```
 pub fn run_skill_target(
     skill: &Skill,
     options: TargetExecutionOptions,
+    wasm_engine: &WasmEngineHandle,
 ) -> Result<ExecutionArtifacts, OpenSkillError> {
+    #[cfg(not(feature = "wasm"))]
+    let _ = wasm_engine;
     // Map allowed-tools to capabilities
     let allowed_tools = options.effective_tools.clone();
-    let mut wasm_config = map_tools_to_capabilities(&allowed_tools);
+    let mut wasm_config = options.capability_mapping.wasm_config(&allowed_tools);
+    options.sandbox_overrides.check()?;
+    options.sandbox_overrides.apply(&mut wasm_config);
 
     // Apply option overrides
     if let Some(timeout) = options.timeout_ms.filter(|&timeout| timeout > 0) {
@@ -238,12 +477,30 @@ pub fn run_skill_target(
     if let Some(memory) = options.memory_mb {
         wasm_config.memory_mb = memory;
     }
+    // Native scripts are only capped when the caller asks for it.
+    let limits = ResourceLimits {
+        memory_mb: options.memory_mb,
+        cpu_quota: options.cpu_quota,
+    };
 
     let enforcer = PermissionEnforcer::new(
         allowed_tools.clone(),
         wasm_config.clone(),
         skill.root.clone(),
-    );
+    )
+    .with_process_tools(options.capability_mapping.process_tools(&allowed_tools))
+    .with_env(options.sandbox_overrides.env.clone())
+    .with_cancellation(options.cancellation.clone())
+    .with_events(options.events.clone())
+    .with_output_redactor(
+        options
+            .secret_redactor
+            .clone()
+            .map(|secrets| OutputRedactor::new().with_secrets(secrets)),
+    )
+    .with_stdin(options.stdin.clone())
+    .with_journal(options.journal.clone())
+    .with_workspace_only_writes(options.workspace_only_writes);
 
     // Prepare input
     let input = options.input.unwrap_or_else(|| {
@@ -256,8 +513,9 @@ pub fn run_skill_target(
     match options.target {
         ExecutionTarget::Auto => {
             // Fallback to auto-detection
-            match detect_execution_mode(&skill.root, None)? {
-                ExecutionMode::Wasm { wasm_module } => {
+            match detect_execution_mode(skill, None, &options.entrypoint_config)? {
+                #[cfg_attr(not(feature = "wasm"), allow(unused_variables))]
+                ExecutionMode::Wasm { wasm_module, python } => {
                     let wasm_path = skill.root.join(&wasm_module);
                     if !wasm_path.exists() {
                         return Err(OpenSkillError::WasmError(format!(
@@ -267,12 +525,15 @@ pub fn run_skill_target(
                     }
                     #[cfg(feature = "wasm")]
                     return execute_wasm(
+                        &*wasm_engine.get()?,
                         skill,
                         &wasm_module,
                         input,
                         wasm_config.timeout_ms,
+                        options.max_fuel,
                         &enforcer,
                         options.workspace_dir.as_deref(),
+                        python.as_ref(),
                     );
                     #[cfg(not(feature = "wasm"))]
                     return Err(OpenSkillError::WasmDisabled);
@@ -280,6 +541,7 @@ pub fn run_skill_target(
                 ExecutionMode::Native {
                     script_path,
                     script_type,
+                    args,
                 } => execute_native(
                     skill,
                     &script_path,
@@ -287,11 +549,13 @@ pub fn run_skill_target(
                     input,
                     wasm_config.timeout_ms,
                     &enforcer,
-                    &allowed_tools,
                     options.workspace_dir.as_deref(),
-                    &[], // No user-provided args for auto-detected scripts
+                    &args, // Declared with `entrypoint:` in SKILL.md, if any
                     options.native_runner_config.as_ref(),
+                    options.secret_redactor.as_ref(),
+                    limits,
                 ),
+                ExecutionMode::InstructionOnly => Ok(instruction_only_artifacts(skill)),
             }
         }
         ExecutionTarget::Path { path, args } => {
@@ -342,12 +606,15 @@ pub fn run_skill_target(
                 // Execute as WASM
                 #[cfg(feature = "wasm")]
                 return execute_wasm(
+                    &*wasm_engine.get()?,
                     skill,
                     &path,
                     input,
                     wasm_config.timeout_ms,
+                    options.max_fuel,
                     &enforcer,
                     options.workspace_dir.as_deref(),
+                    None,
                 );
                 #[cfg(not(feature = "wasm"))]
                 return Err(OpenSkillError::WasmDisabled);
@@ -374,10 +641,11 @@ pub fn run_skill_target(
                     input_with_args,
                     wasm_config.timeout_ms,
                     &enforcer,
-                    &allowed_tools,
                     options.workspace_dir.as_deref(),
                     &args, // Pass args as command-line arguments too
                     options.native_runner_config.as_ref(),
+                    options.secret_redactor.as_ref(),
+                    limits,
                 )
             }
         }
@@ -444,10 +712,11 @@ pub fn run_skill_target(
                 input_with_args,
                 wasm_config.timeout_ms,
                 &enforcer,
-                &allowed_tools,
                 options.workspace_dir.as_deref(),
                 &args, // Pass args as command-line arguments too
                 options.native_runner_config.as_ref(),
+                options.secret_redactor.as_ref(),
+                limits,
             )
         }
         ExecutionTarget::Wasm { path } => {
@@ -481,12 +750,15 @@ pub fn run_skill_target(
 
             #[cfg(feature = "wasm")]
             return execute_wasm(
+                &*wasm_engine.get()?,
                 skill,
                 &path,
                 input,
                 wasm_config.timeout_ms,
+                options.max_fuel,
                 &enforcer,
                 options.workspace_dir.as_deref(),
+                None,
             );
             #[cfg(not(feature = "wasm"))]
             return Err(OpenSkillError::WasmDisabled);
@@ -494,6 +766,46 @@ pub fn run_skill_target(
     }
 }
 
+/// Whether `target` runs as a native script, rather than a WASM component
+/// or nothing (instruction-only skills).
+pub(crate) fn is_native_target(
+    skill: &Skill,
+    target: &ExecutionTarget,
+    entrypoint_config: &EntrypointConfig,
+) -> Result<bool, OpenSkillError> {
+    Ok(match target {
+        ExecutionTarget::Auto => matches!(
+            detect_execution_mode(skill, None, entrypoint_config)?,
+            ExecutionMode::Native { .. }
+        ),
+        ExecutionTarget::Path { path, .. } => !path.to_lowercase().ends_with(".wasm"),
+        ExecutionTarget::Script { .. } => true,
+        ExecutionTarget::Wasm { .. } => false,
+    })
+}
+
+/// Prepare the component a skill's auto-detected WASM entry point runs on.
+/// Skills that run natively or are instruction-only have nothing to precompile
+/// and get empty timings.
+pub fn precompile_skill(
+    skill: &Skill,
+    entrypoint_config: &EntrypointConfig,
+    wasm_engine: &WasmEngineHandle,
+) -> Result<SetupTimings, OpenSkillError> {
+    match detect_execution_mode(skill, None, entrypoint_config)? {
+        ExecutionMode::Wasm { wasm_module, .. } => {
+            #[cfg(feature = "wasm")]
+            return precompile_wasm(&*wasm_engine.get()?, skill, &wasm_module);
+            #[cfg(not(feature = "wasm"))]
+            {
+                let _ = (wasm_engine, wasm_module);
+                Err(OpenSkillError::WasmDisabled)
+            }
+        }
+        ExecutionMode::Native { .. } | ExecutionMode::InstructionOnly => Ok(SetupTimings::default()),
+    }
+}
+
 /// Read a file from a skill directory.
 ///
 /// This allows agents to read helper files (like `docx-js.md`) that skills
@@ -615,105 +927,66 @@ fn collect_files(
 }
 
 fn detect_execution_mode(
-    skill_root: &PathBuf,
-    wasm_override: Option<String>,
+    skill: &Skill,
+    wasm_override: Option<&str>,
+    config: &EntrypointConfig,
 ) -> Result<ExecutionMode, OpenSkillError> {
-    if let Some(wasm_module) = wasm_override {
-        return Ok(ExecutionMode::Wasm { wasm_module });
-    }
-
-    if let Some(wasm_module) = find_wasm_module(skill_root) {
-        return Ok(ExecutionMode::Wasm { wasm_module });
-    }
-
-    if let Some(script_path) = find_native_script(skill_root) {
-        let script_type = detect_script_type(&script_path)?;
-        return Ok(ExecutionMode::Native {
-            script_path,
-            script_type,
+    let resolution = resolve_entrypoint(
+        &skill.root,
+        skill.manifest.entrypoint.as_ref(),
+        wasm_override,
+        config,
+    )?;
+    let Some(selected) = resolution.selected else {
+        return Ok(ExecutionMode::InstructionOnly);
+    };
+    if selected.to_ascii_lowercase().ends_with(".wasm") {
+        return Ok(ExecutionMode::Wasm {
+            wasm_module: selected,
+            python: None,
         });
     }
-
-    Err(OpenSkillError::NativeExecutionError(
-        "No executable artifact found (expected .wasm, .py, or .sh)".to_string(),
-    ))
-}
-
-/// Find a WASM module in the skill directory.
-fn find_wasm_module(skill_root: &PathBuf) -> Option<String> {
-    // Look for common patterns
-    let candidates = [
-        "skill.wasm",
-        "wasm/skill.wasm",
-        "module.wasm",
-        "main.wasm",
-    ];
-
-    for candidate in candidates {
-        if skill_root.join(candidate).exists() {
-            return Some(candidate.to_string());
+    let script_path = skill.root.join(&selected);
+    let script_type = detect_script_type(&script_path)?;
+    // A skill that ships a Python interpreter component runs its Python in
+    // the WASM sandbox.
+    if cfg!(feature = "wasm") && script_type == ScriptType::Python {
+        if let Some(python) = PythonWasm::interpreter(&skill.root, &selected, &resolution.args) {
+            return Ok(ExecutionMode::Wasm {
+                wasm_module: INTERPRETER_MODULE.to_string(),
+                python: Some(python),
+            });
         }
     }
-
-    // Look for any .wasm file
-    if let Ok(entries) = std::fs::read_dir(skill_root) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().map(|e| e == "wasm").unwrap_or(false) {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    return Some(name.to_string());
-                }
-            }
-        }
-    }
-
-    None
+    Ok(ExecutionMode::Native {
+        script_path,
+        script_type,
+        args: resolution.args,
+    })
 }
 
-/// Find a native script in the skill directory.
-fn find_native_script(skill_root: &PathBuf) -> Option<PathBuf> {
-    let candidates = [
-        "script.py",
-        "main.py",
-        "src/main.py",
-        "index.py",
-        "src/index.py",
-        "script.sh",
-        "main.sh",
-        "src/main.sh",
-        "index.sh",
-        "src/index.sh",
-        "script.bash",
-        "main.bash",
-        "src/main.bash",
-        "index.bash",
-        "src/index.bash",
-    ];
-
-    for candidate in candidates {
-        let path = skill_root.join(candidate);
-        if path.exists() {
-            return Some(path);
-        }
-    }
-
-    for dir in [skill_root.to_path_buf(), skill_root.join("src"), skill_root.join("scripts")] {
-        if let Ok(entries) = std::fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() {
-                    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                        let ext = ext.to_ascii_lowercase();
-                        if ext == "py" || ext == "sh" || ext == "bash" {
-                            return Some(path);
-                        }
-                    }
-                }
-            }
-        }
+/// Result for a skill with nothing to execute: hand the instructions back
+/// so the agent can follow them itself.
+fn instruction_only_artifacts(skill: &Skill) -> ExecutionArtifacts {
+    ExecutionArtifacts {
+        output: serde_json::json!({
+            "status": "instruction_only",
+            "skill_id": skill.id,
+            "instructions": skill.instructions,
+        }),
+        stdout: String::new(),
+        stderr: String::new(),
+        permissions_used: Vec::new(),
+        exit_status: ExecutionStatus::InstructionOnly,
+        sandbox_mode: SandboxMode::Disabled,
+        binary_stdout: None,
+        sandbox_violations: Vec::new(),
+        fuel_consumed: None,
+        network_requests: Vec::new(),
+        host_calls: Vec::new(),
+        capability_downgrades: Vec::new(),
+        setup_timings: SetupTimings::default(),
     }
-
-    None
 }
 
 // ============================================================================
@@ -728,8 +1001,20 @@ fn find_native_script(skill_root: &PathBuf) -> Option<PathBuf> {
 pub struct CommandPermissions {
     /// Allow network access.
     pub allow_network: bool,
+    /// Hosts reachable when `allow_network` is set (subdomains match). When
+    /// non-empty, traffic goes through a filtering proxy exported via
+    /// `HTTP(S)_PROXY`; direct connections elsewhere are blocked.
+    pub allowed_hosts: Vec<String>,
+    /// TCP ports reachable when `allow_network` is set (empty = any).
+    pub allowed_ports: Vec<u16>,
     /// Allow subprocess spawning.
     pub allow_process: bool,
+    /// Programs the command and its subprocesses may execute: names looked
+    /// up in `PATH`, or paths (empty = any). See [`crate::command_programs`] to
+    /// derive the list from the command itself. Enforced by the platform
+    /// sandbox, not the container backend; on Linux it needs Landlock (ABI 1
+    /// or later) and the command fails to start without it.
+    pub allowed_programs: Vec<String>,
     /// Directories the command can read from.
     pub read_paths: Vec<PathBuf>,
     /// Directories the command can write to.
@@ -740,6 +1025,11 @@ pub struct CommandPermissions {
     pub timeout_ms: u64,
     /// OS sandbox enforcement for this command (default: enforce).
     pub sandbox_mode: SandboxMode,
+    /// Sandbox backend. Only `Container` changes behavior for commands;
+    /// other values use the platform sandbox.
+    pub backend: ExecutionBackend,
+    /// Engine, image and limits used when `backend` is `Container`.
+    pub container: ContainerConfig,
 }
 
 /// Result from sandboxed command execution.
@@ -790,8 +1080,6 @@ pub fn run_command_direct(
     permissions: &CommandPermissions,
 ) -> Result<CommandResult, OpenSkillError> {
     use std::process::{Command, Stdio};
-    use std::thread;
-    use std::time::{Duration, Instant};
 
     if !working_dir.exists() {
         return Err(OpenSkillError::NativeExecutionError(format!(
@@ -828,18 +1116,127 @@ pub fn run_command_direct(
     if let Ok(path) = std::env::var("PATH") {
         cmd.env("PATH", path);
     }
+    cmd.env("TERM", "dumb");
     for (key, value) in &permissions.env_vars {
         cmd.env(key, value);
     }
 
-    let mut child = cmd.spawn().map_err(|e| {
+    let child = cmd.spawn().map_err(|e| {
         OpenSkillError::NativeExecutionError(format!("Failed to execute command: {e}"))
     })?;
 
+    wait_for_unsandboxed_child(child, permissions)
+}
+
+/// Egress allowlist declared by command permissions.
+#[cfg(unix)]
+fn egress_policy(permissions: &CommandPermissions) -> EgressPolicy {
+    EgressPolicy::new(&permissions.allowed_hosts, &permissions.allowed_ports)
+}
+
+/// Exec allowlist for `permissions`, resolved with the `PATH` the command
+/// will see.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn exec_policy(permissions: &CommandPermissions, working_dir: &Path) -> Option<ExecPolicy> {
+    let search_path = permissions
+        .env_vars
+        .iter()
+        .rev()
+        .find(|(key, _)| key == "PATH")
+        .map(|(_, value)| std::ffi::OsString::from(value))
+        .or_else(|| std::env::var_os("PATH"));
+    ExecPolicy::new(&permissions.allowed_programs, search_path.as_deref(), working_dir)
+}
+
+/// Run a shell command inside a Docker/Podman container.
+///
+/// The working directory and `read_paths` are mounted read-only,
+/// `write_paths` read-write; the network is disabled unless
+/// `allow_network` is set (and `allowed_hosts`/`allowed_ports` are empty,
+/// since the container network cannot be filtered) and `env_vars` are
+/// forwarded by name.
+#[cfg(unix)]
+pub fn run_command_in_container(
+    command: &str,
+    working_dir: &Path,
+    permissions: &CommandPermissions,
+) -> Result<CommandResult, OpenSkillError> {
+    use crate::sandbox_backend::container;
+    use std::process::{Command, Stdio};
+
+    if !working_dir.exists() {
+        return Err(OpenSkillError::NativeExecutionError(format!(
+            "Working directory does not exist: {}",
+            working_dir.display()
+        )));
+    }
+    let canonical_working_dir = working_dir.canonicalize().map_err(|e| {
+        OpenSkillError::NativeExecutionError(format!(
+            "Failed to canonicalize working directory: {}",
+            e
+        ))
+    })?;
+
+    let engine = container::find_engine(&permissions.container).ok_or_else(|| {
+        OpenSkillError::NativeExecutionError(
+            "container backend requires docker or podman in PATH".to_string(),
+        )
+    })?;
+
+    let egress = Egress::start(
+        permissions.allow_network,
+        &egress_policy(permissions),
+        ConnectFilter::None,
+    )?;
+    let mut ro_mounts = vec![canonical_working_dir.clone()];
+    ro_mounts.extend(permissions.read_paths.iter().cloned());
+    let spec = container::ContainerSpec {
+        ro_mounts,
+        rw_mounts: permissions.write_paths.clone(),
+        allow_network: egress.allow_network,
+        workdir: canonical_working_dir,
+        timeout_ms: permissions.timeout_ms,
+    };
+
+    let mut cmd = Command::new(engine);
+    cmd.stdin(Stdio::null());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.env_clear();
+    if let Ok(path) = std::env::var("PATH") {
+        cmd.env("PATH", path);
+    }
+    cmd.env("TERM", "dumb");
+    for (key, value) in &permissions.env_vars {
+        cmd.env(key, value);
+    }
+    container::prepare(
+        &mut cmd,
+        &permissions.container,
+        &spec,
+        "bash",
+        &["-c".to_string(), command.to_string()],
+    );
+
+    let child = cmd.spawn().map_err(|e| {
+        OpenSkillError::NativeExecutionError(format!("Failed to start container: {e}"))
+    })?;
+
+    wait_for_unsandboxed_child(child, permissions)
+}
+
+/// Collect output from a command child, killing it after the timeout.
+fn wait_for_unsandboxed_child(
+    mut child: std::process::Child,
+    permissions: &CommandPermissions,
+) -> Result<CommandResult, OpenSkillError> {
+    use std::thread;
+    use std::time::{Duration, Instant};
+
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
-    let stdout_handle = thread::spawn(move || read_stream_to_string(stdout));
-    let stderr_handle = thread::spawn(move || read_stream_to_string(stderr));
+    let stdout_task = io_pool::spawn(move || read_stream_to_string(stdout));
+    let stderr_task = io_pool::spawn(move || read_stream_to_string(stderr));
 
     let timeout_ms = if permissions.timeout_ms > 0 {
         permissions.timeout_ms
@@ -860,9 +1257,9 @@ pub fn run_command_direct(
         thread::sleep(Duration::from_millis(10));
     };
 
-    let stdout_content = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
+    let stdout_content = stdout_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
-    let stderr_content = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
+    let stderr_content = stderr_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
 
     let exit_code = status
@@ -886,6 +1283,9 @@ pub fn run_sandboxed_command(
     if permissions.sandbox_mode == SandboxMode::Disabled {
         return run_command_direct(command, working_dir, &permissions);
     }
+    if permissions.backend == ExecutionBackend::Container {
+        return run_command_in_container(command, working_dir, &permissions);
+    }
 
     use std::process::{Command, Stdio};
 
@@ -904,18 +1304,26 @@ pub fn run_sandboxed_command(
     })?;
 
     // Build seatbelt profile
+    let egress = Egress::start(
+        permissions.allow_network,
+        &egress_policy(&permissions),
+        ConnectFilter::AddressesAndPorts,
+    )?;
+    let exec_policy = exec_policy(&permissions, &canonical_working_dir);
     let profile = build_command_seatbelt_profile(
         &canonical_working_dir,
         &permissions,
+        &egress,
+        exec_policy.as_ref(),
     );
 
     // Write profile to temp file
-    let profile_path = write_temp_profile(&profile)?;
+    let profile = write_temp_profile(&profile)?;
 
     // Build command: sandbox-exec -f <profile> -- /bin/bash -c "<command>"
     let mut cmd = Command::new("sandbox-exec");
     cmd.arg("-f")
-        .arg(&profile_path)
+        .arg(profile.path())
         .arg("--")
         .arg("/bin/bash")
         .arg("-c")
@@ -935,6 +1343,8 @@ pub fn run_sandboxed_command(
     if let Ok(lang) = std::env::var("LANG") {
         cmd.env("LANG", lang);
     }
+    // Output is captured, not a terminal
+    cmd.env("TERM", "dumb");
     if let Ok(tmpdir) = std::env::var("TMPDIR") {
         cmd.env("TMPDIR", tmpdir);
     }
@@ -942,18 +1352,19 @@ pub fn run_sandboxed_command(
     for (key, value) in &permissions.env_vars {
         cmd.env(key, value);
     }
+    cmd.envs(egress.env_vars());
 
     // Spawn the process
     let child = match cmd.spawn() {
         Ok(child) => child,
         Err(_e) => {
-            let _ = std::fs::remove_file(&profile_path);
+            drop(profile);
             // Fall back to running without sandbox if sandbox-exec fails
             return run_command_direct(command, working_dir, &permissions);
         }
     };
 
-    let result = wait_for_command_child(child, &permissions, Some(profile_path))?;
+    let result = wait_for_command_child(child, &permissions, Some(profile))?;
     if result.exit_code == 134 || result.exit_code == 139 || result.exit_code == 136 || result.exit_code == 1 {
         return run_command_direct(command, working_dir, &permissions);
     }
@@ -964,20 +1375,14 @@ pub fn run_sandboxed_command(
 fn wait_for_command_child(
     mut child: std::process::Child,
     permissions: &CommandPermissions,
-    profile_path: Option<PathBuf>,
+    profile: Option<TempArtifact>,
 ) -> Result<CommandResult, OpenSkillError> {
     use std::time::{Duration, Instant};
 
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
-    let stdout_handle = thread::spawn(move || {
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream_to_string(stdout)))
-            .unwrap_or_else(|_| String::new())
-    });
-    let stderr_handle = thread::spawn(move || {
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream_to_string(stderr)))
-            .unwrap_or_else(|_| String::new())
-    });
+    let stdout_task = io_pool::spawn(move || read_stream_to_string(stdout));
+    let stderr_task = io_pool::spawn(move || read_stream_to_string(stderr));
 
     let timeout_ms = if permissions.timeout_ms > 0 {
         permissions.timeout_ms
@@ -998,13 +1403,11 @@ fn wait_for_command_child(
         thread::sleep(Duration::from_millis(10));
     };
 
-    if let Some(path) = profile_path {
-        let _ = std::fs::remove_file(path);
-    }
+    drop(profile);
 
-    let stdout_content = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
+    let stdout_content = stdout_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
-    let stderr_content = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
+    let stderr_content = stderr_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
 
     let exit_code = status
@@ -1023,11 +1426,25 @@ fn wait_for_command_child(
 fn build_command_seatbelt_profile(
     working_dir: &Path,
     permissions: &CommandPermissions,
+    egress: &Egress,
+    exec_policy: Option<&ExecPolicy>,
 ) -> String {
     let mut profile = String::from("(version 1)\n(deny default)\n");
 
     // Basic system access for running shell commands
-    profile.push_str("(allow process-exec)\n");
+    match exec_policy {
+        Some(policy) => {
+            profile.push_str("(allow process-exec");
+            for program in policy.programs() {
+                profile.push_str(&format!(
+                    " (literal \"{}\")",
+                    escape_seatbelt_path(program.to_string_lossy().as_ref())
+                ));
+            }
+            profile.push_str(")\n");
+        }
+        None => profile.push_str("(allow process-exec)\n"),
+    }
     profile.push_str("(allow sysctl-read)\n");
     profile.push_str("(allow mach-lookup)\n");
     profile.push_str("(allow ipc-posix-shm-read-data)\n");
@@ -1115,10 +1532,8 @@ fn build_command_seatbelt_profile(
         // `process-fork` is sufficient for subprocess spawning in exec tool use.
     }
 
-    // Network permissions
-    if permissions.allow_network {
-        profile.push_str("(allow network*)\n");
-    }
+    // Network permissions (narrowed by allowed_hosts/allowed_ports)
+    profile.push_str(&egress.seatbelt_rules());
 
     profile
 }
@@ -1129,52 +1544,10 @@ fn escape_seatbelt_path(path: &str) -> String {
 }
 
 #[cfg(target_os = "macos")]
-fn write_temp_profile(profile: &str) -> Result<PathBuf, OpenSkillError> {
-    use std::io::Write;
-    
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-    let pid = std::process::id();
-    let random_suffix = rand::random::<u32>();
-    let path = std::env::temp_dir().join(format!("openskills_cmd_{}_{}_{}.sb", pid, timestamp, random_suffix));
-    
-    let mut file = std::fs::File::create(&path).map_err(|e| {
+fn write_temp_profile(profile: &str) -> Result<TempArtifact, OpenSkillError> {
+    TempArtifact::write("cmd.sb", profile.as_bytes()).map_err(|e| {
         OpenSkillError::SeatbeltError(format!("Failed to create profile file: {}", e))
-    })?;
-    file.write_all(profile.as_bytes()).map_err(|e| {
-        OpenSkillError::SeatbeltError(format!("Failed to write profile: {}", e))
-    })?;
-    file.flush().map_err(|e| {
-        OpenSkillError::SeatbeltError(format!("Failed to flush profile: {}", e))
-    })?;
-    
-    Ok(path)
-}
-
-/// Safely join a thread with a timeout to prevent indefinite blocking.
-fn join_thread_with_timeout<T: Send + 'static>(
-    handle: thread::JoinHandle<T>,
-    timeout: Duration,
-) -> Result<T, OpenSkillError> {
-    use std::sync::mpsc;
-
-    let (tx, rx) = mpsc::channel();
-
-    // Spawn a watcher thread
-    thread::spawn(move || {
-        let result = handle.join();
-        let _ = tx.send(result);
-    });
-
-    match rx.recv_timeout(timeout) {
-        Ok(Ok(result)) => Ok(result),
-        Ok(Err(_)) => Err(OpenSkillError::NativeExecutionError(
-            "Thread panicked during execution".to_string(),
-        )),
-        Err(_) => Err(OpenSkillError::Timeout),
-    }
+    })
 }
 
 fn read_stream_to_string<R: std::io::Read>(stream: Option<R>) -> String {
@@ -1199,14 +1572,18 @@ pub fn run_sandboxed_command(
     if permissions.sandbox_mode == SandboxMode::Disabled {
         return run_command_direct(command, working_dir, &permissions);
     }
+    if permissions.backend == ExecutionBackend::Container {
+        return run_command_in_container(command, working_dir, &permissions);
+    }
 
+    use std::os::unix::process::ExitStatusExt;
     use std::os::unix::process::CommandExt;
     use std::process::{Command, Stdio};
     use std::time::Instant;
 
     use landlock::{
-        Access, AccessFs, PathBeneath, PathFd,
-        Ruleset, RulesetAttr, RulesetCreatedAttr, ABI,
+        Access, AccessFs, AccessNet, NetPort, PathBeneath, PathFd,
+        Ruleset, RulesetAttr, RulesetCreatedAttr, RulesetStatus, ABI,
     };
 
     // Validate working directory exists
@@ -1274,6 +1651,8 @@ pub fn run_sandboxed_command(
     if let Ok(lang) = std::env::var("LANG") {
         cmd.env("LANG", lang);
     }
+    // Output is captured, not a terminal
+    cmd.env("TERM", "dumb");
     cmd.env("TMPDIR", "/tmp");
 
     // Pass through user-specified environment variables
@@ -1281,40 +1660,84 @@ pub fn run_sandboxed_command(
         cmd.env(key, value);
     }
 
-    // Apply Landlock sandbox restrictions in the child process before exec
+    // Narrow the network to allowed hosts/ports (kept alive until the command exits)
+    let egress = Egress::start(
+        permissions.allow_network,
+        &egress_policy(&permissions),
+        crate::egress::landlock_connect_filter(),
+    )?;
+    cmd.envs(egress.env_vars());
+
+    // Apply Landlock sandbox restrictions and the seccomp filter in the child process before exec
     let ro_clone = ro_paths;
     let rw_clone = rw_paths;
+    let connect_ports = egress.connect_ports.clone();
+    let syscall_filter = crate::seccomp::build_filter(egress.allow_network);
+    let exec_policy = exec_policy(&permissions, &canonical_working_dir);
     unsafe {
         cmd.pre_exec(move || {
             // Use ABI V2 (Linux 5.19+): includes Refer for link/rename across dirs.
             // npm cacache uses hard links from _cacache/tmp/ to content-v2/; V1 forbids this (EXDEV).
             let abi = ABI::V2;
-            let result = (|| -> Result<(), landlock::RulesetError> {
-                let mut ruleset = Ruleset::default()
-                    .handle_access(AccessFs::from_all(abi))?
-                    .create()?;
+            // With an exec allowlist, directories grant everything but Execute.
+            let mut read_access = AccessFs::from_read(abi);
+            let mut write_access = AccessFs::from_all(abi);
+            if exec_policy.is_some() {
+                read_access.remove(AccessFs::Execute);
+                write_access.remove(AccessFs::Execute);
+            }
+            let result = (|| -> Result<RulesetStatus, landlock::RulesetError> {
+                let mut ruleset = Ruleset::default().handle_access(AccessFs::from_all(abi))?;
+                if connect_ports.is_some() {
+                    ruleset = ruleset.handle_access(AccessNet::ConnectTcp)?;
+                }
+                let mut ruleset = ruleset.create()?;
+
+                for port in connect_ports.iter().flatten() {
+                    ruleset = ruleset.add_rule(NetPort::new(*port, AccessNet::ConnectTcp))?;
+                }
 
                 for path in &ro_clone {
                     if let Ok(fd) = PathFd::new(path) {
-                        ruleset = ruleset.add_rule(PathBeneath::new(fd, AccessFs::from_read(abi)))?;
+                        ruleset = ruleset.add_rule(PathBeneath::new(fd, read_access))?;
                     }
                 }
 
                 for path in &rw_clone {
                     if let Ok(fd) = PathFd::new(path) {
-                        ruleset = ruleset.add_rule(PathBeneath::new(fd, AccessFs::from_all(abi)))?;
+                        ruleset = ruleset.add_rule(PathBeneath::new(fd, write_access))?;
+                    }
+                }
+
+                for program in exec_policy.iter().flat_map(ExecPolicy::programs) {
+                    if let Ok(fd) = PathFd::new(program) {
+                        ruleset = ruleset.add_rule(PathBeneath::new(fd, AccessFs::Execute))?;
                     }
                 }
 
-                ruleset.restrict_self()?;
-                Ok(())
+                Ok(ruleset.restrict_self()?.ruleset)
             })();
 
-            if result.is_err() {
-                // Fallback: apply NO_NEW_PRIVS at minimum (already inside pre_exec unsafe).
-                libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0);
+            match result {
+                // Execute is in ABI V1, so a partially enforced V2 ruleset still handles it.
+                Ok(RulesetStatus::FullyEnforced | RulesetStatus::PartiallyEnforced) => {}
+                // The exec allowlist lives in Landlock alone: never run without it.
+                _ if exec_policy.is_some() => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::PermissionDenied,
+                        "allowed_programs requires Landlock (ABI 1 or later), which is unavailable",
+                    ));
+                }
+                Ok(RulesetStatus::NotEnforced) => {}
+                Err(_) => {
+                    // Fallback: apply NO_NEW_PRIVS at minimum (already inside pre_exec unsafe).
+                    libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0);
+                }
+            }
+            match &syscall_filter {
+                Some(filter) => crate::seccomp::apply(filter),
+                None => Ok(()),
             }
-            Ok(())
         });
     }
 
@@ -1332,14 +1755,8 @@ pub fn run_sandboxed_command(
     // Read stdout/stderr in separate threads with panic handling
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
-    let stdout_handle = thread::spawn(move || {
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream_to_string(stdout)))
-            .unwrap_or_else(|_| String::new())
-    });
-    let stderr_handle = thread::spawn(move || {
-        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream_to_string(stderr)))
-            .unwrap_or_else(|_| String::new())
-    });
+    let stdout_task = io_pool::spawn(move || read_stream_to_string(stdout));
+    let stderr_task = io_pool::spawn(move || read_stream_to_string(stderr));
 
     // Wait with timeout
     let timeout_ms = if permissions.timeout_ms > 0 {
@@ -1362,14 +1779,21 @@ pub fn run_sandboxed_command(
     };
 
     // Collect output with timeout to prevent indefinite blocking
-    let stdout_content = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
+    let stdout_content = stdout_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
-    let stderr_content = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
+    let mut stderr_content = stderr_task.join_timeout(Duration::from_secs(5))
         .unwrap_or_else(|_| String::new());
 
-    let exit_code = status
-        .and_then(|s| s.code())
-        .unwrap_or(if timed_out { -1 } else { 1 });
+    // A seccomp kill has no exit code; report it shell-style (128 + signal).
+    let exit_code = match status {
+        Some(s) if s.signal() == Some(libc::SIGSYS) => {
+            stderr_content.push_str("Sandbox violation: blocked system call (seccomp)\n");
+            128 + libc::SIGSYS
+        }
+        _ => status
+            .and_then(|s| s.code())
+            .unwrap_or(if timed_out { -1 } else { 1 }),
+    };
 
     Ok(CommandResult {
         exit_code,
@@ -1388,6 +1812,10 @@ pub fn run_sandboxed_command(
     if permissions.sandbox_mode == SandboxMode::Disabled {
         return run_command_direct(command, working_dir, &permissions);
     }
+    #[cfg(unix)]
+    if permissions.backend == ExecutionBackend::Container {
+        return run_command_in_container(command, working_dir, &permissions);
+    }
     Err(OpenSkillError::UnsupportedPlatform(
         "Sandboxed command execution requires macOS (seatbelt) or Linux (Landlock); \
          set sandbox_mode=disabled when the host provides an outer sandbox"
@@ -1400,27 +1828,58 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
-    #[test]
-    fn test_find_wasm_module_none() {
-        let path = PathBuf::from("/nonexistent");
-        assert!(find_wasm_module(&path).is_none());
+    fn skill_at(root: &Path) -> Skill {
+        Skill {
+            id: "test".to_string(),
+            manifest: Default::default(),
+            instructions: String::new(),
+            root: root.to_path_buf(),
+            location: crate::registry::SkillLocation::Custom,
+        }
     }
 
     #[test]
-    fn test_find_native_script_none() {
-        let path = PathBuf::from("/nonexistent");
-        assert!(find_native_script(&path).is_none());
+    fn test_detect_execution_mode_none() {
+        let skill = skill_at(Path::new("/nonexistent"));
+        let mode = detect_execution_mode(&skill, None, &EntrypointConfig::default()).unwrap();
+        assert!(matches!(mode, ExecutionMode::InstructionOnly));
     }
 
     #[test]
-    fn test_find_native_script_detects_python() {
+    fn test_detect_execution_mode_python() {
         let temp = TempDir::new().unwrap();
         let skill_root = temp.path();
         let script_path = skill_root.join("script.py");
         std::fs::write(&script_path, "print('ok')").unwrap();
 
-        let found = find_native_script(&skill_root.to_path_buf());
-        assert_eq!(found.unwrap(), script_path);
+        let skill = skill_at(skill_root);
+        match detect_execution_mode(&skill, None, &EntrypointConfig::default()).unwrap() {
+            ExecutionMode::Native { script_path: found, script_type, args } => {
+                assert_eq!(found, script_path);
+                assert_eq!(script_type, ScriptType::Python);
+                assert!(args.is_empty());
+            }
+            other => panic!("expected native script, got {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn test_detect_execution_mode_python_wasm() {
+        let temp = TempDir::new().unwrap();
+        let skill_root = temp.path();
+        std::fs::write(skill_root.join("script.py"), "print('ok')").unwrap();
+        std::fs::create_dir_all(skill_root.join("wasm")).unwrap();
+        std::fs::write(skill_root.join(INTERPRETER_MODULE), b"").unwrap();
+
+        let skill = skill_at(skill_root);
+        match detect_execution_mode(&skill, None, &EntrypointConfig::default()).unwrap() {
+            ExecutionMode::Wasm { wasm_module, python: Some(python) } => {
+                assert_eq!(wasm_module, INTERPRETER_MODULE);
+                assert_eq!(python.argv(), vec!["python", "/skill/script.py"]);
+            }
+            other => panic!("expected python in wasm, got {:?}", other),
+        }
     }
 
     #[test]
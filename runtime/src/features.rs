@@ -0,0 +1,61 @@
+//! Optional subsystems compiled into this build.
+//!
+//! The runtime, its bindings and the `openskills` binary are built with
+//! different Cargo features (the Python and TypeScript bindings turn the
+//! defaults off, the C library builds only `wasm`, ...). [`features`]
+//! lets a host check what it got before relying on it, instead of finding
+//! out from a failing call.
+
+/// Every optional Cargo feature of `openskills-runtime`, and whether this
+/// build has it.
+const FEATURES: &[(&str, bool)] = &[
+    ("wasm", cfg!(feature = "wasm")),
+    ("remote-registry", cfg!(feature = "remote-registry")),
+    ("oci", cfg!(feature = "oci")),
+    ("watch", cfg!(feature = "watch")),
+    ("daemon", cfg!(feature = "daemon")),
+    ("cedar", cfg!(feature = "cedar")),
+    ("http-callback", cfg!(feature = "http-callback")),
+    ("otel", cfg!(feature = "otel")),
+    ("tokio", cfg!(feature = "tokio")),
+    ("build-tool", cfg!(feature = "build-tool")),
+    ("plugin-javy", cfg!(feature = "plugin-javy")),
+    ("plugin-quickjs", cfg!(feature = "plugin-quickjs")),
+    ("plugin-assemblyscript", cfg!(feature = "plugin-assemblyscript")),
+];
+
+/// Cargo features this build of the runtime was compiled with, e.g.
+/// `["wasm"]` for the defaults.
+///
+/// Subsystems that are always compiled in, such as the container backend
+/// and the audit log, are not listed; they may still need tools on the host
+/// (Docker or Podman for containers).
+pub fn features() -> Vec<&'static str> {
+    FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_every_cargo_feature() {
+        let manifest: toml::Table = include_str!("../Cargo.toml").parse().unwrap();
+        let mut declared: Vec<&str> = manifest["features"]
+            .as_table()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .filter(|name| *name != "default")
+            .collect();
+        let mut listed: Vec<&str> = FEATURES.iter().map(|(name, _)| *name).collect();
+        declared.sort_unstable();
+        listed.sort_unstable();
+        assert_eq!(listed, declared);
+        assert_eq!(features().contains(&"wasm"), cfg!(feature = "wasm"));
+    }
+}
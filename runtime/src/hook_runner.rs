@@ -33,6 +33,17 @@ pub enum HookEvent {
     },
 }
 
+impl HookEvent {
+    /// Event name as used in SKILL.md (`PreToolUse`, `PostToolUse`, `Stop`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::PreToolUse { .. } => "PreToolUse",
+            HookEvent::PostToolUse { .. } => "PostToolUse",
+            HookEvent::Stop { .. } => "Stop",
+        }
+    }
+}
+
 /// Hook runner that executes matching hooks for skill lifecycle events.
 pub struct HookRunner {
     hooks: HooksConfig,
@@ -9,25 +9,31 @@
 //! 2. Tool in allow_overrides → APPROVED
 //! 3. trust_skill_allowed_tools AND tool in skill's allowed-tools → APPROVED
 //! 4. fallback = allow → APPROVED, deny → DENIED, prompt → delegate to callback
+//!
+//! A skill's `sandbox:` section (extra paths, network, processes) is decided
+//! separately by `skill_sandbox`, which defaults to deny.
+//!
+//! A policy loaded from a file (see [`crate::policy`]) can also carry rules
+//! per skill, location and trust level; the runtime narrows the policy to
+//! the rules matching a skill before resolving its tools.
 
+use crate::errors::OpenSkillError;
+use crate::policy::{self, Scope, ToolRule, TrustLevel};
+use crate::registry::SkillLocation;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::path::Path;
 
 /// Fallback behavior for tools not covered by overrides or skill pre-approvals.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Fallback {
     Allow,
+    #[default]
     Deny,
     Prompt,
 }
 
-impl Default for Fallback {
-    fn default() -> Self {
-        Fallback::Deny
-    }
-}
-
 /// Result of the host policy resolution for a single tool.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ToolDecision {
@@ -78,8 +84,15 @@ impl Default for PermissionsConfig {
 pub struct HostPolicy {
     pub trust_skill_allowed_tools: bool,
     pub fallback: Fallback,
+    /// Whether skills may widen their native sandbox via `sandbox:` in
+    /// SKILL.md: allow, deny (default) or prompt through the callback.
+    pub skill_sandbox: Fallback,
     deny_overrides: HashSet<String>,
     allow_overrides: HashSet<String>,
+    /// Only these tools may be granted (`None`: no limit).
+    tool_limit: Option<HashSet<String>>,
+    /// Rules from a policy file, applied by [`Self::for_skill`].
+    rules: Vec<(Scope, ToolRule)>,
 }
 
 impl Default for HostPolicy {
@@ -87,8 +100,11 @@ impl Default for HostPolicy {
         Self {
             trust_skill_allowed_tools: true,
             fallback: Fallback::Deny,
+            skill_sandbox: Fallback::Deny,
             deny_overrides: HashSet::new(),
             allow_overrides: HashSet::new(),
+            tool_limit: None,
+            rules: Vec::new(),
         }
     }
 }
@@ -99,22 +115,88 @@ impl HostPolicy {
         Self {
             trust_skill_allowed_tools: config.trust_skill_allowed_tools,
             fallback: config.fallback,
+            skill_sandbox: Fallback::Deny,
             deny_overrides: config.deny.into_iter().collect(),
             allow_overrides: config.allow.into_iter().collect(),
+            tool_limit: None,
+            rules: Vec::new(),
         }
     }
 
+    /// Parse a TOML policy file: the [`PermissionsConfig`] keys plus
+    /// `[skill.<id>]`, `[location.<location>]` and `[trust.<level>]` rules
+    /// (see [`crate::policy`]).
+    pub fn from_toml(content: &str) -> Result<Self, OpenSkillError> {
+        policy::parse(content)
+    }
+
+    /// [`Self::from_toml`] on the contents of `path`.
+    pub fn from_file(path: &Path) -> Result<Self, OpenSkillError> {
+        policy::load(path)
+    }
+
+    /// The policy in `~/.config/openskills/policy.toml`, or `None` if there
+    /// is no such file.
+    pub fn load_default() -> Result<Option<Self>, OpenSkillError> {
+        match policy::default_policy_path().filter(|path| path.is_file()) {
+            Some(path) => Self::from_file(&path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn with_rules(mut self, rules: Vec<(Scope, ToolRule)>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Whether the policy has per-skill, location or trust rules.
+    pub fn has_scoped_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// Whether a rule depends on the skill's trust level.
+    pub(crate) fn has_trust_rules(&self) -> bool {
+        self.rules.iter().any(|(scope, _)| matches!(scope, Scope::Trust(_)))
+    }
+
+    /// This policy with the rules matching one skill folded into its
+    /// overrides.
+    pub fn for_skill(&self, skill_id: &str, location: &SkillLocation, trust: TrustLevel) -> Self {
+        let narrowing = policy::narrow(
+            self.rules
+                .iter()
+                .filter(|(scope, _)| scope.matches(skill_id, location, trust))
+                .map(|(_, rule)| rule),
+        );
+        let mut narrowed = self.clone();
+        narrowed.rules.clear();
+        narrowed.deny_overrides.extend(narrowing.deny);
+        narrowed.allow_overrides.extend(narrowing.allow);
+        narrowed.tool_limit = match (narrowed.tool_limit.take(), narrowing.limit) {
+            (Some(a), Some(b)) => Some(a.intersection(&b).cloned().collect()),
+            (a, b) => a.or(b),
+        };
+        narrowed
+    }
+
+    /// Whether `tool` is denied regardless of what the skill declares: on
+    /// the deny list, or outside the tools the policy allows.
+    pub fn is_denied(&self, tool: &str) -> bool {
+        self.deny_overrides.contains(tool)
+            || self.tool_limit.as_ref().is_some_and(|limit| !limit.contains(tool))
+    }
+
     /// Resolve whether a tool is approved, denied, or needs prompting.
     ///
     /// Implements the resolution algorithm from docs/permissions.md:
-    /// 1. deny_overrides → DENIED
+    /// 1. deny_overrides (or outside the tool limit) → DENIED
     /// 2. allow_overrides → APPROVED
     /// 3. trust + tool in skill's allowed-tools → APPROVED
     ///    (empty allowed-tools = nothing pre-approved, per Claude spec)
     /// 4. fallback
     pub fn resolve_tool(&self, tool: &str, skill_allowed_tools: &[String]) -> ToolDecision {
         // Step 1: deny overrides always win
-        if self.deny_overrides.contains(tool) {
+        if self.is_denied(tool) {
             return ToolDecision::Denied;
         }
 
@@ -137,6 +219,12 @@ impl HostPolicy {
         }
     }
 
+    /// Set how `sandbox:` sections in SKILL.md are treated.
+    pub fn with_skill_sandbox(mut self, decision: Fallback) -> Self {
+        self.skill_sandbox = decision;
+        self
+    }
+
     /// Get the set of allow overrides (for resolve_skill_permissions in lib.rs).
     pub fn allow_overrides(&self) -> &HashSet<String> {
         &self.allow_overrides
@@ -223,6 +311,14 @@ mod tests {
         assert_eq!(p.resolve_tool("Bash", &tools(&["Read", "Bash"])), ToolDecision::Denied);
     }
 
+    #[test]
+    fn skill_sandbox_defaults_to_deny() {
+        // Even a fully permissive tool policy does not approve sandbox sections.
+        let p = policy(true, Fallback::Allow, vec![], vec![]);
+        assert_eq!(p.skill_sandbox, Fallback::Deny);
+        assert_eq!(p.with_skill_sandbox(Fallback::Prompt).skill_sandbox, Fallback::Prompt);
+    }
+
     #[test]
     fn default_policy() {
         let p = HostPolicy::default();
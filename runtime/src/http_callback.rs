@@ -0,0 +1,235 @@
+//! Ask-before-act through a remote approver.
+//!
+//! Headless deployments have no terminal to prompt on. An
+//! [`HttpPermissionCallback`] POSTs each [`PermissionRequest`] as JSON to an
+//! approval service (a Slack bot, a ticketing hook, ...) and waits for its
+//! answer:
+//!
+//! ```json
+//! {"decision": "allow_once"}
+//! {"decision": "allow_for", "seconds": 3600}
+//! ```
+//!
+//! `decision` is `allow_once`, `allow_always`, `allow_for` (with `seconds`),
+//! `allow_in_workspace` (once, writing only inside the workspace) or `deny`. The service may hold the request open while a person decides.
+//! No answer within the timeout, an error status or an unreadable reply
+//! denies the request.
+
+use crate::errors::OpenSkillError;
+use crate::permission_callback::{PermissionCallback, PermissionRequest, PermissionResponse};
+use serde::Deserialize;
+use std::time::Duration;
+use url::Url;
+
+/// How long to wait for the approver by default.
+pub const DEFAULT_APPROVAL_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Sends permission requests to an approval service over HTTP; see the
+/// module docs for the protocol.
+#[derive(Debug)]
+pub struct HttpPermissionCallback {
+    url: Url,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Decision {
+    AllowOnce,
+    AllowAlways,
+    AllowFor,
+    AllowInWorkspace,
+    Deny,
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    decision: Decision,
+    #[serde(default)]
+    seconds: Option<u64>,
+}
+
+impl HttpPermissionCallback {
+    /// Post requests to `url` (http or https).
+    pub fn new(url: &str) -> Result<Self, OpenSkillError> {
+        let url = Url::parse(url).map_err(|e| {
+            OpenSkillError::InvalidConfig(format!("invalid approval URL '{}': {}", url, e))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(OpenSkillError::InvalidConfig(format!(
+                "approval URL must be http or https, got '{}'",
+                url
+            )));
+        }
+        Ok(Self {
+            url,
+            headers: Vec::new(),
+            timeout: DEFAULT_APPROVAL_TIMEOUT,
+        })
+    }
+
+    /// Send `name: value` with every request.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Authenticate with `Authorization: Bearer <token>`.
+    pub fn with_bearer_token(self, token: &str) -> Self {
+        self.with_header("Authorization", format!("Bearer {}", token))
+    }
+
+    /// Deny requests the approver has not answered within `timeout`
+    /// (default [`DEFAULT_APPROVAL_TIMEOUT`]).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn ask(&self, request: &PermissionRequest) -> Result<PermissionResponse, String> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut call = agent
+            .post(self.url.as_str())
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            call = call.set(name, value);
+        }
+        let body = serde_json::to_string(request).map_err(|e| e.to_string())?;
+        let reply = call
+            .send_string(&body)
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())?;
+        let reply: Reply =
+            serde_json::from_str(&reply).map_err(|e| format!("unreadable reply: {}", e))?;
+        Ok(match reply.decision {
+            Decision::AllowOnce => PermissionResponse::AllowOnce,
+            Decision::AllowAlways => PermissionResponse::AllowAlways,
+            Decision::AllowFor => match reply.seconds {
+                Some(seconds) => PermissionResponse::AllowFor(Duration::from_secs(seconds)),
+                None => return Err("allow_for reply without seconds".to_string()),
+            },
+            Decision::AllowInWorkspace => PermissionResponse::AllowInWorkspace,
+            Decision::Deny => PermissionResponse::Deny,
+        })
+    }
+}
+
+impl PermissionCallback for HttpPermissionCallback {
+    fn request_permission(
+        &self,
+        request: &PermissionRequest,
+    ) -> Result<PermissionResponse, OpenSkillError> {
+        match self.ask(request) {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                eprintln!(
+                    "Warning: approval request for '{}' ({}) to {} failed, denying: {}",
+                    request.skill_id, request.tool, self.url, e
+                );
+                Ok(PermissionResponse::Deny)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permission_callback::RiskLevel;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Answer one request with `reply` and hand back the request it got.
+    fn approver(reply: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/approve", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                reply.len(),
+                reply
+            )
+            .unwrap();
+            head + &String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn request() -> PermissionRequest {
+        PermissionRequest {
+            skill_id: "deploy".to_string(),
+            tool: "Bash".to_string(),
+            description: "Bash: ./deploy.sh".to_string(),
+            risk_level: RiskLevel::High,
+            context: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_approver_decides_requests() {
+        let (url, server) = approver(r#"{"decision": "allow_for", "seconds": 60}"#);
+        let callback = HttpPermissionCallback::new(&url)
+            .unwrap()
+            .with_bearer_token("secret");
+        let response = callback.request_permission(&request()).unwrap();
+        assert!(matches!(response, PermissionResponse::AllowFor(d) if d == Duration::from_secs(60)));
+        let sent = server.join().unwrap();
+        assert!(sent.starts_with("POST /approve "));
+        assert!(sent.contains("Bearer secret"));
+        assert!(sent.contains(r#""skill_id":"deploy""#));
+
+        let (url, server) = approver(r#"{"decision": "deny"}"#);
+        let callback = HttpPermissionCallback::new(&url).unwrap();
+        assert!(matches!(
+            callback.request_permission(&request()).unwrap(),
+            PermissionResponse::Deny
+        ));
+        server.join().unwrap();
+
+        assert!(HttpPermissionCallback::new("file:///tmp/approve").is_err());
+    }
+
+    #[test]
+    fn test_unanswered_requests_are_denied() {
+        let (url, server) = approver(r#"{"decision": "maybe"}"#);
+        let callback = HttpPermissionCallback::new(&url).unwrap();
+        assert!(matches!(
+            callback.request_permission(&request()).unwrap(),
+            PermissionResponse::Deny
+        ));
+        server.join().unwrap();
+
+        // Nothing answers within the timeout
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let callback = HttpPermissionCallback::new(&url)
+            .unwrap()
+            .with_timeout(Duration::from_millis(200));
+        assert!(matches!(
+            callback.request_permission(&request()).unwrap(),
+            PermissionResponse::Deny
+        ));
+    }
+}
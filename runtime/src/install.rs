@@ -0,0 +1,447 @@
+//! Installing skills from git repositories.
+//!
+//! [`SkillInstaller`] clones a repository (or one subdirectory of it),
+//! checks the result with [`validate_skill_path`], and moves it into a
+//! skills directory, by default the personal `~/.claude/skills/`. Where the
+//! skill came from is recorded next to its `SKILL.md` in
+//! [`PROVENANCE_FILE`], which is what `update` re-installs from and what
+//! `uninstall` requires before deleting anything: skills that were put in
+//! place by hand are never touched.
+//!
+//! Clones and swaps are staged in a hidden directory inside the skills
+//! directory, so a failed install or update leaves the installed skill as it
+//! was.
+
+use crate::errors::OpenSkillError;
+use crate::validator::{validate_name, validate_skill_path};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Provenance record written into each installed skill.
+pub const PROVENANCE_FILE: &str = ".openskills-install.json";
+
+/// Prefix of the staging directories inside the skills directory.
+const STAGING_PREFIX: &str = ".openskills-install-";
+
+/// Where to install a skill from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallSource {
+    /// Git URL (or local repository path) to clone.
+    pub url: String,
+    /// Directory of the skill inside the repository; the repository root
+    /// if `None`.
+    pub subdir: Option<String>,
+    /// Branch or tag to check out; the default branch if `None`.
+    pub reference: Option<String>,
+    /// Skill ID to install as; defaults to the name of `subdir`, or of the
+    /// repository.
+    pub name: Option<String>,
+}
+
+impl InstallSource {
+    /// Install the repository at `url` as a skill.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Skill ID the source installs as.
+    pub fn skill_id(&self) -> Result<String, OpenSkillError> {
+        let name = match (&self.name, &self.subdir) {
+            (Some(name), _) => name.clone(),
+            (None, Some(subdir)) => last_segment(subdir).to_string(),
+            (None, None) => last_segment(&self.url)
+                .trim_end_matches(".git")
+                .to_string(),
+        };
+        validate_name(&name).map_err(|e| {
+            OpenSkillError::InstallError(format!("cannot install as '{}': {}", name, e))
+        })?;
+        Ok(name)
+    }
+}
+
+/// Where an installed skill came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallProvenance {
+    /// Git URL it was cloned from.
+    pub source_url: String,
+    /// Directory of the skill inside the repository.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Branch or tag that was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Commit SHA that was installed.
+    pub commit: String,
+    /// Install time in milliseconds since the Unix epoch.
+    pub installed_at_ms: u64,
+}
+
+impl InstallProvenance {
+    /// The source to re-install from on update.
+    pub fn source(&self, skill_id: &str) -> InstallSource {
+        InstallSource {
+            url: self.source_url.clone(),
+            subdir: self.subdir.clone(),
+            reference: self.reference.clone(),
+            name: Some(skill_id.to_string()),
+        }
+    }
+}
+
+/// A skill installed by [`SkillInstaller`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledSkill {
+    /// Skill ID (its directory name).
+    pub id: String,
+    /// Directory the skill was installed to.
+    pub path: PathBuf,
+    /// Where it came from.
+    pub provenance: InstallProvenance,
+    /// Validation warnings for the installed skill.
+    pub warnings: Vec<String>,
+}
+
+/// Installs skills into one skills directory.
+#[derive(Debug, Clone)]
+pub struct SkillInstaller {
+    dir: PathBuf,
+}
+
+impl SkillInstaller {
+    /// Install into `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Personal skills directory, `~/.claude/skills/`.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".claude").join("skills"))
+    }
+
+    /// Skills directory installed into.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Clone `source`, validate it and install it. Fails if a skill with
+    /// the same ID is already installed.
+    pub fn install(&self, source: &InstallSource) -> Result<InstalledSkill, OpenSkillError> {
+        let id = source.skill_id()?;
+        let dest = self.dir.join(&id);
+        if dest.exists() {
+            return Err(OpenSkillError::InstallError(format!(
+                "'{}' is already installed at {}",
+                id,
+                dest.display()
+            )));
+        }
+        let staging = self.staging()?;
+        let installed = stage(&staging, source, &id)?;
+        fs::rename(&installed.path, &dest)?;
+        Ok(InstalledSkill {
+            path: dest,
+            ..installed
+        })
+    }
+
+    /// Re-install `skill_id` from the source recorded when it was
+    /// installed, replacing the installed copy.
+    pub fn update(&self, skill_id: &str) -> Result<InstalledSkill, OpenSkillError> {
+        let provenance = self.provenance(skill_id)?;
+        let dest = self.dir.join(skill_id);
+        let staging = self.staging()?;
+        let installed = stage(&staging, &provenance.source(skill_id), skill_id)?;
+        let previous = staging.path.join("previous");
+        fs::rename(&dest, &previous)?;
+        if let Err(err) = fs::rename(&installed.path, &dest) {
+            let _ = fs::rename(&previous, &dest);
+            return Err(err.into());
+        }
+        Ok(InstalledSkill {
+            path: dest,
+            ..installed
+        })
+    }
+
+    /// Remove `skill_id`. Only skills installed by this installer (those
+    /// with a [`PROVENANCE_FILE`]) are removed.
+    pub fn uninstall(&self, skill_id: &str) -> Result<InstallProvenance, OpenSkillError> {
+        let provenance = self.provenance(skill_id)?;
+        fs::remove_dir_all(self.dir.join(skill_id))?;
+        Ok(provenance)
+    }
+
+    /// Provenance of an installed skill.
+    pub fn provenance(&self, skill_id: &str) -> Result<InstallProvenance, OpenSkillError> {
+        validate_name(skill_id)?;
+        let dir = self.dir.join(skill_id);
+        if !dir.is_dir() {
+            return Err(OpenSkillError::SkillNotFound(skill_id.to_string()));
+        }
+        let content = fs::read_to_string(dir.join(PROVENANCE_FILE)).map_err(|_| {
+            OpenSkillError::InstallError(format!(
+                "'{}' was not installed by openskills (no {})",
+                skill_id, PROVENANCE_FILE
+            ))
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    fn staging(&self) -> Result<Staging, OpenSkillError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self
+            .dir
+            .join(format!("{}{:08x}", STAGING_PREFIX, rand::random::<u32>()));
+        fs::create_dir(&path)?;
+        Ok(Staging { path })
+    }
+}
+
+/// Staging directory, removed when dropped.
+struct Staging {
+    path: PathBuf,
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Clone `source` into `staging` and prepare it as skill `id`; the
+/// returned skill's `path` is the staged directory.
+fn stage(
+    staging: &Staging,
+    source: &InstallSource,
+    id: &str,
+) -> Result<InstalledSkill, OpenSkillError> {
+    let checkout = staging.path.join("checkout");
+    let mut clone = Command::new("git");
+    clone.args(["clone", "--quiet", "--depth", "1"]);
+    if let Some(reference) = &source.reference {
+        clone.args(["--branch", reference]);
+    }
+    clone.arg("--").arg(&source.url).arg(&checkout);
+    git(&mut clone)?;
+    let commit = git(Command::new("git")
+        .arg("-C")
+        .arg(&checkout)
+        .args(["rev-parse", "HEAD"]))?;
+
+    let skill_src = match &source.subdir {
+        Some(subdir) => checkout.join(safe_subdir(subdir)?),
+        None => checkout.clone(),
+    };
+    if !skill_src.is_dir() {
+        return Err(OpenSkillError::InstallError(format!(
+            "'{}' not found in {}",
+            source.subdir.as_deref().unwrap_or("."),
+            source.url
+        )));
+    }
+    let staged = staging.path.join(id);
+    fs::rename(&skill_src, &staged)?;
+    let git_dir = staged.join(".git");
+    if git_dir.exists() {
+        fs::remove_dir_all(git_dir)?;
+    }
+
+    let validation = validate_skill_path(&staged);
+    if !validation.errors.is_empty() {
+        return Err(OpenSkillError::InstallError(format!(
+            "{} is not a valid skill: {}",
+            source.url,
+            validation.errors.join("; ")
+        )));
+    }
+
+    let provenance = InstallProvenance {
+        source_url: source.url.clone(),
+        subdir: source.subdir.clone(),
+        reference: source.reference.clone(),
+        commit,
+        installed_at_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+    };
+    fs::write(
+        staged.join(PROVENANCE_FILE),
+        serde_json::to_string_pretty(&provenance)?,
+    )?;
+    Ok(InstalledSkill {
+        id: id.to_string(),
+        path: staged,
+        provenance,
+        warnings: validation.warnings,
+    })
+}
+
+/// Run a git command, returning its trimmed stdout.
+fn git(command: &mut Command) -> Result<String, OpenSkillError> {
+    let output = command
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| OpenSkillError::InstallError(format!("failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(OpenSkillError::InstallError(format!(
+            "git failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// A relative path that stays inside the repository.
+fn safe_subdir(subdir: &str) -> Result<&Path, OpenSkillError> {
+    let path = Path::new(subdir);
+    if path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(path)
+    } else {
+        Err(OpenSkillError::InstallError(format!(
+            "subdirectory must be a relative path inside the repository: {}",
+            subdir
+        )))
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\', ':'])
+        .next()
+        .unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A git repository with a skill at `skills/<name>`, committed once.
+    fn skill_repo(name: &str, description: &str) -> TempDir {
+        let repo = TempDir::new().unwrap();
+        let skill = repo.path().join("skills").join(name);
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(
+            skill.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: {description}\n---\n# Instructions\nDo it.\n"),
+        )
+        .unwrap();
+        commit(repo.path(), "initial");
+        repo
+    }
+
+    fn commit(repo: &Path, message: &str) {
+        for args in [
+            vec!["init", "--quiet"],
+            vec!["add", "-A"],
+            vec!["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "--quiet", "-m", message],
+        ] {
+            let status = Command::new("git").arg("-C").arg(repo).args(args).status().unwrap();
+            assert!(status.success());
+        }
+    }
+
+    fn source(repo: &TempDir, name: &str) -> InstallSource {
+        InstallSource {
+            url: format!("file://{}", repo.path().display()),
+            subdir: Some(format!("skills/{name}")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_install_update_uninstall() {
+        let repo = skill_repo("greeter", "Greets people.");
+        let skills = TempDir::new().unwrap();
+        let installer = SkillInstaller::new(skills.path());
+
+        let installed = installer.install(&source(&repo, "greeter")).unwrap();
+        assert_eq!(installed.id, "greeter");
+        assert_eq!(installed.path, skills.path().join("greeter"));
+        assert!(installed.path.join("SKILL.md").is_file());
+        assert_eq!(installed.provenance.commit.len(), 40);
+        assert_eq!(installed.provenance.subdir.as_deref(), Some("skills/greeter"));
+        assert_eq!(installer.provenance("greeter").unwrap(), installed.provenance);
+        assert!(matches!(
+            installer.install(&source(&repo, "greeter")),
+            Err(OpenSkillError::InstallError(_))
+        ));
+
+        fs::write(
+            repo.path().join("skills/greeter/SKILL.md"),
+            "---\nname: greeter\ndescription: Greets people warmly.\n---\n# Instructions\nDo it.\n",
+        )
+        .unwrap();
+        commit(repo.path(), "update");
+        let updated = installer.update("greeter").unwrap();
+        assert_ne!(updated.provenance.commit, installed.provenance.commit);
+        let skill_md = fs::read_to_string(updated.path.join("SKILL.md")).unwrap();
+        assert!(skill_md.contains("warmly"));
+
+        installer.uninstall("greeter").unwrap();
+        assert!(!skills.path().join("greeter").exists());
+        // No staging directories are left behind.
+        assert_eq!(fs::read_dir(skills.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_sources() {
+        let repo = skill_repo("greeter", "Greets people.");
+        let skills = TempDir::new().unwrap();
+        let installer = SkillInstaller::new(skills.path());
+
+        let missing = InstallSource {
+            subdir: Some("skills/other".to_string()),
+            ..source(&repo, "greeter")
+        };
+        assert!(installer.install(&missing).is_err());
+        let escaping = InstallSource {
+            subdir: Some("../greeter".to_string()),
+            name: Some("greeter".to_string()),
+            ..source(&repo, "greeter")
+        };
+        assert!(installer.install(&escaping).is_err());
+        // The repository root has no SKILL.md.
+        let root = InstallSource {
+            name: Some("whole-repo".to_string()),
+            ..InstallSource::new(format!("file://{}", repo.path().display()))
+        };
+        assert!(installer.install(&root).is_err());
+        assert_eq!(fs::read_dir(skills.path()).unwrap().count(), 0);
+
+        // Hand-placed skills are never uninstalled.
+        fs::create_dir_all(skills.path().join("manual")).unwrap();
+        assert!(matches!(
+            installer.uninstall("manual"),
+            Err(OpenSkillError::InstallError(_))
+        ));
+        assert!(skills.path().join("manual").exists());
+    }
+
+    #[test]
+    fn test_skill_id_from_source() {
+        let id = |url: &str, subdir: Option<&str>| {
+            InstallSource {
+                url: url.to_string(),
+                subdir: subdir.map(str::to_string),
+                ..Default::default()
+            }
+            .skill_id()
+        };
+        assert_eq!(id("https://github.com/acme/pdf-tools.git", None).unwrap(), "pdf-tools");
+        assert_eq!(id("git@github.com:acme/pdf-tools", None).unwrap(), "pdf-tools");
+        assert_eq!(id("https://github.com/acme/skills", Some("skills/pdf/")).unwrap(), "pdf");
+        assert!(id("https://github.com/acme/My_Skills", None).is_err());
+    }
+}
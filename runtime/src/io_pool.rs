@@ -0,0 +1,211 @@
+//! Shared worker threads for blocking child-process I/O.
+//!
+//! Every execution feeds stdin and drains stdout/stderr concurrently with
+//! waiting on the child. Spawning fresh threads for that (plus a watcher
+//! thread per join timeout) costs five threads per run, which adds up on
+//! hosts running hundreds of short commands a minute. Jobs are instead run
+//! on a process-wide pool: idle workers are reused and exit after
+//! [`KEEP_ALIVE`] without work.
+//!
+//! The pool is not bounded. A reader blocks for as long as its child keeps
+//! the pipe open, so queueing readers behind a fixed number of workers could
+//! stall a child on a full pipe; when no worker is idle a new one is started.
+
+use crate::errors::OpenSkillError;
+use std::collections::VecDeque;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+/// How long an idle worker waits for a job before exiting.
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Pool {
+    state: Mutex<PoolState>,
+    work: Condvar,
+    keep_alive: Duration,
+}
+
+#[derive(Default)]
+struct PoolState {
+    queue: VecDeque<Job>,
+    /// Workers waiting for a job.
+    idle: usize,
+    /// Workers started over the pool's lifetime.
+    started: usize,
+}
+
+impl Pool {
+    fn new(keep_alive: Duration) -> Self {
+        Self {
+            state: Mutex::new(PoolState::default()),
+            work: Condvar::new(),
+            keep_alive,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn submit(&'static self, job: Job) {
+        let mut state = self.lock();
+        state.queue.push_back(job);
+        if state.queue.len() <= state.idle {
+            self.work.notify_one();
+            return;
+        }
+        state.started += 1;
+        drop(state);
+        let spawned = thread::Builder::new()
+            .name("openskills-io".to_string())
+            .spawn(move || self.work_loop());
+        if spawned.is_err() {
+            // Out of threads: run the job on whichever worker frees up first.
+            self.lock().started -= 1;
+        }
+    }
+
+    fn work_loop(&self) {
+        let mut state = self.lock();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                job();
+                state = self.lock();
+                continue;
+            }
+            state.idle += 1;
+            let (guard, wait) = self
+                .work
+                .wait_timeout(state, self.keep_alive)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            state = guard;
+            state.idle -= 1;
+            if wait.timed_out() && state.queue.is_empty() {
+                return;
+            }
+        }
+    }
+}
+
+fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+    POOL.get_or_init(|| Pool::new(KEEP_ALIVE))
+}
+
+/// Result of a job submitted with [`spawn`].
+pub(crate) struct Task<T> {
+    result: mpsc::Receiver<thread::Result<T>>,
+}
+
+impl<T> Task<T> {
+    /// Wait up to `timeout` for the job to finish.
+    ///
+    /// Returns [`OpenSkillError::Timeout`] if it is still running and
+    /// `NativeExecutionError` if it panicked.
+    pub(crate) fn join_timeout(self, timeout: Duration) -> Result<T, OpenSkillError> {
+        match self.result.recv_timeout(timeout) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) | Err(mpsc::RecvTimeoutError::Disconnected) => Err(
+                OpenSkillError::NativeExecutionError("Thread panicked during execution".to_string()),
+            ),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(OpenSkillError::Timeout),
+        }
+    }
+}
+
+/// Run `job` on the shared I/O pool.
+pub(crate) fn spawn<T, F>(job: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    spawn_on(pool(), job)
+}
+
+fn spawn_on<T, F>(pool: &'static Pool, job: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    pool.submit(Box::new(move || {
+        let _ = tx.send(catch_unwind(AssertUnwindSafe(job)));
+    }));
+    Task { result: rx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+    use std::sync::Arc;
+
+    fn test_pool(keep_alive: Duration) -> &'static Pool {
+        Box::leak(Box::new(Pool::new(keep_alive)))
+    }
+
+    /// Wait until `count` workers are parked (a worker reports its result
+    /// before it goes idle).
+    fn wait_idle(pool: &Pool, count: usize) {
+        while pool.lock().idle < count {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_reuses_idle_workers() {
+        let pool = test_pool(Duration::from_secs(5));
+        for i in 0..20 {
+            let task = spawn_on(pool, move || i * 2);
+            assert_eq!(task.join_timeout(Duration::from_secs(5)).unwrap(), i * 2);
+            wait_idle(pool, 1);
+        }
+        assert_eq!(pool.lock().started, 1);
+    }
+
+    #[test]
+    fn test_grows_for_blocking_jobs() {
+        let pool = test_pool(Duration::from_secs(5));
+        let barrier = Arc::new(Barrier::new(3));
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let barrier = barrier.clone();
+                spawn_on(pool, move || {
+                    barrier.wait();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.join_timeout(Duration::from_secs(5)).unwrap();
+        }
+        assert_eq!(pool.lock().started, 3);
+    }
+
+    #[test]
+    fn test_reports_panics_and_timeouts() {
+        let pool = test_pool(Duration::from_millis(50));
+        let panicked = spawn_on(pool, || -> u8 { panic!("reader failed") });
+        assert!(matches!(
+            panicked.join_timeout(Duration::from_secs(5)),
+            Err(OpenSkillError::NativeExecutionError(_))
+        ));
+
+        let slow = spawn_on(pool, || thread::sleep(Duration::from_millis(500)));
+        assert!(matches!(
+            slow.join_timeout(Duration::from_millis(10)),
+            Err(OpenSkillError::Timeout)
+        ));
+
+        // Jobs still run after a panic, and idle workers exit after keep-alive.
+        let task = spawn_on(pool, || 7);
+        assert_eq!(task.join_timeout(Duration::from_secs(5)).unwrap(), 7);
+        thread::sleep(Duration::from_millis(800));
+        assert_eq!(pool.lock().idle, 0);
+    }
+}
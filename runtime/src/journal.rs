@@ -0,0 +1,302 @@
+//! Crash-safe execution journal.
+//!
+//! With `OpenSkillRuntime::with_execution_journal`, every execution writes a
+//! small entry (skill, target, start time, and the sandboxed process once it
+//! is spawned) to the journal directory, and removes it when it finishes. If
+//! the runtime crashes or is killed, the entries stay behind.
+//! `OpenSkillRuntime::recover_aborted_executions` finds the entries of
+//! runtimes that are gone, kills the sandboxed processes they left running,
+//! removes their temporary Seatbelt profiles and cgroups, and audits each
+//! execution as `failed:aborted`.
+
+use crate::errors::OpenSkillError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Entries written by this process, for unique file names.
+static NEXT_ENTRY: AtomicU64 = AtomicU64::new(1);
+
+/// What a journal records about a running execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub skill_id: String,
+    pub version: String,
+    /// `auto`, or the script or WASM module path.
+    pub target: String,
+    pub input_hash: String,
+    /// Start time in milliseconds since the Unix epoch.
+    pub start_time_ms: u64,
+    /// Session ID of the runtime running it.
+    pub session_id: String,
+    /// Process ID of the runtime.
+    pub runtime_pid: u32,
+    /// Start time of the runtime process, to tell it from a later process
+    /// that reuses its ID.
+    pub runtime_started: Option<String>,
+    /// Sandboxed process, once spawned (native scripts only).
+    pub pid: Option<u32>,
+    /// Start time of the sandboxed process.
+    pub pid_started: Option<String>,
+}
+
+/// Directory holding one entry file per running execution.
+#[derive(Debug, Clone)]
+pub struct ExecutionJournal {
+    dir: PathBuf,
+}
+
+impl ExecutionJournal {
+    /// Journal in `dir`, created on first use.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `~/.cache/openskills/journal`.
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("openskills")
+            .join("journal")
+    }
+
+    /// Journal directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Entries currently in the journal, from this and other runtimes.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entry_files()
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect()
+    }
+
+    /// Record an execution of `skill_id` starting now; the entry is removed
+    /// when the last clone of the returned record is dropped.
+    pub(crate) fn begin(
+        &self,
+        skill_id: &str,
+        version: &str,
+        target: String,
+        input_hash: &str,
+        session_id: &str,
+    ) -> Result<JournalRecord, OpenSkillError> {
+        fs::create_dir_all(&self.dir)?;
+        let runtime_pid = std::process::id();
+        let path = self.dir.join(format!(
+            "{}-{}.json",
+            runtime_pid,
+            NEXT_ENTRY.fetch_add(1, Ordering::Relaxed)
+        ));
+        let entry = JournalEntry {
+            skill_id: skill_id.to_string(),
+            version: version.to_string(),
+            target,
+            input_hash: input_hash.to_string(),
+            start_time_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            session_id: session_id.to_string(),
+            runtime_pid,
+            runtime_started: own_start().clone(),
+            pid: None,
+            pid_started: None,
+        };
+        write_entry(&path, &entry)?;
+        Ok(JournalRecord(Arc::new(Mutex::new(RecordFile { path, entry }))))
+    }
+
+    /// Remove the entries of runtimes that are no longer running: kill the
+    /// sandboxed processes they left behind and clean up their temporary
+    /// files. Returns the removed entries.
+    pub(crate) fn recover(&self) -> Vec<JournalEntry> {
+        let orphans: Vec<(PathBuf, JournalEntry)> = self
+            .entry_files()
+            .into_iter()
+            .filter(|(_, entry)| !runtime_alive(entry))
+            .collect();
+        let mut runtimes = BTreeSet::new();
+        for (path, entry) in &orphans {
+            kill_orphan(entry);
+            runtimes.insert(entry.runtime_pid);
+            let _ = fs::remove_file(path);
+        }
+        for runtime_pid in runtimes {
+            remove_leftovers(runtime_pid);
+        }
+        orphans.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    fn entry_files(&self) -> Vec<(PathBuf, JournalEntry)> {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<_> = dir
+            .flatten()
+            .map(|file| file.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let entry = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+                Some((path, entry))
+            })
+            .collect();
+        entries.sort_by_key(|(_, entry): &(PathBuf, JournalEntry)| entry.start_time_ms);
+        entries
+    }
+}
+
+/// A running execution's journal entry.
+#[derive(Debug, Clone)]
+pub struct JournalRecord(Arc<Mutex<RecordFile>>);
+
+impl JournalRecord {
+    /// Record the sandboxed process the execution spawned.
+    pub(crate) fn spawned(&self, pid: u32) {
+        let Ok(mut record) = self.0.lock() else {
+            return;
+        };
+        record.entry.pid = Some(pid);
+        record.entry.pid_started = process_started(pid);
+        let _ = write_entry(&record.path, &record.entry);
+    }
+}
+
+#[derive(Debug)]
+struct RecordFile {
+    path: PathBuf,
+    entry: JournalEntry,
+}
+
+impl Drop for RecordFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Replace `path` atomically, so a crash never leaves half an entry.
+fn write_entry(path: &Path, entry: &JournalEntry) -> Result<(), OpenSkillError> {
+    let staging = path.with_extension("json.tmp");
+    fs::write(&staging, serde_json::to_vec(entry)?)?;
+    fs::rename(&staging, path)?;
+    Ok(())
+}
+
+fn own_start() -> &'static Option<String> {
+    static START: OnceLock<Option<String>> = OnceLock::new();
+    START.get_or_init(|| process_started(std::process::id()))
+}
+
+/// Whether the runtime that wrote `entry` is still running. Entries of this
+/// process are running executions.
+fn runtime_alive(entry: &JournalEntry) -> bool {
+    entry.runtime_pid == std::process::id()
+        || (entry.runtime_started.is_some()
+            && process_started(entry.runtime_pid) == entry.runtime_started)
+}
+
+/// Kill the entry's sandboxed process (and its process group, if it leads
+/// one), unless its ID now belongs to another process.
+fn kill_orphan(entry: &JournalEntry) {
+    let (Some(pid), Some(started)) = (entry.pid, &entry.pid_started) else {
+        return;
+    };
+    if process_started(pid).as_ref() != Some(started) {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        let pid = pid as libc::pid_t;
+        // SAFETY: plain signal delivery to a process we verified above.
+        unsafe {
+            if libc::getpgid(pid) == pid {
+                libc::killpg(pid, libc::SIGKILL);
+            }
+            libc::kill(pid, libc::SIGKILL);
+        }
+    }
+}
+
+/// Remove the temporary Seatbelt profiles and cgroups a crashed runtime
+/// created; their names carry its process ID.
+fn remove_leftovers(runtime_pid: u32) {
+    crate::temp_artifacts::remove_artifacts_of(runtime_pid);
+    #[cfg(target_os = "linux")]
+    crate::resource_limits::remove_orphaned_cgroups(runtime_pid);
+}
+
+/// When process `pid` started, or `None` if it does not exist.
+#[cfg(target_os = "linux")]
+fn process_started(pid: u32) -> Option<String> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // Fields after the command name, which may contain spaces; the start
+    // time is field 22 overall.
+    let fields = stat.get(stat.rfind(')')? + 2..)?;
+    fields.split_whitespace().nth(19).map(str::to_string)
+}
+
+/// When process `pid` started, or `None` if it does not exist.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_started(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "lstart=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !started.is_empty()).then_some(started)
+}
+
+#[cfg(not(unix))]
+fn process_started(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_is_removed_when_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = ExecutionJournal::new(dir.path());
+        let record = journal
+            .begin("skill", "1.0.0", "auto".to_string(), "hash", "session-1")
+            .unwrap();
+        record.spawned(std::process::id());
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pid, Some(std::process::id()));
+
+        // Entries of a running runtime are not orphans.
+        assert!(journal.recover().is_empty());
+        drop(record);
+        assert!(journal.entries().is_empty());
+    }
+
+    #[test]
+    fn test_recovers_entries_of_exited_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = ExecutionJournal::new(dir.path());
+        let entry = JournalEntry {
+            skill_id: "skill".to_string(),
+            version: "1.0.0".to_string(),
+            target: "auto".to_string(),
+            input_hash: String::new(),
+            start_time_ms: 1,
+            session_id: "session-1".to_string(),
+            runtime_pid: u32::MAX,
+            runtime_started: Some("0".to_string()),
+            pid: Some(u32::MAX),
+            pid_started: Some("0".to_string()),
+        };
+        write_entry(&dir.path().join("stale.json"), &entry).unwrap();
+
+        assert_eq!(journal.recover(), vec![entry]);
+        assert!(journal.entries().is_empty());
+    }
+}
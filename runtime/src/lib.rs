@@ -29,39 +29,100 @@
 //! ```
 
 mod actions;
+mod active_executions;
+mod artifacts;
 mod audit;
+mod audit_log;
+mod binary_output;
+mod blob_store;
+mod builtin_skills;
+mod cache;
+mod capability_mapping;
+mod capability_needed;
 #[cfg(feature = "build-tool")]
 mod build;
+#[cfg(feature = "cedar")]
+mod cedar;
 mod context;
+#[cfg(all(unix, feature = "daemon"))]
+mod daemon;
 mod deps_check;
+mod dry_run;
+mod entrypoint;
+#[cfg(any(unix, feature = "wasm"))]
+mod egress;
 mod errors;
+mod exec_policy;
+mod events;
+mod execution_history;
+mod execution_queue;
 mod executor;
+mod features;
 mod hook_runner;
+mod install;
+mod io_pool;
+mod journal;
 mod host_policy;
+#[cfg(feature = "http-callback")]
+mod http_callback;
+mod lockfile;
 mod manifest;
+mod metrics;
+#[cfg(feature = "wasm")]
+mod module_cache;
 mod skill_session;
 mod native_runner;
+#[cfg(feature = "oci")]
+mod oci;
+mod output_converters;
+mod output_pipeline;
+mod output_schema;
+mod output_spill;
 mod permission_callback;
+mod pending_execution;
 mod permissions;
+mod policy;
+mod python_wasm;
+mod redact;
 mod registry;
+#[cfg(feature = "remote-registry")]
+mod remote_registry;
+#[cfg(target_os = "linux")]
+mod resource_limits;
+mod retry;
+mod sandbox_backend;
 mod sandbox_mode;
+mod sandbox_overrides;
+mod sandbox_violation;
+mod script_session;
+#[cfg(target_os = "linux")]
+mod seccomp;
+mod secret_redaction;
+mod search;
+mod signing;
+mod skill_card;
 mod skill_parser;
+mod skill_version;
+mod telemetry;
+mod temp_artifacts;
 mod validator;
+mod wasm_engine;
 #[cfg(feature = "wasm")]
 mod wasm_runner;
+mod watch;
+mod workspace_import;
+mod workspace_snapshot;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Generate a unique session ID for workspace isolation.
+///
+/// A UUIDv7 is time-ordered like the millisecond timestamps used before, but
+/// its random bits keep runtimes started in the same millisecond apart.
 fn generate_session_id() -> String {
-    use std::time::SystemTime;
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis())
-        .unwrap_or(0);
-    format!("session-{}", timestamp)
+    format!("session-{}", uuid::Uuid::now_v7())
 }
 
 /// Get the default workspace root directory.
@@ -72,55 +133,147 @@ fn get_default_workspace_root() -> PathBuf {
         .join("workspace")
 }
 
-use audit::{AuditRecord, AuditSink, NoopAuditSink};
+use audit::{AuditRecord, NoopAuditSink};
+use blob_store::BlobStore;
+use execution_history::{ExecutionHistory, ExecutionSample, Outcome};
+use metrics::SandboxMetrics;
+use wasm_engine::WasmEngineHandle;
+use workspace_snapshot::WorkspaceSnapshot;
 use errors::OpenSkillError;
 use executor::{
-    execute_skill, read_skill_file, run_skill_target, list_skill_files,
+    read_skill_file, run_skill_target, list_skill_files, precompile_skill,
     ExecutionOptions as ExecOpts,
 };
-use permission_callback::PermissionManager;
-use registry::{Skill, SkillRegistry};
+use permission_callback::{PermissionDecision, PermissionManager};
+use registry::{Skill, SkillMetadata, SkillRegistry};
 use serde_json::Value;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
 use validator::validate_skill;
 
 // Re-exports for public API
-pub use audit::{AuditRecord as RuntimeAuditRecord, ExecutionStatus as RuntimeExecutionStatus};
+pub use audit::{
+    AuditRecord as RuntimeAuditRecord, AuditSink, CapabilityDowngrade, DowngradeReason,
+    ExecutionStatus as RuntimeExecutionStatus, HostCall, NetworkRequest,
+};
 #[cfg(feature = "build-tool")]
 pub use build::{build_skill, BuildConfig, list_build_plugins};
 pub use errors::OpenSkillError as RuntimeError;
 pub use deps_check::MissingDependencies;
+pub use dry_run::{ExecutionPlan, SandboxProfile};
+pub use entrypoint::{EntrypointConfig, EntrypointPrecedence, EntrypointResolution};
 pub use manifest::{
-    constraints, ActionInputSchema, ActionTarget, HooksConfig, SkillAction, SkillManifest,
-    SkillRequires, WasmConfig,
+    constraints, ActionInputSchema, ActionTarget, HooksConfig, SandboxConfig, SkillAction,
+    SkillEntrypoint, SkillExample, SkillManifest, SkillRequires, ToolPattern, WasmConfig,
+    WorkspaceFile,
 };
+pub use skill_card::{SkillCard, SkillCardExample, MAX_README_BYTES};
 pub use context::{ContextOutput, ExecutionContext, OutputType};
 pub use skill_session::SkillExecutionSession;
+pub use script_session::{ScriptSession, ScriptStdin};
 pub use permission_callback::{
-    CliPermissionCallback, DenyAllCallback, PermissionAuditEntry, PermissionCallback,
-    PermissionRequest, PermissionResponse, RiskLevel, get_risk_level, is_risky_tool,
+    CliPermissionCallback, DenyAllCallback, GrantScope, PermissionAuditEntry, PermissionCallback,
+    PermissionGrant, PermissionProfile, PermissionRequest, PermissionResponse, RiskLevel, default_grants_path,
+    get_risk_level, is_risky_tool,
 };
+#[cfg(feature = "tokio")]
+pub use permission_callback::{AsyncPermissionCallback, PermissionFuture};
 pub use skill_parser::parse_skill_md;
+pub use retry::{Backoff, RetryOn, RetryPolicy};
+pub use search::{Embedder, SearchOptions, SkillMatch, DEFAULT_SEARCH_MIN_SCORE};
+pub use skill_version::{parse_version, SkillReference, UNVERSIONED};
 pub use actions::{
     build_script_args, find_action_by_capability, find_action_by_id, list_skill_actions,
     validate_action_input, SkillActionDescriptor,
 };
-pub use registry::{SkillDescriptor, SkillLocation};
+pub use registry::{SkillCandidate, SkillConflict, SkillDescriptor, SkillKind, SkillLocation, SkillTarget, TargetKind, TargetSandbox};
 pub use validator::{analyze_skill_tokens, validate_skill_path, TokenAnalysis, ValidationResult, ValidationStats};
 
 // Re-export execution target types for public API
 pub use executor::{ExecutionTarget, TargetExecutionOptions};
 pub use native_runner::NativeRunnerConfig;
+pub use sandbox_backend::{ContainerConfig, ExecutionBackend};
 pub use sandbox_mode::SandboxMode;
+pub use sandbox_overrides::SandboxOverrides;
+pub use sandbox_violation::SandboxViolation;
+pub use wasm_engine::{WasmEngineConfig, DEFAULT_WASM_MAX_INSTANCES};
+pub use workspace_import::{WorkspaceImport, DEFAULT_MAX_IMPORT_BYTES};
+pub use workspace_snapshot::Artifact;
+pub use output_converters::OutputConverter;
+pub use metrics::{PhaseMetrics, SetupPhase, SetupTimings};
+pub use execution_history::LimitSuggestion;
+pub use features::features;
+pub use pending_execution::{ExecutionStep, NextAttempt, PendingExecution};
+pub use builtin_skills::BuiltinSkill;
+pub use audit_log::{read_audit_log, skill_usage, skill_usage_csv, AuditLog, AuditLogEntry, SkillUsage};
+pub use execution_queue::{ExecutionQueue, JobStatus, QueueConfig, QueuedJob, RateLimit};
+pub use journal::{ExecutionJournal, JournalEntry, JournalRecord};
+pub use temp_artifacts::cleanup_orphaned_artifacts;
+pub use install::{InstallProvenance, InstallSource, InstalledSkill, SkillInstaller, PROVENANCE_FILE};
+pub use lockfile::{content_hash, LockedSkill, Lockfile, LockfileDrift, LOCKFILE_NAME};
+#[cfg(feature = "remote-registry")]
+pub use remote_registry::{
+    package_skill, RegistryEntry, RegistryIndex, RegistryReference, RemoteRegistry, ResolvedSkill,
+    REGISTRY_PREFIX,
+};
+#[cfg(feature = "oci")]
+pub use oci::{
+    OciClient, OciCredentials, OciPushed, OciReference, OCI_PREFIX, SKILL_ARTIFACT_TYPE,
+    SKILL_LAYER_MEDIA_TYPE,
+};
+pub use signing::{
+    skill_digest, verify_skill, SignatureMode, SignatureStatus, TrustedKey,
+    MINISIGN_SIGNATURE_FILE, SIGSTORE_SIGNATURE_FILE,
+};
+pub use events::{
+    execution_event_channel, ExecutionEvent, ExecutionEventReceiver, ExecutionEventSender,
+    RuntimeEvent, RuntimeEventHandler,
+};
+pub use watch::{SkillChange, SkillChangeKind};
+#[cfg(feature = "cedar")]
+pub use cedar::CedarPolicyCallback;
+#[cfg(feature = "http-callback")]
+pub use http_callback::{HttpPermissionCallback, DEFAULT_APPROVAL_TIMEOUT};
+#[cfg(feature = "watch")]
+pub use watch::SkillWatcher;
+#[cfg(feature = "otel")]
+pub use telemetry::{init_otlp_tracing, OtlpTracing};
+#[cfg(all(unix, feature = "daemon"))]
+pub use daemon::{
+    default_daemon_socket, DaemonAuditRecord, DaemonClient, DaemonExecuteOptions,
+    DaemonExecutionResult, DaemonLoadedSkill, DaemonOutputChunk, DaemonOutputFile,
+    DaemonOutputScope, DaemonRequires, DaemonRetryOptions, DaemonServer, DaemonSession,
+    DaemonStatus, DaemonTargetOptions, DAEMON_SOCKET_ENV, MAX_OUTPUT_CHUNK,
+};
+use watch::SkillSnapshot;
+use search::SkillSearch;
+use events::{EventSink, ExecutionEvents};
+pub use active_executions::{ActiveExecution, CancellationHandle, ExecutionMonitor};
+use active_executions::RunningExecution;
 
 // Re-export sandboxed command execution API
 pub use executor::{CommandPermissions, CommandResult, run_sandboxed_command};
+pub use exec_policy::command_programs;
 
 // Re-export hook execution API
 pub use hook_runner::{HookEvent, HookRunner};
 
 // Re-export host policy types for programmatic configuration
 pub use host_policy::{Fallback, HostPolicy, PermissionsConfig, ToolDecision};
+pub use policy::{default_policy_path, TrustLevel};
+pub use capability_mapping::{CapabilityMapping, ToolCapabilities};
+pub use capability_needed::CapabilityNeeded;
+
+// Re-export workspace artifact retention
+pub use artifacts::{ArtifactInfo, GcReport, RetentionPolicy, ARTIFACTS_DIR};
+
+// Re-export cache management (module, blob, venv, node_modules and registry caches)
+pub use cache::{CacheEntry, CacheGcReport, CacheKind, CacheLimits, CacheManager};
+
+// Re-export output redaction and post-processing
+pub use binary_output::BinaryOutput;
+pub use output_pipeline::{OutputPipeline, OutputProcessor, ProcessedOutput};
+pub use redact::OutputRedactor;
+pub use secret_redaction::SecretRedactor;
 
 /// Runtime configuration for skill discovery.
 #[derive(Debug, Clone)]
@@ -132,7 +285,7 @@ pub struct RuntimeConfig {
     /// Project root for relative path resolution.
     pub project_root: Option<PathBuf>,
     /// Workspace directory for skill I/O operations.
-    /// If not set, defaults to ~/.cache/openskills/workspace/{session_id}/
+    /// If not set, defaults to ~/.cache/openskills/workspace/{session_id}-{hash}/
     pub workspace_dir: Option<PathBuf>,
     /// Optional native runner config (interpreter overrides, Python site visibility).
     pub native_runner_config: Option<NativeRunnerConfig>,
@@ -143,10 +296,36 @@ pub struct RuntimeConfig {
 pub struct ExecutionOptions {
     /// Override timeout in milliseconds.
     pub timeout_ms: Option<u64>,
-    /// Override memory limit in MB.
+    /// Override memory limit in MB. Also caps native scripts on Linux;
+    /// exceeding it fails with `ExecutionStatus::Failed("oom")`.
     pub memory_mb: Option<u64>,
+    /// CPU cap for native scripts on Linux, as a fraction of one CPU
+    /// (e.g. `0.5`). Needs a delegated cgroup v2; ignored otherwise.
+    pub cpu_quota: Option<f64>,
+    /// Fuel budget for WASM skills: roughly one unit per instruction.
+    /// Running out fails with `ExecutionStatus::Failed("fuel exhausted")`;
+    /// consumption is reported in `AuditRecord::fuel_consumed`.
+    pub max_fuel: Option<u64>,
     /// Input data for WASM execution.
     pub input: Option<Value>,
+    /// Extra read/write paths, network access and env vars for this run
+    /// only, e.g. read access to a document the user picked.
+    pub sandbox_overrides: SandboxOverrides,
+    /// Tools granted to the sandbox for this run, replacing the host-policy
+    /// resolution (see `OpenSkillRuntime::resolve_effective_tools`). Tools on
+    /// the host policy's deny list are still dropped.
+    pub effective_tools: Option<Vec<String>>,
+    /// Resolve permissions, entry point, interpreter and sandbox without
+    /// running anything. The result has `ExecutionStatus::DryRun` and the
+    /// [`ExecutionPlan`] as JSON in `output`.
+    pub dry_run: bool,
+    /// Run the skill again when it times out or fails transiently. Each
+    /// attempt is audited separately; the last one is returned.
+    pub retry: Option<RetryPolicy>,
+    /// W3C trace context (`00-<trace-id>-<span-id>-<flags>`) of the caller's
+    /// span. Stored in the audit record and passed to the skill's processes
+    /// as `TRACEPARENT`.
+    pub traceparent: Option<String>,
 }
 
 /// Execution result returned to callers.
@@ -160,6 +339,162 @@ pub struct ExecutionResult {
     pub stderr: String,
     /// Audit record for this execution.
     pub audit: AuditRecord,
+    /// Sandbox setup latency, by phase (empty when nothing was executed).
+    pub setup_timings: SetupTimings,
+    /// Workspace files the execution created or modified (see
+    /// [`OpenSkillRuntime::with_artifact_collection`]).
+    pub artifacts: Vec<Artifact>,
+    /// For executions that did not succeed: tools to ask the user for
+    /// before running the skill again, from what the sandbox denied.
+    pub capabilities_needed: Vec<CapabilityNeeded>,
+}
+
+/// An execution whose skill and options are resolved, waiting for its
+/// sandboxed run to finish.
+struct PreparedExecution {
+    skill: Skill,
+    fork_context: Option<ExecutionContext>,
+    input_hash: String,
+    workspace_dir: Option<PathBuf>,
+    timeout_ms: Option<u64>,
+    memory_mb: Option<u64>,
+    start: Instant,
+    start_epoch: u64,
+    running: RunningExecution,
+    /// Receives this execution's `Finished` event, if the caller streams.
+    events: ExecutionEvents,
+    /// Tools stripped while resolving permissions.
+    downgrades: Vec<CapabilityDowngrade>,
+    /// Which attempt this is (see `ExecutionOptions::retry`).
+    attempt: u32,
+    /// Journal entry, removed once the execution is audited.
+    journal: Option<JournalRecord>,
+    /// Workspace before the run, to collect the files it produced.
+    snapshot: Option<WorkspaceSnapshot>,
+    /// Run output converters on the produced files; off for converters.
+    convert_outputs: bool,
+    /// See `ExecutionOptions::traceparent`.
+    traceparent: Option<String>,
+    /// `openskills.execute` span of this attempt, closed once audited.
+    span: tracing::Span,
+}
+
+/// Why `policy` denied `tool`.
+fn policy_denial(policy: &HostPolicy, tool: &str) -> CapabilityDowngrade {
+    let detail = if policy.deny_overrides().contains(tool) {
+        "on the host policy's deny list"
+    } else if policy.is_denied(tool) {
+        "not among the tools the host policy allows this skill"
+    } else {
+        "not pre-approved, and the host policy falls back to deny"
+    };
+    CapabilityDowngrade::new(tool, DowngradeReason::PolicyDenied, detail)
+}
+
+/// What an execution will run and where it writes, for the context of the
+/// permission requests resolving its tools.
+struct RequestScope {
+    skill_root: PathBuf,
+    command: Option<String>,
+    workspace: Option<PathBuf>,
+}
+
+/// Whether a permission callback confined an execution's writes to its
+/// workspace (see [`PermissionResponse::AllowInWorkspace`]).
+fn writes_workspace_only(downgrades: &[CapabilityDowngrade]) -> bool {
+    downgrades
+        .iter()
+        .any(|downgrade| downgrade.reason == DowngradeReason::WorkspaceOnly)
+}
+
+/// Whether `path` (absolute, or relative to the workspace) stays inside
+/// `workspace`, judged by its components.
+fn path_in_workspace(path: &str, workspace: &Path) -> bool {
+    let path = workspace.join(path);
+    let mut inside = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::ParentDir => {
+                if !inside.pop() {
+                    return false;
+                }
+            }
+            std::path::Component::CurDir => {}
+            component => inside.push(component),
+        }
+    }
+    inside.starts_with(workspace)
+}
+
+/// Reject a `traceparent` that is not `00-<32 hex>-<16 hex>-<2 hex>` with
+/// non-zero trace and span ids (W3C Trace Context, version 00).
+fn check_traceparent(traceparent: &str) -> Result<(), OpenSkillError> {
+    let parts: Vec<&str> = traceparent.split('-').collect();
+    let is_hex = |part: &str, len: usize| {
+        part.len() == len
+            && part
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+    };
+    let valid = matches!(parts.as_slice(), [version, trace_id, span_id, flags]
+        if *version == "00"
+            && is_hex(trace_id, 32)
+            && trace_id.bytes().any(|b| b != b'0')
+            && is_hex(span_id, 16)
+            && span_id.bytes().any(|b| b != b'0')
+            && is_hex(flags, 2));
+    if valid {
+        Ok(())
+    } else {
+        Err(OpenSkillError::InvalidConfig(format!(
+            "traceparent must look like 00-<trace-id>-<span-id>-<flags>: {traceparent}"
+        )))
+    }
+}
+
+/// Backoff before the next attempt, if `policy` retries `result` and
+/// attempts remain.
+fn next_retry(
+    policy: Option<&RetryPolicy>,
+    attempt: u32,
+    result: &Result<ExecutionResult, OpenSkillError>,
+) -> Option<Duration> {
+    let policy = policy?;
+    let outcome = result
+        .as_ref()
+        .map(|result| result.audit.exit_status.clone());
+    (attempt < policy.max_attempts && policy.retries(&outcome))
+        .then(|| policy.backoff.delay(attempt))
+}
+
+/// Run `job` on tokio's blocking pool. The execution is cancelled if the
+/// awaiting future is dropped before the job finishes.
+#[cfg(feature = "tokio")]
+async fn run_blocking<F>(
+    cancellation: CancellationHandle,
+    job: F,
+) -> Result<executor::ExecutionArtifacts, OpenSkillError>
+where
+    F: FnOnce() -> Result<executor::ExecutionArtifacts, OpenSkillError> + Send + 'static,
+{
+    struct CancelOnDrop(Option<CancellationHandle>);
+
+    impl Drop for CancelOnDrop {
+        fn drop(&mut self) {
+            if let Some(cancellation) = self.0.take() {
+                cancellation.cancel();
+            }
+        }
+    }
+
+    let mut guard = CancelOnDrop(Some(cancellation));
+    let result = tokio::task::spawn_blocking(job).await;
+    guard.0 = None;
+    result.unwrap_or_else(|_| {
+        Err(OpenSkillError::NativeExecutionError(
+            "Execution task panicked".to_string(),
+        ))
+    })
 }
 
 /// Loaded skill with full content (for activation).
@@ -204,8 +539,14 @@ pub struct OpenSkillRuntime {
     registry: SkillRegistry,
     audit_sink: Box<dyn AuditSink + Send + Sync>,
     permission_manager: PermissionManager,
+    /// Receives runtime events (see `with_event_handler`).
+    events: EventSink,
     /// Host policy controlling which tools skills are granted.
     host_policy: HostPolicy,
+    /// Host policies replacing `host_policy` for skills from a project root.
+    project_root_policies: HashMap<PathBuf, HostPolicy>,
+    /// What each granted tool allows inside the sandbox.
+    capability_mapping: Arc<CapabilityMapping>,
     custom_directories: Vec<PathBuf>,
     use_standard_locations: bool,
     /// Workspace directory for skill I/O operations.
@@ -214,6 +555,72 @@ pub struct OpenSkillRuntime {
     session_id: String,
     /// Optional native runner config (interpreter overrides, Python site visibility).
     native_runner_config: Option<NativeRunnerConfig>,
+    /// Candidate patterns and precedence for entry-point auto-detection.
+    entrypoint_config: EntrypointConfig,
+    /// Rewrite host paths (workspace, skill root, home) in returned outputs.
+    redact_output: bool,
+    /// Redact credentials in output before it is audited or returned.
+    secret_redactor: Option<SecretRedactor>,
+    /// Strip ANSI escapes and collapse carriage returns in captured output.
+    tty_cleanup: bool,
+    /// Default output post-processing pipeline.
+    output_pipeline: OutputPipeline,
+    /// Per-skill pipeline overrides (replace the default for that skill).
+    skill_output_pipelines: HashMap<String, OutputPipeline>,
+    /// Write binary stdout to a workspace file instead of only describing it.
+    divert_binary_output: bool,
+    /// Report the workspace files each execution produced.
+    collect_artifacts: bool,
+    /// Converters applied to every skill's produced files.
+    output_converters: Vec<OutputConverter>,
+    /// Default retention for runtime-created workspace artifacts.
+    artifact_retention: RetentionPolicy,
+    /// Per-skill retention overrides.
+    skill_artifact_retention: HashMap<String, RetentionPolicy>,
+    /// Remove other session workspaces idle for longer than this.
+    session_retention: Option<Duration>,
+    /// Remove conversation workspaces unused for longer than this.
+    conversation_retention: Option<Duration>,
+    /// Size limit for a single `import_into_workspace`.
+    max_import_bytes: u64,
+    /// Files imported into the workspace, in order.
+    import_log: Mutex<Vec<WorkspaceImport>>,
+    /// Content-addressed store that imports are deduplicated through.
+    blob_store: Option<BlobStore>,
+    /// Size caps for the caches under `~/.cache/openskills/`.
+    cache_limits: CacheLimits,
+    /// Wasmtime engine shared by WASM executions, created on first use.
+    wasm_engine: Arc<WasmEngineHandle>,
+    /// Sandbox setup latency aggregated per backend and phase.
+    sandbox_metrics: SandboxMetrics,
+    /// Recent executions per skill, for limit suggestions.
+    execution_history: ExecutionHistory,
+    /// Run skills with their suggested limits when the caller sets none.
+    auto_limits: bool,
+    /// Executions currently running.
+    executions: ExecutionMonitor,
+    /// Records running executions on disk, to recover them after a crash.
+    journal: Option<ExecutionJournal>,
+    /// Skills [`Self::execute_many`] runs at once; `None` = available cores.
+    max_parallelism: Option<usize>,
+    /// Keys skills must be signed with; empty disables signature checks.
+    trusted_keys: Vec<TrustedKey>,
+    /// Refuse or only report skills whose signature does not verify.
+    signature_mode: SignatureMode,
+    /// Remote registry that `registry:` references resolve through.
+    #[cfg(feature = "remote-registry")]
+    remote_registry: Option<RemoteRegistry>,
+    /// Registry skills loaded so far, reloaded from the cache on rediscovery.
+    #[cfg(feature = "remote-registry")]
+    registry_skills: Vec<ResolvedSkill>,
+    /// Register the runtime's first-party skills on discovery.
+    builtin_skills: bool,
+    /// Skills embedded by the host binary, registered on discovery.
+    embedded_skills: Vec<BuiltinSkill>,
+    /// Loaded skills as of the last discovery, for [`Self::reload_skills`].
+    skill_snapshot: SkillSnapshot,
+    /// Optional embedder and cached skill embeddings for `search_skills`.
+    search: SkillSearch,
 }
 
 impl OpenSkillRuntime {
@@ -223,17 +630,59 @@ impl OpenSkillRuntime {
     /// - `~/.claude/skills/` (personal skills)
     /// - `.claude/skills/` (project skills)
     /// - Nested `.claude/skills/` directories (monorepo support)
+    ///
+    /// The first runtime created in a process also removes temporary
+    /// sandbox files left by runtimes that died mid-run
+    /// (see [`cleanup_orphaned_artifacts`]).
     pub fn new() -> Self {
+        temp_artifacts::cleanup_once();
         Self {
             registry: SkillRegistry::new(),
             audit_sink: Box::new(NoopAuditSink {}),
             permission_manager: PermissionManager::new(),
+            events: EventSink::default(),
             host_policy: HostPolicy::default(),
+            project_root_policies: HashMap::new(),
+            capability_mapping: Arc::default(),
             custom_directories: Vec::new(),
             use_standard_locations: true,
             workspace_dir: None,
             session_id: generate_session_id(),
             native_runner_config: None,
+            entrypoint_config: EntrypointConfig::default(),
+            redact_output: false,
+            secret_redactor: None,
+            tty_cleanup: true,
+            output_pipeline: OutputPipeline::default(),
+            skill_output_pipelines: HashMap::new(),
+            divert_binary_output: true,
+            collect_artifacts: true,
+            output_converters: Vec::new(),
+            artifact_retention: RetentionPolicy::default(),
+            skill_artifact_retention: HashMap::new(),
+            session_retention: None,
+            conversation_retention: None,
+            max_import_bytes: DEFAULT_MAX_IMPORT_BYTES,
+            import_log: Mutex::new(Vec::new()),
+            blob_store: None,
+            cache_limits: CacheLimits::default(),
+            wasm_engine: Arc::default(),
+            sandbox_metrics: SandboxMetrics::default(),
+            execution_history: ExecutionHistory::default(),
+            auto_limits: false,
+            executions: ExecutionMonitor::default(),
+            journal: None,
+            max_parallelism: None,
+            trusted_keys: Vec::new(),
+            signature_mode: SignatureMode::default(),
+            #[cfg(feature = "remote-registry")]
+            remote_registry: None,
+            #[cfg(feature = "remote-registry")]
+            registry_skills: Vec::new(),
+            builtin_skills: false,
+            embedded_skills: Vec::new(),
+            skill_snapshot: SkillSnapshot::default(),
+            search: SkillSearch::default(),
         }
     }
 
@@ -245,14 +694,11 @@ impl OpenSkillRuntime {
         }
         Self {
             registry,
-            audit_sink: Box::new(NoopAuditSink {}),
-            permission_manager: PermissionManager::new(),
-            host_policy: HostPolicy::default(),
             custom_directories: config.custom_directories,
             use_standard_locations: config.use_standard_locations,
             workspace_dir: config.workspace_dir,
-            session_id: generate_session_id(),
             native_runner_config: config.native_runner_config,
+            ..Self::new()
         }
     }
 
@@ -263,14 +709,7 @@ impl OpenSkillRuntime {
         let root_ref = root.as_ref();
         Self {
             registry: SkillRegistry::new().with_project_root(root_ref),
-            audit_sink: Box::new(NoopAuditSink {}),
-            permission_manager: PermissionManager::new(),
-            host_policy: HostPolicy::default(),
-            custom_directories: Vec::new(),
-            use_standard_locations: true,
-            workspace_dir: None,
-            session_id: generate_session_id(),
-            native_runner_config: None,
+            ..Self::new()
         }
     }
 
@@ -285,16 +724,12 @@ impl OpenSkillRuntime {
         let mut registry = SkillRegistry::new();
         let _ = registry.scan_explicit(&dir);
         Self {
+            skill_snapshot: SkillSnapshot::capture(registry.all_versions()),
             registry,
-            audit_sink: Box::new(NoopAuditSink {}),
-            permission_manager: PermissionManager::new(),
-            host_policy: HostPolicy::default(),
             // Keep the root so `discover_skills()` can rescan after `SkillRegistry::clear()`.
             custom_directories: vec![dir],
             use_standard_locations: false,
-            workspace_dir: None,
-            session_id: generate_session_id(),
-            native_runner_config: None,
+            ..Self::new()
         }
     }
 
@@ -317,6 +752,67 @@ impl OpenSkillRuntime {
         self
     }
 
+    /// Add a custom skill directory for later [`Self::discover_skills`] runs.
+    pub fn add_custom_directory<P: AsRef<Path>>(&mut self, dir: P) {
+        self.custom_directories.push(dir.as_ref().to_path_buf());
+    }
+
+    /// Register the runtime's first-party skills (`code-review`,
+    /// `explaining-code`) on every [`Self::discover_skills`] run, with no
+    /// files to install. Discovered skills with the same ID and version
+    /// replace them.
+    pub fn with_builtin_skills(mut self, enabled: bool) -> Self {
+        self.builtin_skills = enabled;
+        self
+    }
+
+    /// Register skills embedded in the host binary with [`builtin_skill!`]
+    /// like the runtime's own (see [`Self::with_builtin_skills`]).
+    pub fn with_embedded_skills(mut self, skills: &[BuiltinSkill]) -> Self {
+        self.embedded_skills.extend_from_slice(skills);
+        self
+    }
+
+    /// Also discover project and nested skills under `root`, e.g. for an
+    /// agent serving several repositories. Skills from later roots override
+    /// earlier ones if IDs conflict; [`SkillDescriptor::project_root`] tells
+    /// which root supplied a skill.
+    pub fn with_additional_project_root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.registry.add_project_root(root);
+        self
+    }
+
+    /// Mutating version of `with_additional_project_root()`; takes effect on
+    /// the next [`Self::discover_skills`].
+    pub fn add_project_root<P: AsRef<Path>>(&mut self, root: P) {
+        self.registry.add_project_root(root);
+    }
+
+    /// Every project root standard discovery scans, the primary one first.
+    pub fn project_roots(&self) -> Vec<PathBuf> {
+        self.registry.project_roots()
+    }
+
+    /// Use `policy` instead of the host policy for skills supplied by the
+    /// project root `root` (see [`Self::with_additional_project_root`]).
+    pub fn with_project_root_policy<P: AsRef<Path>>(mut self, root: P, policy: HostPolicy) -> Self {
+        self.set_project_root_policy(root, Some(policy));
+        self
+    }
+
+    /// Set (or clear, with `None`) the host policy for a project root.
+    pub fn set_project_root_policy<P: AsRef<Path>>(&mut self, root: P, policy: Option<HostPolicy>) {
+        let root = root.as_ref().to_path_buf();
+        match policy {
+            Some(policy) => {
+                self.project_root_policies.insert(root, policy);
+            }
+            None => {
+                self.project_root_policies.remove(&root);
+            }
+        }
+    }
+
     /// Enable or disable discovery from standard locations.
     ///
     /// Standard locations are:
@@ -328,12 +824,43 @@ impl OpenSkillRuntime {
         self
     }
 
+    /// Enable or disable standard locations for later [`Self::discover_skills`] runs.
+    pub fn set_standard_locations(&mut self, enable: bool) {
+        self.use_standard_locations = enable;
+    }
+
     /// Set a custom audit sink.
     pub fn with_audit_sink(mut self, sink: Box<dyn AuditSink + Send + Sync>) -> Self {
         self.audit_sink = sink;
         self
     }
 
+    /// Replace the audit sink.
+    pub fn set_audit_sink(&mut self, sink: Box<dyn AuditSink + Send + Sync>) {
+        self.audit_sink = sink;
+    }
+
+    /// Journal running executions in `dir`, so that a runtime started after
+    /// a crash can clean up after them with
+    /// [`Self::recover_aborted_executions`].
+    pub fn with_execution_journal(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.journal = Some(ExecutionJournal::new(dir));
+        self
+    }
+
+    /// Journal running executions in `dir`, or stop journaling with `None`.
+    pub fn set_execution_journal(&mut self, dir: Option<PathBuf>) {
+        self.journal = dir.map(ExecutionJournal::new);
+    }
+
+    /// Receive typed events as the runtime works: discovery, activation,
+    /// execution start and finish, permission prompts and hook runs.
+    pub fn with_event_handler(mut self, handler: Box<dyn RuntimeEventHandler>) -> Self {
+        self.events = EventSink::new(handler);
+        self.permission_manager.set_events(self.events.clone());
+        self
+    }
+
     /// Enable interactive permission system with a callback.
     ///
     /// The callback will be invoked when skills attempt to use risky tools
@@ -349,7 +876,9 @@ impl OpenSkillRuntime {
     ///     .with_permission_callback(Arc::new(CliPermissionCallback));
     /// ```
     pub fn with_permission_callback(mut self, callback: Arc<dyn PermissionCallback>) -> Self {
-        self.permission_manager = PermissionManager::with_callback(callback);
+        self.permission_manager = PermissionManager::with_callback(callback)
+            .with_grants_of(&self.permission_manager)
+            .with_events(self.events.clone());
         self
     }
 
@@ -359,8 +888,15 @@ impl OpenSkillRuntime {
     /// This means: nothing is pre-approved via skill declarations, and fallback denies.
     /// Useful for testing or high-security environments.
     pub fn with_strict_permissions(mut self) -> Self {
+        self.set_strict_permissions();
+        self
+    }
+
+    /// Switch to strict permissions mode (see [`Self::with_strict_permissions`]).
+    pub fn set_strict_permissions(&mut self) {
         use permission_callback::DenyAllCallback;
-        self.permission_manager = PermissionManager::with_callback(Arc::new(DenyAllCallback));
+        self.permission_manager = PermissionManager::with_callback(Arc::new(DenyAllCallback))
+            .with_events(self.events.clone());
         self.host_policy = HostPolicy::from_config(
             PermissionsConfig {
                 trust_skill_allowed_tools: false,
@@ -369,9 +905,127 @@ impl OpenSkillRuntime {
                 allow: Vec::new(),
             },
         );
+    }
+
+    /// Answer permission requests with a built-in profile instead of a
+    /// hand-written callback:
+    ///
+    /// - [`PermissionProfile::Strict`]: strict permissions mode (see
+    ///   [`Self::with_strict_permissions`]); risky tools are always denied.
+    /// - [`PermissionProfile::Standard`]: low and medium risk tools are
+    ///   allowed, high risk tools go to the permission callback and are
+    ///   denied without one.
+    /// - [`PermissionProfile::Permissive`]: like standard, but high risk
+    ///   tools are allowed without a callback.
+    ///
+    /// Standard and permissive switch the host policy fallback to
+    /// [`Fallback::Prompt`], so tools a skill does not declare are decided
+    /// by the profile rather than denied outright.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use openskills_runtime::{OpenSkillRuntime, CliPermissionCallback, PermissionProfile};
+    /// use std::sync::Arc;
+    ///
+    /// let runtime = OpenSkillRuntime::new()
+    ///     .with_permission_callback(Arc::new(CliPermissionCallback))
+    ///     .with_permission_profile(PermissionProfile::Standard);
+    /// ```
+    pub fn with_permission_profile(mut self, profile: PermissionProfile) -> Self {
+        self.set_permission_profile(profile);
+        self
+    }
+
+    /// Mutating version of `with_permission_profile()`.
+    pub fn set_permission_profile(&mut self, profile: PermissionProfile) {
+        match profile {
+            PermissionProfile::Strict => self.set_strict_permissions(),
+            PermissionProfile::Standard | PermissionProfile::Permissive => {
+                self.host_policy.fallback = Fallback::Prompt;
+            }
+        }
+        self.permission_manager.set_profile(Some(profile));
+    }
+
+    /// The permission profile in use, if one was set.
+    pub fn permission_profile(&self) -> Option<PermissionProfile> {
+        self.permission_manager.profile()
+    }
+
+    /// Audit-only permissions: requests that would prompt the permission
+    /// callback are allowed without asking, and recorded in the permission
+    /// audit log with `would_have_asked`, to observe what skills use before
+    /// enforcing. Grants, profiles and the host policy still apply.
+    pub fn with_permission_audit_only(mut self, enabled: bool) -> Self {
+        self.set_permission_audit_only(enabled);
+        self
+    }
+
+    /// Mutating version of `with_permission_audit_only()`.
+    pub fn set_permission_audit_only(&mut self, enabled: bool) {
+        self.permission_manager.set_audit_only(enabled);
+    }
+
+    /// Whether audit-only permissions are on.
+    pub fn is_permission_audit_only(&self) -> bool {
+        self.permission_manager.is_audit_only()
+    }
+
+    /// Await `callback` for the permission prompts of the async execution
+    /// API (`execute_skill_async`, `run_skill_target_async`) instead of
+    /// blocking on the synchronous callback. Other calls still use the
+    /// synchronous callback; without one, their prompts are denied.
+    #[cfg(feature = "tokio")]
+    pub fn with_async_permission_callback(mut self, callback: Arc<dyn AsyncPermissionCallback>) -> Self {
+        self.set_async_permission_callback(Some(callback));
         self
     }
 
+    /// Mutating version of `with_async_permission_callback()`; `None`
+    /// removes the callback.
+    #[cfg(feature = "tokio")]
+    pub fn set_async_permission_callback(&mut self, callback: Option<Arc<dyn AsyncPermissionCallback>>) {
+        self.permission_manager.set_async_callback(callback);
+    }
+
+    /// Ask the async permission callback, ahead of preparing an execution,
+    /// about the prompts preparing it would raise. Resolution errors are
+    /// left for the preparation to report.
+    #[cfg(feature = "tokio")]
+    async fn ask_permissions_async(
+        &mut self,
+        skill_id: &str,
+        tools: bool,
+        target: &ExecutionTarget,
+        workspace_dir: Option<&Path>,
+    ) -> Result<(), OpenSkillError> {
+        if !self.permission_manager.has_async_callback() {
+            return Ok(());
+        }
+        if self.registry.is_empty() {
+            self.discover_skills()?;
+        }
+        let Ok(mut skill) = self.registry.load_full_skill(skill_id) else {
+            return Ok(());
+        };
+        let allowed_tools = if tools {
+            skill.manifest.get_allowed_tools()
+        } else {
+            Vec::new()
+        };
+        let workspace_dir = workspace_dir
+            .map(Path::to_path_buf)
+            .or_else(|| self.get_workspace_dir().ok());
+        let scope = self.request_scope(&skill.root, skill.manifest.entrypoint.as_ref(), target, workspace_dir);
+        self.permission_manager.start_collecting();
+        let _ = self.resolve_skill_permissions(&skill, &skill.id, &allowed_tools, &scope, &mut Vec::new());
+        let _ = self.resolve_skill_sandbox(&skill.id, skill.manifest.sandbox.take());
+        let _ = self.resolve_skill_root_write(&skill.id, &skill.root, skill.manifest.writes_skill_root);
+        let requests = self.permission_manager.take_collected();
+        self.permission_manager.ask_async(requests).await
+    }
+
     /// Set a host policy programmatically, overriding the default.
     ///
     /// The host policy controls which tools skills are granted.
@@ -399,15 +1053,31 @@ impl OpenSkillRuntime {
         self.host_policy = policy;
     }
 
+    /// Set what each granted tool allows inside the sandbox, e.g. network
+    /// for a custom `Database` tool. Defaults to [`CapabilityMapping::default`];
+    /// load a policy file with [`CapabilityMapping::from_file`].
+    pub fn with_capability_mapping(mut self, mapping: CapabilityMapping) -> Self {
+        self.capability_mapping = Arc::new(mapping);
+        self
+    }
+
+    /// Mutating version of `with_capability_mapping()`.
+    pub fn set_capability_mapping(&mut self, mapping: CapabilityMapping) {
+        self.capability_mapping = Arc::new(mapping);
+    }
+
     /// Set the permission callback on an existing runtime (mutating version).
     ///
     /// Pass `Some(callback)` to enable interactive prompting (e.g., `CliPermissionCallback`),
     /// or `None` to auto-approve all permission requests (default behavior).
+    /// "Allow always" grants are kept.
     pub fn set_permission_callback(&mut self, callback: Option<Arc<dyn PermissionCallback>>) {
         self.permission_manager = match callback {
             Some(cb) => PermissionManager::with_callback(cb),
             None => PermissionManager::new(),
-        };
+        }
+        .with_grants_of(&self.permission_manager)
+        .with_events(self.events.clone());
     }
 
     /// Get the current host policy.
@@ -453,6 +1123,51 @@ impl OpenSkillRuntime {
         self.permission_manager.reset_grants();
     }
 
+    /// Load "allow always" grants saved by earlier processes from `path`
+    /// (see [`default_grants_path`]) and keep later grants and revocations
+    /// there. Returns the number of grants loaded.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openskills_runtime::{default_grants_path, CliPermissionCallback, OpenSkillRuntime};
+    /// use std::sync::Arc;
+    ///
+    /// let mut runtime = OpenSkillRuntime::new()
+    ///     .with_permission_callback(Arc::new(CliPermissionCallback));
+    /// if let Some(path) = default_grants_path() {
+    ///     runtime.load_permission_grants(path).unwrap();
+    /// }
+    /// ```
+    pub fn load_permission_grants<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<usize, OpenSkillError> {
+        self.permission_manager.load_grants(path.as_ref())
+    }
+
+    /// Write the persistent "allow always" grants to `path`.
+    pub fn save_permission_grants<P: AsRef<Path>>(&self, path: P) -> Result<(), OpenSkillError> {
+        self.permission_manager.save_grants(path.as_ref())
+    }
+
+    /// Current "allow always" grants.
+    pub fn permission_grants(&self) -> Vec<PermissionGrant> {
+        self.permission_manager.grants()
+    }
+
+    /// Withdraw the "allow always" grants of `tool` to `skill_id`, also
+    /// from the grant store. Grants are bound to the directory the skill was
+    /// loaded from; this withdraws them for every directory. Returns whether
+    /// there was one.
+    pub fn revoke_permission_grant(
+        &self,
+        skill_id: &str,
+        tool: &str,
+    ) -> Result<bool, OpenSkillError> {
+        self.permission_manager.revoke(skill_id, tool)
+    }
+
     // ==================== Workspace Management ====================
 
     /// Set a custom workspace directory for skill I/O operations.
@@ -467,6 +1182,11 @@ impl OpenSkillRuntime {
         self
     }
 
+    /// Set or clear (back to the per-session default) the workspace directory.
+    pub fn set_workspace_dir(&mut self, dir: Option<PathBuf>) {
+        self.workspace_dir = dir;
+    }
+
     /// Set native runner config (interpreter overrides, Python site-package visibility).
     ///
     /// Embedding systems (e.g. ChatKit) can use this to:
@@ -478,93 +1198,1100 @@ impl OpenSkillRuntime {
         self
     }
 
-    /// Get the current workspace directory.
-    ///
-    /// Returns the configured workspace directory, or generates a default one
-    /// at `~/.cache/openskills/workspace/{session_id}/`.
+    /// Configure the wasmtime engine WASM skills run on.
     ///
-    /// The directory is created if it doesn't exist.
-    pub fn get_workspace_dir(&self) -> Result<PathBuf, OpenSkillError> {
-        let dir = self.workspace_dir.clone().unwrap_or_else(|| {
-            get_default_workspace_root().join(&self.session_id)
-        });
-
-        // Ensure the directory exists
-        if !dir.exists() {
-            std::fs::create_dir_all(&dir)?;
-        }
+    /// The engine, its WASI linker and compiled components are created once
+    /// per runtime and reused by every WASM execution; each execution still
+    /// gets a fresh instance. The default uses the pooling allocator with
+    /// [`DEFAULT_WASM_MAX_INSTANCES`] slots and the on-disk module cache.
+    pub fn with_wasm_engine_config(mut self, config: WasmEngineConfig) -> Self {
+        self.set_wasm_engine_config(config);
+        self
+    }
 
-        Ok(dir)
+    /// Replace the WASM engine configuration on an existing runtime. The
+    /// current engine and its compiled components are dropped.
+    pub fn set_wasm_engine_config(&mut self, config: WasmEngineConfig) {
+        self.wasm_engine = Arc::new(WasmEngineHandle::new(config));
     }
 
-    /// Get the session ID for this runtime instance.
-    pub fn get_session_id(&self) -> &str {
-        &self.session_id
+    /// Sandbox setup latency of this runtime's executions, aggregated per
+    /// backend and phase, e.g. (`landlock`, `profile`) or (`wasm`,
+    /// `component_compiled`). Frequent `component_compiled` phases suggest
+    /// enabling the module cache; each execution's own phases are in
+    /// [`ExecutionResult::setup_timings`].
+    pub fn sandbox_metrics(&self) -> Vec<PhaseMetrics> {
+        self.sandbox_metrics.snapshot()
     }
 
-    /// Clean up the workspace directory.
-    ///
-    /// This removes all files and subdirectories in the workspace.
-    /// Use with caution - this is destructive.
-    pub fn cleanup_workspace(&self) -> Result<(), OpenSkillError> {
-        let dir = self.get_workspace_dir()?;
-        if dir.exists() {
-            std::fs::remove_dir_all(&dir)?;
+    /// Create the WASM engine and compile the component `skill_id` runs on
+    /// now, instead of during its first execution. Latency-sensitive agents
+    /// can call this when a session starts. This is compilation only: every
+    /// execution still instantiates and initializes a fresh instance
+    /// (`component_reused`). Returns the setup phases this took, which are
+    /// empty for skills without a WASM entry point.
+    pub fn precompile_skill(&mut self, skill_id: &str) -> Result<SetupTimings, OpenSkillError> {
+        if self.registry.is_empty() {
+            self.discover_skills()?;
         }
-        Ok(())
+        let skill = self.registry.load_full_skill(skill_id)?;
+        precompile_skill(&skill, &self.entrypoint_config, &self.wasm_engine)
     }
 
-    // ==================== End Workspace Management ====================
+    /// Discard the aggregated sandbox setup metrics.
+    pub fn reset_sandbox_metrics(&self) {
+        self.sandbox_metrics.reset();
+    }
 
-    /// Discover and load skills from configured locations.
-    ///
-    /// This scans:
-    /// - Standard locations (if `use_standard_locations` is true):
-    ///   - `~/.claude/skills/` (personal skills)
-    ///   - `.claude/skills/` (project skills)
-    ///   - Nested `.claude/skills/` directories (monorepo support)
-    /// - Custom directories (if any were configured via `with_custom_directory` or `with_custom_directories`)
-    ///
-    /// Returns skill descriptors (name + description only) for progressive disclosure.
-    /// Skills from later directories override earlier ones if IDs conflict.
-    pub fn discover_skills(&mut self) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
-        self.registry.clear();
-        // Scan standard locations if enabled
-        if self.use_standard_locations {
-            self.registry.discover()?;
-        }
+    /// Timeout and memory limit suggested for `skill_id` by its recent
+    /// executions in this runtime: 50% headroom over the 95th percentile
+    /// duration, and double any limit an execution timed out or ran out of
+    /// memory under. Only limits above the defaults are suggested.
+    pub fn suggest_limits(&self, skill_id: &str) -> LimitSuggestion {
+        self.execution_history
+            .suggest(skill_id, WasmConfig::default().timeout_ms)
+    }
 
-        // Scan custom directories
-        for dir in &self.custom_directories {
-            self.registry.scan_explicit(dir)?;
-        }
+    /// Run skills with their [suggested limits](Self::suggest_limits) when
+    /// the caller does not set a timeout or memory limit.
+    pub fn with_auto_limits(mut self, enable: bool) -> Self {
+        self.auto_limits = enable;
+        self
+    }
 
-        Ok(self.registry.list())
+    /// Executions currently running in this runtime, oldest first, with
+    /// handles to cancel them.
+    pub fn active_executions(&self) -> Vec<ActiveExecution> {
+        self.executions.active_executions()
     }
 
-    /// Load skills from a specific directory (for testing or custom paths).
-    pub fn load_from_directory<P: AsRef<Path>>(
-        &mut self,
-        dir: P,
-    ) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
-        self.registry.clear();
-        self.registry.scan_explicit(dir)?;
-        Ok(self.registry.list())
+    /// Cancel the running execution `id`; `false` if it is not running.
+    pub fn cancel_execution(&self, id: u64) -> bool {
+        self.executions.cancel(id)
     }
 
-    /// List all discovered skills (progressive disclosure - descriptors only).
-    pub fn list_skills(&self) -> Vec<SkillDescriptor> {
-        self.registry.list()
+    /// Clean up after executions whose runtime crashed or was killed: kill
+    /// the sandboxed processes they left running, remove their temporary
+    /// sandbox profiles and cgroups, and audit each as `failed:aborted`.
+    /// Returns the audit records, oldest first; empty without a journal.
+    pub fn recover_aborted_executions(&mut self) -> Vec<AuditRecord> {
+        let Some(journal) = &self.journal else {
+            return Vec::new();
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::from_secs(0))
+            .as_millis() as u64;
+        let records: Vec<AuditRecord> = journal
+            .recover()
+            .into_iter()
+            .map(|entry| AuditRecord {
+                skill_id: entry.skill_id,
+                version: entry.version,
+                input_hash: entry.input_hash,
+                output_hash: String::new(),
+                start_time_ms: entry.start_time_ms,
+                duration_ms: now.saturating_sub(entry.start_time_ms),
+                permissions_used: Vec::new(),
+                exit_status: audit::ExecutionStatus::Failed("aborted".to_string()),
+                stdout: String::new(),
+                stderr: String::new(),
+                sandbox_mode: self
+                    .native_runner_config
+                    .as_ref()
+                    .map(|c| c.sandbox_mode)
+                    .unwrap_or(SandboxMode::Enforce),
+                sandbox_violations: Vec::new(),
+                fuel_consumed: None,
+                network_requests: Vec::new(),
+                host_calls: Vec::new(),
+                capability_downgrades: Vec::new(),
+                attempt: 1,
+                traceparent: None,
+            })
+            .collect();
+        for record in &records {
+            self.audit_sink.record(record);
+        }
+        records
     }
 
-    /// Get discovery warnings (name overrides, description fallbacks, etc.).
-    pub fn get_discovery_warnings(&self) -> &[String] {
-        self.registry.get_discovery_warnings()
+    /// Journal an execution starting now. Journaling is best effort: an
+    /// unwritable journal does not stop the execution.
+    fn journal_execution(
+        &self,
+        skill: &Skill,
+        target: String,
+        input_hash: &str,
+    ) -> Option<JournalRecord> {
+        self.journal.as_ref()?.begin(
+            &skill.id,
+            skill.manifest.version(),
+            target,
+            input_hash,
+            &self.session_id,
+        )
+        .ok()
     }
 
-    /// Get loading errors encountered during discovery.
-    pub fn get_loading_errors(&self) -> &HashMap<String, String> {
-        self.registry.get_loading_errors()
+    /// A view of this runtime's running executions that can be moved to
+    /// another thread, e.g. to cancel an execution while the runtime is
+    /// busy running it.
+    pub fn execution_monitor(&self) -> ExecutionMonitor {
+        self.executions.clone()
+    }
+
+    /// Fill in unset limits from the skill's suggestion, if enabled.
+    fn apply_auto_limits(
+        &self,
+        skill_id: &str,
+        timeout_ms: Option<u64>,
+        memory_mb: Option<u64>,
+    ) -> (Option<u64>, Option<u64>) {
+        if !self.auto_limits {
+            return (timeout_ms, memory_mb);
+        }
+        let suggestion = self.suggest_limits(skill_id);
+        (
+            timeout_ms.filter(|&ms| ms > 0).or(suggestion.timeout_ms),
+            memory_mb.or(suggestion.memory_mb),
+        )
+    }
+
+    fn record_execution(
+        &self,
+        skill_id: &str,
+        status: &audit::ExecutionStatus,
+        duration_ms: u64,
+        timeout_ms: Option<u64>,
+        memory_mb: Option<u64>,
+    ) {
+        let Some(outcome) = Outcome::from_status(status) else {
+            return;
+        };
+        self.execution_history.record(
+            skill_id,
+            ExecutionSample {
+                duration_ms,
+                timeout_ms: timeout_ms
+                    .filter(|&ms| ms > 0)
+                    .unwrap_or(WasmConfig::default().timeout_ms),
+                memory_mb,
+                outcome,
+            },
+        );
+    }
+
+    /// Configure how the entry point is auto-detected when a skill runs
+    /// without an explicit target: whether WASM modules or scripts are tried
+    /// first, and which file patterns count as candidates. A skill's
+    /// `entrypoint:` in SKILL.md always wins over this.
+    pub fn with_entrypoint_config(mut self, config: EntrypointConfig) -> Self {
+        self.entrypoint_config = config;
+        self
+    }
+
+    /// Replace the entry-point detection config on an existing runtime.
+    pub fn set_entrypoint_config(&mut self, config: EntrypointConfig) {
+        self.entrypoint_config = config;
+    }
+
+    /// Bound captured stdout/stderr of native scripts to `max_bytes` each.
+    ///
+    /// Streams over the limit are written in full to a file in the workspace
+    /// and returned as head + tail with a marker naming the file, e.g.
+    /// `... [1048576 bytes omitted; full output: /ws/.openskills/artifacts/skill/stdout-….log] ...`.
+    pub fn with_max_output_bytes(mut self, max_bytes: usize) -> Self {
+        self.set_max_output_bytes(Some(max_bytes));
+        self
+    }
+
+    /// Set (or clear, with `None`) the captured output limit on an existing runtime.
+    pub fn set_max_output_bytes(&mut self, max_bytes: Option<usize>) {
+        self.native_runner_config
+            .get_or_insert_with(NativeRunnerConfig::default)
+            .max_output_bytes = max_bytes;
+    }
+
+    /// Enable or disable redaction of host paths in returned outputs.
+    ///
+    /// When enabled, absolute paths to the workspace, the skill root and the
+    /// user's home directory in `output`, `stdout` and `stderr` are rewritten
+    /// to `$SKILL_WORKSPACE`, `$SKILL_ROOT` and `~` before the result is
+    /// returned. The audit sink still receives the unredacted record.
+    pub fn with_output_redaction(mut self, enable: bool) -> Self {
+        self.redact_output = enable;
+        self
+    }
+
+    /// Enable or disable output redaction on an existing runtime.
+    pub fn set_output_redaction(&mut self, enable: bool) {
+        self.redact_output = enable;
+    }
+
+    /// Redact API keys, tokens and other credentials with `redactor`
+    /// (`SecretRedactor::new()` for the built-in detectors).
+    ///
+    /// Unlike path redaction this happens before the audit record is built:
+    /// `output`, `stdout`, `stderr`, failure reasons and execution errors are
+    /// redacted in both the audit record and the returned result. Output
+    /// that leaves the runtime earlier goes through the same `redact` step of
+    /// the output pipeline a line at a time: streamed output chunks (and so
+    /// script session lines) are redacted before they are sent, and output
+    /// spilled past `NativeRunnerConfig::max_output_bytes` before it is
+    /// written to disk.
+    pub fn with_secret_redaction(mut self, redactor: SecretRedactor) -> Self {
+        self.secret_redactor = Some(redactor);
+        self
+    }
+
+    /// Set (or disable, with `None`) secret redaction on an existing runtime.
+    pub fn set_secret_redaction(&mut self, redactor: Option<SecretRedactor>) {
+        self.secret_redactor = redactor;
+    }
+
+    /// Enable or disable built-in terminal cleanup of captured output.
+    ///
+    /// Enabled by default: ANSI escape sequences are stripped and
+    /// carriage-return progress updates are collapsed to their final state
+    /// before the output pipeline runs. Disable to receive raw bytes.
+    pub fn with_tty_cleanup(mut self, enable: bool) -> Self {
+        self.tty_cleanup = enable;
+        self
+    }
+
+    /// Enable or disable terminal cleanup on an existing runtime.
+    pub fn set_tty_cleanup(&mut self, enable: bool) {
+        self.tty_cleanup = enable;
+    }
+
+    /// Enable or disable diverting binary stdout to the workspace.
+    ///
+    /// Binary stdout (NUL bytes or invalid UTF-8) is never returned as lossy
+    /// text. When enabled (default) and a workspace is available, the bytes
+    /// are written to `<workspace>/.openskills/artifacts/<skill>/stdout-<hash>.bin`; the result
+    /// output then holds `{"status", "binary_output": {path, size, sha256}}`
+    /// and stdout a short placeholder. When disabled, only size and hash are
+    /// reported.
+    pub fn with_binary_output_diversion(mut self, enable: bool) -> Self {
+        self.divert_binary_output = enable;
+        self
+    }
+
+    /// Enable or disable binary stdout diversion on an existing runtime.
+    pub fn set_binary_output_diversion(&mut self, enable: bool) {
+        self.divert_binary_output = enable;
+    }
+
+    /// Enable or disable collecting the files an execution produced.
+    ///
+    /// When enabled (default) and a workspace is available, the workspace is
+    /// snapshotted before each execution, and files created or modified by
+    /// it are returned in `ExecutionResult::artifacts` with their size, MIME
+    /// type and SHA-256. Disable it for large workspaces where the scan is
+    /// too costly.
+    pub fn with_artifact_collection(mut self, enable: bool) -> Self {
+        self.collect_artifacts = enable;
+        self
+    }
+
+    /// Enable or disable artifact collection on an existing runtime.
+    pub fn set_artifact_collection(&mut self, enable: bool) {
+        self.collect_artifacts = enable;
+    }
+
+    /// Convert the files every skill produces with `converter`, in addition
+    /// to the skill's own `output-converters`.
+    ///
+    /// After a successful execution, each artifact matching the converter's
+    /// `from` is converted by running its `run` file as a target of the
+    /// converter's skill, in the same workspace and sandbox; the converted
+    /// file is added to `ExecutionResult::artifacts` with `converted_from`
+    /// set. Conversions are audited as executions of their own, and a
+    /// failed one only leaves its file out. Needs artifact collection.
+    pub fn with_output_converter(mut self, converter: OutputConverter) -> Self {
+        self.output_converters.push(converter);
+        self
+    }
+
+    /// Replace the host's output converters on an existing runtime.
+    pub fn set_output_converters(&mut self, converters: Vec<OutputConverter>) {
+        self.output_converters = converters;
+    }
+
+    /// The host's output converters.
+    pub fn output_converters(&self) -> &[OutputConverter] {
+        &self.output_converters
+    }
+
+    /// Run at most `max` skills at once in [`Self::execute_many`] (default:
+    /// the number of available cores).
+    pub fn with_max_parallelism(mut self, max: usize) -> Self {
+        self.max_parallelism = Some(max.max(1));
+        self
+    }
+
+    /// Change the [`Self::execute_many`] parallelism on an existing runtime.
+    pub fn set_max_parallelism(&mut self, max: Option<usize>) {
+        self.max_parallelism = max.map(|m| m.max(1));
+    }
+
+    /// Set the default output post-processing pipeline.
+    ///
+    /// Processors run in order over stdout/stderr (and, where relevant, the
+    /// structured output) before results are returned. The audit sink still
+    /// receives the raw record.
+    pub fn with_output_pipeline(mut self, pipeline: OutputPipeline) -> Self {
+        self.output_pipeline = pipeline;
+        self
+    }
+
+    /// Override the output pipeline for a single skill.
+    pub fn with_skill_output_pipeline(
+        mut self,
+        skill_id: impl Into<String>,
+        pipeline: OutputPipeline,
+    ) -> Self {
+        self.skill_output_pipelines.insert(skill_id.into(), pipeline);
+        self
+    }
+
+    /// Set the default output pipeline on an existing runtime.
+    pub fn set_output_pipeline(&mut self, pipeline: OutputPipeline) {
+        self.output_pipeline = pipeline;
+    }
+
+    /// Set (or clear, with `None`) the output pipeline override for a skill.
+    pub fn set_skill_output_pipeline(&mut self, skill_id: &str, pipeline: Option<OutputPipeline>) {
+        match pipeline {
+            Some(p) => {
+                self.skill_output_pipelines.insert(skill_id.to_string(), p);
+            }
+            None => {
+                self.skill_output_pipelines.remove(skill_id);
+            }
+        }
+    }
+
+    /// Get the output pipeline that applies to a skill.
+    pub fn output_pipeline_for(&self, skill_id: &str) -> &OutputPipeline {
+        self.skill_output_pipelines
+            .get(skill_id)
+            .unwrap_or(&self.output_pipeline)
+    }
+
+    /// Set the default retention policy for runtime-created artifacts.
+    ///
+    /// Artifacts are the files the runtime writes into the workspace on a
+    /// skill's behalf (spilled output, diverted binary stdout), kept under
+    /// `<workspace>/.openskills/artifacts/<skill>/`. The policy is enforced
+    /// for a skill after each of its executions and for all skills by
+    /// [`Self::gc_artifacts`]. Pinned artifacts are never removed. The
+    /// default keeps everything.
+    pub fn with_artifact_retention(mut self, policy: RetentionPolicy) -> Self {
+        self.artifact_retention = policy;
+        self
+    }
+
+    /// Override the artifact retention policy for a single skill.
+    pub fn with_skill_artifact_retention(
+        mut self,
+        skill_id: impl Into<String>,
+        policy: RetentionPolicy,
+    ) -> Self {
+        self.skill_artifact_retention.insert(skill_id.into(), policy);
+        self
+    }
+
+    /// Set the default artifact retention policy on an existing runtime.
+    pub fn set_artifact_retention(&mut self, policy: RetentionPolicy) {
+        self.artifact_retention = policy;
+    }
+
+    /// Set (or clear, with `None`) the artifact retention override for a skill.
+    pub fn set_skill_artifact_retention(&mut self, skill_id: &str, policy: Option<RetentionPolicy>) {
+        match policy {
+            Some(p) => {
+                self.skill_artifact_retention.insert(skill_id.to_string(), p);
+            }
+            None => {
+                self.skill_artifact_retention.remove(skill_id);
+            }
+        }
+    }
+
+    /// Get the artifact retention policy that applies to a skill.
+    pub fn artifact_retention_for(&self, skill_id: &str) -> &RetentionPolicy {
+        let key = artifacts::sanitize_skill_id(skill_id);
+        self.skill_artifact_retention
+            .get(skill_id)
+            .or_else(|| {
+                self.skill_artifact_retention
+                    .iter()
+                    .find(|(id, _)| artifacts::sanitize_skill_id(id) == key)
+                    .map(|(_, policy)| policy)
+            })
+            .unwrap_or(&self.artifact_retention)
+    }
+
+    /// Remove other sessions' workspaces idle for longer than `max_age`.
+    ///
+    /// Only applies to the default workspace root (no explicit
+    /// `with_workspace_dir`). Stale sessions are swept when this session's
+    /// workspace is first created and on [`Self::gc_artifacts`]; sessions
+    /// holding pinned artifacts are kept.
+    pub fn with_session_retention(mut self, max_age: Duration) -> Self {
+        self.session_retention = Some(max_age);
+        self
+    }
+
+    /// Set (or clear, with `None`) session retention on an existing runtime.
+    pub fn set_session_retention(&mut self, max_age: Option<Duration>) {
+        self.session_retention = max_age;
+    }
+
+    /// Remove conversation workspaces (see [`Self::workspace_for`]) unused
+    /// for longer than `max_age`.
+    ///
+    /// Stale conversations are swept when a new conversation workspace is
+    /// created and on [`Self::gc_artifacts`]; the workspace in use and
+    /// conversations holding pinned artifacts are kept.
+    pub fn with_conversation_retention(mut self, max_age: Duration) -> Self {
+        self.conversation_retention = Some(max_age);
+        self
+    }
+
+    /// Set (or clear, with `None`) conversation retention on an existing
+    /// runtime.
+    pub fn set_conversation_retention(&mut self, max_age: Option<Duration>) {
+        self.conversation_retention = max_age;
+    }
+
+    /// List runtime-created artifacts in the workspace, optionally for one skill.
+    pub fn list_artifacts(&self, skill_id: Option<&str>) -> Result<Vec<ArtifactInfo>, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        Ok(artifacts::list(&workspace, skill_id)?)
+    }
+
+    /// Pin an artifact so retention never removes it.
+    ///
+    /// `path` must be a file under the workspace's artifact directory, e.g. a
+    /// path returned in `binary_output.path` or a spill marker. Returns the
+    /// canonical path. Pins are stored next to the artifact and persist
+    /// across runtimes sharing the workspace.
+    pub fn pin_artifact<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        artifacts::pin(&workspace, path.as_ref())
+    }
+
+    /// Unpin an artifact, making it subject to retention again.
+    pub fn unpin_artifact<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        artifacts::unpin(&workspace, path.as_ref())
+    }
+
+    /// Enforce artifact and session retention now.
+    pub fn gc_artifacts(&self) -> Result<GcReport, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        let now = SystemTime::now();
+        let mut report =
+            artifacts::collect(&workspace, |skill| self.artifact_retention_for(skill), now)?;
+        let sessions = self.collect_stale_sessions(now)?;
+        let conversations = self.collect_stale_conversations(now)?;
+        report.removed_sessions = sessions.removed_sessions;
+        report.removed_sessions.extend(conversations.removed_sessions);
+        Ok(report)
+    }
+
+    /// Get the current workspace directory.
+    ///
+    /// Returns the configured workspace directory, or generates a default one
+    /// at `~/.cache/openskills/workspace/{session_id}-{hash}/`.
+    ///
+    /// The directory is created if it doesn't exist.
+    pub fn get_workspace_dir(&self) -> Result<PathBuf, OpenSkillError> {
+        let dir = self.workspace_dir.clone().unwrap_or_else(|| {
+            artifacts::session_dir(&get_default_workspace_root(), &self.session_id)
+        });
+
+        // Ensure the directory exists
+        if !dir.exists() {
+            std::fs::create_dir_all(&dir)?;
+            // A new session is a good time to sweep stale ones and trim
+            // the caches (best effort).
+            let _ = self.collect_stale_sessions(SystemTime::now());
+            let _ = self.gc_caches();
+        }
+
+        Ok(dir)
+    }
+
+    fn collect_stale_sessions(&self, now: SystemTime) -> Result<GcReport, OpenSkillError> {
+        match self.session_retention {
+            Some(max_age) if self.workspace_dir.is_none() => Ok(artifacts::collect_sessions(
+                &get_default_workspace_root(),
+                &self.session_id,
+                max_age,
+                now,
+            )?),
+            _ => Ok(GcReport::default()),
+        }
+    }
+
+    fn collect_stale_conversations(&self, now: SystemTime) -> Result<GcReport, OpenSkillError> {
+        match self.conversation_retention {
+            Some(max_age) => Ok(artifacts::collect_conversations(
+                &get_default_workspace_root(),
+                self.workspace_dir.as_deref(),
+                max_age,
+                now,
+            )?),
+            None => Ok(GcReport::default()),
+        }
+    }
+
+    /// Enforce a skill's artifact retention after it ran (best effort).
+    fn enforce_artifact_retention(&self, skill_id: &str, workspace_dir: Option<&Path>) {
+        let Some(workspace) = workspace_dir else {
+            return;
+        };
+        let policy = self.artifact_retention_for(skill_id);
+        if policy.is_keep_all() {
+            return;
+        }
+        let dir = artifacts::skill_dir(workspace, skill_id);
+        let skill = artifacts::sanitize_skill_id(skill_id);
+        let _ = artifacts::collect_skill(&dir, &skill, policy, SystemTime::now());
+    }
+
+    /// Get the session ID for this runtime instance.
+    pub fn get_session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Use `session_id` instead of a generated `session-<uuid>` ID.
+    ///
+    /// Hosts can pass their own conversation ID so the default workspace,
+    /// `~/.cache/openskills/workspace/{session_id}-{hash}/`, lines up with
+    /// it; runtimes given the same ID share that workspace. Characters other
+    /// than ASCII letters, digits, `-` and `_` are replaced by `_` in the
+    /// directory name, and the hash of the full ID keeps IDs that sanitize
+    /// alike apart. An empty ID is ignored. Session retention only
+    /// removes the workspaces of generated IDs, so hosts clean up their own.
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.set_session_id(session_id);
+        self
+    }
+
+    /// Change the session ID of an existing runtime. Later executions use
+    /// the new session's default workspace.
+    pub fn set_session_id(&mut self, session_id: impl Into<String>) {
+        let session_id = session_id.into();
+        if !session_id.is_empty() {
+            self.session_id = session_id;
+        }
+    }
+
+    /// Workspace of the host conversation `conversation_id`, created on
+    /// first use.
+    ///
+    /// Each conversation gets its own directory under
+    /// `~/.cache/openskills/workspace/.conversations/`, so a multi-turn
+    /// agent finds the files earlier turns produced while other
+    /// conversations' files stay out of reach. Every call marks the
+    /// workspace as used for [`Self::with_conversation_retention`].
+    pub fn workspace_for(&self, conversation_id: &str) -> Result<PathBuf, OpenSkillError> {
+        if conversation_id.is_empty() {
+            return Err(OpenSkillError::InvalidConfig(
+                "conversation id must not be empty".to_string(),
+            ));
+        }
+        let dir = artifacts::conversation_dir(&get_default_workspace_root(), conversation_id);
+        if dir.exists() {
+            artifacts::touch(&dir);
+        } else {
+            std::fs::create_dir_all(&dir)?;
+            // Sweep stale conversations (best effort).
+            let _ = self.collect_stale_conversations(SystemTime::now());
+        }
+        Ok(dir)
+    }
+
+    /// Run later executions in the workspace of `conversation_id` (see
+    /// [`Self::workspace_for`]) and return it. Call it at the start of every
+    /// turn.
+    pub fn use_conversation(&mut self, conversation_id: &str) -> Result<PathBuf, OpenSkillError> {
+        let dir = self.workspace_for(conversation_id)?;
+        self.workspace_dir = Some(dir.clone());
+        Ok(dir)
+    }
+
+    /// Remove the workspace of `conversation_id`, e.g. when the host deletes
+    /// the conversation. Returns whether it existed.
+    pub fn remove_conversation_workspace(
+        &self,
+        conversation_id: &str,
+    ) -> Result<bool, OpenSkillError> {
+        let dir = artifacts::conversation_dir(&get_default_workspace_root(), conversation_id);
+        match std::fs::remove_dir_all(&dir) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Clean up the workspace directory.
+    ///
+    /// This removes all files and subdirectories in the workspace.
+    /// Use with caution - this is destructive.
+    pub fn cleanup_workspace(&self) -> Result<(), OpenSkillError> {
+        let dir = self.get_workspace_dir()?;
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+
+    /// Limit the size of a single workspace import (default 100 MiB).
+    pub fn with_max_import_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_import_bytes = max_bytes;
+        self
+    }
+
+    /// Set the workspace import size limit on an existing runtime.
+    pub fn set_max_import_bytes(&mut self, max_bytes: u64) {
+        self.max_import_bytes = max_bytes;
+    }
+
+    /// Deduplicate workspace imports through a blob store shared across
+    /// sessions, at `~/.cache/openskills/blobs/`.
+    ///
+    /// Each distinct content is stored once and materialized into workspaces
+    /// as a reflink where the filesystem supports it, otherwise as a copy.
+    /// Workspace files never share the blob's inode, so a skill editing its
+    /// copy cannot change the blob.
+    pub fn with_blob_store(mut self, enable: bool) -> Self {
+        self.set_blob_store(enable);
+        self
+    }
+
+    /// Enable or disable the shared blob store on an existing runtime.
+    pub fn set_blob_store(&mut self, enable: bool) {
+        self.blob_store = enable
+            .then(BlobStore::default_dir)
+            .flatten()
+            .map(BlobStore::new);
+    }
+
+    /// Use a blob store at `dir` (or none, with `None`) on an existing
+    /// runtime.
+    pub fn set_blob_store_dir(&mut self, dir: Option<PathBuf>) {
+        self.blob_store = dir.map(BlobStore::new);
+    }
+
+    /// Cap the size of the module, blob, venv and node_modules caches under
+    /// `~/.cache/openskills/`.
+    ///
+    /// Least recently used entries are evicted when this runtime creates its
+    /// session workspace and on [`Self::gc_caches`]. Unlimited by default.
+    pub fn with_cache_limits(mut self, limits: CacheLimits) -> Self {
+        self.cache_limits = limits;
+        self
+    }
+
+    /// Set the cache size caps on an existing runtime.
+    pub fn set_cache_limits(&mut self, limits: CacheLimits) {
+        self.cache_limits = limits;
+    }
+
+    /// Enforce the cache size caps now.
+    pub fn gc_caches(&self) -> Result<CacheGcReport, OpenSkillError> {
+        match CacheManager::default_root() {
+            Some(root) => Ok(CacheManager::new(root).gc(&self.cache_limits)?),
+            None => Ok(CacheGcReport::default()),
+        }
+    }
+
+    /// Copy a user-provided host file into the workspace at `dest_rel`.
+    ///
+    /// Use this instead of granting read access to the file's directory:
+    /// skills then read it from the workspace like any other input. Fails if
+    /// the file exceeds the import limit, lies in a credential directory
+    /// (`~/.ssh`, ...), or `dest_rel` is absolute, escapes the workspace or
+    /// targets `.openskills/`. An existing file at `dest_rel` is replaced.
+    /// Every import is recorded in [`Self::get_import_audit`].
+    pub fn import_into_workspace<P: AsRef<Path>>(
+        &self,
+        host_path: P,
+        dest_rel: &str,
+    ) -> Result<WorkspaceImport, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        let entry = workspace_import::import_file(
+            &workspace,
+            host_path.as_ref(),
+            dest_rel,
+            self.max_import_bytes,
+            self.blob_store.as_ref(),
+        )?;
+        self.record_import(&entry);
+        Ok(entry)
+    }
+
+    /// Write in-memory content (e.g. an upload) into the workspace at
+    /// `dest_rel`, with the same checks as [`Self::import_into_workspace`].
+    pub fn import_bytes_into_workspace(
+        &self,
+        bytes: &[u8],
+        dest_rel: &str,
+    ) -> Result<WorkspaceImport, OpenSkillError> {
+        let workspace = self.get_workspace_dir()?;
+        let entry = workspace_import::import_bytes(
+            &workspace,
+            bytes,
+            dest_rel,
+            self.max_import_bytes,
+            self.blob_store.as_ref(),
+        )?;
+        self.record_import(&entry);
+        Ok(entry)
+    }
+
+    /// Get the workspace import audit log, oldest first.
+    pub fn get_import_audit(&self) -> Vec<WorkspaceImport> {
+        self.import_log
+            .lock()
+            .map(|log| log.clone())
+            .unwrap_or_default()
+    }
+
+    fn record_import(&self, entry: &WorkspaceImport) {
+        if let Ok(mut log) = self.import_log.lock() {
+            log.push(entry.clone());
+        }
+    }
+
+    // ==================== End Workspace Management ====================
+
+    /// Discover and load skills from configured locations.
+    ///
+    /// This scans:
+    /// - Standard locations (if `use_standard_locations` is true):
+    ///   - `~/.claude/skills/` (personal skills)
+    ///   - `.claude/skills/` (project skills)
+    ///   - Nested `.claude/skills/` directories (monorepo support)
+    /// - Custom directories (if any were configured via `with_custom_directory` or `with_custom_directories`)
+    ///
+    /// Returns skill descriptors (name + description only) for progressive disclosure.
+    /// Skills from later directories override earlier ones if IDs conflict.
+    pub fn discover_skills(&mut self) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
+        self.registry.clear();
+        // Built-in skills first, so discovered copies replace them
+        let builtin = if self.builtin_skills { builtin_skills::BUILTIN_SKILLS } else { &[] };
+        if !builtin.is_empty() || !self.embedded_skills.is_empty() {
+            let dir = builtin_skills::default_unpack_dir();
+            for skill in builtin.iter().chain(&self.embedded_skills) {
+                self.registry.load_builtin(&builtin_skills::unpack(skill, &dir)?)?;
+            }
+        }
+
+        // Scan standard locations if enabled
+        if self.use_standard_locations {
+            self.registry.discover()?;
+        }
+
+        // Scan custom directories
+        for dir in &self.custom_directories {
+            self.registry.scan_explicit(dir)?;
+        }
+
+        // Keep skills loaded from a remote registry
+        #[cfg(feature = "remote-registry")]
+        for skill in &self.registry_skills {
+            self.registry.load_resolved(skill)?;
+        }
+
+        self.discovery_completed();
+        Ok(self.registry.list())
+    }
+
+    /// Rediscover skills like [`Self::discover_skills`] and report which
+    /// skill versions were added, modified (their `SKILL.md` changed or they
+    /// moved) or removed, also as `RuntimeEvent::SkillChanged` events.
+    pub fn reload_skills(&mut self) -> Result<Vec<SkillChange>, OpenSkillError> {
+        let before = self.skill_snapshot.clone();
+        self.discover_skills()?;
+        let changes = before.changes(&self.skill_snapshot);
+        for change in &changes {
+            self.events.emit(|| RuntimeEvent::SkillChanged {
+                skill_id: change.skill_id.clone(),
+                version: change.version.clone(),
+                kind: change.kind,
+            });
+        }
+        Ok(changes)
+    }
+
+    /// Watch the skill directories of a shared runtime and
+    /// [`reload`](Self::reload_skills) it when a `SKILL.md` is written or a
+    /// skill is added, removed or renamed, so long-running servers pick up edits
+    /// without a restart. Changes arrive on [`SkillWatcher::changes`] and as
+    /// events; watching stops when the watcher or the runtime is dropped.
+    #[cfg(feature = "watch")]
+    pub fn watch_skills(runtime: &Arc<Mutex<Self>>) -> Result<SkillWatcher, OpenSkillError> {
+        SkillWatcher::start(runtime)
+    }
+
+    /// Existing directories to watch for skill changes: the custom and
+    /// standard skill directories and those holding nested skills.
+    #[cfg_attr(not(feature = "watch"), allow(dead_code))]
+    pub(crate) fn watch_roots(&self) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = self.custom_directories.clone();
+        if self.use_standard_locations {
+            roots.extend(self.registry.standard_dirs());
+            roots.extend(
+                self.registry
+                    .all_versions()
+                    .filter(|m| m.location == SkillLocation::Nested)
+                    .filter_map(|m| m.root.parent().map(Path::to_path_buf)),
+            );
+        }
+        roots.retain(|dir| dir.is_dir());
+        roots.sort();
+        roots.dedup();
+        roots
+    }
+
+    fn discovery_completed(&mut self) {
+        self.skill_snapshot = SkillSnapshot::capture(self.registry.all_versions());
+        self.events.emit(|| RuntimeEvent::DiscoveryCompleted {
+            skills: self.registry.len(),
+            errors: self.registry.get_loading_errors().len(),
+        });
+    }
+
+    /// Require skills to be signed by one of `keys` (see [`signing`]):
+    /// `activate_skill` and executions check the signature next to
+    /// `SKILL.md` and, per [`Self::with_signature_mode`], refuse or report
+    /// unsigned and invalidly signed skills. No keys means no checks.
+    pub fn with_trusted_keys(mut self, keys: Vec<TrustedKey>) -> Self {
+        self.trusted_keys = keys;
+        self
+    }
+
+    /// Replace the trusted signing keys (see [`Self::with_trusted_keys`]).
+    pub fn set_trusted_keys(&mut self, keys: Vec<TrustedKey>) {
+        self.trusted_keys = keys;
+    }
+
+    /// Choose whether skills failing signature verification are refused
+    /// (default) or allowed with a `SignatureRejected` event.
+    pub fn with_signature_mode(mut self, mode: SignatureMode) -> Self {
+        self.signature_mode = mode;
+        self
+    }
+
+    /// Change the signature mode (see [`Self::with_signature_mode`]).
+    pub fn set_signature_mode(&mut self, mode: SignatureMode) {
+        self.signature_mode = mode;
+    }
+
+    /// Check a loaded skill's signature against the trusted keys.
+    pub fn verify_skill_signature(&self, skill_id: &str) -> Result<SignatureStatus, OpenSkillError> {
+        let root = self.get_skill_root(skill_id)?;
+        Ok(verify_skill(&root, &self.trusted_keys))
+    }
+
+    /// Enforce the signature policy for `skill` before it is activated or
+    /// run; a no-op without trusted keys.
+    fn check_skill_signature(&self, skill: &Skill) -> Result<(), OpenSkillError> {
+        if self.trusted_keys.is_empty() {
+            return Ok(());
+        }
+        let reason = match verify_skill(&skill.root, &self.trusted_keys) {
+            SignatureStatus::Verified { .. } => return Ok(()),
+            SignatureStatus::Unsigned => "skill is not signed".to_string(),
+            SignatureStatus::Invalid(reason) => reason,
+        };
+        match self.signature_mode {
+            SignatureMode::Refuse => Err(OpenSkillError::SignatureError(format!(
+                "{}: {}",
+                skill.id, reason
+            ))),
+            SignatureMode::Warn => {
+                self.events.emit(|| RuntimeEvent::SignatureRejected {
+                    skill_id: skill.id.clone(),
+                    reason,
+                });
+                Ok(())
+            }
+        }
+    }
+
+    /// Resolve `registry:` references through `registry` (see
+    /// [`Self::load_registry_skill`]).
+    #[cfg(feature = "remote-registry")]
+    pub fn with_remote_registry(mut self, registry: RemoteRegistry) -> Self {
+        self.remote_registry = Some(registry);
+        self
+    }
+
+    /// Load a skill from the remote registry by `registry:name[@version]`,
+    /// downloading and verifying it unless that version is cached. The
+    /// skill stays loaded across [`Self::discover_skills`] runs.
+    #[cfg(feature = "remote-registry")]
+    pub fn load_registry_skill(&mut self, reference: &str) -> Result<SkillDescriptor, OpenSkillError> {
+        let remote = self.remote_registry.as_ref().ok_or_else(|| {
+            OpenSkillError::RegistryError("no remote registry configured".to_string())
+        })?;
+        let resolved = self.registry.resolve_remote(remote, reference)?;
+        self.keep_registry_skill(resolved)
+    }
+
+    /// Pull a skill pushed as an OCI artifact (e.g.
+    /// `ghcr.io/acme/skills/pdf:1.2.0`) through `client` and load it. Like
+    /// registry skills, it stays loaded across [`Self::discover_skills`].
+    #[cfg(feature = "oci")]
+    pub fn load_oci_skill(
+        &mut self,
+        client: &OciClient,
+        reference: &str,
+    ) -> Result<SkillDescriptor, OpenSkillError> {
+        let resolved = client.pull(&OciReference::parse(reference)?)?;
+        self.registry.load_resolved(&resolved)?;
+        self.keep_registry_skill(resolved)
+    }
+
+    #[cfg(feature = "remote-registry")]
+    fn keep_registry_skill(&mut self, resolved: ResolvedSkill) -> Result<SkillDescriptor, OpenSkillError> {
+        // Other versions of the skill stay loaded alongside this one.
+        self.registry_skills
+            .retain(|s| s.name != resolved.name || s.version != resolved.version);
+        self.registry_skills.push(resolved.clone());
+        self.discovery_completed();
+        self.registry
+            .get_version(&resolved.name, &resolved.version)
+            .or_else(|| self.registry.get(&resolved.name))
+            .map(|m| m.descriptor())
+            .ok_or(OpenSkillError::SkillNotFound(resolved.name))
+    }
+
+    /// Load skills from a specific directory (for testing or custom paths).
+    pub fn load_from_directory<P: AsRef<Path>>(
+        &mut self,
+        dir: P,
+    ) -> Result<Vec<SkillDescriptor>, OpenSkillError> {
+        self.registry.clear();
+        self.registry.scan_explicit(dir)?;
+        self.discovery_completed();
+        Ok(self.registry.list())
+    }
+
+    /// Lock every loaded skill version: its ID, version, source and content
+    /// hash (see [`Lockfile`]).
+    pub fn generate_lockfile(&self) -> Result<Lockfile, OpenSkillError> {
+        let skills = self
+            .registry
+            .all_versions()
+            .map(|metadata| LockedSkill::from_metadata(metadata, self.resolved_source(metadata)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Lockfile::new(skills))
+    }
+
+    /// Write [`Self::generate_lockfile`] to `path`, conventionally
+    /// [`LOCKFILE_NAME`] in the project root.
+    pub fn write_lockfile<P: AsRef<Path>>(&self, path: P) -> Result<Lockfile, OpenSkillError> {
+        let lockfile = self.generate_lockfile()?;
+        lockfile.write(path.as_ref())?;
+        Ok(lockfile)
+    }
+
+    /// Compare the loaded skills with the lockfile at `path`. Returns every
+    /// skill that is missing, unlocked, or differs in source or content;
+    /// empty when the loaded skills match the lockfile.
+    pub fn verify_lockfile<P: AsRef<Path>>(&self, path: P) -> Result<Vec<LockfileDrift>, OpenSkillError> {
+        let expected = Lockfile::read(path.as_ref())?;
+        Ok(expected.diff(&self.generate_lockfile()?))
+    }
+
+    /// Lockfile source of a skill loaded from the remote registry or OCI.
+    #[cfg(feature = "remote-registry")]
+    fn resolved_source(&self, metadata: &SkillMetadata) -> Option<String> {
+        self.registry_skills
+            .iter()
+            .find(|s| s.name == metadata.id && s.path == metadata.root)
+            .map(|s| s.source.clone())
+    }
+
+    #[cfg(not(feature = "remote-registry"))]
+    fn resolved_source(&self, _metadata: &SkillMetadata) -> Option<String> {
+        None
+    }
+
+    /// List all discovered skills (progressive disclosure - descriptors only).
+    pub fn list_skills(&self) -> Vec<SkillDescriptor> {
+        self.registry.list()
+    }
+
+    /// Display metadata of a skill for pickers in IDEs and web UIs:
+    /// description, author, version, tags, example invocations (from the
+    /// `examples:` frontmatter key or `EXAMPLES.md`) and README. Only reads
+    /// the skill directory; the skill is not activated.
+    pub fn get_skill_card(&self, skill_id: &str) -> Result<SkillCard, OpenSkillError> {
+        let metadata = self
+            .registry
+            .get(skill_id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
+        Ok(skill_card::build(metadata))
+    }
+
+    /// Skills relevant to `query`, best first, for shortlisting before
+    /// prompting. Matches the id, description and action capabilities
+    /// fuzzily (typos, prefixes) and, with an [`Embedder`] set, by meaning.
+    pub fn search_skills(&self, query: &str) -> Result<Vec<SkillMatch>, OpenSkillError> {
+        self.search_skills_with(query, &SearchOptions::default())
+    }
+
+    /// [`Self::search_skills`] with a result limit and minimum score.
+    pub fn search_skills_with(
+        &self,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<SkillMatch>, OpenSkillError> {
+        self.search.search(self.registry.all(), query, options)
+    }
+
+    /// Blend embedding similarity into [`Self::search_skills`].
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.set_embedder(Some(embedder));
+        self
+    }
+
+    /// Set or remove (`None`) the search embedder.
+    pub fn set_embedder(&mut self, embedder: Option<Arc<dyn Embedder>>) {
+        self.search.set_embedder(embedder);
+    }
+
+    /// Every loaded version of `skill_id`, lowest first. [`Self::list_skills`]
+    /// shows only the version a bare id selects.
+    pub fn list_skill_versions(&self, skill_id: &str) -> Vec<SkillDescriptor> {
+        self.registry.versions(skill_id)
+    }
+
+    /// Skill versions found in more than one directory. Unqualified ids load
+    /// the last candidate; `namespace:id` (e.g. `personal:code-review`) or
+    /// `directory:id` (e.g. `repoB/.claude:code-review`) selects another one.
+    pub fn skill_conflicts(&self) -> Vec<SkillConflict> {
+        self.registry.conflicts()
+    }
+
+    /// Get discovery warnings (name overrides, description fallbacks, etc.).
+    pub fn get_discovery_warnings(&self) -> &[String] {
+        self.registry.get_discovery_warnings()
+    }
+
+    /// Get loading errors encountered during discovery.
+    pub fn get_loading_errors(&self) -> &HashMap<String, String> {
+        self.registry.get_loading_errors()
     }
 
     /// List all declared actions from all skills (capability/action model).
@@ -666,7 +2393,10 @@ impl OpenSkillRuntime {
 
         let mut prompt = String::from("You have access to the following skills:\n\n");
         for skill in skills {
-            prompt.push_str(&format!("- {}: {}\n", skill.id, skill.description));
+            prompt.push_str(&format!(
+                "- {} [{}]: {}\n",
+                skill.id, skill.kind, skill.description
+            ));
         }
         prompt.push_str(
             "\nTo use a skill, activate it when the user's request matches the skill's purpose.",
@@ -677,7 +2407,7 @@ impl OpenSkillRuntime {
     /// Format available skill metadata as JSON for structured system prompts.
     ///
     /// Returns a JSON string like:
-    /// {"skills":[{"id":"code-review","description":"...","location":"project","user_invocable":true}]}
+    /// {"skills":[{"id":"code-review","description":"...","location":"project","user_invocable":true,"kind":"instruction_only"}]}
     pub fn get_system_prompt_metadata_json(&self) -> Result<String, OpenSkillError> {
         let skills: Vec<SkillDescriptor> = self
             .list_skills()
@@ -696,6 +2426,7 @@ impl OpenSkillRuntime {
                     "description": skill.description,
                     "location": skill.location,
                     "user_invocable": skill.user_invocable,
+                    "kind": skill.kind,
                 })
             }).collect::<Vec<_>>()
         });
@@ -768,8 +2499,9 @@ impl OpenSkillRuntime {
                     .map(|r| format!(" (requires: {})", r))
                     .unwrap_or_default();
                 prompt.push_str(&format!(
-                    "- **{}**: {}{}\n",
+                    "- **{}** [{}]: {}{}\n",
                     skill.id,
+                    skill.kind,
                     skill.description,
                     req_note
                 ));
@@ -792,6 +2524,7 @@ When a user's request matches a skill's capabilities:
      - For WASM modules, pass JSON input: `run_skill_script("skill-id", "wasm/skill.wasm", null, '{"action": "..."}')`
      - Example: If skill-creator has `wasm/skill.wasm` for creating skills, use: `run_skill_script("skill-creator", "wasm/skill.wasm", null, '{"action": "init_skill", "skill_name": "my-skill", "path": "skills/public"}')`
    - For scripts you generate in the workspace: Use `run_sandboxed_bash()` to execute them
+   - Each skill above is tagged with its kind: `instruction_only` skills ship no scripts, so just follow their instructions; `script`, `wasm` and `hybrid` skills ship executables for `run_skill_script()`
 
 ## Important
 
@@ -872,6 +2605,9 @@ Example response:
 
     /// Activate a skill by ID (load full SKILL.md content).
     ///
+    /// `skill_id` may pin a version: `my-skill@2.1.0` or `my-skill@^2` (see
+    /// [`SkillReference`]); a bare id selects the highest stable version.
+    ///
     /// This implements the "activation" step of progressive disclosure:
     /// the full instructions are only loaded when the skill is activated.
     ///
@@ -892,6 +2628,7 @@ Example response:
         let skill = self.registry.load_full_skill(skill_id)?;
 
         validate_skill(&skill)?;
+        self.check_skill_signature(&skill)?;
 
         let python_interpreter = self
             .native_runner_config
@@ -906,14 +2643,18 @@ Example response:
         } else {
             Some(missing)
         };
-        Ok(LoadedSkill {
+        let loaded = LoadedSkill {
             id: skill.id.clone(),
             manifest: skill.manifest.clone(),
             instructions: skill.instructions.clone(),
             location: skill.location.clone(),
             requires: skill.manifest.requires.clone(),
             missing_dependencies,
-        })
+        };
+        self.events.emit(|| RuntimeEvent::SkillActivated {
+            skill_id: loaded.id.clone(),
+        });
+        Ok(loaded)
     }
 
     /// Start a skill execution session for instruction-based workflows.
@@ -959,6 +2700,25 @@ Example response:
         let skill = self.registry.load_full_skill(skill_id)?;
 
         validate_skill(&skill)?;
+        self.check_skill_signature(&skill)?;
+
+        // Copy the skill's templates in before the agent starts working
+        let workspace_files = if skill.manifest.workspace_files.is_empty() {
+            Vec::new()
+        } else {
+            let workspace = self.get_workspace_dir()?;
+            let imported = workspace_import::populate(
+                &workspace,
+                &skill.root,
+                &skill.manifest.workspace_files,
+                self.max_import_bytes,
+                self.blob_store.as_ref(),
+            )?;
+            for entry in &imported {
+                self.record_import(entry);
+            }
+            imported
+        };
 
         // Fork is created HERE, after skill is loaded
         // This isolates execution outputs, not instruction comprehension
@@ -975,7 +2735,8 @@ Example response:
             is_forked,
             input.unwrap_or(Value::Null),
             context,
-        ))
+        )
+        .with_workspace_files(workspace_files))
     }
 
     /// Finish a skill execution session and return an ExecutionResult.
@@ -985,12 +2746,15 @@ Example response:
     pub fn finish_skill_session(
         &mut self,
         mut session: SkillExecutionSession,
-        output: Value,
-        stdout: String,
-        stderr: String,
-        exit_status: audit::ExecutionStatus,
+        mut output: Value,
+        mut stdout: String,
+        mut stderr: String,
+        mut exit_status: audit::ExecutionStatus,
     ) -> Result<ExecutionResult, OpenSkillError> {
         let duration_ms = session.elapsed_ms();
+        if let Some(redactor) = &self.secret_redactor {
+            redactor.redact_execution(&mut output, &mut stdout, &mut stderr, &mut exit_status);
+        }
 
         // Capture outputs in forked context if applicable
         if session.is_forked() {
@@ -1001,7 +2765,7 @@ Example response:
 
         let audit = AuditRecord {
             skill_id: session.skill().id.clone(),
-            version: "1.0".to_string(),
+            version: session.skill().manifest.version().to_string(),
             input_hash: audit::hash_json_value(session.input()),
             output_hash: audit::hash_json_value(&output),
             start_time_ms: session.start_epoch_ms(),
@@ -1015,13 +2779,20 @@ Example response:
                 .as_ref()
                 .map(|c| c.sandbox_mode)
                 .unwrap_or(SandboxMode::Enforce),
+            sandbox_violations: Vec::new(),
+            fuel_consumed: None,
+            network_requests: Vec::new(),
+            host_calls: Vec::new(),
+            capability_downgrades: Vec::new(),
+            attempt: 1,
+            traceparent: None,
         };
 
         self.audit_sink.record(&audit);
 
-        if session.is_forked() {
+        let result = if session.is_forked() {
             let summary = session.summarize_fork();
-            Ok(ExecutionResult {
+            ExecutionResult {
                 output: serde_json::json!({
                     "summary": summary,
                     "context_id": session.context_id().unwrap_or(""),
@@ -1030,14 +2801,30 @@ Example response:
                 stdout: summary.clone(),
                 stderr: String::new(),
                 audit,
-            })
+                setup_timings: SetupTimings::default(),
+                artifacts: Vec::new(),
+                capabilities_needed: Vec::new(),
+            }
         } else {
-            Ok(ExecutionResult {
+            ExecutionResult {
                 output,
                 stdout,
                 stderr,
                 audit,
-            })
+                setup_timings: SetupTimings::default(),
+                artifacts: Vec::new(),
+                capabilities_needed: Vec::new(),
+            }
+        };
+
+        let skill_id = session.skill().id.clone();
+        let version = session.skill().manifest.version();
+        match self.registry.get_version(&skill_id, version).map(|m| m.root.clone()) {
+            Some(root) => {
+                let workspace_dir = self.get_workspace_dir().ok();
+                Ok(self.process_result(result, &skill_id, &root, workspace_dir.as_deref()))
+            }
+            None => Ok(result),
         }
     }
 
@@ -1045,12 +2832,126 @@ Example response:
     ///
     /// Returns the effective tools list (approved tools) for executor sandbox config.
     /// Tools that fall through to "prompt" are delegated to the PermissionManager callback.
+    /// Tools that are not granted are added to `downgrades`.
     fn resolve_skill_permissions(
         &self,
+        skill: &Skill,
+        skill_id: &str,
+        skill_allowed_tools: &[String],
+        scope: &RequestScope,
+        downgrades: &mut Vec<CapabilityDowngrade>,
+    ) -> Result<Vec<String>, OpenSkillError> {
+        let policy = self.policy_for(&skill.id, &skill.root, &skill.location);
+        self.resolve_tools(&policy, skill_id, skill_allowed_tools, scope, downgrades)
+    }
+
+    /// The [`RequestScope`] of running `target` in a skill.
+    fn request_scope(
+        &self,
+        skill_root: &Path,
+        entrypoint: Option<&SkillEntrypoint>,
+        target: &ExecutionTarget,
+        workspace: Option<PathBuf>,
+    ) -> RequestScope {
+        let (path, args) = match target {
+            ExecutionTarget::Auto => {
+                match entrypoint::resolve_entrypoint(skill_root, entrypoint, None, &self.entrypoint_config) {
+                    Ok(EntrypointResolution {
+                        selected: Some(path),
+                        args,
+                        ..
+                    }) => (Some(path), args),
+                    _ => (None, Vec::new()),
+                }
+            }
+            ExecutionTarget::Path { path, args } | ExecutionTarget::Script { path, args } => {
+                (Some(path.clone()), args.clone())
+            }
+            ExecutionTarget::Wasm { path } => (Some(path.clone()), Vec::new()),
+        };
+        RequestScope {
+            skill_root: skill_root.to_path_buf(),
+            command: path.map(|path| std::iter::once(path).chain(args).collect::<Vec<_>>().join(" ")),
+            workspace,
+        }
+    }
+
+    /// Context of a request for `tool`: the execution's `workspace`, the
+    /// `write_paths` the tool makes writable and, for tools that spawn
+    /// processes, the `command` that runs.
+    fn tool_request_context(&self, tool: &str, scope: &RequestScope) -> HashMap<String, String> {
+        let mut context = HashMap::new();
+        if let Some(workspace) = &scope.workspace {
+            context.insert("workspace".to_string(), workspace.display().to_string());
+        }
+        let Some(capabilities) = self.capability_mapping.get(tool) else {
+            return context;
+        };
+        if !capabilities.write.is_empty() {
+            let write_paths: Vec<String> = capabilities
+                .write
+                .iter()
+                .map(|path| match path.as_str() {
+                    "." => scope.skill_root.display().to_string(),
+                    path => scope.skill_root.join(path).display().to_string(),
+                })
+                .collect();
+            context.insert("write_paths".to_string(), write_paths.join(", "));
+        }
+        if let (true, Some(command)) = (capabilities.process, &scope.command) {
+            context.insert("command".to_string(), command.clone());
+        }
+        context
+    }
+
+    /// The host policy narrowed to the policy-file rules matching a skill
+    /// (see [`HostPolicy::for_skill`]).
+    fn policy_for(
+        &self,
+        skill_id: &str,
+        skill_root: &Path,
+        location: &SkillLocation,
+    ) -> std::borrow::Cow<'_, HostPolicy> {
+        let base = self.base_policy(skill_root, location);
+        if !base.has_scoped_rules() {
+            return std::borrow::Cow::Borrowed(base);
+        }
+        // Hashing the skill is only worth it if a rule depends on the result.
+        let verified = base.has_trust_rules()
+            && !self.trusted_keys.is_empty()
+            && matches!(
+                verify_skill(skill_root, &self.trusted_keys),
+                SignatureStatus::Verified { .. }
+            );
+        let trust = if verified {
+            TrustLevel::Verified
+        } else {
+            TrustLevel::Unverified
+        };
+        std::borrow::Cow::Owned(base.for_skill(skill_id, location, trust))
+    }
+
+    /// The policy of the project root a project or nested skill lives
+    /// under (the innermost one with a policy), or the host policy.
+    fn base_policy(&self, skill_root: &Path, location: &SkillLocation) -> &HostPolicy {
+        if !matches!(location, SkillLocation::Project | SkillLocation::Nested) {
+            return &self.host_policy;
+        }
+        self.project_root_policies
+            .iter()
+            .filter(|(root, _)| skill_root.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map_or(&self.host_policy, |(_, policy)| policy)
+    }
+
+    fn resolve_tools(
+        &self,
+        policy: &HostPolicy,
         skill_id: &str,
         skill_allowed_tools: &[String],
+        scope: &RequestScope,
+        downgrades: &mut Vec<CapabilityDowngrade>,
     ) -> Result<Vec<String>, OpenSkillError> {
-        let policy = &self.host_policy;
         let mut effective_tools = Vec::new();
 
         for tool in skill_allowed_tools {
@@ -1060,19 +2961,33 @@ Example response:
                 }
                 ToolDecision::Denied => {
                     // Tool denied by host policy — not granted to sandbox
+                    downgrades.push(policy_denial(policy, tool));
                 }
                 ToolDecision::Prompt => {
                     // Delegate to existing PermissionManager callback
                     if is_risky_tool(tool) {
-                        let granted = self.permission_manager.check_permission(
+                        let decision = self.decide_permission(
                             skill_id,
                             tool,
                             format!("Execute {} operations", tool),
                             get_risk_level(tool),
-                            std::collections::HashMap::new(),
+                            self.tool_request_context(tool, scope),
                         )?;
-                        if granted {
-                            effective_tools.push(tool.clone());
+                        match decision {
+                            PermissionDecision::Allowed => effective_tools.push(tool.clone()),
+                            PermissionDecision::AllowedInWorkspace => {
+                                effective_tools.push(tool.clone());
+                                downgrades.push(CapabilityDowngrade::new(
+                                    tool,
+                                    DowngradeReason::WorkspaceOnly,
+                                    "the permission callback allowed writes to the workspace only",
+                                ));
+                            }
+                            PermissionDecision::Denied => downgrades.push(CapabilityDowngrade::new(
+                                tool,
+                                DowngradeReason::PermissionDenied,
+                                "the permission callback refused it",
+                            )),
                         }
                     } else {
                         // Non-risky tool in prompt mode: auto-approve
@@ -1085,9 +3000,7 @@ Example response:
         // Also include allow_overrides that aren't already in the list
         // (host may grant tools beyond what the skill declares)
         for tool in policy.allow_overrides() {
-            if !policy.deny_overrides().contains(tool)
-                && !effective_tools.iter().any(|t| t == tool)
-            {
+            if !policy.is_denied(tool) && !effective_tools.iter().any(|t| t == tool) {
                 effective_tools.push(tool.clone());
             }
         }
@@ -1095,6 +3008,180 @@ Example response:
         Ok(effective_tools)
     }
 
+    /// `tools` without the ones the host policy denies outright, which are
+    /// added to `downgrades`.
+    fn filter_denied_tools(
+        &self,
+        skill: &Skill,
+        tools: &[String],
+        downgrades: &mut Vec<CapabilityDowngrade>,
+    ) -> Vec<String> {
+        let policy = self.policy_for(&skill.id, &skill.root, &skill.location);
+        let mut effective_tools: Vec<String> = Vec::new();
+        for tool in tools {
+            if policy.is_denied(tool) {
+                downgrades.push(policy_denial(&policy, tool));
+            } else if !effective_tools.contains(tool) {
+                effective_tools.push(tool.clone());
+            }
+        }
+        effective_tools
+    }
+
+    /// Add the granted `tools` the capability mapping gives nothing to
+    /// `downgrades`.
+    fn record_unmapped_tools(&self, tools: &[String], downgrades: &mut Vec<CapabilityDowngrade>) {
+        for tool in tools {
+            let grants_nothing = self
+                .capability_mapping
+                .get(tool)
+                .is_none_or(|capabilities| *capabilities == ToolCapabilities::default());
+            if grants_nothing {
+                downgrades.push(CapabilityDowngrade::new(
+                    tool,
+                    DowngradeReason::Unmapped,
+                    "the capability mapping grants nothing for it",
+                ));
+            }
+        }
+    }
+
+    /// Decide a skill's `sandbox:` section through the host policy.
+    ///
+    /// Returns the section only if approved; the native runner merges what
+    /// is left on the skill manifest into the sandbox profile.
+    fn resolve_skill_sandbox(
+        &self,
+        skill_id: &str,
+        sandbox: Option<SandboxConfig>,
+    ) -> Result<Option<SandboxConfig>, OpenSkillError> {
+        let Some(sandbox) = sandbox.filter(|s| !s.is_empty()) else {
+            return Ok(None);
+        };
+        let policy = match self.registry.get(skill_id) {
+            Some(metadata) => self.base_policy(&metadata.root, &metadata.location),
+            None => &self.host_policy,
+        };
+        let approved = match policy.skill_sandbox {
+            Fallback::Allow => true,
+            Fallback::Deny => false,
+            Fallback::Prompt => {
+                let mut context = std::collections::HashMap::new();
+                context.insert("read_paths".to_string(), sandbox.read_paths.join(", "));
+                context.insert("write_paths".to_string(), sandbox.write_paths.join(", "));
+                context.insert("allow_network".to_string(), sandbox.allow_network.to_string());
+                context.insert("allow_process".to_string(), sandbox.allow_process.to_string());
+                self.request_permission(
+                    skill_id,
+                    "Sandbox",
+                    "Extend the native sandbox profile".to_string(),
+                    RiskLevel::High,
+                    context,
+                )?
+            }
+        };
+        Ok(approved.then_some(sandbox))
+    }
+
+    /// Ask the permission manager; an answer confining writes to the
+    /// workspace does not grant what is asked here.
+    fn request_permission(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        description: String,
+        risk_level: RiskLevel,
+        context: HashMap<String, String>,
+    ) -> Result<bool, OpenSkillError> {
+        let decision = self.decide_permission(skill_id, tool, description, risk_level, context)?;
+        Ok(decision == PermissionDecision::Allowed)
+    }
+
+    /// Ask the permission manager, adding the skill's location to the
+    /// request context for policy callbacks such as `CedarPolicyCallback`.
+    fn decide_permission(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        description: String,
+        risk_level: RiskLevel,
+        context: HashMap<String, String>,
+    ) -> Result<PermissionDecision, OpenSkillError> {
+        self.decide_permission_scoped(skill_id, tool, tool, description, risk_level, context)
+    }
+
+    /// [`Self::decide_permission`], granting `grant_key` (a tool or an
+    /// `allowed-tools` pattern) on "allow always".
+    fn decide_permission_scoped(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        grant_key: &str,
+        description: String,
+        risk_level: RiskLevel,
+        mut context: HashMap<String, String>,
+    ) -> Result<PermissionDecision, OpenSkillError> {
+        let span = telemetry::permission_span(skill_id, tool, risk_level);
+        let _entered = span.enter();
+        let metadata = self.registry.get(skill_id);
+        if let Some(metadata) = metadata {
+            context
+                .entry("location".to_string())
+                .or_insert_with(|| metadata.location.to_string());
+            if let Some(root) = &metadata.project_root {
+                context
+                    .entry("project_root".to_string())
+                    .or_insert_with(|| root.display().to_string());
+            }
+        }
+        let decision = self.permission_manager.decide_permission(
+            skill_id,
+            metadata.map(|m| m.root.as_path()),
+            tool,
+            grant_key,
+            description,
+            risk_level,
+            context,
+        );
+        span.record("decision", telemetry::decision_label(&decision));
+        decision
+    }
+
+    /// Decide a skill's `writes-skill-root:` request through the permission
+    /// callback. Without approval, including when there is no callback, the
+    /// skill root stays read-only.
+    fn resolve_skill_root_write(
+        &self,
+        skill_id: &str,
+        skill_root: &Path,
+        requested: bool,
+    ) -> Result<bool, OpenSkillError> {
+        if !requested {
+            return Ok(false);
+        }
+        let mut context = std::collections::HashMap::new();
+        context.insert("skill_root".to_string(), skill_root.display().to_string());
+        self.request_permission(
+            skill_id,
+            permission_callback::WRITE_SKILL_ROOT,
+            "Write to the skill's own directory".to_string(),
+            RiskLevel::High,
+            context,
+        )
+    }
+
+    /// Host overrides cannot grant network access the host policy denies
+    /// through a `WebSearch`/`Fetch` deny override.
+    fn check_sandbox_overrides(&self, overrides: &SandboxOverrides) -> Result<(), OpenSkillError> {
+        let denied = self.host_policy.deny_overrides();
+        if overrides.allow_network && (denied.contains("WebSearch") || denied.contains("Fetch")) {
+            return Err(OpenSkillError::PermissionDenied(
+                "network override is denied by host policy".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Check permission for a tool call for a given skill.
     ///
     /// Uses the host policy resolution algorithm:
@@ -1102,20 +3189,56 @@ Example response:
     /// 2. allow_overrides → approved
     /// 3. trust + skill allowed-tools → approved
     /// 4. fallback (allow/deny/prompt)
+    ///
+    /// Entries with an argument pattern, such as `Bash(npm run test:*)`,
+    /// never cover a call without argument; see
+    /// [`Self::check_tool_permission_with_argument`].
     pub fn check_tool_permission(
         &self,
         skill_id: &str,
         tool: &str,
         description: Option<String>,
         context: std::collections::HashMap<String, String>,
+    ) -> Result<bool, OpenSkillError> {
+        self.check_tool_permission_with_argument(skill_id, tool, None, description, context)
+    }
+
+    /// [`Self::check_tool_permission`] for a call with a concrete command
+    /// or path.
+    ///
+    /// An `allowed-tools` entry with an argument pattern only approves
+    /// calls whose argument matches it (see [`ToolPattern`]); other calls
+    /// fall through to the fallback. An "allow always" answer to a call an
+    /// entry's pattern matched grants that pattern, not the whole tool. A
+    /// callback answer confining the tool to the workspace only approves
+    /// calls whose argument is a path inside the workspace.
+    pub fn check_tool_permission_with_argument(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        argument: Option<&str>,
+        description: Option<String>,
+        context: std::collections::HashMap<String, String>,
     ) -> Result<bool, OpenSkillError> {
         let metadata = self
             .registry
             .get(skill_id)
             .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
 
-        let skill_allowed = metadata.manifest.get_allowed_tools();
-        let policy = &self.host_policy;
+        // The declared tools that cover this call
+        let matched: Vec<ToolPattern> = metadata
+            .manifest
+            .allowed_tool_patterns()
+            .into_iter()
+            .filter(|pattern| pattern.allows(tool, argument))
+            .collect();
+        // Grants for a call only a pattern covers are scoped to that pattern
+        let grant_key = match matched.iter().find(|p| p.argument.is_some()) {
+            Some(pattern) if matched.iter().all(|p| p.argument.is_some()) => pattern.to_string(),
+            _ => tool.to_string(),
+        };
+        let skill_allowed: Vec<String> = matched.into_iter().map(|pattern| pattern.tool).collect();
+        let policy = self.policy_for(&metadata.id, &metadata.root, &metadata.location);
 
         match policy.resolve_tool(tool, &skill_allowed) {
             ToolDecision::Approved => Ok(true),
@@ -1125,11 +3248,36 @@ Example response:
             ))),
             ToolDecision::Prompt => {
                 if is_risky_tool(tool) {
-                    let desc = description
-                        .unwrap_or_else(|| format!("Execute {} operations", tool));
-                    self.permission_manager.check_permission(
-                        skill_id, tool, desc, get_risk_level(tool), context,
-                    )
+                    let desc = description.unwrap_or_else(|| match argument {
+                        Some(argument) => format!("{}: {}", tool, argument),
+                        None => format!("Execute {} operations", tool),
+                    });
+                    let mut context = context;
+                    if let Some(argument) = argument {
+                        context
+                            .entry("argument".to_string())
+                            .or_insert_with(|| argument.to_string());
+                    }
+                    let workspace = self.get_workspace_dir().ok();
+                    if let Some(workspace) = &workspace {
+                        context
+                            .entry("workspace".to_string())
+                            .or_insert_with(|| workspace.display().to_string());
+                    }
+                    match self.decide_permission_scoped(
+                        skill_id,
+                        tool,
+                        &grant_key,
+                        desc,
+                        get_risk_level(tool),
+                        context,
+                    )? {
+                        PermissionDecision::Allowed => Ok(true),
+                        PermissionDecision::AllowedInWorkspace => Ok(argument
+                            .zip(workspace.as_deref())
+                            .is_some_and(|(argument, workspace)| path_in_workspace(argument, workspace))),
+                        PermissionDecision::Denied => Ok(false),
+                    }
                 } else {
                     Ok(true)
                 }
@@ -1141,6 +3289,8 @@ Example response:
     ///
     /// Note: Most Claude Skills are instructional (Claude follows the instructions).
     /// WASM execution is for skills that include sandboxed script execution.
+    /// A skill without an auto-detected script or WASM entry point returns
+    /// `ExecutionStatus::InstructionOnly` with its instructions in `output`.
     ///
     /// If a permission callback is configured, risky operations (Write, Bash, etc.)
     /// will require user approval before execution.
@@ -1148,6 +3298,8 @@ Example response:
     /// If the skill has `context: fork`, execution happens in an isolated context
     /// and only a summary is returned. Use `execute_skill_with_context` for explicit
     /// context management.
+    ///
+    /// Like [`Self::activate_skill`], `skill_id` accepts `id@constraint` references.
     pub fn execute_skill(
         &mut self,
         skill_id: &str,
@@ -1190,47 +3342,412 @@ Example response:
         options: ExecutionOptions,
         parent_context: &ExecutionContext,
     ) -> Result<ExecutionResult, OpenSkillError> {
+        let pending =
+            self.begin_next_attempt(NextAttempt::first(skill_id, options, parent_context))?;
+        self.complete_execution(pending)
+    }
+
+    /// Finish `pending`, running it on the calling thread if it has not
+    /// run, and the attempts after it that its retry policy asks for.
+    /// Returns the last result.
+    fn complete_execution(
+        &mut self,
+        mut pending: PendingExecution,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        loop {
+            match self.finish_pending_execution(pending) {
+                ExecutionStep::Finished(result) => return result,
+                ExecutionStep::Retry { delay, next } => {
+                    std::thread::sleep(delay);
+                    pending = self.begin_next_attempt(next)?;
+                }
+            }
+        }
+    }
+
+    /// Like [`execute_skill`](Self::execute_skill), but runs the sandboxed
+    /// process or WASM component on tokio's blocking pool instead of the
+    /// calling thread. Cancelling `cancellation`, or dropping the future,
+    /// kills the child process or interrupts the WASM component.
+    #[cfg(feature = "tokio")]
+    pub async fn execute_skill_async(
+        &mut self,
+        skill_id: &str,
+        options: ExecutionOptions,
+        cancellation: CancellationHandle,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let mut next = NextAttempt::first(skill_id, options, &ExecutionContext::new())
+            .with_cancellation(cancellation);
+        loop {
+            self.ask_permissions_async(
+                skill_id,
+                next.options.effective_tools.is_none(),
+                &ExecutionTarget::Auto,
+                None,
+            )
+            .await?;
+            let pending = self.begin_next_attempt(next);
+            self.permission_manager.clear_answers();
+            let mut pending = pending?;
+            pending.run_blocking().await;
+            match self.finish_pending_execution(pending) {
+                ExecutionStep::Finished(result) => return result,
+                ExecutionStep::Retry { delay, next: retry } => {
+                    // The blocking pool, so callers need no tokio time driver.
+                    let _ = tokio::task::spawn_blocking(move || std::thread::sleep(delay)).await;
+                    next = retry;
+                }
+            }
+        }
+    }
+
+    /// Resolve `skill_id` and its permissions like [`Self::execute_skill`],
+    /// without running it. The returned [`PendingExecution`] runs with no
+    /// borrow of the runtime, so a host sharing the runtime behind a lock
+    /// can release it meanwhile; hand it back to
+    /// [`Self::finish_pending_execution`] afterwards.
+    pub fn begin_execution(
+        &mut self,
+        skill_id: &str,
+        options: ExecutionOptions,
+    ) -> Result<PendingExecution, OpenSkillError> {
+        self.begin_next_attempt(NextAttempt::first(skill_id, options, &ExecutionContext::new()))
+    }
+
+    /// Begin the attempt an [`ExecutionStep::Retry`] asked for.
+    pub fn begin_next_attempt(&mut self, next: NextAttempt) -> Result<PendingExecution, OpenSkillError> {
+        let (mut prepared, exec_options) = self.prepare_skill_execution(
+            &next.skill_id,
+            &next.options,
+            &next.parent_context,
+            next.cancellation.clone(),
+        )?;
+        prepared.attempt = next.attempt;
+        let workspace = next.workspace_dir.clone();
+        let pending = PendingExecution::skill(prepared, exec_options, self.wasm_engine.clone(), next);
+        Ok(match workspace {
+            Some(workspace) => pending.with_workspace(workspace),
+            None => pending,
+        })
+    }
+
+    /// Like [`Self::begin_execution`], for
+    /// [`Self::run_skill_target_with_overrides`]. Targets are not retried.
+    pub fn begin_target_execution(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        sandbox_overrides: SandboxOverrides,
+    ) -> Result<PendingExecution, OpenSkillError> {
+        let (prepared, options) = self.prepare_target_execution(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            sandbox_overrides,
+            None,
+            None,
+        )?;
+        Ok(PendingExecution::target(prepared, options, self.wasm_engine.clone()))
+    }
+
+    /// Audit a [`PendingExecution`], running it first if it has not run,
+    /// and return its result, or the next attempt its retry policy asks for.
+    /// A cancelled execution is not retried.
+    pub fn finish_pending_execution(&mut self, pending: PendingExecution) -> ExecutionStep {
+        let (prepared, execution, next) = pending.into_parts();
+        let attempt = prepared.attempt;
+        let cancelled = prepared.running.cancellation().is_cancelled();
+        let result = self.finish_execution(prepared, execution);
+        let next = next.filter(|_| !cancelled);
+        let retry = next.as_ref().and_then(|next| next_retry(next.options.retry.as_ref(), attempt, &result));
+        match (retry, next) {
+            (Some(delay), Some(next)) => ExecutionStep::Retry { delay, next },
+            _ => ExecutionStep::Finished(result),
+        }
+    }
+
+    /// Execute several skills concurrently, e.g. one validation per document
+    /// in a batch. At most [`Self::with_max_parallelism`] run at once.
+    ///
+    /// Each job gets its own workspace, `<workspace>/batch/<n>-<skill>`,
+    /// so concurrent runs of the same skill cannot overwrite each other's
+    /// files. Results come back in job order; a job that fails to resolve
+    /// or run yields its error without stopping the others.
+    pub fn execute_many(
+        &mut self,
+        jobs: Vec<(String, ExecutionOptions)>,
+    ) -> Vec<Result<ExecutionResult, OpenSkillError>> {
+        let main_context = ExecutionContext::new();
+        let mut pending_jobs = Vec::with_capacity(jobs.len());
+        for (index, (skill_id, options)) in jobs.into_iter().enumerate() {
+            pending_jobs.push(
+                self.begin_next_attempt(NextAttempt::first(&skill_id, options, &main_context))
+                    .and_then(|pending| {
+                        let workspace = self.batch_workspace(index, pending.skill_id())?;
+                        Ok(pending.with_workspace(workspace))
+                    }),
+            );
+        }
+
+        let runnable: Vec<&mut PendingExecution> =
+            pending_jobs.iter_mut().filter_map(|job| job.as_mut().ok()).collect();
+        let workers = self
+            .max_parallelism
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            .min(runnable.len());
+        let queue = Mutex::new(runnable.into_iter());
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let next = queue.lock().unwrap_or_else(PoisonError::into_inner).next();
+                    let Some(pending) = next else {
+                        break;
+                    };
+                    pending.run();
+                });
+            }
+        });
+
+        // Retries run one after another, after the batch.
+        pending_jobs
+            .into_iter()
+            .map(|job| self.complete_execution(job?))
+            .collect()
+    }
+
+    /// Workspace of job `index` in [`Self::execute_many`].
+    fn batch_workspace(&self, index: usize, skill_id: &str) -> Result<PathBuf, OpenSkillError> {
+        let dir = self
+            .get_workspace_dir()?
+            .join("batch")
+            .join(format!("{}-{}", index, skill_id));
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Resolve a skill and its permissions for [`execute_skill_with_context`]
+    /// and register the execution as running, inside its tracing span.
+    fn prepare_skill_execution(
+        &mut self,
+        skill_id: &str,
+        options: &ExecutionOptions,
+        parent_context: &ExecutionContext,
+        cancellation: Option<CancellationHandle>,
+    ) -> Result<(PreparedExecution, ExecOpts), OpenSkillError> {
+        let span = telemetry::execution_span(skill_id, "auto", options.traceparent.as_deref());
+        let prepared = span.in_scope(|| {
+            self.resolve_skill_execution(skill_id, options, parent_context, cancellation)
+        });
+        let (mut prepared, mut exec_options) =
+            prepared.inspect_err(|e| telemetry::fail_execution_span(&span, e))?;
+        if let Some(traceparent) =
+            telemetry::sandbox_traceparent(&span, options.traceparent.as_deref())
+        {
+            exec_options.sandbox_overrides =
+                exec_options.sandbox_overrides.with_env("TRACEPARENT", traceparent);
+        }
+        prepared.span = span;
+        Ok((prepared, exec_options))
+    }
+
+    fn resolve_skill_execution(
+        &mut self,
+        skill_id: &str,
+        options: &ExecutionOptions,
+        parent_context: &ExecutionContext,
+        cancellation: Option<CancellationHandle>,
+    ) -> Result<(PreparedExecution, ExecOpts), OpenSkillError> {
         // Ensure registry is loaded
         if self.registry.is_empty() {
             // Use discover_skills to load from all configured locations
             self.discover_skills()?;
         }
+        self.check_sandbox_overrides(&options.sandbox_overrides)?;
+        if let Some(traceparent) = &options.traceparent {
+            check_traceparent(traceparent)?;
+        }
 
         // Load full skill (with instructions) for execution
-        let skill = self.registry.load_full_skill(skill_id)?;
+        let mut skill = self.registry.load_full_skill(skill_id)?;
 
         validate_skill(&skill)?;
+        self.check_skill_signature(&skill)?;
+        // `skill_id` may carry a version constraint; grants and history use the bare id.
+        let id = skill.id.clone();
+        let skill_id = id.as_str();
 
         // Check if skill should run in forked context
         let is_forked = skill.manifest.is_forked();
-        let mut fork_context = if is_forked {
+        let fork_context = if is_forked {
             Some(parent_context.fork())
         } else {
             None
         };
 
-        // Resolve permissions through host policy
-        let allowed_tools = skill.manifest.get_allowed_tools();
-        let effective_tools = self.resolve_skill_permissions(skill_id, &allowed_tools)?;
-
-        let start = Instant::now();
-        let start_epoch = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_millis() as u64;
-
+        // Resolve permissions through host policy, unless the caller chose the tools
+        let workspace_dir = self.get_workspace_dir().ok();
+        let mut downgrades = Vec::new();
+        let effective_tools = match &options.effective_tools {
+            Some(tools) => self.filter_denied_tools(&skill, tools, &mut downgrades),
+            None => {
+                let allowed_tools = skill.manifest.get_allowed_tools();
+                let scope = self.request_scope(
+                    &skill.root,
+                    skill.manifest.entrypoint.as_ref(),
+                    &ExecutionTarget::Auto,
+                    workspace_dir.clone(),
+                );
+                self.resolve_skill_permissions(&skill, skill_id, &allowed_tools, &scope, &mut downgrades)?
+            }
+        };
+        self.record_unmapped_tools(&effective_tools, &mut downgrades);
+        skill.manifest.sandbox = self.resolve_skill_sandbox(skill_id, skill.manifest.sandbox.take())?;
+        skill.manifest.writes_skill_root =
+            self.resolve_skill_root_write(skill_id, &skill.root, skill.manifest.writes_skill_root)?;
+
+        let (timeout_ms, memory_mb) =
+            self.apply_auto_limits(skill_id, options.timeout_ms, options.memory_mb);
+        let running =
+            self.executions
+                .begin(&skill.id, "auto".to_string(), &self.session_id, cancellation);
+        let input_hash = audit::hash_json_value(&options.input.clone().unwrap_or(Value::Null));
+        let journal = self.journal_execution(&skill, "auto".to_string(), &input_hash);
+        self.events.emit(|| RuntimeEvent::ExecutionStarted {
+            execution_id: running.id(),
+            skill_id: skill.id.clone(),
+            target: "auto".to_string(),
+        });
         let exec_options = ExecOpts {
-            timeout_ms: options.timeout_ms,
-            memory_mb: options.memory_mb,
+            timeout_ms,
+            memory_mb,
+            cpu_quota: options.cpu_quota,
+            max_fuel: options.max_fuel,
             input: options.input.clone(),
             wasm_module: None,
-            workspace_dir: self.get_workspace_dir().ok(),
+            workspace_dir: workspace_dir.clone(),
             effective_tools,
+            capability_mapping: self.capability_mapping.clone(),
             native_runner_config: self.native_runner_config.clone(),
+            entrypoint_config: self.entrypoint_config.clone(),
+            sandbox_overrides: options.sandbox_overrides.clone(),
+            cancellation: Some(running.cancellation()),
+            dry_run: options.dry_run,
+            journal: journal.clone(),
+            workspace_only_writes: writes_workspace_only(&downgrades),
+            secret_redactor: self.secret_redactor.clone(),
         };
+        let snapshot = self.snapshot_workspace(workspace_dir.as_deref(), options.dry_run);
 
-        let execution = execute_skill(&skill, exec_options)?;
+        let prepared = PreparedExecution {
+            skill,
+            fork_context,
+            input_hash,
+            workspace_dir,
+            timeout_ms,
+            memory_mb,
+            start: Instant::now(),
+            start_epoch: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or(Duration::from_secs(0))
+                .as_millis() as u64,
+            running,
+            events: ExecutionEvents::default(),
+            downgrades,
+            attempt: 1,
+            journal,
+            snapshot,
+            convert_outputs: true,
+            traceparent: options.traceparent.clone(),
+            span: tracing::Span::none(),
+        };
+        Ok((prepared, exec_options))
+    }
+
+    /// Record a finished execution (history, audit, metrics) and build the
+    /// result returned to the caller.
+    fn finish_execution(
+        &mut self,
+        prepared: PreparedExecution,
+        execution: Result<executor::ExecutionArtifacts, OpenSkillError>,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let PreparedExecution {
+            skill,
+            mut fork_context,
+            input_hash,
+            workspace_dir,
+            timeout_ms,
+            memory_mb,
+            start,
+            start_epoch,
+            running,
+            events,
+            mut downgrades,
+            attempt,
+            journal: _journal,
+            snapshot,
+            convert_outputs,
+            traceparent,
+            span,
+        } = prepared;
+        let execution_id = running.id();
+        drop(running);
+        let finished = |status: &audit::ExecutionStatus, duration_ms: u64| {
+            RuntimeEvent::ExecutionFinished {
+                execution_id,
+                skill_id: skill.id.clone(),
+                status: status.clone(),
+                duration_ms,
+            }
+        };
+        let execution = match execution {
+            Ok(execution) => execution,
+            Err(e) => {
+                let e = match &self.secret_redactor {
+                    Some(redactor) => redactor.redact_error(e),
+                    None => e,
+                };
+                let duration_ms = start.elapsed().as_millis() as u64;
+                let status = audit::ExecutionStatus::Failed(e.to_string());
+                telemetry::finish_execution_span(&span, &status, attempt, duration_ms);
+                self.events.emit(|| finished(&status, duration_ms));
+                events.emit(|| ExecutionEvent::Finished {
+                    execution_id,
+                    status,
+                    duration_ms,
+                });
+                return Err(e);
+            }
+        };
+        let mut execution = self.resolve_binary_stdout(execution, &skill.id, workspace_dir.as_deref());
+        if let (Some(schema), audit::ExecutionStatus::Success) =
+            (&skill.manifest.output_schema, &execution.exit_status)
+        {
+            if let Some(violations) = output_schema::violations(schema, &execution.output) {
+                execution.exit_status = audit::ExecutionStatus::SchemaViolation(violations);
+            }
+        }
+        if let Some(redactor) = &self.secret_redactor {
+            redactor.redact_execution(
+                &mut execution.output,
+                &mut execution.stdout,
+                &mut execution.stderr,
+                &mut execution.exit_status,
+            );
+        }
         let duration_ms = start.elapsed().as_millis() as u64;
+        telemetry::finish_execution_span(&span, &execution.exit_status, attempt, duration_ms);
+        self.record_execution(&skill.id, &execution.exit_status, duration_ms, timeout_ms, memory_mb);
+        self.events.emit(|| finished(&execution.exit_status, duration_ms));
+        events.emit(|| ExecutionEvent::Finished {
+            execution_id,
+            status: execution.exit_status.clone(),
+            duration_ms,
+        });
 
         // Capture outputs in forked context if applicable
         if let Some(ref mut fork) = fork_context {
@@ -1253,10 +3770,15 @@ Example response:
             }
         }
 
+        downgrades.extend(execution.capability_downgrades.iter().cloned());
+        if matches!(execution.exit_status, audit::ExecutionStatus::DryRun) {
+            // The plan lists policy downgrades too, not just the platform's.
+            execution.output["capability_downgrades"] = serde_json::to_value(&downgrades)?;
+        }
         let audit = AuditRecord {
             skill_id: skill.id.clone(),
-            version: "1.0".to_string(), // Claude Skills don't have version in manifest
-            input_hash: audit::hash_json_value(&options.input.clone().unwrap_or(Value::Null)),
+            version: skill.manifest.version().to_string(),
+            input_hash,
             output_hash: audit::hash_json_value(&execution.output),
             start_time_ms: start_epoch,
             duration_ms,
@@ -1269,15 +3791,48 @@ Example response:
                 .as_ref()
                 .map(|c| c.sandbox_mode)
                 .unwrap_or(SandboxMode::Enforce),
+            sandbox_violations: execution.sandbox_violations.clone(),
+            fuel_consumed: execution.fuel_consumed,
+            network_requests: execution.network_requests.clone(),
+            host_calls: execution.host_calls.clone(),
+            capability_downgrades: downgrades,
+            attempt,
+            traceparent,
         };
 
         self.audit_sink.record(&audit);
+        self.sandbox_metrics.record(&execution.setup_timings);
+
+        let mut artifacts = snapshot.map(|snapshot| snapshot.changes()).unwrap_or_default();
+        if convert_outputs && matches!(execution.exit_status, audit::ExecutionStatus::Success) {
+            if let Some(workspace) = workspace_dir.as_deref() {
+                let converted = self.convert_artifacts(&skill, workspace, &artifacts, timeout_ms);
+                artifacts.extend(converted);
+            }
+        }
+
+        let capabilities_needed = if matches!(
+            audit.exit_status,
+            audit::ExecutionStatus::Success
+                | audit::ExecutionStatus::DryRun
+                | audit::ExecutionStatus::InstructionOnly
+        ) {
+            Vec::new()
+        } else {
+            capability_needed::capabilities_needed(
+                &audit.sandbox_violations,
+                &audit.network_requests,
+                &audit.host_calls,
+                &skill.manifest.get_allowed_tools(),
+                &self.capability_mapping,
+            )
+        };
 
         // For forked contexts, return only the summary
-        if let Some(mut fork) = fork_context {
+        let result = if let Some(mut fork) = fork_context {
             let summary = fork.summarize();
-            
-            Ok(ExecutionResult {
+
+            ExecutionResult {
                 output: serde_json::json!({
                     "summary": summary,
                     "context_id": fork.id(),
@@ -1286,16 +3841,171 @@ Example response:
                 stdout: summary.clone(),
                 stderr: String::new(), // Stderr is captured in fork, not returned
                 audit,
-            })
+                setup_timings: execution.setup_timings,
+                artifacts,
+                capabilities_needed,
+            }
         } else {
             // Normal execution - return full outputs
-            Ok(ExecutionResult {
+            ExecutionResult {
                 output: execution.output,
                 stdout: execution.stdout,
                 stderr: execution.stderr,
                 audit,
-            })
+                setup_timings: execution.setup_timings,
+                artifacts,
+                capabilities_needed,
+            }
+        };
+
+        self.enforce_artifact_retention(&skill.id, workspace_dir.as_deref());
+        Ok(self.process_result(result, &skill.id, &skill.root, workspace_dir.as_deref()))
+    }
+
+    /// Run the skill's and the host's output converters on `artifacts`;
+    /// returns the converted files.
+    fn convert_artifacts(
+        &mut self,
+        skill: &Skill,
+        workspace: &Path,
+        artifacts: &[Artifact],
+        timeout_ms: Option<u64>,
+    ) -> Vec<Artifact> {
+        // A skill's converters run in the skill itself.
+        let converters: Vec<OutputConverter> = skill
+            .manifest
+            .output_converters
+            .iter()
+            .map(|c| OutputConverter { skill: None, ..c.clone() })
+            .chain(self.output_converters.iter().cloned())
+            .filter(|c| output_converters::check(std::slice::from_ref(c)).is_ok())
+            .collect();
+        let mut converted = Vec::new();
+        for artifact in artifacts {
+            let Ok(input) = artifact.path.strip_prefix(workspace) else {
+                continue;
+            };
+            for converter in converters.iter().filter(|c| c.matches(artifact)) {
+                let skill_id = converter.skill.as_deref().unwrap_or(&skill.id);
+                let output = converter.output_path(input);
+                if let Some(file) =
+                    self.run_converter(skill_id, converter, workspace, input, &output, timeout_ms)
+                {
+                    converted.push(Artifact {
+                        converted_from: Some(artifact.path.clone()),
+                        ..file
+                    });
+                }
+            }
+        }
+        converted
+    }
+
+    /// Convert `input` into `output`, both relative to `workspace`; returns
+    /// the output file if the converter succeeded and wrote it.
+    fn run_converter(
+        &mut self,
+        skill_id: &str,
+        converter: &OutputConverter,
+        workspace: &Path,
+        input: &Path,
+        output: &Path,
+        timeout_ms: Option<u64>,
+    ) -> Option<Artifact> {
+        let target = ExecutionTarget::Path {
+            path: converter.run.clone(),
+            args: converter.command_args(input, output),
+        };
+        let (mut prepared, options) = self
+            .prepare_target_execution(
+                skill_id,
+                target,
+                timeout_ms,
+                Some(converter.input(input, output)),
+                Some(workspace.to_path_buf()),
+                SandboxOverrides::default(),
+                None,
+                None,
+            )
+            .ok()?;
+        prepared.convert_outputs = false;
+        let execution = run_skill_target(&prepared.skill, options, &self.wasm_engine);
+        let result = self.finish_execution(prepared, execution).ok()?;
+        if !matches!(result.audit.exit_status, audit::ExecutionStatus::Success) {
+            return None;
+        }
+        workspace_snapshot::describe(workspace.join(output))
+    }
+
+    /// Snapshot `workspace_dir` if artifacts are collected and the execution
+    /// can write to it.
+    fn snapshot_workspace(&self, workspace_dir: Option<&Path>, dry_run: bool) -> Option<WorkspaceSnapshot> {
+        workspace_dir
+            .filter(|_| self.collect_artifacts && !dry_run)
+            .map(WorkspaceSnapshot::capture)
+    }
+
+    /// Replace binary stdout with a reference (see `with_binary_output_diversion`).
+    fn resolve_binary_stdout(
+        &self,
+        mut execution: executor::ExecutionArtifacts,
+        skill_id: &str,
+        workspace_dir: Option<&Path>,
+    ) -> executor::ExecutionArtifacts {
+        let Some(bytes) = execution.binary_stdout.take() else {
+            return execution;
+        };
+        let workspace = workspace_dir.filter(|_| self.divert_binary_output);
+        let reference = binary_output::divert(&bytes, skill_id, workspace);
+        execution.stdout = reference.placeholder();
+        if matches!(execution.exit_status, audit::ExecutionStatus::Success) {
+            execution.output = serde_json::json!({
+                "status": "success",
+                "binary_output": reference.to_value(),
+            });
+        }
+        execution
+    }
+
+    /// Apply terminal cleanup, the skill's output pipeline and redaction (if
+    /// enabled) to a result before returning it.
+    fn process_result(
+        &self,
+        mut result: ExecutionResult,
+        skill_id: &str,
+        skill_root: &Path,
+        workspace_dir: Option<&Path>,
+    ) -> ExecutionResult {
+        let pipeline = self.output_pipeline_for(skill_id);
+        if pipeline.is_empty() && !self.redact_output && !self.tty_cleanup {
+            return result;
+        }
+        let mut redactor = OutputRedactor::for_execution(skill_root, workspace_dir);
+        if let Some(secrets) = &self.secret_redactor {
+            redactor = redactor.with_secrets(secrets.clone());
+        }
+
+        let mut processed = ProcessedOutput {
+            output: result.output,
+            stdout: result.stdout,
+            stderr: result.stderr,
+        };
+        if self.tty_cleanup {
+            processed = OutputPipeline::tty_cleanup().apply(processed, &redactor);
         }
+        let processed = pipeline.apply(processed, &redactor);
+        result.output = processed.output;
+        result.stdout = processed.stdout;
+        result.stderr = processed.stderr;
+
+        if self.redact_output {
+            result.output = redactor.redact_value(&result.output);
+            result.stdout = redactor.redact(&result.stdout);
+            result.stderr = redactor.redact(&result.stderr);
+            result.audit.stdout = redactor.redact(&result.audit.stdout);
+            result.audit.stderr = redactor.redact(&result.audit.stderr);
+        }
+        result
     }
 
     /// Check if a tool is allowed for a skill.
@@ -1309,7 +4019,7 @@ Example response:
             .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
 
         let skill_allowed = metadata.manifest.get_allowed_tools();
-        let policy = &self.host_policy;
+        let policy = self.policy_for(&metadata.id, &metadata.root, &metadata.location);
 
         match policy.resolve_tool(tool, &skill_allowed) {
             ToolDecision::Approved => Ok(true),
@@ -1319,6 +4029,29 @@ Example response:
         }
     }
 
+    /// The tools an execution of `skill_id` would grant to the sandbox:
+    /// the skill's `allowed-tools` resolved through the host policy, plus
+    /// the policy's allow overrides.
+    ///
+    /// Tools that fall through to "prompt" go to the permission callback,
+    /// as they would at execution time. Pass the result, edited or not, as
+    /// `ExecutionOptions::effective_tools` to skip resolving them again.
+    pub fn resolve_effective_tools(&self, skill_id: &str) -> Result<Vec<String>, OpenSkillError> {
+        let metadata = self
+            .registry
+            .get(skill_id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
+        let allowed_tools = metadata.manifest.get_allowed_tools();
+        let policy = self.policy_for(&metadata.id, &metadata.root, &metadata.location);
+        let scope = self.request_scope(
+            &metadata.root,
+            metadata.manifest.entrypoint.as_ref(),
+            &ExecutionTarget::Auto,
+            self.get_workspace_dir().ok(),
+        );
+        self.resolve_tools(&policy, &metadata.id, &allowed_tools, &scope, &mut Vec::new())
+    }
+
     /// Run a specific target (script/WASM) within a skill.
     ///
     /// This is designed for Claude Skills where SKILL.md instructions tell
@@ -1326,7 +4059,7 @@ Example response:
     ///
     /// # Arguments
     ///
-    /// * `skill_id` - The skill containing the target
+    /// * `skill_id` - The skill containing the target (`id` or `id@constraint`)
     /// * `target` - What to execute (Script, Wasm, or Auto)
     /// * `options` - Execution options (timeout, input, etc.)
     ///
@@ -1354,65 +4087,393 @@ Example response:
         input: Option<Value>,
         workspace_dir: Option<PathBuf>,
     ) -> Result<ExecutionResult, OpenSkillError> {
+        self.run_skill_target_with_overrides(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            SandboxOverrides::default(),
+        )
+    }
+
+    /// Run the example named `name` from the skill's `examples:` list: its
+    /// `target` (or the auto-detected entry point) with its `input`. A quick
+    /// check that the skill works on this machine; the result's status says
+    /// whether it did.
+    pub fn run_skill_example(
+        &mut self,
+        skill_id: &str,
+        name: &str,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let metadata = self
+            .registry
+            .get(skill_id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
+        let examples = &metadata.manifest.examples;
+        let example = examples
+            .iter()
+            .find(|e| e.name() == Some(name))
+            .ok_or_else(|| {
+                let names: Vec<&str> = examples.iter().filter_map(|e| e.name()).collect();
+                OpenSkillError::InvalidConfig(format!(
+                    "skill {} has no example named '{}' (examples: {})",
+                    skill_id,
+                    name,
+                    if names.is_empty() { "none".to_string() } else { names.join(", ") }
+                ))
+            })?;
+        let target = match example.target() {
+            Some(path) => ExecutionTarget::Path {
+                path: path.to_string(),
+                args: Vec::new(),
+            },
+            None => ExecutionTarget::Auto,
+        };
+        let input = example.input().cloned();
+        self.run_skill_target(skill_id, target, None, input, None)
+    }
+
+    /// Like [`run_skill_target`](Self::run_skill_target), with extra sandbox
+    /// grants from the host for this run only (see [`SandboxOverrides`]).
+    pub fn run_skill_target_with_overrides(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        sandbox_overrides: SandboxOverrides,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let (prepared, options) = self.prepare_target_execution(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            sandbox_overrides,
+            None,
+            None,
+        )?;
+        let execution = run_skill_target(&prepared.skill, options, &self.wasm_engine);
+        self.finish_execution(prepared, execution)
+    }
+
+    /// Like [`run_skill_target`](Self::run_skill_target), sending the run's
+    /// progress to `events` as it happens: start, permission prompts,
+    /// stdout and stderr chunks as the target writes them, and the finish.
+    ///
+    /// The call still blocks until the run finishes, so read the receiver
+    /// on another thread or task:
+    ///
+    /// ```rust,ignore
+    /// let (events, mut receiver) = execution_event_channel();
+    /// let ui = std::thread::spawn(move || {
+    ///     while let Some(event) = receiver.blocking_recv() {
+    ///         if let ExecutionEvent::StdoutChunk(bytes) = event {
+    ///             print!("{}", String::from_utf8_lossy(&bytes));
+    ///         }
+    ///     }
+    /// });
+    /// let result = runtime.run_skill_target_with_events(
+    ///     "docx", ExecutionTarget::Auto, None, None, None, events,
+    /// )?;
+    /// ```
+    pub fn run_skill_target_with_events(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        events: ExecutionEventSender,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let (prepared, options) = self.prepare_target_execution(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            SandboxOverrides::default(),
+            None,
+            Some(events),
+        )?;
+        let execution = run_skill_target(&prepared.skill, options, &self.wasm_engine);
+        self.finish_execution(prepared, execution)
+    }
+
+    /// Like [`run_skill_target`](Self::run_skill_target), but runs the
+    /// sandboxed process or WASM component on tokio's blocking pool instead
+    /// of the calling thread. Cancelling `cancellation`, or dropping the
+    /// future, kills the child process or interrupts the WASM component.
+    #[cfg(feature = "tokio")]
+    pub async fn run_skill_target_async(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        cancellation: CancellationHandle,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        self.ask_permissions_async(skill_id, true, &target, workspace_dir.as_deref())
+            .await?;
+        let prepared = self.prepare_target_execution(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            SandboxOverrides::default(),
+            Some(cancellation),
+            None,
+        );
+        self.permission_manager.clear_answers();
+        let (prepared, options) = prepared?;
+        let skill = prepared.skill.clone();
+        let wasm_engine = self.wasm_engine.clone();
+        let execution = run_blocking(prepared.running.cancellation(), move || {
+            run_skill_target(&skill, options, &wasm_engine)
+        })
+        .await;
+        self.finish_execution(prepared, execution)
+    }
+
+    /// Start a native script as a long-lived session: the script is spawned
+    /// once in its sandbox, and the returned [`ScriptSession`] writes to its
+    /// stdin and reads its stdout as it answers.
+    ///
+    /// `timeout_ms` bounds the whole session. `input` is passed in
+    /// `SKILL_INPUT` only; stdin carries the session's messages. Dropping the
+    /// session kills the script; [`finish_script_session`](Self::finish_script_session)
+    /// ends it and records the audit entry. WASM and instruction-only targets
+    /// are rejected.
+    ///
+    /// ```rust,ignore
+    /// let mut session = runtime.start_script_session(
+    ///     "converter",
+    ///     ExecutionTarget::Path { path: "scripts/repl.py".into(), args: vec![] },
+    ///     Some(600_000),
+    ///     None,
+    ///     None,
+    /// )?;
+    /// session.write_line(r#"{"convert": "a.md"}"#)?;
+    /// let reply = session.read_line(Duration::from_secs(10))?;
+    /// let result = runtime.finish_script_session(session)?;
+    /// ```
+    pub fn start_script_session(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+    ) -> Result<ScriptSession, OpenSkillError> {
+        let (events, output) = execution_event_channel();
+        let (prepared, mut options) = self.prepare_target_execution(
+            skill_id,
+            target,
+            timeout_ms,
+            input,
+            workspace_dir,
+            SandboxOverrides::default(),
+            None,
+            Some(events),
+        )?;
+        let native = executor::is_native_target(
+            &prepared.skill,
+            &options.target,
+            &options.entrypoint_config,
+        )
+        .and_then(|native| {
+            native.then_some(()).ok_or_else(|| {
+                OpenSkillError::NativeExecutionError(
+                    "script sessions need a native script target".to_string(),
+                )
+            })
+        });
+        if let Err(e) = native {
+            // Close the registered execution with the error.
+            return match self.finish_execution(prepared, Err(e)) {
+                Err(e) => Err(e),
+                Ok(_) => unreachable!("a failed execution has no result"),
+            };
+        }
+        let skill = prepared.skill.clone();
+        let wasm_engine = self.wasm_engine.clone();
+        ScriptSession::start(prepared, output, move |stdin| {
+            options.stdin = Some(stdin);
+            run_skill_target(&skill, options, &wasm_engine)
+        })
+    }
+
+    /// End a script session: close its stdin, wait for the script to exit
+    /// (killing it if it has not after 5 seconds) and record the execution
+    /// like [`run_skill_target`](Self::run_skill_target) does. `stdout` holds
+    /// the output not read through the session.
+    pub fn finish_script_session(
+        &mut self,
+        session: ScriptSession,
+    ) -> Result<ExecutionResult, OpenSkillError> {
+        let (prepared, execution) = session.close();
+        self.finish_execution(prepared, execution)
+    }
+
+    /// Resolve `skill`'s tools, `sandbox:` section and skill-root writes
+    /// through the host policy; returns the effective tools.
+    fn resolve_target_permissions(
+        &self,
+        skill: &mut Skill,
+        scope: &RequestScope,
+        downgrades: &mut Vec<CapabilityDowngrade>,
+    ) -> Result<Vec<String>, OpenSkillError> {
+        let allowed_tools = skill.manifest.get_allowed_tools();
+        let effective_tools =
+            self.resolve_skill_permissions(skill, &skill.id, &allowed_tools, scope, downgrades)?;
+        self.record_unmapped_tools(&effective_tools, downgrades);
+        skill.manifest.sandbox = self.resolve_skill_sandbox(&skill.id, skill.manifest.sandbox.take())?;
+        skill.manifest.writes_skill_root =
+            self.resolve_skill_root_write(&skill.id, &skill.root, skill.manifest.writes_skill_root)?;
+        Ok(effective_tools)
+    }
+
+    /// Resolve a skill and its permissions for [`run_skill_target`] and
+    /// register the execution as running, inside its tracing span.
+    #[allow(clippy::too_many_arguments)]
+    fn prepare_target_execution(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        sandbox_overrides: SandboxOverrides,
+        cancellation: Option<CancellationHandle>,
+        event_sender: Option<ExecutionEventSender>,
+    ) -> Result<(PreparedExecution, TargetExecutionOptions), OpenSkillError> {
+        let span = telemetry::execution_span(skill_id, &target.label(), None);
+        let prepared = span.in_scope(|| {
+            self.resolve_target_execution(
+                skill_id,
+                target,
+                timeout_ms,
+                input,
+                workspace_dir,
+                sandbox_overrides,
+                cancellation,
+                event_sender,
+            )
+        });
+        let (mut prepared, mut options) =
+            prepared.inspect_err(|e| telemetry::fail_execution_span(&span, e))?;
+        if let Some(traceparent) = telemetry::sandbox_traceparent(&span, None) {
+            options.sandbox_overrides = options.sandbox_overrides.with_env("TRACEPARENT", traceparent);
+        }
+        prepared.span = span;
+        Ok((prepared, options))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_target_execution(
+        &mut self,
+        skill_id: &str,
+        target: ExecutionTarget,
+        timeout_ms: Option<u64>,
+        input: Option<Value>,
+        workspace_dir: Option<PathBuf>,
+        sandbox_overrides: SandboxOverrides,
+        cancellation: Option<CancellationHandle>,
+        event_sender: Option<ExecutionEventSender>,
+    ) -> Result<(PreparedExecution, TargetExecutionOptions), OpenSkillError> {
+        let events = ExecutionEvents::new(event_sender.clone());
         // Ensure registry is loaded
         if self.registry.is_empty() {
             self.discover_skills()?;
         }
+        self.check_sandbox_overrides(&sandbox_overrides)?;
 
         // Load full skill (with instructions) for target execution
-        let skill = self.registry.load_full_skill(skill_id)?;
+        let mut skill = self.registry.load_full_skill(skill_id)?;
 
         validate_skill(&skill)?;
-
-        // Resolve permissions through host policy.
-        let allowed_tools = skill.manifest.get_allowed_tools();
-        let effective_tools = self.resolve_skill_permissions(skill_id, &allowed_tools)?;
-
-        let start = Instant::now();
-        let start_epoch = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_millis() as u64;
-
+        self.check_skill_signature(&skill)?;
+        // `skill_id` may carry a version constraint; grants and history use the bare id.
+        let id = skill.id.clone();
+        let skill_id = id.as_str();
+
+        // Resolve permissions through host policy, copying prompts to the caller's events.
+        self.permission_manager
+            .set_events(events.with_permission_prompts(self.events.clone()));
+        let workspace_dir = workspace_dir.or_else(|| self.get_workspace_dir().ok());
+        let scope = self.request_scope(
+            &skill.root,
+            skill.manifest.entrypoint.as_ref(),
+            &target,
+            workspace_dir.clone(),
+        );
+        let mut downgrades = Vec::new();
+        let resolved = self.resolve_target_permissions(&mut skill, &scope, &mut downgrades);
+        self.permission_manager.set_events(self.events.clone());
+        let effective_tools = resolved?;
+
+        let (timeout_ms, memory_mb) = self.apply_auto_limits(skill_id, timeout_ms, None);
+        let running = self
+            .executions
+            .begin(&skill.id, target.label(), &self.session_id, cancellation);
+        let journal = self.journal_execution(&skill, target.label(), "");
+        self.events.emit(|| RuntimeEvent::ExecutionStarted {
+            execution_id: running.id(),
+            skill_id: skill.id.clone(),
+            target: target.label(),
+        });
+        events.emit(|| ExecutionEvent::Started {
+            execution_id: running.id(),
+            skill_id: skill.id.clone(),
+            target: target.label(),
+        });
         let options = TargetExecutionOptions {
             target,
             timeout_ms,
+            memory_mb,
             input,
-            workspace_dir: workspace_dir.or_else(|| self.get_workspace_dir().ok()),
+            workspace_dir: workspace_dir.clone(),
             effective_tools,
+            capability_mapping: self.capability_mapping.clone(),
             native_runner_config: self.native_runner_config.clone(),
+            entrypoint_config: self.entrypoint_config.clone(),
+            sandbox_overrides,
+            cancellation: Some(running.cancellation()),
+            events: event_sender,
+            journal: journal.clone(),
+            workspace_only_writes: writes_workspace_only(&downgrades),
+            secret_redactor: self.secret_redactor.clone(),
             ..Default::default()
         };
+        let snapshot = self.snapshot_workspace(workspace_dir.as_deref(), false);
 
-        let execution = run_skill_target(&skill, options)?;
-        let duration_ms = start.elapsed().as_millis() as u64;
-
-        let audit = AuditRecord {
-            skill_id: skill.id.clone(),
-            version: "1.0".to_string(),
-            input_hash: "".to_string(),
-            output_hash: audit::hash_json_value(&execution.output),
-            start_time_ms: start_epoch,
-            duration_ms,
-            permissions_used: execution.permissions_used.clone(),
-            exit_status: execution.exit_status.clone(),
-            stdout: execution.stdout.clone(),
-            stderr: execution.stderr.clone(),
-            sandbox_mode: self
-                .native_runner_config
-                .as_ref()
-                .map(|c| c.sandbox_mode)
-                .unwrap_or(SandboxMode::Enforce),
+        let prepared = PreparedExecution {
+            skill,
+            fork_context: None,
+            input_hash: String::new(),
+            workspace_dir,
+            timeout_ms,
+            memory_mb,
+            start: Instant::now(),
+            start_epoch: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or(Duration::from_secs(0))
+                .as_millis() as u64,
+            running,
+            events,
+            downgrades,
+            attempt: 1,
+            journal,
+            snapshot,
+            convert_outputs: true,
+            traceparent: None,
+            span: tracing::Span::none(),
         };
-
-        self.audit_sink.record(&audit);
-
-        Ok(ExecutionResult {
-            output: execution.output,
-            stdout: execution.stdout,
-            stderr: execution.stderr,
-            audit,
-        })
+        Ok((prepared, options))
     }
 
     /// Read a file from a skill directory.
@@ -1458,6 +4519,36 @@ Example response:
         list_skill_files(&metadata.root, subdir, recursive)
     }
 
+    /// Explain which file `execute_skill` would run for a skill and why.
+    ///
+    /// The trace lists each source considered in order (SKILL.md
+    /// `entrypoint:`, then the configured WASM/script candidates).
+    pub fn explain_entrypoint(&self, skill_id: &str) -> Result<EntrypointResolution, OpenSkillError> {
+        let metadata = self
+            .registry
+            .get(skill_id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
+
+        entrypoint::resolve_entrypoint(
+            &metadata.root,
+            metadata.manifest.entrypoint.as_ref(),
+            None,
+            &self.entrypoint_config,
+        )
+    }
+
+    /// List what can be executed in a skill: WASM modules and Python/shell
+    /// scripts, with their path relative to the skill root and the sandbox
+    /// `ExecutionTarget::Path` would run them in.
+    pub fn list_skill_targets(&self, skill_id: &str) -> Result<Vec<SkillTarget>, OpenSkillError> {
+        let metadata = self
+            .registry
+            .get(skill_id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(skill_id.to_string()))?;
+
+        Ok(registry::detect_skill_targets(&metadata.root))
+    }
+
     /// Get the root directory path for a skill.
     ///
     /// This is useful for agents that need to construct absolute paths
@@ -1491,14 +4582,30 @@ Example response:
         skill_id: &str,
         event: HookEvent,
     ) -> Result<Vec<CommandResult>, OpenSkillError> {
-        let skill = self.activate_skill(skill_id)?;
+        let span = telemetry::hooks_span(skill_id, event.name());
+        let _entered = span.enter();
+        let skill = self.registry.load_full_skill(skill_id)?;
+        validate_skill(&skill)?;
+        self.check_skill_signature(&skill)?;
 
-        if let Some(hooks) = skill.manifest.hooks {
-            let runner = HookRunner::new(hooks, self.get_skill_root(skill_id)?);
-            runner.execute(&event)
-        } else {
-            Ok(Vec::new())
+        let Some(hooks) = skill.manifest.hooks else {
+            return Ok(Vec::new());
+        };
+        let results = HookRunner::new(hooks, skill.root)
+            .execute(&event)
+            .inspect_err(|_| {
+                span.record("otel.status_code", "ERROR");
+            })?;
+        telemetry::finish_hooks_span(&span, &results);
+        for result in &results {
+            self.events.emit(|| RuntimeEvent::HookExecuted {
+                skill_id: skill.id.clone(),
+                event: event.name().to_string(),
+                exit_code: result.exit_code,
+                timed_out: result.timed_out,
+            });
         }
+        Ok(results)
     }
 }
 
@@ -1534,4 +4641,37 @@ mod tests {
             ""
         );
     }
+
+    #[test]
+    fn test_session_ids() {
+        let a = OpenSkillRuntime::new();
+        let b = OpenSkillRuntime::new();
+        assert!(a.get_session_id().starts_with("session-"));
+        assert_ne!(a.get_session_id(), b.get_session_id());
+
+        let runtime = OpenSkillRuntime::new().with_session_id("conv/42").with_session_id("");
+        assert_eq!(runtime.get_session_id(), "conv/42");
+        let dir = artifacts::session_dir(Path::new("/ws"), runtime.get_session_id());
+        assert!(dir.starts_with("/ws"));
+        assert!(dir.file_name().unwrap().to_str().unwrap().starts_with("conv_42-"));
+    }
+
+    #[test]
+    fn test_conversation_workspaces() {
+        let conversation = format!("conv-{}", uuid::Uuid::now_v7());
+        let mut first = OpenSkillRuntime::new();
+        let dir = first.use_conversation(&conversation).unwrap();
+        assert_eq!(first.get_workspace_dir().unwrap(), dir);
+        std::fs::write(dir.join("notes.md"), "turn 1").unwrap();
+
+        // A later turn, possibly in another runtime, finds the file.
+        let mut second = OpenSkillRuntime::new();
+        assert_eq!(second.use_conversation(&conversation).unwrap(), dir);
+        assert!(dir.join("notes.md").exists());
+        assert!(second.workspace_for("").is_err());
+
+        assert!(second.remove_conversation_workspace(&conversation).unwrap());
+        assert!(!dir.exists());
+        assert!(!second.remove_conversation_workspace(&conversation).unwrap());
+    }
 }
@@ -0,0 +1,313 @@
+//! Skill lockfile (`openskills.lock`).
+//!
+//! The lockfile pins every loaded skill version to where it came from and a
+//! hash of its content, so a team can check that each machine runs the same
+//! skills:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [[skill]]
+//! id = "pdf"
+//! version = "1.2.0"
+//! source = "git+https://github.com/acme/skills?subdir=pdf#3f2c..."
+//! hash = "sha256:9b1e..."
+//! ```
+//!
+//! `source` is `git+<url>[?subdir=<dir>]#<commit>` for skills installed with
+//! [`SkillInstaller`](crate::SkillInstaller), `registry+<index URL>` for
+//! remote registry skills, `oci:<reference>` for OCI artifacts and
+//! `local:<location>` for everything else. `hash` is the SHA-256 of the
+//! skill's [`skill_digest`], the same message a signature signs.
+//! [`Lockfile::diff`] reports every skill that was added, removed, changed or
+//! now comes from elsewhere.
+
+use crate::errors::OpenSkillError;
+use crate::install::{InstallProvenance, PROVENANCE_FILE};
+use crate::registry::SkillMetadata;
+use crate::signing::skill_digest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Default lockfile name, in the project root.
+pub const LOCKFILE_NAME: &str = "openskills.lock";
+
+/// Lockfile format version.
+const LOCKFILE_VERSION: u32 = 1;
+
+const HEADER: &str = "# Generated by openskills. Regenerate with `openskills lock`; do not edit.\n\n";
+
+/// The skills a project runs, pinned by source and content hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Format version.
+    pub version: u32,
+    /// Locked skills, sorted by ID and version.
+    #[serde(default, rename = "skill")]
+    pub skills: Vec<LockedSkill>,
+}
+
+/// One locked skill version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Skill ID.
+    pub id: String,
+    /// Semver version (`0.0.0` when the skill declares none).
+    pub version: String,
+    /// Where the skill came from (see the module docs).
+    pub source: String,
+    /// `sha256:` plus the hex SHA-256 of the skill digest.
+    pub hash: String,
+}
+
+impl LockedSkill {
+    /// Lock a loaded skill. `source` overrides the source derived from its
+    /// directory (used for registry and OCI skills).
+    pub(crate) fn from_metadata(metadata: &SkillMetadata, source: Option<String>) -> Result<Self, OpenSkillError> {
+        Ok(Self {
+            id: metadata.id.clone(),
+            version: metadata.version.to_string(),
+            source: source.unwrap_or_else(|| local_source(metadata)),
+            hash: content_hash(&metadata.root)?,
+        })
+    }
+}
+
+impl Lockfile {
+    /// A lockfile of `skills`, sorted.
+    pub fn new(mut skills: Vec<LockedSkill>) -> Self {
+        skills.sort_by(|a, b| (&a.id, &a.version).cmp(&(&b.id, &b.version)));
+        Self {
+            version: LOCKFILE_VERSION,
+            skills,
+        }
+    }
+
+    /// Parse a lockfile.
+    pub fn parse(content: &str) -> Result<Self, OpenSkillError> {
+        let lockfile: Self = toml::from_str(content)
+            .map_err(|e| OpenSkillError::LockfileError(format!("invalid lockfile: {}", e)))?;
+        if lockfile.version != LOCKFILE_VERSION {
+            return Err(OpenSkillError::LockfileError(format!(
+                "unsupported lockfile version {}",
+                lockfile.version
+            )));
+        }
+        Ok(lockfile)
+    }
+
+    /// Read a lockfile from `path`.
+    pub fn read(path: &Path) -> Result<Self, OpenSkillError> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            OpenSkillError::LockfileError(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&content).map_err(|e| match e {
+            OpenSkillError::LockfileError(msg) => {
+                OpenSkillError::LockfileError(format!("{}: {}", path.display(), msg))
+            }
+            e => e,
+        })
+    }
+
+    /// Serialize the lockfile.
+    pub fn to_toml(&self) -> Result<String, OpenSkillError> {
+        let body = toml::to_string(self)
+            .map_err(|e| OpenSkillError::LockfileError(format!("cannot serialize lockfile: {}", e)))?;
+        Ok(format!("{}{}", HEADER, body))
+    }
+
+    /// Write the lockfile to `path`.
+    pub fn write(&self, path: &Path) -> Result<(), OpenSkillError> {
+        fs::write(path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    /// Compare this (expected) lockfile with `current`, e.g. one generated
+    /// from the skills loaded now. Empty if they match.
+    pub fn diff(&self, current: &Lockfile) -> Vec<LockfileDrift> {
+        let mut drift = Vec::new();
+        for expected in &self.skills {
+            match current.find(&expected.id, &expected.version) {
+                None => drift.push(LockfileDrift::Missing {
+                    id: expected.id.clone(),
+                    version: expected.version.clone(),
+                }),
+                Some(actual) => {
+                    if actual.source != expected.source {
+                        drift.push(LockfileDrift::SourceChanged {
+                            id: expected.id.clone(),
+                            version: expected.version.clone(),
+                            expected: expected.source.clone(),
+                            actual: actual.source.clone(),
+                        });
+                    }
+                    if actual.hash != expected.hash {
+                        drift.push(LockfileDrift::HashMismatch {
+                            id: expected.id.clone(),
+                            version: expected.version.clone(),
+                            expected: expected.hash.clone(),
+                            actual: actual.hash.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        for actual in &current.skills {
+            if self.find(&actual.id, &actual.version).is_none() {
+                drift.push(LockfileDrift::Unlocked {
+                    id: actual.id.clone(),
+                    version: actual.version.clone(),
+                    source: actual.source.clone(),
+                });
+            }
+        }
+        drift
+    }
+
+    fn find(&self, id: &str, version: &str) -> Option<&LockedSkill> {
+        self.skills.iter().find(|s| s.id == id && s.version == version)
+    }
+}
+
+/// A difference between a lockfile and the loaded skills.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LockfileDrift {
+    /// Locked but not loaded.
+    Missing { id: String, version: String },
+    /// Loaded but not locked.
+    Unlocked {
+        id: String,
+        version: String,
+        source: String,
+    },
+    /// Loaded from a different source than locked.
+    SourceChanged {
+        id: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+    /// The skill's files changed since it was locked.
+    HashMismatch {
+        id: String,
+        version: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for LockfileDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockfileDrift::Missing { id, version } => {
+                write!(f, "{}@{}: locked but not loaded", id, version)
+            }
+            LockfileDrift::Unlocked { id, version, source } => {
+                write!(f, "{}@{}: loaded from {} but not locked", id, version, source)
+            }
+            LockfileDrift::SourceChanged {
+                id,
+                version,
+                expected,
+                actual,
+            } => write!(f, "{}@{}: source changed from {} to {}", id, version, expected, actual),
+            LockfileDrift::HashMismatch {
+                id,
+                version,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}@{}: content changed (locked {}, found {})",
+                id, version, expected, actual
+            ),
+        }
+    }
+}
+
+/// `sha256:` plus the hex SHA-256 of the skill digest of `skill_dir`.
+pub fn content_hash(skill_dir: &Path) -> Result<String, OpenSkillError> {
+    let digest = skill_digest(skill_dir)?;
+    Ok(format!("sha256:{}", hex::encode(Sha256::digest(digest.as_bytes()))))
+}
+
+/// Source of a skill found on disk: its install provenance, else where it
+/// was discovered.
+fn local_source(metadata: &SkillMetadata) -> String {
+    let provenance = fs::read_to_string(metadata.root.join(PROVENANCE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<InstallProvenance>(&content).ok());
+    match provenance {
+        Some(p) => match &p.subdir {
+            Some(subdir) => format!("git+{}?subdir={}#{}", p.source_url, subdir, p.commit),
+            None => format!("git+{}#{}", p.source_url, p.commit),
+        },
+        None => format!("local:{}", metadata.location),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(id: &str, source: &str, hash: &str) -> LockedSkill {
+        LockedSkill {
+            id: id.to_string(),
+            version: "1.0.0".to_string(),
+            source: source.to_string(),
+            hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_is_sorted() {
+        let lockfile = Lockfile::new(vec![
+            locked("zeta", "local:project", "sha256:1"),
+            locked("alpha", "registry+https://example.com/index.json", "sha256:2"),
+        ]);
+        let text = lockfile.to_toml().unwrap();
+        assert!(text.starts_with("# Generated by openskills"));
+        assert!(text.find("alpha").unwrap() < text.find("zeta").unwrap());
+        assert_eq!(Lockfile::parse(&text).unwrap(), lockfile);
+        assert!(matches!(
+            Lockfile::parse("version = 2\n"),
+            Err(OpenSkillError::LockfileError(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff_reports_each_kind_of_drift() {
+        let expected = Lockfile::new(vec![
+            locked("same", "local:project", "sha256:1"),
+            locked("edited", "local:project", "sha256:1"),
+            locked("moved", "local:project", "sha256:1"),
+            locked("gone", "local:project", "sha256:1"),
+        ]);
+        let current = Lockfile::new(vec![
+            locked("same", "local:project", "sha256:1"),
+            locked("edited", "local:project", "sha256:2"),
+            locked("moved", "local:personal", "sha256:1"),
+            locked("new", "local:custom", "sha256:1"),
+        ]);
+        let drift = expected.diff(&current);
+        assert_eq!(drift.len(), 4, "{:?}", drift);
+        assert!(drift.contains(&LockfileDrift::Missing {
+            id: "gone".to_string(),
+            version: "1.0.0".to_string()
+        }));
+        assert!(drift
+            .iter()
+            .any(|d| matches!(d, LockfileDrift::HashMismatch { id, .. } if id == "edited")));
+        assert!(drift
+            .iter()
+            .any(|d| matches!(d, LockfileDrift::SourceChanged { id, .. } if id == "moved")));
+        assert!(drift
+            .iter()
+            .any(|d| matches!(d, LockfileDrift::Unlocked { id, .. } if id == "new")));
+        assert!(expected.diff(&expected).is_empty());
+    }
+}
@@ -3,14 +3,16 @@
 //! Conforms to the Claude Code Agent Skills specification:
 //! https://code.claude.com/docs/en/skills
 
-use serde::{Deserialize, Serialize};
+use crate::errors::OpenSkillError;
+use crate::skill_version::UNVERSIONED;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Claude Skill manifest parsed from SKILL.md YAML frontmatter.
 ///
 /// The upstream spec treats `name` and `description` as required; this runtime allows them
 /// to be omitted in the file and fills them during discovery (directory name, body text).
 ///
-/// Optional fields: `allowed_tools`, `model`, `context`, `agent`, `hooks`, `user_invocable`
+/// Optional fields: `version`, `allowed_tools`, `allowed_hosts`, `allowed_ports`, `model`, `context`, `agent`, `hooks`, `user_invocable`, `sandbox`, `entrypoint`, `writes_skill_root`, `output_schema`, `output_converters`, `examples`
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct SkillManifest {
@@ -27,11 +29,25 @@ pub struct SkillManifest {
     #[serde(default)]
     pub description: String,
 
+    /// Semver version of the skill (e.g. "2.1.0"). Several versions of one
+    /// skill can be loaded side by side; see `skill_version`.
+    #[serde(default, deserialize_with = "deserialize_version")]
+    pub version: Option<String>,
+
     /// Tools Claude can use without asking permission when this Skill is active.
     /// Supports comma-separated values or YAML-style lists.
     #[serde(default)]
     pub allowed_tools: Option<AllowedTools>,
 
+    /// Hosts native scripts may reach when network access is granted
+    /// (WebSearch/Fetch). Subdomains match; empty means any host.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// TCP ports native scripts may connect to; empty means any port.
+    #[serde(default)]
+    pub allowed_ports: Vec<u16>,
+
     /// Model to use when this Skill is active (e.g., "claude-sonnet-4-20250514").
     /// Defaults to the conversation's model.
     #[serde(default)]
@@ -75,6 +91,252 @@ pub struct SkillManifest {
     /// OpenSkills action/capability descriptors (machine-readable actions this skill provides).
     #[serde(default)]
     pub actions: Option<Vec<SkillAction>>,
+
+    /// Extra native sandbox grants (OpenSkills extension). Applied only
+    /// when the host policy approves them.
+    #[serde(default)]
+    pub sandbox: Option<SandboxConfig>,
+
+    /// File to run when the skill is executed without an explicit target
+    /// (OpenSkills extension), relative to the skill directory, with
+    /// optional default arguments. Takes precedence over entry-point
+    /// auto-detection.
+    #[serde(default)]
+    pub entrypoint: Option<SkillEntrypoint>,
+
+    /// Whether native scripts may write to the skill's own directory
+    /// (OpenSkills extension). The skill root is read-only unless this is
+    /// set and the permission callback approves it.
+    #[serde(default)]
+    pub writes_skill_root: bool,
+
+    /// JSON Schema the output of script and WASM executions must match
+    /// (OpenSkills extension); see `output_schema`.
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
+
+    /// Converters applied to the files executions produce (OpenSkills
+    /// extension); see `output_converters`.
+    #[serde(default)]
+    pub output_converters: Vec<crate::output_converters::OutputConverter>,
+
+    /// Example invocations shown by skill pickers (OpenSkills extension);
+    /// see `SkillCard`.
+    #[serde(default)]
+    pub examples: Vec<SkillExample>,
+
+    /// Template files copied from the skill into the workspace when a
+    /// session starts (OpenSkills extension).
+    #[serde(default)]
+    pub workspace_files: Vec<WorkspaceFile>,
+}
+
+/// An example invocation can be a bare prompt or a prompt with input.
+/// Named examples can be run with `run_skill_example` as a smoke test:
+///
+/// ```yaml
+/// examples:
+///   - Convert report.docx to PDF
+///   - prompt: Merge the quarterly reports
+///     input: {files: ["q1.pdf", "q2.pdf"]}
+///   - name: hello
+///     target: scripts/greet.py
+///     input: {name: World}
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SkillExample {
+    Prompt(String),
+    WithInput {
+        /// Name to run the example by.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(default)]
+        prompt: String,
+        /// File to run, relative to the skill root; the auto-detected
+        /// entry point without one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+        #[serde(default)]
+        input: Option<serde_json::Value>,
+    },
+}
+
+impl SkillExample {
+    /// What a user would ask for; the name for examples without a prompt.
+    pub fn prompt(&self) -> &str {
+        match self {
+            SkillExample::Prompt(prompt) => prompt,
+            SkillExample::WithInput { prompt, name, .. } if prompt.is_empty() => {
+                name.as_deref().unwrap_or_default()
+            }
+            SkillExample::WithInput { prompt, .. } => prompt,
+        }
+    }
+
+    /// Name to run the example by, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            SkillExample::Prompt(_) => None,
+            SkillExample::WithInput { name, .. } => name.as_deref(),
+        }
+    }
+
+    /// File the example runs, if not the entry point.
+    pub fn target(&self) -> Option<&str> {
+        match self {
+            SkillExample::Prompt(_) => None,
+            SkillExample::WithInput { target, .. } => target.as_deref(),
+        }
+    }
+
+    /// Input passed to the skill, if the example gives one.
+    pub fn input(&self) -> Option<&serde_json::Value> {
+        match self {
+            SkillExample::Prompt(_) => None,
+            SkillExample::WithInput { input, .. } => input.as_ref(),
+        }
+    }
+}
+
+/// Reject examples without a prompt or name, duplicate names, and targets
+/// outside the skill.
+pub(crate) fn check_examples(examples: &[SkillExample]) -> Result<(), OpenSkillError> {
+    let mut names = std::collections::HashSet::new();
+    for example in examples {
+        if example.prompt().trim().is_empty() {
+            return Err(OpenSkillError::InvalidManifest(
+                "example needs a prompt or a name".to_string(),
+            ));
+        }
+        if let Some(name) = example.name() {
+            if !names.insert(name) {
+                return Err(OpenSkillError::InvalidManifest(format!(
+                    "duplicate example name: {}",
+                    name
+                )));
+            }
+        }
+        if let Some(target) = example.target() {
+            let path = std::path::Path::new(target);
+            if target.is_empty()
+                || !path
+                    .components()
+                    .all(|c| matches!(c, std::path::Component::Normal(_)))
+            {
+                return Err(OpenSkillError::InvalidManifest(format!(
+                    "example target must be a path inside the skill: {}",
+                    target
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A workspace file can be a bare path, copied to the same path in the
+/// workspace, or a `from`/`to` pair. Directories are copied recursively:
+///
+/// ```yaml
+/// workspace-files:
+///   - templates/report.docx
+///   - from: templates/letters
+///     to: drafts
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorkspaceFile {
+    Path(String),
+    Mapped {
+        from: String,
+        #[serde(default)]
+        to: Option<String>,
+    },
+}
+
+impl WorkspaceFile {
+    /// File or directory to copy, relative to the skill root.
+    pub fn source(&self) -> &str {
+        match self {
+            WorkspaceFile::Path(path) | WorkspaceFile::Mapped { from: path, .. } => path,
+        }
+    }
+
+    /// Where the copy goes, relative to the workspace.
+    pub fn dest(&self) -> &str {
+        match self {
+            WorkspaceFile::Mapped { to: Some(to), .. } => to,
+            _ => self.source(),
+        }
+    }
+}
+
+/// Entry point can be a bare path or a path with arguments:
+///
+/// ```yaml
+/// entrypoint: scripts/run.py
+/// # or
+/// entrypoint:
+///   path: scripts/run.py
+///   args: ["--format", "json"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SkillEntrypoint {
+    Path(String),
+    WithArgs {
+        path: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl SkillEntrypoint {
+    /// Path relative to the skill directory.
+    pub fn path(&self) -> &str {
+        match self {
+            SkillEntrypoint::Path(path) | SkillEntrypoint::WithArgs { path, .. } => path,
+        }
+    }
+
+    /// Arguments passed to the script on every auto-targeted run.
+    pub fn args(&self) -> &[String] {
+        match self {
+            SkillEntrypoint::Path(_) => &[],
+            SkillEntrypoint::WithArgs { args, .. } => args,
+        }
+    }
+}
+
+/// Native sandbox profile fragment from the `sandbox:` section of SKILL.md.
+///
+/// Merged into the generated seatbelt/Landlock profile. Paths may be
+/// absolute, `~`-relative or relative to the skill directory; sensitive
+/// paths (`~/.ssh`, `~/.aws`, ...) stay denied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Additional readable paths.
+    #[serde(default)]
+    pub read_paths: Vec<String>,
+    /// Additional writable paths.
+    #[serde(default)]
+    pub write_paths: Vec<String>,
+    /// Allow network access without `WebSearch`/`Fetch` in allowed-tools.
+    #[serde(default)]
+    pub allow_network: bool,
+    /// Allow spawning subprocesses without `Bash`/`Terminal`.
+    #[serde(default)]
+    pub allow_process: bool,
+}
+
+impl SandboxConfig {
+    /// True when the section grants nothing.
+    pub fn is_empty(&self) -> bool {
+        self.read_paths.is_empty()
+            && self.write_paths.is_empty()
+            && !self.allow_network
+            && !self.allow_process
+    }
 }
 
 /// Machine-readable action descriptor (OpenSkills extension).
@@ -163,12 +425,31 @@ pub enum AllowedTools {
 impl AllowedTools {
     /// Get the list of allowed tools as a Vec<String>.
     /// Supports comma-separated, space-separated, or YAML list formats.
+    /// Entries keep their argument patterns, e.g. `Bash(npm run test:*)`.
     pub fn to_vec(&self) -> Vec<String> {
         match self {
-            AllowedTools::List(v) => v.clone(),
+            AllowedTools::List(v) => v.iter().map(|t| t.trim().to_string()).collect(),
             AllowedTools::CommaSeparated(s) => {
-                // Support both comma-delimited AND space-delimited
-                s.split(|c| c == ',' || c == ' ')
+                // Support both comma-delimited AND space-delimited, except
+                // inside an argument pattern
+                let mut entries = Vec::new();
+                let mut entry = String::new();
+                let mut depth = 0usize;
+                for c in s.chars() {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth = depth.saturating_sub(1),
+                        ',' | ' ' if depth == 0 => {
+                            entries.push(std::mem::take(&mut entry));
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    entry.push(c);
+                }
+                entries.push(entry);
+                entries
+                    .into_iter()
                     .map(|t| t.trim().to_string())
                     .filter(|t| !t.is_empty())
                     .collect()
@@ -177,6 +458,100 @@ impl AllowedTools {
     }
 }
 
+/// An `allowed-tools` entry: a tool, optionally only for arguments matching
+/// a pattern, as in `Bash(npm run test:*)` or `Read(./docs/**)`.
+///
+/// For file tools (`Read`, `Write`, `Edit`, `MultiEdit`, `Grep`, `Glob`,
+/// `LS`) the pattern is a path glob where `*` stays within a directory and
+/// `**` crosses them; a leading `./` is ignored and paths with `..` never
+/// match. For other tools it is a glob over the whole argument, and a
+/// trailing `:*` matches any argument starting with what precedes it.
+/// An argument containing a shell metacharacter (`;`, `&`, `|`, `` ` ``,
+/// `$`, `(`, `)`, `<`, `>`) only matches patterns that contain that
+/// character too, so `Bash(npm run test:*)` does not cover
+/// `npm run test & rm -rf ~` or `npm run test > ~/.bashrc`; arguments
+/// spanning several lines never match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolPattern {
+    /// Tool name, e.g. `Bash`.
+    pub tool: String,
+    /// Pattern the argument must match; `None` allows every use.
+    pub argument: Option<String>,
+}
+
+impl ToolPattern {
+    /// Parse an `allowed-tools` entry.
+    pub fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        match entry.split_once('(') {
+            Some((tool, rest)) if rest.ends_with(')') => {
+                let argument = rest[..rest.len() - 1].trim();
+                Self {
+                    tool: tool.trim().to_string(),
+                    argument: (!argument.is_empty()).then(|| argument.to_string()),
+                }
+            }
+            _ => Self {
+                tool: entry.to_string(),
+                argument: None,
+            },
+        }
+    }
+
+    /// Whether this entry allows a call of `tool` with `argument` (the
+    /// command or path). A pattern never allows a call without argument.
+    pub fn allows(&self, tool: &str, argument: Option<&str>) -> bool {
+        if self.tool != tool {
+            return false;
+        }
+        let Some(pattern) = &self.argument else {
+            return true;
+        };
+        let Some(argument) = argument else {
+            return false;
+        };
+        if is_file_tool(tool) {
+            let argument = argument.trim_start_matches("./");
+            if argument.split('/').any(|c| c == "..") {
+                return false;
+            }
+            let options = glob::MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            };
+            return glob::Pattern::new(pattern.trim_start_matches("./"))
+                .is_ok_and(|p| p.matches_with(argument, options));
+        }
+        const SHELL_METACHARACTERS: [char; 9] = [';', '&', '|', '`', '$', '(', ')', '<', '>'];
+        if argument.contains(['\n', '\r'])
+            || argument
+                .chars()
+                .any(|c| SHELL_METACHARACTERS.contains(&c) && !pattern.contains(c))
+        {
+            return false;
+        }
+        match pattern.strip_suffix(":*") {
+            Some(prefix) => argument.starts_with(prefix),
+            None => glob::Pattern::new(pattern).is_ok_and(|p| p.matches(argument)),
+        }
+    }
+}
+
+impl std::fmt::Display for ToolPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.argument {
+            Some(argument) => write!(f, "{}({})", self.tool, argument),
+            None => f.write_str(&self.tool),
+        }
+    }
+}
+
+/// Tools whose argument is a file path.
+fn is_file_tool(tool: &str) -> bool {
+    matches!(tool, "Read" | "Write" | "Edit" | "MultiEdit" | "Grep" | "Glob" | "LS")
+}
+
 /// Hooks configuration for skill lifecycle events.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "PascalCase")]
@@ -245,6 +620,22 @@ impl Default for WasmConfig {
     }
 }
 
+/// Accept `version: 2.1` (a YAML number) as well as `version: "2.1.0"`.
+fn deserialize_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawVersion {
+        Text(String),
+        Integer(u64),
+        Float(f64),
+    }
+    Ok(Option::<RawVersion>::deserialize(deserializer)?.map(|raw| match raw {
+        RawVersion::Text(text) => text,
+        RawVersion::Integer(n) => n.to_string(),
+        RawVersion::Float(f) => f.to_string(),
+    }))
+}
+
 fn default_timeout_ms() -> u64 {
     30000 // 30 seconds
 }
@@ -288,12 +679,32 @@ impl SkillManifest {
         self.user_invocable.unwrap_or(true)
     }
 
-    /// Get allowed tools as a vector.
+    /// Declared version, or [`UNVERSIONED`].
+    pub fn version(&self) -> &str {
+        self.version.as_deref().unwrap_or(UNVERSIONED)
+    }
+
+    /// Get allowed tools as a vector of tool names; an entry with an
+    /// argument pattern, e.g. `Bash(npm run test:*)`, yields its tool.
     pub fn get_allowed_tools(&self) -> Vec<String> {
+        let mut tools: Vec<String> = Vec::new();
+        for pattern in self.allowed_tool_patterns() {
+            if !tools.contains(&pattern.tool) {
+                tools.push(pattern.tool);
+            }
+        }
+        tools
+    }
+
+    /// The `allowed-tools` entries with their argument patterns.
+    pub fn allowed_tool_patterns(&self) -> Vec<ToolPattern> {
         self.allowed_tools
             .as_ref()
             .map(|t| t.to_vec())
             .unwrap_or_default()
+            .iter()
+            .map(|entry| ToolPattern::parse(entry))
+            .collect()
     }
 }
 
@@ -367,6 +778,48 @@ mod tests {
         assert_eq!(tools.to_vec(), vec!["Read", "Write", "Bash"]);
     }
 
+    #[test]
+    fn test_allowed_tools_argument_patterns() {
+        let tools = AllowedTools::CommaSeparated("Bash(npm run test:*), Read(./docs/**) Write".to_string());
+        assert_eq!(tools.to_vec(), vec!["Bash(npm run test:*)", "Read(./docs/**)", "Write"]);
+        let manifest = SkillManifest {
+            allowed_tools: Some(tools),
+            ..Default::default()
+        };
+        assert_eq!(manifest.get_allowed_tools(), vec!["Bash", "Read", "Write"]);
+
+        let patterns = manifest.allowed_tool_patterns();
+        let bash = &patterns[0];
+        assert!(bash.allows("Bash", Some("npm run test")));
+        assert!(bash.allows("Bash", Some("npm run test -- --watch")));
+        assert!(!bash.allows("Bash", Some("npm run build")));
+        assert!(!bash.allows("Bash", Some("npm run test && rm -rf /")));
+        assert!(!bash.allows("Bash", Some("npm run test & rm -rf ~")));
+        assert!(!bash.allows("Bash", Some("npm run test > ~/.bashrc")));
+        assert!(!bash.allows("Bash", Some("npm run test < /etc/passwd")));
+        assert!(!bash.allows("Bash", Some("npm run test $HOME")));
+        assert!(!bash.allows("Bash", Some("npm run test\nrm -rf ~")));
+        assert!(!bash.allows("Bash", None));
+        assert_eq!(bash.to_string(), "Bash(npm run test:*)");
+        // Metacharacters the pattern spells out are matched like any other
+        let piped = ToolPattern::parse("Bash(git log | head:*)");
+        assert!(piped.allows("Bash", Some("git log | head -5")));
+        assert!(!piped.allows("Bash", Some("git log | head > out.txt")));
+        assert!(!bash.allows("Read", Some("npm run test")));
+
+        let read = &patterns[1];
+        assert!(read.allows("Read", Some("docs/guide.md")));
+        assert!(read.allows("Read", Some("./docs/api/index.md")));
+        assert!(!read.allows("Read", Some("src/main.rs")));
+        assert!(!read.allows("Read", Some("docs/../secrets.env")));
+
+        assert!(patterns[2].allows("Write", None));
+        assert!(patterns[2].allows("Write", Some("anything")));
+        let one_level = ToolPattern::parse("Read(docs/*.md)");
+        assert!(one_level.allows("Read", Some("docs/a.md")));
+        assert!(!one_level.allows("Read", Some("docs/api/a.md")));
+    }
+
     #[test]
     fn test_allowed_tools_yaml_list() {
         let tools = AllowedTools::List(vec!["Read".to_string(), "Write".to_string(), "Bash".to_string()]);
@@ -392,6 +845,22 @@ agent: Explore"#;
         assert_eq!(manifest.agent, Some("Explore".to_string()));
     }
 
+    #[test]
+    fn test_examples_need_unique_names_and_inner_targets() {
+        let examples: Vec<SkillExample> = serde_yaml::from_str(
+            "- Say hello\n- name: smoke\n  target: scripts/run.sh\n  input: {a: 1}\n",
+        )
+        .unwrap();
+        assert_eq!(examples[1].prompt(), "smoke");
+        assert_eq!(examples[1].target(), Some("scripts/run.sh"));
+        assert!(check_examples(&examples).is_ok());
+
+        let parse = |yaml: &str| serde_yaml::from_str::<Vec<SkillExample>>(yaml).unwrap();
+        assert!(check_examples(&parse("- name: a\n- name: a\n")).is_err());
+        assert!(check_examples(&parse("- name: a\n  target: ../run.sh\n")).is_err());
+        assert!(check_examples(&parse("- input: {a: 1}\n")).is_err());
+    }
+
     #[test]
     fn test_user_invocable_defaults_to_true() {
         let yaml = r#"name: test-skill
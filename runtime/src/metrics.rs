@@ -0,0 +1,209 @@
+//! Sandbox setup latency, per execution backend.
+//!
+//! Each execution records how long the work before the skill's own code
+//! took, split into phases: resolving sandbox paths, the interpreter and the
+//! sandbox profile, spawning the process, or, for WASM, creating the engine,
+//! loading the component and instantiating it. The runtime aggregates these
+//! per backend and phase, which shows where setup time goes (e.g. how often
+//! components are compiled rather than taken from the module cache).
+
+use crate::executor::ExecutionArtifacts;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Setup phases of one execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetupTimings {
+    /// Backend that ran the skill: `wasm`, `seatbelt`, `landlock`,
+    /// `bubblewrap`, `nsjail`, `container` or `direct` (unsandboxed).
+    /// Empty when nothing was executed.
+    pub backend: String,
+    /// Phases in the order they ran.
+    pub phases: Vec<SetupPhase>,
+}
+
+impl SetupTimings {
+    /// Total setup time.
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|p| p.duration).sum()
+    }
+}
+
+/// One timed setup phase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupPhase {
+    /// Phase name, e.g. `profile` or `component_compiled`.
+    pub name: String,
+    /// Time spent in the phase.
+    pub duration: Duration,
+}
+
+/// Aggregated latency of one phase of one backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseMetrics {
+    /// Backend, as in [`SetupTimings::backend`].
+    pub backend: String,
+    /// Phase name.
+    pub phase: String,
+    /// Executions that went through the phase.
+    pub count: u64,
+    /// Time spent in the phase across those executions.
+    pub total: Duration,
+    /// Slowest single occurrence.
+    pub max: Duration,
+}
+
+impl PhaseMetrics {
+    /// Average time per occurrence.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.count as u32
+        }
+    }
+}
+
+/// Marks phase boundaries during setup.
+pub(crate) struct SetupTimer {
+    phases: Vec<SetupPhase>,
+    last: Instant,
+}
+
+impl SetupTimer {
+    pub(crate) fn start() -> Self {
+        Self {
+            phases: Vec::new(),
+            last: Instant::now(),
+        }
+    }
+
+    /// End the current phase, naming it `name`.
+    pub(crate) fn mark(&mut self, name: &str) {
+        let now = Instant::now();
+        self.record(name, now - self.last);
+        self.last = now;
+    }
+
+    /// Add a phase measured elsewhere.
+    pub(crate) fn record(&mut self, name: &str, duration: Duration) {
+        self.phases.push(SetupPhase {
+            name: name.to_string(),
+            duration,
+        });
+    }
+
+    /// The phases marked so far, attributed to `backend`.
+    pub(crate) fn into_timings(self, backend: &str) -> SetupTimings {
+        SetupTimings {
+            backend: backend.to_string(),
+            phases: self.phases,
+        }
+    }
+
+    /// Attach the timings to the artifacts of the finished execution.
+    pub(crate) fn finish(self, backend: &str, mut artifacts: ExecutionArtifacts) -> ExecutionArtifacts {
+        artifacts.setup_timings = self.into_timings(backend);
+        artifacts
+    }
+}
+
+/// Per-backend, per-phase aggregates kept by a runtime.
+#[derive(Debug, Default)]
+pub(crate) struct SandboxMetrics {
+    phases: Mutex<BTreeMap<(String, String), PhaseMetrics>>,
+}
+
+impl SandboxMetrics {
+    pub(crate) fn record(&self, timings: &SetupTimings) {
+        if timings.backend.is_empty() {
+            return;
+        }
+        let mut phases = self.phases.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for phase in &timings.phases {
+            let entry = phases
+                .entry((timings.backend.clone(), phase.name.clone()))
+                .or_insert_with(|| PhaseMetrics {
+                    backend: timings.backend.clone(),
+                    phase: phase.name.clone(),
+                    count: 0,
+                    total: Duration::ZERO,
+                    max: Duration::ZERO,
+                });
+            entry.count += 1;
+            entry.total += phase.duration;
+            entry.max = entry.max.max(phase.duration);
+        }
+    }
+
+    /// Aggregates ordered by backend and phase name.
+    pub(crate) fn snapshot(&self) -> Vec<PhaseMetrics> {
+        let phases = self.phases.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        phases.values().cloned().collect()
+    }
+
+    pub(crate) fn reset(&self) {
+        self.phases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(backend: &str, phases: &[(&str, u64)]) -> SetupTimings {
+        SetupTimings {
+            backend: backend.to_string(),
+            phases: phases
+                .iter()
+                .map(|(name, ms)| SetupPhase {
+                    name: name.to_string(),
+                    duration: Duration::from_millis(*ms),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_aggregates_per_backend_and_phase() {
+        let metrics = SandboxMetrics::default();
+        metrics.record(&timings("landlock", &[("profile", 2), ("spawn", 4)]));
+        metrics.record(&timings("landlock", &[("profile", 6), ("spawn", 4)]));
+        metrics.record(&timings("wasm", &[("component_compiled", 90)]));
+        metrics.record(&timings("", &[("ignored", 1)]));
+
+        let snapshot = metrics.snapshot();
+        let summary: Vec<_> = snapshot
+            .iter()
+            .map(|m| (m.backend.as_str(), m.phase.as_str(), m.count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("landlock", "profile", 2),
+                ("landlock", "spawn", 2),
+                ("wasm", "component_compiled", 1),
+            ]
+        );
+        assert_eq!(snapshot[0].total, Duration::from_millis(8));
+        assert_eq!(snapshot[0].max, Duration::from_millis(6));
+        assert_eq!(snapshot[0].mean(), Duration::from_millis(4));
+
+        metrics.reset();
+        assert!(metrics.snapshot().is_empty());
+    }
+
+    #[test]
+    fn test_timer_marks_phases() {
+        let mut timer = SetupTimer::start();
+        timer.mark("paths");
+        timer.record("engine", Duration::from_millis(5));
+        let names: Vec<_> = timer.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["paths", "engine"]);
+        assert_eq!(timings("wasm", &[("a", 1), ("b", 2)]).total(), Duration::from_millis(3));
+    }
+}
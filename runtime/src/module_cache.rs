@@ -0,0 +1,131 @@
+//! On-disk cache of precompiled WASM components.
+//!
+//! Compiling a large component with Cranelift dominates cold-start time, so
+//! compiled artifacts (`.cwasm`) are kept under
+//! `~/.cache/openskills/modules/`. Entries are keyed by the SHA-256 of the
+//! component bytes and the engine's compatibility hash (wasmtime version and
+//! compilation settings), so an edited module or an upgraded runtime simply
+//! misses. The cache is best-effort: any I/O or deserialization failure
+//! falls back to compiling from source. Size caps and eviction are handled
+//! by [`crate::cache`].
+
+use crate::cache::{CacheKind, CacheManager};
+use sha2::{Digest, Sha256};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+/// Default cache location.
+pub(crate) fn default_cache_dir() -> Option<PathBuf> {
+    CacheManager::default_dir(CacheKind::Modules)
+}
+
+/// Load the component at `path`, from `cache_dir` when a compatible
+/// precompiled entry exists, compiling and storing it otherwise. The flag is
+/// true when the component came from the cache.
+pub(crate) fn load_component(
+    engine: &Engine,
+    path: &Path,
+    cache_dir: Option<&Path>,
+) -> wasmtime::Result<(Component, bool)> {
+    let bytes = std::fs::read(path)?;
+    let Some(cache_dir) = cache_dir else {
+        return Ok((Component::new(engine, &bytes)?, false));
+    };
+    let entry = cache_dir.join(cache_key(engine, &bytes));
+
+    if entry.is_file() {
+        // SAFETY: entries are only written by `store` below, from
+        // `Component::serialize` on an engine with the same compatibility
+        // hash, and wasmtime re-checks the artifact header on load.
+        if let Ok(component) = unsafe { Component::deserialize_file(engine, &entry) } {
+            // Keep hot entries at the back of the eviction order.
+            CacheManager::touch(&entry);
+            return Ok((component, true));
+        }
+    }
+
+    let component = Component::new(engine, &bytes)?;
+    store(&component, cache_dir, &entry);
+    Ok((component, false))
+}
+
+/// `<sha256 of bytes>-<engine hash>.cwasm`
+fn cache_key(engine: &Engine, bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    engine.precompile_compatibility_hash().hash(&mut hasher);
+    format!("{}-{:016x}.cwasm", hex::encode(Sha256::digest(bytes)), hasher.finish())
+}
+
+/// Write `component` to `entry` via a temporary file, so a concurrent
+/// execution never sees a partial artifact.
+fn store(component: &Component, cache_dir: &Path, entry: &Path) {
+    let Ok(serialized) = component.serialize() else {
+        return;
+    };
+    if std::fs::create_dir_all(cache_dir).is_err() {
+        return;
+    }
+    let tmp = entry.with_extension(format!("tmp{}", std::process::id()));
+    if std::fs::write(&tmp, serialized).is_ok() && std::fs::rename(&tmp, entry).is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cached_entries(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .map(|entries| entries.map(|e| e.unwrap().path()).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_component_cached_by_content() {
+        let engine = Engine::default();
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("modules");
+        let module = temp.path().join("skill.wasm");
+
+        std::fs::write(&module, "(component)").unwrap();
+        assert!(!load_component(&engine, &module, Some(&cache)).unwrap().1);
+        let entries = cached_entries(&cache);
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].to_string_lossy().ends_with(".cwasm"));
+
+        // A hit leaves the cache unchanged.
+        assert!(load_component(&engine, &module, Some(&cache)).unwrap().1);
+        assert_eq!(cached_entries(&cache), entries);
+
+        // Changed content gets its own entry.
+        std::fs::write(&module, "(component (core module))").unwrap();
+        load_component(&engine, &module, Some(&cache)).unwrap();
+        assert_eq!(cached_entries(&cache).len(), 2);
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_recompiled() {
+        let engine = Engine::default();
+        let temp = TempDir::new().unwrap();
+        let module = temp.path().join("skill.wasm");
+        std::fs::write(&module, "(component)").unwrap();
+
+        let entry = temp.path().join(cache_key(&engine, b"(component)"));
+        std::fs::write(&entry, b"not a cwasm").unwrap();
+        load_component(&engine, &module, Some(temp.path())).unwrap();
+        assert_ne!(std::fs::read(&entry).unwrap(), b"not a cwasm");
+    }
+
+    #[test]
+    fn test_no_cache_dir_compiles() {
+        let temp = TempDir::new().unwrap();
+        let module = temp.path().join("skill.wasm");
+        std::fs::write(&module, "(component)").unwrap();
+        load_component(&Engine::default(), &module, None).unwrap();
+        assert!(load_component(&Engine::default(), &temp.path().join("missing.wasm"), None).is_err());
+    }
+}
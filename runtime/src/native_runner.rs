@@ -14,15 +14,29 @@
 //! Both platforms follow Claude Code's security approach:
 //! - Allow broad file reads (interpreters need access to libraries)
 //! - Deny specific sensitive paths (~/.ssh, ~/.aws, etc.)
-//! - Allow writes only to explicitly permitted paths (skill root, workspace, temp)
+//! - Allow writes only to explicitly permitted paths (workspace, temp; skill root on opt-in)
 //! - Network and process spawning controlled by allowed_tools
 
 use crate::audit::ExecutionStatus;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::egress::{Egress, EgressPolicy};
+use crate::dry_run::NativePlan;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::dry_run::SandboxProfile;
+use crate::events::OutputStream;
+use crate::io_pool;
+use crate::output_spill::{read_bounded, SpillTarget};
 use crate::errors::OpenSkillError;
+use crate::sandbox_backend::{ContainerConfig, ExecutionBackend};
 use crate::sandbox_mode::SandboxMode;
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+use crate::sandbox_violation::parse_violations;
 use crate::executor::ExecutionArtifacts;
+use crate::metrics::{SetupTimer, SetupTimings};
 use crate::permissions::PermissionEnforcer;
 use crate::registry::Skill;
+use crate::script_session::forward_stdin;
+use crate::secret_redaction::SecretRedactor;
 use serde_json::Value;
 use std::path::{Path, PathBuf};
 use std::thread;
@@ -41,6 +55,41 @@ pub struct NativeRunnerConfig {
     pub python_allow_user_site: bool,
     /// OS sandbox enforcement for native script execution (default: enforce).
     pub sandbox_mode: SandboxMode,
+    /// Sandbox backend used when `sandbox_mode` is enforce (default: auto,
+    /// which prefers bubblewrap on Linux when installed).
+    pub backend: ExecutionBackend,
+    /// Engine, image and limits for the container backend.
+    pub container: ContainerConfig,
+    /// Keep at most this many bytes of stdout/stderr in memory. Larger
+    /// streams are written in full to a workspace file and returned as
+    /// head + tail with a marker naming that file. `None` = unbounded.
+    pub max_output_bytes: Option<usize>,
+}
+
+/// Per-execution CPU and memory caps for native scripts. Enforced on Linux
+/// (cgroup v2, else `RLIMIT_AS` for memory) and by the container backend.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ResourceLimits {
+    /// Memory cap in MB.
+    pub memory_mb: Option<u64>,
+    /// CPU cap as a fraction of one CPU (e.g. `0.5`).
+    pub cpu_quota: Option<f64>,
+}
+
+impl ResourceLimits {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.memory_mb.is_none() && self.cpu_quota.is_none()
+    }
+
+    /// Container config with these limits overriding the configured ones.
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    fn container_config(&self, native_config: Option<&NativeRunnerConfig>) -> ContainerConfig {
+        let mut config = native_config.map(|c| c.container.clone()).unwrap_or_default();
+        config.memory_mb = self.memory_mb.or(config.memory_mb);
+        config.cpus = self.cpu_quota.or(config.cpus);
+        config
+    }
 }
 
 /// Supported native script types.
@@ -72,39 +121,6 @@ pub fn detect_script_type(path: &Path) -> Result<ScriptType, OpenSkillError> {
 // Shared utility functions (platform-independent)
 // ============================================================================
 
-/// Safely join a thread with a timeout to prevent indefinite blocking.
-fn join_thread_with_timeout<T: Send + 'static>(
-    handle: thread::JoinHandle<T>,
-    timeout: Duration,
-) -> Result<T, OpenSkillError> {
-    use std::sync::mpsc;
-
-    let (tx, rx) = mpsc::channel();
-
-    // Spawn a watcher thread
-    thread::spawn(move || {
-        let result = handle.join();
-        let _ = tx.send(result);
-    });
-
-    match rx.recv_timeout(timeout) {
-        Ok(Ok(result)) => Ok(result),
-        Ok(Err(_)) => Err(OpenSkillError::NativeExecutionError(
-            "Thread panicked during execution".to_string(),
-        )),
-        Err(_) => Err(OpenSkillError::Timeout),
-    }
-}
-
-/// Read all bytes from an optional stream (used for stdout/stderr capture).
-fn read_stream<T: std::io::Read>(mut stream: Option<T>) -> Vec<u8> {
-    let mut buf = Vec::new();
-    if let Some(ref mut reader) = stream {
-        let _ = reader.read_to_end(&mut buf);
-    }
-    buf
-}
-
 /// Resolve an executable by searching PATH.
 fn resolve_executable(program: &str) -> Option<PathBuf> {
     let program_path = Path::new(program);
@@ -125,7 +141,7 @@ fn resolve_executable(program: &str) -> Option<PathBuf> {
 
 // Sensitive paths that should never be readable even with broad file-read access.
 // Following Claude Code's approach: allow broad reads, deny specific sensitive paths.
-const SENSITIVE_DENY_PATHS: &[&str] = &[
+pub(crate) const SENSITIVE_DENY_PATHS: &[&str] = &[
     "~/.ssh",
     "~/.gnupg",
     "~/.aws",
@@ -145,6 +161,211 @@ const SENSITIVE_DENY_PATHS: &[&str] = &[
     "~/.zprofile",
 ];
 
+/// Network egress allowlist declared in the skill manifest.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn skill_egress_policy(skill: &Skill) -> EgressPolicy {
+    EgressPolicy::new(&skill.manifest.allowed_hosts, &skill.manifest.allowed_ports)
+}
+
+/// Grants from the skill's `sandbox:` section. The runtime clears the
+/// section before execution unless the host policy approved it.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+#[derive(Debug, Default)]
+struct SkillSandbox {
+    read_paths: Vec<PathBuf>,
+    write_paths: Vec<PathBuf>,
+    allow_network: bool,
+    allow_process: bool,
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn skill_sandbox(skill: &Skill, skill_root: &Path) -> SkillSandbox {
+    let Some(section) = skill.manifest.sandbox.as_ref() else {
+        return SkillSandbox::default();
+    };
+    let home = std::env::var("HOME").ok();
+    let resolve = |path: &String| {
+        let path = match (path.strip_prefix("~/"), &home) {
+            (Some(rest), Some(home)) => Path::new(home).join(rest),
+            _ => skill_root.join(path),
+        };
+        path.canonicalize().unwrap_or(path)
+    };
+    SkillSandbox {
+        read_paths: section.read_paths.iter().map(resolve).collect(),
+        write_paths: section.write_paths.iter().map(resolve).collect(),
+        allow_network: section.allow_network,
+        allow_process: section.allow_process,
+    }
+}
+
+/// The skill root is read-only unless the skill declared `writes-skill-root`
+/// and the host approved it. `Write`/`Bash` map to a `.` write path, which
+/// would otherwise make the root writable as well. With workspace-only
+/// writes, nothing but the workspace (added by the caller) stays writable.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn skill_root_write_access(
+    write_paths: &mut Vec<PathBuf>,
+    skill: &Skill,
+    skill_root: &Path,
+    enforcer: &PermissionEnforcer,
+) {
+    write_paths.retain(|p| p != skill_root);
+    if enforcer.writes_workspace_only() {
+        write_paths.clear();
+    } else if skill.manifest.writes_skill_root {
+        write_paths.push(skill_root.to_path_buf());
+    }
+}
+
+/// Sandbox grants for a dry run, resolved like `execute_native` does but
+/// without creating the workspace.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+struct PlannedGrants {
+    skill_root: PathBuf,
+    read_paths: Vec<PathBuf>,
+    write_paths: Vec<PathBuf>,
+    allow_network: bool,
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    allow_process: bool,
+    egress_policy: EgressPolicy,
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+impl PlannedGrants {
+    fn resolve(
+        skill: &Skill,
+        script_type: ScriptType,
+        enforcer: &PermissionEnforcer,
+        workspace_dir: Option<&Path>,
+    ) -> Self {
+        let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+        let skill_root = canonical(&skill.root);
+        let extra = skill_sandbox(skill, &skill_root);
+        let mut read_paths: Vec<PathBuf> = enforcer
+            .filesystem_read_paths()
+            .iter()
+            .map(|p| canonical(p))
+            .collect();
+        let mut write_paths: Vec<PathBuf> = enforcer
+            .filesystem_write_paths()
+            .iter()
+            .map(|p| canonical(p))
+            .collect();
+        read_paths.extend(extra.read_paths);
+        write_paths.extend(extra.write_paths);
+        skill_root_write_access(&mut write_paths, skill, &skill_root, enforcer);
+        write_paths.extend(workspace_dir.map(canonical));
+        Self {
+            skill_root,
+            read_paths,
+            write_paths,
+            allow_network: extra.allow_network || enforcer.network_enabled(),
+            allow_process: script_type == ScriptType::Shell
+                || extra.allow_process
+                || enforcer.process_enabled(),
+            egress_policy: skill_egress_policy(skill),
+        }
+    }
+
+    /// Profile for the container backend: see [`spawn_in_container`].
+    fn container_profile(&self) -> SandboxProfile {
+        let mut read_only = Vec::new();
+        if !self.write_paths.contains(&self.skill_root) {
+            read_only.push(self.skill_root.clone());
+        }
+        read_only.extend(self.read_paths.iter().cloned());
+        SandboxProfile {
+            read_only,
+            read_write: self.write_paths.clone(),
+            allow_network: self.allow_network && self.egress_policy.is_unrestricted(),
+            allow_process: true,
+            ..SandboxProfile::default()
+        }
+    }
+}
+
+// ============================================================================
+// Container backend (Docker/Podman), shared by macOS and Linux
+// ============================================================================
+
+/// Spawn a native script inside a container (see [`ExecutionBackend::Container`]).
+///
+/// `apply_env` sets the script environment on the engine command; variables
+/// are then forwarded into the container by name. The skill root and read
+/// paths are mounted read-only, write paths (including the workspace and an
+/// approved writable skill root) read-write, and the interpreter is resolved inside the image. The
+/// container network cannot be filtered, so a skill with an egress
+/// allowlist gets no network at all.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+#[allow(clippy::too_many_arguments)]
+fn spawn_in_container(
+    config: &ContainerConfig,
+    script_type: ScriptType,
+    script_path: &Path,
+    script_args: &[String],
+    skill_root: &Path,
+    read_paths: &[PathBuf],
+    write_paths: &[PathBuf],
+    allow_network: bool,
+    egress_policy: &EgressPolicy,
+    timeout_ms: u64,
+    apply_env: impl FnOnce(&mut std::process::Command),
+) -> Result<std::process::Child, OpenSkillError> {
+    use crate::sandbox_backend::container;
+    use std::process::{Command, Stdio};
+
+    let engine = container::find_engine(config).ok_or_else(|| {
+        OpenSkillError::NativeExecutionError(
+            "container backend requires docker or podman in PATH".to_string(),
+        )
+    })?;
+
+    // Docker rejects duplicate mount points; a writable skill root is
+    // mounted once, read-write.
+    let mut ro_mounts = Vec::new();
+    if !write_paths.iter().any(|p| p == skill_root) {
+        ro_mounts.push(skill_root.to_path_buf());
+    }
+    ro_mounts.extend(read_paths.iter().cloned());
+    let spec = container::ContainerSpec {
+        ro_mounts,
+        rw_mounts: write_paths.to_vec(),
+        allow_network: allow_network && egress_policy.is_unrestricted(),
+        workdir: skill_root.to_path_buf(),
+        timeout_ms,
+    };
+    let command = container_command(script_type, script_path, script_args);
+
+    let mut cmd = Command::new(engine);
+    apply_env(&mut cmd);
+    container::prepare(&mut cmd, config, &spec, &command[0], &command[1..]);
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.spawn().map_err(|e| {
+        OpenSkillError::NativeExecutionError(format!("Failed to start container: {e}"))
+    })
+}
+
+/// Interpreter (resolved inside the image), script and arguments for the
+/// container backend.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn container_command(
+    script_type: ScriptType,
+    script_path: &Path,
+    script_args: &[String],
+) -> Vec<String> {
+    let program = match script_type {
+        ScriptType::Python => "python3",
+        ScriptType::Shell => "bash",
+    };
+    std::iter::once(program.to_string())
+        .chain(std::iter::once(script_path.to_string_lossy().to_string()))
+        .chain(script_args.iter().cloned())
+        .collect()
+}
+
 // ============================================================================
 // macOS implementation (Seatbelt)
 // ============================================================================
@@ -152,7 +373,7 @@ const SENSITIVE_DENY_PATHS: &[&str] = &[
 #[cfg(target_os = "macos")]
 mod macos {
     use super::*;
-    use std::fs::OpenOptions;
+    use crate::temp_artifacts::TempArtifact;
     use std::io::Write;
     use std::process::{Command, Stdio};
     use std::time::Instant;
@@ -199,6 +420,7 @@ mod macos {
         "/private/var/folders",
     ];
 
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_native(
         skill: &Skill,
         script_path: &Path,
@@ -206,10 +428,11 @@ mod macos {
         input: Value,
         timeout_ms: u64,
         enforcer: &PermissionEnforcer,
-        allowed_tools: &[String],
         workspace_dir: Option<&Path>,
         script_args: &[String],
         native_config: Option<&NativeRunnerConfig>,
+        secret_redactor: Option<&SecretRedactor>,
+        limits: ResourceLimits,
     ) -> Result<ExecutionArtifacts, OpenSkillError> {
         if !script_path.exists() {
             return Err(OpenSkillError::NativeExecutionError(format!(
@@ -218,24 +441,27 @@ mod macos {
             )));
         }
         // Callers (executor) only pass timeout_ms from WasmConfig default or options.timeout_ms when > 0; 0 is never passed.
+        let mut timer = SetupTimer::start();
 
         let input_json = serde_json::to_string(&input)?;
-        let allow_network = allowed_tools.iter().any(|t| t == "WebSearch" || t == "Fetch");
-        // Only Shell scripts get process permissions by default.
-        // Python scripts require explicit Bash/Terminal permission to spawn subprocesses.
-        let allow_process = script_type == ScriptType::Shell
-            || allowed_tools
-                .iter()
-                .any(|t| t == "Bash" || t == "Terminal");
-
+        let spill = SpillTarget::for_execution(native_config, workspace_dir, &skill.id, secret_redactor);
         // Canonicalize skill_root first to ensure path consistency
         let skill_root = skill
             .root
             .canonicalize()
             .unwrap_or_else(|_| skill.root.clone());
+        let extra = skill_sandbox(skill, &skill_root);
+
+        let allow_network = extra.allow_network || enforcer.network_enabled();
+        let egress_policy = skill_egress_policy(skill);
+        // Only Shell scripts get process permissions by default. Other
+        // scripts need a tool that grants subprocesses (Bash/Terminal by default).
+        let allow_process = script_type == ScriptType::Shell
+            || extra.allow_process
+            || enforcer.process_enabled();
 
         // Get paths from enforcer and canonicalize them to match canonicalized skill_root
-        let read_paths: Vec<PathBuf> = enforcer
+        let mut read_paths: Vec<PathBuf> = enforcer
             .filesystem_read_paths()
             .iter()
             .map(|p| {
@@ -251,6 +477,9 @@ mod macos {
                     .unwrap_or_else(|_| p.to_path_buf())
             })
             .collect();
+        read_paths.extend(extra.read_paths);
+        write_paths.extend(extra.write_paths);
+        skill_root_write_access(&mut write_paths, skill, &skill_root, enforcer);
 
         // Add workspace directory to write paths if configured (create if missing, for parity with Linux)
         if let Some(workspace) = workspace_dir {
@@ -266,8 +495,10 @@ mod macos {
             .map(|c| c.sandbox_mode)
             .unwrap_or(SandboxMode::Enforce);
 
+        timer.mark("paths");
         let (program, args, program_path) =
             command_for_script(script_type, script_path, native_config)?;
+        timer.mark("interpreter");
 
         if sandbox_mode == SandboxMode::Disabled {
             let mut cmd = Command::new(&program);
@@ -294,6 +525,7 @@ mod macos {
                     "Failed to execute without sandbox: {e}"
                 ))
             })?;
+            timer.mark("spawn");
             return run_native_child(
                 child,
                 &input_json,
@@ -301,7 +533,54 @@ mod macos {
                 enforcer,
                 None,
                 sandbox_mode,
-            );
+                spill,
+            )
+            .map(|artifacts| timer.finish("direct", artifacts));
+        }
+
+        let backend = native_config.map(|c| c.backend).unwrap_or_default();
+        if backend == ExecutionBackend::Bubblewrap {
+            return Err(OpenSkillError::UnsupportedPlatform(
+                "bubblewrap backend is only available on Linux".to_string(),
+            ));
+        }
+        if backend == ExecutionBackend::Container {
+            timer.mark("profile");
+            let child = spawn_in_container(
+                &limits.container_config(native_config),
+                script_type,
+                script_path,
+                script_args,
+                &skill_root,
+                &read_paths,
+                &write_paths,
+                allow_network,
+                &egress_policy,
+                timeout_ms,
+                |cmd| {
+                    apply_environment(
+                        cmd,
+                        skill,
+                        &input_json,
+                        timeout_ms,
+                        enforcer,
+                        script_type,
+                        workspace_dir,
+                        native_config,
+                    )
+                },
+            )?;
+            timer.mark("spawn");
+            return run_native_child(
+                child,
+                &input_json,
+                timeout_ms,
+                enforcer,
+                None,
+                SandboxMode::Enforce,
+                spill,
+            )
+            .map(|artifacts| timer.finish("container", artifacts));
         }
 
         // Canonicalize the executable path for the seatbelt profile
@@ -330,19 +609,24 @@ mod macos {
         } else {
             None
         };
+        // Kept alive until the script exits (may run the filtering proxy).
+        let egress = Egress::start(
+            allow_network,
+            &egress_policy,
+            crate::egress::ConnectFilter::AddressesAndPorts,
+        )?;
         let profile = build_seatbelt_profile(
-            &skill_root,
             &read_paths_with_parent,
             &write_paths,
-            allow_network,
+            &egress,
             allow_process,
             exec_path.as_deref(),
             exec_parent_path.as_deref(),
         );
 
-        let profile_path = write_profile(&profile)?;
+        let profile = write_profile(&profile)?;
         let mut cmd = Command::new("sandbox-exec");
-        cmd.arg("-f").arg(&profile_path).arg("--").arg(program).args(args);
+        cmd.arg("-f").arg(profile.path()).arg("--").arg(program).args(args);
         // Append user-provided script arguments (e.g., "my-test" for init-artifact.sh)
         if !script_args.is_empty() {
             cmd.args(script_args);
@@ -365,25 +649,128 @@ mod macos {
             workspace_dir,
             native_config,
         );
+        cmd.envs(egress.env_vars());
 
+        timer.mark("profile");
         let child = match cmd.spawn() {
             Ok(child) => child,
             Err(e) => {
-                let _ = std::fs::remove_file(&profile_path);
                 return Err(OpenSkillError::SeatbeltError(format!(
                     "Failed to execute with seatbelt: {e}"
                 )));
             }
         };
+        timer.mark("spawn");
 
         run_native_child(
             child,
             &input_json,
             timeout_ms,
             enforcer,
-            Some(profile_path),
+            Some(profile),
             sandbox_mode,
+            spill,
         )
+        .map(|artifacts| timer.finish("seatbelt", artifacts))
+    }
+
+    /// Resolve how `execute_native` would run the script, without running it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_native(
+        skill: &Skill,
+        script_path: &Path,
+        script_type: ScriptType,
+        enforcer: &PermissionEnforcer,
+        workspace_dir: Option<&Path>,
+        script_args: &[String],
+        native_config: Option<&NativeRunnerConfig>,
+        _limits: ResourceLimits,
+    ) -> Result<NativePlan, OpenSkillError> {
+        if !script_path.exists() {
+            return Err(OpenSkillError::NativeExecutionError(format!(
+                "Script not found: {}",
+                script_path.display()
+            )));
+        }
+        let grants = PlannedGrants::resolve(skill, script_type, enforcer, workspace_dir);
+        let (program, args, program_path) =
+            command_for_script(script_type, script_path, native_config)?;
+        let command: Vec<String> = std::iter::once(program)
+            .chain(args)
+            .chain(script_args.iter().cloned())
+            .collect();
+        let plan = |backend, sandbox| NativePlan {
+            command: command.clone(),
+            backend,
+            sandbox,
+        };
+
+        let sandbox_mode = native_config
+            .map(|c| c.sandbox_mode)
+            .unwrap_or(SandboxMode::Enforce);
+        if sandbox_mode == SandboxMode::Disabled {
+            return Ok(plan("direct", None));
+        }
+        let backend = native_config.map(|c| c.backend).unwrap_or_default();
+        if backend == ExecutionBackend::Bubblewrap {
+            return Err(OpenSkillError::UnsupportedPlatform(
+                "bubblewrap backend is only available on Linux".to_string(),
+            ));
+        }
+        if backend == ExecutionBackend::Container {
+            return Ok(NativePlan {
+                command: container_command(script_type, script_path, script_args),
+                backend: "container",
+                sandbox: Some(grants.container_profile()),
+            });
+        }
+
+        let exec_path = program_path
+            .as_ref()
+            .map(|p| p.canonicalize().unwrap_or_else(|_| p.clone()));
+        let mut read_paths = grants.read_paths.clone();
+        if let Some(parent) = exec_path.as_ref().and_then(|p| p.parent()) {
+            let parent = parent.canonicalize().unwrap_or_else(|_| parent.to_path_buf());
+            if !SYSTEM_READ_PATHS.iter().any(|sys_path| parent.starts_with(sys_path)) {
+                read_paths.push(parent);
+            }
+        }
+        // Rendering the network rules of a host allowlist starts the
+        // filtering proxy; each run gets its own, on a different port.
+        let egress = Egress::start(
+            grants.allow_network,
+            &grants.egress_policy,
+            crate::egress::ConnectFilter::AddressesAndPorts,
+        )?;
+        let profile = build_seatbelt_profile(
+            &read_paths,
+            &grants.write_paths,
+            &egress,
+            grants.allow_process,
+            exec_path.as_deref(),
+            None,
+        );
+        let home = std::env::var("HOME").unwrap_or_else(|_| "/Users".to_string());
+        Ok(plan(
+            "seatbelt",
+            Some(SandboxProfile {
+                read_only: read_paths,
+                read_write: TEMP_PATHS
+                    .iter()
+                    .map(PathBuf::from)
+                    .chain(grants.write_paths.iter().cloned())
+                    .collect(),
+                deny: SENSITIVE_DENY_PATHS
+                    .iter()
+                    .map(|p| PathBuf::from(p.replace('~', &home)))
+                    .collect(),
+                allow_network: egress.allow_network,
+                allowed_hosts: grants.egress_policy.hosts.clone(),
+                allowed_ports: grants.egress_policy.ports.clone(),
+                allow_process: grants.allow_process,
+                generated: Some(profile),
+            }),
+        ))
     }
 
     fn run_native_child(
@@ -391,30 +778,32 @@ mod macos {
         input_json: &str,
         timeout_ms: u64,
         enforcer: &PermissionEnforcer,
-        profile_path: Option<PathBuf>,
+        profile: Option<TempArtifact>,
         sandbox_mode: SandboxMode,
+        spill: Option<SpillTarget>,
     ) -> Result<ExecutionArtifacts, OpenSkillError> {
+        enforcer.spawned(child.id());
         if let Some(mut stdin) = child.stdin.take() {
             let input_clone = input_json.to_string();
-            thread::spawn(move || {
-                let _ = stdin.write_all(input_clone.as_bytes());
+            let session = enforcer.session_stdin();
+            io_pool::spawn(move || match session {
+                Some(messages) => forward_stdin(messages, stdin),
+                None => {
+                    let _ = stdin.write_all(input_clone.as_bytes());
+                }
             });
         }
 
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
+        let stdout = enforcer.events().forward(child.stdout.take(), OutputStream::Stdout);
+        let stderr = enforcer.events().forward(child.stderr.take(), OutputStream::Stderr);
 
-        let stdout_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stdout)))
-                .unwrap_or_else(|_| Vec::new())
-        });
-        let stderr_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stderr)))
-                .unwrap_or_else(|_| Vec::new())
-        });
+        let stderr_spill = spill.clone();
+        let stdout_task = io_pool::spawn(move || read_bounded(stdout, spill.as_ref(), "stdout"));
+        let stderr_task = io_pool::spawn(move || read_bounded(stderr, stderr_spill.as_ref(), "stderr"));
 
         let start = Instant::now();
         let mut timed_out = false;
+        let mut cancelled = false;
         let status = loop {
             if let Some(status) = child.try_wait().map_err(OpenSkillError::Io)? {
                 break Some(status);
@@ -424,21 +813,32 @@ mod macos {
                 let _ = child.kill();
                 break child.wait().ok();
             }
+            if enforcer.cancellation().is_cancelled() {
+                cancelled = true;
+                let _ = child.kill();
+                break child.wait().ok();
+            }
             thread::sleep(Duration::from_millis(10));
         };
 
-        let stdout_bytes = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stderr_bytes = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
-        let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+        let (stdout, binary_stdout) =
+            stdout_task.join_timeout(Duration::from_secs(5))
+                .unwrap_or_default()
+                .into_stdout();
+        let stderr = stderr_task.join_timeout(Duration::from_secs(5))
+            .unwrap_or_default()
+            .into_text();
 
         let (exit_status, output) = if timed_out {
             (
                 ExecutionStatus::Timeout,
                 serde_json::json!({ "status": "error", "error": "execution timeout" }),
             )
+        } else if cancelled {
+            (
+                ExecutionStatus::Failed("cancelled".to_string()),
+                serde_json::json!({ "status": "error", "error": "cancelled" }),
+            )
         } else if let Some(status) = status {
             if status.success() {
                 let output = if let Ok(json) = serde_json::from_str::<Value>(&stdout) {
@@ -465,9 +865,13 @@ mod macos {
             )
         };
 
-        if let Some(path) = profile_path {
-            let _ = std::fs::remove_file(path);
-        }
+        drop(profile);
+
+        let sandbox_violations = if sandbox_mode == SandboxMode::Enforce {
+            parse_violations(&stderr, None)
+        } else {
+            Vec::new()
+        };
 
         Ok(ExecutionArtifacts {
             output,
@@ -476,6 +880,13 @@ mod macos {
             permissions_used: enforcer.permissions_used(),
             exit_status,
             sandbox_mode,
+            binary_stdout,
+            sandbox_violations,
+            fuel_consumed: None,
+            network_requests: Vec::new(),
+            host_calls: Vec::new(),
+            capability_downgrades: Vec::new(),
+            setup_timings: SetupTimings::default(),
         })
     }
 
@@ -538,6 +949,7 @@ mod macos {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_environment(
         cmd: &mut Command,
         skill: &Skill,
@@ -565,6 +977,10 @@ mod macos {
         // would cause issues since stdin may contain JSON input data.
         cmd.env("CI", "true");
 
+        // Captured output is not a terminal: ask tools for plain output
+        // (no colors, cursor movement or progress bars).
+        cmd.env("TERM", "dumb");
+
         if let Ok(lang) = std::env::var("LANG") {
             cmd.env("LANG", lang);
         }
@@ -595,6 +1011,7 @@ mod macos {
                 cmd.env(key, val);
             }
         }
+        cmd.envs(enforcer.extra_env().iter().cloned());
 
         if script_type == ScriptType::Python {
             cmd.env("PYTHONUNBUFFERED", "1");
@@ -622,10 +1039,9 @@ mod macos {
     }
 
     fn build_seatbelt_profile(
-        skill_root: &Path,
         read_paths: &[PathBuf],
         write_paths: &[PathBuf],
-        allow_network: bool,
+        egress: &Egress,
         allow_process: bool,
         _exec_path: Option<&Path>,
         _exec_parent_path: Option<&Path>,
@@ -695,12 +1111,6 @@ mod macos {
             ));
         }
 
-        // Allow writes to skill root directory
-        profile.push_str(&format!(
-            "(allow file-write* (subpath \"{}\"))\n",
-            escape_path(skill_root.to_string_lossy().as_ref())
-        ));
-
         // Allow writes to explicitly configured paths
         for path in write_paths {
             profile.push_str(&format!(
@@ -726,10 +1136,9 @@ mod macos {
             // strict-sandbox execution with exit code 1.
         }
 
-        // Network access only if explicitly allowed
-        if allow_network {
-            profile.push_str("(allow network*)\n");
-        }
+        // Network access only if explicitly allowed, narrowed to the
+        // manifest's allowed hosts/ports
+        profile.push_str(&egress.seatbelt_rules());
 
         profile
     }
@@ -738,40 +1147,15 @@ mod macos {
         path.replace('"', "\\\"")
     }
 
-    fn write_profile(profile: &str) -> Result<PathBuf, OpenSkillError> {
-        use rand::Rng;
-        
-        let temp_dir = std::env::temp_dir();
-        let pid = std::process::id();
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis();
-        let random_suffix: u32 = rand::thread_rng().gen();
-        
-        let filename = format!(
-            "openskills-seatbelt-{}-{}-{}.sb",
-            pid, timestamp, random_suffix
-        );
-        let path = temp_dir.join(filename);
-        
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&path)
-            .map_err(|e| OpenSkillError::SeatbeltError(format!(
-                "Failed to create seatbelt profile file: {}", e
-            )))?;
-        
-        file.write_all(profile.as_bytes()).map_err(OpenSkillError::Io)?;
-        file.flush().map_err(OpenSkillError::Io)?;
-        
-        Ok(path)
+    fn write_profile(profile: &str) -> Result<TempArtifact, OpenSkillError> {
+        TempArtifact::write("seatbelt.sb", profile.as_bytes()).map_err(|e| {
+            OpenSkillError::SeatbeltError(format!("Failed to create seatbelt profile file: {}", e))
+        })
     }
 }
 
 #[cfg(target_os = "macos")]
-pub use macos::execute_native;
+pub use macos::{execute_native, plan_native};
 
 // ============================================================================
 // Linux implementation (Landlock LSM)
@@ -782,12 +1166,15 @@ mod linux {
     use super::*;
     use std::io::Write;
     use std::os::unix::process::CommandExt;
+    use std::os::fd::AsRawFd;
     use std::os::unix::process::ExitStatusExt;
     use std::process::{Command, Stdio};
     use std::time::Instant;
 
+    use crate::resource_limits::LimitGuard;
+    use crate::sandbox_backend::{bwrap, nsjail};
     use landlock::{
-        Access, AccessFs, PathBeneath, PathFd,
+        Access, AccessFs, AccessNet, NetPort, PathBeneath, PathFd,
         Ruleset, RulesetAttr, RulesetCreatedAttr, ABI,
     };
 
@@ -823,6 +1210,7 @@ mod linux {
     // Temp directories that need read/write access
     const TEMP_PATHS: &[&str] = &["/tmp", "/var/tmp"];
 
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_native(
         skill: &Skill,
         script_path: &Path,
@@ -830,10 +1218,11 @@ mod linux {
         input: Value,
         timeout_ms: u64,
         enforcer: &PermissionEnforcer,
-        allowed_tools: &[String],
         workspace_dir: Option<&Path>,
         script_args: &[String],
         native_config: Option<&NativeRunnerConfig>,
+        secret_redactor: Option<&SecretRedactor>,
+        limits: ResourceLimits,
     ) -> Result<ExecutionArtifacts, OpenSkillError> {
         if !script_path.exists() {
             return Err(OpenSkillError::NativeExecutionError(format!(
@@ -842,23 +1231,24 @@ mod linux {
             )));
         }
         // Callers (executor) only pass timeout_ms from WasmConfig default or options.timeout_ms when > 0; 0 is never passed.
+        let mut timer = SetupTimer::start();
 
         let input_json = serde_json::to_string(&input)?;
-        let _allow_network = allowed_tools
-            .iter()
-            .any(|t| t == "WebSearch" || t == "Fetch");
-        let _allow_process = script_type == ScriptType::Shell
-            || allowed_tools
-                .iter()
-                .any(|t| t == "Bash" || t == "Terminal");
-
+        let spill = SpillTarget::for_execution(native_config, workspace_dir, &skill.id, secret_redactor);
         // Canonicalize paths
         let skill_root = skill
             .root
             .canonicalize()
             .unwrap_or_else(|_| skill.root.clone());
+        let extra = skill_sandbox(skill, &skill_root);
 
-        let read_paths: Vec<PathBuf> = enforcer
+        let allow_network = extra.allow_network || enforcer.network_enabled();
+        let egress_policy = skill_egress_policy(skill);
+        let _allow_process = script_type == ScriptType::Shell
+            || extra.allow_process
+            || enforcer.process_enabled();
+
+        let mut read_paths: Vec<PathBuf> = enforcer
             .filesystem_read_paths()
             .iter()
             .map(|p| p.canonicalize().unwrap_or_else(|_| p.to_path_buf()))
@@ -869,6 +1259,9 @@ mod linux {
             .iter()
             .map(|p| p.canonicalize().unwrap_or_else(|_| p.to_path_buf()))
             .collect();
+        read_paths.extend(extra.read_paths);
+        write_paths.extend(extra.write_paths);
+        skill_root_write_access(&mut write_paths, skill, &skill_root, enforcer);
 
         // Add workspace directory to write paths if configured
         if let Some(workspace) = workspace_dir {
@@ -886,8 +1279,16 @@ mod linux {
             .map(|c| c.sandbox_mode)
             .unwrap_or(SandboxMode::Enforce);
 
+        timer.mark("paths");
         let (program, args) =
             command_for_script(script_type, script_path, native_config)?;
+        timer.mark("interpreter");
+        // Held until the script exits; removes the transient cgroup on drop.
+        let limit_guard = LimitGuard::new(&limits);
+        let child_limits = limit_guard.child_limits();
+        if !limits.is_unlimited() {
+            timer.mark("cgroup");
+        }
 
         if sandbox_mode == SandboxMode::Disabled {
             let mut cmd = Command::new(&program);
@@ -909,12 +1310,18 @@ mod linux {
                 workspace_dir,
                 native_config,
             );
+            unsafe {
+                cmd.pre_exec(move || child_limits.apply());
+            }
             let child = cmd.spawn().map_err(|e| {
                 OpenSkillError::NativeExecutionError(format!(
                     "Failed to execute without sandbox: {e}"
                 ))
             })?;
-            return run_native_child(child, &input_json, timeout_ms, enforcer, sandbox_mode);
+            timer.mark("spawn");
+            let artifacts =
+                run_native_child(child, &input_json, timeout_ms, enforcer, sandbox_mode, spill)?;
+            return Ok(timer.finish("direct", limit_guard.finish(artifacts)));
         }
 
         // --- Collect Landlock path sets ---
@@ -933,13 +1340,13 @@ mod linux {
             }
         }
 
-        // Read-write paths: temp dirs + skill root + enforcer write paths + workspace
+        // Read-write paths: temp dirs + enforcer write paths + workspace
+        // (+ skill root when `writes-skill-root` was approved)
         let mut rw_paths: Vec<PathBuf> = TEMP_PATHS
             .iter()
             .map(PathBuf::from)
             .filter(|p| p.exists())
             .collect();
-        rw_paths.push(skill_root.clone());
         for p in &write_paths {
             if !rw_paths.contains(p) {
                 rw_paths.push(p.clone());
@@ -952,6 +1359,144 @@ mod linux {
             .map(|p| PathBuf::from(p.replace('~', &home)))
             .collect();
 
+        let backend = native_config.map(|c| c.backend).unwrap_or_default();
+        if backend == ExecutionBackend::Container {
+            timer.mark("profile");
+            let child = spawn_in_container(
+                &limits.container_config(native_config),
+                script_type,
+                script_path,
+                script_args,
+                &skill_root,
+                &read_paths,
+                &write_paths,
+                allow_network,
+                &egress_policy,
+                timeout_ms,
+                |cmd| {
+                    apply_environment(
+                        cmd,
+                        skill,
+                        &input_json,
+                        timeout_ms,
+                        enforcer,
+                        script_type,
+                        workspace_dir,
+                        native_config,
+                    )
+                },
+            )?;
+            timer.mark("spawn");
+            let artifacts =
+                run_native_child(child, &input_json, timeout_ms, enforcer, SandboxMode::Enforce, spill)?;
+            return Ok(timer.finish("container", artifacts));
+        }
+        // bwrap and nsjail cannot filter outbound ports: Auto prefers Landlock
+        // for skills with an egress allowlist, an explicit Bubblewrap or
+        // Nsjail backend fails closed.
+        let egress_restricted = allow_network && !egress_policy.is_unrestricted();
+        // `resolve()` turns Nsjail into Native when nsjail is unavailable.
+        if backend.resolve() == ExecutionBackend::Nsjail {
+            let spec = nsjail_spec(
+                &program,
+                &skill_root,
+                &read_paths,
+                &write_paths,
+                deny_paths,
+                allow_network && !egress_restricted,
+                timeout_ms,
+                &limits,
+            );
+            let mut cmd = nsjail::command(&spec, &program, &args, script_args).ok_or_else(|| {
+                OpenSkillError::LinuxSandboxError("nsjail not found in PATH".to_string())
+            })?;
+            cmd.stdin(Stdio::piped());
+            cmd.stdout(Stdio::piped());
+            cmd.stderr(Stdio::piped());
+            apply_environment(
+                &mut cmd,
+                skill,
+                &input_json,
+                timeout_ms,
+                enforcer,
+                script_type,
+                workspace_dir,
+                native_config,
+            );
+            // nsjail and the jailed script inherit the cgroup.
+            unsafe {
+                cmd.pre_exec(move || child_limits.apply());
+            }
+            timer.mark("profile");
+            let child = cmd.spawn().map_err(|e| {
+                OpenSkillError::LinuxSandboxError(format!(
+                    "Failed to execute with nsjail sandbox: {e}"
+                ))
+            })?;
+            timer.mark("spawn");
+            let artifacts =
+                run_native_child(child, &input_json, timeout_ms, enforcer, SandboxMode::Enforce, spill)?;
+            return Ok(timer.finish("nsjail", limit_guard.finish(artifacts)));
+        }
+        if backend.resolve() == ExecutionBackend::Bubblewrap
+            && !(backend == ExecutionBackend::Auto && egress_restricted)
+        {
+            let spec = bwrap_spec(
+                &program,
+                &skill_root,
+                &read_paths,
+                &write_paths,
+                deny_paths,
+                allow_network && !egress_restricted,
+            );
+            // bwrap installs the seccomp filter itself, after setting up the namespaces.
+            let syscall_filter_fd = crate::seccomp::build_filter(spec.allow_network)
+                .and_then(|filter| crate::seccomp::to_memfd(&filter).ok());
+            let spec = bwrap::BwrapSpec {
+                seccomp_fd: syscall_filter_fd.as_ref().map(|fd| fd.as_raw_fd()),
+                ..spec
+            };
+            let mut cmd = bwrap::command(&spec, &program, &args, script_args).ok_or_else(|| {
+                OpenSkillError::LinuxSandboxError("bwrap not found in PATH".to_string())
+            })?;
+            cmd.stdin(Stdio::piped());
+            cmd.stdout(Stdio::piped());
+            cmd.stderr(Stdio::piped());
+            apply_environment(
+                &mut cmd,
+                skill,
+                &input_json,
+                timeout_ms,
+                enforcer,
+                script_type,
+                workspace_dir,
+                native_config,
+            );
+            // bwrap and everything it starts inherit the cgroup and rlimits.
+            // Only bwrap inherits the filter memfd; it is close-on-exec otherwise.
+            let filter_fd = spec.seccomp_fd;
+            unsafe {
+                cmd.pre_exec(move || {
+                    child_limits.apply()?;
+                    match filter_fd {
+                        Some(fd) => crate::seccomp::inherit_fd(fd),
+                        None => Ok(()),
+                    }
+                });
+            }
+            timer.mark("profile");
+            let child = cmd.spawn().map_err(|e| {
+                OpenSkillError::LinuxSandboxError(format!(
+                    "Failed to execute with bubblewrap sandbox: {e}"
+                ))
+            })?;
+            timer.mark("spawn");
+            drop(syscall_filter_fd);
+            let artifacts =
+                run_native_child(child, &input_json, timeout_ms, enforcer, SandboxMode::Enforce, spill)?;
+            return Ok(timer.finish("bubblewrap", limit_guard.finish(artifacts)));
+        }
+
         // --- Build command with pre_exec Landlock sandbox ---
         let mut cmd = Command::new(&program);
         cmd.args(&args);
@@ -976,25 +1521,234 @@ mod linux {
             native_config,
         );
 
+        // Narrow the network to the manifest's allowed hosts/ports; kept alive
+        // until the script exits (may run the filtering proxy).
+        let egress = Egress::start(
+            allow_network,
+            &egress_policy,
+            crate::egress::landlock_connect_filter(),
+        )?;
+        cmd.envs(egress.env_vars());
+
         // Apply Landlock sandbox restrictions in the child process before exec.
         // This is the correct approach: restrictions are applied between fork() and exec(),
         // so they are inherited by the target command.
+        // The seccomp filter is compiled here, before fork; pre_exec only installs it.
         let ro_clone = ro_paths;
         let rw_clone = rw_paths;
         let deny_clone = deny_paths;
+        let connect_ports = egress.connect_ports.clone();
+        let syscall_filter = crate::seccomp::build_filter(egress.allow_network);
         unsafe {
             cmd.pre_exec(move || {
-                apply_landlock(&ro_clone, &rw_clone, &deny_clone)
+                // Join the cgroup first; Landlock and seccomp follow.
+                child_limits.apply()?;
+                apply_landlock(&ro_clone, &rw_clone, &deny_clone, connect_ports.as_deref())?;
+                match &syscall_filter {
+                    Some(filter) => crate::seccomp::apply(filter),
+                    None => Ok(()),
+                }
             });
         }
 
+        timer.mark("profile");
+        // Landlock and seccomp are installed in the child, before exec.
         let child = cmd.spawn().map_err(|e| {
             OpenSkillError::LinuxSandboxError(format!(
                 "Failed to execute with Landlock sandbox: {e}"
             ))
         })?;
+        timer.mark("spawn");
+
+        let artifacts =
+            run_native_child(child, &input_json, timeout_ms, enforcer, SandboxMode::Enforce, spill)?;
+        Ok(timer.finish("landlock", limit_guard.finish(artifacts)))
+    }
+
+    /// Resolve how `execute_native` would run the script, without running it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_native(
+        skill: &Skill,
+        script_path: &Path,
+        script_type: ScriptType,
+        enforcer: &PermissionEnforcer,
+        workspace_dir: Option<&Path>,
+        script_args: &[String],
+        native_config: Option<&NativeRunnerConfig>,
+        limits: ResourceLimits,
+    ) -> Result<NativePlan, OpenSkillError> {
+        if !script_path.exists() {
+            return Err(OpenSkillError::NativeExecutionError(format!(
+                "Script not found: {}",
+                script_path.display()
+            )));
+        }
+        let grants = PlannedGrants::resolve(skill, script_type, enforcer, workspace_dir);
+        let (program, args) = command_for_script(script_type, script_path, native_config)?;
+        let command: Vec<String> = std::iter::once(program.clone())
+            .chain(args.iter().cloned())
+            .chain(script_args.iter().cloned())
+            .collect();
+        let plan = |backend, sandbox| NativePlan {
+            command: command.clone(),
+            backend,
+            sandbox,
+        };
+
+        let sandbox_mode = native_config
+            .map(|c| c.sandbox_mode)
+            .unwrap_or(SandboxMode::Enforce);
+        if sandbox_mode == SandboxMode::Disabled {
+            return Ok(plan("direct", None));
+        }
+        let backend = native_config.map(|c| c.backend).unwrap_or_default();
+        if backend == ExecutionBackend::Container {
+            return Ok(NativePlan {
+                command: container_command(script_type, script_path, script_args),
+                backend: "container",
+                sandbox: Some(grants.container_profile()),
+            });
+        }
+
+        let home = std::env::var("HOME").unwrap_or_else(|_| "/root".to_string());
+        let deny_paths: Vec<PathBuf> = SENSITIVE_DENY_PATHS
+            .iter()
+            .map(|p| PathBuf::from(p.replace('~', &home)))
+            .collect();
+        let egress_restricted = grants.allow_network && !grants.egress_policy.is_unrestricted();
+        let layout = bwrap_spec(
+            &program,
+            &grants.skill_root,
+            &grants.read_paths,
+            &grants.write_paths,
+            deny_paths.clone(),
+            grants.allow_network && !egress_restricted,
+        );
+        let wrapped = |backend: &'static str,
+                       cmd: Option<Command>|
+         -> Result<NativePlan, OpenSkillError> {
+            let cmd = cmd.ok_or_else(|| {
+                OpenSkillError::LinuxSandboxError(format!("{backend} not found in PATH"))
+            })?;
+            Ok(plan(
+                backend,
+                Some(SandboxProfile {
+                    read_only: layout.ro_paths.clone(),
+                    read_write: layout.rw_paths.clone(),
+                    deny: layout.deny_paths.clone(),
+                    allow_network: layout.allow_network,
+                    allow_process: true,
+                    generated: Some(crate::dry_run::command_line(&cmd)),
+                    ..SandboxProfile::default()
+                }),
+            ))
+        };
+        if backend.resolve() == ExecutionBackend::Nsjail {
+            let spec = nsjail_spec(
+                &program,
+                &grants.skill_root,
+                &grants.read_paths,
+                &grants.write_paths,
+                deny_paths,
+                grants.allow_network && !egress_restricted,
+                enforcer.timeout_ms(),
+                &limits,
+            );
+            return wrapped("nsjail", nsjail::command(&spec, &program, &args, script_args));
+        }
+        if backend.resolve() == ExecutionBackend::Bubblewrap
+            && !(backend == ExecutionBackend::Auto && egress_restricted)
+        {
+            return wrapped("bubblewrap", bwrap::command(&layout, &program, &args, script_args));
+        }
+
+        // Landlock: system paths and the skill root read-only, temp dirs and
+        // write paths read-write.
+        let mut read_only: Vec<PathBuf> = SYSTEM_READ_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|p| p.exists())
+            .collect();
+        read_only.push(grants.skill_root.clone());
+        read_only.extend(grants.read_paths.iter().filter(|p| p.exists()).cloned());
+        let mut read_write: Vec<PathBuf> = TEMP_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|p| p.exists())
+            .collect();
+        read_write.extend(grants.write_paths.iter().cloned());
+        Ok(plan(
+            "landlock",
+            Some(SandboxProfile {
+                read_only,
+                read_write,
+                deny: deny_paths,
+                allow_network: grants.allow_network
+                    && crate::egress::landlock_connect_filter() >= grants.egress_policy.required_filter(),
+                allowed_hosts: grants.egress_policy.hosts.clone(),
+                allowed_ports: grants.egress_policy.ports.clone(),
+                // Landlock does not restrict spawning subprocesses.
+                allow_process: true,
+                generated: None,
+            }),
+        ))
+    }
+
+    /// Bubblewrap layout: skill root and read paths read-only, workspace and
+    /// write paths read-write, network only when WebSearch/Fetch is allowed.
+    fn bwrap_spec(
+        program: &str,
+        skill_root: &Path,
+        read_paths: &[PathBuf],
+        write_paths: &[PathBuf],
+        deny_paths: Vec<PathBuf>,
+        allow_network: bool,
+    ) -> bwrap::BwrapSpec {
+        let mut ro_paths = vec![skill_root.to_path_buf()];
+        if let Some(prefix) = bwrap::interpreter_prefix(Path::new(program)) {
+            ro_paths.push(prefix);
+        }
+        ro_paths.extend(read_paths.iter().cloned());
+        bwrap::BwrapSpec {
+            ro_paths,
+            rw_paths: write_paths.to_vec(),
+            deny_paths,
+            allow_network,
+            chdir: skill_root.to_path_buf(),
+            seccomp_fd: None,
+        }
+    }
 
-        run_native_child(child, &input_json, timeout_ms, enforcer, SandboxMode::Enforce)
+    /// nsjail layout: the Bubblewrap layout plus `RLIMIT_AS` from the memory
+    /// limit and `RLIMIT_CPU` from the timeout.
+    #[allow(clippy::too_many_arguments)]
+    fn nsjail_spec(
+        program: &str,
+        skill_root: &Path,
+        read_paths: &[PathBuf],
+        write_paths: &[PathBuf],
+        deny_paths: Vec<PathBuf>,
+        allow_network: bool,
+        timeout_ms: u64,
+        limits: &ResourceLimits,
+    ) -> nsjail::NsjailSpec {
+        let layout = bwrap_spec(
+            program,
+            skill_root,
+            read_paths,
+            write_paths,
+            deny_paths,
+            allow_network,
+        );
+        nsjail::NsjailSpec {
+            ro_paths: layout.ro_paths,
+            rw_paths: layout.rw_paths,
+            deny_paths: layout.deny_paths,
+            allow_network: layout.allow_network,
+            cwd: layout.chdir,
+            rlimit_as_mb: limits.memory_mb,
+            rlimit_cpu_secs: Some(timeout_ms.div_ceil(1000) + 1),
+        }
     }
 
     fn run_native_child(
@@ -1003,28 +1757,30 @@ mod linux {
         timeout_ms: u64,
         enforcer: &PermissionEnforcer,
         sandbox_mode: SandboxMode,
+        spill: Option<SpillTarget>,
     ) -> Result<ExecutionArtifacts, OpenSkillError> {
+        enforcer.spawned(child.id());
         if let Some(mut stdin) = child.stdin.take() {
             let input_clone = input_json.to_string();
-            thread::spawn(move || {
-                let _ = stdin.write_all(input_clone.as_bytes());
+            let session = enforcer.session_stdin();
+            io_pool::spawn(move || match session {
+                Some(messages) => forward_stdin(messages, stdin),
+                None => {
+                    let _ = stdin.write_all(input_clone.as_bytes());
+                }
             });
         }
 
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
+        let stdout = enforcer.events().forward(child.stdout.take(), OutputStream::Stdout);
+        let stderr = enforcer.events().forward(child.stderr.take(), OutputStream::Stderr);
 
-        let stdout_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stdout)))
-                .unwrap_or_else(|_| Vec::new())
-        });
-        let stderr_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stderr)))
-                .unwrap_or_else(|_| Vec::new())
-        });
+        let stderr_spill = spill.clone();
+        let stdout_task = io_pool::spawn(move || read_bounded(stdout, spill.as_ref(), "stdout"));
+        let stderr_task = io_pool::spawn(move || read_bounded(stderr, stderr_spill.as_ref(), "stderr"));
 
         let start = Instant::now();
         let mut timed_out = false;
+        let mut cancelled = false;
         let status = loop {
             if let Some(status) = child.try_wait().map_err(OpenSkillError::Io)? {
                 break Some(status);
@@ -1034,21 +1790,32 @@ mod linux {
                 let _ = child.kill();
                 break child.wait().ok();
             }
+            if enforcer.cancellation().is_cancelled() {
+                cancelled = true;
+                let _ = child.kill();
+                break child.wait().ok();
+            }
             thread::sleep(Duration::from_millis(10));
         };
 
-        let stdout_bytes = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stderr_bytes = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
-        let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+        let (stdout, binary_stdout) =
+            stdout_task.join_timeout(Duration::from_secs(5))
+                .unwrap_or_default()
+                .into_stdout();
+        let stderr = stderr_task.join_timeout(Duration::from_secs(5))
+            .unwrap_or_default()
+            .into_text();
 
         let (exit_status, output) = if timed_out {
             (
                 ExecutionStatus::Timeout,
                 serde_json::json!({ "status": "error", "error": "execution timeout" }),
             )
+        } else if cancelled {
+            (
+                ExecutionStatus::Failed("cancelled".to_string()),
+                serde_json::json!({ "status": "error", "error": "cancelled" }),
+            )
         } else if let Some(status) = status {
             if status.success() {
                 let output = if let Ok(json) = serde_json::from_str::<Value>(&stdout) {
@@ -1058,27 +1825,38 @@ mod linux {
                 };
                 (ExecutionStatus::Success, output)
             } else {
-                let message = if let Some(signal) = status.signal() {
-                    match signal {
-                        libc::SIGSYS => "Sandbox violation: blocked system call".to_string(),
-                        libc::SIGKILL => {
-                            if stderr.contains("landlock") || stderr.contains("Permission denied") {
-                                "Sandbox violation: blocked file access (Landlock)".to_string()
-                            } else {
-                                format!("Process killed (signal {})", signal)
-                            }
-                        }
-                        _ => format!("Process terminated by signal {}", signal),
+                let violation = match status.signal() {
+                    Some(libc::SIGSYS) => {
+                        Some("Sandbox violation: blocked system call (seccomp)".to_string())
                     }
-                } else if !stderr.trim().is_empty() {
-                    stderr.trim().to_string()
-                } else {
-                    format!("Process exited with status {}", status)
+                    Some(libc::SIGKILL)
+                        if stderr.contains("landlock") || stderr.contains("Permission denied") =>
+                    {
+                        Some("Sandbox violation: blocked file access (Landlock)".to_string())
+                    }
+                    _ => None,
                 };
-                (
-                    ExecutionStatus::Failed(message.clone()),
-                    serde_json::json!({ "status": "error", "error": message }),
-                )
+                if let Some(message) = violation {
+                    (
+                        ExecutionStatus::SandboxViolation(message.clone()),
+                        serde_json::json!({ "status": "error", "error": message }),
+                    )
+                } else {
+                    let message = if let Some(signal) = status.signal() {
+                        match signal {
+                            libc::SIGKILL => format!("Process killed (signal {})", signal),
+                            _ => format!("Process terminated by signal {}", signal),
+                        }
+                    } else if !stderr.trim().is_empty() {
+                        stderr.trim().to_string()
+                    } else {
+                        format!("Process exited with status {}", status)
+                    };
+                    (
+                        ExecutionStatus::Failed(message.clone()),
+                        serde_json::json!({ "status": "error", "error": message }),
+                    )
+                }
             }
         } else {
             (
@@ -1087,6 +1865,12 @@ mod linux {
             )
         };
 
+        let sandbox_violations = if sandbox_mode == SandboxMode::Enforce {
+            parse_violations(&stderr, status.and_then(|s| s.signal()))
+        } else {
+            Vec::new()
+        };
+
         Ok(ExecutionArtifacts {
             output,
             stdout,
@@ -1094,12 +1878,20 @@ mod linux {
             permissions_used: enforcer.permissions_used(),
             exit_status,
             sandbox_mode,
+            binary_stdout,
+            sandbox_violations,
+            fuel_consumed: None,
+            network_requests: Vec::new(),
+            host_calls: Vec::new(),
+            capability_downgrades: Vec::new(),
+            setup_timings: SetupTimings::default(),
         })
     }
 
     /// Apply Landlock filesystem restrictions to the current process (called in pre_exec).
     ///
-    /// Uses the `landlock` crate to create a ruleset that restricts filesystem access.
+    /// Uses the `landlock` crate to create a ruleset that restricts filesystem access,
+    /// and outbound TCP to `connect_ports` when given (Landlock ABI v4).
     /// On kernels that don't support Landlock (< 5.13), falls back to NO_NEW_PRIVS only.
     /// Never returns Err to avoid preventing process execution — sandbox failures are
     /// logged to stderr and execution continues with reduced security.
@@ -1107,15 +1899,22 @@ mod linux {
         ro_paths: &[PathBuf],
         rw_paths: &[PathBuf],
         deny_paths: &[PathBuf],
+        connect_ports: Option<&[u16]>,
     ) -> std::io::Result<()> {
         // Use ABI V2 (Linux 5.19+): includes Refer for link/rename across dirs.
         // npm cacache uses hard links; V1 forbids this (EXDEV).
         let abi = ABI::V2;
 
         let result = (|| -> Result<(), landlock::RulesetError> {
-            let mut ruleset = Ruleset::default()
-                .handle_access(AccessFs::from_all(abi))?
-                .create()?;
+            let mut ruleset = Ruleset::default().handle_access(AccessFs::from_all(abi))?;
+            if connect_ports.is_some() {
+                ruleset = ruleset.handle_access(AccessNet::ConnectTcp)?;
+            }
+            let mut ruleset = ruleset.create()?;
+
+            for port in connect_ports.unwrap_or_default() {
+                ruleset = ruleset.add_rule(NetPort::new(*port, AccessNet::ConnectTcp))?;
+            }
 
             // Add read-only rules (excluding denied paths)
             for path in ro_paths {
@@ -1211,6 +2010,7 @@ mod linux {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_environment(
         cmd: &mut Command,
         skill: &Skill,
@@ -1231,6 +2031,10 @@ mod linux {
         cmd.env("COREPACK_ENABLE_AUTO_PIN", "0");
         cmd.env("CI", "true");
 
+        // Captured output is not a terminal: ask tools for plain output
+        // (no colors, cursor movement or progress bars).
+        cmd.env("TERM", "dumb");
+
         // Locale settings
         if let Ok(lang) = std::env::var("LANG") {
             cmd.env("LANG", lang);
@@ -1261,6 +2065,7 @@ mod linux {
                 cmd.env(key, val);
             }
         }
+        cmd.envs(enforcer.extra_env().iter().cloned());
 
         // Python-specific settings
         if script_type == ScriptType::Python {
@@ -1275,7 +2080,7 @@ mod linux {
 }
 
 #[cfg(target_os = "linux")]
-pub use linux::execute_native;
+pub use linux::{execute_native, plan_native};
 
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
 mod direct {
@@ -1284,6 +2089,7 @@ mod direct {
     use std::process::{Command, Stdio};
     use std::time::Instant;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn execute_native(
         skill: &Skill,
         script_path: &Path,
@@ -1291,10 +2097,11 @@ mod direct {
         input: Value,
         timeout_ms: u64,
         enforcer: &PermissionEnforcer,
-        _allowed_tools: &[String],
         workspace_dir: Option<&Path>,
         script_args: &[String],
         native_config: Option<&NativeRunnerConfig>,
+        secret_redactor: Option<&SecretRedactor>,
+        _limits: ResourceLimits,
     ) -> Result<ExecutionArtifacts, OpenSkillError> {
         if !script_path.exists() {
             return Err(OpenSkillError::NativeExecutionError(format!(
@@ -1313,12 +2120,16 @@ mod direct {
             ));
         }
 
+        let mut timer = SetupTimer::start();
         let input_json = serde_json::to_string(&input)?;
+        let spill = SpillTarget::for_execution(native_config, workspace_dir, &skill.id, secret_redactor);
         let skill_root = skill
             .root
             .canonicalize()
             .unwrap_or_else(|_| skill.root.clone());
+        timer.mark("paths");
         let (program, args) = command_for_script(script_type, script_path, native_config)?;
+        timer.mark("interpreter");
 
         let mut cmd = Command::new(&program);
         cmd.args(&args);
@@ -1345,27 +2156,29 @@ mod direct {
                 "Failed to execute without sandbox: {e}"
             ))
         })?;
+        timer.mark("spawn");
+        enforcer.spawned(child.id());
 
         if let Some(mut stdin) = child.stdin.take() {
             let input_clone = input_json.clone();
-            thread::spawn(move || {
-                let _ = stdin.write_all(input_clone.as_bytes());
+            let session = enforcer.session_stdin();
+            io_pool::spawn(move || match session {
+                Some(messages) => forward_stdin(messages, stdin),
+                None => {
+                    let _ = stdin.write_all(input_clone.as_bytes());
+                }
             });
         }
 
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
-        let stdout_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stdout)))
-                .unwrap_or_else(|_| Vec::new())
-        });
-        let stderr_handle = thread::spawn(move || {
-            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| read_stream(stderr)))
-                .unwrap_or_else(|_| Vec::new())
-        });
+        let stdout = enforcer.events().forward(child.stdout.take(), OutputStream::Stdout);
+        let stderr = enforcer.events().forward(child.stderr.take(), OutputStream::Stderr);
+        let stderr_spill = spill.clone();
+        let stdout_task = io_pool::spawn(move || read_bounded(stdout, spill.as_ref(), "stdout"));
+        let stderr_task = io_pool::spawn(move || read_bounded(stderr, stderr_spill.as_ref(), "stderr"));
 
         let start = Instant::now();
         let mut timed_out = false;
+        let mut cancelled = false;
         let status = loop {
             if let Some(status) = child.try_wait().map_err(OpenSkillError::Io)? {
                 break Some(status);
@@ -1375,21 +2188,32 @@ mod direct {
                 let _ = child.kill();
                 break child.wait().ok();
             }
+            if enforcer.cancellation().is_cancelled() {
+                cancelled = true;
+                let _ = child.kill();
+                break child.wait().ok();
+            }
             thread::sleep(Duration::from_millis(10));
         };
 
-        let stdout_bytes = join_thread_with_timeout(stdout_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stderr_bytes = join_thread_with_timeout(stderr_handle, Duration::from_secs(5))
-            .unwrap_or_else(|_| Vec::new());
-        let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
-        let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+        let (stdout, binary_stdout) =
+            stdout_task.join_timeout(Duration::from_secs(5))
+                .unwrap_or_default()
+                .into_stdout();
+        let stderr = stderr_task.join_timeout(Duration::from_secs(5))
+            .unwrap_or_default()
+            .into_text();
 
         let (exit_status, output) = if timed_out {
             (
                 ExecutionStatus::Timeout,
                 serde_json::json!({ "status": "error", "error": "execution timeout" }),
             )
+        } else if cancelled {
+            (
+                ExecutionStatus::Failed("cancelled".to_string()),
+                serde_json::json!({ "status": "error", "error": "cancelled" }),
+            )
         } else if let Some(status) = status {
             if status.success() {
                 let output = if let Ok(json) = serde_json::from_str::<Value>(&stdout) {
@@ -1416,13 +2240,61 @@ mod direct {
             )
         };
 
-        Ok(ExecutionArtifacts {
-            output,
-            stdout,
-            stderr,
-            permissions_used: enforcer.permissions_used(),
-            exit_status,
-            sandbox_mode,
+        Ok(timer.finish(
+            "direct",
+            ExecutionArtifacts {
+                output,
+                stdout,
+                stderr,
+                permissions_used: enforcer.permissions_used(),
+                exit_status,
+                sandbox_mode,
+                binary_stdout,
+                sandbox_violations: Vec::new(),
+                fuel_consumed: None,
+                network_requests: Vec::new(),
+                host_calls: Vec::new(),
+                capability_downgrades: Vec::new(),
+                setup_timings: SetupTimings::default(),
+            },
+        ))
+    }
+
+    /// Resolve how `execute_native` would run the script, without running it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn plan_native(
+        _skill: &Skill,
+        script_path: &Path,
+        script_type: ScriptType,
+        _enforcer: &PermissionEnforcer,
+        _workspace_dir: Option<&Path>,
+        script_args: &[String],
+        native_config: Option<&NativeRunnerConfig>,
+        _limits: ResourceLimits,
+    ) -> Result<NativePlan, OpenSkillError> {
+        if !script_path.exists() {
+            return Err(OpenSkillError::NativeExecutionError(format!(
+                "Script not found: {}",
+                script_path.display()
+            )));
+        }
+        let sandbox_mode = native_config
+            .map(|c| c.sandbox_mode)
+            .unwrap_or(SandboxMode::Enforce);
+        if sandbox_mode == SandboxMode::Enforce {
+            return Err(OpenSkillError::UnsupportedPlatform(
+                "Native sandboxed execution requires macOS (seatbelt) or Linux (Landlock)"
+                    .to_string(),
+            ));
+        }
+        let (program, args) = command_for_script(script_type, script_path, native_config)?;
+        Ok(NativePlan {
+            command: std::iter::once(program)
+                .chain(args)
+                .chain(script_args.iter().cloned())
+                .collect(),
+            backend: "direct",
+            sandbox: None,
         })
     }
 
@@ -1487,6 +2359,7 @@ mod direct {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn apply_environment(
         cmd: &mut Command,
         skill: &Skill,
@@ -1502,6 +2375,10 @@ mod direct {
         }
         cmd.env("COREPACK_ENABLE_AUTO_PIN", "0");
         cmd.env("CI", "true");
+
+        // Captured output is not a terminal: ask tools for plain output
+        // (no colors, cursor movement or progress bars).
+        cmd.env("TERM", "dumb");
         if let Ok(lang) = std::env::var("LANG") {
             cmd.env("LANG", lang);
         }
@@ -1518,6 +2395,7 @@ mod direct {
                 cmd.env(key, val);
             }
         }
+        cmd.envs(enforcer.extra_env().iter().cloned());
         if script_type == ScriptType::Python {
             cmd.env("PYTHONUNBUFFERED", "1");
             cmd.env("PYTHONDONTWRITEBYTECODE", "1");
@@ -1530,4 +2408,4 @@ mod direct {
 }
 
 #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-pub use direct::execute_native;
\ No newline at end of file
+pub use direct::{execute_native, plan_native};
\ No newline at end of file
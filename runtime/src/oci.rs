@@ -0,0 +1,742 @@
+//! Skills as OCI artifacts.
+//!
+//! Like Helm charts, a skill can be stored in any OCI-compliant container
+//! registry (GHCR, ECR, Harbor, Artifactory, ...). [`OciClient::push`] packs
+//! the skill with [`package_skill`] and uploads it as a single layer of
+//! type [`SKILL_LAYER_MEDIA_TYPE`], under an image manifest whose
+//! `artifactType` is [`SKILL_ARTIFACT_TYPE`] and whose title annotation is
+//! the skill ID. [`OciClient::pull`] fetches the manifest, downloads and
+//! verifies the layer against its digest, and unpacks it into the registry
+//! cache (`~/.cache/openskills/registry/<id>/sha256-<hex>/`), so pulling an
+//! unchanged tag again does not download anything but the manifest.
+//!
+//! References look like `ghcr.io/acme/skills/pdf:1.2.0` or
+//! `registry.example.com/pdf@sha256:<hex>` (an `oci://` prefix is
+//! accepted); the registry host is required. Registries on `localhost` are
+//! spoken to over plain HTTP, all others over HTTPS.
+//!
+//! Authentication follows the registry's `WWW-Authenticate` challenge
+//! (bearer token or basic). Credentials are taken from
+//! [`OciClient::with_credentials`], else `OPENSKILLS_OCI_USERNAME` /
+//! `OPENSKILLS_OCI_PASSWORD`, else the `auths` of the Docker config
+//! (`$DOCKER_CONFIG/config.json` or `~/.docker/config.json`) as written by
+//! `docker login`; credential helpers are not consulted.
+
+use crate::cache::{CacheKind, CacheManager};
+use crate::errors::OpenSkillError;
+use crate::remote_registry::{cached_skill, package_skill, read_limited, unpack_skill, ResolvedSkill};
+use crate::validator::validate_name;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+
+/// `artifactType` of skill manifests.
+pub const SKILL_ARTIFACT_TYPE: &str = "application/vnd.openskills.skill.v1";
+/// Media type of the skill tarball layer.
+pub const SKILL_LAYER_MEDIA_TYPE: &str = "application/vnd.openskills.skill.layer.v1.tar+gzip";
+
+const MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+const EMPTY_CONFIG_MEDIA_TYPE: &str = "application/vnd.oci.empty.v1+json";
+const EMPTY_CONFIG: &[u8] = b"{}";
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+const VERSION_ANNOTATION: &str = "org.opencontainers.image.version";
+
+/// Prefix accepted in front of OCI references.
+pub const OCI_PREFIX: &str = "oci://";
+
+/// Where an OCI artifact lives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciReference {
+    /// Registry host, with port if any (`ghcr.io`, `localhost:5000`).
+    pub registry: String,
+    /// Repository inside the registry (`acme/skills/pdf`).
+    pub repository: String,
+    /// Tag; `latest` if neither a tag nor a digest is given.
+    pub tag: Option<String>,
+    /// Manifest digest (`sha256:<hex>`); takes precedence over the tag.
+    pub digest: Option<String>,
+}
+
+impl OciReference {
+    /// Parse `[oci://]host/repository[:tag][@sha256:<hex>]`.
+    pub fn parse(reference: &str) -> Result<Self, OpenSkillError> {
+        let invalid = |why: &str| {
+            OpenSkillError::RegistryError(format!("invalid OCI reference '{}': {}", reference, why))
+        };
+        let rest = reference.strip_prefix(OCI_PREFIX).unwrap_or(reference);
+        let (rest, digest) = match rest.split_once('@') {
+            Some((rest, digest)) => {
+                if !is_sha256_digest(digest) {
+                    return Err(invalid("digest must be sha256:<64 hex digits>"));
+                }
+                (rest, Some(digest.to_string()))
+            }
+            None => (rest, None),
+        };
+        let (registry, path) = rest
+            .split_once('/')
+            .ok_or_else(|| invalid("expected <registry>/<repository>"))?;
+        if !(registry.contains('.') || registry.contains(':') || registry == "localhost") {
+            return Err(invalid("the registry host is required (e.g. ghcr.io/...)"));
+        }
+        // A ':' after the last '/' separates the tag.
+        let (repository, tag) = match path.rsplit_once(':') {
+            Some((repository, tag)) if !tag.contains('/') => (repository, Some(tag.to_string())),
+            _ => (path, None),
+        };
+        let valid_repository = !repository.is_empty()
+            && repository.split('/').all(|part| {
+                !part.is_empty()
+                    && part
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "._-".contains(c))
+            });
+        if !valid_repository {
+            return Err(invalid("repository must be lowercase path components"));
+        }
+        if tag.as_deref().is_some_and(|t| {
+            t.is_empty()
+                || t.len() > 128
+                || !t.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+        }) {
+            return Err(invalid("invalid tag"));
+        }
+        Ok(Self {
+            registry: registry.to_string(),
+            repository: repository.to_string(),
+            tag,
+            digest,
+        })
+    }
+
+    /// Digest if given, else the tag (`latest` by default).
+    pub fn reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+
+    /// Base URL of the repository in the distribution API.
+    fn repository_url(&self) -> String {
+        let host = self.registry.split(':').next().unwrap_or_default();
+        let scheme = if matches!(host, "localhost" | "127.0.0.1") {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{}://{}/v2/{}", scheme, self.registry, self.repository)
+    }
+
+    fn name(&self) -> &str {
+        self.repository.rsplit('/').next().unwrap_or(&self.repository)
+    }
+}
+
+impl fmt::Display for OciReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.registry, self.repository)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Username and password (or token) for a registry.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OciCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for OciCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OciCredentials")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A skill pushed to a registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciPushed {
+    /// Skill ID recorded in the manifest.
+    pub id: String,
+    /// Digest of the manifest, for pinning (`<reference>@<digest>`).
+    pub digest: String,
+    /// Digest of the skill layer.
+    pub layer_digest: String,
+    /// Size of the skill layer in bytes.
+    pub layer_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artifact_type: Option<String>,
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+/// Client for pushing and pulling skills to and from OCI registries.
+#[derive(Debug)]
+pub struct OciClient {
+    credentials: Option<OciCredentials>,
+    cache_dir: PathBuf,
+    agent: ureq::Agent,
+    /// `Authorization` header from the last successful challenge.
+    authorization: Mutex<Option<String>>,
+}
+
+impl Default for OciClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OciClient {
+    /// Client with credentials from the environment or Docker config,
+    /// caching pulled skills in the default registry cache.
+    pub fn new() -> Self {
+        Self {
+            credentials: None,
+            cache_dir: CacheManager::default_dir(CacheKind::Registry)
+                .unwrap_or_else(|| std::env::temp_dir().join("openskills").join("registry")),
+            agent: ureq::AgentBuilder::new().build(),
+            authorization: Mutex::new(None),
+        }
+    }
+
+    /// Authenticate with these credentials.
+    pub fn with_credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some(OciCredentials {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Cache pulled skills under `dir` instead.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// Package the skill at `skill_dir` and push it to `reference`.
+    pub fn push(&self, skill_dir: &Path, reference: &OciReference) -> Result<OciPushed, OpenSkillError> {
+        let tarball = package_skill(skill_dir)?;
+        let id = skill_dir
+            .canonicalize()?
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let config_digest = self.push_blob(reference, EMPTY_CONFIG)?;
+        let layer_digest = self.push_blob(reference, &tarball)?;
+        let mut annotations = BTreeMap::from([(TITLE_ANNOTATION.to_string(), id.clone())]);
+        if let Some(tag) = &reference.tag {
+            annotations.insert(VERSION_ANNOTATION.to_string(), tag.clone());
+        }
+        let manifest = Manifest {
+            schema_version: 2,
+            media_type: Some(MANIFEST_MEDIA_TYPE.to_string()),
+            artifact_type: Some(SKILL_ARTIFACT_TYPE.to_string()),
+            config: Descriptor {
+                media_type: EMPTY_CONFIG_MEDIA_TYPE.to_string(),
+                digest: config_digest,
+                size: EMPTY_CONFIG.len() as u64,
+                annotations: BTreeMap::new(),
+            },
+            layers: vec![Descriptor {
+                media_type: SKILL_LAYER_MEDIA_TYPE.to_string(),
+                digest: layer_digest.clone(),
+                size: tarball.len() as u64,
+                annotations: BTreeMap::from([(
+                    TITLE_ANNOTATION.to_string(),
+                    format!("{}.tar.gz", id),
+                )]),
+            }],
+            annotations,
+        };
+        let body = serde_json::to_vec(&manifest)?;
+        let url = format!("{}/manifests/{}", reference.repository_url(), reference.reference());
+        self.call("PUT", &url, &[("Content-Type", MANIFEST_MEDIA_TYPE)], Some(&body))?
+            .ok_or_else(|| not_found(reference))?;
+        Ok(OciPushed {
+            id,
+            digest: sha256_digest(&body),
+            layer_digest,
+            layer_size: tarball.len() as u64,
+        })
+    }
+
+    /// Pull the skill at `reference` into the cache, downloading its layer
+    /// unless that exact content is cached already.
+    pub fn pull(&self, reference: &OciReference) -> Result<ResolvedSkill, OpenSkillError> {
+        let url = format!("{}/manifests/{}", reference.repository_url(), reference.reference());
+        let response = self
+            .call("GET", &url, &[("Accept", MANIFEST_MEDIA_TYPE)], None)?
+            .ok_or_else(|| not_found(reference))?;
+        let body = read_limited(response.into_reader(), &url)?;
+        if let Some(digest) = &reference.digest {
+            verify_digest(&body, digest, &reference.to_string())?;
+        }
+        let manifest: Manifest = serde_json::from_slice(&body).map_err(|e| {
+            OpenSkillError::RegistryError(format!("invalid manifest for {}: {}", reference, e))
+        })?;
+        let layer = manifest
+            .layers
+            .iter()
+            .find(|l| l.media_type == SKILL_LAYER_MEDIA_TYPE)
+            .ok_or_else(|| {
+                OpenSkillError::RegistryError(format!("{} is not an OpenSkills artifact", reference))
+            })?;
+        if !is_sha256_digest(&layer.digest) {
+            return Err(OpenSkillError::RegistryError(format!(
+                "unsupported layer digest '{}' in {}",
+                layer.digest, reference
+            )));
+        }
+        let id = manifest
+            .annotations
+            .get(TITLE_ANNOTATION)
+            .map(String::as_str)
+            .unwrap_or(reference.name())
+            .to_string();
+        validate_name(&id).map_err(|e| {
+            OpenSkillError::RegistryError(format!("{} has an invalid skill ID '{}': {}", reference, id, e))
+        })?;
+        let version = manifest
+            .annotations
+            .get(VERSION_ANNOTATION)
+            .cloned()
+            .unwrap_or_else(|| reference.reference().to_string());
+        let cache_key = layer.digest.replace(':', "-");
+
+        if let Some(path) = cached_skill(&self.cache_dir, &id, &cache_key) {
+            return Ok(ResolvedSkill {
+                name: id,
+                version,
+                path,
+                cached: true,
+                source: format!("oci:{}", reference),
+            });
+        }
+        let url = format!("{}/blobs/{}", reference.repository_url(), layer.digest);
+        let response = self
+            .call("GET", &url, &[], None)?
+            .ok_or_else(|| not_found(reference))?;
+        let tarball = read_limited(response.into_reader(), &url)?;
+        verify_digest(&tarball, &layer.digest, &reference.to_string())?;
+        let path = unpack_skill(&self.cache_dir, &id, &cache_key, &tarball)?;
+        Ok(ResolvedSkill {
+            name: id,
+            version,
+            path,
+            cached: false,
+            source: format!("oci:{}", reference),
+        })
+    }
+
+    /// Upload a blob unless the repository has it already.
+    fn push_blob(&self, reference: &OciReference, bytes: &[u8]) -> Result<String, OpenSkillError> {
+        let digest = sha256_digest(bytes);
+        let base = reference.repository_url();
+        if self.call("HEAD", &format!("{}/blobs/{}", base, digest), &[], None)?.is_some() {
+            return Ok(digest);
+        }
+        let uploads = format!("{}/blobs/uploads/", base);
+        let response = self
+            .call("POST", &uploads, &[], Some(&[][..]))?
+            .ok_or_else(|| not_found(reference))?;
+        let location = response.header("Location").ok_or_else(|| {
+            OpenSkillError::RegistryError(format!("{} returned no upload location", uploads))
+        })?;
+        let mut upload = Url::parse(&uploads)
+            .and_then(|u| u.join(location))
+            .map_err(|e| OpenSkillError::RegistryError(format!("invalid upload location: {}", e)))?;
+        upload.query_pairs_mut().append_pair("digest", &digest);
+        self.call(
+            "PUT",
+            upload.as_str(),
+            &[("Content-Type", "application/octet-stream")],
+            Some(bytes),
+        )?
+        .ok_or_else(|| not_found(reference))?;
+        Ok(digest)
+    }
+
+    /// Send a request, answering one authentication challenge. `None` on
+    /// 404.
+    fn call(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&[u8]>,
+    ) -> Result<Option<ureq::Response>, OpenSkillError> {
+        let mut challenged = false;
+        loop {
+            let mut request = self.agent.request(method, url);
+            for (name, value) in headers {
+                request = request.set(name, value);
+            }
+            if let Some(authorization) = self.lock_authorization().clone() {
+                request = request.set("Authorization", &authorization);
+            }
+            let result = match body {
+                Some(body) => request.send_bytes(body),
+                None => request.call(),
+            };
+            match result {
+                Ok(response) => return Ok(Some(response)),
+                Err(ureq::Error::Status(404, _)) => return Ok(None),
+                Err(ureq::Error::Status(401, response)) if !challenged => {
+                    challenged = true;
+                    let challenge = response.header("WWW-Authenticate").unwrap_or_default().to_string();
+                    let authorization = self.authenticate(url, &challenge)?;
+                    *self.lock_authorization() = Some(authorization);
+                }
+                Err(ureq::Error::Status(code, response)) => {
+                    let detail = response.into_string().unwrap_or_default();
+                    return Err(OpenSkillError::RegistryError(format!(
+                        "{} {} failed with HTTP {}: {}",
+                        method,
+                        url,
+                        code,
+                        detail.trim()
+                    )));
+                }
+                Err(e) => {
+                    return Err(OpenSkillError::RegistryError(format!("{} {}: {}", method, url, e)));
+                }
+            }
+        }
+    }
+
+    /// Answer a `WWW-Authenticate` challenge with an `Authorization` value.
+    fn authenticate(&self, url: &str, challenge: &str) -> Result<String, OpenSkillError> {
+        let host = Url::parse(url)
+            .ok()
+            .map(|u| match u.port() {
+                Some(port) => format!("{}:{}", u.host_str().unwrap_or_default(), port),
+                None => u.host_str().unwrap_or_default().to_string(),
+            })
+            .unwrap_or_default();
+        let credentials = self.credentials.clone().or_else(|| lookup_credentials(&host));
+        let (scheme, params) = challenge.split_once(' ').unwrap_or((challenge, ""));
+
+        if scheme.eq_ignore_ascii_case("basic") {
+            let credentials = credentials.ok_or_else(|| {
+                OpenSkillError::RegistryError(format!("{} requires credentials", host))
+            })?;
+            return Ok(format!("Basic {}", basic_token(&credentials)));
+        }
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return Err(OpenSkillError::RegistryError(format!(
+                "unsupported authentication challenge from {}: '{}'",
+                host, challenge
+            )));
+        }
+
+        let params = challenge_params(params);
+        let realm = params.get("realm").ok_or_else(|| {
+            OpenSkillError::RegistryError(format!("bearer challenge from {} has no realm", host))
+        })?;
+        let mut token_url = Url::parse(realm)
+            .map_err(|e| OpenSkillError::RegistryError(format!("invalid token realm '{}': {}", realm, e)))?;
+        for key in ["service", "scope"] {
+            if let Some(value) = params.get(key) {
+                token_url.query_pairs_mut().append_pair(key, value);
+            }
+        }
+        let mut request = self.agent.get(token_url.as_str());
+        if let Some(credentials) = &credentials {
+            request = request.set("Authorization", &format!("Basic {}", basic_token(credentials)));
+        }
+        let response = request.call().map_err(|e| {
+            OpenSkillError::RegistryError(format!("token request to {} failed: {}", realm, e))
+        })?;
+        let body: serde_json::Value = serde_json::from_reader(response.into_reader())?;
+        let token = body
+            .get("token")
+            .or_else(|| body.get("access_token"))
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| OpenSkillError::RegistryError(format!("no token in response from {}", realm)))?;
+        Ok(format!("Bearer {}", token))
+    }
+
+    fn lock_authorization(&self) -> std::sync::MutexGuard<'_, Option<String>> {
+        self.authorization.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Parse `key="value",key2="value2"` challenge parameters.
+fn challenge_params(params: &str) -> BTreeMap<String, String> {
+    let mut result = BTreeMap::new();
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_ascii_lowercase();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, remaining)) => (value, remaining),
+                None => (quoted, ""),
+            },
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        result.insert(key, value.to_string());
+        rest = remaining.trim_start_matches(',').trim();
+    }
+    result
+}
+
+/// Credentials for `host` from the environment or the Docker config.
+fn lookup_credentials(host: &str) -> Option<OciCredentials> {
+    if let (Ok(username), Ok(password)) = (
+        std::env::var("OPENSKILLS_OCI_USERNAME"),
+        std::env::var("OPENSKILLS_OCI_PASSWORD"),
+    ) {
+        return Some(OciCredentials { username, password });
+    }
+    let config_dir = std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".docker")))?;
+    let config: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(config_dir.join("config.json")).ok()?).ok()?;
+    let auths = config.get("auths")?.as_object()?;
+    let auth = auths.iter().find_map(|(key, value)| {
+        let key_host = key
+            .split_once("://")
+            .map_or(key.as_str(), |(_, rest)| rest)
+            .split('/')
+            .next()?;
+        (key_host == host).then(|| value.get("auth")?.as_str())?
+    })?;
+    let decoded = base64::engine::general_purpose::STANDARD.decode(auth).ok()?;
+    let (username, password) = std::str::from_utf8(&decoded).ok()?.split_once(':')?;
+    Some(OciCredentials {
+        username: username.to_string(),
+        password: password.to_string(),
+    })
+}
+
+fn basic_token(credentials: &OciCredentials) -> String {
+    base64::engine::general_purpose::STANDARD
+        .encode(format!("{}:{}", credentials.username, credentials.password))
+}
+
+fn sha256_digest(bytes: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(bytes)))
+}
+
+fn is_sha256_digest(digest: &str) -> bool {
+    digest
+        .strip_prefix("sha256:")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn verify_digest(bytes: &[u8], expected: &str, what: &str) -> Result<(), OpenSkillError> {
+    let actual = sha256_digest(bytes);
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(OpenSkillError::RegistryError(format!(
+            "digest mismatch for {}: expected {}, got {}",
+            what, expected, actual
+        )));
+    }
+    Ok(())
+}
+
+fn not_found(reference: &OciReference) -> OpenSkillError {
+    OpenSkillError::RegistryError(format!("{} not found", reference))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    /// Blobs and manifests stored by the test registry, by digest or path.
+    type Store = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Minimal distribution API that demands a bearer token from `/token`
+    /// for basic credentials `user:secret`.
+    fn serve_registry() -> (String, Store) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = format!("localhost:{}", listener.local_addr().unwrap().port());
+        let store = Store::default();
+        let shared = store.clone();
+        let realm = format!("http://{}/token", addr);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut parts = line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let target = parts.next().unwrap_or_default().to_string();
+                let mut headers = HashMap::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((k, v)) = header.split_once(':') {
+                        headers.insert(k.trim().to_ascii_lowercase(), v.trim().to_string());
+                    }
+                }
+                let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+                let authorized = headers.get("authorization").map(String::as_str) == Some("Bearer t0k3n");
+                let (status, extra, payload): (&str, String, Vec<u8>) = if path == "/token" {
+                    if headers.get("authorization").map(String::as_str) == Some("Basic dXNlcjpzZWNyZXQ=") {
+                        ("200 OK", String::new(), br#"{"token":"t0k3n"}"#.to_vec())
+                    } else {
+                        ("401 Unauthorized", String::new(), Vec::new())
+                    }
+                } else if !authorized {
+                    (
+                        "401 Unauthorized",
+                        format!("WWW-Authenticate: Bearer realm=\"{}\",service=\"test\"\r\n", realm),
+                        Vec::new(),
+                    )
+                } else {
+                    let mut store = shared.lock().unwrap();
+                    match method.as_str() {
+                        "POST" => ("202 Accepted", "Location: /upload/1\r\n".to_string(), Vec::new()),
+                        "PUT" if path.starts_with("/upload/") => {
+                            let digest = query.trim_start_matches("digest=").replace("%3A", ":");
+                            store.insert(digest, body);
+                            ("201 Created", String::new(), Vec::new())
+                        }
+                        "PUT" => {
+                            let (repository, _) = path.split_once("/manifests/").unwrap();
+                            store.insert(format!("{}/manifests/{}", repository, sha256_digest(&body)), body.clone());
+                            store.insert(path.to_string(), body);
+                            ("201 Created", String::new(), Vec::new())
+                        }
+                        _ => {
+                            let key = match path.split_once("/blobs/") {
+                                Some((_, digest)) => digest.to_string(),
+                                None => path.to_string(),
+                            };
+                            match store.get(&key) {
+                                Some(_) if method == "HEAD" => ("200 OK", String::new(), Vec::new()),
+                                Some(data) => ("200 OK", String::new(), data.clone()),
+                                None => ("404 Not Found", String::new(), Vec::new()),
+                            }
+                        }
+                    }
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    extra,
+                    payload.len()
+                );
+                let _ = stream.write_all(head.as_bytes());
+                if method != "HEAD" {
+                    let _ = stream.write_all(&payload);
+                }
+            }
+        });
+        (addr, store)
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let r = OciReference::parse("oci://ghcr.io/acme/skills/pdf:1.2.0").unwrap();
+        assert_eq!(r.registry, "ghcr.io");
+        assert_eq!(r.repository, "acme/skills/pdf");
+        assert_eq!(r.reference(), "1.2.0");
+        assert_eq!(r.to_string(), "ghcr.io/acme/skills/pdf:1.2.0");
+        assert_eq!(r.repository_url(), "https://ghcr.io/v2/acme/skills/pdf");
+
+        let r = OciReference::parse("localhost:5000/pdf").unwrap();
+        assert_eq!(r.reference(), "latest");
+        assert_eq!(r.repository_url(), "http://localhost:5000/v2/pdf");
+
+        let digest = format!("sha256:{}", "a".repeat(64));
+        let r = OciReference::parse(&format!("example.com/pdf@{}", digest)).unwrap();
+        assert_eq!(r.reference(), digest);
+
+        assert!(OciReference::parse("acme/pdf:1.0").is_err());
+        assert!(OciReference::parse("ghcr.io/Acme/pdf").is_err());
+        assert!(OciReference::parse("ghcr.io/pdf@sha256:xyz").is_err());
+    }
+
+    #[test]
+    fn test_challenge_params() {
+        let params = challenge_params(r#"realm="https://auth.example/token",service="registry",scope="repository:a/b:pull,push""#);
+        assert_eq!(params["realm"], "https://auth.example/token");
+        assert_eq!(params["service"], "registry");
+        assert_eq!(params["scope"], "repository:a/b:pull,push");
+    }
+
+    #[test]
+    fn test_push_and_pull_round_trip() {
+        let (addr, store) = serve_registry();
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("demo");
+        std::fs::create_dir_all(&skill).unwrap();
+        std::fs::write(skill.join("SKILL.md"), "---\nname: demo\ndescription: Demo skill\n---\nSay hi.\n").unwrap();
+
+        let reference = OciReference::parse(&format!("{}/acme/demo-skill:1.0.0", addr)).unwrap();
+        let anonymous = OciClient::new().with_cache_dir(dir.path().join("cache"));
+        assert!(anonymous.push(&skill, &reference).is_err());
+
+        let client = OciClient::new()
+            .with_credentials("user", "secret")
+            .with_cache_dir(dir.path().join("cache"));
+        let pushed = client.push(&skill, &reference).unwrap();
+        assert_eq!(pushed.id, "demo");
+        assert!(store.lock().unwrap().contains_key(&pushed.layer_digest));
+
+        let pinned = OciReference::parse(&format!("{}/acme/demo-skill@{}", addr, pushed.digest)).unwrap();
+        let pulled = client.pull(&pinned).unwrap();
+        assert_eq!(pulled.name, "demo");
+        assert_eq!(pulled.version, "1.0.0");
+        assert!(!pulled.cached);
+        assert!(pulled.path.ends_with(format!("demo/{}/demo", pushed.layer_digest.replace(':', "-"))));
+        assert!(pulled.path.join("SKILL.md").is_file());
+        assert!(client.pull(&reference).unwrap().cached);
+
+        // A tampered layer is rejected.
+        std::fs::remove_dir_all(dir.path().join("cache")).unwrap();
+        store.lock().unwrap().insert(pushed.layer_digest.clone(), b"tampered".to_vec());
+        let err = client.pull(&reference).unwrap_err();
+        assert!(err.to_string().contains("digest mismatch"), "{}", err);
+    }
+}
@@ -0,0 +1,197 @@
+//! Declarative post-processing of produced files.
+//!
+//! A skill can declare converters for the files it writes, and a host can
+//! add its own, so delivery pipelines get e.g. a PDF next to every DOCX
+//! without a second toolchain:
+//!
+//! ```yaml
+//! output-converters:
+//!   - from: docx
+//!     to: pdf
+//!     run: converters/docx2pdf.wasm
+//!   - from: image/*
+//!     to: png
+//!     run: scripts/resize.py
+//!     args: ["--max-width", "1024"]
+//! ```
+//!
+//! After a successful execution, each file in `ExecutionResult::artifacts`
+//! that matches a converter's `from` is converted by running `run` as a
+//! target of the skill, in the same workspace and sandbox. The converted
+//! file is added to the artifacts with `converted_from` set.
+
+use crate::errors::OpenSkillError;
+use crate::workspace_snapshot::Artifact;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Component, Path, PathBuf};
+
+/// Converts produced files of one type into another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OutputConverter {
+    /// Files to convert: an extension (`docx`) or a MIME type (`image/png`,
+    /// `image/*`).
+    pub from: String,
+    /// Extension of the converted file, written next to the original.
+    pub to: String,
+    /// Script or WASM module that converts, relative to the skill root.
+    /// It gets the input and output paths, relative to the workspace, as
+    /// its first two arguments and as `input`/`output` in its input JSON.
+    pub run: String,
+    /// Arguments after the input and output paths.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Skill that provides `run`, for host converters; converters a skill
+    /// declares always run in that skill.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+}
+
+impl OutputConverter {
+    /// A host converter running `run` from the skill `skill_id`.
+    pub fn new(
+        from: impl Into<String>,
+        to: impl Into<String>,
+        skill_id: impl Into<String>,
+        run: impl Into<String>,
+    ) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            run: run.into(),
+            args: Vec::new(),
+            skill: Some(skill_id.into()),
+        }
+    }
+
+    /// Append arguments passed after the input and output paths.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Whether `artifact` is a file this converter takes.
+    pub(crate) fn matches(&self, artifact: &Artifact) -> bool {
+        let from = self.from.trim().to_ascii_lowercase();
+        match from.split_once('/') {
+            Some((kind, "*")) => artifact
+                .mime
+                .split_once('/')
+                .is_some_and(|(k, _)| k.eq_ignore_ascii_case(kind)),
+            Some(_) => artifact.mime.eq_ignore_ascii_case(&from),
+            None => artifact
+                .path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case(from.trim_start_matches('.'))),
+        }
+    }
+
+    /// Where the converted `input` goes: `input` with the `to` extension,
+    /// or `<stem>-converted.<to>` if that is `input` itself.
+    pub(crate) fn output_path(&self, input: &Path) -> PathBuf {
+        let to = self.to.trim_start_matches('.');
+        let output = input.with_extension(to);
+        if output != input {
+            return output;
+        }
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        input.with_file_name(format!("{}-converted.{}", stem, to))
+    }
+
+    /// Arguments for converting `input` into `output`.
+    pub(crate) fn command_args(&self, input: &Path, output: &Path) -> Vec<String> {
+        let mut args = vec![
+            input.to_string_lossy().to_string(),
+            output.to_string_lossy().to_string(),
+        ];
+        args.extend(self.args.iter().cloned());
+        args
+    }
+
+    /// Input JSON for converting `input` into `output`.
+    pub(crate) fn input(&self, input: &Path, output: &Path) -> Value {
+        json!({
+            "input": input,
+            "output": output,
+            "from": self.from,
+            "to": self.to,
+        })
+    }
+}
+
+/// Reject converters that could not run: empty types, or a `run` path that
+/// leaves the skill root.
+pub(crate) fn check(converters: &[OutputConverter]) -> Result<(), OpenSkillError> {
+    for converter in converters {
+        let to = converter.to.trim_start_matches('.');
+        if converter.from.trim().is_empty()
+            || to.is_empty()
+            || !to.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(OpenSkillError::InvalidManifest(format!(
+                "output converter needs a `from` type and a `to` extension, got {:?} -> {:?}",
+                converter.from, converter.to
+            )));
+        }
+        let run = Path::new(&converter.run);
+        if converter.run.is_empty() || !run.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(OpenSkillError::InvalidManifest(format!(
+                "output converter `run` must be a path inside the skill: {}",
+                converter.run
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(path: &str, mime: &str) -> Artifact {
+        Artifact {
+            path: PathBuf::from(path),
+            size: 1,
+            mime: mime.to_string(),
+            sha256: String::new(),
+            converted_from: None,
+        }
+    }
+
+    #[test]
+    fn test_converters_match_by_extension_or_mime() {
+        let docx = OutputConverter::new("docx", "pdf", "office", "docx2pdf.wasm");
+        let word = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+        assert!(docx.matches(&artifact("/ws/Report.DOCX", word)));
+        assert!(!docx.matches(&artifact("/ws/report.pdf", "application/pdf")));
+
+        let images = OutputConverter::new("image/*", "png", "imaging", "resize.py");
+        assert!(images.matches(&artifact("/ws/chart.jpg", "image/jpeg")));
+        assert!(!images.matches(&artifact("/ws/chart.svgz", "application/octet-stream")));
+        let jpeg = OutputConverter::new("image/jpeg", "png", "imaging", "resize.py");
+        assert!(!jpeg.matches(&artifact("/ws/chart.png", "image/png")));
+
+        assert_eq!(docx.output_path(Path::new("out/report.docx")), PathBuf::from("out/report.pdf"));
+        assert_eq!(
+            images.output_path(Path::new("chart.png")),
+            PathBuf::from("chart-converted.png")
+        );
+        assert_eq!(
+            images
+                .with_args(["--max-width", "1024"])
+                .command_args(Path::new("a.png"), Path::new("a-converted.png")),
+            ["a.png", "a-converted.png", "--max-width", "1024"]
+        );
+    }
+
+    #[test]
+    fn test_check_rejects_unusable_converters() {
+        assert!(check(&[OutputConverter::new("docx", "pdf", "office", "bin/convert")]).is_ok());
+        assert!(check(&[OutputConverter::new("docx", "", "office", "convert")]).is_err());
+        assert!(check(&[OutputConverter::new("docx", "p/df", "office", "convert")]).is_err());
+        assert!(check(&[OutputConverter::new("docx", "pdf", "office", "../convert")]).is_err());
+        assert!(check(&[OutputConverter::new("docx", "pdf", "office", "/usr/bin/convert")]).is_err());
+    }
+}
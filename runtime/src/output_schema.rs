@@ -0,0 +1,85 @@
+//! Validation of execution output against a skill's `output-schema`.
+//!
+//! A skill may declare a JSON Schema for its output in SKILL.md:
+//!
+//! ```yaml
+//! output-schema:
+//!   type: object
+//!   required: [valid]
+//!   properties:
+//!     valid: { type: boolean }
+//! ```
+//!
+//! The schema is checked when the skill is validated. After a script or
+//! WASM execution succeeds, its output is validated against it; output
+//! that does not match turns the status into
+//! `ExecutionStatus::SchemaViolation`, so agents can rely on the shape of
+//! results marked `Success`.
+
+use crate::errors::OpenSkillError;
+use serde_json::Value;
+
+/// Violations reported per execution; the rest are counted.
+const MAX_REPORTED: usize = 5;
+
+/// Check that `schema` is a usable JSON Schema.
+pub(crate) fn check_schema(schema: &Value) -> Result<(), OpenSkillError> {
+    jsonschema::validator_for(schema)
+        .map(|_| ())
+        .map_err(|err| OpenSkillError::InvalidManifest(format!("Invalid output-schema: {}", err)))
+}
+
+/// Validate `output` against `schema`. Returns a description of the
+/// violations, e.g. `/valid: "yes" is not of type "boolean"`, if it does
+/// not match.
+pub(crate) fn violations(schema: &Value, output: &Value) -> Option<String> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(err) => return Some(format!("invalid output-schema: {}", err)),
+    };
+    let errors: Vec<String> = validator
+        .iter_errors(output)
+        .map(|err| match err.instance_path.as_str() {
+            "" => err.to_string(),
+            path => format!("{}: {}", path, err),
+        })
+        .collect();
+    if errors.is_empty() {
+        return None;
+    }
+    let mut message = errors[..errors.len().min(MAX_REPORTED)].join("; ");
+    if errors.len() > MAX_REPORTED {
+        message.push_str(&format!(" (and {} more)", errors.len() - MAX_REPORTED));
+    }
+    Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_violations() {
+        let schema = json!({
+            "type": "object",
+            "required": ["valid"],
+            "properties": { "valid": { "type": "boolean" } }
+        });
+        assert_eq!(violations(&schema, &json!({ "valid": true })), None);
+        assert_eq!(
+            violations(&schema, &json!({ "valid": "yes" })).as_deref(),
+            Some(r#"/valid: "yes" is not of type "boolean""#)
+        );
+        assert!(violations(&schema, &json!({})).unwrap().contains("\"valid\" is a required property"));
+    }
+
+    #[test]
+    fn test_check_schema_rejects_invalid_schema() {
+        assert!(check_schema(&json!({ "type": "object" })).is_ok());
+        assert!(matches!(
+            check_schema(&json!({ "type": 12 })),
+            Err(OpenSkillError::InvalidManifest(_))
+        ));
+    }
+}
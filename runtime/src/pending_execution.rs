@@ -0,0 +1,212 @@
+//! Executions run without borrowing the runtime.
+//!
+//! `OpenSkillRuntime::execute_skill` borrows the runtime for the whole run,
+//! so a host that shares one runtime behind a lock (a binding running
+//! executions on a worker pool) would block every other call on it until
+//! the skill finishes. A [`PendingExecution`] splits the execution in three:
+//! `begin_execution` / `begin_target_execution` resolve the skill and its
+//! permissions, [`PendingExecution::run`] runs the sandboxed process or WASM
+//! component with no access to the runtime, and `finish_pending_execution`
+//! audits it and builds the result. Only the first and last step need the
+//! lock.
+
+use crate::active_executions::CancellationHandle;
+use crate::context::ExecutionContext;
+use crate::errors::OpenSkillError;
+use crate::executor::{
+    execute_skill, run_skill_target, ExecutionArtifacts, ExecutionOptions as ExecOpts,
+    TargetExecutionOptions,
+};
+use crate::registry::Skill;
+use crate::wasm_engine::WasmEngineHandle;
+use crate::{ExecutionOptions, ExecutionResult, PreparedExecution};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+enum Job {
+    Skill(ExecOpts),
+    Target(TargetExecutionOptions),
+}
+
+impl Job {
+    fn run(
+        self,
+        skill: &Skill,
+        wasm_engine: &WasmEngineHandle,
+    ) -> Result<ExecutionArtifacts, OpenSkillError> {
+        match self {
+            Job::Skill(options) => execute_skill(skill, options, wasm_engine),
+            Job::Target(options) => run_skill_target(skill, options, wasm_engine),
+        }
+    }
+}
+
+/// An execution resolved by `OpenSkillRuntime::begin_execution` or
+/// `begin_target_execution`, to [`run`](Self::run) without the runtime and
+/// hand back to `OpenSkillRuntime::finish_pending_execution`.
+pub struct PendingExecution {
+    prepared: PreparedExecution,
+    job: Option<Job>,
+    outcome: Option<Result<ExecutionArtifacts, OpenSkillError>>,
+    wasm_engine: Arc<WasmEngineHandle>,
+    /// The attempt this execution was begun from, to prepare a retry
+    /// from (`ExecutionOptions::retry`); `None` for targets.
+    retry: Option<NextAttempt>,
+}
+
+/// What `OpenSkillRuntime::finish_pending_execution` leaves to do.
+pub enum ExecutionStep {
+    /// The execution is audited; this is its result.
+    Finished(Result<ExecutionResult, OpenSkillError>),
+    /// The attempt failed and `ExecutionOptions::retry` asks for another:
+    /// wait `delay`, then begin `next` with `OpenSkillRuntime::begin_next_attempt`.
+    Retry { delay: Duration, next: NextAttempt },
+}
+
+/// The next attempt of a retried execution (see [`ExecutionStep::Retry`]).
+#[derive(Debug, Clone)]
+pub struct NextAttempt {
+    pub(crate) skill_id: String,
+    pub(crate) options: ExecutionOptions,
+    pub(crate) attempt: u32,
+    pub(crate) parent_context: ExecutionContext,
+    /// Overrides the skill's workspace (see `OpenSkillRuntime::execute_many`).
+    pub(crate) workspace_dir: Option<PathBuf>,
+    pub(crate) cancellation: Option<CancellationHandle>,
+}
+
+impl NextAttempt {
+    /// The first attempt of `skill_id`.
+    pub(crate) fn first(skill_id: &str, options: ExecutionOptions, parent_context: &ExecutionContext) -> Self {
+        Self {
+            skill_id: skill_id.to_string(),
+            options,
+            attempt: 1,
+            parent_context: parent_context.clone(),
+            workspace_dir: None,
+            cancellation: None,
+        }
+    }
+
+    pub(crate) fn with_cancellation(mut self, cancellation: CancellationHandle) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+}
+
+impl PendingExecution {
+    pub(crate) fn skill(
+        prepared: PreparedExecution,
+        options: ExecOpts,
+        wasm_engine: Arc<WasmEngineHandle>,
+        retry: NextAttempt,
+    ) -> Self {
+        Self {
+            prepared,
+            job: Some(Job::Skill(options)),
+            outcome: None,
+            wasm_engine,
+            retry: Some(retry),
+        }
+    }
+
+    pub(crate) fn target(
+        prepared: PreparedExecution,
+        options: TargetExecutionOptions,
+        wasm_engine: Arc<WasmEngineHandle>,
+    ) -> Self {
+        Self {
+            prepared,
+            job: Some(Job::Target(options)),
+            outcome: None,
+            wasm_engine,
+            retry: None,
+        }
+    }
+
+    /// The skill being executed.
+    pub fn skill_id(&self) -> &str {
+        &self.prepared.skill.id
+    }
+
+    /// ID of the running execution (see `OpenSkillRuntime::cancel_execution`).
+    pub(crate) fn execution_id(&self) -> u64 {
+        self.prepared.running.id()
+    }
+
+    /// Run in `workspace` instead of the skill's workspace, retries too.
+    pub(crate) fn with_workspace(mut self, workspace: PathBuf) -> Self {
+        if let Some(Job::Skill(options)) = &mut self.job {
+            options.workspace_dir = Some(workspace.clone());
+        }
+        if let Some(retry) = &mut self.retry {
+            retry.workspace_dir = Some(workspace.clone());
+        }
+        self.prepared.workspace_dir = Some(workspace);
+        self
+    }
+
+    /// Run the sandboxed process or WASM component on the calling thread,
+    /// blocking until it exits. Running twice does nothing. A panic during
+    /// the run becomes the execution's error, so a worker running several
+    /// executions keeps going.
+    pub fn run(&mut self) {
+        let Some(job) = self.job.take() else {
+            return;
+        };
+        // Report the run time, not the time spent waiting to run.
+        self.prepared.start = Instant::now();
+        let (skill, wasm_engine) = (&self.prepared.skill, &self.wasm_engine);
+        self.outcome = Some(
+            catch_unwind(AssertUnwindSafe(|| job.run(skill, wasm_engine))).unwrap_or_else(
+                |panic| {
+                    let message = panic
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| panic.downcast_ref::<String>().cloned())
+                        .unwrap_or_default();
+                    Err(OpenSkillError::NativeExecutionError(format!(
+                        "execution panicked: {}",
+                        message
+                    )))
+                },
+            ),
+        );
+    }
+
+    /// Like [`run`](Self::run), on tokio's blocking pool. Dropping the
+    /// future cancels the execution.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn run_blocking(&mut self) {
+        let Some(job) = self.job.take() else {
+            return;
+        };
+        self.prepared.start = Instant::now();
+        let skill = self.prepared.skill.clone();
+        let wasm_engine = self.wasm_engine.clone();
+        let cancellation = self.prepared.running.cancellation();
+        self.outcome = Some(
+            crate::run_blocking(cancellation, move || job.run(&skill, &wasm_engine)).await,
+        );
+    }
+
+    /// Run if not run yet, and return the parts `finish_execution` needs.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn into_parts(
+        mut self,
+    ) -> (
+        PreparedExecution,
+        Result<ExecutionArtifacts, OpenSkillError>,
+        Option<NextAttempt>,
+    ) {
+        self.run();
+        let outcome = self.outcome.take().expect("run() sets the outcome");
+        let next = self.retry.map(|next| NextAttempt {
+            attempt: next.attempt + 1,
+            ..next
+        });
+        (self.prepared, outcome, next)
+    }
+}
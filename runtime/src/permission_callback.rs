@@ -3,12 +3,38 @@
 //! This module provides a callback-based permission system that allows
 //! runtime users to approve or deny potentially dangerous operations
 //! before they execute.
+//!
+//! "Allow always" answers become [`PermissionGrant`]s. With a grant store
+//! (see [`PermissionManager::load_grants`]) they are saved to a JSON file,
+//! by default `~/.config/openskills/grants.json`, so users are not asked
+//! again after a restart. "Allow for" answers become grants that expire,
+//! after which the user is asked again. A grant is bound to the root
+//! directory of the skill it was made for, so a different skill that
+//! happens to use the same id is asked again. Runtimes sharing a grant store
+//! update it under a file lock, keeping each other's grants.
+//!
+//! With the `tokio` feature, an [`AsyncPermissionCallback`] answers the
+//! prompts of the async execution API without blocking a thread while a
+//! person decides.
 
 use crate::errors::OpenSkillError;
+use crate::events::{EventSink, RuntimeEvent};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+#[cfg(feature = "tokio")]
+use std::future::Future;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Permission a skill's `writes-skill-root:` opt-in is requested as.
+pub(crate) const WRITE_SKILL_ROOT: &str = "WriteSkillRoot";
+
+/// Requests that are denied rather than auto-allowed without a callback:
+/// they only ever come from a skill asking for more than the sandbox gives.
+const CALLBACK_REQUIRED: &[&str] = &[WRITE_SKILL_ROOT];
 
 /// Risk level for permission requests.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -33,10 +59,55 @@ pub struct PermissionRequest {
     pub description: String,
     /// Risk level: low, medium, high.
     pub risk_level: RiskLevel,
-    /// Additional context (e.g., file path, command).
+    /// Additional context (e.g., file path, command). Requests made before
+    /// an execution carry the `command` it runs, the `write_paths` the tool
+    /// makes writable and the execution's `workspace`.
     pub context: HashMap<String, String>,
 }
 
+/// Ready-made answers to permission requests, so common setups need no
+/// hand-written callback (see `OpenSkillRuntime::with_permission_profile`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionProfile {
+    /// Deny every risky tool without asking.
+    Strict,
+    /// Allow low and medium risk tools; ask the callback for high risk
+    /// ones, denying them without a callback.
+    Standard,
+    /// Allow low and medium risk tools; ask the callback for high risk
+    /// ones, allowing them without a callback.
+    Permissive,
+}
+
+impl PermissionProfile {
+    /// The profile's answer for a request of `risk_level`, or `None` to ask
+    /// the callback.
+    fn decide(self, risk_level: RiskLevel) -> Option<bool> {
+        match (self, risk_level) {
+            (PermissionProfile::Strict, _) => Some(false),
+            (_, RiskLevel::High) => None,
+            (_, RiskLevel::Low | RiskLevel::Medium) => Some(true),
+        }
+    }
+}
+
+impl std::str::FromStr for PermissionProfile {
+    type Err = OpenSkillError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "strict" => Ok(PermissionProfile::Strict),
+            "standard" => Ok(PermissionProfile::Standard),
+            "permissive" => Ok(PermissionProfile::Permissive),
+            other => Err(OpenSkillError::InvalidConfig(format!(
+                "unknown permission profile '{}'",
+                other
+            ))),
+        }
+    }
+}
+
 /// User's response to a permission request.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PermissionResponse {
@@ -44,10 +115,141 @@ pub enum PermissionResponse {
     AllowOnce,
     /// Allow all operations of this type for this skill.
     AllowAlways,
+    /// Allow all operations of this type for this skill for a while, e.g.
+    /// Bash for the next hour; then ask again.
+    AllowFor(Duration),
+    /// Allow this operation once, but let it write only inside the
+    /// execution's workspace: the sandbox gets no other writable paths.
+    AllowInWorkspace,
     /// Deny this operation.
     Deny,
 }
 
+/// Outcome of a permission check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionDecision {
+    Allowed,
+    /// Allowed with writes confined to the workspace
+    /// ([`PermissionResponse::AllowInWorkspace`]).
+    AllowedInWorkspace,
+    Denied,
+}
+
+impl From<bool> for PermissionDecision {
+    fn from(allowed: bool) -> Self {
+        if allowed {
+            PermissionDecision::Allowed
+        } else {
+            PermissionDecision::Denied
+        }
+    }
+}
+
+/// How long a [`PermissionGrant`] lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrantScope {
+    /// Until the process exits.
+    Session,
+    /// Saved to the grant store and loaded again by later processes.
+    Persistent,
+}
+
+/// A standing "allow always" approval of a tool for a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionGrant {
+    pub skill_id: String,
+    /// Directory the skill was loaded from. The grant only answers for the
+    /// skill with `skill_id` at this root; grants without one are not
+    /// loaded from a grant store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill_root: Option<PathBuf>,
+    pub tool: String,
+    pub scope: GrantScope,
+    /// When the grant was made, in milliseconds since the Unix epoch.
+    pub granted_at: u64,
+    /// When the grant runs out, in milliseconds since the Unix epoch;
+    /// `None` for grants that do not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl PermissionGrant {
+    /// Time left before the grant runs out; `None` for grants that do not.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires_at
+            .map(|expires_at| Duration::from_millis(expires_at.saturating_sub(now_ms())))
+    }
+
+    fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    fn key(&self) -> GrantKey {
+        (self.skill_id.clone(), self.skill_root.clone(), self.tool.clone())
+    }
+}
+
+/// (skill_id, skill_root, tool) a grant answers for.
+type GrantKey = (String, Option<PathBuf>, String);
+
+/// Contents of a grant store.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GrantFile {
+    grants: Vec<PermissionGrant>,
+}
+
+impl GrantFile {
+    /// Read `path`; a missing file holds no grants.
+    fn read(path: &Path) -> Result<Self, OpenSkillError> {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                OpenSkillError::InvalidConfig(format!(
+                    "invalid grant store {}: {e}",
+                    path.display()
+                ))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace `path` atomically. The caller holds the store lock.
+    fn write(mut self, path: &Path) -> Result<(), OpenSkillError> {
+        self.grants.sort_by_key(PermissionGrant::key);
+        let staging = path.with_extension("json.tmp");
+        std::fs::write(&staging, serde_json::to_vec_pretty(&self)?)?;
+        std::fs::rename(&staging, path)?;
+        Ok(())
+    }
+}
+
+/// Exclusive lock on a grant store, held while it is read, changed and
+/// written back. Released when dropped.
+struct StoreLock {
+    _file: std::fs::File,
+}
+
+impl StoreLock {
+    fn acquire(path: &Path) -> Result<Self, OpenSkillError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_extension("json.lock"))?;
+        file.lock()?;
+        Ok(Self { _file: file })
+    }
+}
+
+/// `~/.config/openskills/grants.json`.
+pub fn default_grants_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("openskills").join("grants.json"))
+}
+
 /// Callback trait for requesting user permissions.
 ///
 /// Implement this trait to provide custom permission prompts (CLI, GUI, etc.).
@@ -63,20 +265,59 @@ pub trait PermissionCallback: Send + Sync {
     ) -> Result<PermissionResponse, OpenSkillError>;
 }
 
+/// Future returned by [`AsyncPermissionCallback::request_permission`].
+#[cfg(feature = "tokio")]
+pub type PermissionFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<PermissionResponse, OpenSkillError>> + Send + 'a>>;
+
+/// Async variant of [`PermissionCallback`], awaited by the async execution
+/// API (`execute_skill_async`, `run_skill_target_async`) so waiting for a
+/// person ties up no worker thread.
+///
+/// ```rust,ignore
+/// impl AsyncPermissionCallback for SlackApprover {
+///     fn request_permission<'a>(&'a self, request: &'a PermissionRequest) -> PermissionFuture<'a> {
+///         Box::pin(async move { self.ask_channel(request).await })
+///     }
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+pub trait AsyncPermissionCallback: Send + Sync {
+    /// Request permission for a potentially dangerous operation.
+    fn request_permission<'a>(&'a self, request: &'a PermissionRequest) -> PermissionFuture<'a>;
+}
+
 /// Permission manager that tracks approvals and denials.
 pub struct PermissionManager {
     callback: Option<Arc<dyn PermissionCallback>>,
-    // Track "allow always" grants: (skill_id, tool) -> granted
-    always_allowed: Arc<Mutex<HashMap<(String, String), bool>>>,
+    // Answers requests before the grants and the callback
+    profile: Option<PermissionProfile>,
+    // Allow and record requests instead of asking the callback
+    audit_only: bool,
+    // Track "allow always" grants: (skill_id, skill_root, tool) -> grant
+    always_allowed: Arc<Mutex<HashMap<GrantKey, PermissionGrant>>>,
+    // File persistent grants are saved to
+    store: Option<PathBuf>,
     // Audit log of permission requests
     audit_log: Arc<Mutex<Vec<PermissionAuditEntry>>>,
+    // Receives PermissionRequested/PermissionResolved events
+    events: EventSink,
+    #[cfg(feature = "tokio")]
+    async_callback: Option<Arc<dyn AsyncPermissionCallback>>,
+    // Requests that would ask the callback, while collecting them
+    collected: Mutex<Option<Vec<PermissionRequest>>>,
+    // Answers given ahead of time by the async callback: (skill_id, tool) -> answer
+    answers: Mutex<HashMap<(String, String), PermissionResponse>>,
 }
 
 impl std::fmt::Debug for PermissionManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PermissionManager")
             .field("has_callback", &self.callback.is_some())
+            .field("profile", &self.profile)
+            .field("audit_only", &self.audit_only)
             .field("always_allowed_count", &self.always_allowed.lock().unwrap().len())
+            .field("store", &self.store)
             .field("audit_log_count", &self.audit_log.lock().unwrap().len())
             .finish()
     }
@@ -89,6 +330,18 @@ pub struct PermissionAuditEntry {
     pub skill_id: String,
     pub tool: String,
     pub response: PermissionResponse,
+    /// Whether an earlier time-limited grant answered, instead of the
+    /// callback.
+    #[serde(default)]
+    pub from_grant: bool,
+    /// Time left on the time-limited grant this answer made or used, in
+    /// milliseconds.
+    #[serde(default)]
+    pub remaining_ttl_ms: Option<u64>,
+    /// Whether the callback would have been asked, in audit-only mode;
+    /// the request was allowed without asking.
+    #[serde(default)]
+    pub would_have_asked: bool,
 }
 
 impl PermissionManager {
@@ -96,8 +349,16 @@ impl PermissionManager {
     pub fn new() -> Self {
         Self {
             callback: None,
+            profile: None,
+            audit_only: false,
             always_allowed: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
             audit_log: Arc::new(Mutex::new(Vec::new())),
+            events: EventSink::default(),
+            #[cfg(feature = "tokio")]
+            async_callback: None,
+            collected: Mutex::new(None),
+            answers: Mutex::new(HashMap::new()),
         }
     }
 
@@ -105,35 +366,343 @@ impl PermissionManager {
     pub fn with_callback(callback: Arc<dyn PermissionCallback>) -> Self {
         Self {
             callback: Some(callback),
+            profile: None,
+            audit_only: false,
             always_allowed: Arc::new(Mutex::new(HashMap::new())),
+            store: None,
             audit_log: Arc::new(Mutex::new(Vec::new())),
+            events: EventSink::default(),
+            #[cfg(feature = "tokio")]
+            async_callback: None,
+            collected: Mutex::new(None),
+            answers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Report permission prompts to `events`.
+    pub(crate) fn with_events(mut self, events: EventSink) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub(crate) fn set_events(&mut self, events: EventSink) {
+        self.events = events;
+    }
+
+    /// Keep the grants, grant store, profile and audit-only mode of
+    /// `other`, e.g. when the callback changes.
+    pub(crate) fn with_grants_of(mut self, other: &PermissionManager) -> Self {
+        self.always_allowed = Arc::clone(&other.always_allowed);
+        self.store = other.store.clone();
+        self.profile = other.profile;
+        self.audit_only = other.audit_only;
+        #[cfg(feature = "tokio")]
+        {
+            self.async_callback = other.async_callback.clone();
+        }
+        self
+    }
+
+    /// Answer the async execution API's requests with `callback`.
+    #[cfg(feature = "tokio")]
+    pub fn set_async_callback(&mut self, callback: Option<Arc<dyn AsyncPermissionCallback>>) {
+        self.async_callback = callback;
+    }
+
+    /// Whether an async callback is set.
+    #[cfg(feature = "tokio")]
+    pub fn has_async_callback(&self) -> bool {
+        self.async_callback.is_some()
+    }
+
+    /// Until [`Self::take_collected`], note the requests that would ask the
+    /// callback and deny them, without recording anything.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn start_collecting(&self) {
+        *self.collected.lock().unwrap() = Some(Vec::new());
+    }
+
+    /// Stop collecting and return the requests noted since
+    /// [`Self::start_collecting`].
+    #[cfg(feature = "tokio")]
+    pub(crate) fn take_collected(&self) -> Vec<PermissionRequest> {
+        self.collected.lock().unwrap().take().unwrap_or_default()
+    }
+
+    /// Ask the async callback about `requests`; the answers are used by the
+    /// next [`Self::decide_permission`] for the same skill and tool.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn ask_async(&self, requests: Vec<PermissionRequest>) -> Result<(), OpenSkillError> {
+        let Some(callback) = self.async_callback.clone() else {
+            return Ok(());
+        };
+        for request in requests {
+            self.events.emit(|| RuntimeEvent::PermissionRequested {
+                request: request.clone(),
+            });
+            let response = callback.request_permission(&request).await?;
+            self.answers
+                .lock()
+                .unwrap()
+                .insert((request.skill_id, request.tool), response);
+        }
+        Ok(())
+    }
+
+    /// Drop answers [`Self::ask_async`] got that were not used.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn clear_answers(&self) {
+        self.answers.lock().unwrap().clear();
+    }
+
+    /// In audit-only mode, requests that would ask the callback are allowed
+    /// without asking and recorded with `would_have_asked`.
+    pub fn set_audit_only(&mut self, audit_only: bool) {
+        self.audit_only = audit_only;
+    }
+
+    /// Whether audit-only mode is on.
+    pub fn is_audit_only(&self) -> bool {
+        self.audit_only
+    }
+
+    /// Answer requests with `profile` where it decides, before consulting
+    /// grants or the callback.
+    pub fn set_profile(&mut self, profile: Option<PermissionProfile>) {
+        self.profile = profile;
+    }
+
+    /// The profile answering requests, if any.
+    pub fn profile(&self) -> Option<PermissionProfile> {
+        self.profile
+    }
+
+    /// Load the grants saved in `path` and make it the grant store: later
+    /// "allow always" answers and revocations are saved there. A missing
+    /// file holds no grants, and expired grants and grants not bound to a
+    /// skill root are skipped. Returns the number of grants loaded.
+    pub fn load_grants(&mut self, path: &Path) -> Result<usize, OpenSkillError> {
+        let now = now_ms();
+        let grants: Vec<PermissionGrant> = GrantFile::read(path)?
+            .grants
+            .into_iter()
+            .filter(|g| g.skill_root.is_some() && !g.is_expired(now))
+            .collect();
+        let count = grants.len();
+        let mut always_allowed = self.always_allowed.lock().unwrap();
+        for grant in grants {
+            let key = grant.key();
+            let grant = PermissionGrant {
+                scope: GrantScope::Persistent,
+                ..grant
+            };
+            always_allowed.insert(key, grant);
         }
+        drop(always_allowed);
+        self.store = Some(path.to_path_buf());
+        Ok(count)
+    }
+
+    /// Write the persistent grants to `path`, replacing it atomically.
+    pub fn save_grants(&self, path: &Path) -> Result<(), OpenSkillError> {
+        let grants: Vec<PermissionGrant> = self
+            .grants()
+            .into_iter()
+            .filter(|g| g.scope == GrantScope::Persistent)
+            .collect();
+        let _lock = StoreLock::acquire(path)?;
+        GrantFile { grants }.write(path)
+    }
+
+    /// All current grants; expired ones are left out.
+    pub fn grants(&self) -> Vec<PermissionGrant> {
+        let now = now_ms();
+        let always_allowed = self.always_allowed.lock().unwrap();
+        always_allowed
+            .values()
+            .filter(|g| !g.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Approve `tool` for the skill `skill_id` loaded from `skill_root`
+    /// without asking, as if the user had answered "allow always".
+    pub fn grant(
+        &self,
+        skill_id: &str,
+        skill_root: Option<&Path>,
+        tool: &str,
+        scope: GrantScope,
+    ) -> Result<(), OpenSkillError> {
+        self.insert_grant(skill_id, skill_root, tool, scope, None)
+    }
+
+    /// Approve `tool` for the skill `skill_id` loaded from `skill_root`
+    /// without asking for `ttl`, as if the user had answered "allow for";
+    /// afterwards the user is asked again.
+    pub fn grant_for(
+        &self,
+        skill_id: &str,
+        skill_root: Option<&Path>,
+        tool: &str,
+        scope: GrantScope,
+        ttl: Duration,
+    ) -> Result<(), OpenSkillError> {
+        let ttl_ms = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        self.insert_grant(
+            skill_id,
+            skill_root,
+            tool,
+            scope,
+            Some(now_ms().saturating_add(ttl_ms)),
+        )
     }
 
-    /// Check if permission is granted for this operation.
-    pub fn check_permission(
+    fn insert_grant(
         &self,
         skill_id: &str,
+        skill_root: Option<&Path>,
         tool: &str,
+        scope: GrantScope,
+        expires_at: Option<u64>,
+    ) -> Result<(), OpenSkillError> {
+        let grant = PermissionGrant {
+            skill_id: skill_id.to_string(),
+            skill_root: skill_root.map(Path::to_path_buf),
+            tool: tool.to_string(),
+            scope,
+            granted_at: now_ms(),
+            expires_at,
+        };
+        let key = grant.key();
+        self.always_allowed.lock().unwrap().insert(key.clone(), grant.clone());
+        if scope != GrantScope::Persistent {
+            return Ok(());
+        }
+        self.update_store(|grants| {
+            grants.retain(|g| g.key() != key);
+            grants.push(grant);
+        })
+    }
+
+    /// Withdraw the grants of `tool` to `skill_id`, whatever root the skill
+    /// was loaded from; the next use asks again. Returns whether there was
+    /// one.
+    pub fn revoke(&self, skill_id: &str, tool: &str) -> Result<bool, OpenSkillError> {
+        let matches = |g: &PermissionGrant| g.skill_id == skill_id && g.tool == tool;
+        let mut removed = {
+            let mut always_allowed = self.always_allowed.lock().unwrap();
+            let before = always_allowed.len();
+            always_allowed.retain(|_, g| !matches(g));
+            always_allowed.len() != before
+        };
+        self.update_store(|grants| {
+            let before = grants.len();
+            grants.retain(|g| !matches(g));
+            removed |= grants.len() != before;
+        })?;
+        Ok(removed)
+    }
+
+    /// Change the grants in the grant store, if there is one. The store is
+    /// read, changed and written back under a file lock, so runtimes
+    /// sharing it keep each other's grants.
+    fn update_store(
+        &self,
+        update: impl FnOnce(&mut Vec<PermissionGrant>),
+    ) -> Result<(), OpenSkillError> {
+        let Some(path) = &self.store else {
+            return Ok(());
+        };
+        let _lock = StoreLock::acquire(path)?;
+        let mut file = GrantFile::read(path)?;
+        update(&mut file.grants);
+        file.write(path)
+    }
+
+    /// Check if permission is granted for this operation, and whether the
+    /// answer confined it to the workspace. "Allow always" and "allow for"
+    /// answers grant `grant_key`: the tool, or for a call an `allowed-tools`
+    /// pattern such as `Bash(npm run test:*)` matched, that pattern. A grant
+    /// of the whole tool also answers pattern-matched calls. Grants are
+    /// bound to `skill_root`, the directory the skill was loaded from.
+    #[allow(clippy::too_many_arguments)]
+    pub fn decide_permission(
+        &self,
+        skill_id: &str,
+        skill_root: Option<&Path>,
+        tool: &str,
+        grant_key: &str,
         description: String,
         risk_level: RiskLevel,
         context: HashMap<String, String>,
-    ) -> Result<bool, OpenSkillError> {
-        // Check if previously granted "allow always"
+    ) -> Result<PermissionDecision, OpenSkillError> {
+        let collecting = self.collected.lock().unwrap().is_some();
+        // The profile decides without asking, e.g. strict denies even granted tools
+        if let Some(allowed) = self.profile.and_then(|p| p.decide(risk_level)) {
+            let response = if allowed {
+                PermissionResponse::AllowOnce
+            } else {
+                PermissionResponse::Deny
+            };
+            if !collecting {
+                self.record_permission_audit(skill_id, tool, response, false, None);
+            }
+            return Ok(allowed.into());
+        }
+
+        // Check if previously granted "allow always" or "allow for"
         let key = (skill_id.to_string(), tool.to_string());
-        {
+        let root = skill_root.map(Path::to_path_buf);
+        let grant_keys = [
+            (skill_id.to_string(), root.clone(), grant_key.to_string()),
+            (skill_id.to_string(), root, tool.to_string()),
+        ];
+        let existing = {
             let always_allowed = self.always_allowed.lock().unwrap();
-            if let Some(&granted) = always_allowed.get(&key) {
-                return Ok(granted);
+            grant_keys
+                .into_iter()
+                .find_map(|key| always_allowed.get(&key).cloned().map(|grant| (key, grant)))
+        };
+        if let Some((key, grant)) = existing {
+            let now = now_ms();
+            match grant.expires_at {
+                None => return Ok(PermissionDecision::Allowed),
+                Some(_) if collecting => return Ok(PermissionDecision::Allowed),
+                Some(expires_at) if expires_at > now => {
+                    let remaining = expires_at - now;
+                    self.record_permission_audit(
+                        skill_id,
+                        tool,
+                        PermissionResponse::AllowFor(Duration::from_millis(remaining)),
+                        true,
+                        Some(remaining),
+                    );
+                    return Ok(PermissionDecision::Allowed);
+                }
+                Some(_) => {
+                    // Expired: forget it and ask again
+                    self.always_allowed.lock().unwrap().remove(&key);
+                    if grant.scope == GrantScope::Persistent {
+                        let _ = self.update_store(|grants| grants.retain(|g| !g.is_expired(now)));
+                    }
+                }
             }
         }
 
-        // No callback means auto-allow (for backward compatibility)
-        let Some(ref callback) = self.callback else {
-            return Ok(true);
-        };
+        // Audit-only: note the prompt that would have happened, never block
+        if self.audit_only && collecting {
+            return Ok(PermissionDecision::Allowed);
+        }
+        if self.audit_only {
+            let entry = PermissionAuditEntry {
+                would_have_asked: true,
+                ..Self::audit_entry(skill_id, tool, PermissionResponse::AllowOnce)
+            };
+            self.audit_log.lock().unwrap().push(entry);
+            return Ok(PermissionDecision::Allowed);
+        }
 
-        // Request permission
         let request = PermissionRequest {
             skill_id: skill_id.to_string(),
             tool: tool.to_string(),
@@ -141,52 +710,126 @@ impl PermissionManager {
             risk_level,
             context,
         };
-
-        let response = callback.request_permission(&request)?;
+        let answer = self.answers.lock().unwrap().remove(&key);
+        let response = match answer {
+            Some(response) => response,
+            None => {
+                if let Some(collected) = self.collected.lock().unwrap().as_mut() {
+                    collected.push(request);
+                    return Ok(PermissionDecision::Denied);
+                }
+                #[cfg(feature = "tokio")]
+                let async_only = self.async_callback.is_some();
+                #[cfg(not(feature = "tokio"))]
+                let async_only = false;
+                // No callback means auto-allow (for backward compatibility),
+                // unless the standard profile wants an answer, only the
+                // async execution API can ask, or the request needs an answer
+                let Some(ref callback) = self.callback else {
+                    return Ok((!async_only
+                        && self.profile != Some(PermissionProfile::Standard)
+                        && !CALLBACK_REQUIRED.contains(&tool))
+                    .into());
+                };
+                self.events.emit(|| RuntimeEvent::PermissionRequested {
+                    request: request.clone(),
+                });
+                callback.request_permission(&request)?
+            }
+        };
+        self.events.emit(|| RuntimeEvent::PermissionResolved {
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            response: response.clone(),
+        });
 
         // Record audit
-        self.record_permission_audit(skill_id, tool, response.clone());
+        let remaining_ttl_ms = match &response {
+            PermissionResponse::AllowFor(ttl) => {
+                Some(u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX))
+            }
+            _ => None,
+        };
+        self.record_permission_audit(skill_id, tool, response.clone(), false, remaining_ttl_ms);
 
+        // Grants last across restarts when there is a grant store
+        let scope = if self.store.is_some() {
+            GrantScope::Persistent
+        } else {
+            GrantScope::Session
+        };
         match response {
-            PermissionResponse::AllowOnce => Ok(true),
+            PermissionResponse::AllowOnce => Ok(PermissionDecision::Allowed),
             PermissionResponse::AllowAlways => {
-                // Grant permanently for this (skill, tool) pair
-                let mut always_allowed = self.always_allowed.lock().unwrap();
-                always_allowed.insert(key, true);
-                Ok(true)
+                // Grant permanently for this (skill, tool) pair.
+                // Best effort: a failed save still leaves the grant for this process.
+                let _ = self.grant(skill_id, skill_root, grant_key, scope);
+                Ok(PermissionDecision::Allowed)
+            }
+            PermissionResponse::AllowFor(ttl) => {
+                let _ = self.grant_for(skill_id, skill_root, grant_key, scope, ttl);
+                Ok(PermissionDecision::Allowed)
             }
-            PermissionResponse::Deny => Ok(false),
+            PermissionResponse::AllowInWorkspace => Ok(PermissionDecision::AllowedInWorkspace),
+            PermissionResponse::Deny => Ok(PermissionDecision::Denied),
         }
     }
 
-    fn record_permission_audit(&self, skill_id: &str, tool: &str, response: PermissionResponse) {
+    fn record_permission_audit(
+        &self,
+        skill_id: &str,
+        tool: &str,
+        response: PermissionResponse,
+        from_grant: bool,
+        remaining_ttl_ms: Option<u64>,
+    ) {
         let entry = PermissionAuditEntry {
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            skill_id: skill_id.to_string(),
-            tool: tool.to_string(),
-            response,
+            from_grant,
+            remaining_ttl_ms,
+            ..Self::audit_entry(skill_id, tool, response)
         };
 
         let mut audit_log = self.audit_log.lock().unwrap();
         audit_log.push(entry);
     }
 
+    fn audit_entry(
+        skill_id: &str,
+        tool: &str,
+        response: PermissionResponse,
+    ) -> PermissionAuditEntry {
+        PermissionAuditEntry {
+            timestamp: now_ms(),
+            skill_id: skill_id.to_string(),
+            tool: tool.to_string(),
+            response,
+            from_grant: false,
+            remaining_ttl_ms: None,
+            would_have_asked: false,
+        }
+    }
+
     /// Get permission audit log.
     pub fn get_audit_log(&self) -> Vec<PermissionAuditEntry> {
         let audit_log = self.audit_log.lock().unwrap();
         audit_log.clone()
     }
 
-    /// Reset all "allow always" grants (for testing or security).
+    /// Reset all "allow always" grants (for testing or security), including
+    /// those in the grant store.
     pub fn reset_grants(&self) {
-        let mut always_allowed = self.always_allowed.lock().unwrap();
-        always_allowed.clear();
+        self.always_allowed.lock().unwrap().clear();
+        let _ = self.update_store(Vec::clear);
     }
 }
 
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
 impl Default for PermissionManager {
     fn default() -> Self {
         Self::new()
@@ -233,7 +876,9 @@ impl PermissionCallback for CliPermissionCallback {
         println!("  [1] Allow once");
         println!("  [2] Allow always (for this skill + tool)");
         println!("  [3] Deny");
-        print!("\nYour choice (1-3): ");
+        println!("  [4] Allow for 1 hour (for this skill + tool)");
+        println!("  [5] Allow once, writing only inside the workspace");
+        print!("\nYour choice (1-5): ");
         io::stdout().flush().unwrap();
 
         let mut input = String::new();
@@ -245,6 +890,8 @@ impl PermissionCallback for CliPermissionCallback {
             "1" => Ok(PermissionResponse::AllowOnce),
             "2" => Ok(PermissionResponse::AllowAlways),
             "3" | "" => Ok(PermissionResponse::Deny),
+            "4" => Ok(PermissionResponse::AllowFor(Duration::from_secs(60 * 60))),
+            "5" => Ok(PermissionResponse::AllowInWorkspace),
             _ => {
                 println!("Invalid choice. Denying by default.");
                 Ok(PermissionResponse::Deny)
@@ -279,30 +926,54 @@ mod tests {
     fn test_permission_manager_auto_allow() {
         let manager = PermissionManager::new();
         let granted = manager
-            .check_permission(
+            .decide_permission(
                 "test-skill",
+                None,
+                "Write",
                 "Write",
                 "Write file".to_string(),
                 RiskLevel::Medium,
                 HashMap::new(),
             )
             .unwrap();
-        assert!(granted); // Auto-allows when no callback
+        assert_eq!(granted, PermissionDecision::Allowed); // Auto-allows when no callback
+
+        // Writing to the skill root needs an answer.
+        let write_root = |manager: &PermissionManager| {
+            manager
+                .decide_permission(
+                    "test-skill",
+                    None,
+                    WRITE_SKILL_ROOT,
+                    WRITE_SKILL_ROOT,
+                    String::new(),
+                    RiskLevel::High,
+                    HashMap::new(),
+                )
+                .unwrap()
+        };
+        assert_eq!(write_root(&manager), PermissionDecision::Denied);
+        manager
+            .grant("test-skill", None, WRITE_SKILL_ROOT, GrantScope::Session)
+            .unwrap();
+        assert_eq!(write_root(&manager), PermissionDecision::Allowed);
     }
 
     #[test]
     fn test_permission_manager_deny_all() {
         let manager = PermissionManager::with_callback(Arc::new(DenyAllCallback));
         let granted = manager
-            .check_permission(
+            .decide_permission(
                 "test-skill",
+                None,
+                "Write",
                 "Write",
                 "Write file".to_string(),
                 RiskLevel::Medium,
                 HashMap::new(),
             )
             .unwrap();
-        assert!(!granted);
+        assert_eq!(granted, PermissionDecision::Denied);
     }
 
     #[test]
@@ -321,27 +992,31 @@ mod tests {
 
         // First request
         let granted1 = manager
-            .check_permission(
+            .decide_permission(
                 "test-skill",
+                None,
+                "Write",
                 "Write",
                 "Write file".to_string(),
                 RiskLevel::Medium,
                 HashMap::new(),
             )
             .unwrap();
-        assert!(granted1);
+        assert_eq!(granted1, PermissionDecision::Allowed);
 
         // Second request should be auto-approved
         let granted2 = manager
-            .check_permission(
+            .decide_permission(
                 "test-skill",
+                None,
+                "Write",
                 "Write",
                 "Write file".to_string(),
                 RiskLevel::Medium,
                 HashMap::new(),
             )
             .unwrap();
-        assert!(granted2);
+        assert_eq!(granted2, PermissionDecision::Allowed);
 
         // Check audit log
         let audit = manager.get_audit_log();
@@ -350,6 +1025,174 @@ mod tests {
         assert_eq!(audit[0].tool, "Write");
     }
 
+    #[test]
+    fn test_grants_persist_in_store() {
+        struct AllowAlwaysCallback;
+        impl PermissionCallback for AllowAlwaysCallback {
+            fn request_permission(
+                &self,
+                _request: &PermissionRequest,
+            ) -> Result<PermissionResponse, OpenSkillError> {
+                Ok(PermissionResponse::AllowAlways)
+            }
+        }
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().join("openskills").join("grants.json");
+        let root = dir.path().join("skills").join("pdf");
+        let check = |manager: &PermissionManager, tool: &str| {
+            manager
+                .decide_permission("pdf", Some(&root), tool, tool, String::new(), RiskLevel::High, HashMap::new())
+                .unwrap()
+                == PermissionDecision::Allowed
+        };
+
+        let mut first = PermissionManager::with_callback(Arc::new(AllowAlwaysCallback));
+        assert_eq!(first.load_grants(&store).unwrap(), 0);
+        assert!(check(&first, "Bash"));
+        assert!(check(&first, "Write"));
+        assert_eq!(first.grants().len(), 2);
+
+        // A new process loads the grants and is not asked again.
+        let mut second = PermissionManager::with_callback(Arc::new(DenyAllCallback));
+        assert_eq!(second.load_grants(&store).unwrap(), 2);
+        assert!(check(&second, "Bash"));
+        assert!(second.grants().iter().all(|g| g.scope == GrantScope::Persistent));
+
+        assert!(second.revoke("pdf", "Bash").unwrap());
+        assert!(!second.revoke("pdf", "Bash").unwrap());
+        assert!(!check(&second, "Bash"));
+        assert!(check(&second, "Write"));
+
+        let mut third = PermissionManager::new();
+        assert_eq!(third.load_grants(&store).unwrap(), 1);
+        assert_eq!(third.grants()[0].tool, "Write");
+
+        // Session grants are not saved.
+        third.grant("pdf", Some(&root), "Fetch", GrantScope::Session).unwrap();
+        let mut fourth = PermissionManager::new();
+        assert_eq!(fourth.load_grants(&store).unwrap(), 1);
+
+        std::fs::write(&store, "not json").unwrap();
+        assert!(matches!(
+            PermissionManager::new().load_grants(&store),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_grants_are_bound_to_the_skill_root() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().join("grants.json");
+        let trusted = dir.path().join("trusted").join("pdf");
+        let other = dir.path().join("downloads").join("pdf");
+        let check = |manager: &PermissionManager, root: &Path| {
+            manager
+                .decide_permission("pdf", Some(root), "Bash", "Bash", String::new(), RiskLevel::High, HashMap::new())
+                .unwrap()
+                == PermissionDecision::Allowed
+        };
+
+        let mut manager = PermissionManager::with_callback(Arc::new(DenyAllCallback));
+        manager.load_grants(&store).unwrap();
+        manager.grant("pdf", Some(&trusted), "Bash", GrantScope::Persistent).unwrap();
+        assert!(check(&manager, &trusted));
+        // Another skill with the same id does not inherit the grant.
+        assert!(!check(&manager, &other));
+
+        // Grants without a root, e.g. from older stores, are not loaded.
+        let mut file = GrantFile::read(&store).unwrap();
+        file.grants.push(PermissionGrant {
+            skill_root: None,
+            ..file.grants[0].clone()
+        });
+        file.write(&store).unwrap();
+        let mut loaded = PermissionManager::with_callback(Arc::new(DenyAllCallback));
+        assert_eq!(loaded.load_grants(&store).unwrap(), 1);
+        assert!(check(&loaded, &trusted));
+        assert!(!check(&loaded, &other));
+    }
+
+    #[test]
+    fn test_runtimes_sharing_a_store_keep_each_others_grants() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().join("grants.json");
+        let root = dir.path().join("pdf");
+        let mut first = PermissionManager::new();
+        let mut second = PermissionManager::new();
+        first.load_grants(&store).unwrap();
+        second.load_grants(&store).unwrap();
+
+        std::thread::scope(|scope| {
+            for (manager, tool) in [(&first, "Bash"), (&second, "Write")] {
+                let root = &root;
+                scope.spawn(move || {
+                    for i in 0..20 {
+                        let tool = format!("{tool}{i}");
+                        manager.grant("pdf", Some(root), &tool, GrantScope::Persistent).unwrap();
+                    }
+                });
+            }
+        });
+        assert_eq!(PermissionManager::new().load_grants(&store).unwrap(), 40);
+
+        // A revocation by one leaves the other's grants in the store.
+        assert!(first.revoke("pdf", "Write0").unwrap());
+        assert_eq!(PermissionManager::new().load_grants(&store).unwrap(), 39);
+    }
+
+    #[test]
+    fn test_time_limited_grants_expire() {
+        struct AllowForCallback;
+        impl PermissionCallback for AllowForCallback {
+            fn request_permission(
+                &self,
+                _request: &PermissionRequest,
+            ) -> Result<PermissionResponse, OpenSkillError> {
+                Ok(PermissionResponse::AllowFor(Duration::from_secs(3600)))
+            }
+        }
+        let check = |manager: &PermissionManager| {
+            manager
+                .decide_permission("pdf", None, "Bash", "Bash", String::new(), RiskLevel::High, HashMap::new())
+                .unwrap()
+                == PermissionDecision::Allowed
+        };
+
+        let manager = PermissionManager::with_callback(Arc::new(AllowForCallback));
+        assert!(check(&manager));
+        assert!(check(&manager));
+        let audit = manager.get_audit_log();
+        assert_eq!(audit.len(), 2);
+        assert!(!audit[0].from_grant);
+        assert_eq!(audit[0].remaining_ttl_ms, Some(3_600_000));
+        assert!(audit[1].from_grant);
+        assert!(audit[1].remaining_ttl_ms.is_some_and(|ms| ms <= 3_600_000 && ms > 3_500_000));
+        let remaining = manager.grants()[0].remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(3600));
+
+        // Once the grant runs out, the callback is asked again.
+        let strict = PermissionManager::with_callback(Arc::new(DenyAllCallback));
+        strict.grant_for("pdf", None, "Bash", GrantScope::Session, Duration::ZERO).unwrap();
+        assert!(strict.grants().is_empty());
+        assert!(!check(&strict));
+        assert_eq!(strict.get_audit_log()[0].remaining_ttl_ms, None);
+
+        // Expired grants are not loaded from the store.
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().join("grants.json");
+        let root = Some(dir.path());
+        let mut saved = PermissionManager::new();
+        saved.load_grants(&store).unwrap();
+        saved.grant_for("pdf", root, "Bash", GrantScope::Persistent, Duration::from_secs(3600)).unwrap();
+        saved.grant_for("pdf", root, "Fetch", GrantScope::Persistent, Duration::ZERO).unwrap();
+        saved.grant("pdf", root, "Write", GrantScope::Persistent).unwrap();
+        let mut loaded = PermissionManager::new();
+        assert_eq!(loaded.load_grants(&store).unwrap(), 2);
+        let mut tools: Vec<_> = loaded.grants().into_iter().map(|g| (g.tool, g.expires_at.is_some())).collect();
+        tools.sort();
+        assert_eq!(tools, [("Bash".to_string(), true), ("Write".to_string(), false)]);
+    }
+
     #[test]
     fn test_is_risky_tool() {
         assert!(!is_risky_tool("Read"));
@@ -2,13 +2,19 @@
 //!
 //! Maps Claude Skills' `allowed-tools` to WASI capability grants.
 
+use crate::active_executions::CancellationHandle;
 use crate::errors::OpenSkillError;
+use crate::events::{ExecutionEventSender, ExecutionEvents};
+use crate::journal::JournalRecord;
 use crate::manifest::WasmConfig;
+use crate::redact::OutputRedactor;
+use crate::script_session::ScriptStdin;
 use std::collections::HashSet;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use url::Url;
 
 /// Permission enforcer for WASM sandbox execution.
+#[derive(Clone)]
 pub struct PermissionEnforcer {
     /// Allowed tools from skill manifest.
     allowed_tools: HashSet<String>,
@@ -16,6 +22,20 @@ pub struct PermissionEnforcer {
     wasm_config: WasmConfig,
     /// Skill root directory.
     skill_root: PathBuf,
+    /// Variables set by the host for this execution.
+    extra_env: Vec<(String, String)>,
+    /// Stops the execution when cancelled.
+    cancellation: CancellationHandle,
+    /// Approved tools that let native scripts spawn subprocesses.
+    process_tools: Vec<String>,
+    /// Receives the execution's output as it is written.
+    events: ExecutionEvents,
+    /// Messages for a script session's stdin, instead of the input JSON.
+    stdin: Option<ScriptStdin>,
+    /// Journal entry of this execution, if the runtime keeps a journal.
+    journal: Option<JournalRecord>,
+    /// Only the workspace is writable, whatever the tools grant.
+    workspace_only_writes: bool,
 }
 
 impl PermissionEnforcer {
@@ -29,6 +49,92 @@ impl PermissionEnforcer {
             allowed_tools: allowed_tools.into_iter().collect(),
             wasm_config,
             skill_root,
+            extra_env: Vec::new(),
+            cancellation: CancellationHandle::default(),
+            process_tools: Vec::new(),
+            events: ExecutionEvents::default(),
+            stdin: None,
+            journal: None,
+            workspace_only_writes: false,
+        }
+    }
+
+    /// Let native scripts spawn subprocesses, as granted by `tools` in the
+    /// capability mapping.
+    pub fn with_process_tools(mut self, tools: Vec<String>) -> Self {
+        self.process_tools = tools;
+        self
+    }
+
+    /// Set environment variables (name, value) for the executed code.
+    pub fn with_env(mut self, env: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.extra_env = env.into_iter().collect();
+        self
+    }
+
+    /// Stop the execution when `cancellation` is cancelled.
+    pub fn with_cancellation(mut self, cancellation: Option<CancellationHandle>) -> Self {
+        self.cancellation = cancellation.unwrap_or_default();
+        self
+    }
+
+    /// Confine writes to the workspace, as a permission callback answered
+    /// with `AllowInWorkspace`: [`Self::filesystem_write_paths`] is empty,
+    /// while workspace writes through host functions still work.
+    pub fn with_workspace_only_writes(mut self, workspace_only: bool) -> Self {
+        self.workspace_only_writes = workspace_only;
+        self
+    }
+
+    /// Whether writes are confined to the workspace.
+    pub fn writes_workspace_only(&self) -> bool {
+        self.workspace_only_writes
+    }
+
+    /// Handle the host cancels the execution through.
+    pub fn cancellation(&self) -> &CancellationHandle {
+        &self.cancellation
+    }
+
+    /// Stream stdout and stderr chunks to `events` while the code runs.
+    pub fn with_events(mut self, events: Option<ExecutionEventSender>) -> Self {
+        self.events = ExecutionEvents::new(events);
+        self
+    }
+
+    /// Redact output chunks with `redactor` before they are streamed.
+    pub(crate) fn with_output_redactor(mut self, redactor: Option<OutputRedactor>) -> Self {
+        self.events = self.events.with_redactor(redactor);
+        self
+    }
+
+    /// Where output chunks go.
+    pub fn events(&self) -> &ExecutionEvents {
+        &self.events
+    }
+
+    /// Stream `stdin` to a native script instead of writing the input JSON.
+    pub fn with_stdin(mut self, stdin: Option<ScriptStdin>) -> Self {
+        self.stdin = stdin;
+        self
+    }
+
+    /// Messages for a script session's stdin, if this execution is one.
+    pub(crate) fn session_stdin(&self) -> Option<std::sync::mpsc::Receiver<Vec<u8>>> {
+        self.stdin.as_ref()?.take()
+    }
+
+    /// Record spawned processes in `journal`.
+    pub fn with_journal(mut self, journal: Option<JournalRecord>) -> Self {
+        self.journal = journal;
+        self
+    }
+
+    /// Note that the execution spawned process `pid`, so a later runtime
+    /// can kill it if this one crashes.
+    pub(crate) fn spawned(&self, pid: u32) {
+        if let Some(journal) = &self.journal {
+            journal.spawned(pid);
         }
     }
 
@@ -58,6 +164,9 @@ impl PermissionEnforcer {
 
     /// Get filesystem write paths for WASI.
     pub fn filesystem_write_paths(&self) -> Vec<PathBuf> {
+        if self.workspace_only_writes {
+            return Vec::new();
+        }
         self.wasm_config
             .filesystem
             .write
@@ -84,11 +193,72 @@ impl PermissionEnforcer {
         }))
     }
 
+    /// Resolve `path` in `workspace` for a host-function read.
+    ///
+    /// Requires a filesystem read grant (`Read`, `Grep`, `Glob`, `LS` or
+    /// `Bash`).
+    #[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+    pub fn workspace_read_path(
+        &self,
+        workspace: Option<&Path>,
+        path: &str,
+    ) -> Result<PathBuf, OpenSkillError> {
+        if self.wasm_config.filesystem.read.is_empty() {
+            return Err(OpenSkillError::PermissionDenied(
+                "reading workspace files requires Read, Grep, Glob, LS or Bash".to_string(),
+            ));
+        }
+        resolve_in_workspace(workspace, path)
+    }
+
+    /// Resolve `path` in `workspace` for a host-function write.
+    ///
+    /// Requires a filesystem write grant (`Write`, `Edit`, `MultiEdit` or
+    /// `Bash`).
+    #[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+    pub fn workspace_write_path(
+        &self,
+        workspace: Option<&Path>,
+        path: &str,
+    ) -> Result<PathBuf, OpenSkillError> {
+        if self.wasm_config.filesystem.write.is_empty() {
+            return Err(OpenSkillError::PermissionDenied(
+                "writing workspace files requires Write, Edit, MultiEdit or Bash".to_string(),
+            ));
+        }
+        resolve_in_workspace(workspace, path)
+    }
+
     /// Get environment variable allowlist for WASI.
     pub fn env_allowlist(&self) -> &[String] {
         &self.wasm_config.env.allow
     }
 
+    /// Environment variables set by the host (not read from the host env).
+    pub fn extra_env(&self) -> &[(String, String)] {
+        &self.extra_env
+    }
+
+    /// Whether any network access is granted.
+    pub fn network_enabled(&self) -> bool {
+        !self.wasm_config.network.allow.is_empty()
+    }
+
+    /// Hosts network access is granted for (`*` = any).
+    pub fn network_hosts(&self) -> &[String] {
+        &self.wasm_config.network.allow
+    }
+
+    /// Check if native scripts may spawn subprocesses.
+    pub fn process_enabled(&self) -> bool {
+        !self.process_tools.is_empty()
+    }
+
+    /// Approved tools that grant subprocesses.
+    pub fn process_tools(&self) -> &[String] {
+        &self.process_tools
+    }
+
     /// Get the timeout in milliseconds.
     #[allow(dead_code)] // May be useful for future API extensions
     pub fn timeout_ms(&self) -> u64 {
@@ -96,7 +266,6 @@ impl PermissionEnforcer {
     }
 
     /// Get the memory limit in MB.
-    #[allow(dead_code)] // May be useful for future API extensions
     pub fn memory_mb(&self) -> u64 {
         self.wasm_config.memory_mb
     }
@@ -149,54 +318,49 @@ impl PermissionEnforcer {
     }
 }
 
-/// Map Claude Skills tool names to WASI capabilities.
-/// 
-/// This provides a mapping from high-level tool names (like "Read", "Write", "Bash")
-/// to WASI capability grants.
-pub fn map_tools_to_capabilities(tools: &[String]) -> WasmConfig {
-    let mut config = WasmConfig::default();
-
-    for tool in tools {
-        match tool.as_str() {
-            "Read" | "Grep" | "Glob" | "LS" => {
-                // Read access to current directory
-                if !config.filesystem.read.contains(&".".to_string()) {
-                    config.filesystem.read.push(".".to_string());
-                }
-            }
-            "Write" | "Edit" | "MultiEdit" => {
-                // Write access to current directory
-                if !config.filesystem.write.contains(&".".to_string()) {
-                    config.filesystem.write.push(".".to_string());
-                }
-            }
-            "Bash" | "Terminal" => {
-                // Full filesystem access for shell commands
-                if !config.filesystem.read.contains(&".".to_string()) {
-                    config.filesystem.read.push(".".to_string());
-                }
-                if !config.filesystem.write.contains(&".".to_string()) {
-                    config.filesystem.write.push(".".to_string());
-                }
-            }
-            "WebSearch" | "Fetch" => {
-                // Network access (all hosts for simplicity)
-                if !config.network.allow.contains(&"*".to_string()) {
-                    config.network.allow.push("*".to_string());
-                }
-            }
-            _ => {
-                // Unknown tool, no special capabilities
-            }
-        }
+/// Join a relative `path` onto `workspace`, refusing anything that would
+/// resolve outside it, including through symlinks.
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+fn resolve_in_workspace(workspace: Option<&Path>, path: &str) -> Result<PathBuf, OpenSkillError> {
+    let workspace = workspace.ok_or_else(|| {
+        OpenSkillError::PermissionDenied("no workspace is configured for this execution".to_string())
+    })?;
+    let relative = Path::new(path);
+    let is_plain = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if path.is_empty() || !is_plain {
+        return Err(OpenSkillError::PermissionDenied(format!(
+            "path must be relative to the workspace: {path}"
+        )));
     }
 
-    config
+    let root = workspace.canonicalize()?;
+    let target = root.join(relative);
+    // The deepest existing ancestor decides where the path really leads.
+    let mut existing = target.as_path();
+    while existing.symlink_metadata().is_err() {
+        existing = existing.parent().unwrap_or(&root);
+    }
+    let resolved = existing.canonicalize().map_err(|_| {
+        OpenSkillError::PermissionDenied(format!("path escapes the workspace: {path}"))
+    })?;
+    if !resolved.starts_with(&root) {
+        return Err(OpenSkillError::PermissionDenied(format!(
+            "path escapes the workspace: {path}"
+        )));
+    }
+    Ok(target)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capability_mapping::CapabilityMapping;
+
+    fn map_tools_to_capabilities(tools: &[String]) -> WasmConfig {
+        CapabilityMapping::default().wasm_config(tools)
+    }
 
     #[test]
     fn test_tool_allowed_empty_list() {
@@ -241,4 +405,34 @@ mod tests {
         let config = map_tools_to_capabilities(&["Write".to_string()]);
         assert!(config.filesystem.write.contains(&".".to_string()));
     }
+
+    #[test]
+    fn test_workspace_paths_need_grants_and_stay_inside() {
+        let ws = tempfile::TempDir::new().unwrap();
+        let root = ws.path().canonicalize().unwrap();
+        let reader = PermissionEnforcer::new(
+            vec!["Read".to_string()],
+            map_tools_to_capabilities(&["Read".to_string()]),
+            PathBuf::from("."),
+        );
+
+        assert_eq!(
+            reader.workspace_read_path(Some(ws.path()), "out/data.json").unwrap(),
+            root.join("out/data.json")
+        );
+        assert!(reader.workspace_write_path(Some(ws.path()), "out/data.json").is_err());
+        assert!(reader.workspace_read_path(None, "data.json").is_err());
+        for escape in ["../secret", "/etc/passwd", "a/../../b", ""] {
+            assert!(
+                reader.workspace_read_path(Some(ws.path()), escape).is_err(),
+                "{escape}"
+            );
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/etc", ws.path().join("link")).unwrap();
+            assert!(reader.workspace_read_path(Some(ws.path()), "link/passwd").is_err());
+        }
+    }
 }
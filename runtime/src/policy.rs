@@ -0,0 +1,206 @@
+//! Host policy file.
+//!
+//! Instead of computing `effective_tools` by hand, hosts can describe which
+//! tools skills get in a TOML file, by default
+//! `~/.config/openskills/policy.toml`, and load it with
+//! [`HostPolicy::from_file`]:
+//!
+//! ```toml
+//! # Defaults for every skill (see `PermissionsConfig`).
+//! trust_skill_allowed_tools = true
+//! fallback = "deny"
+//! deny = ["Terminal"]
+//!
+//! # Personal skills never get network tools.
+//! [location.personal]
+//! deny = ["WebSearch", "Fetch"]
+//!
+//! # Skills not signed by a trusted key may only read.
+//! [trust.unverified]
+//! allowed-tools = ["Read", "Grep", "Glob", "LS"]
+//!
+//! # The pdf skill may also write, even if it does not declare it.
+//! [skill.pdf]
+//! allow = ["Write"]
+//! ```
+//!
+//! Every rule matching a skill applies: its `deny` tools are denied, tools
+//! outside its `allowed-tools` (when given) are denied, and its `allow`
+//! tools are approved like the policy's allow overrides. Denials win.
+//! Trust levels are `verified` (signed by one of the runtime's trusted keys)
+//! and `unverified`; locations are `personal`, `project`, `nested` and
+//! `custom`.
+
+use crate::errors::OpenSkillError;
+use crate::host_policy::{HostPolicy, PermissionsConfig};
+use crate::registry::SkillLocation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// How much the runtime trusts a skill, for `[trust.<level>]` rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustLevel {
+    /// Signed by one of the runtime's trusted keys.
+    Verified,
+    /// Unsigned, invalidly signed, or no trusted keys are configured.
+    Unverified,
+}
+
+/// Tools granted or withheld for the skills a rule matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub(crate) struct ToolRule {
+    /// Only these tools may be granted; `None` does not limit.
+    pub allowed_tools: Option<Vec<String>>,
+    /// Tools approved even if the skill does not declare them.
+    pub allow: Vec<String>,
+    /// Tools never granted.
+    pub deny: Vec<String>,
+}
+
+/// Which skills a [`ToolRule`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Scope {
+    Skill(String),
+    Location(SkillLocation),
+    Trust(TrustLevel),
+}
+
+/// Contents of a policy file; see the module docs.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PolicyFile {
+    #[serde(flatten)]
+    permissions: PermissionsConfig,
+    #[serde(default)]
+    skill: BTreeMap<String, ToolRule>,
+    #[serde(default)]
+    location: BTreeMap<String, ToolRule>,
+    #[serde(default)]
+    trust: BTreeMap<TrustLevel, ToolRule>,
+}
+
+/// `~/.config/openskills/policy.toml`.
+pub fn default_policy_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("openskills").join("policy.toml"))
+}
+
+/// Parse a policy file into a host policy with its scoped rules.
+pub(crate) fn parse(content: &str) -> Result<HostPolicy, OpenSkillError> {
+    let file: PolicyFile = toml::from_str(content)
+        .map_err(|e| OpenSkillError::InvalidConfig(format!("invalid policy file: {e}")))?;
+    let mut rules = Vec::new();
+    for (skill, rule) in file.skill {
+        rules.push((Scope::Skill(skill), rule));
+    }
+    for (location, rule) in file.location {
+        rules.push((Scope::Location(location.parse()?), rule));
+    }
+    for (trust, rule) in file.trust {
+        rules.push((Scope::Trust(trust), rule));
+    }
+    Ok(HostPolicy::from_config(file.permissions).with_rules(rules))
+}
+
+/// [`parse`] on the contents of `path`.
+pub(crate) fn load(path: &Path) -> Result<HostPolicy, OpenSkillError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        OpenSkillError::InvalidConfig(format!("cannot read policy file {}: {e}", path.display()))
+    })?;
+    parse(&content)
+}
+
+/// The tools the rules matching one skill leave it.
+#[derive(Debug, Default)]
+pub(crate) struct Narrowing {
+    pub(crate) allow: HashSet<String>,
+    pub(crate) deny: HashSet<String>,
+    pub(crate) limit: Option<HashSet<String>>,
+}
+
+/// Combine the rules that match a skill.
+pub(crate) fn narrow<'a>(rules: impl IntoIterator<Item = &'a ToolRule>) -> Narrowing {
+    let mut narrowing = Narrowing::default();
+    for rule in rules {
+        narrowing.allow.extend(rule.allow.iter().cloned());
+        narrowing.deny.extend(rule.deny.iter().cloned());
+        if let Some(allowed) = &rule.allowed_tools {
+            let allowed: HashSet<String> = allowed.iter().cloned().collect();
+            narrowing.limit = Some(match narrowing.limit.take() {
+                Some(limit) => limit.intersection(&allowed).cloned().collect(),
+                None => allowed,
+            });
+        }
+    }
+    narrowing
+}
+
+impl Scope {
+    /// Whether this scope covers a skill.
+    pub(crate) fn matches(&self, skill_id: &str, location: &SkillLocation, trust: TrustLevel) -> bool {
+        match self {
+            Scope::Skill(id) => id == skill_id,
+            Scope::Location(l) => l == location,
+            Scope::Trust(t) => *t == trust,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host_policy::{Fallback, ToolDecision};
+
+    const POLICY: &str = r#"
+fallback = "deny"
+deny = ["Terminal"]
+
+[location.personal]
+deny = ["WebSearch"]
+
+[trust.unverified]
+allowed-tools = ["Read", "Grep", "WebSearch"]
+
+[skill.pdf]
+allow = ["Write"]
+"#;
+
+    fn tools(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rules_narrow_the_policy_per_skill() {
+        let policy = parse(POLICY).unwrap();
+        assert_eq!(policy.fallback, Fallback::Deny);
+        let declared = tools(&["Read", "WebSearch", "Bash"]);
+
+        let pdf = policy.for_skill("pdf", &SkillLocation::Project, TrustLevel::Verified);
+        assert_eq!(pdf.resolve_tool("Bash", &declared), ToolDecision::Approved);
+        assert_eq!(pdf.resolve_tool("Write", &[]), ToolDecision::Approved);
+        assert_eq!(pdf.resolve_tool("Terminal", &tools(&["Terminal"])), ToolDecision::Denied);
+
+        let personal = policy.for_skill("notes", &SkillLocation::Personal, TrustLevel::Unverified);
+        assert_eq!(personal.resolve_tool("Read", &declared), ToolDecision::Approved);
+        assert_eq!(personal.resolve_tool("WebSearch", &declared), ToolDecision::Denied);
+        assert_eq!(personal.resolve_tool("Bash", &declared), ToolDecision::Denied);
+        assert!(personal.is_denied("Bash"));
+    }
+
+    #[test]
+    fn test_invalid_policy_files_are_rejected() {
+        assert!(matches!(
+            parse("[location.elsewhere]\ndeny = [\"Bash\"]\n"),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            parse("[trust.trusted]\ndeny = [\"Bash\"]\n"),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+        assert!(matches!(
+            parse("[skill.pdf]\nallowed_tools = [\"Read\"]\n"),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,126 @@
+//! Python skills in the WASM sandbox.
+//!
+//! Two kinds of artifacts run Python under wasmtime instead of a native
+//! interpreter:
+//! - **componentize-py components**, which bundle the interpreter, the
+//!   standard library and the skill's code. They are recognized by the
+//!   `componentize-py` marker the tool leaves in the binary and run like
+//!   any other component.
+//! - **An interpreter component at `wasm/python.wasm`** (a CPython build for
+//!   WASI) next to the skill's `.py` sources. When auto-detection selects a
+//!   Python script and the skill ships this interpreter, the script runs as
+//!   `python /skill/<script> [args]` in the WASM sandbox, with `wasm/lib`
+//!   mounted read-only as the interpreter's `/usr/local/lib`.
+//!
+//! Both get `PYTHONPATH` covering the skill's usual source directories.
+#![cfg_attr(not(feature = "wasm"), allow(dead_code))]
+
+use std::path::{Path, PathBuf};
+
+/// Interpreter component, relative to the skill root.
+pub(crate) const INTERPRETER_MODULE: &str = "wasm/python.wasm";
+
+/// Standard library of the interpreter component, relative to the skill root.
+const STDLIB_DIR: &str = "wasm/lib";
+
+/// Where the standard library is mounted in the guest (`PYTHONHOME/lib`).
+pub(crate) const GUEST_STDLIB_DIR: &str = "/usr/local/lib";
+
+/// Marker componentize-py leaves in the components it builds.
+const COMPONENTIZE_PY_MARKER: &[u8] = b"componentize-py";
+
+/// How a WASM execution runs Python.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PythonWasm {
+    /// Script run by the interpreter component, relative to the skill root;
+    /// `None` for componentize-py components.
+    pub script: Option<String>,
+    /// Arguments after the script.
+    pub args: Vec<String>,
+    /// Host directory mounted at [`GUEST_STDLIB_DIR`].
+    pub stdlib: Option<PathBuf>,
+}
+
+impl PythonWasm {
+    /// Run `script` with the skill's interpreter component, if it ships one.
+    pub(crate) fn interpreter(skill_root: &Path, script: &str, args: &[String]) -> Option<Self> {
+        if !skill_root.join(INTERPRETER_MODULE).is_file() {
+            return None;
+        }
+        let stdlib = skill_root.join(STDLIB_DIR);
+        Some(Self {
+            script: Some(script.replace('\\', "/")),
+            args: args.to_vec(),
+            stdlib: stdlib.is_dir().then_some(stdlib),
+        })
+    }
+
+    /// A self-contained componentize-py component.
+    pub(crate) fn componentize_py() -> Self {
+        Self {
+            script: None,
+            args: Vec::new(),
+            stdlib: None,
+        }
+    }
+
+    /// Guest command line; empty for componentize-py components.
+    pub(crate) fn argv(&self) -> Vec<String> {
+        let Some(script) = &self.script else {
+            return Vec::new();
+        };
+        let mut argv = vec!["python".to_string(), format!("/skill/{script}")];
+        argv.extend(self.args.iter().cloned());
+        argv
+    }
+
+    /// Guest environment for the interpreter.
+    pub(crate) fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("PYTHONPATH", "/skill:/skill/src:/skill/scripts".to_string()),
+            ("PYTHONDONTWRITEBYTECODE", "1".to_string()),
+            ("PYTHONUNBUFFERED", "1".to_string()),
+        ];
+        if self.stdlib.is_some() {
+            env.push(("PYTHONHOME", "/usr/local".to_string()));
+        }
+        env
+    }
+}
+
+/// Whether the component `bytes` was built by componentize-py.
+pub(crate) fn is_componentize_py(bytes: &[u8]) -> bool {
+    bytes
+        .windows(COMPONENTIZE_PY_MARKER.len())
+        .any(|window| window == COMPONENTIZE_PY_MARKER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_interpreter_layout() {
+        let skill = TempDir::new().unwrap();
+        assert_eq!(PythonWasm::interpreter(skill.path(), "main.py", &[]), None);
+
+        fs::create_dir_all(skill.path().join("wasm/lib/python3.12")).unwrap();
+        fs::write(skill.path().join(INTERPRETER_MODULE), b"").unwrap();
+        let python =
+            PythonWasm::interpreter(skill.path(), "scripts/run.py", &["--fast".to_string()]).unwrap();
+        assert_eq!(python.argv(), vec!["python", "/skill/scripts/run.py", "--fast"]);
+        assert_eq!(python.stdlib, Some(skill.path().join("wasm/lib")));
+        assert!(python.env().contains(&("PYTHONHOME", "/usr/local".to_string())));
+    }
+
+    #[test]
+    fn test_componentize_py_detection() {
+        assert!(is_componentize_py(b"\0asm\x0d\0\x01\0processed-by\x0fcomponentize-py"));
+        assert!(!is_componentize_py(b"\0asm\x0d\0\x01\0processed-by\x0dwit-component"));
+        let python = PythonWasm::componentize_py();
+        assert!(python.argv().is_empty());
+        assert!(!python.env().iter().any(|(key, _)| *key == "PYTHONHOME"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Output redaction for host paths and usernames.
+//!
+//! Skill outputs frequently echo absolute paths (workspace files, script
+//! locations, Python tracebacks). When returned verbatim they leak the host
+//! username and directory layout into model context and transcripts.
+//!
+//! [`OutputRedactor`] rewrites known absolute paths to stable placeholders:
+//! - workspace directory → `$SKILL_WORKSPACE`
+//! - skill root → `$SKILL_ROOT`
+//! - home directory (and `/home/<user>`, `/Users/<user>`) → `~`
+//!
+//! Placeholders match the environment variable names injected into the
+//! sandbox, so redacted output still reads naturally to an agent. With a
+//! [`SecretRedactor`] attached, credentials are replaced as well.
+
+use crate::secret_redaction::SecretRedactor;
+use serde_json::Value;
+use std::path::Path;
+
+/// Placeholder used for the workspace directory.
+pub const WORKSPACE_PLACEHOLDER: &str = "$SKILL_WORKSPACE";
+/// Placeholder used for the skill root directory.
+pub const SKILL_ROOT_PLACEHOLDER: &str = "$SKILL_ROOT";
+/// Placeholder used for the user's home directory.
+pub const HOME_PLACEHOLDER: &str = "~";
+
+/// Rewrites absolute host paths in text and JSON values to placeholders.
+#[derive(Debug, Clone, Default)]
+pub struct OutputRedactor {
+    /// (path, placeholder) pairs, kept sorted longest path first so nested
+    /// paths (a workspace under the home directory) win over their parents.
+    rules: Vec<(String, String)>,
+    /// Credentials to replace after the paths.
+    secrets: Option<SecretRedactor>,
+}
+
+impl OutputRedactor {
+    /// Create an empty redactor (no rules).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a redactor for a single execution.
+    ///
+    /// Covers the workspace directory, the skill root, the home directory and
+    /// the current username's conventional home paths.
+    pub fn for_execution(skill_root: &Path, workspace_dir: Option<&Path>) -> Self {
+        let mut redactor = Self::new().with_path(skill_root, SKILL_ROOT_PLACEHOLDER);
+        if let Some(workspace) = workspace_dir {
+            redactor = redactor.with_path(workspace, WORKSPACE_PLACEHOLDER);
+        }
+        if let Some(home) = dirs::home_dir() {
+            redactor = redactor.with_path(home, HOME_PLACEHOLDER);
+        }
+        if let Some(user) = current_username() {
+            redactor = redactor
+                .with_path(format!("/home/{}", user), HOME_PLACEHOLDER)
+                .with_path(format!("/Users/{}", user), HOME_PLACEHOLDER);
+        }
+        redactor
+    }
+
+    /// Also replace credentials found by `secrets`.
+    pub fn with_secrets(mut self, secrets: SecretRedactor) -> Self {
+        self.secrets = Some(secrets);
+        self
+    }
+
+    /// Add a path to redact.
+    ///
+    /// Both the path as given and its canonical form are registered, since
+    /// scripts often print resolved paths (e.g. `/private/var/...` on macOS).
+    pub fn with_path<P: AsRef<Path>>(mut self, path: P, placeholder: &str) -> Self {
+        let path = path.as_ref();
+        self.add_rule(&path.to_string_lossy(), placeholder);
+        if let Ok(canonical) = path.canonicalize() {
+            self.add_rule(&canonical.to_string_lossy(), placeholder);
+        }
+        self
+    }
+
+    fn add_rule(&mut self, path: &str, placeholder: &str) {
+        let path = path.trim_end_matches('/');
+        // Never redact "/" or relative fragments; they would match everywhere.
+        if path.is_empty() || !path.starts_with('/') || path.len() < 2 {
+            return;
+        }
+        if self.rules.iter().any(|(p, _)| p == path) {
+            return;
+        }
+        self.rules.push((path.to_string(), placeholder.to_string()));
+        self.rules.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+    }
+
+    /// Returns true if no rules are configured.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.secrets.is_none()
+    }
+
+    /// Redact all configured paths, then credentials, in a string.
+    ///
+    /// A path only matches on a component boundary, so `/home/al` does not
+    /// rewrite `/home/alice`.
+    pub fn redact(&self, text: &str) -> String {
+        let text = self.redact_paths(text);
+        match &self.secrets {
+            Some(secrets) => secrets.redact(&text),
+            None => text,
+        }
+    }
+
+    fn redact_paths(&self, text: &str) -> String {
+        if self.rules.is_empty() || text.is_empty() {
+            return text.to_string();
+        }
+
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        'outer: while !rest.is_empty() {
+            for (path, placeholder) in &self.rules {
+                if rest.starts_with(path.as_str()) && is_boundary(&rest[path.len()..]) {
+                    out.push_str(placeholder);
+                    rest = &rest[path.len()..];
+                    continue 'outer;
+                }
+            }
+            let ch = rest.chars().next().expect("rest is not empty");
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+        out
+    }
+
+    /// Redact all string values (and object keys) in a JSON value.
+    pub fn redact_value(&self, value: &Value) -> Value {
+        let value = self.redact_path_value(value);
+        match &self.secrets {
+            Some(secrets) => secrets.redact_value(&value),
+            None => value,
+        }
+    }
+
+    fn redact_path_value(&self, value: &Value) -> Value {
+        if self.rules.is_empty() {
+            return value.clone();
+        }
+        match value {
+            Value::String(s) => Value::String(self.redact_paths(s)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|v| self.redact_path_value(v)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| (self.redact_paths(k), self.redact_path_value(v)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// A match ends on a boundary if the next character cannot continue a path component.
+fn is_boundary(rest: &str) -> bool {
+    match rest.chars().next() {
+        None => true,
+        Some(c) => !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'),
+    }
+}
+
+fn current_username() -> Option<String> {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok()
+        .filter(|u| !u.is_empty() && !u.contains('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact_nested_paths_prefers_longest() {
+        let redactor = OutputRedactor::new()
+            .with_path("/home/alice", HOME_PLACEHOLDER)
+            .with_path("/home/alice/.cache/openskills/workspace/s1", WORKSPACE_PLACEHOLDER);
+
+        assert_eq!(
+            redactor.redact("wrote /home/alice/.cache/openskills/workspace/s1/out.txt"),
+            "wrote $SKILL_WORKSPACE/out.txt"
+        );
+        assert_eq!(redactor.redact("cfg at /home/alice/.bashrc"), "cfg at ~/.bashrc");
+    }
+
+    #[test]
+    fn test_redact_respects_component_boundary() {
+        let redactor = OutputRedactor::new().with_path("/home/al", HOME_PLACEHOLDER);
+        assert_eq!(redactor.redact("/home/alice/x"), "/home/alice/x");
+        assert_eq!(redactor.redact("/home/al/x and /home/al"), "~/x and ~");
+    }
+
+    #[test]
+    fn test_redact_ignores_root_and_relative() {
+        let redactor = OutputRedactor::new()
+            .with_path("/", HOME_PLACEHOLDER)
+            .with_path("relative/dir", HOME_PLACEHOLDER);
+        assert!(redactor.is_empty());
+        assert_eq!(redactor.redact("/etc/hosts"), "/etc/hosts");
+    }
+
+    #[test]
+    fn test_redact_value_recurses() {
+        let redactor = OutputRedactor::new().with_path("/srv/skills/demo", SKILL_ROOT_PLACEHOLDER);
+        let value = json!({
+            "file": "/srv/skills/demo/data.csv",
+            "list": ["/srv/skills/demo", 3],
+            "ok": true
+        });
+        assert_eq!(
+            redactor.redact_value(&value),
+            json!({
+                "file": "$SKILL_ROOT/data.csv",
+                "list": ["$SKILL_ROOT", 3],
+                "ok": true
+            })
+        );
+    }
+}
@@ -4,17 +4,28 @@
 //! - `~/.claude/skills/` (personal skills)
 //! - `.claude/skills/` (project skills)
 //! - Nested `.claude/skills/` directories (monorepo support)
+//!
+//! Several versions of a skill can be loaded at once (see `skill_version`);
+//! lookups take `id` or `id@constraint` references.
 
+use crate::entrypoint::check_manifest_entrypoint;
 use crate::errors::OpenSkillError;
 use crate::manifest::SkillManifest;
+#[cfg(feature = "remote-registry")]
+use crate::remote_registry::{RegistryReference, RemoteRegistry, ResolvedSkill};
 use crate::skill_parser::{extract_description_from_body, parse_frontmatter_only, parse_skill_md};
+use crate::skill_version::{parse_version, split_versioned_dir, SkillReference};
+use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// How deep `SkillKind::detect` and `detect_skill_targets` look for executables (e.g. `ooxml/scripts/x.py`).
+const KIND_SCAN_DEPTH: usize = 4;
+
 /// Skill metadata stored at discovery time (progressive disclosure tier 1).
 /// Does NOT include instructions - those are loaded on activation.
 #[derive(Debug, Clone)]
@@ -27,6 +38,39 @@ pub struct SkillMetadata {
     pub manifest: SkillManifest,
     /// Location type (personal, project, nested).
     pub location: SkillLocation,
+    /// Executables the skill ships, classified at discovery.
+    pub kind: SkillKind,
+    /// Parsed `manifest.version` (`0.0.0` when the skill declares none).
+    pub version: Version,
+    /// Project root whose `.claude/skills` (or nested skills) supplied the
+    /// skill; `None` for personal and custom skills.
+    pub project_root: Option<PathBuf>,
+}
+
+impl SkillMetadata {
+    /// Progressive-disclosure view of this skill version.
+    pub fn descriptor(&self) -> SkillDescriptor {
+        let requires_summary = self.manifest.requires.as_ref().map(|r| {
+            let mut parts: Vec<String> = Vec::new();
+            if !r.bins.is_empty() {
+                parts.push(r.bins.join(", "));
+            }
+            if !r.env.is_empty() {
+                parts.push(r.env.join(", "));
+            }
+            parts.join(", ")
+        }).filter(|s| !s.is_empty());
+        SkillDescriptor {
+            id: self.id.clone(),
+            version: self.manifest.version().to_string(),
+            description: self.manifest.description.clone(),
+            location: self.location.clone(),
+            user_invocable: self.manifest.is_user_invocable(),
+            requires_summary,
+            kind: self.kind,
+            project_root: self.project_root.clone(),
+        }
+    }
 }
 
 /// A loaded Claude Skill with full content (for backward compatibility and internal use).
@@ -70,24 +114,237 @@ impl fmt::Display for SkillLocation {
     }
 }
 
+impl std::str::FromStr for SkillLocation {
+    type Err = OpenSkillError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "personal" => Ok(SkillLocation::Personal),
+            "project" => Ok(SkillLocation::Project),
+            "nested" => Ok(SkillLocation::Nested),
+            "custom" => Ok(SkillLocation::Custom),
+            other => Err(OpenSkillError::InvalidConfig(format!(
+                "unknown skill location '{}'",
+                other
+            ))),
+        }
+    }
+}
+
+/// What a skill ships besides its instructions.
+///
+/// Classified at discovery by scanning the skill directory for WASM
+/// modules and Python/shell scripts, so agents can tell up front whether
+/// `run_skill_script` applies or they should simply follow the instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillKind {
+    /// SKILL.md (and reference files) only; there is nothing to execute.
+    InstructionOnly,
+    /// Native Python or shell scripts.
+    Script,
+    /// WASM modules.
+    Wasm,
+    /// Both scripts and WASM modules.
+    Hybrid,
+}
+
+impl SkillKind {
+    /// Scan a skill directory for executables. Hidden directories and
+    /// `node_modules` are skipped.
+    pub fn detect(skill_root: &Path) -> Self {
+        let mut has_wasm = false;
+        let mut has_script = false;
+        for (_, kind) in executable_files(skill_root) {
+            match kind {
+                TargetKind::Wasm => has_wasm = true,
+                TargetKind::Python | TargetKind::Shell => has_script = true,
+            }
+        }
+        match (has_script, has_wasm) {
+            (false, false) => SkillKind::InstructionOnly,
+            (true, false) => SkillKind::Script,
+            (false, true) => SkillKind::Wasm,
+            (true, true) => SkillKind::Hybrid,
+        }
+    }
+}
+
+impl fmt::Display for SkillKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkillKind::InstructionOnly => write!(f, "instruction_only"),
+            SkillKind::Script => write!(f, "script"),
+            SkillKind::Wasm => write!(f, "wasm"),
+            SkillKind::Hybrid => write!(f, "hybrid"),
+        }
+    }
+}
+
+/// Type of an executable artifact inside a skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetKind {
+    Wasm,
+    Python,
+    Shell,
+}
+
+impl TargetKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("wasm") => Some(TargetKind::Wasm),
+            Some("py") => Some(TargetKind::Python),
+            Some("sh" | "bash") => Some(TargetKind::Shell),
+            _ => None,
+        }
+    }
+
+    /// Sandbox `ExecutionTarget::Path` runs this kind of file in.
+    pub fn sandbox(&self) -> TargetSandbox {
+        match self {
+            TargetKind::Wasm => TargetSandbox::Wasm,
+            TargetKind::Python | TargetKind::Shell => TargetSandbox::Native,
+        }
+    }
+}
+
+impl fmt::Display for TargetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetKind::Wasm => write!(f, "wasm"),
+            TargetKind::Python => write!(f, "python"),
+            TargetKind::Shell => write!(f, "shell"),
+        }
+    }
+}
+
+/// Sandbox an execution target runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetSandbox {
+    /// Wasmtime with WASI capabilities.
+    Wasm,
+    /// Native runner (Seatbelt on macOS, Landlock/bubblewrap on Linux).
+    Native,
+}
+
+impl fmt::Display for TargetSandbox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetSandbox::Wasm => write!(f, "wasm"),
+            TargetSandbox::Native => write!(f, "native"),
+        }
+    }
+}
+
+/// An executable artifact that can be passed to `run_skill_target`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillTarget {
+    /// Path relative to the skill root (use with `ExecutionTarget::Path`).
+    pub path: String,
+    pub kind: TargetKind,
+    pub sandbox: TargetSandbox,
+}
+
+/// List the executable artifacts in a skill directory, sorted by path.
+pub fn detect_skill_targets(skill_root: &Path) -> Vec<SkillTarget> {
+    let mut targets: Vec<SkillTarget> = executable_files(skill_root)
+        .filter_map(|(path, kind)| {
+            let relative = path.strip_prefix(skill_root).ok()?;
+            Some(SkillTarget {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                kind,
+                sandbox: kind.sandbox(),
+            })
+        })
+        .collect();
+    targets.sort_by(|a, b| a.path.cmp(&b.path));
+    targets
+}
+
+/// Walk a skill directory for WASM modules and scripts, skipping hidden
+/// directories and `node_modules`.
+fn executable_files(skill_root: &Path) -> impl Iterator<Item = (PathBuf, TargetKind)> {
+    WalkDir::new(skill_root)
+        .max_depth(KIND_SCAN_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !e
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|n| n.starts_with('.') || n == "node_modules")
+        })
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let kind = TargetKind::from_path(e.path())?;
+            Some((e.into_path(), kind))
+        })
+}
+
 /// Skill descriptor for listing (progressive disclosure - only name/description).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkillDescriptor {
     pub id: String,
+    /// Declared version, or `0.0.0` for unversioned skills.
+    pub version: String,
     pub description: String,
     pub location: SkillLocation,
     pub user_invocable: bool,
     /// OpenClaw-compatible: "bins, env" summary from requires (e.g. "git, GITHUB_TOKEN").
     #[serde(skip_serializing_if = "Option::is_none")]
     pub requires_summary: Option<String>,
+    /// Executables the skill ships (see `SkillKind`).
+    pub kind: SkillKind,
+    /// Project root that supplied the skill, for project and nested skills.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_root: Option<PathBuf>,
+}
+
+impl SkillDescriptor {
+    /// Whether the skill has scripts or WASM modules to run.
+    pub fn skill_kind(&self) -> SkillKind {
+        self.kind
+    }
+}
+
+/// One loaded copy of a skill that collides with another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillCandidate {
+    pub namespace: SkillLocation,
+    pub path: PathBuf,
+}
+
+/// Skill version found in more than one directory. Candidates are in
+/// discovery order; the last one is what unqualified references load, the
+/// others stay reachable as `namespace:id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillConflict {
+    pub id: String,
+    pub version: String,
+    pub candidates: Vec<SkillCandidate>,
 }
 
 /// Registry of discovered Claude Skills.
 pub struct SkillRegistry {
     /// Skill metadata only - instructions NOT loaded (progressive disclosure).
-    skills: HashMap<String, SkillMetadata>,
+    /// Every loaded version of each skill id, ordered by version.
+    skills: HashMap<String, BTreeMap<Version, SkillMetadata>>,
+    /// Copies overridden by a later directory with the same id and version,
+    /// in discovery order.
+    shadowed: Vec<SkillMetadata>,
     /// Project root for relative path resolution.
     project_root: Option<PathBuf>,
+    /// Further project roots, each contributing `.claude/skills` and nested
+    /// skills after the primary one.
+    extra_project_roots: Vec<PathBuf>,
     /// Loading errors encountered during discovery (skill_id -> error message)
     loading_errors: HashMap<String, String>,
     /// Warnings from tolerant discovery (name overrides, description fallbacks, etc.)
@@ -99,7 +356,9 @@ impl SkillRegistry {
     pub fn new() -> Self {
         Self {
             skills: HashMap::new(),
+            shadowed: Vec::new(),
             project_root: None,
+            extra_project_roots: Vec::new(),
             loading_errors: HashMap::new(),
             discovery_warnings: Vec::new(),
         }
@@ -110,6 +369,7 @@ impl SkillRegistry {
     /// reflect only the latest run.
     pub fn clear(&mut self) {
         self.skills.clear();
+        self.shadowed.clear();
         self.loading_errors.clear();
         self.discovery_warnings.clear();
     }
@@ -141,50 +401,94 @@ impl SkillRegistry {
         self
     }
 
+    /// Add a project root to discover skills from, after the primary one
+    /// (e.g. an agent serving several repositories). Adding a root twice has
+    /// no effect.
+    pub fn add_project_root<P: AsRef<Path>>(&mut self, root: P) {
+        let root = root.as_ref().to_path_buf();
+        if self.project_root.as_ref() != Some(&root) && !self.extra_project_roots.contains(&root) {
+            self.extra_project_roots.push(root);
+        }
+    }
+
+    /// Every project root, the primary one (`project_root` or the current
+    /// directory) first.
+    pub fn project_roots(&self) -> Vec<PathBuf> {
+        let primary = self
+            .project_root
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."));
+        std::iter::once(primary)
+            .chain(self.extra_project_roots.iter().cloned())
+            .collect()
+    }
+
     /// Check if the registry is empty.
     pub fn is_empty(&self) -> bool {
         self.skills.is_empty()
     }
 
+    /// Number of loaded skills (each id counts once, whatever its versions).
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
     /// Discover and load skills from all standard locations.
     ///
     /// Discovery order (later entries override earlier):
     /// 1. Personal: ~/.claude/skills/
     /// 2. Project: .claude/skills/ (relative to project_root or cwd)
     /// 3. Nested: any .claude/skills/ in subdirectories
+    ///
+    /// Steps 2 and 3 repeat for each root added with [`Self::add_project_root`].
     pub fn discover(&mut self) -> Result<(), OpenSkillError> {
         // 1. Personal skills
         if let Some(home) = dirs::home_dir() {
             let personal_path = home.join(".claude").join("skills");
             if personal_path.exists() {
-                self.scan_directory(&personal_path, SkillLocation::Personal)?;
+                self.scan_directory(&personal_path, SkillLocation::Personal, None)?;
             }
         }
 
-        // 2. Project skills
-        let project_root = self
-            .project_root
-            .clone()
-            .or_else(|| std::env::current_dir().ok())
-            .unwrap_or_else(|| PathBuf::from("."));
+        for project_root in self.project_roots() {
+            // 2. Project skills
+            let project_skills = project_root.join(".claude").join("skills");
+            if project_skills.exists() {
+                self.scan_directory(&project_skills, SkillLocation::Project, Some(&project_root))?;
+            }
 
-        let project_skills = project_root.join(".claude").join("skills");
-        if project_skills.exists() {
-            self.scan_directory(&project_skills, SkillLocation::Project)?;
+            // 3. Nested skills (monorepo support)
+            self.discover_nested(&project_root)?;
         }
 
-        // 3. Nested skills (monorepo support)
-        self.discover_nested(&project_root)?;
-
         Ok(())
     }
 
+    /// The personal and project skill directories, whether or not they
+    /// exist.
+    pub(crate) fn standard_dirs(&self) -> Vec<PathBuf> {
+        dirs::home_dir()
+            .map(|home| home.join(".claude").join("skills"))
+            .into_iter()
+            .chain(
+                self.project_roots()
+                    .into_iter()
+                    .map(|root| root.join(".claude").join("skills")),
+            )
+            .collect()
+    }
+
     /// Discover skills from nested .claude/skills/ directories.
     fn discover_nested(&mut self, root: &Path) -> Result<(), OpenSkillError> {
         for entry in WalkDir::new(root)
             .follow_links(false)
             .into_iter()
             .filter_entry(|e| {
+                // The root itself is always walked, whatever its name.
+                if e.depth() == 0 {
+                    return true;
+                }
                 // Skip hidden directories (except .claude), node_modules, target, etc.
                 let name = e.file_name().to_string_lossy();
                 if name.starts_with('.') && name != ".claude" {
@@ -205,7 +509,7 @@ impl SkillRegistry {
                     // Skip if it's the project root .claude/skills (already scanned)
                     let project_skills = root.join(".claude").join("skills");
                     if path != project_skills {
-                        self.scan_directory(path, SkillLocation::Nested)?;
+                        self.scan_directory(path, SkillLocation::Nested, Some(root))?;
                     }
                 }
             }
@@ -213,8 +517,13 @@ impl SkillRegistry {
         Ok(())
     }
 
-    /// Scan a directory for skills.
-    fn scan_directory(&mut self, dir: &Path, location: SkillLocation) -> Result<(), OpenSkillError> {
+    /// Scan a directory for skills, attributing them to `project_root`.
+    fn scan_directory(
+        &mut self,
+        dir: &Path,
+        location: SkillLocation,
+        project_root: Option<&Path>,
+    ) -> Result<(), OpenSkillError> {
         let entries = match fs::read_dir(dir) {
             Ok(e) => e,
             Err(_) => return Ok(()), // Directory not readable, skip
@@ -231,10 +540,12 @@ impl SkillRegistry {
                 continue;
             }
 
-            let id = match path.file_name().and_then(|v| v.to_str()) {
+            let dir_name = match path.file_name().and_then(|v| v.to_str()) {
                 Some(name) => name.to_string(),
                 None => continue,
             };
+            // `my-skill@2.1.0/` holds version 2.1.0 of `my-skill`.
+            let (id, dir_version) = split_versioned_dir(&dir_name);
 
             // Check for SKILL.md
             let skill_md_path = path.join("SKILL.md");
@@ -243,20 +554,21 @@ impl SkillRegistry {
             }
 
             // Load and parse the skill metadata (frontmatter only)
-            match self.load_skill_metadata(&id, &path, &skill_md_path, location.clone()) {
-                Ok(metadata) => {
-                    self.skills.insert(id, metadata);
-                }
+            match self.load_skill_metadata(id, dir_version, &path, &skill_md_path, location.clone()) {
+                Ok(metadata) => self.insert(SkillMetadata {
+                    project_root: project_root.map(Path::to_path_buf),
+                    ..metadata
+                }),
                 Err(e) => {
                     // Log warning with structured format for better observability
                     eprintln!(
                         "Warning: Failed to load skill '{}' from {}: {}",
-                        id,
+                        dir_name,
                         skill_md_path.display(),
                         e
                     );
                     // Store error in registry for later retrieval
-                    self.loading_errors.insert(id.clone(), e.to_string());
+                    self.loading_errors.insert(dir_name.clone(), e.to_string());
                 }
             }
         }
@@ -264,15 +576,78 @@ impl SkillRegistry {
         Ok(())
     }
 
+    /// Add a skill version; an already loaded copy of the same version is
+    /// replaced (later discovery locations override earlier ones) and kept
+    /// reachable through its namespace.
+    fn insert(&mut self, metadata: SkillMetadata) {
+        let id = metadata.id.clone();
+        let Some(previous) = self
+            .skills
+            .entry(id.clone())
+            .or_default()
+            .insert(metadata.version.clone(), metadata)
+        else {
+            return;
+        };
+        let current = &self.skills[&id][&previous.version];
+        if previous.root == current.root {
+            return;
+        }
+        self.discovery_warnings.push(format!(
+            "Skill '{}': {} ({}) overrides {} ({}); use '{}:{}' for the other copy",
+            id,
+            current.root.display(),
+            current.location,
+            previous.root.display(),
+            previous.location,
+            previous.location,
+            id
+        ));
+        self.shadowed.push(previous);
+    }
+
+    /// Skill versions loaded from more than one directory, sorted by id and
+    /// version.
+    pub fn conflicts(&self) -> Vec<SkillConflict> {
+        let mut conflicts: BTreeMap<(&str, &Version), Vec<SkillCandidate>> = BTreeMap::new();
+        for metadata in &self.shadowed {
+            conflicts
+                .entry((&metadata.id, &metadata.version))
+                .or_default()
+                .push(SkillCandidate {
+                    namespace: metadata.location.clone(),
+                    path: metadata.root.clone(),
+                });
+        }
+        conflicts
+            .into_iter()
+            .filter_map(|((id, version), mut candidates)| {
+                let winner = self.skills.get(id)?.get(version)?;
+                candidates.push(SkillCandidate {
+                    namespace: winner.location.clone(),
+                    path: winner.root.clone(),
+                });
+                Some(SkillConflict {
+                    id: id.to_string(),
+                    version: version.to_string(),
+                    candidates,
+                })
+            })
+            .collect()
+    }
+
     /// Load skill metadata from a SKILL.md file (frontmatter only).
     /// This implements progressive disclosure - only metadata is loaded at discovery time.
     ///
     /// Tolerant discovery: the directory name is the authoritative skill ID.
     /// If the frontmatter `name` differs, it is overwritten with the directory name.
     /// If the frontmatter `description` is missing, the first body line is used.
+    /// `dir_version` (from a `name@version` directory) supplies the version when
+    /// the frontmatter has none and must agree with it otherwise.
     fn load_skill_metadata(
         &mut self,
         id: &str,
+        dir_version: Option<&str>,
         root: &Path,
         skill_md_path: &Path,
         location: SkillLocation,
@@ -304,34 +679,193 @@ impl SkillRegistry {
             }
         }
 
+        if let Some(dir_version) = dir_version {
+            match &manifest.version {
+                Some(declared) if parse_version(declared)? != parse_version(dir_version)? => {
+                    return Err(OpenSkillError::InvalidManifest(format!(
+                        "version '{}' does not match directory suffix '@{}'",
+                        declared, dir_version
+                    )));
+                }
+                Some(_) => {}
+                None => manifest.version = Some(dir_version.to_string()),
+            }
+        }
+
         validate_skill_id(id, &manifest)?;
+        let version = parse_version(manifest.version())?;
+        if manifest.version.is_some() {
+            // Normalize `2.1` / `v2.1.0` so every reference to it reads `2.1.0`.
+            manifest.version = Some(version.to_string());
+        }
+        if let Some(entrypoint) = &manifest.entrypoint {
+            check_manifest_entrypoint(root, entrypoint)?;
+        }
+        if let Some(schema) = &manifest.output_schema {
+            crate::output_schema::check_schema(schema)?;
+        }
+        crate::output_converters::check(&manifest.output_converters)?;
+        crate::manifest::check_examples(&manifest.examples)?;
+        crate::workspace_import::check_workspace_files(&manifest.workspace_files)?;
 
         Ok(SkillMetadata {
             id: id.to_string(),
             root: root.to_path_buf(),
             manifest,
             location,
+            kind: SkillKind::detect(root),
+            version,
+            project_root: None,
         })
     }
 
     /// Load skills from an explicit directory (for testing or custom paths).
     pub fn scan_explicit<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), OpenSkillError> {
-        self.scan_directory(dir.as_ref(), SkillLocation::Custom)
+        self.scan_directory(dir.as_ref(), SkillLocation::Custom, None)
+    }
+
+    /// Load a built-in skill unpacked in `dir` (see `OpenSkillRuntime::with_builtin_skills`).
+    pub(crate) fn load_builtin(&mut self, dir: &Path) -> Result<(), OpenSkillError> {
+        let id = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| OpenSkillError::InvalidManifest(format!("invalid skill directory: {}", dir.display())))?;
+        let metadata = self.load_skill_metadata(id, None, dir, &dir.join("SKILL.md"), SkillLocation::Custom)?;
+        self.insert(metadata);
+        Ok(())
+    }
+
+    /// Resolve a `registry:name[@version]` reference through `remote`,
+    /// downloading the skill unless it is cached, and load it.
+    #[cfg(feature = "remote-registry")]
+    pub fn resolve_remote(
+        &mut self,
+        remote: &RemoteRegistry,
+        reference: &str,
+    ) -> Result<ResolvedSkill, OpenSkillError> {
+        let resolved = remote.resolve(&RegistryReference::parse(reference)?)?;
+        self.load_resolved(&resolved)?;
+        Ok(resolved)
+    }
+
+    /// Load a skill that was already resolved into the registry cache. The
+    /// resolved version is used unless SKILL.md declares one (OCI tags that
+    /// are not semver, like `latest`, are ignored).
+    #[cfg(feature = "remote-registry")]
+    pub fn load_resolved(&mut self, skill: &ResolvedSkill) -> Result<(), OpenSkillError> {
+        let skill_md_path = skill.path.join("SKILL.md");
+        let version = parse_version(&skill.version).ok().map(|v| v.to_string());
+        let metadata = self.load_skill_metadata(
+            &skill.name,
+            version.as_deref(),
+            &skill.path,
+            &skill_md_path,
+            SkillLocation::Custom,
+        )?;
+        self.insert(metadata);
+        Ok(())
     }
 
-    /// Get skill metadata by ID.
-    pub fn get(&self, id: &str) -> Option<&SkillMetadata> {
-        self.skills.get(id)
+    /// Resolve an `id` or `id@constraint` reference, optionally prefixed with
+    /// a `namespace:` (see [`SkillLocation`]) or `directory:`, to the skill
+    /// version it selects.
+    pub fn resolve(&self, reference: &str) -> Result<&SkillMetadata, OpenSkillError> {
+        let reference = SkillReference::parse(reference)?;
+        if reference.namespace.is_some() || reference.directory.is_some() {
+            return self.resolve_qualified(&reference);
+        }
+        let versions = self
+            .skills
+            .get(&reference.id)
+            .ok_or_else(|| OpenSkillError::SkillNotFound(reference.id.clone()))?;
+        let version = reference.select(versions.keys()).ok_or_else(|| {
+            let available: Vec<String> = versions.keys().map(ToString::to_string).collect();
+            OpenSkillError::SkillNotFound(format!(
+                "{} (available versions: {})",
+                reference,
+                available.join(", ")
+            ))
+        })?;
+        Ok(&versions[version])
+    }
+
+    fn resolve_qualified(&self, reference: &SkillReference) -> Result<&SkillMetadata, OpenSkillError> {
+        // Later copies override earlier ones, as in unqualified lookups.
+        let mut versions: BTreeMap<&Version, &SkillMetadata> = BTreeMap::new();
+        let mut dirs: Vec<&Path> = Vec::new();
+        let active = self.skills.get(&reference.id).into_iter().flat_map(|v| v.values());
+        for metadata in self.shadowed.iter().filter(|m| m.id == reference.id).chain(active) {
+            let in_namespace = reference
+                .namespace
+                .as_ref()
+                .is_none_or(|namespace| &metadata.location == namespace);
+            if in_namespace && reference.in_directory(&metadata.root) {
+                versions.insert(&metadata.version, metadata);
+                let dir = metadata.root.parent().unwrap_or(&metadata.root);
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+        let qualified = SkillReference {
+            constraint: None,
+            ..reference.clone()
+        };
+        if versions.is_empty() {
+            return Err(OpenSkillError::SkillNotFound(qualified.to_string()));
+        }
+        // A directory is meant to pick one copy; say so if it does not.
+        if reference.directory.is_some() && dirs.len() > 1 {
+            let dirs: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+            return Err(OpenSkillError::SkillNotFound(format!(
+                "{} is ambiguous (found in {})",
+                qualified,
+                dirs.join(", ")
+            )));
+        }
+        let version = reference.select(versions.keys().copied()).ok_or_else(|| {
+            let available: Vec<String> = versions.keys().map(ToString::to_string).collect();
+            OpenSkillError::SkillNotFound(format!(
+                "{} (available versions: {})",
+                reference,
+                available.join(", ")
+            ))
+        })?;
+        Ok(versions[version])
+    }
+
+    /// Get skill metadata by `[namespace:]id[@constraint]` reference.
+    pub fn get(&self, reference: &str) -> Option<&SkillMetadata> {
+        self.resolve(reference).ok()
+    }
+
+    /// Get one exact version of a skill (`version` as in its manifest).
+    pub fn get_version(&self, id: &str, version: &str) -> Option<&SkillMetadata> {
+        self.skills.get(id)?.get(&parse_version(version).ok()?)
+    }
+
+    /// Metadata of every loaded skill version.
+    pub fn all_versions(&self) -> impl Iterator<Item = &SkillMetadata> {
+        self.skills.values().flat_map(|versions| versions.values())
+    }
+
+    /// Every loaded version of `id`, lowest first.
+    pub fn versions(&self, id: &str) -> Vec<SkillDescriptor> {
+        self.skills
+            .get(id)
+            .map(|versions| versions.values().map(SkillMetadata::descriptor).collect())
+            .unwrap_or_default()
     }
     
-    /// Load full skill content (including instructions) by ID.
+    /// Load full skill content (including instructions) by `id` or
+    /// `id@constraint` reference.
     /// This is used when a skill is activated (progressive disclosure tier 2).
     ///
-    /// `name` and `description` always match discovery-time normalization (directory ID,
-    /// body-derived description). Other manifest fields come from the fresh full parse.
-    pub fn load_full_skill(&self, id: &str) -> Result<Skill, OpenSkillError> {
-        let metadata = self.skills.get(id)
-            .ok_or_else(|| OpenSkillError::SkillNotFound(id.to_string()))?;
+    /// `name`, `description` and `version` always match discovery-time normalization
+    /// (directory ID, body-derived description, directory version suffix). Other manifest
+    /// fields come from the fresh full parse.
+    pub fn load_full_skill(&self, reference: &str) -> Result<Skill, OpenSkillError> {
+        let metadata = self.resolve(reference)?;
         
         // Lazy load: read and parse full SKILL.md NOW (not at discovery)
         let skill_md_path = metadata.root.join("SKILL.md");
@@ -341,6 +875,7 @@ impl SkillRegistry {
         let mut manifest = parsed.manifest;
         manifest.name = metadata.manifest.name.clone();
         manifest.description = metadata.manifest.description.clone();
+        manifest.version = metadata.manifest.version.clone();
         
         Ok(Skill {
             id: metadata.id.clone(),
@@ -351,36 +886,24 @@ impl SkillRegistry {
         })
     }
 
-    /// List all skills (progressive disclosure - only descriptors).
+    /// List all skills (progressive disclosure - only descriptors), one per
+    /// id at the version a bare reference selects.
     pub fn list(&self) -> Vec<SkillDescriptor> {
-        self.skills
-            .values()
-            .map(|s| {
-                let requires_summary = s.manifest.requires.as_ref().map(|r| {
-                    let mut parts: Vec<String> = Vec::new();
-                    if !r.bins.is_empty() {
-                        parts.push(r.bins.join(", "));
-                    }
-                    if !r.env.is_empty() {
-                        parts.push(r.env.join(", "));
-                    }
-                    parts.join(", ")
-                }).filter(|s| !s.is_empty());
-                SkillDescriptor {
-                    id: s.id.clone(),
-                    description: s.manifest.description.clone(),
-                    location: s.location.clone(),
-                    user_invocable: s.manifest.is_user_invocable(),
-                    requires_summary,
-                }
-            })
-            .collect()
+        self.all().map(SkillMetadata::descriptor).collect()
     }
 
-    /// Get all skill metadata.
-    #[allow(dead_code)] // May be useful for future API extensions
+    /// Get the metadata of every skill id at the version a bare reference
+    /// selects.
     pub fn all(&self) -> impl Iterator<Item = &SkillMetadata> {
-        self.skills.values()
+        let latest = SkillReference {
+            namespace: None,
+            directory: None,
+            id: String::new(),
+            constraint: None,
+        };
+        self.skills
+            .values()
+            .filter_map(move |versions| latest.select(versions.keys()).map(|v| &versions[v]))
     }
 }
 
@@ -412,7 +935,10 @@ mod tests {
         let manifest = SkillManifest {
             name: "my-skill".to_string(),
             description: "A valid skill".to_string(),
+            version: None,
             allowed_tools: None,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
             model: None,
             context: None,
             agent: None,
@@ -423,6 +949,13 @@ mod tests {
             metadata: None,
             requires: None,
             actions: None,
+            sandbox: None,
+            entrypoint: None,
+            writes_skill_root: false,
+            output_schema: None,
+            output_converters: Vec::new(),
+            examples: Vec::new(),
+            workspace_files: Vec::new(),
         };
         assert!(validate_skill_id("my-skill", &manifest).is_ok());
     }
@@ -432,7 +965,10 @@ mod tests {
         let manifest = SkillManifest {
             name: "other-name".to_string(),
             description: "A valid skill".to_string(),
+            version: None,
             allowed_tools: None,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
             model: None,
             context: None,
             agent: None,
@@ -443,6 +979,13 @@ mod tests {
             metadata: None,
             requires: None,
             actions: None,
+            sandbox: None,
+            entrypoint: None,
+            writes_skill_root: false,
+            output_schema: None,
+            output_converters: Vec::new(),
+            examples: Vec::new(),
+            workspace_files: Vec::new(),
         };
         assert!(
             validate_skill_id("my-skill", &manifest).is_ok(),
@@ -468,7 +1011,10 @@ mod tests {
         let manifest = SkillManifest {
             name: "My_Skill".to_string(),
             description: "A valid skill".to_string(),
+            version: None,
             allowed_tools: None,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
             model: None,
             context: None,
             agent: None,
@@ -479,6 +1025,13 @@ mod tests {
             metadata: None,
             requires: None,
             actions: None,
+            sandbox: None,
+            entrypoint: None,
+            writes_skill_root: false,
+            output_schema: None,
+            output_converters: Vec::new(),
+            examples: Vec::new(),
+            workspace_files: Vec::new(),
         };
         assert!(validate_skill_id("My_Skill", &manifest).is_err());
     }
@@ -0,0 +1,594 @@
+//! Skills from a remote HTTP registry.
+//!
+//! A registry is a static `index.json` listing skill tarballs:
+//!
+//! ```json
+//! { "skills": [
+//!     { "name": "my-skill", "version": "1.2.0",
+//!       "url": "my-skill-1.2.0.tar.gz", "sha256": "<hex>" }
+//! ] }
+//! ```
+//!
+//! Relative tarball URLs resolve against the index URL, so a registry can be
+//! any static file server (or a `file://` mirror). A reference
+//! `registry:my-skill@1.2.0` picks that exact version; `registry:my-skill`
+//! picks the highest non-prerelease semver version. Tarballs are gzip'd tar
+//! archives with `SKILL.md` at the top or inside a single top-level
+//! directory.
+//!
+//! [`RemoteRegistry::resolve`] downloads the tarball, checks its SHA-256,
+//! and unpacks it under `~/.cache/openskills/registry/<name>/<version>/`.
+//! Extraction is staged in a hidden directory next to the entry, so a failed
+//! or interrupted download never leaves a partial skill behind. Exact
+//! versions that are already cached resolve without contacting the
+//! registry.
+
+use crate::cache::{CacheKind, CacheManager};
+use crate::errors::OpenSkillError;
+use crate::install::PROVENANCE_FILE;
+use crate::validator::{validate_name, validate_skill_path};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Prefix of registry skill references.
+pub const REGISTRY_PREFIX: &str = "registry:";
+
+/// Largest index or tarball downloaded.
+const MAX_DOWNLOAD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// A `registry:name[@version]` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryReference {
+    /// Skill name.
+    pub name: String,
+    /// Exact version; the latest release if `None`.
+    pub version: Option<String>,
+}
+
+impl RegistryReference {
+    /// Parse `registry:name[@version]`; the prefix is optional.
+    pub fn parse(reference: &str) -> Result<Self, OpenSkillError> {
+        let spec = reference.strip_prefix(REGISTRY_PREFIX).unwrap_or(reference);
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (spec, None),
+        };
+        validate_name(name).map_err(|e| {
+            OpenSkillError::RegistryError(format!("invalid reference '{}': {}", reference, e))
+        })?;
+        if version.is_some_and(|v| v.is_empty() || !is_safe_segment(v)) {
+            return Err(OpenSkillError::RegistryError(format!(
+                "invalid version in reference '{}'",
+                reference
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        })
+    }
+}
+
+/// A registry's `index.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryIndex {
+    /// Every published version of every skill.
+    pub skills: Vec<RegistryEntry>,
+}
+
+/// One published skill version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    /// Skill name, which becomes its ID.
+    pub name: String,
+    /// Version, normally semver.
+    pub version: String,
+    /// Tarball URL, absolute or relative to the index.
+    pub url: String,
+    /// Hex SHA-256 of the tarball.
+    pub sha256: String,
+    /// Short description for listings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A registry skill unpacked in the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedSkill {
+    /// Skill name.
+    pub name: String,
+    /// Version that was selected.
+    pub version: String,
+    /// Skill directory (contains `SKILL.md`); its name is the skill name.
+    pub path: PathBuf,
+    /// True if no download was needed.
+    pub cached: bool,
+    /// Where it came from, as recorded in `openskills.lock`:
+    /// `registry+<index URL>` or `oci:<reference>`.
+    pub source: String,
+}
+
+/// Client for one registry index.
+#[derive(Debug, Clone)]
+pub struct RemoteRegistry {
+    index_url: Url,
+    cache_dir: PathBuf,
+}
+
+impl RemoteRegistry {
+    /// Use the registry whose index is at `index_url` (`http`, `https` or
+    /// `file`), caching skills in the default registry cache.
+    pub fn new(index_url: &str) -> Result<Self, OpenSkillError> {
+        let index_url = Url::parse(index_url).map_err(|e| {
+            OpenSkillError::RegistryError(format!("invalid index URL '{}': {}", index_url, e))
+        })?;
+        if !matches!(index_url.scheme(), "http" | "https" | "file") {
+            return Err(OpenSkillError::RegistryError(format!(
+                "unsupported index URL scheme '{}'",
+                index_url.scheme()
+            )));
+        }
+        let cache_dir = CacheManager::default_dir(CacheKind::Registry)
+            .unwrap_or_else(|| std::env::temp_dir().join("openskills").join("registry"));
+        Ok(Self {
+            index_url,
+            cache_dir,
+        })
+    }
+
+    /// Cache skills under `dir` instead.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = dir.into();
+        self
+    }
+
+    /// URL of the index.
+    pub fn index_url(&self) -> &str {
+        self.index_url.as_str()
+    }
+
+    /// Cache directory.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Download and parse the index.
+    pub fn fetch_index(&self) -> Result<RegistryIndex, OpenSkillError> {
+        let bytes = fetch(&self.index_url)?;
+        serde_json::from_slice(&bytes).map_err(|e| {
+            OpenSkillError::RegistryError(format!("invalid index {}: {}", self.index_url, e))
+        })
+    }
+
+    /// Make the referenced skill available in the cache, downloading and
+    /// verifying it if needed.
+    pub fn resolve(&self, reference: &RegistryReference) -> Result<ResolvedSkill, OpenSkillError> {
+        if let Some(version) = &reference.version {
+            if let Some(path) = cached_skill(&self.cache_dir, &reference.name, version) {
+                return Ok(ResolvedSkill {
+                    name: reference.name.clone(),
+                    version: version.clone(),
+                    path,
+                    cached: true,
+                    source: self.source(),
+                });
+            }
+        }
+
+        let index = self.fetch_index()?;
+        let entry = select(&index, reference)?;
+        if !is_safe_segment(&entry.version) {
+            return Err(OpenSkillError::RegistryError(format!(
+                "invalid version '{}' for '{}' in index",
+                entry.version, entry.name
+            )));
+        }
+        if let Some(path) = cached_skill(&self.cache_dir, &entry.name, &entry.version) {
+            return Ok(ResolvedSkill {
+                name: entry.name.clone(),
+                version: entry.version.clone(),
+                path,
+                cached: true,
+                source: self.source(),
+            });
+        }
+
+        let url = self.index_url.join(&entry.url).map_err(|e| {
+            OpenSkillError::RegistryError(format!("invalid tarball URL '{}': {}", entry.url, e))
+        })?;
+        let tarball = fetch(&url)?;
+        let digest = hex::encode(Sha256::digest(&tarball));
+        if !digest.eq_ignore_ascii_case(entry.sha256.trim()) {
+            return Err(OpenSkillError::RegistryError(format!(
+                "checksum mismatch for {}@{}: expected {}, got {}",
+                entry.name, entry.version, entry.sha256, digest
+            )));
+        }
+        let path = unpack_skill(&self.cache_dir, &entry.name, &entry.version, &tarball)?;
+        Ok(ResolvedSkill {
+            name: entry.name.clone(),
+            version: entry.version.clone(),
+            path,
+            cached: false,
+            source: self.source(),
+        })
+    }
+
+    fn source(&self) -> String {
+        format!("registry+{}", self.index_url)
+    }
+}
+
+/// Skill directory of a cached `name`/`version` under `cache_dir`, if
+/// present.
+pub(crate) fn cached_skill(cache_dir: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    let path = cache_dir.join(name).join(version).join(name);
+    let skill_md = path.join("SKILL.md");
+    if skill_md.is_file() {
+        CacheManager::touch(&skill_md);
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Extract a skill tarball into a staging directory and move the skill into
+/// its cache entry `cache_dir/<name>/<version>/<name>`.
+pub(crate) fn unpack_skill(
+    cache_dir: &Path,
+    name: &str,
+    version: &str,
+    tarball: &[u8],
+) -> Result<PathBuf, OpenSkillError> {
+    let parent = cache_dir.join(name);
+    fs::create_dir_all(&parent)?;
+    let staging = Staging {
+        path: parent.join(format!(".fetch-{:08x}", rand::random::<u32>())),
+    };
+    let unpacked = staging.path.join("unpacked");
+    fs::create_dir_all(&unpacked)?;
+    tar::Archive::new(flate2::read::GzDecoder::new(tarball))
+        .unpack(&unpacked)
+        .map_err(|e| {
+            OpenSkillError::RegistryError(format!("cannot unpack {}@{}: {}", name, version, e))
+        })?;
+
+    let skill_src = skill_root(&unpacked).ok_or_else(|| {
+        OpenSkillError::RegistryError(format!(
+            "{}@{}: no SKILL.md at the top of the tarball",
+            name, version
+        ))
+    })?;
+    let staged = staging.path.join("entry");
+    fs::create_dir_all(&staged)?;
+    let skill_dir = staged.join(name);
+    fs::rename(&skill_src, &skill_dir)?;
+
+    let validation = validate_skill_path(&skill_dir);
+    if !validation.errors.is_empty() {
+        return Err(OpenSkillError::RegistryError(format!(
+            "{}@{} is not a valid skill: {}",
+            name,
+            version,
+            validation.errors.join("; ")
+        )));
+    }
+
+    let target = parent.join(version);
+    if let Err(e) = fs::rename(&staged, &target) {
+        // Another process may have cached the same version meanwhile.
+        if !target.join(name).join("SKILL.md").is_file() {
+            return Err(e.into());
+        }
+    }
+    Ok(target.join(name))
+}
+
+/// Pack the skill at `skill_dir` as a registry tarball: a gzip'd tar with
+/// the skill under a top-level directory named after it. Entries are
+/// sorted and carry no timestamps or owners, so packing the same tree
+/// twice gives the same bytes (and checksum). `.git` and install
+/// provenance are left out.
+pub fn package_skill(skill_dir: &Path) -> Result<Vec<u8>, OpenSkillError> {
+    let validation = validate_skill_path(skill_dir);
+    if !validation.errors.is_empty() {
+        return Err(OpenSkillError::RegistryError(format!(
+            "{} is not a valid skill: {}",
+            skill_dir.display(),
+            validation.errors.join("; ")
+        )));
+    }
+    let skill_dir = skill_dir.canonicalize()?;
+    let name = skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let walker = walkdir::WalkDir::new(&skill_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 || (e.file_name() != ".git" && e.file_name() != PROVENANCE_FILE)
+        });
+    for entry in walker {
+        let entry = entry.map_err(|e| OpenSkillError::Io(e.into()))?;
+        let rel = entry.path().strip_prefix(&skill_dir).unwrap_or(entry.path());
+        let path = Path::new(&name).join(rel);
+        let metadata = fs::metadata(entry.path())?;
+        let mut header = tar::Header::new_gnu();
+        header.set_mtime(0);
+        header.set_uid(0);
+        header.set_gid(0);
+        if metadata.is_dir() {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_mode(0o755);
+            header.set_size(0);
+            builder.append_data(&mut header, &path, std::io::empty())?;
+        } else {
+            header.set_mode(if is_executable(&metadata) { 0o755 } else { 0o644 });
+            header.set_size(metadata.len());
+            builder.append_data(&mut header, &path, fs::File::open(entry.path())?)?;
+        }
+    }
+    Ok(builder.into_inner()?.finish()?)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// Pick the index entry a reference asks for.
+fn select<'a>(
+    index: &'a RegistryIndex,
+    reference: &RegistryReference,
+) -> Result<&'a RegistryEntry, OpenSkillError> {
+    let mut versions = index.skills.iter().filter(|e| e.name == reference.name);
+    let selected = match &reference.version {
+        Some(version) => versions.find(|e| e.version == *version),
+        None => versions
+            .filter_map(|e| Some((semver::Version::parse(&e.version).ok()?, e)))
+            .filter(|(v, _)| v.pre.is_empty())
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, e)| e),
+    };
+    selected.ok_or_else(|| {
+        OpenSkillError::RegistryError(match &reference.version {
+            Some(version) => format!("{}@{} not found in registry", reference.name, version),
+            None => format!("{} not found in registry", reference.name),
+        })
+    })
+}
+
+/// `dir` if it holds `SKILL.md`, else its only subdirectory if that does.
+fn skill_root(dir: &Path) -> Option<PathBuf> {
+    if dir.join("SKILL.md").is_file() {
+        return Some(dir.to_path_buf());
+    }
+    let mut children = fs::read_dir(dir).ok()?.filter_map(|e| e.ok());
+    let only = children.next()?.path();
+    if children.next().is_some() || !only.join("SKILL.md").is_file() {
+        return None;
+    }
+    Some(only)
+}
+
+/// Whether `segment` can be used as a cache path component.
+fn is_safe_segment(segment: &str) -> bool {
+    !segment.starts_with('.')
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'))
+}
+
+/// Read `url` into memory.
+fn fetch(url: &Url) -> Result<Vec<u8>, OpenSkillError> {
+    let fail = |e: &dyn std::fmt::Display| {
+        OpenSkillError::RegistryError(format!("cannot fetch {}: {}", url, e))
+    };
+    let reader: Box<dyn Read> = match url.scheme() {
+        "file" => {
+            let path = url.to_file_path().map_err(|_| fail(&"not a local path"))?;
+            Box::new(fs::File::open(path).map_err(|e| fail(&e))?)
+        }
+        "http" | "https" => ureq::get(url.as_str())
+            .call()
+            .map_err(|e| fail(&e))?
+            .into_reader(),
+        other => return Err(fail(&format!("unsupported scheme '{}'", other))),
+    };
+    read_limited(reader, url.as_str())
+}
+
+/// Read a download of at most [`MAX_DOWNLOAD_BYTES`] into memory.
+pub(crate) fn read_limited(reader: impl Read, what: &str) -> Result<Vec<u8>, OpenSkillError> {
+    let mut bytes = Vec::new();
+    reader
+        .take(MAX_DOWNLOAD_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| OpenSkillError::RegistryError(format!("cannot fetch {}: {}", what, e)))?;
+    if bytes.len() as u64 > MAX_DOWNLOAD_BYTES {
+        return Err(OpenSkillError::RegistryError(format!(
+            "cannot fetch {}: larger than {} bytes",
+            what, MAX_DOWNLOAD_BYTES
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Staging directory removed on drop.
+struct Staging {
+    path: PathBuf,
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tarball(prefix: &str, skill_md: &str) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(skill_md.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, format!("{}SKILL.md", prefix), skill_md.as_bytes())
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    /// A `file://` registry publishing `(version, tarball)` pairs of `demo`.
+    fn registry(dir: &Path, versions: &[(&str, Vec<u8>)]) -> RemoteRegistry {
+        let served = dir.join("served");
+        fs::create_dir_all(&served).unwrap();
+        let mut index = RegistryIndex::default();
+        for (version, bytes) in versions {
+            let file = format!("demo-{}.tar.gz", version);
+            fs::write(served.join(&file), bytes).unwrap();
+            index.skills.push(RegistryEntry {
+                name: "demo".to_string(),
+                version: version.to_string(),
+                url: file,
+                sha256: hex::encode(Sha256::digest(bytes)),
+                description: None,
+            });
+        }
+        fs::write(served.join("index.json"), serde_json::to_vec(&index).unwrap()).unwrap();
+        let url = Url::from_file_path(served.join("index.json")).unwrap();
+        RemoteRegistry::new(url.as_str())
+            .unwrap()
+            .with_cache_dir(dir.join("cache"))
+    }
+
+    const SKILL_MD: &str = "---\nname: demo\ndescription: Demo skill\n---\nSay hi.\n";
+
+    #[test]
+    fn test_package_skill_is_reproducible_and_resolvable() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = dir.path().join("demo");
+        fs::create_dir_all(skill.join(".git")).unwrap();
+        fs::write(skill.join("SKILL.md"), SKILL_MD).unwrap();
+        fs::write(skill.join(".git/HEAD"), "ref").unwrap();
+        fs::write(skill.join(PROVENANCE_FILE), "{}").unwrap();
+
+        let packed = package_skill(&skill).unwrap();
+        assert_eq!(packed, package_skill(&skill).unwrap());
+        let unpacked = unpack_skill(&dir.path().join("cache"), "demo", "0.1.0", &packed).unwrap();
+        assert_eq!(fs::read_to_string(unpacked.join("SKILL.md")).unwrap(), SKILL_MD);
+        assert!(!unpacked.join(".git").exists());
+        assert!(!unpacked.join(PROVENANCE_FILE).exists());
+        assert!(cached_skill(&dir.path().join("cache"), "demo", "0.1.0").is_some());
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        assert_eq!(
+            RegistryReference::parse("registry:my-skill@1.2.0").unwrap(),
+            RegistryReference {
+                name: "my-skill".to_string(),
+                version: Some("1.2.0".to_string()),
+            }
+        );
+        assert_eq!(RegistryReference::parse("my-skill").unwrap().version, None);
+        assert!(RegistryReference::parse("registry:../x").is_err());
+        assert!(RegistryReference::parse("registry:x@../1").is_err());
+        assert!(RegistryReference::parse("registry:x@").is_err());
+    }
+
+    #[test]
+    fn test_resolve_downloads_verifies_and_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(
+            dir.path(),
+            &[
+                ("1.2.0", tarball("", SKILL_MD)),
+                ("1.10.0", tarball("demo-1.10.0/", SKILL_MD)),
+                ("2.0.0-beta.1", tarball("", SKILL_MD)),
+            ],
+        );
+
+        let latest = registry
+            .resolve(&RegistryReference::parse("registry:demo").unwrap())
+            .unwrap();
+        assert_eq!(latest.version, "1.10.0");
+        assert!(!latest.cached);
+        assert_eq!(latest.path, dir.path().join("cache/demo/1.10.0/demo"));
+        assert!(latest.path.join("SKILL.md").is_file());
+
+        let exact = RegistryReference::parse("registry:demo@1.2.0").unwrap();
+        assert!(!registry.resolve(&exact).unwrap().cached);
+        // Cached exact versions do not need the registry anymore.
+        fs::remove_dir_all(dir.path().join("served")).unwrap();
+        let again = registry.resolve(&exact).unwrap();
+        assert!(again.cached);
+        assert_eq!(again.path, dir.path().join("cache/demo/1.2.0/demo"));
+
+        let leftovers: Vec<_> = fs::read_dir(dir.path().join("cache/demo"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|n| n.starts_with('.'))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    }
+
+    #[test]
+    fn test_runtime_keeps_registry_skills_across_discovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(dir.path(), &[("1.0.0", tarball("", SKILL_MD))]);
+        let index_url = registry.index_url().to_string();
+        let empty = dir.path().join("skills");
+        fs::create_dir_all(&empty).unwrap();
+        let mut runtime = crate::OpenSkillRuntime::from_directory(&empty).with_remote_registry(registry);
+
+        let skill = runtime.load_registry_skill("registry:demo@1.0.0").unwrap();
+        assert_eq!(skill.id, "demo");
+        assert_eq!(skill.description, "Demo skill");
+        let ids: Vec<_> = runtime.discover_skills().unwrap().into_iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec!["demo"]);
+        assert!(runtime.activate_skill("demo").unwrap().instructions.contains("Say hi."));
+
+        let locked = runtime.generate_lockfile().unwrap();
+        assert_eq!(locked.skills[0].source, format!("registry+{}", index_url));
+    }
+
+    #[test]
+    fn test_resolve_rejects_bad_checksum_and_missing_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = registry(dir.path(), &[("1.0.0", tarball("", SKILL_MD))]);
+        fs::write(
+            dir.path().join("served/demo-1.0.0.tar.gz"),
+            tarball("", "---\nname: demo\ndescription: Tampered\n---\n"),
+        )
+        .unwrap();
+
+        let err = registry
+            .resolve(&RegistryReference::parse("demo@1.0.0").unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"), "{}", err);
+        assert!(!dir.path().join("cache/demo/1.0.0").exists());
+
+        let err = registry
+            .resolve(&RegistryReference::parse("demo@3.0.0").unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("demo@3.0.0 not found"), "{}", err);
+    }
+}
@@ -0,0 +1,330 @@
+//! CPU and memory limits for Linux native execution.
+//!
+//! Each limited execution gets a transient cgroup v2 with `memory.max` /
+//! `cpu.max` set, created next to the runtime's own cgroup: cgroup v2 does
+//! not let a cgroup that holds processes (the runtime's does) hand
+//! controllers to children. Only a runtime at the root of the hierarchy
+//! gets child groups. The child process joins the group in `pre_exec`,
+//! before the sandbox is applied. When no cgroup can be created (cgroup v1
+//! hosts, controllers not delegated), memory falls back to `RLIMIT_AS` and
+//! the CPU quota is not enforced; the fallback is reported on stderr once
+//! per process.
+
+use crate::audit::ExecutionStatus;
+use crate::executor::ExecutionArtifacts;
+use crate::native_runner::ResourceLimits;
+use std::fs;
+use std::os::fd::{AsRawFd, OwnedFd, RawFd};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// `cpu.max` period in microseconds (the kernel default).
+const CPU_PERIOD_US: u64 = 100_000;
+
+/// Stderr fragments interpreters print when an allocation fails under
+/// `RLIMIT_AS` (Python `MemoryError`, bash/libc, C++ `bad_alloc`).
+const ALLOCATION_FAILURES: &[&str] = &[
+    "memoryerror",
+    "cannot allocate memory",
+    "out of memory",
+    "bad_alloc",
+];
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Set once the rlimit fallback has been reported.
+static FALLBACK_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Limits for one native execution; owns the transient cgroup, which is
+/// removed on drop.
+pub(crate) struct LimitGuard {
+    cgroup: Option<TransientCgroup>,
+    /// `RLIMIT_AS` in bytes, used when no cgroup could be created.
+    address_space: Option<u64>,
+}
+
+/// What the child applies to itself in `pre_exec`.
+#[derive(Clone, Copy)]
+pub(crate) struct ChildLimits {
+    cgroup_procs: Option<RawFd>,
+    address_space: Option<u64>,
+}
+
+struct TransientCgroup {
+    dir: PathBuf,
+    procs: OwnedFd,
+}
+
+impl LimitGuard {
+    pub(crate) fn new(limits: &ResourceLimits) -> Self {
+        if limits.is_unlimited() {
+            return Self {
+                cgroup: None,
+                address_space: None,
+            };
+        }
+        let cgroup = TransientCgroup::create(limits);
+        let address_space = match &cgroup {
+            Ok(_) => None,
+            Err(reason) => {
+                if !FALLBACK_REPORTED.swap(true, Ordering::Relaxed) {
+                    eprintln!(
+                        "Warning: no cgroup for resource limits ({}); memory is limited with RLIMIT_AS and the CPU quota is not enforced",
+                        reason
+                    );
+                }
+                limits.memory_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+            }
+        };
+        let cgroup = cgroup.ok();
+        Self {
+            cgroup,
+            address_space,
+        }
+    }
+
+    /// Values the `pre_exec` hook needs; must not outlive `self`.
+    pub(crate) fn child_limits(&self) -> ChildLimits {
+        ChildLimits {
+            cgroup_procs: self.cgroup.as_ref().map(|cg| cg.procs.as_raw_fd()),
+            address_space: self.address_space,
+        }
+    }
+
+    /// Report an execution that ran out of memory as `Failed("oom")`.
+    pub(crate) fn finish(&self, mut artifacts: ExecutionArtifacts) -> ExecutionArtifacts {
+        let oom = match &self.cgroup {
+            Some(cgroup) => cgroup.oom_killed(),
+            None => {
+                self.address_space.is_some()
+                    && matches!(artifacts.exit_status, ExecutionStatus::Failed(_))
+                    && is_allocation_failure(&artifacts.stderr)
+            }
+        };
+        if oom {
+            artifacts.exit_status = ExecutionStatus::Failed("oom".to_string());
+            artifacts.output = serde_json::json!({ "status": "error", "error": "oom" });
+        }
+        artifacts
+    }
+}
+
+impl ChildLimits {
+    /// Join the cgroup and set rlimits (called in `pre_exec`, so only
+    /// async-signal-safe calls).
+    pub(crate) fn apply(&self) -> std::io::Result<()> {
+        if let Some(fd) = self.cgroup_procs {
+            // Writing "0" to cgroup.procs moves the calling process.
+            // SAFETY: fd is an open cgroup.procs descriptor owned by the parent.
+            if unsafe { libc::write(fd, b"0".as_ptr().cast(), 1) } < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        if let Some(bytes) = self.address_space {
+            let limit = libc::rlimit {
+                rlim_cur: bytes as libc::rlim_t,
+                rlim_max: bytes as libc::rlim_t,
+            };
+            // SAFETY: setrlimit with a valid rlimit struct.
+            if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TransientCgroup {
+    /// Create the cgroup, or say why there is none.
+    fn create(limits: &ResourceLimits) -> Result<Self, String> {
+        let parent = transient_cgroup_parent().ok_or("cgroup v2 is not mounted")?;
+        let mut controllers = Vec::new();
+        if limits.memory_mb.is_some() {
+            controllers.push("memory");
+        }
+        if limits.cpu_quota.is_some() {
+            controllers.push("cpu");
+        }
+        if !enable_controllers(&parent, &controllers) {
+            return Err(format!(
+                "cannot enable {} for children of {}",
+                controllers.join(" and "),
+                parent.display()
+            ));
+        }
+
+        let dir = parent.join(format!(
+            "openskills-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+        let cgroup = Self::configure(&dir, limits);
+        if cgroup.is_none() {
+            let _ = fs::remove_dir(&dir);
+        }
+        cgroup.ok_or_else(|| format!("cannot configure {}", dir.display()))
+    }
+
+    fn configure(dir: &Path, limits: &ResourceLimits) -> Option<Self> {
+        if let Some(mb) = limits.memory_mb {
+            fs::write(dir.join("memory.max"), mb.saturating_mul(1024 * 1024).to_string()).ok()?;
+            // Without swap the limit is hard; not every kernel has swap accounting.
+            let _ = fs::write(dir.join("memory.swap.max"), "0");
+        }
+        if let Some(quota) = limits.cpu_quota {
+            fs::write(dir.join("cpu.max"), cpu_max(quota)).ok()?;
+        }
+        let procs = fs::OpenOptions::new()
+            .write(true)
+            .open(dir.join("cgroup.procs"))
+            .ok()?;
+        Some(Self {
+            dir: dir.to_path_buf(),
+            procs: procs.into(),
+        })
+    }
+
+    fn oom_killed(&self) -> bool {
+        fs::read_to_string(self.dir.join("memory.events"))
+            .map(|events| oom_kill_count(&events) > 0)
+            .unwrap_or(false)
+    }
+}
+
+impl Drop for TransientCgroup {
+    fn drop(&mut self) {
+        // Kill anything the script left behind, then remove the group.
+        let _ = fs::write(self.dir.join("cgroup.kill"), "1");
+        for _ in 0..50 {
+            if fs::remove_dir(&self.dir).is_ok() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+}
+
+/// Kill and remove the transient cgroups a crashed runtime with process ID
+/// `runtime_pid` left next to ours (see `ExecutionJournal`).
+pub(crate) fn remove_orphaned_cgroups(runtime_pid: u32) {
+    let Some(parent) = transient_cgroup_parent() else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&parent) else {
+        return;
+    };
+    let prefix = format!("openskills-{runtime_pid}-");
+    for entry in entries.flatten() {
+        if !entry.file_name().to_string_lossy().starts_with(&prefix) {
+            continue;
+        }
+        let dir = entry.path();
+        let _ = fs::write(dir.join("cgroup.kill"), "1");
+        for _ in 0..50 {
+            if fs::remove_dir(&dir).is_ok() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+}
+
+/// Directory the transient cgroups go in: the parent of the runtime's own
+/// cgroup v2, or that cgroup itself at the root of the hierarchy.
+fn transient_cgroup_parent() -> Option<PathBuf> {
+    let mounts = fs::read_to_string("/proc/self/mounts").ok()?;
+    let root = cgroup2_mount(&mounts)?;
+    let membership = fs::read_to_string("/proc/self/cgroup").ok()?;
+    let relative = Path::new(unified_path(&membership)?.trim_start_matches('/'));
+    Some(root.join(relative.parent().unwrap_or(relative)))
+}
+
+/// Make `controllers` available to children of `parent`. Fails when they
+/// are not delegated to us or `parent` holds processes (the cgroup v2
+/// "no internal processes" rule), which is when callers fall back to rlimits.
+fn enable_controllers(parent: &Path, controllers: &[&str]) -> bool {
+    let subtree = parent.join("cgroup.subtree_control");
+    let enabled = |wanted: &[&str]| {
+        fs::read_to_string(&subtree)
+            .map(|current| {
+                wanted
+                    .iter()
+                    .all(|c| current.split_whitespace().any(|e| e == *c))
+            })
+            .unwrap_or(false)
+    };
+    if enabled(controllers) {
+        return true;
+    }
+    let request = controllers
+        .iter()
+        .map(|c| format!("+{c}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    fs::write(&subtree, request).is_ok() && enabled(controllers)
+}
+
+fn cgroup2_mount(mounts: &str) -> Option<PathBuf> {
+    mounts.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let (_, mount_point, fs_type) = (fields.next()?, fields.next()?, fields.next()?);
+        (fs_type == "cgroup2").then(|| PathBuf::from(mount_point))
+    })
+}
+
+fn unified_path(membership: &str) -> Option<&str> {
+    membership.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+fn cpu_max(quota: f64) -> String {
+    // The kernel rejects quotas below 1ms.
+    let quota_us = ((quota * CPU_PERIOD_US as f64) as u64).max(1_000);
+    format!("{quota_us} {CPU_PERIOD_US}")
+}
+
+fn oom_kill_count(events: &str) -> u64 {
+    events
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))
+        .and_then(|n| n.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn is_allocation_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_ascii_lowercase();
+    ALLOCATION_FAILURES.iter().any(|f| stderr.contains(f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cgroup_files() {
+        let mounts = "proc /proc proc rw 0 0\ncgroup2 /sys/fs/cgroup cgroup2 rw,nosuid 0 0\n";
+        assert_eq!(cgroup2_mount(mounts), Some(PathBuf::from("/sys/fs/cgroup")));
+        assert_eq!(cgroup2_mount("tmpfs /sys/fs/cgroup tmpfs rw 0 0\n"), None);
+
+        let membership = "4:memory:/legacy\n0::/user.slice/app.scope\n";
+        assert_eq!(unified_path(membership), Some("/user.slice/app.scope"));
+
+        let events = "low 0\nhigh 0\nmax 3\noom 1\noom_kill 1\noom_group_kill 0\n";
+        assert_eq!(oom_kill_count(events), 1);
+        assert_eq!(oom_kill_count("oom 0\n"), 0);
+    }
+
+    #[test]
+    fn test_cpu_max() {
+        assert_eq!(cpu_max(0.5), "50000 100000");
+        assert_eq!(cpu_max(2.0), "200000 100000");
+        assert_eq!(cpu_max(0.0), "1000 100000");
+    }
+
+    #[test]
+    fn test_allocation_failure_detection() {
+        assert!(is_allocation_failure("Traceback ...\nMemoryError\n"));
+        assert!(is_allocation_failure("bash: xmalloc: cannot allocate memory"));
+        assert!(!is_allocation_failure("ValueError: bad input"));
+    }
+}
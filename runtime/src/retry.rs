@@ -0,0 +1,162 @@
+//! Retrying executions that fail transiently.
+//!
+//! A skill that calls a flaky API can time out or exit non-zero for reasons
+//! that go away on the next try. With `ExecutionOptions::retry` set, the
+//! runtime runs it again after a backoff delay, up to `max_attempts` times.
+//! Every attempt is audited on its own, with `AuditRecord::attempt` set.
+
+use crate::audit::ExecutionStatus;
+use crate::errors::OpenSkillError;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// When and how often to retry an execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first (values below 1 count as 1).
+    pub max_attempts: u32,
+    /// Delay before each retry.
+    pub backoff: Backoff,
+    /// Outcomes that are retried; anything else is returned as is.
+    pub retry_on: Vec<RetryOn>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 500 ms then 1 s apart, on timeouts and failures.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Backoff::Exponential {
+                initial: Duration::from_millis(500),
+                max: Duration::from_secs(30),
+            },
+            retry_on: vec![RetryOn::Timeout, RetryOn::Failure],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff from `backoff_ms`, capped at `max_backoff_ms`
+    /// (default 30 s), retrying on the named outcomes (`timeout`,
+    /// `failure`, `error`; empty = timeouts and failures). For bindings and
+    /// configuration files.
+    pub fn from_millis(
+        max_attempts: u32,
+        backoff_ms: u64,
+        max_backoff_ms: Option<u64>,
+        retry_on: &[String],
+    ) -> Result<Self, OpenSkillError> {
+        let default = Self::default();
+        let retry_on = if retry_on.is_empty() {
+            default.retry_on
+        } else {
+            retry_on
+                .iter()
+                .map(|on| on.parse())
+                .collect::<Result<_, _>>()?
+        };
+        Ok(Self {
+            max_attempts,
+            backoff: Backoff::Exponential {
+                initial: Duration::from_millis(backoff_ms),
+                max: max_backoff_ms.map_or(Duration::from_secs(30), Duration::from_millis),
+            },
+            retry_on,
+        })
+    }
+
+    /// Whether an attempt that ended with `outcome` should be retried.
+    /// Cancelled executions never are.
+    pub(crate) fn retries(&self, outcome: &Result<ExecutionStatus, &OpenSkillError>) -> bool {
+        self.retry_on.iter().any(|on| match (on, outcome) {
+            (RetryOn::Timeout, Ok(ExecutionStatus::Timeout)) => true,
+            (RetryOn::Failure, Ok(ExecutionStatus::Failed(reason))) => reason != "cancelled",
+            (RetryOn::Error, Err(_)) => true,
+            _ => false,
+        })
+    }
+}
+
+/// Delay between attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay before every retry.
+    Fixed(Duration),
+    /// `initial`, doubled before each further retry, capped at `max`.
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    /// Delay before retry number `retry` (1 = the second attempt).
+    pub fn delay(&self, retry: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => {
+                let factor = 1u32
+                    .checked_shl(retry.saturating_sub(1))
+                    .unwrap_or(u32::MAX);
+                initial.saturating_mul(factor).min(max)
+            }
+        }
+    }
+}
+
+/// An outcome [`RetryPolicy`] can retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryOn {
+    /// The execution timed out.
+    Timeout,
+    /// The script or component failed (`ExecutionStatus::Failed`), e.g. a
+    /// non-zero exit.
+    Failure,
+    /// The execution could not be run, e.g. the interpreter failed to
+    /// start. Errors resolving the skill or its permissions are not retried.
+    Error,
+}
+
+impl FromStr for RetryOn {
+    type Err = OpenSkillError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "timeout" => Ok(Self::Timeout),
+            "failure" | "failed" => Ok(Self::Failure),
+            "error" => Ok(Self::Error),
+            other => Err(OpenSkillError::InvalidConfig(format!(
+                "unknown retry condition: {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff_is_capped() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(350),
+        };
+        let delays: Vec<u128> = (1..=4)
+            .map(|retry| backoff.delay(retry).as_millis())
+            .collect();
+        assert_eq!(delays, [100, 200, 350, 350]);
+        assert_eq!(backoff.delay(200), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_retries_matching_outcomes_only() {
+        let policy = RetryPolicy::default();
+        assert!(policy.retries(&Ok(ExecutionStatus::Timeout)));
+        assert!(policy.retries(&Ok(ExecutionStatus::Failed("exit 1".to_string()))));
+        assert!(!policy.retries(&Ok(ExecutionStatus::Failed("cancelled".to_string()))));
+        assert!(!policy.retries(&Ok(ExecutionStatus::Success)));
+        let error = OpenSkillError::NativeExecutionError("spawn failed".to_string());
+        assert!(!policy.retries(&Err(&error)));
+        assert!("error".parse::<RetryOn>().is_ok());
+        assert!("later".parse::<RetryOn>().is_err());
+    }
+}
@@ -0,0 +1,753 @@
+//! OS sandbox backend selection for native script execution.
+//!
+//! [`crate::SandboxMode`] decides *whether* OpenSkills sandboxes native
+//! scripts; [`ExecutionBackend`] decides *how*:
+//!
+//! - **Native**: the platform default (Seatbelt on macOS, Landlock on Linux).
+//! - **Bubblewrap** (Linux): a mount-namespace sandbox built with `bwrap`.
+//!   Unlike Landlock it does not depend on kernel 5.13+, so it does not
+//!   silently degrade to NO_NEW_PRIVS on older kernels.
+//! - **Nsjail** (Linux): `nsjail` with PID, user, mount, IPC, UTS and
+//!   network namespaces plus rlimits, for multi-tenant hosts running
+//!   untrusted skills. Falls back to Native when `nsjail` is not installed
+//!   or cannot create namespaces. Never chosen by `Auto`.
+//! - **Container**: `docker run` / `podman run` with the skill root mounted
+//!   read-only and the workspace read-write, for CI hosts where neither
+//!   Seatbelt nor Landlock is acceptable. Never chosen by `Auto`.
+//! - **Auto** (default): Bubblewrap when `bwrap` is installed and usable,
+//!   otherwise Native. Skills with a network egress allowlist run under
+//!   Native, since only Landlock can filter outbound ports.
+
+use crate::errors::OpenSkillError;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Sandbox backend used for native script execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExecutionBackend {
+    /// Prefer Bubblewrap on Linux when available, otherwise the platform default.
+    #[default]
+    Auto,
+    /// Platform default: Seatbelt (macOS) or Landlock (Linux).
+    Native,
+    /// Bubblewrap mount-namespace sandbox (Linux only).
+    Bubblewrap,
+    /// nsjail namespace + rlimit sandbox (Linux only; falls back to Native).
+    Nsjail,
+    /// Docker/Podman container (see [`ContainerConfig`]).
+    Container,
+}
+
+impl ExecutionBackend {
+    /// Stable lowercase name (used in audit records and configuration).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Native => "native",
+            Self::Bubblewrap => "bubblewrap",
+            Self::Nsjail => "nsjail",
+            Self::Container => "container",
+        }
+    }
+
+    /// Resolve `Auto` to a concrete backend for this host, and `Nsjail` to
+    /// `Native` where nsjail is unavailable.
+    pub fn resolve(self) -> ExecutionBackend {
+        match self {
+            Self::Auto => {
+                #[cfg(target_os = "linux")]
+                {
+                    if bwrap::is_available() {
+                        return Self::Bubblewrap;
+                    }
+                }
+                Self::Native
+            }
+            Self::Nsjail => {
+                #[cfg(target_os = "linux")]
+                {
+                    if nsjail::is_available() {
+                        return Self::Nsjail;
+                    }
+                }
+                Self::Native
+            }
+            other => other,
+        }
+    }
+}
+
+impl FromStr for ExecutionBackend {
+    type Err = OpenSkillError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "native" | "landlock" | "seatbelt" => Ok(Self::Native),
+            "bubblewrap" | "bwrap" => Ok(Self::Bubblewrap),
+            "nsjail" => Ok(Self::Nsjail),
+            "container" | "docker" | "podman" => Ok(Self::Container),
+            other => Err(OpenSkillError::InvalidConfig(format!(
+                "unknown execution backend: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Settings for [`ExecutionBackend::Container`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerConfig {
+    /// Container engine binary. Default: `docker`, then `podman`, from PATH.
+    pub engine: Option<PathBuf>,
+    /// Image to run scripts in; must provide `bash`, `python3` and `timeout`.
+    pub image: String,
+    /// Memory limit in MB (`--memory`).
+    pub memory_mb: Option<u64>,
+    /// CPU limit (`--cpus`).
+    pub cpus: Option<f64>,
+    /// Maximum number of processes (`--pids-limit`).
+    pub pids_limit: Option<u64>,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            engine: None,
+            image: "python:3-slim".to_string(),
+            memory_mb: None,
+            cpus: None,
+            pids_limit: Some(256),
+        }
+    }
+}
+
+/// Locate an executable by name in PATH.
+pub(crate) fn find_in_path(name: &str) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Container command construction.
+#[cfg(unix)]
+pub(crate) mod container {
+    use super::{find_in_path, ContainerConfig};
+    use std::os::unix::fs::MetadataExt;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// Host variables the engine client itself needs (never forwarded).
+    const CLIENT_ENV: &[&str] = &[
+        "HOME",
+        "DOCKER_HOST",
+        "DOCKER_CONTEXT",
+        "DOCKER_CONFIG",
+        "CONTAINER_HOST",
+        "XDG_RUNTIME_DIR",
+    ];
+
+    /// Host-specific variables that must not leak into the container.
+    const NOT_FORWARDED: &[&str] = &["PATH", "TMPDIR", "HOME"];
+
+    /// Mounts, network and working directory for one container run.
+    #[derive(Debug, Clone, Default)]
+    pub struct ContainerSpec {
+        /// Bind-mounted read-only at the same path.
+        pub ro_mounts: Vec<PathBuf>,
+        /// Bind-mounted read-write at the same path.
+        pub rw_mounts: Vec<PathBuf>,
+        /// Keep the default network instead of `--network none`.
+        pub allow_network: bool,
+        /// Working directory inside the container.
+        pub workdir: PathBuf,
+        /// Kill the containerized process after this many milliseconds.
+        pub timeout_ms: u64,
+    }
+
+    /// Resolve the engine binary from the config or PATH.
+    pub fn find_engine(config: &ContainerConfig) -> Option<PathBuf> {
+        match &config.engine {
+            Some(engine) if engine.components().count() > 1 => {
+                engine.is_file().then(|| engine.clone())
+            }
+            Some(engine) => find_in_path(&engine.to_string_lossy()),
+            None => find_in_path("docker").or_else(|| find_in_path("podman")),
+        }
+    }
+
+    /// Turn `cmd` (an engine command whose environment has already been set
+    /// up for the script) into `engine run ... image program args`.
+    ///
+    /// Every variable set on `cmd` is forwarded by name with `-e KEY`, so
+    /// values never appear on the engine command line.
+    pub fn prepare(
+        cmd: &mut Command,
+        config: &ContainerConfig,
+        spec: &ContainerSpec,
+        program: &str,
+        args: &[String],
+    ) {
+        let forwarded: Vec<String> = cmd
+            .get_envs()
+            .filter(|(_, v)| v.is_some())
+            .map(|(k, _)| k.to_string_lossy().to_string())
+            .filter(|k| !NOT_FORWARDED.contains(&k.as_str()))
+            .collect();
+        for key in CLIENT_ENV {
+            if let Some(val) = std::env::var_os(key) {
+                cmd.env(key, val);
+            }
+        }
+        cmd.args(build_args(config, spec, &forwarded, program, args));
+    }
+
+    /// Build the `run` argument list.
+    pub fn build_args(
+        config: &ContainerConfig,
+        spec: &ContainerSpec,
+        env_keys: &[String],
+        program: &str,
+        args: &[String],
+    ) -> Vec<String> {
+        let mut out: Vec<String> = vec![
+            "run".into(),
+            "--rm".into(),
+            "-i".into(),
+            "--cap-drop".into(),
+            "ALL".into(),
+            "--security-opt".into(),
+            "no-new-privileges".into(),
+            "--tmpfs".into(),
+            "/tmp".into(),
+        ];
+        if !spec.allow_network {
+            out.extend(["--network".into(), "none".into()]);
+        }
+        if let Some(mb) = config.memory_mb {
+            out.extend(["--memory".into(), format!("{}m", mb)]);
+        }
+        if let Some(cpus) = config.cpus {
+            out.extend(["--cpus".into(), cpus.to_string()]);
+        }
+        if let Some(pids) = config.pids_limit {
+            out.extend(["--pids-limit".into(), pids.to_string()]);
+        }
+        // Run as the owner of the workspace so created files stay usable on the host.
+        if let Some(meta) = spec
+            .rw_mounts
+            .first()
+            .and_then(|p| std::fs::metadata(p).ok())
+        {
+            out.extend(["--user".into(), format!("{}:{}", meta.uid(), meta.gid())]);
+        }
+        for path in &spec.ro_mounts {
+            if path.exists() {
+                let p = path.to_string_lossy();
+                out.extend(["-v".into(), format!("{}:{}:ro", p, p)]);
+            }
+        }
+        for path in &spec.rw_mounts {
+            if path.exists() {
+                let p = path.to_string_lossy();
+                out.extend(["-v".into(), format!("{}:{}", p, p)]);
+            }
+        }
+        out.extend(["-w".into(), spec.workdir.to_string_lossy().to_string()]);
+        for key in env_keys {
+            out.extend(["-e".into(), key.clone()]);
+        }
+        out.push(config.image.clone());
+        // The engine client being killed does not stop the container, so the
+        // timeout is also enforced inside it.
+        if spec.timeout_ms > 0 {
+            let secs = spec.timeout_ms.div_ceil(1000) + 1;
+            out.extend(["timeout".into(), "-s".into(), "KILL".into(), secs.to_string()]);
+        }
+        out.push(program.to_string());
+        out.extend(args.iter().cloned());
+        out
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_build_args_maps_permissions() {
+            let config = ContainerConfig {
+                memory_mb: Some(512),
+                cpus: Some(1.5),
+                ..Default::default()
+            };
+            let spec = ContainerSpec {
+                ro_mounts: vec![PathBuf::from("/")],
+                workdir: PathBuf::from("/"),
+                timeout_ms: 2500,
+                ..Default::default()
+            };
+            let args = build_args(&config, &spec, &["SKILL_ID".to_string()], "bash", &[]);
+            let joined = args.join(" ");
+            assert!(joined.starts_with("run --rm -i"));
+            assert!(joined.contains("--network none"));
+            assert!(joined.contains("--memory 512m"));
+            assert!(joined.contains("--cpus 1.5"));
+            assert!(joined.contains("-v /:/:ro"));
+            assert!(joined.contains("-e SKILL_ID"));
+            assert!(joined.ends_with("python:3-slim timeout -s KILL 4 bash"));
+        }
+
+        #[test]
+        fn test_build_args_network_allowed() {
+            let spec = ContainerSpec {
+                allow_network: true,
+                workdir: PathBuf::from("/"),
+                ..Default::default()
+            };
+            let args = build_args(&ContainerConfig::default(), &spec, &[], "python3", &[]);
+            assert!(!args.contains(&"none".to_string()));
+            assert_eq!(args.last().unwrap(), "python3");
+        }
+
+        #[test]
+        fn test_prepare_forwards_env_by_name() {
+            let mut cmd = Command::new("docker");
+            cmd.env_clear();
+            cmd.env("PATH", "/usr/bin");
+            cmd.env("SKILL_INPUT", "{\"secret\":1}");
+            let spec = ContainerSpec {
+                workdir: PathBuf::from("/"),
+                ..Default::default()
+            };
+            prepare(&mut cmd, &ContainerConfig::default(), &spec, "bash", &[]);
+            let args: Vec<String> = cmd
+                .get_args()
+                .map(|a| a.to_string_lossy().to_string())
+                .collect();
+            assert!(args.contains(&"SKILL_INPUT".to_string()));
+            assert!(!args.contains(&"PATH".to_string()));
+            assert!(!args.iter().any(|a| a.contains("secret")));
+        }
+    }
+}
+
+/// Bubblewrap command construction (Linux only).
+#[cfg(target_os = "linux")]
+pub(crate) mod bwrap {
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Stdio};
+    use std::sync::OnceLock;
+
+    /// System directories bound read-only into the sandbox.
+    pub(super) const SYSTEM_RO_PATHS: &[&str] = &[
+        "/usr", "/bin", "/sbin", "/lib", "/lib64", "/lib32", "/etc", "/opt",
+    ];
+
+    /// Path layout for a bubblewrap sandbox.
+    #[derive(Debug, Clone, Default)]
+    pub struct BwrapSpec {
+        /// Extra read-only binds (skill root, enforcer read paths, interpreter prefix).
+        pub ro_paths: Vec<PathBuf>,
+        /// Read-write binds (workspace, enforcer write paths).
+        pub rw_paths: Vec<PathBuf>,
+        /// Paths masked even if they fall under a bound directory.
+        pub deny_paths: Vec<PathBuf>,
+        /// Keep the host network namespace.
+        pub allow_network: bool,
+        /// Working directory inside the sandbox.
+        pub chdir: PathBuf,
+        /// Inherited fd holding a seccomp BPF program (`--seccomp`).
+        pub seccomp_fd: Option<i32>,
+    }
+
+    /// Locate the `bwrap` binary in PATH.
+    pub fn find_bwrap() -> Option<PathBuf> {
+        super::find_in_path("bwrap")
+    }
+
+    /// True when `bwrap` is installed and can create namespaces on this host.
+    ///
+    /// Probes once per process: bwrap is often installed but unusable inside
+    /// containers that disallow unprivileged user namespaces.
+    pub fn is_available() -> bool {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            let Some(bwrap) = find_bwrap() else {
+                return false;
+            };
+            Command::new(bwrap)
+                .args(["--unshare-all", "--ro-bind", "/", "/", "--", "/bin/true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Build the `bwrap` argument list that runs `program args...` inside the sandbox.
+    pub fn build_args(spec: &BwrapSpec, program: &str, args: &[String]) -> Vec<String> {
+        let mut out: Vec<String> = vec![
+            "--die-with-parent".into(),
+            "--new-session".into(),
+            "--unshare-all".into(),
+        ];
+        if spec.allow_network {
+            out.push("--share-net".into());
+        }
+
+        for sys in SYSTEM_RO_PATHS {
+            let path = Path::new(sys);
+            match std::fs::symlink_metadata(path) {
+                Ok(meta) if meta.file_type().is_symlink() => {
+                    if let Ok(target) = std::fs::read_link(path) {
+                        push3(&mut out, "--symlink", &target, path);
+                    }
+                }
+                Ok(_) => push3(&mut out, "--ro-bind", path, path),
+                Err(_) => {}
+            }
+        }
+        out.extend(["--proc".into(), "/proc".into(), "--dev".into(), "/dev".into()]);
+        out.extend(["--tmpfs".into(), "/tmp".into()]);
+
+        for path in &spec.ro_paths {
+            if path.exists() && !is_under_system(path) {
+                push3(&mut out, "--ro-bind", path, path);
+            }
+        }
+        for path in &spec.rw_paths {
+            if path.exists() {
+                push3(&mut out, "--bind", path, path);
+            }
+        }
+
+        // Mask sensitive paths that would otherwise be visible through a bind.
+        let bound: Vec<&PathBuf> = spec.ro_paths.iter().chain(spec.rw_paths.iter()).collect();
+        for path in &spec.deny_paths {
+            if !path.exists() || !bound.iter().any(|b| path.starts_with(b)) {
+                continue;
+            }
+            if path.is_dir() {
+                out.extend(["--tmpfs".into(), path.to_string_lossy().to_string()]);
+            } else {
+                push3(&mut out, "--ro-bind", Path::new("/dev/null"), path);
+            }
+        }
+
+        if let Some(fd) = spec.seccomp_fd {
+            out.extend(["--seccomp".into(), fd.to_string()]);
+        }
+        out.extend(["--chdir".into(), spec.chdir.to_string_lossy().to_string()]);
+        out.push("--".into());
+        out.push(program.to_string());
+        out.extend(args.iter().cloned());
+        out
+    }
+
+    /// `bwrap` command running `program args... extra_args...` inside the
+    /// sandbox described by `spec`, or `None` when bwrap is not installed.
+    pub fn command(
+        spec: &BwrapSpec,
+        program: &str,
+        args: &[String],
+        extra_args: &[String],
+    ) -> Option<Command> {
+        let bwrap = find_bwrap()?;
+        let all_args: Vec<String> = args.iter().chain(extra_args).cloned().collect();
+        let mut cmd = Command::new(bwrap);
+        cmd.args(build_args(spec, program, &all_args));
+        Some(cmd)
+    }
+
+    fn push3(out: &mut Vec<String>, flag: &str, src: &Path, dest: &Path) {
+        out.push(flag.to_string());
+        out.push(src.to_string_lossy().to_string());
+        out.push(dest.to_string_lossy().to_string());
+    }
+
+    pub(super) fn is_under_system(path: &Path) -> bool {
+        SYSTEM_RO_PATHS.iter().any(|sys| path.starts_with(sys))
+    }
+
+    /// Installation prefix of an interpreter outside the system directories
+    /// (e.g. `/home/u/.pyenv` for `/home/u/.pyenv/shims/python3`), so it can be
+    /// bound read-only.
+    pub fn interpreter_prefix(program: &Path) -> Option<PathBuf> {
+        let resolved = program.canonicalize().unwrap_or_else(|_| program.to_path_buf());
+        if is_under_system(&resolved) {
+            return None;
+        }
+        resolved.parent()?.parent().map(Path::to_path_buf)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_build_args_layout() {
+            let spec = BwrapSpec {
+                ro_paths: vec![PathBuf::from("/")],
+                rw_paths: vec![],
+                deny_paths: vec![],
+                allow_network: false,
+                chdir: PathBuf::from("/"),
+                seccomp_fd: Some(7),
+            };
+            let args = build_args(&spec, "/bin/bash", &["script.sh".to_string()]);
+            assert!(args.contains(&"--unshare-all".to_string()));
+            assert!(!args.contains(&"--share-net".to_string()));
+            assert!(args.windows(2).any(|w| w == ["--seccomp", "7"]));
+            let sep = args.iter().position(|a| a == "--").unwrap();
+            assert_eq!(&args[sep + 1..], &["/bin/bash", "script.sh"]);
+        }
+
+        #[test]
+        fn test_build_args_shares_network_when_allowed() {
+            let spec = BwrapSpec {
+                allow_network: true,
+                chdir: PathBuf::from("/"),
+                ..Default::default()
+            };
+            let args = build_args(&spec, "/bin/true", &[]);
+            assert!(args.contains(&"--share-net".to_string()));
+        }
+
+        #[test]
+        fn test_interpreter_prefix_skips_system_paths() {
+            assert_eq!(interpreter_prefix(Path::new("/usr/bin/python3")), None);
+        }
+    }
+}
+
+/// nsjail command construction (Linux only).
+#[cfg(target_os = "linux")]
+pub(crate) mod nsjail {
+    use super::bwrap::{is_under_system, SYSTEM_RO_PATHS};
+    use std::path::{Path, PathBuf};
+    use std::process::{Command, Stdio};
+    use std::sync::OnceLock;
+
+    /// Device nodes bound read-write into the jail.
+    const DEVICES: &[&str] = &["/dev/null", "/dev/zero", "/dev/random", "/dev/urandom"];
+    /// `RLIMIT_FSIZE` in MB (nsjail's own default of 1 MB is too small for outputs).
+    const RLIMIT_FSIZE_MB: u64 = 1024;
+    /// `RLIMIT_NOFILE`.
+    const RLIMIT_NOFILE: u64 = 1024;
+
+    /// Path layout and rlimits for an nsjail sandbox.
+    #[derive(Debug, Clone, Default)]
+    pub struct NsjailSpec {
+        /// Extra read-only binds (skill root, enforcer read paths, interpreter prefix).
+        pub ro_paths: Vec<PathBuf>,
+        /// Read-write binds (workspace, enforcer write paths).
+        pub rw_paths: Vec<PathBuf>,
+        /// Paths masked even if they fall under a bound directory.
+        pub deny_paths: Vec<PathBuf>,
+        /// Keep the host network namespace.
+        pub allow_network: bool,
+        /// Working directory inside the jail.
+        pub cwd: PathBuf,
+        /// `RLIMIT_AS` in MB (unlimited when unset).
+        pub rlimit_as_mb: Option<u64>,
+        /// `RLIMIT_CPU` in seconds (unlimited when unset).
+        pub rlimit_cpu_secs: Option<u64>,
+    }
+
+    /// Locate the `nsjail` binary in PATH.
+    pub fn find_nsjail() -> Option<PathBuf> {
+        super::find_in_path("nsjail")
+    }
+
+    /// True when `nsjail` is installed and can create namespaces on this host.
+    pub fn is_available() -> bool {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            let Some(nsjail) = find_nsjail() else {
+                return false;
+            };
+            Command::new(nsjail)
+                .args(["-Mo", "--really_quiet", "--chroot", "/", "--", "/bin/true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Build the `nsjail` argument list that runs `program args...` inside the jail.
+    pub fn build_args(spec: &NsjailSpec, program: &str, args: &[String]) -> Vec<String> {
+        let limit = |value: Option<u64>| value.map_or("max".to_string(), |v| v.to_string());
+        let mut out: Vec<String> = vec![
+            "--mode".into(),
+            "o".into(),
+            "--quiet".into(),
+            "--hostname".into(),
+            "openskills".into(),
+            // The runtime enforces the timeout itself.
+            "--time_limit".into(),
+            "0".into(),
+            // Environment is prepared by the runtime (see `apply_environment`).
+            "--keep_env".into(),
+            "--rlimit_as".into(),
+            limit(spec.rlimit_as_mb),
+            "--rlimit_cpu".into(),
+            limit(spec.rlimit_cpu_secs),
+            "--rlimit_fsize".into(),
+            RLIMIT_FSIZE_MB.to_string(),
+            "--rlimit_nofile".into(),
+            RLIMIT_NOFILE.to_string(),
+        ];
+        if spec.allow_network {
+            out.push("--disable_clone_newnet".into());
+        }
+
+        for sys in SYSTEM_RO_PATHS {
+            let path = Path::new(sys);
+            match std::fs::symlink_metadata(path) {
+                Ok(meta) if meta.file_type().is_symlink() => {
+                    if let Ok(target) = std::fs::read_link(path) {
+                        push_mount(&mut out, "--symlink", &target, path);
+                    }
+                }
+                Ok(_) => push_mount(&mut out, "--bindmount_ro", path, path),
+                Err(_) => {}
+            }
+        }
+        for dev in DEVICES {
+            let path = Path::new(dev);
+            if path.exists() {
+                push_mount(&mut out, "--bindmount", path, path);
+            }
+        }
+        out.extend(["--tmpfsmount".into(), "/tmp".into()]);
+
+        for path in &spec.ro_paths {
+            if path.exists() && !is_under_system(path) {
+                push_mount(&mut out, "--bindmount_ro", path, path);
+            }
+        }
+        for path in &spec.rw_paths {
+            if path.exists() {
+                push_mount(&mut out, "--bindmount", path, path);
+            }
+        }
+
+        // Mask sensitive paths that would otherwise be visible through a bind.
+        let bound: Vec<&PathBuf> = spec.ro_paths.iter().chain(spec.rw_paths.iter()).collect();
+        for path in &spec.deny_paths {
+            if !path.exists() || !bound.iter().any(|b| path.starts_with(b)) {
+                continue;
+            }
+            if path.is_dir() {
+                out.extend(["--tmpfsmount".into(), path.to_string_lossy().to_string()]);
+            } else {
+                push_mount(&mut out, "--bindmount_ro", Path::new("/dev/null"), path);
+            }
+        }
+
+        out.extend(["--cwd".into(), spec.cwd.to_string_lossy().to_string()]);
+        out.push("--".into());
+        out.push(program.to_string());
+        out.extend(args.iter().cloned());
+        out
+    }
+
+    /// `nsjail` command running `program args... extra_args...` inside the
+    /// jail described by `spec`, or `None` when nsjail is not installed.
+    pub fn command(
+        spec: &NsjailSpec,
+        program: &str,
+        args: &[String],
+        extra_args: &[String],
+    ) -> Option<Command> {
+        let nsjail = find_nsjail()?;
+        let all_args: Vec<String> = args.iter().chain(extra_args).cloned().collect();
+        let mut cmd = Command::new(nsjail);
+        cmd.args(build_args(spec, program, &all_args));
+        Some(cmd)
+    }
+
+    /// `--bindmount src:dst` style flags (`src` alone when both are equal).
+    fn push_mount(out: &mut Vec<String>, flag: &str, src: &Path, dest: &Path) {
+        out.push(flag.to_string());
+        if src == dest {
+            out.push(src.to_string_lossy().to_string());
+        } else {
+            out.push(format!("{}:{}", src.to_string_lossy(), dest.to_string_lossy()));
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_build_args_layout() {
+            let spec = NsjailSpec {
+                ro_paths: vec![PathBuf::from("/")],
+                cwd: PathBuf::from("/"),
+                rlimit_as_mb: Some(256),
+                rlimit_cpu_secs: Some(31),
+                ..Default::default()
+            };
+            let args = build_args(&spec, "/bin/bash", &["script.sh".to_string()]);
+            assert!(args.windows(2).any(|w| w == ["--mode", "o"]));
+            assert!(args.windows(2).any(|w| w == ["--rlimit_as", "256"]));
+            assert!(args.windows(2).any(|w| w == ["--rlimit_cpu", "31"]));
+            assert!(args.contains(&"--keep_env".to_string()));
+            assert!(!args.contains(&"--disable_clone_newnet".to_string()));
+            let sep = args.iter().position(|a| a == "--").unwrap();
+            assert_eq!(&args[sep + 1..], &["/bin/bash", "script.sh"]);
+        }
+
+        #[test]
+        fn test_build_args_network_and_unlimited() {
+            let spec = NsjailSpec {
+                allow_network: true,
+                cwd: PathBuf::from("/"),
+                ..Default::default()
+            };
+            let args = build_args(&spec, "/bin/true", &[]);
+            assert!(args.contains(&"--disable_clone_newnet".to_string()));
+            assert!(args.windows(2).any(|w| w == ["--rlimit_as", "max"]));
+        }
+
+        #[test]
+        fn test_push_mount_formats_src_dst() {
+            let mut out = Vec::new();
+            push_mount(&mut out, "--bindmount_ro", Path::new("/dev/null"), Path::new("/x/y"));
+            push_mount(&mut out, "--bindmount", Path::new("/w"), Path::new("/w"));
+            assert_eq!(out, ["--bindmount_ro", "/dev/null:/x/y", "--bindmount", "/w"]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_names() {
+        assert_eq!("auto".parse::<ExecutionBackend>().unwrap(), ExecutionBackend::Auto);
+        assert_eq!("bwrap".parse::<ExecutionBackend>().unwrap(), ExecutionBackend::Bubblewrap);
+        assert_eq!("Landlock".parse::<ExecutionBackend>().unwrap(), ExecutionBackend::Native);
+        assert_eq!("podman".parse::<ExecutionBackend>().unwrap(), ExecutionBackend::Container);
+        assert_eq!("nsjail".parse::<ExecutionBackend>().unwrap(), ExecutionBackend::Nsjail);
+        assert!("docker-ish".parse::<ExecutionBackend>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_keeps_explicit_backend() {
+        assert_eq!(ExecutionBackend::Native.resolve(), ExecutionBackend::Native);
+        assert_eq!(ExecutionBackend::Bubblewrap.resolve(), ExecutionBackend::Bubblewrap);
+        assert_eq!(ExecutionBackend::Container.resolve(), ExecutionBackend::Container);
+        assert_ne!(ExecutionBackend::Auto.resolve(), ExecutionBackend::Auto);
+        assert_ne!(ExecutionBackend::Auto.resolve(), ExecutionBackend::Container);
+    }
+}
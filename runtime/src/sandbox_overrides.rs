@@ -0,0 +1,187 @@
+//! Per-execution sandbox grants from the host.
+//!
+//! A host can widen the sandbox for a single run, e.g. to let a skill read a
+//! document the user picked outside the workspace. Overrides are merged into
+//! the permission enforcer, so they reach WASM preopens (directories only)
+//! and native sandbox profiles alike. They never open credential
+//! directories or replace environment variables the runtime manages, and a
+//! network grant is still narrowed by the skill's `allowed-hosts`.
+
+use crate::errors::OpenSkillError;
+use crate::manifest::WasmConfig;
+use crate::native_runner::SENSITIVE_DENY_PATHS;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Variables set by the runtime or the egress proxy.
+const RESERVED_ENV: &[&str] = &[
+    "PATH",
+    "TIMEOUT_MS",
+    "TMPDIR",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "NO_PROXY",
+];
+
+/// Prefixes of variables that are runtime-managed or change how the
+/// interpreter is loaded.
+const RESERVED_ENV_PREFIXES: &[&str] = &["SKILL_", "LD_", "DYLD_"];
+
+/// Extra sandbox grants for one execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SandboxOverrides {
+    /// Absolute paths (files or directories) the script may read.
+    pub read_paths: Vec<PathBuf>,
+    /// Absolute paths the script may write.
+    pub write_paths: Vec<PathBuf>,
+    /// Grant network access as if `WebSearch`/`Fetch` were allowed.
+    pub allow_network: bool,
+    /// Environment variables to set for the script.
+    pub env: BTreeMap<String, String>,
+}
+
+impl SandboxOverrides {
+    /// Allow reading `path`.
+    pub fn with_read_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.read_paths.push(path.into());
+        self
+    }
+
+    /// Allow writing `path`.
+    pub fn with_write_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.write_paths.push(path.into());
+        self
+    }
+
+    /// Grant or withhold network access.
+    pub fn with_network(mut self, allow: bool) -> Self {
+        self.allow_network = allow;
+        self
+    }
+
+    /// Set an environment variable for the script.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Whether no grant is requested.
+    pub fn is_empty(&self) -> bool {
+        self.read_paths.is_empty()
+            && self.write_paths.is_empty()
+            && !self.allow_network
+            && self.env.is_empty()
+    }
+
+    /// Reject relative paths, paths that overlap a credential directory and
+    /// reserved environment variables.
+    pub(crate) fn check(&self) -> Result<(), OpenSkillError> {
+        let home = std::env::var("HOME").ok();
+        for path in self.read_paths.iter().chain(&self.write_paths) {
+            if !path.is_absolute() {
+                return Err(OpenSkillError::InvalidConfig(format!(
+                    "sandbox override path must be absolute: {}",
+                    path.display()
+                )));
+            }
+            if let Some(home) = &home {
+                if let Some(sensitive) = overlapping_sensitive_path(path, Path::new(home)) {
+                    return Err(OpenSkillError::PermissionDenied(format!(
+                        "sandbox override {} would expose {}",
+                        path.display(),
+                        sensitive.display()
+                    )));
+                }
+            }
+        }
+        for key in self.env.keys() {
+            if is_reserved_env(key) {
+                return Err(OpenSkillError::PermissionDenied(format!(
+                    "environment variable {key} is managed by the runtime"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the path and network grants into the enforcer's config.
+    pub(crate) fn apply(&self, config: &mut WasmConfig) {
+        let fs = &mut config.filesystem;
+        for (paths, grants) in [(&self.read_paths, &mut fs.read), (&self.write_paths, &mut fs.write)] {
+            for path in paths {
+                let path = path.to_string_lossy().to_string();
+                if !grants.contains(&path) {
+                    grants.push(path);
+                }
+            }
+        }
+        if self.allow_network && config.network.allow.is_empty() {
+            config.network.allow.push("*".to_string());
+        }
+    }
+}
+
+/// Sensitive path that `path` is inside of or contains.
+pub(crate) fn overlapping_sensitive_path(path: &Path, home: &Path) -> Option<PathBuf> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    SENSITIVE_DENY_PATHS
+        .iter()
+        .map(|p| home.join(p.trim_start_matches("~/")))
+        .find(|sensitive| path.starts_with(sensitive) || sensitive.starts_with(&path))
+}
+
+fn is_reserved_env(key: &str) -> bool {
+    let upper = key.to_ascii_uppercase();
+    RESERVED_ENV.contains(&upper.as_str())
+        || RESERVED_ENV_PREFIXES.iter().any(|p| upper.starts_with(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rejects_unsafe_overrides() {
+        assert!(SandboxOverrides::default().check().is_ok());
+        assert!(SandboxOverrides::default()
+            .with_read_path("/srv/data/input.pdf")
+            .with_env("REPORT_TITLE", "Q3")
+            .check()
+            .is_ok());
+
+        assert!(matches!(
+            SandboxOverrides::default().with_read_path("docs/input.pdf").check(),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+        for key in ["PATH", "SKILL_ROOT", "LD_PRELOAD", "https_proxy"] {
+            assert!(matches!(
+                SandboxOverrides::default().with_env(key, "x").check(),
+                Err(OpenSkillError::PermissionDenied(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sensitive_overlap() {
+        let home = Path::new("/home/u");
+        assert!(overlapping_sensitive_path(Path::new("/home/u/.ssh/id_rsa"), home).is_some());
+        // A parent of a credential directory would expose it too.
+        assert!(overlapping_sensitive_path(Path::new("/home/u"), home).is_some());
+        assert!(overlapping_sensitive_path(Path::new("/home/u/Documents"), home).is_none());
+    }
+
+    #[test]
+    fn test_apply_merges_into_config() {
+        let mut config = WasmConfig::default();
+        config.filesystem.read.push(".".to_string());
+        SandboxOverrides::default()
+            .with_read_path("/srv/in")
+            .with_write_path("/srv/out")
+            .with_network(true)
+            .apply(&mut config);
+        assert_eq!(config.filesystem.read, vec![".", "/srv/in"]);
+        assert_eq!(config.filesystem.write, vec!["/srv/out"]);
+        assert_eq!(config.network.allow, vec!["*"]);
+    }
+}
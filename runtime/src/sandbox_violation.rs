@@ -0,0 +1,193 @@
+//! Structured reporting of operations the OS sandbox blocked.
+//!
+//! Seatbelt and Landlock do not tell the caller what they denied; the script
+//! just sees `EPERM`/`EACCES` (or, for seccomp, dies with `SIGSYS`). This
+//! module recovers what it can from the script's stderr: Seatbelt
+//! `deny(1) <operation> <path>` log lines when they are forwarded, and the
+//! "Permission denied" / "Operation not permitted" messages interpreters
+//! and coreutils print. The result is a best-effort list, not an audit trail:
+//! a denial the script swallows silently is not reported.
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on reported violations per execution.
+const MAX_VIOLATIONS: usize = 32;
+
+/// Error suffixes printed for `EACCES` and `EPERM`.
+const DENIAL_MESSAGES: &[&str] = &["Permission denied", "Operation not permitted"];
+
+/// Words in a denial line that suggest a write rather than a read.
+const WRITE_HINTS: &[&str] = &[
+    "cannot touch",
+    "cannot create",
+    "cannot remove",
+    "cannot make directory",
+    "cannot move",
+    "for writing",
+];
+
+/// Words in a path-less denial line that suggest a blocked connection.
+const NETWORK_HINTS: &[&str] = &["connect", "socket", "network", "urlopen", "http"];
+
+/// One operation the sandbox blocked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxViolation {
+    /// What was blocked: the Seatbelt operation name when the sandbox logged
+    /// one (`file-write-create`, `network-outbound`, ...), otherwise
+    /// `file-read`/`file-write`/`file-access`, `network` or `syscall`.
+    pub operation: String,
+    /// Path the operation targeted, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// Extract sandbox denials from a sandboxed script's stderr and, on Linux,
+/// the signal that terminated it (`SIGSYS` from seccomp).
+#[cfg_attr(not(any(target_os = "macos", target_os = "linux")), allow(dead_code))]
+pub(crate) fn parse_violations(stderr: &str, signal: Option<i32>) -> Vec<SandboxViolation> {
+    let mut violations: Vec<SandboxViolation> = Vec::new();
+    #[cfg(target_os = "linux")]
+    if signal == Some(libc::SIGSYS) {
+        violations.push(SandboxViolation {
+            operation: "syscall".to_string(),
+            path: None,
+        });
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = signal;
+
+    for line in stderr.lines() {
+        let Some(violation) = parse_seatbelt_line(line).or_else(|| parse_denial_line(line)) else {
+            continue;
+        };
+        if !violations.contains(&violation) {
+            violations.push(violation);
+        }
+        if violations.len() >= MAX_VIOLATIONS {
+            break;
+        }
+    }
+    violations
+}
+
+/// `Sandbox: python3(4242) deny(1) file-write-create /private/tmp/out.txt`
+fn parse_seatbelt_line(line: &str) -> Option<SandboxViolation> {
+    let start = line.find("deny(")?;
+    let rest = &line[start..];
+    let rest = rest[rest.find(')')? + 1..].trim_start();
+    let (operation, target) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if operation.is_empty() || !operation.contains('-') {
+        return None;
+    }
+    let target = target.trim();
+    Some(SandboxViolation {
+        operation: operation.to_string(),
+        path: (!target.is_empty()).then(|| target.to_string()),
+    })
+}
+
+/// Interpreter and coreutils messages for `EACCES`/`EPERM`, e.g.
+/// `PermissionError: [Errno 13] Permission denied: '/etc/shadow'` or
+/// `touch: cannot touch '/etc/x': Permission denied`.
+fn parse_denial_line(line: &str) -> Option<SandboxViolation> {
+    let message = DENIAL_MESSAGES.iter().find(|m| line.contains(*m))?;
+    let lower = line.to_ascii_lowercase();
+
+    let path = match line.split_once(&format!("{message}: ")) {
+        // Python: `... Permission denied: '/path'`
+        Some((_, after)) => Some(unquote(after.trim())),
+        // Shells and coreutils: `prog: [action ]'/path': Permission denied`
+        None => line
+            .rsplit_once(&format!(": {message}"))
+            .and_then(|(before, _)| before.rsplit(": ").next())
+            .map(|segment| {
+                let segment = segment.trim();
+                let segment = segment.rsplit_once(' ').map_or(segment, |(_, p)| p);
+                unquote(segment)
+            }),
+    }
+    .filter(|p| looks_like_path(p));
+
+    let operation = match &path {
+        Some(_) if WRITE_HINTS.iter().any(|h| lower.contains(h)) => "file-write",
+        Some(_) if lower.contains("cannot open") || lower.contains("cannot read") => "file-read",
+        Some(_) => "file-access",
+        None if NETWORK_HINTS.iter().any(|h| lower.contains(h)) => "network",
+        None => return None,
+    };
+    Some(SandboxViolation {
+        operation: operation.to_string(),
+        path,
+    })
+}
+
+fn unquote(s: &str) -> String {
+    s.trim_matches(|c| matches!(c, '\'' | '"' | '`' | '‘' | '’'))
+        .to_string()
+}
+
+fn looks_like_path(s: &str) -> bool {
+    s.starts_with('/') || s.starts_with("~/") || s.starts_with("./") || s.starts_with("../")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(operation: &str, path: Option<&str>) -> SandboxViolation {
+        SandboxViolation {
+            operation: operation.to_string(),
+            path: path.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_seatbelt_log_lines() {
+        let stderr = "Sandbox: python3(4242) deny(1) file-write-create /private/tmp/out.txt\n\
+                      Sandbox: curl(77) deny(1) network-outbound 93.184.216.34:443\n\
+                      Sandbox: sh(1) deny(1) process-fork\n";
+        assert_eq!(
+            parse_violations(stderr, None),
+            vec![
+                v("file-write-create", Some("/private/tmp/out.txt")),
+                v("network-outbound", Some("93.184.216.34:443")),
+                v("process-fork", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errno_messages() {
+        let stderr = "Traceback (most recent call last):\n\
+                      PermissionError: [Errno 13] Permission denied: '/etc/shadow'\n\
+                      touch: cannot touch '/home/u/.bashrc': Permission denied\n\
+                      cat: /root/secret: Permission denied\n\
+                      bash: line 1: /usr/local/bin/x: Operation not permitted\n\
+                      PermissionError: [Errno 13] Permission denied\n\
+                      ConnectionError: connect failed: Permission denied\n";
+        assert_eq!(
+            parse_violations(stderr, None),
+            vec![
+                v("file-access", Some("/etc/shadow")),
+                v("file-write", Some("/home/u/.bashrc")),
+                v("file-access", Some("/root/secret")),
+                v("file-access", Some("/usr/local/bin/x")),
+                v("network", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_and_noise() {
+        let stderr = "cat: /x/y: Permission denied\ncat: /x/y: Permission denied\nValueError: bad\n";
+        assert_eq!(parse_violations(stderr, None), vec![v("file-access", Some("/x/y"))]);
+        assert!(parse_violations("all good\n", None).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_sigsys_is_a_syscall_violation() {
+        assert_eq!(parse_violations("", Some(libc::SIGSYS)), vec![v("syscall", None)]);
+        assert!(parse_violations("", Some(libc::SIGKILL)).is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+//! Long-lived interactive script processes.
+//!
+//! Some skills keep a helper process around (a REPL-style converter, a
+//! language server) instead of starting a script per request.
+//! `OpenSkillRuntime::start_script_session` spawns the sandboxed script once
+//! and returns a [`ScriptSession`]: the host writes messages to the script's
+//! stdin and reads its stdout as the script answers. The script's input is
+//! still available in `SKILL_INPUT`; stdin carries only the session's
+//! messages.
+//!
+//! The session's timeout bounds its whole lifetime. Dropping the session
+//! kills the process; `OpenSkillRuntime::finish_script_session` closes its
+//! stdin, waits for it to exit and records the audit entry.
+
+use crate::active_executions::CancellationHandle;
+use crate::errors::OpenSkillError;
+use crate::events::{ExecutionEvent, ExecutionEventReceiver};
+use crate::executor::ExecutionArtifacts;
+use crate::PreparedExecution;
+use std::io::Write;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::error::TryRecvError;
+
+/// How long `finish_script_session` waits for the script to exit after
+/// closing its stdin before killing it.
+const CLOSE_GRACE: Duration = Duration::from_secs(5);
+
+/// How often a blocked read checks for new output.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Messages written to a script session's stdin. Set on
+/// `TargetExecutionOptions::stdin` by `start_script_session`; the native
+/// runner streams them to the script instead of writing the input JSON.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptStdin(Arc<Mutex<Option<Receiver<Vec<u8>>>>>);
+
+impl ScriptStdin {
+    fn new(messages: Receiver<Vec<u8>>) -> Self {
+        Self(Arc::new(Mutex::new(Some(messages))))
+    }
+
+    /// The messages, for the one child that reads them.
+    pub(crate) fn take(&self) -> Option<Receiver<Vec<u8>>> {
+        self.0.lock().ok()?.take()
+    }
+}
+
+/// Write each message to `stdin` as it arrives. Returns, closing the pipe,
+/// once the session closes its end or the script stops reading.
+pub(crate) fn forward_stdin(messages: Receiver<Vec<u8>>, mut stdin: impl Write) {
+    for message in messages {
+        if stdin.write_all(&message).and_then(|_| stdin.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+/// A sandboxed script started by `OpenSkillRuntime::start_script_session`,
+/// running until its stdin is closed, it exits, or its timeout passes.
+pub struct ScriptSession {
+    prepared: Option<PreparedExecution>,
+    stdin: Option<Sender<Vec<u8>>>,
+    output: ExecutionEventReceiver,
+    /// Stdout received but not yet read.
+    buffer: Vec<u8>,
+    cancellation: CancellationHandle,
+    process: Option<JoinHandle<Result<ExecutionArtifacts, OpenSkillError>>>,
+}
+
+impl ScriptSession {
+    /// Run `spawn` on its own thread with the session's stdin.
+    pub(crate) fn start(
+        prepared: PreparedExecution,
+        output: ExecutionEventReceiver,
+        spawn: impl FnOnce(ScriptStdin) -> Result<ExecutionArtifacts, OpenSkillError> + Send + 'static,
+    ) -> Result<Self, OpenSkillError> {
+        let (stdin, messages) = mpsc::channel();
+        let messages = ScriptStdin::new(messages);
+        let process = thread::Builder::new()
+            .name(format!("openskills-session-{}", prepared.running.id()))
+            .spawn(move || spawn(messages))?;
+        Ok(Self {
+            cancellation: prepared.running.cancellation(),
+            prepared: Some(prepared),
+            stdin: Some(stdin),
+            output,
+            buffer: Vec::new(),
+            process: Some(process),
+        })
+    }
+
+    /// Execution ID, as in `list_active_executions`.
+    pub fn execution_id(&self) -> u64 {
+        self.prepared.as_ref().map_or(0, |prepared| prepared.running.id())
+    }
+
+    /// Skill the script belongs to.
+    pub fn skill_id(&self) -> &str {
+        self.prepared
+            .as_ref()
+            .map_or("", |prepared| prepared.skill.id.as_str())
+    }
+
+    /// Whether the script is still running.
+    pub fn is_running(&self) -> bool {
+        self.process
+            .as_ref()
+            .is_some_and(|process| !process.is_finished())
+    }
+
+    /// Write `data` to the script's stdin.
+    pub fn write(&mut self, data: &[u8]) -> Result<(), OpenSkillError> {
+        let stdin = self.stdin.as_ref().filter(|_| self.is_running()).ok_or_else(|| {
+            OpenSkillError::NativeExecutionError("script session is closed".to_string())
+        })?;
+        stdin.send(data.to_vec()).map_err(|_| {
+            OpenSkillError::NativeExecutionError("script session is closed".to_string())
+        })
+    }
+
+    /// Write `line` and a newline to the script's stdin.
+    pub fn write_line(&mut self, line: &str) -> Result<(), OpenSkillError> {
+        self.write(format!("{line}\n").as_bytes())
+    }
+
+    /// Close the script's stdin, signalling that no more messages follow.
+    pub fn close_stdin(&mut self) {
+        self.stdin = None;
+    }
+
+    /// Read the next line the script writes to stdout, without its line
+    /// ending. Returns `None` once the script has exited and all its output
+    /// was read, and `OpenSkillError::Timeout` if no full line arrives
+    /// within `timeout`.
+    pub fn read_line(&mut self, timeout: Duration) -> Result<Option<String>, OpenSkillError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+                let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                return Ok(Some(String::from_utf8_lossy(&line).into_owned()));
+            }
+            match self.output.try_recv() {
+                Ok(ExecutionEvent::StdoutChunk(bytes)) => self.buffer.extend(bytes),
+                Ok(_) => {}
+                Err(TryRecvError::Empty) if self.is_running() => {
+                    if Instant::now() >= deadline {
+                        return Err(OpenSkillError::Timeout);
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Err(_) => {
+                    // Exited: collect what was sent just before, then return
+                    // any remaining lines; the last may be unterminated.
+                    while let Ok(event) = self.output.try_recv() {
+                        if let ExecutionEvent::StdoutChunk(bytes) = event {
+                            self.buffer.extend(bytes);
+                        }
+                    }
+                    if self.buffer.contains(&b'\n') {
+                        continue;
+                    }
+                    if self.buffer.is_empty() {
+                        return Ok(None);
+                    }
+                    let line = std::mem::take(&mut self.buffer);
+                    return Ok(Some(String::from_utf8_lossy(&line).into_owned()));
+                }
+            }
+        }
+    }
+
+    /// Close stdin, wait for the script to exit (killing it after
+    /// [`CLOSE_GRACE`]) and return the execution for auditing.
+    pub(crate) fn close(
+        mut self,
+    ) -> (PreparedExecution, Result<ExecutionArtifacts, OpenSkillError>) {
+        self.stdin = None;
+        let deadline = Instant::now() + CLOSE_GRACE;
+        while self.is_running() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+        if self.is_running() {
+            self.cancellation.cancel();
+        }
+        let execution = self
+            .process
+            .take()
+            .map(|process| {
+                process.join().unwrap_or_else(|_| {
+                    Err(OpenSkillError::NativeExecutionError(
+                        "Script session panicked".to_string(),
+                    ))
+                })
+            })
+            .unwrap_or(Err(OpenSkillError::Cancelled));
+        let prepared = self.prepared.take().expect("session is closed once");
+        (prepared, execution)
+    }
+}
+
+impl Drop for ScriptSession {
+    /// Kill the script if it is still running; nothing is audited.
+    fn drop(&mut self) {
+        if self.process.is_some() {
+            self.stdin = None;
+            self.cancellation.cancel();
+        }
+    }
+}
+
+impl std::fmt::Debug for ScriptSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptSession")
+            .field("execution_id", &self.execution_id())
+            .field("skill_id", &self.skill_id())
+            .field("running", &self.is_running())
+            .finish()
+    }
+}
@@ -0,0 +1,297 @@
+//! Skill search: shortlist skills for a query before prompting.
+//!
+//! Every skill gets a fuzzy score from its id, description and action
+//! capabilities: whole-word, prefix, substring and typo-tolerant (edit
+//! distance) matches, with the id weighted above the description. When an
+//! [`Embedder`] is configured, the cosine similarity between the query and
+//! each skill's `id: description` is blended in, so "summarize a paper"
+//! can find `pdf-digest`. Skill embeddings are cached by text.
+
+use crate::errors::OpenSkillError;
+use crate::registry::{SkillDescriptor, SkillMetadata};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Share of the combined score that comes from embeddings when an
+/// [`Embedder`] is set.
+const SEMANTIC_WEIGHT: f32 = 0.6;
+
+/// Weight of description and capability matches relative to id matches.
+const DESCRIPTION_WEIGHT: f32 = 0.7;
+
+/// Default [`SearchOptions::min_score`].
+pub const DEFAULT_SEARCH_MIN_SCORE: f32 = 0.2;
+
+/// Turns text into vectors for semantic search (a local model, an
+/// embeddings API, ...). Vectors only need to be comparable by cosine
+/// similarity with each other.
+pub trait Embedder: Send + Sync {
+    /// One vector per text, in order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OpenSkillError>;
+}
+
+/// Options for [`crate::OpenSkillRuntime::search_skills_with`].
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    /// Return at most this many matches.
+    pub limit: Option<usize>,
+    /// Drop matches scoring below this (`0.0..=1.0`).
+    pub min_score: f32,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            min_score: DEFAULT_SEARCH_MIN_SCORE,
+        }
+    }
+}
+
+/// A skill matching a search query.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillMatch {
+    pub skill: SkillDescriptor,
+    /// Combined relevance in `0.0..=1.0`; results are sorted by it.
+    pub score: f32,
+    /// Fuzzy match on the id, description and action capabilities.
+    pub fuzzy_score: f32,
+    /// Cosine similarity to the query, when an [`Embedder`] is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_score: Option<f32>,
+}
+
+/// Search state kept by the runtime.
+#[derive(Default)]
+pub(crate) struct SkillSearch {
+    embedder: Option<Arc<dyn Embedder>>,
+    /// Embeddings of skill texts, keyed by the text.
+    embeddings: Mutex<HashMap<String, Vec<f32>>>,
+}
+
+impl SkillSearch {
+    pub(crate) fn set_embedder(&mut self, embedder: Option<Arc<dyn Embedder>>) {
+        self.embedder = embedder;
+        self.embeddings.get_mut().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    pub(crate) fn search<'a>(
+        &self,
+        skills: impl Iterator<Item = &'a SkillMetadata>,
+        query: &str,
+        options: &SearchOptions,
+    ) -> Result<Vec<SkillMatch>, OpenSkillError> {
+        let skills: Vec<&SkillMetadata> = skills.collect();
+        let semantic = match &self.embedder {
+            Some(embedder) if !query.trim().is_empty() => Some(self.similarities(embedder.as_ref(), &skills, query)?),
+            _ => None,
+        };
+
+        let mut matches: Vec<SkillMatch> = skills
+            .iter()
+            .enumerate()
+            .map(|(i, skill)| {
+                let fuzzy_score = fuzzy_score(query, skill);
+                let semantic_score = semantic.as_ref().map(|s| s[i]);
+                let score = match semantic_score {
+                    Some(s) => (1.0 - SEMANTIC_WEIGHT) * fuzzy_score + SEMANTIC_WEIGHT * s,
+                    None => fuzzy_score,
+                };
+                SkillMatch {
+                    skill: skill.descriptor(),
+                    score,
+                    fuzzy_score,
+                    semantic_score,
+                }
+            })
+            .filter(|m| m.score > 0.0 && m.score >= options.min_score)
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.skill.id.cmp(&b.skill.id))
+        });
+        if let Some(limit) = options.limit {
+            matches.truncate(limit);
+        }
+        Ok(matches)
+    }
+
+    /// Cosine similarity of `query` to each skill, embedding only skill
+    /// texts not seen before.
+    fn similarities(
+        &self,
+        embedder: &dyn Embedder,
+        skills: &[&SkillMetadata],
+        query: &str,
+    ) -> Result<Vec<f32>, OpenSkillError> {
+        let texts: Vec<String> = skills.iter().map(|s| embedding_text(s)).collect();
+        let mut cache = self.embeddings.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut missing: Vec<String> = texts.iter().filter(|t| !cache.contains_key(*t)).cloned().collect();
+        missing.sort();
+        missing.dedup();
+        missing.push(query.to_string());
+
+        let mut vectors = embedder.embed(&missing)?;
+        if vectors.len() != missing.len() {
+            return Err(OpenSkillError::InvalidConfig(format!(
+                "embedder returned {} vectors for {} texts",
+                vectors.len(),
+                missing.len()
+            )));
+        }
+        let query_vector = vectors.pop().unwrap_or_default();
+        missing.pop();
+        cache.extend(missing.into_iter().zip(vectors));
+
+        Ok(texts
+            .iter()
+            .map(|text| cosine(&query_vector, &cache[text]).max(0.0))
+            .collect())
+    }
+}
+
+fn embedding_text(skill: &SkillMetadata) -> String {
+    format!("{}: {}", skill.id, skill.manifest.description)
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        (dot / denominator).min(1.0)
+    }
+}
+
+/// Lowercase alphanumeric words of `text`.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Fuzzy relevance of `skill` to `query` in `0.0..=1.0`: the average over
+/// query words of their best match in the id, or (weighted down) in the
+/// description and action capabilities.
+fn fuzzy_score(query: &str, skill: &SkillMetadata) -> f32 {
+    let query_words = words(query);
+    if query_words.is_empty() {
+        return 0.0;
+    }
+    let normalized = query_words.join("-");
+    if normalized == skill.id {
+        return 1.0;
+    }
+
+    let id_words = words(&skill.id);
+    let mut text_words = words(&skill.manifest.description);
+    for action in skill.manifest.actions.iter().flatten() {
+        for capability in &action.capabilities {
+            text_words.extend(words(capability));
+        }
+    }
+
+    let total: f32 = query_words
+        .iter()
+        .map(|word| word_score(word, &id_words).max(DESCRIPTION_WEIGHT * word_score(word, &text_words)))
+        .sum();
+    let score = total / query_words.len() as f32;
+    if skill.id.contains(&normalized) {
+        score.max(0.9)
+    } else {
+        score
+    }
+}
+
+/// Best match of one query word among `candidates`.
+fn word_score(word: &str, candidates: &[String]) -> f32 {
+    candidates
+        .iter()
+        .map(|candidate| {
+            if candidate == word {
+                1.0
+            } else if word.len() >= 2 && candidate.starts_with(word) {
+                0.85
+            } else if word.len() >= 3 && candidate.contains(word) {
+                0.7
+            } else {
+                match edit_distance(word, candidate, typo_budget(word)) {
+                    Some(1) => 0.6,
+                    Some(2) => 0.45,
+                    _ => 0.0,
+                }
+            }
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Typos tolerated in a word: none below 4 characters, two from 8.
+fn typo_budget(word: &str) -> usize {
+    match word.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein distance between `a` and `b` if it is at most `max`.
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    if max == 0 {
+        return None;
+    }
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        if current.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        previous = current;
+    }
+    Some(previous[b.len()]).filter(|&d| d <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("review", "reveiw", 2), Some(2));
+        assert_eq!(edit_distance("review", "reviw", 1), Some(1));
+        assert_eq!(edit_distance("review", "summary", 2), None);
+        assert_eq!(edit_distance("pdf", "pdx", 0), None);
+    }
+
+    #[test]
+    fn test_word_score_prefers_exact_then_prefix_then_typo() {
+        let candidates = vec!["review".to_string(), "reviewer".to_string()];
+        assert_eq!(word_score("review", &candidates), 1.0);
+        assert_eq!(word_score("rev", &candidates), 0.85);
+        assert_eq!(word_score("reviw", &candidates), 0.6);
+        assert_eq!(word_score("pdf", &candidates), 0.0);
+    }
+
+    #[test]
+    fn test_cosine() {
+        assert_eq!(cosine(&[1.0, 0.0], &[1.0, 0.0]), 1.0);
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+}
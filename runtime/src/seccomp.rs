@@ -0,0 +1,290 @@
+//! seccomp-bpf syscall filtering for Linux native execution.
+//!
+//! Landlock only restricts the filesystem. This layer kills the process
+//! (SIGSYS) on syscalls a skill script has no business making: debugging
+//! other processes, mounting, loading kernel code, creating namespaces
+//! (`unshare`, or `clone` with a `CLONE_NEW*` flag), and opening IP sockets
+//! when network access was not granted. The runners report SIGSYS as
+//! `ExecutionStatus::SandboxViolation`.
+//!
+//! A few syscalls fail with `ENOSYS` instead, as if the kernel predated
+//! them, so runtimes fall back to what the filter can inspect: `clone3`
+//! (its flags are behind a pointer; libc falls back to `clone`) and
+//! io_uring (`IORING_OP_SOCKET` would open sockets past the `socket` rule;
+//! libuv and others fall back to plain syscalls).
+
+use seccompiler::{
+    BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp, SeccompCondition, SeccompFilter,
+    SeccompRule, TargetArch,
+};
+use std::collections::BTreeMap;
+
+/// Syscalls blocked for every sandboxed script.
+const BLOCKED_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_chroot,
+    libc::SYS_setns,
+    libc::SYS_unshare,
+    libc::SYS_kexec_load,
+    libc::SYS_kexec_file_load,
+    libc::SYS_reboot,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_userfaultfd,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_acct,
+];
+
+/// Syscalls that fail with `ENOSYS` for every sandboxed script.
+const UNAVAILABLE_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_clone3,
+    libc::SYS_io_uring_setup,
+    libc::SYS_io_uring_enter,
+    libc::SYS_io_uring_register,
+];
+
+/// `clone` flags creating namespaces.
+const NAMESPACE_FLAGS: &[libc::c_int] = &[
+    libc::CLONE_NEWNS,
+    libc::CLONE_NEWUTS,
+    libc::CLONE_NEWIPC,
+    libc::CLONE_NEWUSER,
+    libc::CLONE_NEWPID,
+    libc::CLONE_NEWNET,
+    libc::CLONE_NEWCGROUP,
+];
+
+/// Socket families blocked when network access is not allowed. Unix
+/// sockets stay available (local IPC, nscd lookups).
+const NETWORK_FAMILIES: &[libc::c_int] = &[libc::AF_INET, libc::AF_INET6, libc::AF_PACKET];
+
+/// Compile the filter for this host's architecture.
+///
+/// Returns `None` on architectures seccompiler does not support; callers
+/// then run with Landlock/bubblewrap only.
+// `c_long` is already `i64` on 64-bit targets; the conversion is for 32-bit ones.
+#[allow(clippy::useless_conversion)]
+pub(crate) fn build_filter(allow_network: bool) -> Option<BpfProgram> {
+    let arch = TargetArch::try_from(std::env::consts::ARCH).ok()?;
+
+    let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BLOCKED_SYSCALLS
+        .iter()
+        .map(|&nr| (i64::from(nr), Vec::new()))
+        .collect();
+
+    // The flags are `clone`'s first argument on every architecture
+    // seccompiler supports.
+    let clone_rules = NAMESPACE_FLAGS
+        .iter()
+        .map(|&flag| {
+            let flag = flag as u64;
+            let cond =
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::MaskedEq(flag), flag)?;
+            SeccompRule::new(vec![cond])
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    rules.insert(i64::from(libc::SYS_clone), clone_rules);
+
+    if !allow_network {
+        let socket_rules = NETWORK_FAMILIES
+            .iter()
+            .map(|&family| {
+                let cond = SeccompCondition::new(
+                    0,
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::Eq,
+                    family as u64,
+                )?;
+                SeccompRule::new(vec![cond])
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        rules.insert(i64::from(libc::SYS_socket), socket_rules);
+    }
+
+    let kill = SeccompFilter::new(
+        rules,
+        SeccompAction::Allow,
+        SeccompAction::KillProcess,
+        arch,
+    )
+    .ok()?;
+    let unavailable = SeccompFilter::new(
+        UNAVAILABLE_SYSCALLS
+            .iter()
+            .map(|&nr| (i64::from(nr), Vec::new()))
+            .collect(),
+        SeccompAction::Allow,
+        SeccompAction::Errno(libc::ENOSYS as u32),
+        arch,
+    )
+    .ok()?;
+    Some(chain(
+        BpfProgram::try_from(kill).ok()?,
+        BpfProgram::try_from(unavailable).ok()?,
+    ))
+}
+
+/// One program running `first`, then `second` for the syscalls `first`
+/// allows, so a single filter can kill some syscalls and fail others.
+fn chain(mut first: BpfProgram, second: BpfProgram) -> BpfProgram {
+    const RET_K: u16 = (libc::BPF_RET | libc::BPF_K) as u16;
+    const JA: u16 = (libc::BPF_JMP | libc::BPF_JA) as u16;
+    let len = first.len();
+    for (i, insn) in first.iter_mut().enumerate() {
+        if insn.code == RET_K && insn.k == libc::SECCOMP_RET_ALLOW {
+            // Jump to the first instruction of `second`.
+            insn.code = JA;
+            insn.k = (len - i - 1) as u32;
+        }
+    }
+    first.extend(second);
+    first
+}
+
+/// Install `filter` on the calling thread (used in `pre_exec`).
+pub(crate) fn apply(filter: &BpfProgram) -> std::io::Result<()> {
+    seccompiler::apply_filter(filter).map_err(std::io::Error::other)
+}
+
+/// Write `filter` to a memfd for `bwrap --seccomp <fd>`.
+///
+/// The memfd is close-on-exec, so children spawned concurrently by other
+/// threads do not inherit it; the bwrap child calls [`inherit_fd`] in its
+/// `pre_exec`.
+pub(crate) fn to_memfd(filter: &BpfProgram) -> std::io::Result<std::os::fd::OwnedFd> {
+    use std::io::{Seek, SeekFrom, Write};
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    // SAFETY: memfd_create with a valid NUL-terminated name.
+    let fd = unsafe { libc::memfd_create(c"openskills-seccomp".as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: fd was just created and is owned here.
+    let owned = unsafe { OwnedFd::from_raw_fd(fd) };
+    let mut file = std::fs::File::from(owned);
+    let mut bytes = Vec::with_capacity(filter.len() * 8);
+    for insn in filter {
+        bytes.extend_from_slice(&insn.code.to_ne_bytes());
+        bytes.push(insn.jt);
+        bytes.push(insn.jf);
+        bytes.extend_from_slice(&insn.k.to_ne_bytes());
+    }
+    file.write_all(&bytes)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(OwnedFd::from(file))
+}
+
+/// Clear close-on-exec on `fd` so the program about to be executed
+/// inherits it (used in `pre_exec`; async-signal-safe).
+pub(crate) fn inherit_fd(fd: std::os::fd::RawFd) -> std::io::Result<()> {
+    // SAFETY: fcntl on a descriptor number; it fails with EBADF if closed.
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+    if flags < 0 || unsafe { libc::fcntl(fd, libc::F_SETFD, flags & !libc::FD_CLOEXEC) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_filter() {
+        let Some(strict) = build_filter(false) else {
+            return; // unsupported architecture
+        };
+        let relaxed = build_filter(true).unwrap();
+        assert!(strict.len() > relaxed.len());
+    }
+
+    #[test]
+    fn test_filter_kills_blocked_syscall() {
+        use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+        let Some(filter) = build_filter(false) else {
+            return;
+        };
+        // bash's /dev/tcp redirection calls socket(AF_INET, ...).
+        let mut cmd = std::process::Command::new("/bin/bash");
+        cmd.args(["-c", "echo > /dev/tcp/127.0.0.1/9"]);
+        cmd.stderr(std::process::Stdio::null());
+        unsafe {
+            cmd.pre_exec(move || apply(&filter));
+        }
+        let status = cmd.status().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGSYS));
+    }
+
+    #[test]
+    fn test_filter_kills_namespace_clone() {
+        use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+        let Some(filter) = build_filter(true) else {
+            return;
+        };
+        let mut cmd = std::process::Command::new("/bin/true");
+        unsafe {
+            cmd.pre_exec(move || {
+                apply(&filter)?;
+                let flags = libc::CLONE_NEWUSER | libc::SIGCHLD;
+                libc::syscall(libc::SYS_clone, flags, 0, 0, 0, 0);
+                Ok(())
+            });
+        }
+        let status = cmd.status().unwrap();
+        assert_eq!(status.signal(), Some(libc::SIGSYS));
+    }
+
+    #[test]
+    fn test_filter_hides_clone3_and_io_uring() {
+        let Some(filter) = build_filter(true) else {
+            return;
+        };
+        // Filters apply to the calling thread only.
+        std::thread::spawn(move || {
+            apply(&filter).unwrap();
+            for nr in [libc::SYS_clone3, libc::SYS_io_uring_setup] {
+                let ret = unsafe { libc::syscall(nr, 0, 0) };
+                assert_eq!(ret, -1);
+                let errno = std::io::Error::last_os_error().raw_os_error();
+                assert_eq!(errno, Some(libc::ENOSYS));
+            }
+            // Plain thread creation still works.
+            std::thread::spawn(|| ()).join().unwrap();
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_memfd_is_close_on_exec() {
+        use std::os::fd::AsRawFd;
+
+        let Some(filter) = build_filter(true) else {
+            return;
+        };
+        let fd = to_memfd(&filter).unwrap();
+        let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+        assert_ne!(flags & libc::FD_CLOEXEC, 0);
+        inherit_fd(fd.as_raw_fd()).unwrap();
+        let flags = unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_GETFD) };
+        assert_eq!(flags & libc::FD_CLOEXEC, 0);
+    }
+}
@@ -0,0 +1,435 @@
+//! Skill signatures.
+//!
+//! A signed skill carries a detached signature next to its `SKILL.md`:
+//! [`MINISIGN_SIGNATURE_FILE`] (minisign, Ed25519) or
+//! [`SIGSTORE_SIGNATURE_FILE`] (sigstore `cosign sign-blob --key`, ECDSA
+//! P-256). The signed message is the skill's [`skill_digest`], one
+//! `sha256sum`-style line per file, so a signature covers the scripts and
+//! WASM modules as well as the instructions:
+//!
+//! ```text
+//! openskills verify ./my-skill --digest > digest.txt
+//! minisign -Sm digest.txt -x ./my-skill/SKILL.md.minisig
+//! ```
+//!
+//! Once a host configures trusted keys (`OpenSkillRuntime::with_trusted_keys`),
+//! `activate_skill` and the execution entry points check every skill
+//! against them and, depending on the [`SignatureMode`], refuse skills that
+//! are unsigned or invalidly signed or only report them.
+
+use crate::errors::OpenSkillError;
+use crate::install::PROVENANCE_FILE;
+use base64::Engine;
+use blake2::{Blake2b512, Digest};
+use p256::ecdsa::signature::Verifier;
+use p256::pkcs8::DecodePublicKey;
+use sha2::Sha256;
+use std::fs;
+use std::path::Path;
+
+/// Minisign signature file, next to `SKILL.md`.
+pub const MINISIGN_SIGNATURE_FILE: &str = "SKILL.md.minisig";
+
+/// Sigstore (`cosign sign-blob --output-signature`) signature file, next to `SKILL.md`.
+pub const SIGSTORE_SIGNATURE_FILE: &str = "SKILL.md.sig";
+
+/// What the runtime does with a skill that fails signature verification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignatureMode {
+    /// Refuse to activate or run it (default).
+    #[default]
+    Refuse,
+    /// Allow it, but emit a `RuntimeEvent::SignatureRejected` event.
+    Warn,
+}
+
+/// A public key skills may be signed with.
+#[derive(Debug, Clone)]
+pub struct TrustedKey {
+    kind: KeyKind,
+}
+
+#[derive(Debug, Clone)]
+enum KeyKind {
+    Minisign {
+        key_id: [u8; 8],
+        key: ed25519_dalek::VerifyingKey,
+    },
+    Sigstore(p256::ecdsa::VerifyingKey),
+}
+
+impl TrustedKey {
+    /// Parse a minisign public key (the `.pub` file or its base64 line) or a
+    /// PEM-encoded P-256 public key as written by `cosign generate-key-pair`.
+    pub fn parse(text: &str) -> Result<Self, OpenSkillError> {
+        let text = text.trim();
+        if text.contains("-----BEGIN PUBLIC KEY-----") {
+            let key = p256::ecdsa::VerifyingKey::from_public_key_pem(text).map_err(|e| {
+                OpenSkillError::InvalidConfig(format!("invalid sigstore public key: {}", e))
+            })?;
+            return Ok(Self {
+                kind: KeyKind::Sigstore(key),
+            });
+        }
+
+        let line = text
+            .lines()
+            .map(str::trim)
+            .rfind(|l| !l.is_empty() && !l.starts_with("untrusted comment:"))
+            .unwrap_or_default();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(line)
+            .ok()
+            .filter(|b| b.len() == 42 && &b[..2] == b"Ed")
+            .ok_or_else(|| {
+                OpenSkillError::InvalidConfig(
+                    "not a minisign or PEM public key".to_string(),
+                )
+            })?;
+        let key = ed25519_dalek::VerifyingKey::from_bytes(bytes[10..].try_into().unwrap())
+            .map_err(|e| OpenSkillError::InvalidConfig(format!("invalid minisign public key: {}", e)))?;
+        Ok(Self {
+            kind: KeyKind::Minisign {
+                key_id: bytes[2..10].try_into().unwrap(),
+                key,
+            },
+        })
+    }
+
+    /// Read a key file (see [`parse`](Self::parse)).
+    pub fn from_file(path: &Path) -> Result<Self, OpenSkillError> {
+        Self::parse(&fs::read_to_string(path)?).map_err(|e| match e {
+            OpenSkillError::InvalidConfig(msg) => {
+                OpenSkillError::InvalidConfig(format!("{}: {}", path.display(), msg))
+            }
+            e => e,
+        })
+    }
+
+    /// Key ID: the minisign key ID, or `sha256:` plus a prefix of the
+    /// key's SHA-256 for sigstore keys.
+    pub fn id(&self) -> String {
+        match &self.kind {
+            KeyKind::Minisign { key_id, .. } => minisign_key_id(key_id),
+            KeyKind::Sigstore(key) => {
+                let digest = Sha256::digest(key.to_encoded_point(true).as_bytes());
+                format!("sha256:{}", &hex::encode(digest)[..16])
+            }
+        }
+    }
+}
+
+/// Outcome of checking a skill's signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Signed by the trusted key with this ID.
+    Verified { key_id: String },
+    /// No signature file next to `SKILL.md`.
+    Unsigned,
+    /// A signature is present but no trusted key verifies it.
+    Invalid(String),
+}
+
+/// The message a skill signature signs, one line per entry of the skill
+/// directory sorted by path: `<sha256>  <path>` for a file, `directory  <path>`
+/// for a directory and `symlink  <path> -> <target>` for a symlink, which is
+/// not followed. `.git`, the install provenance file and the signature files
+/// themselves are left out.
+pub fn skill_digest(skill_dir: &Path) -> Result<String, OpenSkillError> {
+    let mut digest = String::new();
+    let walker = walkdir::WalkDir::new(skill_dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || e.file_name() != ".git");
+    for entry in walker {
+        let entry = entry.map_err(|e| OpenSkillError::Io(e.into()))?;
+        if entry.depth() == 0 {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(skill_dir).unwrap_or(entry.path());
+        let rel = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let file_type = entry.file_type();
+        if file_type.is_symlink() {
+            // Hash where the link points, so retargeting it breaks the signature.
+            let target = fs::read_link(entry.path())?;
+            digest.push_str(&format!("symlink  {} -> {}\n", rel, target.to_string_lossy()));
+        } else if file_type.is_dir() {
+            digest.push_str(&format!("directory  {}\n", rel));
+        } else if file_type.is_file() {
+            if [PROVENANCE_FILE, MINISIGN_SIGNATURE_FILE, SIGSTORE_SIGNATURE_FILE].contains(&rel.as_str()) {
+                continue;
+            }
+            let hash = Sha256::digest(fs::read(entry.path())?);
+            digest.push_str(&format!("{}  {}\n", hex::encode(hash), rel));
+        } else {
+            return Err(OpenSkillError::InvalidManifest(format!(
+                "cannot sign special file in skill: {}",
+                rel
+            )));
+        }
+    }
+    Ok(digest)
+}
+
+/// Check the signature next to a skill's `SKILL.md` against `keys`.
+pub fn verify_skill(skill_dir: &Path, keys: &[TrustedKey]) -> SignatureStatus {
+    let minisig = fs::read_to_string(skill_dir.join(MINISIGN_SIGNATURE_FILE)).ok();
+    let sigstore = fs::read_to_string(skill_dir.join(SIGSTORE_SIGNATURE_FILE)).ok();
+    if minisig.is_none() && sigstore.is_none() {
+        return SignatureStatus::Unsigned;
+    }
+    let message = match skill_digest(skill_dir) {
+        Ok(digest) => digest,
+        Err(e) => return SignatureStatus::Invalid(format!("cannot digest skill: {}", e)),
+    };
+
+    let mut failures = Vec::new();
+    if let Some(signature) = minisig {
+        match verify_minisign(&signature, message.as_bytes(), keys) {
+            Ok(key_id) => return SignatureStatus::Verified { key_id },
+            Err(e) => failures.push(format!("{}: {}", MINISIGN_SIGNATURE_FILE, e)),
+        }
+    }
+    if let Some(signature) = sigstore {
+        match verify_sigstore(&signature, message.as_bytes(), keys) {
+            Ok(key_id) => return SignatureStatus::Verified { key_id },
+            Err(e) => failures.push(format!("{}: {}", SIGSTORE_SIGNATURE_FILE, e)),
+        }
+    }
+    SignatureStatus::Invalid(failures.join("; "))
+}
+
+/// Verify a minisign signature file (legacy or pre-hashed) and its trusted
+/// comment; returns the ID of the key that signed it.
+fn verify_minisign(signature: &str, message: &[u8], keys: &[TrustedKey]) -> Result<String, String> {
+    let lines: Vec<&str> = signature.lines().map(str::trim_end).collect();
+    let [_, sig_line, trusted_line, global_line, ..] = lines.as_slice() else {
+        return Err("truncated signature file".to_string());
+    };
+    let trusted_comment = trusted_line
+        .strip_prefix("trusted comment: ")
+        .ok_or("missing trusted comment")?;
+    let sig = decode_base64(sig_line)
+        .filter(|b| b.len() == 74)
+        .ok_or("malformed signature")?;
+    let global_sig = decode_base64(global_line)
+        .and_then(|b| <[u8; 64]>::try_from(b).ok())
+        .ok_or("malformed trusted comment signature")?;
+
+    let signed_message = match &sig[..2] {
+        b"Ed" => message.to_vec(),
+        b"ED" => Blake2b512::digest(message).to_vec(),
+        _ => return Err("unsupported signature algorithm".to_string()),
+    };
+    let key_id: [u8; 8] = sig[2..10].try_into().unwrap();
+    let key = keys
+        .iter()
+        .find_map(|k| match &k.kind {
+            KeyKind::Minisign { key_id: id, key } if *id == key_id => Some(key),
+            _ => None,
+        })
+        .ok_or_else(|| format!("signed with untrusted key {}", minisign_key_id(&key_id)))?;
+
+    let signature = ed25519_dalek::Signature::from_bytes(sig[10..].try_into().unwrap());
+    key.verify_strict(&signed_message, &signature)
+        .map_err(|_| "signature does not match skill contents".to_string())?;
+    let mut global_message = sig[10..].to_vec();
+    global_message.extend_from_slice(trusted_comment.as_bytes());
+    key.verify_strict(&global_message, &ed25519_dalek::Signature::from_bytes(&global_sig))
+        .map_err(|_| "trusted comment signature does not match".to_string())?;
+    Ok(minisign_key_id(&key_id))
+}
+
+/// Verify a base64 DER ECDSA P-256 signature against every trusted sigstore
+/// key; returns the ID of the key that signed it.
+fn verify_sigstore(signature: &str, message: &[u8], keys: &[TrustedKey]) -> Result<String, String> {
+    let signature = decode_base64(signature.trim())
+        .and_then(|der| p256::ecdsa::Signature::from_der(&der).ok())
+        .ok_or("malformed signature")?;
+    keys.iter()
+        .find(|k| matches!(&k.kind, KeyKind::Sigstore(key) if key.verify(message, &signature).is_ok()))
+        .map(TrustedKey::id)
+        .ok_or_else(|| "no trusted key verifies the signature".to_string())
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD.decode(text).ok()
+}
+
+/// Minisign displays key IDs as the little-endian key number in hex.
+fn minisign_key_id(key_id: &[u8; 8]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Signer as _;
+    use p256::pkcs8::EncodePublicKey;
+    use tempfile::TempDir;
+
+    const KEY_ID: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn skill() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: signed\ndescription: A signed skill.\n---\nDo things.\n",
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/run.py"), "print('hi')\n").unwrap();
+        dir
+    }
+
+    fn minisign_key() -> (ed25519_dalek::SigningKey, TrustedKey) {
+        let signing = ed25519_dalek::SigningKey::from_bytes(&[7; 32]);
+        let mut public = b"Ed".to_vec();
+        public.extend_from_slice(&KEY_ID);
+        public.extend_from_slice(signing.verifying_key().as_bytes());
+        let text = format!(
+            "untrusted comment: minisign public key\n{}\n",
+            base64::engine::general_purpose::STANDARD.encode(public)
+        );
+        (signing, TrustedKey::parse(&text).unwrap())
+    }
+
+    /// Write a pre-hashed minisign signature, as `minisign -S` does.
+    fn minisign(dir: &Path, signing: &ed25519_dalek::SigningKey) {
+        let digest = skill_digest(dir).unwrap();
+        let sig = signing.sign(&Blake2b512::digest(digest.as_bytes()));
+        let mut sig_bytes = b"ED".to_vec();
+        sig_bytes.extend_from_slice(&KEY_ID);
+        sig_bytes.extend_from_slice(&sig.to_bytes());
+        let trusted_comment = "timestamp:0\tfile:digest.txt\thashed";
+        let mut global = sig.to_bytes().to_vec();
+        global.extend_from_slice(trusted_comment.as_bytes());
+        let b64 = base64::engine::general_purpose::STANDARD;
+        fs::write(
+            dir.join(MINISIGN_SIGNATURE_FILE),
+            format!(
+                "untrusted comment: signature\n{}\ntrusted comment: {}\n{}\n",
+                b64.encode(sig_bytes),
+                trusted_comment,
+                b64.encode(signing.sign(&global).to_bytes())
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_digest_lists_files_and_skips_signatures() {
+        let dir = skill();
+        fs::write(dir.path().join(MINISIGN_SIGNATURE_FILE), "x").unwrap();
+        fs::write(dir.path().join(PROVENANCE_FILE), "{}").unwrap();
+        let digest = skill_digest(dir.path()).unwrap();
+        let paths: Vec<&str> = digest.lines().map(|l| l.split_once("  ").unwrap().1).collect();
+        assert_eq!(paths, ["SKILL.md", "scripts", "scripts/run.py"]);
+        assert!(digest.contains("directory  scripts\n"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_added_or_retargeted_symlink_breaks_signature() {
+        let dir = skill();
+        let outside = TempDir::new().unwrap();
+        fs::write(outside.path().join("skill.wasm"), b"\0asm").unwrap();
+        let (signing, key) = minisign_key();
+        minisign(dir.path(), &signing);
+        assert!(matches!(
+            verify_skill(dir.path(), std::slice::from_ref(&key)),
+            SignatureStatus::Verified { .. }
+        ));
+
+        // A directory symlink is not walked into, but is part of the digest.
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("wasm")).unwrap();
+        assert!(skill_digest(dir.path())
+            .unwrap()
+            .contains(&format!("symlink  wasm -> {}\n", outside.path().display())));
+        assert!(matches!(
+            verify_skill(dir.path(), std::slice::from_ref(&key)),
+            SignatureStatus::Invalid(_)
+        ));
+
+        // Pointing a signed link elsewhere is caught too.
+        minisign(dir.path(), &signing);
+        fs::remove_file(dir.path().join("wasm")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("scripts"), dir.path().join("wasm")).unwrap();
+        assert!(matches!(
+            verify_skill(dir.path(), &[key]),
+            SignatureStatus::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_minisign_signature_verifies_until_a_file_changes() {
+        let dir = skill();
+        let (signing, key) = minisign_key();
+        assert_eq!(verify_skill(dir.path(), std::slice::from_ref(&key)), SignatureStatus::Unsigned);
+
+        minisign(dir.path(), &signing);
+        assert_eq!(
+            verify_skill(dir.path(), std::slice::from_ref(&key)),
+            SignatureStatus::Verified {
+                key_id: "0807060504030201".to_string()
+            }
+        );
+
+        fs::write(dir.path().join("scripts/run.py"), "print('pwned')\n").unwrap();
+        assert!(matches!(
+            verify_skill(dir.path(), &[key]),
+            SignatureStatus::Invalid(reason) if reason.contains("does not match")
+        ));
+    }
+
+    #[test]
+    fn test_minisign_signature_from_untrusted_key_is_invalid() {
+        let dir = skill();
+        let other = ed25519_dalek::SigningKey::from_bytes(&[9; 32]);
+        minisign(dir.path(), &other);
+        let (_, key) = minisign_key();
+        // Same key ID, different key.
+        assert!(matches!(verify_skill(dir.path(), &[key]), SignatureStatus::Invalid(_)));
+        assert!(matches!(verify_skill(dir.path(), &[]), SignatureStatus::Invalid(reason)
+            if reason.contains("untrusted key 0807060504030201")));
+    }
+
+    #[test]
+    fn test_sigstore_signature_verifies() {
+        let dir = skill();
+        let signing = p256::ecdsa::SigningKey::from_slice(&[3; 32]).unwrap();
+        let pem = signing
+            .verifying_key()
+            .to_public_key_pem(p256::pkcs8::LineEnding::LF)
+            .unwrap();
+        let key = TrustedKey::parse(&pem).unwrap();
+        let digest = skill_digest(dir.path()).unwrap();
+        let sig: p256::ecdsa::Signature = p256::ecdsa::signature::Signer::sign(&signing, digest.as_bytes());
+        fs::write(
+            dir.path().join(SIGSTORE_SIGNATURE_FILE),
+            base64::engine::general_purpose::STANDARD.encode(sig.to_der()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            verify_skill(dir.path(), std::slice::from_ref(&key)),
+            SignatureStatus::Verified { key_id: key.id() }
+        );
+        let (_, minisign_only) = minisign_key();
+        assert!(matches!(
+            verify_skill(dir.path(), &[minisign_only]),
+            SignatureStatus::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert!(TrustedKey::parse("not a key").is_err());
+        assert!(TrustedKey::parse("-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PUBLIC KEY-----").is_err());
+    }
+}
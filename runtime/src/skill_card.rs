@@ -0,0 +1,160 @@
+//! Display metadata for skill pickers.
+//!
+//! A [`SkillCard`] gathers what an IDE or web UI shows for a skill: its
+//! description, author, version, tags, example invocations and README, so
+//! hosts do not have to parse the skill directory themselves. Examples come
+//! from the `examples:` frontmatter key or, without it, from the bullet
+//! points of an `EXAMPLES.md` next to SKILL.md.
+
+use crate::manifest::SkillExample;
+use crate::registry::{SkillKind, SkillLocation, SkillMetadata};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+/// README bytes included in a card; longer READMEs are cut.
+pub const MAX_README_BYTES: usize = 64 * 1024;
+
+/// Display-oriented metadata of a skill (see [`OpenSkillRuntime::get_skill_card`]).
+///
+/// [`OpenSkillRuntime::get_skill_card`]: crate::OpenSkillRuntime::get_skill_card
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillCard {
+    pub id: String,
+    /// Declared version, or `0.0.0` for unversioned skills.
+    pub version: String,
+    pub description: String,
+    /// `metadata.author`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// `metadata.homepage`, or `metadata.repository` without one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// `metadata.keywords`.
+    pub tags: Vec<String>,
+    pub location: SkillLocation,
+    pub kind: SkillKind,
+    pub user_invocable: bool,
+    /// Example invocations, from `examples:` or `EXAMPLES.md`.
+    pub examples: Vec<SkillCardExample>,
+    /// Contents of the skill's README.md, up to [`MAX_README_BYTES`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+}
+
+/// An example invocation on a [`SkillCard`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkillCardExample {
+    /// Name to run the example by (see `OpenSkillRuntime::run_skill_example`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// What a user would ask for.
+    pub prompt: String,
+    /// Input passed to the skill, if the example gives one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+}
+
+impl From<&SkillExample> for SkillCardExample {
+    fn from(example: &SkillExample) -> Self {
+        Self {
+            name: example.name().map(str::to_string),
+            prompt: example.prompt().to_string(),
+            input: example.input().cloned(),
+        }
+    }
+}
+
+/// Assemble the card of a discovered skill.
+pub(crate) fn build(metadata: &SkillMetadata) -> SkillCard {
+    let manifest = &metadata.manifest;
+    let info = manifest.metadata.clone().unwrap_or_default();
+    let examples = if manifest.examples.is_empty() {
+        read_examples_file(&metadata.root)
+    } else {
+        manifest.examples.iter().map(SkillCardExample::from).collect()
+    };
+    SkillCard {
+        id: metadata.id.clone(),
+        version: manifest.version().to_string(),
+        description: manifest.description.clone(),
+        author: info.author,
+        license: manifest.license.clone(),
+        homepage: info.homepage.or(info.repository),
+        tags: info.keywords.unwrap_or_default(),
+        location: metadata.location.clone(),
+        kind: metadata.kind,
+        user_invocable: manifest.is_user_invocable(),
+        examples,
+        readme: read_readme(&metadata.root),
+    }
+}
+
+/// Bullet points of `EXAMPLES.md`, one example each.
+fn read_examples_file(root: &Path) -> Vec<SkillCardExample> {
+    let Ok(content) = std::fs::read_to_string(root.join("EXAMPLES.md")) else {
+        return Vec::new();
+    };
+    parse_examples(&content)
+}
+
+fn parse_examples(markdown: &str) -> Vec<SkillCardExample> {
+    let mut in_code = false;
+    markdown
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        })
+        .filter_map(|line| {
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    let (number, rest) = line.split_once(". ")?;
+                    number.chars().all(|c| c.is_ascii_digit()).then_some(rest)
+                })?;
+            let prompt = item.trim().trim_matches(|c| c == '`' || c == '"').trim();
+            (!prompt.is_empty()).then(|| SkillCardExample {
+                name: None,
+                prompt: prompt.to_string(),
+                input: None,
+            })
+        })
+        .collect()
+}
+
+/// `README.md` (or `readme.md`), cut at a character boundary.
+fn read_readme(root: &Path) -> Option<String> {
+    let mut readme = ["README.md", "readme.md", "Readme.md"]
+        .iter()
+        .find_map(|name| std::fs::read_to_string(root.join(name)).ok())?;
+    if readme.len() > MAX_README_BYTES {
+        let mut end = MAX_README_BYTES;
+        while !readme.is_char_boundary(end) {
+            end -= 1;
+        }
+        readme.truncate(end);
+    }
+    Some(readme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_examples_from_bullets() {
+        let markdown = "# Examples\n\n- Convert `report.docx` to PDF\n* \"Merge two PDFs\"\n1. `Split a PDF by page`\n\n```\n- not an example\n```\n  - nested, skipped\n";
+        let prompts: Vec<_> = parse_examples(markdown).into_iter().map(|e| e.prompt).collect();
+        assert_eq!(
+            prompts,
+            ["Convert `report.docx` to PDF", "Merge two PDFs", "Split a PDF by page"]
+        );
+    }
+}
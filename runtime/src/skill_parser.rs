@@ -91,7 +91,7 @@ pub fn parse_frontmatter_only(content: &str) -> Result<SkillManifest, OpenSkillE
 
 /// Line-by-line `key: value` fallback when YAML parsing fails (inspired by Hermes Agent).
 ///
-/// Only reads `name` and `description`; other frontmatter keys are ignored (no full YAML).
+/// Only reads `name`, `description` and `version`; other frontmatter keys are ignored (no full YAML).
 fn parse_frontmatter_fallback(yaml_content: &str) -> SkillManifest {
     let mut manifest = SkillManifest::default();
     for line in yaml_content.lines() {
@@ -102,6 +102,7 @@ fn parse_frontmatter_fallback(yaml_content: &str) -> SkillManifest {
             match key {
                 "name" => manifest.name = value.to_string(),
                 "description" => manifest.description = value.to_string(),
+                "version" if !value.is_empty() => manifest.version = Some(value.to_string()),
                 _ => {}
             }
         }
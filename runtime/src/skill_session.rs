@@ -6,6 +6,7 @@
 //! by the agent rather than the runtime.
 
 use crate::context::{ExecutionContext, OutputType};
+use crate::workspace_import::WorkspaceImport;
 use crate::LoadedSkill;
 use serde_json::Value;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
@@ -20,6 +21,7 @@ pub struct SkillExecutionSession {
     start_epoch_ms: u64,
     permissions_used: Vec<String>,
     context: Option<ExecutionContext>,
+    workspace_files: Vec<WorkspaceImport>,
 }
 
 impl SkillExecutionSession {
@@ -41,9 +43,16 @@ impl SkillExecutionSession {
             start_epoch_ms,
             permissions_used: Vec::new(),
             context,
+            workspace_files: Vec::new(),
         }
     }
 
+    /// Record the `workspace-files` copied into the workspace for this session.
+    pub(crate) fn with_workspace_files(mut self, files: Vec<WorkspaceImport>) -> Self {
+        self.workspace_files = files;
+        self
+    }
+
     pub fn skill(&self) -> &LoadedSkill {
         &self.skill
     }
@@ -68,6 +77,12 @@ impl SkillExecutionSession {
         self.start_instant.elapsed().as_millis() as u64
     }
 
+    /// Template files the session copied into the workspace; files that
+    /// were already there are not listed.
+    pub fn workspace_files(&self) -> &[WorkspaceImport] {
+        &self.workspace_files
+    }
+
     pub fn permissions_used(&self) -> &[String] {
         &self.permissions_used
     }
@@ -129,6 +144,6 @@ impl SkillExecutionSession {
         self.context
             .as_mut()
             .map(|ctx| ctx.summarize())
-            .unwrap_or_else(String::new)
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,232 @@
+//! Skill versions and `id@constraint` references.
+//!
+//! A skill's version comes from the `version` frontmatter field or, failing
+//! that, a `name@version` directory suffix (`my-skill@2.1.0/SKILL.md`).
+//! Skills without either are treated as [`UNVERSIONED`]. Several versions
+//! of one id can be loaded at once; a reference picks one:
+//!
+//! - `my-skill`: the highest stable version (highest prerelease if there is
+//!   no stable one)
+//! - `my-skill@2.1.0`: exactly that version
+//! - `my-skill@^2`, `my-skill@>=1.2, <2`: the highest version matching the
+//!   semver requirement
+//!
+//! When directories hold skills with the same id, later locations override
+//! earlier ones. A `namespace:` prefix (`personal`, `project`, `nested` or
+//! `custom`) picks the copy from one location instead, e.g.
+//! `personal:code-review` or `project:code-review@^2`. A directory prefix
+//! picks the copy under that directory, e.g. `repoB/.claude:code-review`
+//! when several project roots have a `code-review` skill: the prefix is
+//! matched against the trailing components of the directories a skill lies
+//! in, or is an absolute path.
+
+use crate::errors::OpenSkillError;
+use crate::registry::SkillLocation;
+use semver::{Version, VersionReq};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+/// Version reported for skills that do not declare one.
+pub const UNVERSIONED: &str = "0.0.0";
+
+/// A skill id with an optional version requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillReference {
+    /// Location to take the skill from; `None` takes the copy that won.
+    pub namespace: Option<SkillLocation>,
+    /// Directory to take the skill from (see [`Self::in_directory`]).
+    pub directory: Option<PathBuf>,
+    pub id: String,
+    /// `None` selects the highest stable version.
+    pub constraint: Option<VersionReq>,
+}
+
+impl SkillReference {
+    /// Parse `id`, `id@version` or `id@requirement`, optionally prefixed
+    /// with `namespace:` or `directory:`. A bare version is an exact match
+    /// (`2.1.0` means `=2.1.0`, not `^2.1.0`).
+    pub fn parse(reference: &str) -> Result<Self, OpenSkillError> {
+        // Directories may contain `:` themselves (`C:\repos:code-review`).
+        let (directory, reference) = match reference.rsplit_once(':') {
+            Some((directory, rest)) if directory.contains(['/', '\\']) => {
+                let directory: PathBuf = Path::new(directory)
+                    .components()
+                    .filter(|c| !matches!(c, Component::CurDir))
+                    .collect();
+                (Some(directory), rest)
+            }
+            _ => (None, reference),
+        };
+        let (namespace, unqualified) = match reference.split_once(':') {
+            Some((namespace, rest)) if !namespace.contains('@') => {
+                let namespace = namespace.parse::<SkillLocation>().map_err(|_| {
+                    OpenSkillError::SkillNotFound(format!(
+                        "unknown namespace '{}' in '{}' (expected personal, project, nested or custom)",
+                        namespace, reference
+                    ))
+                })?;
+                (Some(namespace), rest)
+            }
+            _ => (None, reference),
+        };
+        let Some((id, constraint)) = unqualified.split_once('@') else {
+            return Ok(Self {
+                namespace,
+                directory,
+                id: unqualified.to_string(),
+                constraint: None,
+            });
+        };
+        let constraint = constraint.trim();
+        let req = match Version::parse(constraint) {
+            Ok(version) => VersionReq::parse(&format!("={}", version)),
+            Err(_) => VersionReq::parse(constraint),
+        }
+        .map_err(|e| OpenSkillError::InvalidVersion(format!("'{}': {}", reference, e)))?;
+        Ok(Self {
+            namespace,
+            directory,
+            id: id.to_string(),
+            constraint: Some(req),
+        })
+    }
+
+    /// Whether a skill rooted at `root` lies under the reference's
+    /// directory: one of the directories containing it is that directory or
+    /// ends with its components. Always true without a directory.
+    pub fn in_directory(&self, root: &Path) -> bool {
+        match &self.directory {
+            Some(directory) => root.ancestors().skip(1).any(|dir| dir.ends_with(directory)),
+            None => true,
+        }
+    }
+
+    /// Pick the version this reference selects out of `versions`.
+    pub fn select<'a>(&self, versions: impl IntoIterator<Item = &'a Version>) -> Option<&'a Version> {
+        let candidates = versions.into_iter();
+        match &self.constraint {
+            Some(req) => candidates.filter(|v| req.matches(v)).max(),
+            None => {
+                let all: Vec<&Version> = candidates.collect();
+                let stable = all.iter().copied().filter(|v| v.pre.is_empty()).max();
+                stable.or_else(|| all.into_iter().max())
+            }
+        }
+    }
+}
+
+impl fmt::Display for SkillReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(directory) = &self.directory {
+            write!(f, "{}:", directory.display())?;
+        }
+        if let Some(namespace) = &self.namespace {
+            write!(f, "{}:", namespace)?;
+        }
+        match &self.constraint {
+            Some(req) => write!(f, "{}@{}", self.id, req),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// Parse a declared skill version. YAML numbers like `2.1` are accepted and
+/// padded (`2.1.0`), as are a leading `v`.
+pub fn parse_version(version: &str) -> Result<Version, OpenSkillError> {
+    let trimmed = version.trim();
+    let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+    let (core, rest) = match trimmed.find(['-', '+']) {
+        Some(i) => trimmed.split_at(i),
+        None => (trimmed, ""),
+    };
+    let padded = match core.matches('.').count() {
+        0 => format!("{}.0.0{}", core, rest),
+        1 => format!("{}.0{}", core, rest),
+        _ => trimmed.to_string(),
+    };
+    Version::parse(&padded)
+        .map_err(|e| OpenSkillError::InvalidVersion(format!("'{}': {}", version, e)))
+}
+
+/// Split a `name@version` skill directory name.
+pub(crate) fn split_versioned_dir(dir_name: &str) -> (&str, Option<&str>) {
+    match dir_name.split_once('@') {
+        Some((id, version)) => (id, Some(version)),
+        None => (dir_name, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<Version> {
+        list.iter().map(|v| Version::parse(v).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_reference() {
+        let bare = SkillReference::parse("my-skill").unwrap();
+        assert_eq!(bare.id, "my-skill");
+        assert!(bare.constraint.is_none());
+
+        let exact = SkillReference::parse("my-skill@2.1.0").unwrap();
+        assert_eq!(exact.to_string(), "my-skill@=2.1.0");
+
+        let range = SkillReference::parse("my-skill@>=1.2, <2").unwrap();
+        assert!(range.constraint.unwrap().matches(&Version::new(1, 5, 0)));
+
+        assert!(matches!(
+            SkillReference::parse("my-skill@not a version"),
+            Err(OpenSkillError::InvalidVersion(_))
+        ));
+
+        let namespaced = SkillReference::parse("project:my-skill@^2").unwrap();
+        assert_eq!(namespaced.namespace, Some(SkillLocation::Project));
+        assert_eq!(namespaced.id, "my-skill");
+        assert_eq!(namespaced.to_string(), "project:my-skill@^2");
+        assert!(bare.namespace.is_none());
+        assert!(matches!(
+            SkillReference::parse("elsewhere:my-skill"),
+            Err(OpenSkillError::SkillNotFound(_))
+        ));
+
+        let qualified = SkillReference::parse("./repoB/.claude:my-skill@^2").unwrap();
+        assert_eq!(qualified.directory, Some(PathBuf::from("repoB/.claude")));
+        assert_eq!(qualified.id, "my-skill");
+        assert_eq!(qualified.to_string(), "repoB/.claude:my-skill@^2");
+        assert!(qualified.in_directory(Path::new("/srv/repoB/.claude/skills/my-skill")));
+        assert!(!qualified.in_directory(Path::new("/srv/repoA/.claude/skills/my-skill")));
+        assert!(!qualified.in_directory(Path::new("/srv/my-repoB/.claude/skills/my-skill")));
+        assert!(bare.in_directory(Path::new("/srv/repoA/.claude/skills/my-skill")));
+    }
+
+    #[test]
+    fn test_select_version() {
+        let available = versions(&["1.0.0", "1.4.2", "2.0.0", "2.1.0", "3.0.0-beta.1"]);
+        let pick = |reference: &str| {
+            SkillReference::parse(reference)
+                .unwrap()
+                .select(&available)
+                .map(ToString::to_string)
+        };
+        assert_eq!(pick("s").as_deref(), Some("2.1.0"));
+        assert_eq!(pick("s@^1").as_deref(), Some("1.4.2"));
+        assert_eq!(pick("s@2.0.0").as_deref(), Some("2.0.0"));
+        assert_eq!(pick("s@>=3.0.0-beta").as_deref(), Some("3.0.0-beta.1"));
+        assert_eq!(pick("s@^4"), None);
+
+        let prerelease_only = versions(&["1.0.0-alpha", "1.0.0-beta"]);
+        let bare = SkillReference::parse("s").unwrap();
+        assert_eq!(bare.select(&prerelease_only).unwrap().to_string(), "1.0.0-beta");
+    }
+
+    #[test]
+    fn test_parse_version_pads_short_versions() {
+        assert_eq!(parse_version("2").unwrap(), Version::new(2, 0, 0));
+        assert_eq!(parse_version("2.1").unwrap(), Version::new(2, 1, 0));
+        assert_eq!(parse_version("v1.2.3").unwrap(), Version::new(1, 2, 3));
+        assert_eq!(parse_version("1.2-rc.1").unwrap().to_string(), "1.2.0-rc.1");
+        assert!(parse_version("latest").is_err());
+    }
+}
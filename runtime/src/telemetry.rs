@@ -0,0 +1,268 @@
+//! Tracing spans for executions, hook runs and permission checks.
+//!
+//! The runtime always emits [`tracing`] spans: `openskills.execute` for each
+//! execution attempt (entry point or target), `openskills.hooks` for a hook
+//! event, and `openskills.permission` for each permission decision, nested
+//! under the execution that asked. Without a subscriber they cost next to
+//! nothing; hosts that already use `tracing` see them in their own pipeline.
+//!
+//! With the `otel` feature, [`init_otlp_tracing`] installs a subscriber that
+//! exports the spans to an OpenTelemetry collector over OTLP/HTTP. Execution
+//! spans then continue the caller's `ExecutionOptions::traceparent`, and the
+//! skill's processes get the execution span as `TRACEPARENT`, so their own
+//! spans nest under it.
+
+use crate::audit::ExecutionStatus;
+use crate::errors::OpenSkillError;
+use crate::executor::CommandResult;
+use crate::permission_callback::{PermissionDecision, RiskLevel};
+use tracing::field::Empty;
+use tracing::Span;
+
+/// Span for one execution attempt, recorded by [`finish_execution_span`].
+pub(crate) fn execution_span(skill_id: &str, target: &str, traceparent: Option<&str>) -> Span {
+    let span = tracing::info_span!(
+        "openskills.execute",
+        skill_id = skill_id,
+        target = target,
+        attempt = Empty,
+        status = Empty,
+        duration_ms = Empty,
+        otel.status_code = Empty,
+        otel.status_description = Empty,
+    );
+    #[cfg(feature = "otel")]
+    if let Some(traceparent) = traceparent {
+        otlp::set_remote_parent(&span, traceparent);
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = traceparent;
+    span
+}
+
+/// Record how an execution attempt ended on its span.
+pub(crate) fn finish_execution_span(
+    span: &Span,
+    status: &ExecutionStatus,
+    attempt: u32,
+    duration_ms: u64,
+) {
+    let (label, reason) = status_label(status);
+    span.record("attempt", attempt);
+    span.record("status", label);
+    span.record("duration_ms", duration_ms);
+    if is_failure(status) {
+        span.record("otel.status_code", "ERROR");
+        span.record("otel.status_description", reason.unwrap_or(label));
+    }
+}
+
+/// Record an execution that ended with an error instead of a status.
+pub(crate) fn fail_execution_span(span: &Span, error: &dyn std::fmt::Display) {
+    span.record("status", "error");
+    span.record("otel.status_code", "ERROR");
+    span.record("otel.status_description", error.to_string().as_str());
+}
+
+/// Span for running the hooks of one event.
+pub(crate) fn hooks_span(skill_id: &str, event: &str) -> Span {
+    tracing::info_span!(
+        "openskills.hooks",
+        skill_id = skill_id,
+        event = event,
+        hooks = Empty,
+        otel.status_code = Empty,
+    )
+}
+
+/// Record how many hooks ran and whether any failed or timed out.
+pub(crate) fn finish_hooks_span(span: &Span, results: &[CommandResult]) {
+    span.record("hooks", results.len());
+    if results.iter().any(|r| r.exit_code != 0 || r.timed_out) {
+        span.record("otel.status_code", "ERROR");
+    }
+}
+
+/// Span for one permission decision; see [`decision_label`].
+pub(crate) fn permission_span(skill_id: &str, tool: &str, risk_level: RiskLevel) -> Span {
+    let risk_level = match risk_level {
+        RiskLevel::Low => "low",
+        RiskLevel::Medium => "medium",
+        RiskLevel::High => "high",
+    };
+    tracing::info_span!(
+        "openskills.permission",
+        skill_id = skill_id,
+        tool = tool,
+        risk_level = risk_level,
+        decision = Empty,
+    )
+}
+
+/// `decision` of a permission span.
+pub(crate) fn decision_label(decision: &Result<PermissionDecision, OpenSkillError>) -> &'static str {
+    match decision {
+        Ok(PermissionDecision::Allowed) => "allowed",
+        Ok(PermissionDecision::AllowedInWorkspace) => "allowed_in_workspace",
+        Ok(PermissionDecision::Denied) => "denied",
+        Err(_) => "error",
+    }
+}
+
+/// `TRACEPARENT` for the processes of the execution `span`: the span itself
+/// when spans are exported, otherwise the caller's trace context unchanged.
+pub(crate) fn sandbox_traceparent(span: &Span, traceparent: Option<&str>) -> Option<String> {
+    #[cfg(feature = "otel")]
+    if let Some(own) = otlp::span_traceparent(span) {
+        return Some(own);
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = span;
+    traceparent.map(str::to_string)
+}
+
+fn status_label(status: &ExecutionStatus) -> (&'static str, Option<&str>) {
+    let reason = match status {
+        ExecutionStatus::Failed(reason)
+        | ExecutionStatus::SandboxViolation(reason)
+        | ExecutionStatus::SchemaViolation(reason) => Some(reason.as_str()),
+        _ => None,
+    };
+    (status.label(), reason)
+}
+
+fn is_failure(status: &ExecutionStatus) -> bool {
+    !matches!(
+        status,
+        ExecutionStatus::Success | ExecutionStatus::InstructionOnly | ExecutionStatus::DryRun
+    )
+}
+
+#[cfg(feature = "otel")]
+pub use otlp::{init_otlp_tracing, OtlpTracing};
+
+#[cfg(feature = "otel")]
+mod otlp {
+    use crate::errors::OpenSkillError;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider};
+    use opentelemetry_http::{Bytes, HttpClient, HttpError, Request, Response};
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig, WithHttpConfig};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::time::Duration;
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    /// How long one export request may take.
+    const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Keeps the OTLP exporter running; dropping it flushes the spans that
+    /// are still buffered.
+    #[derive(Debug)]
+    pub struct OtlpTracing {
+        provider: SdkTracerProvider,
+    }
+
+    impl OtlpTracing {
+        /// Export the spans buffered so far, e.g. before a short-lived
+        /// process exits.
+        pub fn flush(&self) -> Result<(), OpenSkillError> {
+            self.provider
+                .force_flush()
+                .map_err(|e| OpenSkillError::InvalidConfig(format!("OTLP export failed: {e}")))
+        }
+    }
+
+    /// Export the runtime's spans to an OpenTelemetry collector over
+    /// OTLP/HTTP (protobuf) as `service_name`.
+    ///
+    /// `endpoint` is the collector's traces URL, e.g.
+    /// `http://localhost:4318/v1/traces`. Without it, the standard
+    /// `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` / `OTEL_EXPORTER_OTLP_ENDPOINT`
+    /// variables apply, falling back to a collector on localhost.
+    ///
+    /// Installs the global `tracing` subscriber, so it fails if the host set
+    /// one already; such hosts add a `tracing-opentelemetry` layer to their
+    /// own subscriber instead.
+    pub fn init_otlp_tracing(
+        endpoint: Option<&str>,
+        service_name: &str,
+    ) -> Result<OtlpTracing, OpenSkillError> {
+        let client = UreqClient(ureq::AgentBuilder::new().timeout(EXPORT_TIMEOUT).build());
+        let mut exporter = SpanExporter::builder()
+            .with_http()
+            .with_http_client(client)
+            .with_timeout(EXPORT_TIMEOUT);
+        if let Some(endpoint) = endpoint {
+            exporter = exporter.with_endpoint(endpoint);
+        }
+        let exporter = exporter
+            .build()
+            .map_err(|e| OpenSkillError::InvalidConfig(format!("OTLP exporter: {e}")))?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(service_name.to_string())
+                    .build(),
+            )
+            .build();
+        let tracer = provider.tracer("openskills");
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .try_init()
+            .map_err(|e| OpenSkillError::InvalidConfig(format!("tracing subscriber: {e}")))?;
+        Ok(OtlpTracing { provider })
+    }
+
+    /// Make `span` a child of the caller's span in `traceparent`.
+    pub(super) fn set_remote_parent(span: &Span, traceparent: &str) {
+        let carrier = HashMap::from([("traceparent".to_string(), traceparent.to_string())]);
+        let parent = TraceContextPropagator::new().extract(&carrier);
+        // Only fails once the span has started, which it has not.
+        let _ = span.set_parent(parent);
+    }
+
+    /// `span` as a W3C `traceparent`, if spans are being exported.
+    pub(super) fn span_traceparent(span: &Span) -> Option<String> {
+        let context = span.context();
+        if !context.span().span_context().is_valid() {
+            return None;
+        }
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&context, &mut carrier);
+        carrier.remove("traceparent")
+    }
+
+    /// OTLP transport over the runtime's blocking HTTP client; the batch
+    /// exporter sends from its own thread.
+    #[derive(Debug)]
+    struct UreqClient(ureq::Agent);
+
+    #[async_trait::async_trait]
+    impl HttpClient for UreqClient {
+        async fn send_bytes(&self, request: Request<Bytes>) -> Result<Response<Bytes>, HttpError> {
+            let (parts, body) = request.into_parts();
+            let mut call = self.0.request(parts.method.as_str(), &parts.uri.to_string());
+            for (name, value) in &parts.headers {
+                if let Ok(value) = value.to_str() {
+                    call = call.set(name.as_str(), value);
+                }
+            }
+            let response = match call.send_bytes(&body) {
+                Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+                Err(e) => return Err(Box::new(e)),
+            };
+            let status = response.status();
+            let mut bytes = Vec::new();
+            response.into_reader().read_to_end(&mut bytes)?;
+            Ok(Response::builder().status(status).body(Bytes::from(bytes))?)
+        }
+    }
+}
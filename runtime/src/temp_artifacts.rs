@@ -0,0 +1,174 @@
+//! Temporary files the runtime hands to sandbox tools.
+//!
+//! Seatbelt profiles (and any other file a sandbox tool reads by path) are
+//! created through [`TempArtifact`], in one directory,
+//! `<temp>/openskills/tmp/`, named after the process that created them. The
+//! artifact is removed when its guard is dropped, and each process keeps a
+//! registry of the artifacts it has live. Files left by processes that died
+//! mid-run are removed by [`cleanup_orphaned_artifacts`], which the runtime
+//! runs once per process, when the first `OpenSkillRuntime` is created.
+
+use crate::errors::OpenSkillError;
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Artifacts of this process that are still in use.
+fn live() -> &'static Mutex<HashSet<PathBuf>> {
+    static LIVE: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    LIVE.get_or_init(Default::default)
+}
+
+/// Directory holding the artifacts of all runtimes.
+pub(crate) fn artifacts_dir() -> PathBuf {
+    std::env::temp_dir().join("openskills").join("tmp")
+}
+
+/// A temporary file, removed when dropped.
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) struct TempArtifact {
+    path: PathBuf,
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+impl TempArtifact {
+    /// Create `<pid>-<n>-<name>` with `contents`, readable only by us.
+    pub(crate) fn write(name: &str, contents: &[u8]) -> Result<Self, OpenSkillError> {
+        let dir = artifacts_dir();
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "{}-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&path)?;
+        live()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(path.clone());
+        // Registered first, so the guard removes a partly written file too.
+        let artifact = Self { path };
+        file.write_all(contents)?;
+        file.flush()?;
+        Ok(artifact)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempArtifact {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+        live()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.path);
+    }
+}
+
+/// Remove the temporary artifacts of processes that are no longer running,
+/// and artifacts of this process that nothing uses anymore. Returns how
+/// many files were removed.
+pub fn cleanup_orphaned_artifacts() -> usize {
+    let Ok(entries) = fs::read_dir(artifacts_dir()) else {
+        return 0;
+    };
+    let own_pid = std::process::id();
+    let live = live().lock().unwrap_or_else(PoisonError::into_inner);
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(pid) = owner(&path) else {
+            continue;
+        };
+        let orphaned = if pid == own_pid {
+            !live.contains(&path)
+        } else {
+            !process_alive(pid)
+        };
+        if orphaned && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Run [`cleanup_orphaned_artifacts`] once per process.
+pub(crate) fn cleanup_once() {
+    static SWEPT: std::sync::Once = std::sync::Once::new();
+    SWEPT.call_once(|| {
+        cleanup_orphaned_artifacts();
+    });
+}
+
+/// Remove all artifacts of process `pid`, which is known to be gone.
+pub(crate) fn remove_artifacts_of(pid: u32) {
+    let Ok(entries) = fs::read_dir(artifacts_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if owner(&path) == Some(pid) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Process that created the artifact at `path`.
+fn owner(path: &Path) -> Option<u32> {
+    path.file_name()?.to_str()?.split('-').next()?.parse().ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    // SAFETY: signal 0 only checks whether the process exists.
+    unsafe {
+        libc::kill(pid as libc::pid_t, 0) == 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+}
+
+/// Without a way to check, every owner counts as running.
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_is_removed_on_drop_and_orphans_are_swept() {
+        let artifact = TempArtifact::write("profile.sb", b"(version 1)").unwrap();
+        let path = artifact.path().to_path_buf();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "(version 1)");
+        assert_eq!(owner(&path), Some(std::process::id()));
+
+        // A live artifact survives the sweep; one from a dead process does not.
+        let orphan = artifacts_dir().join(format!("{}-0-profile.sb", u32::MAX / 2));
+        fs::write(&orphan, "").unwrap();
+        cleanup_orphaned_artifacts();
+        assert!(path.exists());
+        assert!(!orphan.exists());
+
+        drop(artifact);
+        assert!(!path.exists());
+    }
+}
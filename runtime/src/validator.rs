@@ -1,5 +1,6 @@
 //! Validation for Claude Skills.
 
+use crate::entrypoint::check_manifest_entrypoint;
 use crate::errors::OpenSkillError;
 use crate::manifest::{constraints, SkillManifest};
 use crate::registry::Skill;
@@ -22,6 +23,11 @@ pub fn validate_manifest(manifest: &SkillManifest) -> Result<(), OpenSkillError>
     // Validate description
     validate_description(&manifest.description)?;
     
+    // Validate version if present
+    if let Some(ref version) = manifest.version {
+        crate::skill_version::parse_version(version)?;
+    }
+
     // Validate context value if present
     if let Some(ref ctx) = manifest.context {
         if ctx != "fork" {
@@ -31,6 +37,13 @@ pub fn validate_manifest(manifest: &SkillManifest) -> Result<(), OpenSkillError>
             )));
         }
     }
+
+    if let Some(ref schema) = manifest.output_schema {
+        crate::output_schema::check_schema(schema)?;
+    }
+    crate::output_converters::check(&manifest.output_converters)?;
+    crate::manifest::check_examples(&manifest.examples)?;
+    crate::workspace_import::check_workspace_files(&manifest.workspace_files)?;
     
     Ok(())
 }
@@ -248,6 +261,30 @@ pub fn validate_skill_path(path: &Path) -> ValidationResult {
         warnings.push("Description is long; consider shortening for better discovery".to_string());
     }
 
+    if let Some(entrypoint) = &parsed.manifest.entrypoint {
+        if let Err(err) = check_manifest_entrypoint(path, entrypoint) {
+            errors.push(err.to_string());
+        }
+    }
+
+    if let Some(schema) = &parsed.manifest.output_schema {
+        if let Err(err) = crate::output_schema::check_schema(schema) {
+            errors.push(err.to_string());
+        }
+    }
+
+    if let Err(err) = crate::output_converters::check(&parsed.manifest.output_converters) {
+        errors.push(err.to_string());
+    }
+    if let Err(err) = crate::manifest::check_examples(&parsed.manifest.examples) {
+        errors.push(err.to_string());
+    }
+    if let Err(err) =
+        crate::workspace_import::check_workspace_files(&parsed.manifest.workspace_files)
+    {
+        errors.push(err.to_string());
+    }
+
     let has_wasm = find_wasm_module(path);
     let stats = ValidationStats {
         name: effective_name.to_string(),
@@ -414,7 +451,10 @@ mod tests {
             name: "test-skill".to_string(),
             description: "Test".to_string(),
             context: Some("invalid".to_string()),
+            version: None,
             allowed_tools: None,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
             model: None,
             agent: None,
             hooks: None,
@@ -424,6 +464,13 @@ mod tests {
             metadata: None,
             requires: None,
             actions: None,
+            sandbox: None,
+            entrypoint: None,
+            writes_skill_root: false,
+            output_schema: None,
+            output_converters: Vec::new(),
+            examples: Vec::new(),
+            workspace_files: Vec::new(),
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -435,7 +482,10 @@ mod tests {
             name: "test-skill".to_string(),
             description: "Test".to_string(),
             context: Some("fork".to_string()),
+            version: None,
             allowed_tools: None,
+            allowed_hosts: Vec::new(),
+            allowed_ports: Vec::new(),
             model: None,
             agent: None,
             hooks: None,
@@ -445,6 +495,13 @@ mod tests {
             metadata: None,
             requires: None,
             actions: None,
+            sandbox: None,
+            entrypoint: None,
+            writes_skill_root: false,
+            output_schema: None,
+            output_converters: Vec::new(),
+            examples: Vec::new(),
+            workspace_files: Vec::new(),
         };
         assert!(validate_manifest(&manifest).is_ok());
     }
@@ -0,0 +1,143 @@
+//! Wasmtime engine shared by a runtime's WASM executions.
+//!
+//! Creating an engine, populating a WASI linker and compiling a component
+//! each cost far more than running a small skill, so a runtime creates its
+//! engine on first use and keeps it, along with the linker and every
+//! compiled component (revalidated against the file's size and mtime).
+//! By default the engine is process-wide: every runtime with the same
+//! [`WasmEngineConfig`] uses one engine, so hosts running many runtimes
+//! compile each component once and keep one instance pool.
+//! Instances are never reused: each execution gets a fresh store, WASI
+//! context and instance, so no state leaks between runs.
+
+use serde::{Deserialize, Serialize};
+
+/// Default number of pooled component instances.
+pub const DEFAULT_WASM_MAX_INSTANCES: u32 = 16;
+
+/// Engine settings for WASM execution.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WasmEngineConfig {
+    /// Pre-reserve instance slots with wasmtime's pooling allocator, making
+    /// instantiation cheaper. Falls back to on-demand allocation if the
+    /// address-space reservation fails.
+    pub pooling_allocator: bool,
+    /// Component instances the pool holds, i.e. concurrent WASM executions.
+    pub max_instances: u32,
+    /// Keep compiled components under `~/.cache/openskills/modules/` so
+    /// new runtimes skip compilation too.
+    pub module_cache: bool,
+    /// Share one engine with every runtime in the process that uses the
+    /// same settings (the pool's `max_instances` is then shared too). When
+    /// `false` the runtime gets an engine of its own.
+    pub shared: bool,
+}
+
+impl Default for WasmEngineConfig {
+    fn default() -> Self {
+        Self {
+            pooling_allocator: true,
+            max_instances: DEFAULT_WASM_MAX_INSTANCES,
+            module_cache: true,
+            shared: true,
+        }
+    }
+}
+
+/// A runtime's engine, created on first use.
+#[derive(Default)]
+pub(crate) struct WasmEngineHandle {
+    #[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+    config: WasmEngineConfig,
+    #[cfg(feature = "wasm")]
+    engine: std::sync::Mutex<Option<std::sync::Arc<crate::wasm_runner::WasmEngine>>>,
+}
+
+impl std::fmt::Debug for WasmEngineHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmEngineHandle")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmEngineHandle {
+    pub(crate) fn new(config: WasmEngineConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// The shared engine, creating it if needed.
+    #[cfg(feature = "wasm")]
+    pub(crate) fn get(
+        &self,
+    ) -> Result<std::sync::Arc<crate::wasm_runner::WasmEngine>, crate::errors::OpenSkillError> {
+        let mut engine = self
+            .engine
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(engine) = engine.as_ref() {
+            return Ok(engine.clone());
+        }
+        let created = if self.config.shared {
+            shared_engine(&self.config)?
+        } else {
+            std::sync::Arc::new(crate::wasm_runner::WasmEngine::new(&self.config)?)
+        };
+        *engine = Some(created.clone());
+        Ok(created)
+    }
+}
+
+/// The process-wide engine for `config`, creating it if needed. Engines are
+/// kept for the life of the process.
+#[cfg(feature = "wasm")]
+fn shared_engine(
+    config: &WasmEngineConfig,
+) -> Result<std::sync::Arc<crate::wasm_runner::WasmEngine>, crate::errors::OpenSkillError> {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    type Engines = Mutex<HashMap<WasmEngineConfig, Arc<crate::wasm_runner::WasmEngine>>>;
+    static ENGINES: OnceLock<Engines> = OnceLock::new();
+
+    let mut engines = ENGINES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(engine) = engines.get(config) {
+        return Ok(engine.clone());
+    }
+    let created = Arc::new(crate::wasm_runner::WasmEngine::new(config)?);
+    engines.insert(config.clone(), created.clone());
+    Ok(created)
+}
+
+#[cfg(all(test, feature = "wasm"))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_shared_engine_is_reused_across_handles() {
+        let config = WasmEngineConfig {
+            max_instances: 3,
+            module_cache: false,
+            ..Default::default()
+        };
+        let first = WasmEngineHandle::new(config.clone()).get().unwrap();
+        let second = WasmEngineHandle::new(config.clone()).get().unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let private = WasmEngineHandle::new(WasmEngineConfig {
+            shared: false,
+            ..config
+        });
+        let own = private.get().unwrap();
+        assert!(!Arc::ptr_eq(&first, &own));
+        assert!(Arc::ptr_eq(&own, &private.get().unwrap()));
+    }
+}
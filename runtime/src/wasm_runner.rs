@@ -2,44 +2,551 @@
 //!
 //! Provides capability-based sandboxing as an alternative to OS-level
 //! sandboxing (seatbelt on macOS, seccomp on Linux).
+//!
+//! Two component shapes are accepted, told apart by their exports before
+//! anything is instantiated:
+//! - the typed `openskills:skill` world (`wit/skill.wit`), whose `run`
+//!   export takes and returns JSON directly;
+//! - a `wasi:cli/command`, which reads JSON input from stdin and writes its
+//!   JSON output to stdout.
+//!
+//! Components may also import `wasi:http/outgoing-handler`. Requests are only
+//! sent when the skill has network access (`WebSearch`/`Fetch`) and the
+//! destination passes its `allowed-hosts`/`allowed-ports`; every request,
+//! sent or denied, is recorded in the audit trail.
+//!
+//! Both shapes can import `openskills:skill/host` (workspace file access,
+//! logging, `http-fetch`). Each host call is checked by the
+//! [`PermissionEnforcer`] and recorded in the audit trail.
 
-use crate::audit::ExecutionStatus;
+use crate::audit::{CapabilityDowngrade, DowngradeReason, ExecutionStatus, HostCall, NetworkRequest};
+use crate::binary_output::decode_stdout;
+use crate::egress::EgressPolicy;
 use crate::errors::OpenSkillError;
+use crate::events::{ExecutionEvents, OutputStream};
 use crate::executor::ExecutionArtifacts;
+use crate::metrics::{SetupTimer, SetupTimings};
+use crate::module_cache::{default_cache_dir, load_component};
 use crate::sandbox_mode::SandboxMode;
 use crate::permissions::PermissionEnforcer;
+use crate::python_wasm::{is_componentize_py, PythonWasm, GUEST_STDLIB_DIR};
 use crate::registry::Skill;
+use crate::wasm_engine::WasmEngineConfig;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, Limited};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
 use tokio::io::AsyncWrite;
-use wasmtime::{Config, Engine, Store};
-use wasmtime::component::{Component, Linker as ComponentLinker, ResourceTable};
+use wasmtime::{
+    Config, Engine, InstanceAllocationStrategy, PoolingAllocationConfig, Store, UpdateDeadline,
+};
+use wasmtime::component::{
+    Component, HasSelf, InstancePre, Linker as ComponentLinker, ResourceTable,
+};
 use wasmtime_wasi::{DirPerms, FilePerms, WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
+use wasmtime_wasi_http::bindings::http::types::ErrorCode;
+use wasmtime_wasi_http::body::HyperOutgoingBody;
+use wasmtime_wasi_http::types::{
+    default_send_request, default_send_request_handler, HostFutureIncomingResponse,
+    IncomingResponse, OutgoingRequestConfig,
+};
+use wasmtime_wasi_http::{HttpResult, WasiHttpCtx, WasiHttpView};
+
+mod skill_world {
+    wasmtime::component::bindgen!({
+        path: "wit/skill.wit",
+        world: "skill",
+        imports: { default: async },
+        exports: { default: async },
+    });
+}
+
+use skill_world::openskills::skill::host::{self as skill_host, HttpRequest, HttpResponse, LogLevel};
+
+/// Interval of the engine's epoch ticker; each execution checks its own
+/// deadline and cancellation on every tick, so concurrent executions on one
+/// engine time out independently.
+const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Pool slots per component instance. Components built with the preview1
+/// adapter contain several core instances, memories and tables.
+const POOL_CORE_INSTANCES_PER_COMPONENT: u32 = 16;
+const POOL_MEMORIES_PER_COMPONENT: u32 = 4;
+const POOL_TABLES_PER_COMPONENT: u32 = 16;
+
+/// Upper bound on audited HTTP requests per execution; later requests are
+/// still checked, just not recorded.
+const MAX_RECORDED_REQUESTS: usize = 256;
+
+/// Upper bound on audited host function calls per execution.
+const MAX_RECORDED_HOST_CALLS: usize = 256;
+
+/// Largest response body `http-fetch` returns.
+const MAX_FETCH_BODY: usize = 16 * 1024 * 1024;
+
+/// Interface a component is executed through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WasmInterface {
+    /// `openskills:skill` world: `run(input: json) -> result<json, string>`.
+    Skill,
+    /// `wasi:cli/command`: JSON over stdin/stdout.
+    Command,
+}
+
+/// How a component finished.
+enum RunOutcome {
+    /// `wasi:cli/run` returned.
+    Exited(Result<(), ()>),
+    /// The typed `run` export returned.
+    Returned(Result<String, String>),
+}
+
+/// Classify `component` by its exports, rejecting components that implement
+/// neither supported interface.
+fn component_interface(engine: &Engine, component: &Component) -> Result<WasmInterface, OpenSkillError> {
+    let ty = component.component_type();
+    let exports: Vec<&str> = ty.exports(engine).map(|(name, _)| name).collect();
+    if exports.contains(&"run") && exports.contains(&"metadata") {
+        Ok(WasmInterface::Skill)
+    } else if exports.iter().any(|name| name.starts_with("wasi:cli/run@")) {
+        Ok(WasmInterface::Command)
+    } else {
+        Err(OpenSkillError::WasmError(format!(
+            "Incompatible WASM component: expected the openskills:skill world \
+(`run` and `metadata` exports) or a wasi:cli command, found exports [{}]",
+            exports.join(", ")
+        )))
+    }
+}
+
+struct WasiComponentState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+    http: WasiHttpCtx,
+    network: HttpEgress,
+    host: SkillHost,
+}
+
+/// Outbound HTTP policy of one execution, and the requests it has seen.
+struct HttpEgress {
+    /// `None` when the skill has no network access.
+    policy: Option<EgressPolicy>,
+    requests: Vec<NetworkRequest>,
+}
+
+impl HttpEgress {
+    fn new(policy: Option<EgressPolicy>) -> Self {
+        Self {
+            policy,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Check a request against the policy and record it.
+    fn admit(&mut self, method: &str, uri: &hyper::Uri, use_tls: bool) -> bool {
+        let host = uri.host().unwrap_or_default().to_string();
+        let port = uri.port_u16().unwrap_or(if use_tls { 443 } else { 80 });
+        let allowed = self
+            .policy
+            .as_ref()
+            .is_some_and(|policy| policy.allows(&host, port));
+        if self.requests.len() < MAX_RECORDED_REQUESTS {
+            self.requests.push(NetworkRequest {
+                method: method.to_string(),
+                url: uri.to_string(),
+                host,
+                port,
+                allowed,
+            });
+        }
+        allowed
+    }
+}
+
+impl WasiView for WasiComponentState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.ctx,
+            table: &mut self.table,
+        }
+    }
+}
+
+/// State behind the `openskills:skill/host` functions.
+struct SkillHost {
+    enforcer: PermissionEnforcer,
+    workspace: Option<PathBuf>,
+    /// The execution's captured stderr, which `log` appends to.
+    stderr: Arc<Mutex<Vec<u8>>>,
+    /// Connect, first-byte and between-bytes timeout of `http-fetch`.
+    fetch_timeout: Duration,
+    calls: Vec<HostCall>,
+}
+
+impl SkillHost {
+    fn record(&mut self, function: &str, target: &str, allowed: bool) {
+        if self.calls.len() < MAX_RECORDED_HOST_CALLS {
+            self.calls.push(HostCall {
+                function: function.to_string(),
+                target: target.to_string(),
+                allowed,
+            });
+        }
+    }
+}
+
+impl skill_host::Host for WasiComponentState {
+    async fn read_workspace_file(&mut self, path: String) -> Result<Vec<u8>, String> {
+        let host = &mut self.host;
+        let resolved = host.enforcer.workspace_read_path(host.workspace.as_deref(), &path);
+        host.record("read-workspace-file", &path, resolved.is_ok());
+        std::fs::read(resolved.map_err(|e| e.to_string())?).map_err(|e| e.to_string())
+    }
+
+    async fn write_workspace_file(&mut self, path: String, contents: Vec<u8>) -> Result<(), String> {
+        let host = &mut self.host;
+        let resolved = host.enforcer.workspace_write_path(host.workspace.as_deref(), &path);
+        host.record("write-workspace-file", &path, resolved.is_ok());
+        let resolved = resolved.map_err(|e| e.to_string())?;
+        if let Some(parent) = resolved.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(resolved, contents).map_err(|e| e.to_string())
+    }
+
+    async fn log(&mut self, level: LogLevel, message: String) {
+        let level = match level {
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        };
+        self.host.record("log", level, true);
+        self.host
+            .stderr
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .extend_from_slice(format!("[{level}] {message}\n").as_bytes());
+    }
+
+    async fn http_fetch(&mut self, request: HttpRequest) -> Result<HttpResponse, String> {
+        let uri: hyper::Uri = match request.url.parse() {
+            Ok(uri) => uri,
+            Err(e) => {
+                self.host.record("http-fetch", &request.url, false);
+                return Err(format!("invalid URL {}: {e}", request.url));
+            }
+        };
+        let use_tls = match uri.scheme_str() {
+            Some("https") => true,
+            Some("http") => false,
+            _ => {
+                self.host.record("http-fetch", &request.url, false);
+                return Err(format!("unsupported URL scheme: {}", request.url));
+            }
+        };
+        let allowed = self.network.admit(&request.method, &uri, use_tls);
+        self.host.record("http-fetch", &request.url, allowed);
+        if !allowed {
+            return Err(format!("network access denied: {}", request.url));
+        }
+        fetch(request, uri, use_tls, self.host.fetch_timeout).await
+    }
+}
+
+/// Send an admitted `http-fetch` request and read the whole response.
+async fn fetch(
+    request: HttpRequest,
+    uri: hyper::Uri,
+    use_tls: bool,
+    timeout: Duration,
+) -> Result<HttpResponse, String> {
+    let method = hyper::Method::from_bytes(request.method.as_bytes())
+        .map_err(|e| format!("invalid method {}: {e}", request.method))?;
+    let authority = uri.authority().map(|a| a.to_string()).unwrap_or_default();
+    let mut builder = hyper::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(hyper::header::HOST, authority);
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_str());
+    }
+    let body = Full::new(Bytes::from(request.body.unwrap_or_default()))
+        .map_err(|never| match never {})
+        .boxed_unsync();
+    let request = builder.body(body).map_err(|e| e.to_string())?;
+    let config = OutgoingRequestConfig {
+        use_tls,
+        connect_timeout: timeout,
+        first_byte_timeout: timeout,
+        between_bytes_timeout: timeout,
+    };
+
+    // `_worker` drives the connection until the body has been read.
+    let IncomingResponse {
+        resp, worker: _worker, ..
+    } = default_send_request_handler(request, config)
+        .await
+        .map_err(|e| format!("request failed: {e}"))?;
+    let (parts, body) = resp.into_parts();
+    let body = Limited::new(body, MAX_FETCH_BODY)
+        .collect()
+        .await
+        .map_err(|e| format!("reading response failed: {e}"))?
+        .to_bytes();
+    Ok(HttpResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+            })
+            .collect(),
+        body: body.to_vec(),
+    })
+}
+
+impl WasiHttpView for WasiComponentState {
+    fn ctx(&mut self) -> &mut WasiHttpCtx {
+        &mut self.http
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn send_request(
+        &mut self,
+        request: hyper::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        if !self
+            .network
+            .admit(request.method().as_str(), request.uri(), config.use_tls)
+        {
+            return Err(ErrorCode::HttpRequestDenied.into());
+        }
+        Ok(default_send_request(request, config))
+    }
+}
+
+/// A compiled component with its imports resolved against the linker.
+struct LoadedComponent {
+    modified: Option<SystemTime>,
+    len: u64,
+    interface: WasmInterface,
+    /// Built by componentize-py, so it needs the Python guest environment.
+    componentize_py: bool,
+    pre: InstancePre<WasiComponentState>,
+}
+
+/// Engine, WASI linker and compiled components shared by a runtime's WASM
+/// executions.
+pub(crate) struct WasmEngine {
+    engine: Engine,
+    linker: ComponentLinker<WasiComponentState>,
+    cache_dir: Option<PathBuf>,
+    components: Mutex<HashMap<PathBuf, Arc<LoadedComponent>>>,
+    /// Time it took to create the engine, until the first execution reports it.
+    startup: Mutex<Option<Duration>>,
+}
+
+impl WasmEngine {
+    pub(crate) fn new(config: &WasmEngineConfig) -> Result<Self, OpenSkillError> {
+        let started = Instant::now();
+        let engine = build_engine(config)?;
+        let mut linker: ComponentLinker<WasiComponentState> = ComponentLinker::new(&engine);
+
+        // Add WASI 0.3 (p3) interfaces
+        wasmtime_wasi::p3::add_to_linker(&mut linker).map_err(|e| {
+            OpenSkillError::WasmError(format!("Failed to add WASI 0.3 (p3) interfaces to linker: {e}"))
+        })?;
+
+        // Components created with wasm-tools component new --adapt wasi_snapshot_preview1
+        // import WASI 0.2 CLI interfaces (wasi:cli/*@0.2.1). We need to add p2 interfaces
+        // alongside p3 to support these components. The p2 feature is enabled in workspace Cargo.toml.
+        //
+        // Add WASI 0.2 (p2) interfaces using add_to_linker_async (since we're using async component model).
+        // This provides the wasi:cli/*@0.2.1 interfaces that components built with the adapter require.
+        wasmtime_wasi::p2::add_to_linker_async(&mut linker).map_err(|e| {
+            OpenSkillError::WasmError(format!("Failed to add WASI 0.2 (p2) interfaces to linker: {e}"))
+        })?;
+
+        // wasi:http outgoing requests; whether they are sent is decided per
+        // execution in `WasiHttpView::send_request`.
+        wasmtime_wasi_http::add_only_http_to_linker_async(&mut linker).map_err(|e| {
+            OpenSkillError::WasmError(format!("Failed to add wasi:http interfaces to linker: {e}"))
+        })?;
+
+        skill_world::Skill::add_to_linker::<_, HasSelf<_>>(&mut linker, |state| state).map_err(|e| {
+            OpenSkillError::WasmError(format!("Failed to add openskills:skill/host to linker: {e}"))
+        })?;
+
+        start_epoch_ticker(&engine);
+        Ok(Self {
+            engine,
+            linker,
+            cache_dir: if config.module_cache { default_cache_dir() } else { None },
+            components: Mutex::new(HashMap::new()),
+            startup: Mutex::new(Some(started.elapsed())),
+        })
+    }
+
+    /// Engine creation time, returned to the first caller only.
+    fn take_startup(&self) -> Option<Duration> {
+        self.startup
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    /// Compile (or reuse) the component at `path`, returning the setup
+    /// phase that describes where it came from.
+    fn load(&self, path: &Path) -> Result<(Arc<LoadedComponent>, &'static str), OpenSkillError> {
+        let metadata = std::fs::metadata(path)?;
+        let (modified, len) = (metadata.modified().ok(), metadata.len());
+        let mut components = self
+            .components
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(loaded) = components.get(path) {
+            if loaded.modified == modified && loaded.len == len {
+                return Ok((loaded.clone(), "component_reused"));
+            }
+        }
+
+        // WASI 0.3 / WASIp3 component execution only.
+        let (component, from_cache) = load_component(&self.engine, path, self.cache_dir.as_deref()).map_err(|e| {
+            OpenSkillError::WasmError(format!(
+                "Invalid WASM artifact (expected a WASI 0.3 component): {e}. \
+OpenSkills runtime does not support legacy core-module WASM artifacts."
+            ))
+        })?;
+        let interface = component_interface(&self.engine, &component)?;
+        let componentize_py = is_componentize_py(&std::fs::read(path)?);
+        let pre = self.linker.instantiate_pre(&component).map_err(|e| {
+            OpenSkillError::WasmError(format!("Component imports are not supported: {e}"))
+        })?;
+        let loaded = Arc::new(LoadedComponent {
+            modified,
+            len,
+            interface,
+            componentize_py,
+            pre,
+        });
+        components.insert(path.to_path_buf(), loaded.clone());
+        let phase = if from_cache { "component_cached" } else { "component_compiled" };
+        Ok((loaded, phase))
+    }
+}
+
+fn build_engine(settings: &WasmEngineConfig) -> Result<Engine, OpenSkillError> {
+    let mut config = Config::new();
+    config.epoch_interruption(true);
+    // Fuel is metered on every store; unmetered executions just get an
+    // unlimited budget.
+    config.consume_fuel(true);
+    config.async_support(true);
+    config.wasm_component_model_async(true);
+
+    if settings.pooling_allocator {
+        let instances = settings.max_instances.max(1);
+        let mut pool = PoolingAllocationConfig::default();
+        pool.total_component_instances(instances)
+            .total_core_instances(instances.saturating_mul(POOL_CORE_INSTANCES_PER_COMPONENT))
+            .total_memories(instances.saturating_mul(POOL_MEMORIES_PER_COMPONENT))
+            .total_tables(instances.saturating_mul(POOL_TABLES_PER_COMPONENT))
+            .total_stacks(instances)
+            .max_tables_per_module(POOL_TABLES_PER_COMPONENT);
+        config.allocation_strategy(InstanceAllocationStrategy::Pooling(pool));
+        if let Ok(engine) = Engine::new(&config) {
+            return Ok(engine);
+        }
+        // The pool reserves its address space up front, which can fail
+        // under tight virtual memory limits.
+        config.allocation_strategy(InstanceAllocationStrategy::OnDemand);
+    }
+    Engine::new(&config).map_err(|e| OpenSkillError::WasmError(format!("Engine init failed: {e}")))
+}
+
+/// Wrap a wasmtime error, reporting a passed epoch deadline as a timeout,
+/// an empty fuel tank as exhaustion and a cancellation as such.
+fn run_error(context: &str, e: wasmtime::Error) -> OpenSkillError {
+    if let Some(OpenSkillError::Cancelled) = e.downcast_ref::<OpenSkillError>() {
+        return OpenSkillError::Cancelled;
+    }
+    match e.downcast_ref::<wasmtime::Trap>() {
+        Some(wasmtime::Trap::Interrupt) => OpenSkillError::Timeout,
+        Some(wasmtime::Trap::OutOfFuel) => OpenSkillError::FuelExhausted,
+        _ => OpenSkillError::WasmError(format!("{context}: {e}")),
+    }
+}
+
+/// Approved tools whose subprocess grant a component cannot use.
+pub(crate) fn process_downgrades(enforcer: &PermissionEnforcer) -> Vec<CapabilityDowngrade> {
+    enforcer
+        .process_tools()
+        .iter()
+        .map(|tool| {
+            CapabilityDowngrade::new(
+                tool,
+                DowngradeReason::Platform,
+                "WASM components cannot spawn subprocesses",
+            )
+        })
+        .collect()
+}
+
+/// Advance the engine's epoch every [`EPOCH_TICK`] until it is dropped.
+fn start_epoch_ticker(engine: &Engine) {
+    let engine = engine.weak();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(EPOCH_TICK);
+        match engine.upgrade() {
+            Some(engine) => engine.increment_epoch(),
+            None => break,
+        }
+    });
+}
+
+/// Create the engine and compile the component at `wasm_path` ahead of the
+/// first execution. Nothing is instantiated: executions still start from a
+/// fresh instance.
+pub fn precompile_wasm(engine: &WasmEngine, skill: &Skill, wasm_path: &str) -> Result<SetupTimings, OpenSkillError> {
+    let mut timer = SetupTimer::start();
+    if let Some(startup) = engine.take_startup() {
+        timer.record("engine", startup);
+    }
+    let (_, load_phase) = engine.load(&skill.root.join(wasm_path))?;
+    timer.mark(load_phase);
+    Ok(timer.into_timings("wasm"))
+}
 
 /// Execute a WASM module with WASI sandbox.
+///
+/// `max_fuel` bounds the instructions executed, independently of the
+/// wall-clock `timeout_ms`.
+#[allow(clippy::too_many_arguments)]
 pub fn execute_wasm(
+    engine: &WasmEngine,
     skill: &Skill,
     wasm_path: &str,
     input: Value,
     timeout_ms: u64,
+    max_fuel: Option<u64>,
     enforcer: &PermissionEnforcer,
     workspace_dir: Option<&std::path::Path>,
+    python: Option<&PythonWasm>,
 ) -> Result<ExecutionArtifacts, OpenSkillError> {
     let wasm_full_path = skill.root.join(wasm_path);
     let input_json = serde_json::to_string(&input)?;
 
-    // Configure wasmtime with epoch interruption for timeout
-    let mut config = Config::new();
-    config.epoch_interruption(true);
-    config.async_support(true);
-    config.wasm_component_model_async(true);
-
-    let engine = Engine::new(&config)
-        .map_err(|e| OpenSkillError::WasmError(format!("Engine init failed: {e}")))?;
-
     // Build WASI context with capability-based permissions.
     //
     // NOTE: OpenSkills runtime is WASI 0.3 (WASIp3) / component-model-only.
@@ -87,8 +594,7 @@ pub fn execute_wasm(
                         Err(e) => Poll::Ready(Err(e)),
                     }
                 }
-                Err(_) => Poll::Ready(Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
+                Err(_) => Poll::Ready(Err(std::io::Error::other(
                     "stdin lock poisoned",
                 ))),
             }
@@ -103,9 +609,10 @@ pub fn execute_wasm(
         }
     }
 
-    // Minimal in-memory stdout/stderr capture stream implementation.
+    // Minimal in-memory stdout/stderr capture stream implementation; writes
+    // are also sent as output chunks when the host streams events.
     #[derive(Clone)]
-    struct SharedVecStdout(Arc<Mutex<Vec<u8>>>);
+    struct SharedVecStdout(Arc<Mutex<Vec<u8>>>, ExecutionEvents, OutputStream);
 
     impl wasmtime_wasi::cli::IsTerminal for SharedVecStdout {
         fn is_terminal(&self) -> bool {
@@ -115,6 +622,8 @@ pub fn execute_wasm(
 
     struct SharedVecWriter {
         buf: Arc<Mutex<Vec<u8>>>,
+        events: ExecutionEvents,
+        stream: OutputStream,
     }
 
     impl AsyncWrite for SharedVecWriter {
@@ -126,10 +635,10 @@ pub fn execute_wasm(
             match self.buf.lock() {
                 Ok(mut guard) => {
                     guard.extend_from_slice(data);
+                    self.events.output(self.stream, data);
                     Poll::Ready(Ok(data.len()))
                 }
-                Err(_) => Poll::Ready(Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
+                Err(_) => Poll::Ready(Err(std::io::Error::other(
                     "stdout lock poisoned",
                 ))),
             }
@@ -148,6 +657,8 @@ pub fn execute_wasm(
         fn async_stream(&self) -> Box<dyn tokio::io::AsyncWrite + Send + Sync> {
             Box::new(SharedVecWriter {
                 buf: self.0.clone(),
+                events: self.1.clone(),
+                stream: self.2,
             })
         }
     }
@@ -156,14 +667,23 @@ pub fn execute_wasm(
         // Provide stdin with input JSON for WASM modules that read from stdin
         builder.stdin(SharedVecStdin { data: stdin_buf.clone() });
         // Capture stdout/stderr for audit (default is "empty" sinks in wasmtime-wasi).
-        builder.stdout(SharedVecStdout(stdout_buf.clone()));
-        builder.stderr(SharedVecStdout(stderr_buf.clone()));
+        builder.stdout(SharedVecStdout(
+            stdout_buf.clone(),
+            enforcer.events().clone(),
+            OutputStream::Stdout,
+        ));
+        builder.stderr(SharedVecStdout(
+            stderr_buf.clone(),
+            enforcer.events().clone(),
+            OutputStream::Stderr,
+        ));
 
         // Inject skill metadata as environment variables
         builder.env("SKILL_ID", &skill.id);
         builder.env("SKILL_NAME", &skill.manifest.name);
         builder.env("SKILL_INPUT", &input_json);
-        builder.env("TIMEOUT_MS", &timeout_ms.to_string());
+        builder.env("TIMEOUT_MS", timeout_ms.to_string());
+        builder.env("TERM", "dumb");
 
         // Inject workspace directory if configured
         if let Some(workspace) = workspace_dir {
@@ -172,7 +692,7 @@ pub fn execute_wasm(
 
         // Inject random seed if configured
         if let Some(seed) = enforcer.random_seed() {
-            builder.env("RANDOM_SEED", &seed.to_string());
+            builder.env("RANDOM_SEED", seed.to_string());
         }
 
         // Inject allowed environment variables from host
@@ -181,6 +701,9 @@ pub fn execute_wasm(
                 builder.env(key, &val);
             }
         }
+        for (key, val) in enforcer.extra_env() {
+            builder.env(key, val);
+        }
 
         for dir in &read_paths {
             if dir.exists() && dir.is_dir() {
@@ -242,84 +765,106 @@ pub fn execute_wasm(
         }
     };
 
-    // WASI 0.3 / WASIp3 component execution only.
-    let component = Component::from_file(&engine, &wasm_full_path).map_err(|e| {
-        OpenSkillError::WasmError(format!(
-            "Invalid WASM artifact (expected a WASI 0.3 component): {e}. \
-OpenSkills runtime does not support legacy core-module WASM artifacts."
-        ))
-    })?;
-
-    struct WasiComponentState {
-        ctx: WasiCtx,
-        table: ResourceTable,
-    }
-
-    impl WasiView for WasiComponentState {
-        fn ctx(&mut self) -> WasiCtxView<'_> {
-            WasiCtxView {
-                ctx: &mut self.ctx,
-                table: &mut self.table,
-            }
-        }
+    let mut timer = SetupTimer::start();
+    if let Some(startup) = engine.take_startup() {
+        timer.record("engine", startup);
     }
+    let (loaded, load_phase) = engine.load(&wasm_full_path)?;
+    timer.mark(load_phase);
 
     let mut component_builder = WasiCtxBuilder::new();
     configure_wasi_builder(&mut component_builder);
+    let python = python
+        .cloned()
+        .or_else(|| loaded.componentize_py.then(PythonWasm::componentize_py));
+    if let Some(python) = &python {
+        let argv = python.argv();
+        if !argv.is_empty() {
+            component_builder.args(&argv);
+        }
+        for (key, val) in python.env() {
+            component_builder.env(key, val);
+        }
+        if let Some(stdlib) = &python.stdlib {
+            let _ = component_builder.preopened_dir(
+                stdlib,
+                GUEST_STDLIB_DIR,
+                DirPerms::READ,
+                FilePerms::READ,
+            );
+        }
+    }
     let component_ctx = component_builder.build();
 
-    let mut linker: ComponentLinker<WasiComponentState> = ComponentLinker::new(&engine);
-    
-    // Add WASI 0.3 (p3) interfaces
-    wasmtime_wasi::p3::add_to_linker(&mut linker).map_err(|e| {
-        OpenSkillError::WasmError(format!("Failed to add WASI 0.3 (p3) interfaces to linker: {e}"))
-    })?;
-    
-    // Components created with wasm-tools component new --adapt wasi_snapshot_preview1
-    // import WASI 0.2 CLI interfaces (wasi:cli/*@0.2.1). We need to add p2 interfaces
-    // alongside p3 to support these components. The p2 feature is enabled in workspace Cargo.toml.
-    // 
-    // Add WASI 0.2 (p2) interfaces using add_to_linker_async (since we're using async component model).
-    // This provides the wasi:cli/*@0.2.1 interfaces that components built with the adapter require.
-    wasmtime_wasi::p2::add_to_linker_async(&mut linker).map_err(|e| {
-        OpenSkillError::WasmError(format!("Failed to add WASI 0.2 (p2) interfaces to linker: {e}"))
-    })?;
-
     let mut store = Store::new(
-        &engine,
+        &engine.engine,
         WasiComponentState {
             ctx: component_ctx,
             table: ResourceTable::new(),
+            http: WasiHttpCtx::new(),
+            network: HttpEgress::new(enforcer.network_enabled().then(|| {
+                EgressPolicy::new(&skill.manifest.allowed_hosts, &skill.manifest.allowed_ports)
+            })),
+            host: SkillHost {
+                enforcer: enforcer.clone(),
+                workspace: workspace_dir.map(Path::to_path_buf),
+                stderr: stderr_buf.clone(),
+                fetch_timeout: Duration::from_millis(timeout_ms),
+                calls: Vec::new(),
+            },
         },
     );
+    // Check for cancellation and the deadline on every epoch tick.
+    let cancellation = enforcer.cancellation().clone();
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms) + EPOCH_TICK;
     store.set_epoch_deadline(1);
-
-    let done = Arc::new(AtomicBool::new(false));
-    let done_for_thread = done.clone();
-    let engine_clone = engine.clone();
-    let timeout_handle = std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_millis(timeout_ms));
-        if !done_for_thread.load(Ordering::Relaxed) {
-            engine_clone.increment_epoch();
+    store.epoch_deadline_callback(move |_| {
+        if cancellation.is_cancelled() {
+            return Err(wasmtime::Error::new(OpenSkillError::Cancelled));
         }
+        if Instant::now() >= deadline {
+            return Ok(UpdateDeadline::Interrupt);
+        }
+        Ok(UpdateDeadline::Continue(1))
     });
+    let fuel_budget = max_fuel.unwrap_or(u64::MAX);
+    store
+        .set_fuel(fuel_budget)
+        .map_err(|e| OpenSkillError::WasmError(format!("Failed to set fuel: {e}")))?;
+    timer.mark("context");
+
+    let mut instantiated_in: Option<Duration> = None;
+    let run_result: Result<RunOutcome, OpenSkillError> = wasmtime_wasi::runtime::in_tokio(async {
+        let instantiate_started = Instant::now();
+        if loaded.interface == WasmInterface::Skill {
+            let instantiated = match skill_world::SkillPre::new(loaded.pre.clone()) {
+                Ok(pre) => pre.instantiate_async(&mut store).await,
+                Err(e) => Err(e),
+            };
+            instantiated_in = Some(instantiate_started.elapsed());
+            let skill = instantiated
+                .map_err(|e| run_error("Component instantiation failed", e))?;
+            let returned = skill
+                .call_run(&mut store, &input_json)
+                .await
+                .map_err(|e| run_error("Component run trapped", e))?;
+            return Ok(RunOutcome::Returned(returned));
+        }
 
-    let run_result: Result<Result<(), ()>, OpenSkillError> = wasmtime_wasi::runtime::in_tokio(async {
         // Try p3 bindings first (for native 0.3 components)
         // If that fails, fall back to p2 bindings (for components built with wasi_snapshot_preview1 adapter)
-        let program_result = match wasmtime_wasi::p3::bindings::Command::instantiate_async(
-            &mut store,
-            &component,
-            &linker,
-        )
-        .await
-        {
+        let p3_command = match wasmtime_wasi::p3::bindings::CommandPre::new(loaded.pre.clone()) {
+            Ok(pre) => pre.instantiate_async(&mut store).await,
+            Err(e) => Err(e),
+        };
+        let program_result = match p3_command {
             Ok(command) => {
+                instantiated_in = Some(instantiate_started.elapsed());
                 // Component is WASI 0.3 - use p3 bindings
                 store
                     .run_concurrent(async move |store| command.wasi_cli_run().call_run(store).await)
                     .await
-                    .map_err(|e| OpenSkillError::WasmError(format!("Component run failed: {e}")))?
+                    .map_err(|e| run_error("Component run failed", e))?
             }
             Err(e) => {
                 // Log the actual error for debugging
@@ -330,13 +875,12 @@ OpenSkills runtime does not support legacy core-module WASM artifacts."
                 // Component is WASI 0.2 - use p2 bindings to instantiate and call run
                 // For WASI CLI command components built with wasi_snapshot_preview1 adapter,
                 // the component exports wasi:cli/run@0.2.x which we need to explicitly invoke.
-                let command = wasmtime_wasi::p2::bindings::Command::instantiate_async(
-                    &mut store,
-                    &component,
-                    &linker,
-                )
-                .await
-                .map_err(|e| OpenSkillError::WasmError(format!("WASI 0.2 component instantiation failed: {e}")))?;
+                let command = match wasmtime_wasi::p2::bindings::CommandPre::new(loaded.pre.clone()) {
+                    Ok(pre) => pre.instantiate_async(&mut store).await,
+                    Err(e) => Err(e),
+                }
+                .map_err(|e| run_error("WASI 0.2 component instantiation failed", e))?;
+                instantiated_in = Some(instantiate_started.elapsed());
 
                 if std::env::var("DEBUG_WASM").is_ok() {
                     eprintln!("[DEBUG_WASM] WASI 0.2 instantiation succeeded, calling run...");
@@ -347,19 +891,25 @@ OpenSkills runtime does not support legacy core-module WASM artifacts."
                     .wasi_cli_run()
                     .call_run(&mut store)
                     .await
-                    .map_err(|e| OpenSkillError::WasmError(format!("WASI 0.2 run failed: {e}")))?;
+                    .map_err(|e| run_error("WASI 0.2 run failed", e))?;
                 Ok(run_result)
             }
         };
 
         let program_result = program_result
-            .map_err(|e| OpenSkillError::WasmError(format!("Component run trapped: {e}")))?;
+            .map_err(|e| run_error("Component run trapped", e))?;
 
-        Ok(program_result)
+        Ok(RunOutcome::Exited(program_result))
     });
+    let fuel_consumed = fuel_budget - store.get_fuel().unwrap_or(0);
+    let network_requests = std::mem::take(&mut store.data_mut().network.requests);
+    let host_calls = std::mem::take(&mut store.data_mut().host.calls);
+    if let Some(instantiate) = instantiated_in {
+        timer.record("instantiate", instantiate);
+    }
 
-    done.store(true, Ordering::Relaxed);
-    let _ = timeout_handle.join();
+    enforcer.events().finish_output(OutputStream::Stdout);
+    enforcer.events().finish_output(OutputStream::Stderr);
 
     // Collect stdout/stderr with proper mutex poisoning handling
     let stdout_bytes: Vec<u8> = match stdout_buf.lock() {
@@ -378,12 +928,26 @@ OpenSkills runtime does not support legacy core-module WASM artifacts."
         }
     };
     
-    let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
+    let (stdout, binary_stdout) = decode_stdout(stdout_bytes);
     let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
 
     // Determine exit status and output
     let (exit_status, output) = match run_result {
-        Ok(Ok(())) => {
+        Ok(RunOutcome::Returned(Ok(json))) => match serde_json::from_str::<Value>(&json) {
+            Ok(output) => (ExecutionStatus::Success, output),
+            Err(e) => {
+                let error_msg = format!("Component returned invalid JSON: {e}");
+                (
+                    ExecutionStatus::Failed(error_msg.clone()),
+                    serde_json::json!({ "status": "error", "error": error_msg }),
+                )
+            }
+        },
+        Ok(RunOutcome::Returned(Err(error_msg))) => (
+            ExecutionStatus::Failed(error_msg.clone()),
+            serde_json::json!({ "status": "error", "error": error_msg }),
+        ),
+        Ok(RunOutcome::Exited(Ok(()))) => {
             let output = if let Ok(json) = serde_json::from_str::<Value>(&stdout) {
                 json
             } else {
@@ -391,13 +955,13 @@ OpenSkills runtime does not support legacy core-module WASM artifacts."
             };
             (ExecutionStatus::Success, output)
         }
-        Ok(Err(())) => (
+        Ok(RunOutcome::Exited(Err(()))) => (
             ExecutionStatus::Failed("Component exited with error".to_string()),
             serde_json::json!({ "status": "error", "error": "Component exited with error" }),
         ),
         Err(e) => {
             let error_msg = e.to_string();
-            let status = if error_msg.contains("epoch") {
+            let status = if matches!(e, OpenSkillError::Timeout) {
                 ExecutionStatus::Timeout
             } else {
                 ExecutionStatus::Failed(error_msg.clone())
@@ -409,18 +973,74 @@ OpenSkills runtime does not support legacy core-module WASM artifacts."
         }
     };
 
-    Ok(ExecutionArtifacts {
-        output,
-        stdout,
-        stderr,
-        permissions_used: enforcer.permissions_used(),
-        exit_status,
-        sandbox_mode: SandboxMode::Enforce,
-    })
+    Ok(timer.finish(
+        "wasm",
+        ExecutionArtifacts {
+            output,
+            stdout,
+            stderr,
+            permissions_used: enforcer.permissions_used(),
+            exit_status,
+            sandbox_mode: SandboxMode::Enforce,
+            binary_stdout,
+            sandbox_violations: Vec::new(),
+            fuel_consumed: Some(fuel_consumed),
+            network_requests,
+            host_calls,
+            capability_downgrades: process_downgrades(enforcer),
+            setup_timings: SetupTimings::default(),
+        },
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    // WASM execution tests would require actual WASM modules
-    // These are integration tests better suited for a separate test harness
+    // Executing components is covered by tests/wasm_tests.rs.
+    use super::*;
+
+    fn uri(s: &str) -> hyper::Uri {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_http_egress_without_network_denies_and_records() {
+        let mut egress = HttpEgress::new(None);
+        assert!(!egress.admit("GET", &uri("https://example.com/a"), true));
+        assert_eq!(
+            egress.requests,
+            vec![NetworkRequest {
+                method: "GET".to_string(),
+                url: "https://example.com/a".to_string(),
+                host: "example.com".to_string(),
+                port: 443,
+                allowed: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_http_egress_applies_allowlist() {
+        let policy = EgressPolicy::new(&["example.com".to_string()], &[]);
+        let mut egress = HttpEgress::new(Some(policy));
+        assert!(egress.admit("GET", &uri("https://api.example.com/"), true));
+        assert!(egress.admit("POST", &uri("http://example.com:8080/x"), false));
+        assert!(!egress.admit("GET", &uri("https://evil.test/"), true));
+        let seen: Vec<_> = egress
+            .requests
+            .iter()
+            .map(|r| (r.host.as_str(), r.port, r.allowed))
+            .collect();
+        assert_eq!(
+            seen,
+            vec![
+                ("api.example.com", 443, true),
+                ("example.com", 8080, true),
+                ("evil.test", 443, false),
+            ]
+        );
+
+        let mut ports_only = HttpEgress::new(Some(EgressPolicy::new(&[], &[443])));
+        assert!(ports_only.admit("GET", &uri("https://any.test/"), true));
+        assert!(!ports_only.admit("GET", &uri("http://any.test/"), false));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,301 @@
+//! Hot reload of skill directories.
+//!
+//! [`OpenSkillRuntime::reload_skills`](crate::OpenSkillRuntime::reload_skills)
+//! rediscovers skills and reports which ones were added, modified or
+//! removed, as [`SkillChange`]s and `RuntimeEvent::SkillChanged` events.
+//! With the `watch` feature, `OpenSkillRuntime::watch_skills` does that
+//! automatically: a [`SkillWatcher`] thread watches the discovery
+//! directories (through the `notify` crate) and reloads when a `SKILL.md`
+//! is written or a directory is created, removed or renamed, so
+//! long-running agent servers pick up edits without a restart.
+
+use crate::registry::SkillMetadata;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// How a skill changed between two discoveries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillChangeKind {
+    /// The skill version appeared.
+    Added,
+    /// Its `SKILL.md` changed or it moved to another directory.
+    Modified,
+    /// The skill version is gone.
+    Removed,
+}
+
+/// A skill version that changed on reload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillChange {
+    pub skill_id: String,
+    pub version: String,
+    pub kind: SkillChangeKind,
+    /// Skill directory (the old one for removed skills).
+    pub path: PathBuf,
+}
+
+/// Loaded skill versions with their directory and a hash of their
+/// `SKILL.md`, to compare discoveries.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SkillSnapshot {
+    skills: BTreeMap<(String, String), (PathBuf, String)>,
+}
+
+impl SkillSnapshot {
+    pub(crate) fn capture<'a>(skills: impl Iterator<Item = &'a SkillMetadata>) -> Self {
+        let skills = skills
+            .map(|m| {
+                let hash = fs::read(m.root.join("SKILL.md"))
+                    .map(|bytes| hex::encode(Sha256::digest(bytes)))
+                    .unwrap_or_default();
+                ((m.id.clone(), m.version.to_string()), (m.root.clone(), hash))
+            })
+            .collect();
+        Self { skills }
+    }
+
+    /// Changes from `self` to `after`, ordered by skill ID and version.
+    pub(crate) fn changes(&self, after: &SkillSnapshot) -> Vec<SkillChange> {
+        let change = |(id, version): &(String, String), kind, path: &PathBuf| SkillChange {
+            skill_id: id.clone(),
+            version: version.clone(),
+            kind,
+            path: path.clone(),
+        };
+        let mut changes = Vec::new();
+        for (key, (path, hash)) in &after.skills {
+            match self.skills.get(key) {
+                None => changes.push(change(key, SkillChangeKind::Added, path)),
+                Some((old_path, old_hash)) if old_path != path || old_hash != hash => {
+                    changes.push(change(key, SkillChangeKind::Modified, path))
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, (path, _)) in &self.skills {
+            if !after.skills.contains_key(key) {
+                changes.push(change(key, SkillChangeKind::Removed, path));
+            }
+        }
+        changes.sort_by(|a, b| (&a.skill_id, &a.version).cmp(&(&b.skill_id, &b.version)));
+        changes
+    }
+}
+
+#[cfg(feature = "watch")]
+pub use watcher::SkillWatcher;
+
+#[cfg(feature = "watch")]
+mod watcher {
+    use super::SkillChange;
+    use crate::errors::OpenSkillError;
+    use crate::OpenSkillRuntime;
+    use notify::event::{CreateKind, ModifyKind};
+    use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::sync::{Arc, Mutex, PoisonError, Weak};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Quiet period after the last file system event before reloading, so
+    /// an editor's burst of writes causes one reload.
+    const DEBOUNCE: Duration = Duration::from_millis(200);
+
+    enum Message {
+        Fs(notify::Result<Event>),
+        Stop,
+    }
+
+    /// Watches a runtime's skill directories and reloads it on changes.
+    ///
+    /// Created by `OpenSkillRuntime::watch_skills`. Each reload that
+    /// changes something sends its [`SkillChange`]s on [`Self::changes`].
+    /// Dropping the watcher stops it.
+    pub struct SkillWatcher {
+        changes: Receiver<SkillChange>,
+        control: Sender<Message>,
+    }
+
+    impl SkillWatcher {
+        pub(crate) fn start(runtime: &Arc<Mutex<OpenSkillRuntime>>) -> Result<Self, OpenSkillError> {
+            let (control, messages) = mpsc::channel();
+            let events = control.clone();
+            let watcher = notify::recommended_watcher(move |event| {
+                let _ = events.send(Message::Fs(event));
+            })
+            .map_err(watch_error)?;
+            let (sender, changes) = mpsc::channel();
+            let mut worker = Worker {
+                runtime: Arc::downgrade(runtime),
+                watcher,
+                watched: BTreeSet::new(),
+                changes: sender,
+            };
+            worker.sync_roots(&lock(runtime).watch_roots())?;
+            thread::Builder::new()
+                .name("openskills-watch".to_string())
+                .spawn(move || worker.run(messages))?;
+            Ok(Self { changes, control })
+        }
+
+        /// Changes found by each reload, in order.
+        pub fn changes(&self) -> &Receiver<SkillChange> {
+            &self.changes
+        }
+    }
+
+    impl Drop for SkillWatcher {
+        fn drop(&mut self) {
+            // The worker may be waiting for the runtime lock, so it is not
+            // joined: it exits once it sees the message.
+            let _ = self.control.send(Message::Stop);
+        }
+    }
+
+    impl std::fmt::Debug for SkillWatcher {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("SkillWatcher").finish_non_exhaustive()
+        }
+    }
+
+    struct Worker {
+        runtime: Weak<Mutex<OpenSkillRuntime>>,
+        watcher: RecommendedWatcher,
+        watched: BTreeSet<PathBuf>,
+        changes: Sender<SkillChange>,
+    }
+
+    impl Worker {
+        fn run(mut self, messages: Receiver<Message>) {
+            loop {
+                let mut reload = match messages.recv() {
+                    Ok(Message::Fs(event)) => is_relevant(&event),
+                    Ok(Message::Stop) | Err(_) => return,
+                };
+                loop {
+                    match messages.recv_timeout(DEBOUNCE) {
+                        Ok(Message::Fs(event)) => reload |= is_relevant(&event),
+                        Ok(Message::Stop) | Err(RecvTimeoutError::Disconnected) => return,
+                        Err(RecvTimeoutError::Timeout) => break,
+                    }
+                }
+                if !reload {
+                    continue;
+                }
+                let Some(runtime) = self.runtime.upgrade() else {
+                    return;
+                };
+                let (changes, roots) = {
+                    let mut runtime = lock(&runtime);
+                    // A failed rescan keeps the previous skills; the next
+                    // change retries.
+                    (runtime.reload_skills().unwrap_or_default(), runtime.watch_roots())
+                };
+                let _ = self.sync_roots(&roots);
+                for change in changes {
+                    let _ = self.changes.send(change);
+                }
+            }
+        }
+
+        /// Watch `roots`, and stop watching directories no longer in it.
+        fn sync_roots(&mut self, roots: &[PathBuf]) -> Result<(), OpenSkillError> {
+            let roots: BTreeSet<PathBuf> = roots.iter().cloned().collect();
+            for old in self.watched.difference(&roots) {
+                let _ = self.watcher.unwatch(old);
+            }
+            self.watched.retain(|dir| roots.contains(dir));
+            for dir in roots {
+                if !self.watched.contains(&dir) {
+                    self.watcher
+                        .watch(&dir, RecursiveMode::Recursive)
+                        .map_err(watch_error)?;
+                    self.watched.insert(dir);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// A `SKILL.md` changed, or a directory was created or something was
+    /// removed or renamed (any of which may be a whole skill directory).
+    /// Created directories count because their `SKILL.md` may be written
+    /// before the recursive watch reaches them.
+    fn is_relevant(event: &notify::Result<Event>) -> bool {
+        let Ok(event) = event else {
+            return false;
+        };
+        match event.kind {
+            EventKind::Access(_) => false,
+            EventKind::Create(CreateKind::Folder)
+            | EventKind::Remove(_)
+            | EventKind::Modify(ModifyKind::Name(_)) => true,
+            _ => event
+                .paths
+                .iter()
+                .any(|p| p.file_name().is_some_and(|name| name == "SKILL.md")),
+        }
+    }
+
+    fn lock(runtime: &Mutex<OpenSkillRuntime>) -> std::sync::MutexGuard<'_, OpenSkillRuntime> {
+        runtime.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn watch_error(err: notify::Error) -> OpenSkillError {
+        OpenSkillError::WatchError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entries: &[(&str, &str, &str)]) -> SkillSnapshot {
+        SkillSnapshot {
+            skills: entries
+                .iter()
+                .map(|(id, path, hash)| {
+                    ((id.to_string(), "0.0.0".to_string()), (PathBuf::from(path), hash.to_string()))
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_changes_between_snapshots() {
+        let before = snapshot(&[
+            ("kept", "/s/kept", "a"),
+            ("edited", "/s/edited", "a"),
+            ("gone", "/s/gone", "a"),
+            ("moved", "/s/moved", "a"),
+        ]);
+        let after = snapshot(&[
+            ("kept", "/s/kept", "a"),
+            ("edited", "/s/edited", "b"),
+            ("moved", "/t/moved", "a"),
+            ("new", "/s/new", "a"),
+        ]);
+
+        let changes: Vec<(String, SkillChangeKind)> = before
+            .changes(&after)
+            .into_iter()
+            .map(|c| (c.skill_id, c.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("edited".to_string(), SkillChangeKind::Modified),
+                ("gone".to_string(), SkillChangeKind::Removed),
+                ("moved".to_string(), SkillChangeKind::Modified),
+                ("new".to_string(), SkillChangeKind::Added),
+            ]
+        );
+        assert!(after.changes(&after).is_empty());
+    }
+}
@@ -0,0 +1,415 @@
+//! Bringing user-provided files into the workspace.
+//!
+//! Skills only see the workspace and the paths their tools grant, so a
+//! document the user picked elsewhere on the host is copied in rather than
+//! widening the sandbox to its directory. Imports are size-limited, refuse
+//! credential directories and destinations outside the workspace (including
+//! via symlinks) or in the runtime's own `.openskills/` tree, and each one
+//! is recorded in the runtime's import audit log. With a blob store
+//! configured, content is deduplicated across sessions (see
+//! [`crate::blob_store`]).
+//!
+//! The same path copies a skill's `workspace-files:` templates into the
+//! workspace when a session starts.
+
+use crate::audit::hash_bytes;
+use crate::blob_store::BlobStore;
+use crate::errors::OpenSkillError;
+use crate::manifest::WorkspaceFile;
+use crate::sandbox_overrides::overlapping_sensitive_path;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default limit on a single import.
+pub const DEFAULT_MAX_IMPORT_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Workspace directory reserved for the runtime.
+const RUNTIME_DIR: &str = ".openskills";
+
+/// Audit entry for a file imported into the workspace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceImport {
+    pub timestamp: u64,
+    /// Host file the content was copied from; `None` for in-memory bytes.
+    pub source: Option<PathBuf>,
+    /// Destination as requested, relative to the workspace.
+    pub dest: String,
+    /// Absolute destination path.
+    pub path: PathBuf,
+    pub size: u64,
+    /// SHA-256 of the imported content.
+    pub sha256: String,
+}
+
+/// Copy the host file `host_path` to `dest_rel` in `workspace`.
+pub(crate) fn import_file(
+    workspace: &Path,
+    host_path: &Path,
+    dest_rel: &str,
+    max_bytes: u64,
+    blobs: Option<&BlobStore>,
+) -> Result<WorkspaceImport, OpenSkillError> {
+    import_file_outside(workspace, host_path, dest_rel, max_bytes, blobs, dirs::home_dir().as_deref())
+}
+
+/// [`import_file`], refusing credential directories under `home`.
+fn import_file_outside(
+    workspace: &Path,
+    host_path: &Path,
+    dest_rel: &str,
+    max_bytes: u64,
+    blobs: Option<&BlobStore>,
+    home: Option<&Path>,
+) -> Result<WorkspaceImport, OpenSkillError> {
+    // Resolve symlinks before the check and read the file that was checked,
+    // so a link into ~/.ssh cannot pass as an ordinary document.
+    let source = host_path.canonicalize()?;
+    if let Some(home) = home {
+        let home = home.canonicalize().unwrap_or_else(|_| home.to_path_buf());
+        if let Some(sensitive) = overlapping_sensitive_path(&source, &home) {
+            return Err(OpenSkillError::PermissionDenied(format!(
+                "cannot import {}: inside {}",
+                host_path.display(),
+                sensitive.display()
+            )));
+        }
+    }
+    let file = std::fs::File::open(&source)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Err(OpenSkillError::InvalidConfig(format!(
+            "cannot import {}: not a regular file",
+            host_path.display()
+        )));
+    }
+    check_size(metadata.len(), max_bytes)?;
+
+    // The file may grow between the check and the read.
+    let mut bytes = Vec::with_capacity(metadata.len() as usize);
+    file.take(max_bytes + 1).read_to_end(&mut bytes)?;
+    write_import(workspace, &bytes, dest_rel, max_bytes, blobs, Some(source))
+}
+
+/// Write `bytes` to `dest_rel` in `workspace`.
+pub(crate) fn import_bytes(
+    workspace: &Path,
+    bytes: &[u8],
+    dest_rel: &str,
+    max_bytes: u64,
+    blobs: Option<&BlobStore>,
+) -> Result<WorkspaceImport, OpenSkillError> {
+    write_import(workspace, bytes, dest_rel, max_bytes, blobs, None)
+}
+
+fn write_import(
+    workspace: &Path,
+    bytes: &[u8],
+    dest_rel: &str,
+    max_bytes: u64,
+    blobs: Option<&BlobStore>,
+    source: Option<PathBuf>,
+) -> Result<WorkspaceImport, OpenSkillError> {
+    check_size(bytes.len() as u64, max_bytes)?;
+    let path = destination(workspace, dest_rel)?;
+
+    // Write next to the destination and rename, so skills never see a
+    // partial file.
+    let tmp = path.with_file_name(format!(
+        ".{}.import-{}",
+        path.file_name().and_then(|n| n.to_str()).unwrap_or("file"),
+        std::process::id()
+    ));
+    let sha256 = match blobs {
+        Some(store) => {
+            let (hash, blob) = store.put(bytes)?;
+            store.materialize(&blob, &tmp)?;
+            hash
+        }
+        None => {
+            std::fs::write(&tmp, bytes)?;
+            hash_bytes(bytes)
+        }
+    };
+    if let Err(e) = std::fs::rename(&tmp, &path) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e.into());
+    }
+
+    Ok(WorkspaceImport {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        source,
+        dest: dest_rel.to_string(),
+        path,
+        size: bytes.len() as u64,
+        sha256,
+    })
+}
+
+/// Copy a skill's `workspace-files` from `skill_root` into `workspace`.
+/// Files already in the workspace are left alone, so a user's edits to a
+/// template survive the next session.
+pub(crate) fn populate(
+    workspace: &Path,
+    skill_root: &Path,
+    files: &[WorkspaceFile],
+    max_bytes: u64,
+    blobs: Option<&BlobStore>,
+) -> Result<Vec<WorkspaceImport>, OpenSkillError> {
+    let root = skill_root.canonicalize()?;
+    let mut imported = Vec::new();
+    for file in files {
+        let source = skill_root.join(file.source());
+        // Symlinks in the skill must not reach outside it.
+        let source = match source.canonicalize() {
+            Ok(path) if path.starts_with(&root) => path,
+            Ok(_) => {
+                return Err(OpenSkillError::PermissionDenied(format!(
+                    "workspace file {} resolves outside the skill",
+                    file.source()
+                )))
+            }
+            Err(_) => {
+                return Err(OpenSkillError::InvalidManifest(format!(
+                    "workspace file not found in the skill: {}",
+                    file.source()
+                )))
+            }
+        };
+        let copies: Vec<(PathBuf, String)> = if source.is_dir() {
+            walkdir::WalkDir::new(&source)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| {
+                    let rel = entry.path().strip_prefix(&source).ok()?;
+                    let dest = Path::new(file.dest()).join(rel);
+                    Some((entry.into_path(), dest.to_string_lossy().to_string()))
+                })
+                .collect()
+        } else {
+            vec![(source, file.dest().to_string())]
+        };
+        for (source, dest) in copies {
+            if std::fs::symlink_metadata(workspace.join(&dest)).is_ok() {
+                continue;
+            }
+            imported.push(import_file(workspace, &source, &dest, max_bytes, blobs)?);
+        }
+    }
+    Ok(imported)
+}
+
+/// Reject workspace files whose source leaves the skill or whose
+/// destination leaves the workspace or targets `.openskills/`.
+pub(crate) fn check_workspace_files(files: &[WorkspaceFile]) -> Result<(), OpenSkillError> {
+    for file in files {
+        for path in [file.source(), file.dest()] {
+            let rel = Path::new(path);
+            if path.is_empty()
+                || !rel.components().all(|c| matches!(c, Component::Normal(_)))
+                || rel.starts_with(RUNTIME_DIR)
+            {
+                return Err(OpenSkillError::InvalidManifest(format!(
+                    "workspace file paths must be relative paths without `..`: {}",
+                    path
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_size(size: u64, max_bytes: u64) -> Result<(), OpenSkillError> {
+    if size > max_bytes {
+        return Err(OpenSkillError::PermissionDenied(format!(
+            "import of {size} bytes exceeds the {max_bytes}-byte limit"
+        )));
+    }
+    Ok(())
+}
+
+/// Resolve `dest_rel` to a file path inside `workspace`, creating parent
+/// directories.
+fn destination(workspace: &Path, dest_rel: &str) -> Result<PathBuf, OpenSkillError> {
+    let invalid = |reason: &str| {
+        OpenSkillError::InvalidConfig(format!("invalid import destination {dest_rel:?}: {reason}"))
+    };
+    let rel = Path::new(dest_rel);
+    if dest_rel.is_empty() || rel.file_name().is_none() {
+        return Err(invalid("expected a file path"));
+    }
+    if !rel.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(invalid("must be relative and must not contain `..`"));
+    }
+    if rel.starts_with(RUNTIME_DIR) {
+        return Err(invalid("reserved for the runtime"));
+    }
+
+    let path = workspace.join(rel);
+    let parent = path.parent().unwrap_or(workspace);
+    std::fs::create_dir_all(parent)?;
+    let inside = match (parent.canonicalize(), workspace.canonicalize()) {
+        (Ok(parent), Ok(workspace)) => parent.starts_with(workspace),
+        _ => false,
+    };
+    if !inside {
+        return Err(invalid("resolves outside the workspace"));
+    }
+    if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+        if !metadata.is_file() {
+            return Err(invalid("exists and is not a regular file"));
+        }
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_import_bytes_and_file() {
+        let workspace = TempDir::new().unwrap();
+        let entry = import_bytes(workspace.path(), b"hello", "inbox/note.txt", 1024, None).unwrap();
+        assert_eq!(entry.path, workspace.path().join("inbox/note.txt"));
+        assert_eq!(std::fs::read(&entry.path).unwrap(), b"hello");
+        assert_eq!((entry.size, entry.source.as_ref()), (5, None));
+        assert_eq!(entry.sha256, hash_bytes(b"hello"));
+
+        let host = TempDir::new().unwrap();
+        let doc = host.path().join("report.pdf");
+        std::fs::write(&doc, b"%PDF").unwrap();
+        let entry = import_file(workspace.path(), &doc, "report.pdf", 1024, None).unwrap();
+        assert_eq!(std::fs::read(workspace.path().join("report.pdf")).unwrap(), b"%PDF");
+        assert_eq!(entry.source, Some(doc.canonicalize().unwrap()));
+
+        // Re-importing replaces the file.
+        import_bytes(workspace.path(), b"v2", "report.pdf", 1024, None).unwrap();
+        assert_eq!(std::fs::read(workspace.path().join("report.pdf")).unwrap(), b"v2");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlinks_into_credential_directories() {
+        let host = TempDir::new().unwrap();
+        let real_home = host.path().join("real-home");
+        std::fs::create_dir_all(real_home.join(".ssh")).unwrap();
+        std::fs::write(real_home.join(".ssh/id_rsa"), "secret").unwrap();
+        // Both the home directory and the imported file are links.
+        let home = host.path().join("home");
+        std::os::unix::fs::symlink(&real_home, &home).unwrap();
+        let link = host.path().join("notes.txt");
+        std::os::unix::fs::symlink(real_home.join(".ssh/id_rsa"), &link).unwrap();
+
+        let workspace = TempDir::new().unwrap();
+        let err = import_file_outside(workspace.path(), &link, "notes.txt", 1024, None, Some(&home))
+            .unwrap_err();
+        assert!(matches!(err, OpenSkillError::PermissionDenied(_)), "{err}");
+        assert!(!workspace.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn test_populate_copies_templates_once() {
+        let skill = TempDir::new().unwrap();
+        std::fs::create_dir_all(skill.path().join("templates/letters")).unwrap();
+        std::fs::write(skill.path().join("templates/report.md"), "# Report").unwrap();
+        std::fs::write(skill.path().join("templates/letters/a.md"), "Dear A").unwrap();
+        let files: Vec<WorkspaceFile> = serde_yaml::from_str(
+            "- templates/report.md\n- from: templates/letters\n  to: drafts\n",
+        )
+        .unwrap();
+        check_workspace_files(&files).unwrap();
+
+        let workspace = TempDir::new().unwrap();
+        let imported = populate(workspace.path(), skill.path(), &files, 1024, None).unwrap();
+        let dests: Vec<_> = imported.iter().map(|e| e.dest.as_str()).collect();
+        assert_eq!(dests, ["templates/report.md", "drafts/a.md"]);
+        assert_eq!(std::fs::read_to_string(workspace.path().join("drafts/a.md")).unwrap(), "Dear A");
+
+        // Edits in the workspace survive the next session.
+        std::fs::write(workspace.path().join("drafts/a.md"), "Dear B").unwrap();
+        assert!(populate(workspace.path(), skill.path(), &files, 1024, None).unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(workspace.path().join("drafts/a.md")).unwrap(), "Dear B");
+
+        let escape = [WorkspaceFile::Path("../secret".to_string())];
+        assert!(check_workspace_files(&escape).is_err());
+        let missing = [WorkspaceFile::Path("missing.md".to_string())];
+        assert!(populate(workspace.path(), skill.path(), &missing, 1024, None).is_err());
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let workspace = TempDir::new().unwrap();
+        assert!(matches!(
+            import_bytes(workspace.path(), &[0; 11], "big.bin", 10, None),
+            Err(OpenSkillError::PermissionDenied(_))
+        ));
+        let host = TempDir::new().unwrap();
+        let doc = host.path().join("big.bin");
+        std::fs::write(&doc, [0; 11]).unwrap();
+        assert!(matches!(
+            import_file(workspace.path(), &doc, "big.bin", 10, None),
+            Err(OpenSkillError::PermissionDenied(_))
+        ));
+        assert!(!workspace.path().join("big.bin").exists());
+    }
+
+    #[test]
+    fn test_rejects_bad_destinations() {
+        let workspace = TempDir::new().unwrap();
+        for dest in ["", "/etc/passwd", "../escape.txt", "a/../../b", ".openskills/artifacts/x"] {
+            assert!(
+                matches!(
+                    import_bytes(workspace.path(), b"x", dest, 10, None),
+                    Err(OpenSkillError::InvalidConfig(_))
+                ),
+                "{dest}"
+            );
+        }
+
+        // A directory or a symlink can't be overwritten or followed out.
+        std::fs::create_dir(workspace.path().join("dir")).unwrap();
+        assert!(import_bytes(workspace.path(), b"x", "dir", 10, None).is_err());
+        #[cfg(unix)]
+        {
+            let outside = TempDir::new().unwrap();
+            std::os::unix::fs::symlink(outside.path(), workspace.path().join("link")).unwrap();
+            assert!(import_bytes(workspace.path(), b"x", "link/x.txt", 10, None).is_err());
+            assert!(!outside.path().join("x.txt").exists());
+        }
+    }
+
+    #[test]
+    fn test_blob_store_dedupes_across_workspaces() {
+        let blobs = TempDir::new().unwrap();
+        let store = BlobStore::new(blobs.path());
+        let (first, second) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let a = import_bytes(first.path(), b"dataset", "in/data.csv", 1024, Some(&store)).unwrap();
+        let b = import_bytes(second.path(), b"dataset", "data.csv", 1024, Some(&store)).unwrap();
+        assert_eq!(a.sha256, b.sha256);
+        assert_eq!(std::fs::read(&b.path).unwrap(), b"dataset");
+        let stored: Vec<_> = walkdir::WalkDir::new(blobs.path())
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .collect();
+        assert_eq!(stored.len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_directories() {
+        let workspace = TempDir::new().unwrap();
+        let host = TempDir::new().unwrap();
+        assert!(matches!(
+            import_file(workspace.path(), host.path(), "copy", 10, None),
+            Err(OpenSkillError::InvalidConfig(_))
+        ));
+    }
+}
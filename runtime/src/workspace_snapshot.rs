@@ -0,0 +1,191 @@
+//! Files a skill produced in its workspace.
+//!
+//! Before an execution the runtime records the size and modification time of
+//! every file in the workspace; afterwards, files that are new or changed are
+//! returned as [`Artifact`]s on `ExecutionResult::artifacts`, so callers do not
+//! have to scan the workspace themselves. Symlinks are not followed, and very
+//! large workspaces are only scanned up to [`MAX_FILES`] files.
+
+use crate::binary_output;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Files scanned per snapshot; the rest of the workspace is ignored.
+pub const MAX_FILES: usize = 10_000;
+
+/// Bytes read to tell text from binary files of unknown type.
+const SNIFF_BYTES: usize = 8192;
+
+/// A file created or modified during an execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Artifact {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Size in bytes.
+    pub size: u64,
+    /// MIME type, guessed from the extension (or the content, for unknown
+    /// extensions).
+    pub mime: String,
+    /// Hex-encoded SHA-256 of the contents.
+    pub sha256: String,
+    /// The artifact this file was converted from, for files written by an
+    /// output converter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_from: Option<PathBuf>,
+}
+
+/// Size and modification time of every file in a workspace.
+#[derive(Debug, Default)]
+pub(crate) struct WorkspaceSnapshot {
+    root: PathBuf,
+    files: HashMap<PathBuf, (u64, Option<SystemTime>)>,
+}
+
+impl WorkspaceSnapshot {
+    /// Record the files currently in `root`.
+    pub(crate) fn capture(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            files: scan(root).collect(),
+        }
+    }
+
+    /// Files created or modified since the snapshot was taken, by path.
+    pub(crate) fn changes(&self) -> Vec<Artifact> {
+        let mut artifacts: Vec<Artifact> = scan(&self.root)
+            .filter(|(path, stamp)| self.files.get(path) != Some(stamp))
+            .filter_map(|(path, _)| describe(path))
+            .collect();
+        artifacts.sort_by(|a, b| a.path.cmp(&b.path));
+        artifacts
+    }
+}
+
+fn scan(root: &Path) -> impl Iterator<Item = (PathBuf, (u64, Option<SystemTime>))> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .take(MAX_FILES)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((entry.into_path(), (metadata.len(), metadata.modified().ok())))
+        })
+}
+
+/// Describe the file at `path` as an artifact.
+pub(crate) fn describe(path: PathBuf) -> Option<Artifact> {
+    let mut file = File::open(&path).ok()?;
+    let mut hasher = Sha256::new();
+    let mut head = Vec::new();
+    let mut buf = [0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+        if head.len() < SNIFF_BYTES {
+            let take = n.min(SNIFF_BYTES - head.len());
+            head.extend_from_slice(&buf[..take]);
+        }
+        hasher.update(&buf[..n]);
+        size += n as u64;
+    }
+    let mime = mime_from_extension(&path)
+        .unwrap_or_else(|| sniffed_mime(&head))
+        .to_string();
+    Some(Artifact {
+        path,
+        size,
+        mime,
+        sha256: hex::encode(hasher.finalize()),
+        converted_from: None,
+    })
+}
+
+/// MIME type of the file at `path`, guessed like [`Artifact::mime`].
+#[cfg_attr(not(all(unix, feature = "daemon")), allow(dead_code))]
+pub(crate) fn guess_mime(path: &Path) -> String {
+    if let Some(mime) = mime_from_extension(path) {
+        return mime.to_string();
+    }
+    let mut head = Vec::new();
+    let _ = File::open(path).and_then(|file| file.take(SNIFF_BYTES as u64).read_to_end(&mut head));
+    sniffed_mime(&head).to_string()
+}
+
+fn sniffed_mime(head: &[u8]) -> &'static str {
+    if binary_output::is_binary(head) {
+        "application/octet-stream"
+    } else {
+        "text/plain"
+    }
+}
+
+fn mime_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "xml" => "application/xml",
+        "json" => "application/json",
+        "yaml" | "yml" => "application/yaml",
+        "js" => "text/javascript",
+        "py" => "text/x-python",
+        "sh" => "text/x-shellscript",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "wasm" => "application/wasm",
+        "bin" => "application/octet-stream",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_changes_lists_new_and_modified_files() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("kept.txt"), "same").unwrap();
+        std::fs::write(dir.path().join("edited.json"), "{}").unwrap();
+        let snapshot = WorkspaceSnapshot::capture(dir.path());
+
+        std::fs::write(dir.path().join("edited.json"), "{\"a\": 1}").unwrap();
+        std::fs::create_dir(dir.path().join("out")).unwrap();
+        std::fs::write(dir.path().join("out/report"), "done\n").unwrap();
+
+        let changes = snapshot.changes();
+        let names: Vec<_> = changes
+            .iter()
+            .map(|a| a.path.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(names, [PathBuf::from("edited.json"), PathBuf::from("out/report")]);
+        assert_eq!(changes[0].mime, "application/json");
+        assert_eq!(changes[0].size, 8);
+        assert_eq!(changes[1].mime, "text/plain");
+        assert_eq!(changes[1].sha256, hex::encode(Sha256::digest(b"done\n")));
+    }
+}
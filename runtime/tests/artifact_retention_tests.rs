@@ -0,0 +1,109 @@
+//! Artifact Retention Tests
+//!
+//! Verifies that runtime-created workspace artifacts are collected according
+//! to the retention policy and that pinned artifacts survive collection.
+
+#![cfg(unix)]
+
+use openskills_runtime::{
+    ExecutionTarget, NativeRunnerConfig, OpenSkillRuntime, RetentionPolicy, SandboxMode,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_skill(temp_dir: &TempDir) {
+    let skill_dir = temp_dir.path().join("snapshots");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: snapshots\ndescription: Writes distinct binary output.\n---\n# Instructions\nRun it.\n",
+    )
+    .unwrap();
+    // NUL byte makes the output binary; the timestamp makes each run distinct.
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\nprintf '\\x00'\ndate +%s%N\n",
+    )
+    .unwrap();
+}
+
+fn run(runtime: &mut OpenSkillRuntime) -> String {
+    let result = runtime
+        .run_skill_target("snapshots", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    result.output["binary_output"]["path"]
+        .as_str()
+        .expect("diverted path")
+        .to_string()
+}
+
+#[test]
+fn test_retention_enforced_after_execution() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&temp_dir);
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace.path())
+        .with_native_runner_config(NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        })
+        .with_skill_artifact_retention(
+            "snapshots",
+            RetentionPolicy {
+                max_count: Some(2),
+                ..Default::default()
+            },
+        );
+    runtime.discover_skills().unwrap();
+
+    for _ in 0..4 {
+        run(&mut runtime);
+    }
+    let artifacts = runtime.list_artifacts(Some("snapshots")).unwrap();
+    assert_eq!(artifacts.len(), 2);
+    assert!(artifacts
+        .iter()
+        .all(|a| a.path.starts_with(workspace.path().join(".openskills/artifacts/snapshots"))));
+}
+
+#[test]
+fn test_pinned_artifact_survives_gc() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&temp_dir);
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace.path())
+        .with_native_runner_config(NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        });
+    runtime.discover_skills().unwrap();
+
+    let keep = run(&mut runtime);
+    let drop = run(&mut runtime);
+    runtime.pin_artifact(&keep).unwrap();
+
+    // Default policy keeps everything.
+    assert!(runtime.gc_artifacts().unwrap().removed.is_empty());
+
+    runtime.set_artifact_retention(RetentionPolicy {
+        max_count: Some(0),
+        ..Default::default()
+    });
+    let report = runtime.gc_artifacts().unwrap();
+    assert_eq!(report.removed.len(), 1);
+    assert!(fs::metadata(&keep).is_ok());
+    assert!(fs::metadata(&drop).is_err());
+
+    runtime.unpin_artifact(&keep).unwrap();
+    runtime.gc_artifacts().unwrap();
+    assert!(runtime.list_artifacts(None).unwrap().is_empty());
+
+    // Files the skill writes itself are not artifacts.
+    let own = workspace.path().join("result.txt");
+    fs::write(&own, "x").unwrap();
+    assert!(runtime.pin_artifact(&own).is_err());
+}
@@ -0,0 +1,90 @@
+//! `execute_many`: concurrent skill runs with isolated workspaces.
+
+#![cfg(unix)]
+
+use openskills_runtime::{
+    ExecutionOptions, NativeRunnerConfig, OpenSkillRuntime, RuntimeError,
+    RuntimeExecutionStatus, SandboxMode,
+};
+use serde_json::json;
+use std::fs;
+use std::time::Instant;
+use tempfile::TempDir;
+
+fn create_skill(root: &TempDir) {
+    let dir = root.path().join("validate");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: validate\ndescription: Validates one document.\n---\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("script.sh"),
+        "#!/bin/bash\nsleep 0.5\nprintf '%s' \"$SKILL_INPUT\" > \"$SKILL_WORKSPACE/report.json\"\necho '{\"valid\": true}'\n",
+    )
+    .unwrap();
+}
+
+fn runtime(skills: &TempDir, workspace: &TempDir) -> OpenSkillRuntime {
+    let mut runtime = OpenSkillRuntime::from_directory(skills.path())
+        .with_workspace_dir(workspace.path())
+        .with_native_runner_config(NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        });
+    runtime.discover_skills().unwrap();
+    runtime
+}
+
+fn job(doc: &str) -> (String, ExecutionOptions) {
+    let options = ExecutionOptions {
+        input: Some(json!({ "doc": doc })),
+        timeout_ms: Some(10_000),
+        ..Default::default()
+    };
+    ("validate".to_string(), options)
+}
+
+#[test]
+fn test_execute_many_runs_jobs_concurrently_in_separate_workspaces() {
+    let skills = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&skills);
+    let mut runtime = runtime(&skills, &workspace).with_max_parallelism(4);
+
+    let started = Instant::now();
+    let results = runtime.execute_many(vec![job("a.pdf"), job("b.pdf"), job("c.pdf"), job("d.pdf")]);
+    let elapsed = started.elapsed();
+
+    assert_eq!(results.len(), 4);
+    for (index, (result, doc)) in results.iter().zip(["a.pdf", "b.pdf", "c.pdf", "d.pdf"]).enumerate() {
+        let result = result.as_ref().unwrap();
+        assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+        assert!(result.audit.duration_ms < 1500, "queued time counted: {}", result.audit.duration_ms);
+        let report = workspace.path().join("batch").join(format!("{}-validate", index)).join("report.json");
+        let report: serde_json::Value = serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(report["doc"], doc);
+    }
+    // Four half-second jobs would take two seconds one after another.
+    assert!(elapsed.as_millis() < 1800, "jobs were serialized: {:?}", elapsed);
+}
+
+#[test]
+fn test_execute_many_reports_each_failure_in_place() {
+    let skills = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&skills);
+    let mut runtime = runtime(&skills, &workspace).with_max_parallelism(1);
+
+    let results = runtime.execute_many(vec![
+        job("a.pdf"),
+        ("missing".to_string(), ExecutionOptions::default()),
+        job("c.pdf"),
+    ]);
+
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(RuntimeError::SkillNotFound(_))));
+    assert!(results[2].is_ok());
+    assert!(runtime.execute_many(Vec::new()).is_empty());
+}
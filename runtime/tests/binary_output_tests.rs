@@ -0,0 +1,74 @@
+//! Binary Output Tests
+//!
+//! Verifies that binary stdout is diverted to the workspace (or described by
+//! size and hash) instead of being decoded as lossy text.
+
+use openskills_runtime::{ExecutionTarget, NativeRunnerConfig, OpenSkillRuntime, SandboxMode};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_binary_skill(temp_dir: &TempDir) {
+    let skill_dir = temp_dir.path().join("binary-out");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: binary-out\ndescription: Writes raw bytes to stdout.\n---\n# Instructions\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\nprintf '\\x89PNG\\r\\n\\x1a\\n\\x00\\xff\\xfe'\n",
+    )
+    .unwrap();
+}
+
+fn runtime_for(temp_dir: &TempDir, workspace: &TempDir) -> OpenSkillRuntime {
+    OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace.path())
+        .with_native_runner_config(NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        })
+}
+
+const EXPECTED: &[u8] = b"\x89PNG\r\n\x1a\n\x00\xff\xfe";
+
+#[test]
+#[cfg(unix)]
+fn test_binary_stdout_diverted_to_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_binary_skill(&temp_dir);
+
+    let mut runtime = runtime_for(&temp_dir, &workspace);
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("binary-out", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    let reference = &result.output["binary_output"];
+    assert_eq!(reference["size"], EXPECTED.len() as u64);
+    let path = reference["path"].as_str().expect("diverted path");
+    assert_eq!(fs::read(path).unwrap(), EXPECTED);
+    assert!(result.stdout.starts_with("[binary stdout:"), "stdout: {}", result.stdout);
+    assert!(!result.stdout.contains('\u{FFFD}'));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_binary_stdout_reference_without_diversion() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_binary_skill(&temp_dir);
+
+    let mut runtime = runtime_for(&temp_dir, &workspace).with_binary_output_diversion(false);
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("binary-out", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    let reference = &result.output["binary_output"];
+    assert!(reference.get("path").is_none());
+    assert_eq!(reference["sha256"].as_str().unwrap().len(), 64);
+    assert_eq!(fs::read_dir(workspace.path()).unwrap().count(), 0);
+}
@@ -0,0 +1,128 @@
+//! Bubblewrap sandbox backend tests.
+//!
+//! These tests run only on Linux and skip themselves when `bwrap` is not
+//! installed (or cannot create namespaces on this host).
+
+#[cfg(target_os = "linux")]
+use openskills_runtime::{
+    ExecutionBackend, ExecutionTarget, NativeRunnerConfig, OpenSkillRuntime,
+    RuntimeExecutionStatus,
+};
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use tempfile::TempDir;
+
+#[cfg(target_os = "linux")]
+fn bwrap_usable() -> bool {
+    std::process::Command::new("bwrap")
+        .args(["--unshare-all", "--ro-bind", "/", "/", "--", "/bin/true"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn create_skill(temp_dir: &TempDir, name: &str, script: &str) {
+    let skill_dir = temp_dir.path().join(name);
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!(
+            "---\nname: {}\ndescription: Bubblewrap test skill.\nallowed_tools: [\"Write\"]\n---\n# Instructions\nRun it.\n",
+            name
+        ),
+    )
+    .unwrap();
+    fs::write(skill_dir.join("script.sh"), script).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+fn bwrap_runtime(temp_dir: &TempDir, workspace: &std::path::Path) -> OpenSkillRuntime {
+    OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace)
+        .with_native_runner_config(NativeRunnerConfig {
+            backend: ExecutionBackend::Bubblewrap,
+            ..Default::default()
+        })
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_bubblewrap_workspace_rw_skill_root_ro() {
+    if !bwrap_usable() {
+        eprintln!("Skipping: bwrap not available");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(
+        &temp_dir,
+        "bwrap-write",
+        r#"#!/bin/bash
+echo ok > "$SKILL_WORKSPACE/out.txt"
+if echo bad > "$SKILL_ROOT/tampered.txt" 2>/dev/null; then
+  echo '{"root_writable": true}'
+else
+  echo '{"root_writable": false}'
+fi
+"#,
+    );
+
+    let mut runtime = bwrap_runtime(&temp_dir, workspace.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("bwrap-write", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    assert!(
+        matches!(result.audit.exit_status, RuntimeExecutionStatus::Success),
+        "stderr: {}",
+        result.stderr
+    );
+    assert_eq!(result.output["root_writable"], false);
+    assert!(workspace.path().join("out.txt").exists());
+    assert!(!temp_dir.path().join("bwrap-write/tampered.txt").exists());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_bubblewrap_denies_network_by_default() {
+    if !bwrap_usable() {
+        eprintln!("Skipping: bwrap not available");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(
+        &temp_dir,
+        "bwrap-net",
+        "#!/bin/bash\nls /sys/class/net 2>/dev/null | grep -vx lo | wc -l\n",
+    );
+
+    let mut runtime = bwrap_runtime(&temp_dir, workspace.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("bwrap-net", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert_eq!(result.stdout.trim(), "0", "stdout: {}", result.stdout);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_bubblewrap_backend_errors_without_bwrap() {
+    if bwrap_usable() {
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&temp_dir, "bwrap-missing", "#!/bin/bash\necho hi\n");
+
+    let mut runtime = bwrap_runtime(&temp_dir, workspace.path());
+    runtime.discover_skills().unwrap();
+    let result =
+        runtime.run_skill_target("bwrap-missing", ExecutionTarget::Auto, Some(10000), None, None);
+    assert!(result.is_err());
+}
@@ -216,9 +216,9 @@ description: Skill from nested directory
     
     // Should find the nested skill (if nested discovery is working)
     let skill = skills.iter().find(|s| s.id == "nested-skill");
-    if skill.is_some() {
-        assert_eq!(skill.unwrap().description, "Skill from nested directory");
-        assert_eq!(skill.unwrap().location, openskills_runtime::SkillLocation::Nested);
+    if let Some(skill) = skill {
+        assert_eq!(skill.description, "Skill from nested directory");
+        assert_eq!(skill.location, openskills_runtime::SkillLocation::Nested);
     } else {
         // Nested discovery might require the directory to be within the project root structure
         // This test verifies the mechanism exists, even if it doesn't find the skill in this setup
@@ -0,0 +1,117 @@
+//! Container execution backend tests.
+//!
+//! Tests that start containers skip themselves unless a Docker or Podman
+//! engine is reachable (`docker info` / `podman info` succeeds).
+
+#![cfg(unix)]
+
+use openskills_runtime::{
+    run_sandboxed_command, CommandPermissions, ContainerConfig, ExecutionBackend, ExecutionTarget,
+    NativeRunnerConfig, OpenSkillRuntime,
+};
+use std::fs;
+use tempfile::TempDir;
+
+fn container_engine() -> Option<&'static str> {
+    ["docker", "podman"].into_iter().find(|engine| {
+        std::process::Command::new(engine)
+            .arg("info")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    })
+}
+
+fn container_permissions(engine: &str) -> CommandPermissions {
+    CommandPermissions {
+        backend: ExecutionBackend::Container,
+        container: ContainerConfig {
+            engine: Some(engine.into()),
+            ..Default::default()
+        },
+        timeout_ms: 120_000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_container_command_workspace_mounts() {
+    let Some(engine) = container_engine() else {
+        eprintln!("Skipping: no container engine available");
+        return;
+    };
+    let working = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    fs::write(working.path().join("input.txt"), "hello").unwrap();
+
+    let perms = CommandPermissions {
+        write_paths: vec![workspace.path().to_path_buf()],
+        env_vars: vec![("GREETING".to_string(), "hi".to_string())],
+        ..container_permissions(engine)
+    };
+    let command = format!(
+        "cat input.txt; echo \" $GREETING\"; echo out > {}/out.txt; touch blocked.txt 2>/dev/null || echo ro",
+        workspace.path().display()
+    );
+    let result = run_sandboxed_command(&command, working.path(), perms).unwrap();
+
+    assert_eq!(result.exit_code, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("hello hi"), "stdout: {}", result.stdout);
+    assert!(result.stdout.contains("ro"));
+    assert!(workspace.path().join("out.txt").exists());
+    assert!(!working.path().join("blocked.txt").exists());
+}
+
+#[test]
+fn test_container_skill_execution() {
+    let Some(engine) = container_engine() else {
+        eprintln!("Skipping: no container engine available");
+        return;
+    };
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("in-container");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: in-container\ndescription: Runs in a container.\n---\n# Instructions\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\necho \"{\\\"skill\\\": \\\"$SKILL_ID\\\"}\"\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_native_runner_config(
+        NativeRunnerConfig {
+            backend: ExecutionBackend::Container,
+            container: ContainerConfig {
+                engine: Some(engine.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("in-container", ExecutionTarget::Auto, Some(120_000), None, None)
+        .unwrap();
+    assert_eq!(result.output["skill"], "in-container", "stderr: {}", result.stderr);
+}
+
+#[test]
+fn test_container_backend_reports_missing_engine() {
+    let working = TempDir::new().unwrap();
+    let perms = CommandPermissions {
+        backend: ExecutionBackend::Container,
+        container: ContainerConfig {
+            engine: Some("/nonexistent/docker".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = run_sandboxed_command("true", working.path(), perms).unwrap_err();
+    assert!(err.to_string().contains("docker or podman"), "{}", err);
+}
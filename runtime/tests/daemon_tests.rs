@@ -0,0 +1,235 @@
+//! `openskillsd` server and client over a Unix socket.
+#![cfg(all(unix, feature = "daemon"))]
+
+use openskills_runtime::{
+    DaemonClient, DaemonExecuteOptions, DaemonOutputScope, DaemonServer, OpenSkillRuntime,
+    RuntimeError,
+};
+use serde_json::json;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use tempfile::TempDir;
+
+fn create_skill(root: &Path, name: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: Test skill.\n---\nFollow these steps.\n"),
+    )
+    .unwrap();
+}
+
+fn start_daemon(skills: &Path, socket_dir: &TempDir) -> (PathBuf, JoinHandle<()>) {
+    let socket = socket_dir.path().join("openskillsd.sock");
+    let mut runtime = OpenSkillRuntime::from_directory(skills);
+    runtime.discover_skills().unwrap();
+    let server = DaemonServer::bind(&socket, runtime).unwrap();
+    let handle = thread::spawn(move || server.serve().unwrap());
+    (socket, handle)
+}
+
+#[test]
+fn test_clients_share_the_daemon_runtime() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha");
+    let socket_dir = TempDir::new().unwrap();
+    let (socket, daemon) = start_daemon(skills.path(), &socket_dir);
+
+    // Only the owner can connect, from the moment the socket exists.
+    let mode = fs::metadata(&socket).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(fs::read_dir(socket_dir.path()).unwrap().count(), 1);
+
+    let mut first = DaemonClient::connect(&socket).unwrap();
+    let status = first.status().unwrap();
+    assert_eq!(status.skills, 1);
+    assert_eq!(status.pid, std::process::id());
+    assert!(status.features.iter().any(|f| f == "daemon"));
+
+    let loaded = first.activate_skill("alpha").unwrap();
+    assert_eq!(loaded.instructions.trim(), "Follow these steps.");
+    let result = first
+        .execute_skill("alpha", &DaemonExecuteOptions::default())
+        .unwrap();
+    assert_eq!(result.audit.exit_status, "instruction_only");
+
+    // A skill added on disk shows up for every client after one reload.
+    create_skill(skills.path(), "beta");
+    let mut second = DaemonClient::connect(&socket).unwrap();
+    let changes = second.reload_skills().unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].skill_id, "beta");
+    let mut ids: Vec<String> = first.list_skills().unwrap().into_iter().map(|s| s.id).collect();
+    ids.sort();
+    assert_eq!(ids, ["alpha", "beta"]);
+
+    match first.activate_skill("missing") {
+        Err(RuntimeError::DaemonError(message)) => assert!(message.contains("missing"), "{}", message),
+        other => panic!("expected a daemon error, got {:?}", other.map(|s| s.id)),
+    }
+
+    second.shutdown().unwrap();
+    daemon.join().unwrap();
+    assert!(!socket.exists());
+}
+
+#[test]
+fn test_sessions_belong_to_their_connection() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha");
+    let socket_dir = TempDir::new().unwrap();
+    let (socket, daemon) = start_daemon(skills.path(), &socket_dir);
+
+    let mut client = DaemonClient::connect(&socket).unwrap();
+    let session = client.start_session("alpha", Some(json!({ "doc": "a.pdf" })), None).unwrap();
+    assert_eq!(session.skill.id, "alpha");
+    client
+        .record_tool_call(&session.session_id, "Read", &json!("contents"))
+        .unwrap();
+
+    let mut other = DaemonClient::connect(&socket).unwrap();
+    assert!(matches!(
+        other.record_tool_call(&session.session_id, "Read", &json!(null)),
+        Err(RuntimeError::DaemonError(_))
+    ));
+
+    // A status the daemon does not know is an error, not a success.
+    assert!(matches!(
+        client.finish_session(&session.session_id, json!(null), "", "", Some("sucess")),
+        Err(RuntimeError::DaemonError(_))
+    ));
+    let result = client
+        .finish_session(&session.session_id, json!({ "ok": true }), "done", "", None)
+        .unwrap();
+    assert_eq!(result.output, json!({ "ok": true }));
+    assert_eq!(result.audit.exit_status, "success");
+    assert!(client
+        .finish_session(&session.session_id, json!(null), "", "", None)
+        .is_err());
+
+    // A second daemon cannot take over a live socket.
+    assert!(matches!(
+        DaemonServer::bind(&socket, OpenSkillRuntime::from_directory(skills.path())),
+        Err(RuntimeError::DaemonError(_))
+    ));
+
+    other.shutdown().unwrap();
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_detached_session_is_continued_by_another_client() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha");
+    let socket_dir = TempDir::new().unwrap();
+    let (socket, daemon) = start_daemon(skills.path(), &socket_dir);
+
+    // A CLI starts the workflow, records a tool call, hands it off and exits.
+    let session_id = {
+        let mut cli = DaemonClient::connect(&socket).unwrap();
+        let session = cli
+            .start_session("alpha", Some(json!({ "doc": "a.pdf" })), Some(skills.path()))
+            .unwrap();
+        cli.record_tool_call(&session.session_id, "Read", &json!("contents"))
+            .unwrap();
+        let detached = cli.detach_session(&session.session_id).unwrap();
+        assert_eq!(detached.permissions_used, ["Read"]);
+        assert!(cli
+            .record_tool_call(&session.session_id, "Write", &json!(null))
+            .is_err());
+        session.session_id
+    };
+
+    let mut ide = DaemonClient::connect(&socket).unwrap();
+    let waiting = ide.list_sessions().unwrap();
+    assert_eq!(waiting.len(), 1);
+    assert_eq!(waiting[0].session_id, session_id);
+
+    let attached = ide.attach_session(&session_id).unwrap();
+    assert_eq!(attached.skill.id, "alpha");
+    assert_eq!(attached.input, json!({ "doc": "a.pdf" }));
+    assert_eq!(attached.permissions_used, ["Read"]);
+    assert_eq!(
+        attached.workspace_dir.as_deref(),
+        Some(skills.path().to_str().unwrap())
+    );
+    assert!(ide.list_sessions().unwrap().is_empty());
+
+    // Only one client can hold the session.
+    let mut other = DaemonClient::connect(&socket).unwrap();
+    assert!(matches!(
+        other.attach_session(&session_id),
+        Err(RuntimeError::DaemonError(_))
+    ));
+
+    ide.record_tool_call(&session_id, "Write", &json!("saved")).unwrap();
+    let result = ide
+        .finish_session(&session_id, json!({ "ok": true }), "", "", None)
+        .unwrap();
+    assert_eq!(result.audit.permissions_used, ["Read", "Write"]);
+
+    other.shutdown().unwrap();
+    daemon.join().unwrap();
+}
+
+#[test]
+fn test_session_outputs_are_listed_and_downloaded() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha");
+    let workspace = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    let socket_dir = TempDir::new().unwrap();
+    let (socket, daemon) = start_daemon(skills.path(), &socket_dir);
+
+    let mut client = DaemonClient::connect(&socket).unwrap();
+    let session = client
+        .start_session("alpha", None, Some(workspace.path()))
+        .unwrap();
+    let report: Vec<u8> = (0..3000u32).map(|i| (i % 251) as u8).collect();
+    fs::create_dir_all(workspace.path().join("out")).unwrap();
+    fs::write(workspace.path().join("out/report.pdf"), &report).unwrap();
+    fs::write(workspace.path().join("notes.md"), "# Notes\n").unwrap();
+    fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(outside.path().join("secret.txt"), workspace.path().join("link.txt"))
+        .unwrap();
+
+    let scope = DaemonOutputScope {
+        session_id: Some(session.session_id.clone()),
+        ..Default::default()
+    };
+    let outputs = client.list_outputs(&scope).unwrap();
+    let listed: Vec<(&str, u64, &str)> = outputs
+        .iter()
+        .map(|o| (o.path.as_str(), o.size, o.mime.as_str()))
+        .collect();
+    assert_eq!(
+        listed,
+        [("notes.md", 8, "text/markdown"), ("out/report.pdf", 3000, "application/pdf")]
+    );
+
+    let chunk = client
+        .read_output(&scope, "out/report.pdf", 1000, Some(500))
+        .unwrap();
+    assert_eq!(chunk.size, 3000);
+    assert!(!chunk.eof);
+    assert_eq!(chunk.bytes().unwrap(), &report[1000..1500]);
+    assert_eq!(client.download_output(&scope, "out/report.pdf").unwrap(), report);
+
+    for path in ["../secret.txt", "/etc/passwd", "link.txt", "missing.txt"] {
+        assert!(
+            matches!(client.read_output(&scope, path, 0, None), Err(RuntimeError::DaemonError(_))),
+            "{path} should be refused"
+        );
+    }
+    let unknown = DaemonOutputScope {
+        session_id: Some("session-999".to_string()),
+        ..Default::default()
+    };
+    assert!(client.list_outputs(&unknown).is_err());
+
+    client.shutdown().unwrap();
+    daemon.join().unwrap();
+}
@@ -5,6 +5,7 @@ use std::fs;
 use tempfile::TempDir;
 
 // Helper to verify hooks are parsed correctly
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
 fn verify_hooks_parsed(runtime: &OpenSkillRuntime, skill_id: &str) -> bool {
     if let Ok(loaded) = runtime.activate_skill(skill_id) {
         loaded.manifest.hooks.is_some()
@@ -135,6 +135,36 @@ fn test_host_policy_deny_list() {
     assert!(result.is_err(), "Bash should be denied by host policy deny list");
 }
 
+#[test]
+fn test_allowed_tools_argument_patterns() {
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_allowed_tools(&temp_dir, "pattern-skill", "Bash(npm run test:*), Read(./docs/**)");
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    runtime.set_host_policy(HostPolicy::from_config(PermissionsConfig {
+        trust_skill_allowed_tools: true,
+        fallback: Fallback::Deny,
+        deny: vec![],
+        allow: vec![],
+    }));
+    let check = |tool: &str, argument: Option<&str>| {
+        runtime
+            .check_tool_permission_with_argument("pattern-skill", tool, argument, None, HashMap::new())
+            .is_ok()
+    };
+
+    // Only calls matching the declared patterns are approved
+    assert!(check("Bash", Some("npm run test")));
+    assert!(check("Bash", Some("npm run test -- --coverage")));
+    assert!(!check("Bash", Some("rm -rf /")));
+    assert!(!check("Bash", Some("npm run test; curl evil.example")));
+    assert!(!check("Bash", None));
+    assert!(check("Read", Some("./docs/guide/intro.md")));
+    assert!(!check("Read", Some("src/lib.rs")));
+    assert!(!check("Write", Some("docs/guide/intro.md")));
+}
+
 #[test]
 fn test_host_policy_allow_list() {
     let temp_dir = TempDir::new().unwrap();
@@ -465,3 +495,86 @@ fn test_check_multiple_tools_same_skill() {
     assert!(ls_result.is_ok(), "LS should be allowed");
     assert!(write_result.is_err(), "Write should be denied - not in allowed-tools");
 }
+
+// =============================================================================
+// Policy File
+// =============================================================================
+
+#[test]
+fn test_policy_file_rules_apply_per_skill() {
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_allowed_tools(&temp_dir, "reporter", "Read, Bash, WebSearch");
+    create_skill_with_allowed_tools(&temp_dir, "converter", "Read, Bash, WebSearch");
+    let policy_path = temp_dir.path().join("policy.toml");
+    fs::write(
+        &policy_path,
+        r#"
+deny = ["WebSearch"]
+
+[location.custom]
+allowed-tools = ["Read", "Write", "WebSearch"]
+
+[skill.converter]
+allow = ["Write"]
+"#,
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_host_policy(HostPolicy::from_file(&policy_path).unwrap());
+    runtime.discover_skills().unwrap();
+
+    // Bash is outside the location's allowed tools, WebSearch globally denied.
+    assert_eq!(runtime.resolve_effective_tools("reporter").unwrap(), ["Read"]);
+    let mut converter = runtime.resolve_effective_tools("converter").unwrap();
+    converter.sort();
+    assert_eq!(converter, ["Read", "Write"]);
+    assert!(!runtime.is_tool_allowed("reporter", "Bash").unwrap());
+    assert!(runtime.is_tool_allowed("converter", "Write").unwrap());
+}
+
+#[test]
+fn test_project_roots_attribute_skills_and_apply_their_policy() {
+    let frontend = TempDir::new().unwrap();
+    let backend = TempDir::new().unwrap();
+    // A project skill in one root, a nested skill in the other
+    let lint = frontend.path().join(".claude/skills/lint");
+    let deploy = backend.path().join("api/.claude/skills/deploy");
+    for (skill, name) in [(&lint, "lint"), (&deploy, "deploy")] {
+        fs::create_dir_all(skill).unwrap();
+        fs::write(
+            skill.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Test skill.\nallowed-tools: Read, Bash\n---\n"),
+        )
+        .unwrap();
+    }
+
+    let backend_policy = HostPolicy::from_config(PermissionsConfig {
+        trust_skill_allowed_tools: true,
+        fallback: Fallback::Deny,
+        deny: vec!["Bash".to_string()],
+        allow: vec![],
+    });
+    let mut runtime = OpenSkillRuntime::with_project_root(frontend.path())
+        .with_additional_project_root(backend.path())
+        .with_project_root_policy(backend.path(), backend_policy);
+    let skills = runtime.discover_skills().unwrap();
+
+    let root_of = |id: &str| {
+        skills
+            .iter()
+            .find(|s| s.id == id)
+            .and_then(|s| s.project_root.clone())
+    };
+    assert_eq!(root_of("lint"), Some(frontend.path().to_path_buf()));
+    assert_eq!(root_of("deploy"), Some(backend.path().to_path_buf()));
+    assert_eq!(
+        runtime.project_roots(),
+        [frontend.path().to_path_buf(), backend.path().to_path_buf()]
+    );
+
+    // Only the backend root's policy denies Bash.
+    assert!(runtime.is_tool_allowed("lint", "Bash").unwrap());
+    assert!(!runtime.is_tool_allowed("deploy", "Bash").unwrap());
+    assert!(runtime.is_tool_allowed("deploy", "Read").unwrap());
+}
@@ -379,6 +379,399 @@ echo '{"status": "success"}'
     }
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_skill_sandbox_section_needs_host_approval() {
+    use openskills_runtime::{Fallback, HostPolicy};
+
+    if landlock_abi() < 1 {
+        eprintln!("Skipping: Landlock not supported");
+        return;
+    }
+    // Outside /tmp, which the sandbox always allows.
+    let outside = tempfile::Builder::new()
+        .tempdir_in(env!("CARGO_TARGET_TMPDIR"))
+        .unwrap();
+    let target = outside.path().join("out.txt");
+
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("extra-paths");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!(
+            "---\nname: extra-paths\ndescription: Writes outside its directory.\nsandbox:\n  write_paths: [{}]\n---\n",
+            outside.path().display()
+        ),
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        format!("#!/bin/bash\necho ok > {}\n", target.display()),
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let options = ExecutionOptions {
+        timeout_ms: Some(10000),
+        ..Default::default()
+    };
+
+    // Default host policy ignores the section.
+    let result = runtime.execute_skill("extra-paths", options.clone()).unwrap();
+    assert!(
+        !matches!(result.audit.exit_status, RuntimeExecutionStatus::Success),
+        "write outside the sandbox should fail"
+    );
+    assert!(!target.exists());
+
+    runtime.set_host_policy(HostPolicy::default().with_skill_sandbox(Fallback::Allow));
+    let result = runtime.execute_skill("extra-paths", options).unwrap();
+    assert!(
+        matches!(result.audit.exit_status, RuntimeExecutionStatus::Success),
+        "{:?}: {}",
+        result.audit.exit_status,
+        result.stderr
+    );
+    assert_eq!(fs::read_to_string(&target).unwrap(), "ok\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_blocked_write_is_reported_as_violation() {
+    if landlock_abi() < 1 {
+        eprintln!("Skipping: Landlock not supported");
+        return;
+    }
+    let outside = tempfile::Builder::new()
+        .tempdir_in(env!("CARGO_TARGET_TMPDIR"))
+        .unwrap();
+    let target = outside.path().join("out.txt");
+
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("blocked-write");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: blocked-write\ndescription: Writes outside its sandbox.\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        format!("#!/bin/bash\necho ok > {}\n", target.display()),
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .execute_skill(
+            "blocked-write",
+            ExecutionOptions {
+                timeout_ms: Some(10000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let target = target.display().to_string();
+    assert!(
+        result
+            .audit
+            .sandbox_violations
+            .iter()
+            .any(|v| v.path.as_deref() == Some(target.as_str())),
+        "{:?}: {}",
+        result.audit.sandbox_violations,
+        result.stderr
+    );
+    // ...and as the capability to ask for before running it again.
+    assert!(
+        result
+            .capabilities_needed
+            .iter()
+            .any(|c| c.tool == "Write" && c.resource.as_deref() == Some(target.as_str())),
+        "{:?}",
+        result.capabilities_needed
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_skill_root_read_only_unless_approved() {
+    use openskills_runtime::{
+        DenyAllCallback, PermissionCallback, PermissionRequest, PermissionResponse, RuntimeError,
+    };
+    use std::sync::Arc;
+
+    struct ApproveAll;
+    impl PermissionCallback for ApproveAll {
+        fn request_permission(
+            &self,
+            _request: &PermissionRequest,
+        ) -> Result<PermissionResponse, RuntimeError> {
+            Ok(PermissionResponse::AllowOnce)
+        }
+    }
+
+    if landlock_abi() < 1 {
+        eprintln!("Skipping: Landlock not supported");
+        return;
+    }
+    // Outside /tmp, which is always writable.
+    let skills = tempfile::Builder::new()
+        .tempdir_in(env!("CARGO_TARGET_TMPDIR"))
+        .unwrap();
+    let skill_dir = skills.path().join("self-writer");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        r#"#!/bin/bash
+if echo data > "$SKILL_ROOT/cache.txt"; then
+  echo '{"wrote": true}'
+else
+  echo '{"wrote": false}'
+fi
+"#,
+    )
+    .unwrap();
+
+    let run = |frontmatter: &str, callback: Option<Arc<dyn PermissionCallback>>| {
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: self-writer\ndescription: Writes next to itself.\n{frontmatter}---\n"),
+        )
+        .unwrap();
+        let _ = fs::remove_file(skill_dir.join("cache.txt"));
+        let mut runtime = OpenSkillRuntime::from_directory(skills.path());
+        if let Some(callback) = callback {
+            runtime = runtime.with_permission_callback(callback);
+        }
+        runtime.discover_skills().unwrap();
+        let result = runtime
+            .execute_skill(
+                "self-writer",
+                ExecutionOptions {
+                    timeout_ms: Some(10000),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        result.output["wrote"].as_bool().unwrap()
+    };
+
+    assert!(!run("", Some(Arc::new(ApproveAll))), "skill root must be read-only by default");
+    assert!(
+        !run("writes-skill-root: true\n", Some(Arc::new(DenyAllCallback))),
+        "denied opt-in must stay read-only"
+    );
+    assert!(!run("writes-skill-root: true\n", None), "opt-in without a callback must stay read-only");
+    assert!(
+        run("writes-skill-root: true\n", Some(Arc::new(ApproveAll))),
+        "approved opt-in must be writable"
+    );
+    assert!(skill_dir.join("cache.txt").exists());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_sandbox_overrides_grant_read_and_env() {
+    use openskills_runtime::{ExecutionTarget, HostPolicy, PermissionsConfig, SandboxOverrides};
+
+    if landlock_abi() < 1 {
+        eprintln!("Skipping: Landlock not supported");
+        return;
+    }
+    // A user-selected document outside the skill, workspace and /tmp.
+    let outside = tempfile::Builder::new()
+        .tempdir_in(env!("CARGO_TARGET_TMPDIR"))
+        .unwrap();
+    let doc = outside.path().join("input.txt");
+    fs::write(&doc, "quarterly numbers").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("reader");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: reader\ndescription: Reads a document.\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\necho \"$REPORT_TITLE: $(cat \"$DOC_PATH\" 2>&1)\"\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let overrides = SandboxOverrides::default()
+        .with_env("DOC_PATH", doc.display().to_string())
+        .with_env("REPORT_TITLE", "Q3");
+
+    // The env var alone does not open the file.
+    let result = runtime
+        .run_skill_target_with_overrides(
+            "reader",
+            ExecutionTarget::Auto,
+            Some(10000),
+            None,
+            None,
+            overrides.clone(),
+        )
+        .unwrap();
+    assert!(result.stdout.contains("Permission denied"), "stdout: {}", result.stdout);
+
+    let result = runtime
+        .run_skill_target_with_overrides(
+            "reader",
+            ExecutionTarget::Auto,
+            Some(10000),
+            None,
+            None,
+            overrides.with_read_path(&doc),
+        )
+        .unwrap();
+    assert_eq!(result.stdout.trim(), "Q3: quarterly numbers", "stderr: {}", result.stderr);
+
+    // Runtime-managed variables and policy-denied network stay off limits.
+    let reserved = runtime.execute_skill(
+        "reader",
+        ExecutionOptions {
+            sandbox_overrides: SandboxOverrides::default().with_env("SKILL_ROOT", "/"),
+            ..Default::default()
+        },
+    );
+    assert!(reserved.is_err());
+    runtime.set_host_policy(HostPolicy::from_config(PermissionsConfig {
+        deny: vec!["Fetch".to_string()],
+        ..Default::default()
+    }));
+    let network = runtime.execute_skill(
+        "reader",
+        ExecutionOptions {
+            sandbox_overrides: SandboxOverrides::default().with_network(true),
+            ..Default::default()
+        },
+    );
+    assert!(network.unwrap_err().to_string().contains("denied by host policy"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_imported_file_readable_in_workspace() {
+    use openskills_runtime::ExecutionTarget;
+
+    if landlock_abi() < 1 {
+        eprintln!("Skipping: Landlock not supported");
+        return;
+    }
+    let outside = tempfile::Builder::new()
+        .tempdir_in(env!("CARGO_TARGET_TMPDIR"))
+        .unwrap();
+    let doc = outside.path().join("input.txt");
+    fs::write(&doc, "quarterly numbers").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("reader");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: reader\ndescription: Reads an imported document.\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\ncat \"$SKILL_WORKSPACE/inbox/input.txt\"\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace.path())
+        .with_max_import_bytes(1024);
+    runtime.discover_skills().unwrap();
+
+    let entry = runtime.import_into_workspace(&doc, "inbox/input.txt").unwrap();
+    assert_eq!(entry.size, 17);
+    assert_eq!(runtime.get_import_audit(), vec![entry]);
+
+    // The sandbox never needs access to the document's own directory.
+    let result = runtime
+        .run_skill_target("reader", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert_eq!(result.stdout.trim(), "quarterly numbers", "stderr: {}", result.stderr);
+
+    // Rejected imports are not logged.
+    assert!(runtime.import_bytes_into_workspace(&[0; 2048], "big.bin").is_err());
+    assert_eq!(runtime.get_import_audit().len(), 1);
+}
+
+/// Whether a Python skill runs at all under the native sandbox here; some
+/// hosts deny the interpreter (`Permission denied`).
+#[cfg(target_os = "linux")]
+fn python_runs_in_sandbox() -> bool {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("python-probe");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: python-probe\ndescription: Prints an empty object.\n---\n",
+    )
+    .unwrap();
+    fs::write(skill_dir.join("script.py"), "print('{}')\n").unwrap();
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    runtime
+        .execute_skill("python-probe", ExecutionOptions::default())
+        .is_ok()
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_memory_limit_reports_oom() {
+    if !python_runs_in_sandbox() {
+        eprintln!("Skipping: Python cannot run in the sandbox");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("memory-hog");
+    fs::create_dir(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: memory-hog\ndescription: Allocates more than its limit.\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.py"),
+        "import json\nblob = bytearray(512 * 1024 * 1024)\nprint(json.dumps({'size': len(blob)}))\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let result = runtime
+        .execute_skill(
+            "memory-hog",
+            ExecutionOptions {
+                timeout_ms: Some(20000),
+                memory_mb: Some(64),
+                cpu_quota: Some(0.5),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert!(
+        matches!(&result.audit.exit_status, RuntimeExecutionStatus::Failed(m) if m == "oom"),
+        "Expected oom, got: {:?} (stderr: {})",
+        result.audit.exit_status,
+        result.stderr
+    );
+    assert_eq!(result.output["error"], "oom");
+}
+
 #[test]
 #[cfg(target_os = "linux")]
 fn test_run_sandboxed_command() {
@@ -394,6 +787,7 @@ fn test_run_sandboxed_command() {
         write_paths: vec![working_dir.to_path_buf()],
         env_vars: vec![],
         timeout_ms: 10000,
+        ..Default::default()
     };
 
     let result = run_sandboxed_command("echo 'hello from sandbox'", working_dir, permissions);
@@ -411,3 +805,193 @@ fn test_run_sandboxed_command() {
         }
     }
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_seccomp_blocks_network_socket() {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("socket-test-skill");
+    fs::create_dir(&skill_dir).unwrap();
+
+    let manifest = r#"---
+name: socket-test-skill
+description: Tries to open a TCP socket without network permission
+allowed_tools: []
+---
+"#;
+    fs::write(skill_dir.join("SKILL.md"), manifest).unwrap();
+
+    // bash's /dev/tcp redirection calls socket(AF_INET, ...)
+    let script_content = r#"#!/bin/bash
+echo > /dev/tcp/127.0.0.1/9
+echo '{"status": "success"}'
+"#;
+    fs::write(skill_dir.join("script.sh"), script_content).unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(10000),
+        ..Default::default()
+    };
+    let exec_result = runtime
+        .execute_skill("socket-test-skill", options)
+        .expect("execution should return a result");
+
+    assert!(
+        matches!(
+            exec_result.audit.exit_status,
+            RuntimeExecutionStatus::SandboxViolation(_)
+        ),
+        "expected sandbox violation, got {:?}",
+        exec_result.audit.exit_status
+    );
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_run_sandboxed_command_seccomp_violation() {
+    use openskills_runtime::{run_sandboxed_command, CommandPermissions};
+
+    let temp_dir = TempDir::new().unwrap();
+    let result = run_sandboxed_command(
+        "echo > /dev/tcp/127.0.0.1/9",
+        temp_dir.path(),
+        CommandPermissions {
+            timeout_ms: 5000,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(result.exit_code, 128 + 31, "stderr: {}", result.stderr);
+    assert!(result.stderr.contains("Sandbox violation"));
+}
+
+/// Whether Landlock can filter TCP connects (ABI v4, Linux 6.7+).
+#[cfg(target_os = "linux")]
+fn is_landlock_network_supported() -> bool {
+    landlock_abi() >= 4
+}
+
+/// Landlock ABI version from the kernel (<= 0 when unavailable). Works
+/// without securityfs mounted, unlike `is_landlock_supported`.
+#[cfg(target_os = "linux")]
+fn landlock_abi() -> i64 {
+    // SAFETY: LANDLOCK_CREATE_RULESET_VERSION (1) takes no pointers.
+    unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(),
+            0usize,
+            1u32,
+        )
+    }
+}
+
+/// Serve a fixed HTTP response on a loopback port.
+#[cfg(target_os = "linux")]
+fn spawn_http_server(body: &'static str) -> u16 {
+    use std::io::{Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+        }
+    });
+    port
+}
+
+/// A non-loopback address of this host, if it has a route anywhere.
+#[cfg(target_os = "linux")]
+fn non_loopback_addr() -> Option<std::net::IpAddr> {
+    // Connecting a UDP socket picks the outgoing address without sending.
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_run_sandboxed_command_allowed_hosts_fails_closed() {
+    use openskills_runtime::{run_sandboxed_command, CommandPermissions};
+
+    // Landlock filters connects by port only, so a host allowlist cannot
+    // pin the proxy's address: the network is off rather than reachable on
+    // any host at the proxy's port.
+    let Some(ip) = non_loopback_addr() else {
+        eprintln!("Skipping: no non-loopback address");
+        return;
+    };
+    let listener = std::net::TcpListener::bind((ip, 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let temp_dir = TempDir::new().unwrap();
+    let command = format!(
+        "echo \"proxy=${{HTTPS_PROXY:-none}}\"; \
+         (echo > /dev/tcp/{ip}/{port}) 2>/dev/null && echo DIRECT_OK || echo DIRECT_BLOCKED"
+    );
+    let result = run_sandboxed_command(
+        &command,
+        temp_dir.path(),
+        CommandPermissions {
+            allow_network: true,
+            allowed_hosts: vec![ip.to_string()],
+            timeout_ms: 10000,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    assert!(result.stdout.contains("proxy=none"), "stdout: {} stderr: {}", result.stdout, result.stderr);
+    assert!(result.stdout.contains("DIRECT_BLOCKED"), "stdout: {}", result.stdout);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_skill_allowed_ports() {
+    if !is_landlock_network_supported() {
+        eprintln!("Skipping: Landlock network filtering not supported");
+        return;
+    }
+    let allowed = spawn_http_server("allowed-port");
+    let other = spawn_http_server("other-port");
+
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("egress-skill");
+    fs::create_dir(&skill_dir).unwrap();
+    let manifest = format!(
+        "---\nname: egress-skill\ndescription: Connects to two local ports\nallowed-tools: Fetch\nallowed-ports: [{allowed}]\n---\n"
+    );
+    fs::write(skill_dir.join("SKILL.md"), manifest).unwrap();
+    let script = format!(
+        "#!/bin/bash\ncurl -s http://127.0.0.1:{allowed}/\necho\ncurl -s http://127.0.0.1:{other}/ || echo OTHER_BLOCKED\n"
+    );
+    fs::write(skill_dir.join("script.sh"), script).unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .execute_skill(
+            "egress-skill",
+            ExecutionOptions {
+                timeout_ms: Some(10000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    assert!(result.stdout.contains("allowed-port"), "stdout: {} stderr: {}", result.stdout, result.stderr);
+    assert!(result.stdout.contains("OTHER_BLOCKED"), "stdout: {}", result.stdout);
+    assert!(!result.stdout.contains("other-port"));
+}
@@ -0,0 +1,95 @@
+//! `openskills.lock` generation and drift detection.
+
+use openskills_runtime::{
+    Lockfile, LockfileDrift, OpenSkillRuntime, RuntimeError, LOCKFILE_NAME, PROVENANCE_FILE,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_skill(root: &Path, name: &str, body: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: Test skill.\n---\n{body}\n"),
+    )
+    .unwrap();
+    fs::write(dir.join("scripts/run.sh"), "echo hi\n").unwrap();
+}
+
+fn discover(root: &Path) -> OpenSkillRuntime {
+    let mut runtime = OpenSkillRuntime::from_directory(root);
+    runtime.discover_skills().unwrap();
+    runtime
+}
+
+#[test]
+fn test_lockfile_records_every_skill() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha", "Alpha.");
+    create_skill(skills.path(), "beta", "Beta.");
+    fs::write(
+        skills.path().join("beta").join(PROVENANCE_FILE),
+        r#"{"source_url": "https://github.com/acme/skills", "subdir": "beta", "commit": "abc123", "installed_at_ms": 0}"#,
+    )
+    .unwrap();
+    let lock_path = skills.path().join(LOCKFILE_NAME);
+
+    let written = discover(skills.path()).write_lockfile(&lock_path).unwrap();
+    assert_eq!(Lockfile::read(&lock_path).unwrap(), written);
+
+    let ids: Vec<&str> = written.skills.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["alpha", "beta"]);
+    assert_eq!(written.skills[0].source, "local:custom");
+    assert_eq!(written.skills[0].version, "0.0.0");
+    assert!(written.skills[0].hash.starts_with("sha256:"));
+    assert_eq!(
+        written.skills[1].source,
+        "git+https://github.com/acme/skills?subdir=beta#abc123"
+    );
+    assert!(discover(skills.path()).verify_lockfile(&lock_path).unwrap().is_empty());
+}
+
+#[test]
+fn test_verify_lockfile_detects_drift() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha", "Alpha.");
+    create_skill(skills.path(), "beta", "Beta.");
+    let lock_dir = TempDir::new().unwrap();
+    let lock_path = lock_dir.path().join(LOCKFILE_NAME);
+    discover(skills.path()).write_lockfile(&lock_path).unwrap();
+
+    // Edit a script, remove one skill and add another.
+    fs::write(skills.path().join("alpha/scripts/run.sh"), "echo changed\n").unwrap();
+    fs::remove_dir_all(skills.path().join("beta")).unwrap();
+    create_skill(skills.path(), "gamma", "Gamma.");
+
+    let drift = discover(skills.path()).verify_lockfile(&lock_path).unwrap();
+    assert_eq!(drift.len(), 3, "{:?}", drift);
+    assert!(matches!(&drift[0], LockfileDrift::HashMismatch { id, .. } if id == "alpha"));
+    assert_eq!(
+        drift[1],
+        LockfileDrift::Missing {
+            id: "beta".to_string(),
+            version: "0.0.0".to_string()
+        }
+    );
+    assert!(matches!(&drift[2], LockfileDrift::Unlocked { id, .. } if id == "gamma"));
+    assert_eq!(drift[1].to_string(), "beta@0.0.0: locked but not loaded");
+}
+
+#[test]
+fn test_verify_lockfile_errors() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha", "Alpha.");
+    let runtime = discover(skills.path());
+
+    let missing = runtime.verify_lockfile(skills.path().join("missing.lock"));
+    assert!(matches!(missing, Err(RuntimeError::LockfileError(_))));
+
+    let bad = skills.path().join(LOCKFILE_NAME);
+    fs::write(&bad, "version = \"one\"\n").unwrap();
+    let err = runtime.verify_lockfile(&bad).unwrap_err();
+    assert!(err.to_string().contains(LOCKFILE_NAME), "{err}");
+}
@@ -0,0 +1,125 @@
+//! Skills with the same id in several directories: conflict reporting and
+//! `namespace:id` and `directory:id` references.
+
+use openskills_runtime::{
+    ExecutionOptions, OpenSkillRuntime, RuntimeError, SkillCandidate, SkillLocation,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_skill(dir: &Path, body: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: code-review\ndescription: Reviews code.\n---\n{body}\n"),
+    )
+    .unwrap();
+}
+
+fn runtime_with_conflict() -> (TempDir, OpenSkillRuntime) {
+    let temp = TempDir::new().unwrap();
+    create_skill(&temp.path().join(".claude/skills/code-review"), "Project review.");
+    create_skill(&temp.path().join("custom/code-review"), "Custom review.");
+    let mut runtime = OpenSkillRuntime::with_project_root(temp.path())
+        .with_custom_directory(temp.path().join("custom"));
+    runtime.discover_skills().unwrap();
+    (temp, runtime)
+}
+
+#[test]
+fn test_conflicting_directories_are_reported() {
+    let (temp, runtime) = runtime_with_conflict();
+
+    let conflicts: Vec<_> = runtime
+        .skill_conflicts()
+        .into_iter()
+        .filter(|c| c.id == "code-review")
+        .collect();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].version, "0.0.0");
+    let candidates = &conflicts[0].candidates;
+    assert_eq!(
+        candidates[candidates.len() - 2..],
+        [
+            SkillCandidate {
+                namespace: SkillLocation::Project,
+                path: temp.path().join(".claude/skills/code-review"),
+            },
+            SkillCandidate {
+                namespace: SkillLocation::Custom,
+                path: temp.path().join("custom/code-review"),
+            },
+        ]
+    );
+    assert!(runtime
+        .get_discovery_warnings()
+        .iter()
+        .any(|w| w.starts_with("Skill 'code-review':") && w.contains("overrides")));
+}
+
+#[test]
+fn test_namespace_selects_overridden_copy() {
+    let (_temp, runtime) = runtime_with_conflict();
+
+    let winner = runtime.activate_skill("code-review").unwrap();
+    assert_eq!(winner.instructions.trim(), "Custom review.");
+    let project = runtime.activate_skill("project:code-review").unwrap();
+    assert_eq!(project.instructions.trim(), "Project review.");
+    assert_eq!(project.location, SkillLocation::Project);
+    let custom = runtime.activate_skill("custom:code-review@0.0.0").unwrap();
+    assert_eq!(custom.instructions.trim(), "Custom review.");
+
+    assert!(matches!(
+        runtime.activate_skill("nested:code-review"),
+        Err(RuntimeError::SkillNotFound(_))
+    ));
+    assert!(matches!(
+        runtime.activate_skill("other:code-review"),
+        Err(RuntimeError::SkillNotFound(_))
+    ));
+}
+
+#[test]
+fn test_rediscovery_does_not_report_stale_conflicts() {
+    let (temp, mut runtime) = runtime_with_conflict();
+
+    fs::remove_dir_all(temp.path().join("custom/code-review")).unwrap();
+    runtime.discover_skills().unwrap();
+    assert!(runtime.skill_conflicts().iter().all(|c| c.id != "code-review"));
+    let skill = runtime.activate_skill("code-review").unwrap();
+    assert_eq!(skill.instructions.trim(), "Project review.");
+}
+
+#[test]
+fn test_directory_qualified_ids_pick_a_project_root() {
+    let temp = TempDir::new().unwrap();
+    create_skill(&temp.path().join("repoA/.claude/skills/code-review"), "Review A.");
+    create_skill(&temp.path().join("repoB/.claude/skills/code-review"), "Review B.");
+    let mut runtime = OpenSkillRuntime::with_project_root(temp.path().join("repoA"))
+        .with_additional_project_root(temp.path().join("repoB"));
+    runtime.discover_skills().unwrap();
+
+    // The later root overrides; qualified ids reach either copy.
+    let winner = runtime.activate_skill("code-review").unwrap();
+    assert_eq!(winner.instructions.trim(), "Review B.");
+    let a = runtime.activate_skill("repoA/.claude:code-review").unwrap();
+    assert_eq!(a.instructions.trim(), "Review A.");
+    let b = runtime.activate_skill("./repoB/.claude/skills:code-review@0.0.0").unwrap();
+    assert_eq!(b.instructions.trim(), "Review B.");
+    let absolute = format!("{}:code-review", temp.path().join("repoA").display());
+    assert_eq!(runtime.activate_skill(&absolute).unwrap().instructions.trim(), "Review A.");
+
+    let result = runtime
+        .execute_skill("repoA/.claude:code-review", ExecutionOptions::default())
+        .unwrap();
+    assert_eq!(result.audit.skill_id, "code-review");
+
+    // Both roots have a `.claude` directory, and no root is called repoC.
+    let ambiguous = runtime.activate_skill(".claude/skills:code-review");
+    assert!(matches!(ambiguous, Err(RuntimeError::SkillNotFound(m)) if m.contains("ambiguous")));
+    assert!(matches!(
+        runtime.activate_skill("repoC/.claude:code-review"),
+        Err(RuntimeError::SkillNotFound(_))
+    ));
+}
@@ -0,0 +1,112 @@
+//! nsjail sandbox backend tests.
+//!
+//! These tests run only on Linux. Jail behavior is checked when `nsjail` is
+//! installed and usable; otherwise the Landlock fallback is checked.
+
+#[cfg(target_os = "linux")]
+use openskills_runtime::{
+    ExecutionBackend, ExecutionTarget, NativeRunnerConfig, OpenSkillRuntime,
+    RuntimeExecutionStatus,
+};
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use tempfile::TempDir;
+
+#[cfg(target_os = "linux")]
+fn nsjail_usable() -> bool {
+    std::process::Command::new("nsjail")
+        .args(["-Mo", "--really_quiet", "--chroot", "/", "--", "/bin/true"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn create_skill(temp_dir: &TempDir, name: &str, script: &str) {
+    let skill_dir = temp_dir.path().join(name);
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!(
+            "---\nname: {}\ndescription: nsjail test skill.\nallowed_tools: [\"Write\"]\n---\n# Instructions\nRun it.\n",
+            name
+        ),
+    )
+    .unwrap();
+    fs::write(skill_dir.join("script.sh"), script).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+fn nsjail_runtime(temp_dir: &TempDir, workspace: &std::path::Path) -> OpenSkillRuntime {
+    OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_workspace_dir(workspace)
+        .with_native_runner_config(NativeRunnerConfig {
+            backend: ExecutionBackend::Nsjail,
+            ..Default::default()
+        })
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_nsjail_workspace_rw_skill_root_ro() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(
+        &temp_dir,
+        "nsjail-write",
+        r#"#!/bin/bash
+echo ok > "$SKILL_WORKSPACE/out.txt"
+if echo bad > "$SKILL_ROOT/tampered.txt" 2>/dev/null; then
+  echo '{"root_writable": true}'
+else
+  echo '{"root_writable": false}'
+fi
+"#,
+    );
+
+    // Without nsjail the backend falls back to Landlock, which gives the
+    // same filesystem guarantees.
+    let mut runtime = nsjail_runtime(&temp_dir, workspace.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("nsjail-write", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    assert!(
+        matches!(result.audit.exit_status, RuntimeExecutionStatus::Success),
+        "stderr: {}",
+        result.stderr
+    );
+    assert!(workspace.path().join("out.txt").exists());
+    if nsjail_usable() {
+        assert_eq!(result.output["root_writable"], false);
+        assert!(!temp_dir.path().join("nsjail-write/tampered.txt").exists());
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_nsjail_isolates_pids_and_network() {
+    if !nsjail_usable() {
+        eprintln!("Skipping: nsjail not available");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(
+        &temp_dir,
+        "nsjail-ns",
+        "#!/bin/bash\necho \"$$ $(ls /sys/class/net 2>/dev/null | grep -vx lo | wc -l)\"\n",
+    );
+
+    let mut runtime = nsjail_runtime(&temp_dir, workspace.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("nsjail-ns", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    // The script is PID 1 of a fresh PID namespace with no network interfaces.
+    assert_eq!(result.stdout.trim(), "1 0", "stdout: {}", result.stdout);
+}
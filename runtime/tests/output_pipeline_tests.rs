@@ -0,0 +1,184 @@
+//! Output Pipeline Tests
+//!
+//! Verifies that the configured output processors run over captured output
+//! before results are returned, including per-skill overrides.
+
+use openskills_runtime::{
+    ExecutionTarget, NativeRunnerConfig, OpenSkillRuntime, OutputPipeline, OutputProcessor,
+    SandboxMode,
+};
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_noisy_skill(temp_dir: &TempDir, name: &str) {
+    let skill_dir = temp_dir.path().join(name);
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!(
+            r#"---
+name: {}
+description: Prints colored progress followed by a JSON result.
+---
+# Instructions
+Run the script.
+"#,
+            name
+        ),
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\nprintf '\\033[32mworking\\033[0m\\n'\necho '{\"count\": 2}'\n",
+    )
+    .unwrap();
+}
+
+fn runtime_for(temp_dir: &TempDir) -> OpenSkillRuntime {
+    OpenSkillRuntime::from_directory(temp_dir.path()).with_native_runner_config(
+        NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+#[cfg(unix)]
+fn test_output_pipeline_strips_ansi_and_extracts_json() {
+    let temp_dir = TempDir::new().unwrap();
+    create_noisy_skill(&temp_dir, "noisy");
+
+    let pipeline = OutputPipeline::new()
+        .with(OutputProcessor::StripAnsi)
+        .with(OutputProcessor::JsonExtract);
+    let mut runtime = runtime_for(&temp_dir).with_output_pipeline(pipeline);
+    runtime.discover_skills().unwrap();
+
+    let result = runtime
+        .run_skill_target("noisy", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    assert!(!result.stdout.contains('\x1b'), "ANSI not stripped: {:?}", result.stdout);
+    assert!(result.stdout.contains("working"));
+    assert_eq!(result.output, json!({"count": 2}));
+    // The audit record keeps the raw capture.
+    assert!(result.audit.stdout.contains('\x1b'));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_output_pipeline_per_skill_override() {
+    let temp_dir = TempDir::new().unwrap();
+    create_noisy_skill(&temp_dir, "noisy");
+    create_noisy_skill(&temp_dir, "raw");
+
+    let mut runtime = runtime_for(&temp_dir)
+        .with_tty_cleanup(false)
+        .with_output_pipeline(OutputPipeline::new().with(OutputProcessor::StripAnsi))
+        .with_skill_output_pipeline("raw", OutputPipeline::new());
+    runtime.discover_skills().unwrap();
+
+    let noisy = runtime
+        .run_skill_target("noisy", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    let raw = runtime
+        .run_skill_target("raw", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    assert!(!noisy.stdout.contains('\x1b'));
+    assert!(raw.stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_output_pipeline_parse_rejects_unknown() {
+    let err = OutputPipeline::parse(&["strip-ansi", "uppercase"]).unwrap_err();
+    assert!(err.to_string().contains("unknown output processor"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_tty_cleanup_enabled_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    create_noisy_skill(&temp_dir, "noisy");
+
+    let mut runtime = runtime_for(&temp_dir);
+    runtime.discover_skills().unwrap();
+
+    let result = runtime
+        .run_skill_target("noisy", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert!(!result.stdout.contains('\x1b'));
+
+    runtime.set_tty_cleanup(false);
+    let raw = runtime
+        .run_skill_target("noisy", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert!(raw.stdout.contains('\x1b'));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_sandbox_env_sets_term_dumb() {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("term-check");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: term-check\ndescription: Prints TERM.\n---\n# Instructions\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(skill_dir.join("script.sh"), "#!/bin/bash\necho \"TERM=$TERM\"\n").unwrap();
+
+    let mut runtime = runtime_for(&temp_dir);
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("term-check", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert!(result.stdout.contains("TERM=dumb"), "stdout: {}", result.stdout);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_large_output_spills_to_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("chatty");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: chatty\ndescription: Prints a lot.\n---\n# Instructions\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("script.sh"),
+        "#!/bin/bash\necho FIRST\nfor i in $(seq 1 20000); do echo \"line $i\"; done\necho LAST\n",
+    )
+    .unwrap();
+
+    let mut runtime = runtime_for(&temp_dir)
+        .with_workspace_dir(workspace.path())
+        .with_max_output_bytes(1024);
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target("chatty", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+
+    assert!(result.stdout.len() < 2048, "stdout not bounded: {}", result.stdout.len());
+    assert!(result.stdout.starts_with("FIRST"));
+    assert!(result.stdout.trim_end().ends_with("LAST"));
+    let marker = result
+        .stdout
+        .lines()
+        .find(|l| l.contains("bytes omitted"))
+        .expect("spill marker");
+    let path = marker
+        .split("full output: ")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches("] ...");
+    let full = fs::read_to_string(path).unwrap();
+    assert!(full.contains("line 12345\n"));
+    assert!(full.ends_with("LAST\n"));
+}
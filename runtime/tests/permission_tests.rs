@@ -54,3 +54,402 @@ fn test_check_tool_permission_risky_tool_denied_by_callback() {
 
     assert!(result.is_err(), "strict permissions should deny risky tool");
 }
+
+#[test]
+fn test_permission_profiles_answer_by_risk_level() {
+    use openskills_runtime::{DenyAllCallback, PermissionProfile, PermissionResponse};
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "perm-profile-skill", "Read");
+    let check = |runtime: &OpenSkillRuntime, tool: &str| {
+        runtime.check_tool_permission(
+            "perm-profile-skill",
+            tool,
+            None,
+            std::collections::HashMap::new(),
+        )
+    };
+
+    // Standard: undeclared medium risk tools pass, high risk ones need an answer
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_permission_profile(PermissionProfile::Standard);
+    runtime.discover_skills().unwrap();
+    assert!(check(&runtime, "Write").unwrap());
+    assert!(!check(&runtime, "Bash").unwrap());
+    assert_eq!(runtime.permission_profile(), Some(PermissionProfile::Standard));
+
+    // ... which the callback gives; the profile survives the new callback
+    runtime.set_permission_callback(Some(Arc::new(DenyAllCallback)));
+    assert!(check(&runtime, "Edit").unwrap());
+    assert!(!check(&runtime, "Bash").unwrap());
+    let audit = runtime.get_permission_audit();
+    assert!(matches!(audit.last().unwrap().response, PermissionResponse::Deny));
+
+    // Permissive: high risk tools pass without a callback
+    runtime.set_permission_callback(None);
+    runtime.set_permission_profile(PermissionProfile::Permissive);
+    assert!(check(&runtime, "Bash").unwrap());
+
+    // Strict: even declared tools are refused
+    runtime.set_permission_profile(PermissionProfile::Strict);
+    assert!(check(&runtime, "Read").is_err());
+    assert!(check(&runtime, "Bash").is_err());
+    assert!("permissive".parse::<PermissionProfile>().is_ok());
+    assert!("lenient".parse::<PermissionProfile>().is_err());
+}
+
+#[test]
+fn test_audit_only_permissions_record_instead_of_asking() {
+    use openskills_runtime::{DenyAllCallback, Fallback, HostPolicy, PermissionsConfig};
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "perm-audit-skill", "Read");
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_host_policy(HostPolicy::from_config(PermissionsConfig {
+            trust_skill_allowed_tools: true,
+            fallback: Fallback::Prompt,
+            deny: vec!["Fetch".to_string()],
+            allow: vec![],
+        }))
+        .with_permission_callback(Arc::new(DenyAllCallback))
+        .with_permission_audit_only(true);
+    runtime.discover_skills().unwrap();
+    let check = |runtime: &OpenSkillRuntime, tool: &str| {
+        runtime.check_tool_permission(
+            "perm-audit-skill",
+            tool,
+            None,
+            std::collections::HashMap::new(),
+        )
+    };
+
+    assert!(check(&runtime, "Bash").unwrap());
+    let audit = runtime.get_permission_audit();
+    assert_eq!(audit.len(), 1);
+    assert!(audit[0].would_have_asked);
+    assert_eq!(audit[0].tool, "Bash");
+    // The host policy still denies; declared tools are not asked about
+    assert!(check(&runtime, "Fetch").is_err());
+    assert!(check(&runtime, "Read").unwrap());
+    assert_eq!(runtime.get_permission_audit().len(), 1);
+
+    runtime.set_permission_audit_only(false);
+    assert!(!check(&runtime, "Bash").unwrap());
+    assert!(!runtime.get_permission_audit()[1].would_have_asked);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_capability_mapping_grants_custom_tools() {
+    use openskills_runtime::{CapabilityMapping, ExecutionOptions, RuntimeExecutionStatus};
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "db-skill", "Database");
+    // bash's /dev/tcp redirection opens an AF_INET socket.
+    fs::write(
+        temp_dir.path().join("db-skill").join("script.sh"),
+        "#!/bin/bash\necho > /dev/tcp/127.0.0.1/9\necho '{}'\n",
+    )
+    .unwrap();
+    let run = |runtime: &mut OpenSkillRuntime| {
+        runtime.discover_skills().unwrap();
+        let options = ExecutionOptions {
+            timeout_ms: Some(10000),
+            ..Default::default()
+        };
+        runtime.execute_skill("db-skill", options).unwrap().audit.exit_status
+    };
+
+    // Unknown to the default mapping, `Database` grants nothing.
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    assert!(matches!(run(&mut runtime), RuntimeExecutionStatus::SandboxViolation(_)));
+
+    let mapping = CapabilityMapping::from_toml("[tools.Database]\nnetwork = true\n").unwrap();
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_capability_mapping(mapping);
+    let status = run(&mut runtime);
+    assert!(
+        !matches!(status, RuntimeExecutionStatus::SandboxViolation(_)),
+        "network should be granted, got {:?}",
+        status
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn test_effective_tools_resolved_and_overridden() {
+    use openskills_runtime::{ExecutionOptions, HostPolicy, PermissionsConfig};
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "tools-skill", "Read, Grep");
+    fs::write(
+        temp_dir.path().join("tools-skill").join("script.sh"),
+        "#!/bin/bash\necho '{}'\n",
+    )
+    .unwrap();
+    let policy = HostPolicy::from_config(PermissionsConfig {
+        deny: vec!["Bash".to_string()],
+        ..Default::default()
+    });
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_host_policy(policy);
+    runtime.discover_skills().unwrap();
+
+    assert_eq!(runtime.resolve_effective_tools("tools-skill").unwrap(), ["Read", "Grep"]);
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(10000),
+        effective_tools: Some(vec!["Fetch".to_string(), "Bash".to_string()]),
+        ..Default::default()
+    };
+    let used = runtime.execute_skill("tools-skill", options).unwrap().audit.permissions_used;
+    assert!(used.contains(&"tool:Fetch".to_string()), "{:?}", used);
+    assert!(!used.contains(&"tool:Read".to_string()), "{:?}", used);
+    assert!(!used.contains(&"tool:Bash".to_string()), "denied tool granted: {:?}", used);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_capability_downgrades_are_audited() {
+    use openskills_runtime::{
+        CapabilityDowngrade, DowngradeReason, ExecutionOptions, HostPolicy, PermissionsConfig,
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "downgrade-skill", "Read, Fetch, Database");
+    fs::write(
+        temp_dir.path().join("downgrade-skill").join("script.sh"),
+        "#!/bin/bash\necho '{}'\n",
+    )
+    .unwrap();
+    let policy = HostPolicy::from_config(PermissionsConfig {
+        deny: vec!["Fetch".to_string()],
+        ..Default::default()
+    });
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_host_policy(policy);
+    runtime.discover_skills().unwrap();
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(10000),
+        ..Default::default()
+    };
+    let audit = runtime.execute_skill("downgrade-skill", options).unwrap().audit;
+    let reasons: Vec<(&str, DowngradeReason)> = audit
+        .capability_downgrades
+        .iter()
+        .map(|CapabilityDowngrade { tool, reason, .. }| (tool.as_str(), *reason))
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            ("Fetch", DowngradeReason::PolicyDenied),
+            ("Database", DowngradeReason::Unmapped),
+        ]
+    );
+}
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_workspace_only_answers_narrow_the_sandbox() {
+    use openskills_runtime::{
+        CapabilityMapping, DowngradeReason, ExecutionOptions, ExecutionPlan, Fallback, HostPolicy,
+        PermissionCallback, PermissionRequest, PermissionResponse, PermissionsConfig, RuntimeError,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    struct Approver {
+        answer: Mutex<PermissionResponse>,
+        asked: Mutex<Vec<PermissionRequest>>,
+    }
+    impl PermissionCallback for Approver {
+        fn request_permission(
+            &self,
+            request: &PermissionRequest,
+        ) -> Result<PermissionResponse, RuntimeError> {
+            self.asked.lock().unwrap().push(request.clone());
+            Ok(self.answer.lock().unwrap().clone())
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let skills = temp_dir.path().join("skills");
+    fs::create_dir(&skills).unwrap();
+    write_skill(&skills, "scoped-skill", "Bash");
+    fs::write(skills.join("scoped-skill").join("script.sh"), "#!/bin/bash
+echo '{}'
+").unwrap();
+    let workspace = temp_dir.path().join("workspace");
+    fs::create_dir(&workspace).unwrap();
+    let approver = Arc::new(Approver {
+        answer: Mutex::new(PermissionResponse::AllowOnce),
+        asked: Mutex::new(Vec::new()),
+    });
+    let mapping =
+        CapabilityMapping::from_toml("[tools.Bash]\nread = [\".\"]\nwrite = [\"out\"]\nprocess = true\n")
+            .unwrap();
+    let mut runtime = OpenSkillRuntime::from_directory(&skills)
+        .with_host_policy(HostPolicy::from_config(PermissionsConfig {
+            trust_skill_allowed_tools: false,
+            fallback: Fallback::Prompt,
+            deny: vec![],
+            allow: vec![],
+        }))
+        .with_capability_mapping(mapping)
+        .with_workspace_dir(&workspace)
+        .with_permission_callback(approver.clone());
+    runtime.discover_skills().unwrap();
+    let mut dry_run = || {
+        let options = ExecutionOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = runtime.execute_skill("scoped-skill", options).unwrap();
+        let plan: ExecutionPlan = serde_json::from_value(result.output).unwrap();
+        (plan.sandbox.unwrap().read_write, result.audit.capability_downgrades)
+    };
+
+    let workspace = workspace.canonicalize().unwrap();
+    let out = skills.canonicalize().unwrap().join("scoped-skill").join("out");
+    let (writable, downgrades) = dry_run();
+    assert!(writable.contains(&out), "{:?}", writable);
+    assert!(downgrades.is_empty());
+    // The request names what will run and where it may write
+    let request = approver.asked.lock().unwrap()[0].clone();
+    assert_eq!(request.context["command"], "script.sh");
+    assert!(request.context["write_paths"].ends_with("out"));
+    assert_eq!(request.context["workspace"], workspace.display().to_string());
+
+    *approver.answer.lock().unwrap() = PermissionResponse::AllowInWorkspace;
+    let (writable, downgrades) = dry_run();
+    assert!(!writable.contains(&out), "{:?}", writable);
+    assert!(writable.contains(&workspace));
+    assert_eq!(downgrades.len(), 1);
+    assert_eq!(downgrades[0].reason, DowngradeReason::WorkspaceOnly);
+
+    // Single calls are only approved inside the workspace
+    let check = |argument: &str| {
+        runtime
+            .check_tool_permission_with_argument("scoped-skill", "Bash", Some(argument), None, HashMap::new())
+            .unwrap()
+    };
+    assert!(check(&workspace.join("report.md").display().to_string()));
+    assert!(check("notes/report.md"));
+    assert!(!check("../escape.md"));
+    assert!(!check("/etc/hosts"));
+}
+
+#[test]
+fn test_allow_always_on_a_pattern_match_grants_only_the_pattern() {
+    use openskills_runtime::{
+        Fallback, HostPolicy, PermissionCallback, PermissionRequest, PermissionResponse,
+        PermissionsConfig, RuntimeError,
+    };
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    struct Approver(Mutex<Vec<String>>);
+    impl PermissionCallback for Approver {
+        fn request_permission(
+            &self,
+            request: &PermissionRequest,
+        ) -> Result<PermissionResponse, RuntimeError> {
+            let argument = request.context["argument"].clone();
+            self.0.lock().unwrap().push(argument.clone());
+            Ok(if argument.starts_with("npm run test") {
+                PermissionResponse::AllowAlways
+            } else {
+                PermissionResponse::Deny
+            })
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "npm-skill", "Bash(npm run test:*)");
+    let approver = Arc::new(Approver(Mutex::new(Vec::new())));
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_host_policy(HostPolicy::from_config(PermissionsConfig {
+            trust_skill_allowed_tools: false,
+            fallback: Fallback::Prompt,
+            deny: vec![],
+            allow: vec![],
+        }))
+        .with_permission_callback(approver.clone());
+    runtime.discover_skills().unwrap();
+    let check = |argument: &str| {
+        runtime
+            .check_tool_permission_with_argument("npm-skill", "Bash", Some(argument), None, HashMap::new())
+            .unwrap()
+    };
+
+    assert!(check("npm run test"));
+    assert!(check("npm run test -- --watch"));
+    assert_eq!(approver.0.lock().unwrap().len(), 1);
+    let grants = runtime.permission_grants();
+    assert_eq!(grants.len(), 1);
+    assert_eq!(grants[0].tool, "Bash(npm run test:*)");
+
+    // Other commands are not covered by the grant and ask again
+    assert!(!check("rm -rf ~"));
+    assert_eq!(*approver.0.lock().unwrap(), vec!["npm run test", "rm -rf ~"]);
+}
+
+#[test]
+#[cfg(feature = "tokio")]
+fn test_async_permission_callback_answers_async_executions() {
+    use openskills_runtime::{
+        AsyncPermissionCallback, CancellationHandle, ExecutionOptions, Fallback, HostPolicy,
+        PermissionFuture, PermissionRequest, PermissionResponse, PermissionsConfig,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Approver {
+        asked: Mutex<Vec<String>>,
+    }
+    impl AsyncPermissionCallback for Approver {
+        fn request_permission<'a>(&'a self, request: &'a PermissionRequest) -> PermissionFuture<'a> {
+            Box::pin(async move {
+                tokio::task::yield_now().await;
+                self.asked.lock().unwrap().push(request.tool.clone());
+                Ok(PermissionResponse::AllowOnce)
+            })
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    write_skill(temp_dir.path(), "perm-async-skill", "Read, Bash");
+    let approver = Arc::new(Approver::default());
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_host_policy(HostPolicy::from_config(PermissionsConfig {
+            trust_skill_allowed_tools: false,
+            fallback: Fallback::Prompt,
+            deny: vec![],
+            allow: vec![],
+        }))
+        .with_async_permission_callback(approver.clone());
+    runtime.discover_skills().unwrap();
+
+    let tokio = tokio::runtime::Runtime::new().unwrap();
+    let _ = tokio.block_on(runtime.execute_skill_async(
+        "perm-async-skill",
+        ExecutionOptions::default(),
+        CancellationHandle::default(),
+    ));
+    // Read is not risky, so only Bash is asked, once
+    assert_eq!(*approver.asked.lock().unwrap(), ["Bash"]);
+    let audit = runtime.get_permission_audit();
+    assert_eq!(audit.len(), 1);
+    assert_eq!(audit[0].tool, "Bash");
+    assert!(matches!(audit[0].response, PermissionResponse::AllowOnce));
+
+    // The synchronous API cannot await it and denies instead
+    let check = runtime.check_tool_permission(
+        "perm-async-skill",
+        "Bash",
+        None,
+        std::collections::HashMap::new(),
+    );
+    assert!(!check.unwrap());
+    assert_eq!(approver.asked.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,115 @@
+//! `ExecutionQueue`: concurrency limits, priorities and job status.
+
+#![cfg(unix)]
+
+use openskills_runtime::{
+    ExecutionOptions, ExecutionQueue, JobStatus, NativeRunnerConfig, OpenSkillRuntime,
+    QueueConfig, RuntimeError, RuntimeExecutionStatus, SandboxMode,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+fn create_skill(root: &TempDir) {
+    let dir = root.path().join("validate");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: validate\ndescription: Validates one document.\n---\nRun it.\n",
+    )
+    .unwrap();
+    fs::write(dir.join("script.sh"), "#!/bin/bash\nsleep 0.5\necho '{\"valid\": true}'\n").unwrap();
+}
+
+fn runtime(skills: &TempDir, workspace: &TempDir) -> Arc<Mutex<OpenSkillRuntime>> {
+    let mut runtime = OpenSkillRuntime::from_directory(skills.path())
+        .with_workspace_dir(workspace.path())
+        .with_native_runner_config(NativeRunnerConfig {
+            sandbox_mode: SandboxMode::Disabled,
+            ..Default::default()
+        });
+    runtime.discover_skills().unwrap();
+    Arc::new(Mutex::new(runtime))
+}
+
+fn options(doc: &str) -> ExecutionOptions {
+    ExecutionOptions {
+        input: Some(json!({ "doc": doc })),
+        timeout_ms: Some(10_000),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_queue_runs_jobs_concurrently_within_skill_limit() {
+    let skills = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&skills);
+    let queue = ExecutionQueue::new(
+        runtime(&skills, &workspace),
+        QueueConfig {
+            max_concurrent: 4,
+            skill_concurrency: HashMap::from([("validate".to_string(), 2)]),
+            ..Default::default()
+        },
+    );
+
+    let started = Instant::now();
+    let jobs: Vec<u64> = ["a", "b", "c", "d"]
+        .iter()
+        .map(|doc| queue.submit("validate", options(doc), 0).unwrap())
+        .collect();
+    for id in &jobs {
+        let result = queue.wait(*id, Some(Duration::from_secs(10))).unwrap();
+        assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+    }
+    let elapsed = started.elapsed();
+
+    // Two at a time: two rounds of half-second jobs, not one or four.
+    assert!(elapsed >= Duration::from_millis(1000), "skill limit ignored: {:?}", elapsed);
+    assert!(elapsed < Duration::from_millis(1800), "jobs were serialized: {:?}", elapsed);
+    assert!(queue.jobs().is_empty());
+}
+
+#[test]
+fn test_queue_starts_higher_priority_first_and_cancels_queued_jobs() {
+    let skills = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill(&skills);
+    let queue = ExecutionQueue::new(
+        runtime(&skills, &workspace),
+        QueueConfig {
+            max_concurrent: 1,
+            ..Default::default()
+        },
+    );
+
+    let first = queue.submit("validate", options("first"), 0).unwrap();
+    let low = queue.submit("validate", options("low"), 0).unwrap();
+    let high = queue.submit("validate", options("high"), 10).unwrap();
+    let cancelled = queue.submit("validate", options("cancelled"), 0).unwrap();
+    let missing = queue.submit("missing", ExecutionOptions::default(), 0).unwrap();
+    assert!(matches!(queue.status(low).unwrap().status, JobStatus::Queued));
+    assert!(matches!(queue.wait(low, Some(Duration::ZERO)), Err(RuntimeError::Timeout)));
+
+    assert!(queue.cancel(cancelled));
+    assert!(matches!(queue.status(cancelled).unwrap().status, JobStatus::Cancelled));
+    assert!(matches!(queue.wait(cancelled, None), Err(RuntimeError::Cancelled)));
+    assert!(!queue.cancel(cancelled));
+
+    // `missing` was queued last, so the others are done when it fails.
+    assert!(matches!(queue.wait(missing, None), Err(RuntimeError::SkillNotFound(_))));
+    let high_job = queue.status(high).unwrap();
+    let low_job = queue.status(low).unwrap();
+    assert!(matches!(low_job.status, JobStatus::Finished(RuntimeExecutionStatus::Success)));
+    assert!(high_job.finished_at_ms.unwrap() <= low_job.started_at_ms.unwrap());
+
+    for id in [first, low, high] {
+        queue.wait(id, None).unwrap();
+    }
+    assert!(matches!(queue.wait(first, None), Err(RuntimeError::QueueError(_))));
+    assert_eq!(queue.queued_len(), 0);
+}
@@ -32,8 +32,13 @@ fn validates_input_schema() {
         },
     );
 
-    // This will fail due to placeholder wasm, but should fail after validation
-    assert!(result.is_err());
+    // code-review ships only SKILL.md, so validation passes and the result
+    // tells the caller to follow the instructions.
+    let result = result.expect("instruction-only skill");
+    assert!(matches!(
+        result.audit.exit_status,
+        openskills_runtime::RuntimeExecutionStatus::InstructionOnly
+    ));
 }
 
 #[test]
@@ -131,3 +136,215 @@ Body line for the model.
     assert_eq!(loaded.manifest.description, "Kept from frontmatter.");
     assert!(loaded.instructions.contains("Body line for the model."));
 }
+
+#[test]
+fn test_skill_kind_classified_at_discovery() {
+    use openskills_runtime::SkillKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp = TempDir::new().unwrap();
+    let files: &[(&str, &[&str])] = &[
+        ("docs-only", &["reference.md", ".venv/bin/activate.sh"]),
+        ("nested-script", &["ooxml/scripts/unpack.py"]),
+        ("wasm-only", &["wasm/skill.wasm"]),
+        ("both", &["scripts/run.sh", "skill.wasm"]),
+    ];
+    for (id, paths) in files {
+        let dir = temp.path().join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {id}\ndescription: Kind test.\n---\n"),
+        )
+        .unwrap();
+        for path in *paths {
+            let file = dir.join(path);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "").unwrap();
+        }
+    }
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path());
+    runtime.discover_skills().unwrap();
+    let kind = |id: &str| {
+        runtime
+            .list_skills()
+            .into_iter()
+            .find(|s| s.id == id)
+            .unwrap()
+            .skill_kind()
+    };
+    assert_eq!(kind("docs-only"), SkillKind::InstructionOnly);
+    assert_eq!(kind("nested-script"), SkillKind::Script);
+    assert_eq!(kind("wasm-only"), SkillKind::Wasm);
+    assert_eq!(kind("both"), SkillKind::Hybrid);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&runtime.get_system_prompt_metadata_json().unwrap()).unwrap();
+    let both = json["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["id"] == "both")
+        .unwrap();
+    assert_eq!(both["kind"], "hybrid");
+    assert!(runtime
+        .get_system_prompt_metadata()
+        .contains("- docs-only [instruction_only]: Kind test."));
+}
+
+#[test]
+fn test_list_skill_targets() {
+    use openskills_runtime::{TargetKind, TargetSandbox};
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp = TempDir::new().unwrap();
+    let dir = temp.path().join("mixed");
+    for path in [
+        "scripts/run.sh",
+        "ooxml/scripts/unpack.py",
+        "skill.wasm",
+        "reference.md",
+        "node_modules/pkg/install.sh",
+    ] {
+        let file = dir.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, "").unwrap();
+    }
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: mixed\ndescription: Targets test.\n---\n",
+    )
+    .unwrap();
+
+    let runtime = OpenSkillRuntime::from_directory(temp.path());
+    let targets = runtime.list_skill_targets("mixed").unwrap();
+    let summary: Vec<_> = targets
+        .iter()
+        .map(|t| (t.path.as_str(), t.kind, t.sandbox))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("ooxml/scripts/unpack.py", TargetKind::Python, TargetSandbox::Native),
+            ("scripts/run.sh", TargetKind::Shell, TargetSandbox::Native),
+            ("skill.wasm", TargetKind::Wasm, TargetSandbox::Wasm),
+        ]
+    );
+    assert!(runtime.list_skill_targets("missing").is_err());
+}
+
+#[test]
+fn test_skill_card_collects_display_metadata() {
+    use openskills_runtime::{SkillKind, SkillLocation};
+    use std::fs;
+
+    let temp = tempfile::TempDir::new().unwrap();
+    let pdf = temp.path().join("pdf");
+    fs::create_dir_all(&pdf).unwrap();
+    fs::write(
+        pdf.join("SKILL.md"),
+        r#"---
+name: pdf
+description: Work with PDF files.
+version: 1.2.0
+license: MIT
+metadata:
+  author: Docs Team
+  repository: https://example.com/pdf
+  keywords: [pdf, documents]
+examples:
+  - Merge the PDFs in this folder
+  - prompt: Split report.pdf by page
+    input: {file: report.pdf}
+---
+Instructions.
+"#,
+    )
+    .unwrap();
+    fs::write(pdf.join("README.md"), "# PDF\n\nTools for PDFs.\n").unwrap();
+    // Frontmatter examples win over EXAMPLES.md.
+    fs::write(pdf.join("EXAMPLES.md"), "- Ignored\n").unwrap();
+
+    let notes = temp.path().join("notes");
+    fs::create_dir_all(&notes).unwrap();
+    fs::write(notes.join("SKILL.md"), "---\nname: notes\ndescription: Take notes.\n---\n").unwrap();
+    fs::write(notes.join("EXAMPLES.md"), "# Examples\n\n- Summarize today's meeting\n- `Draft a follow-up`\n").unwrap();
+
+    let runtime = OpenSkillRuntime::from_directory(temp.path());
+    let card = runtime.get_skill_card("pdf").unwrap();
+    assert_eq!(card.version, "1.2.0");
+    assert_eq!(card.description, "Work with PDF files.");
+    assert_eq!(card.author.as_deref(), Some("Docs Team"));
+    assert_eq!(card.license.as_deref(), Some("MIT"));
+    assert_eq!(card.homepage.as_deref(), Some("https://example.com/pdf"));
+    assert_eq!(card.tags, ["pdf", "documents"]);
+    assert_eq!(card.location, SkillLocation::Custom);
+    assert_eq!(card.kind, SkillKind::InstructionOnly);
+    assert_eq!(card.examples.len(), 2);
+    assert_eq!(card.examples[0].prompt, "Merge the PDFs in this folder");
+    assert_eq!(card.examples[1].input, Some(json!({"file": "report.pdf"})));
+    assert_eq!(card.readme.as_deref(), Some("# PDF\n\nTools for PDFs.\n"));
+
+    let card = runtime.get_skill_card("notes").unwrap();
+    let prompts: Vec<_> = card.examples.iter().map(|e| e.prompt.as_str()).collect();
+    assert_eq!(prompts, ["Summarize today's meeting", "Draft a follow-up"]);
+    assert!(card.tags.is_empty());
+    assert!(card.readme.is_none());
+    assert!(runtime.get_skill_card("missing").is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_builtin_and_embedded_skills_are_registered_on_discovery() {
+    use openskills_runtime::{builtin_skill, BuiltinSkill, ExecutionOptions};
+    use std::fs;
+
+    static EMBEDDED: &[BuiltinSkill] = &[
+        BuiltinSkill::new(
+            "greeter",
+            &[
+                ("SKILL.md", b"---\nname: greeter\ndescription: Greets.\n---\nRun the script.\n"),
+                ("script.sh", b"#!/bin/bash\necho '{\"greeting\": \"hi\"}'\n"),
+            ],
+        ),
+        builtin_skill!("fork-test", "../examples/skills/fork-test", ["SKILL.md"]),
+    ];
+
+    // A discovered copy replaces the built-in one.
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let review = temp_dir.path().join("code-review");
+    fs::create_dir(&review).unwrap();
+    fs::write(
+        review.join("SKILL.md"),
+        "---\nname: code-review\ndescription: Reviews code our way.\n---\nReview.\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_builtin_skills(true)
+        .with_embedded_skills(EMBEDDED);
+    let skills = runtime.discover_skills().unwrap();
+    let mut ids: Vec<&str> = skills.iter().map(|s| s.id.as_str()).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, ["code-review", "explaining-code", "fork-test", "greeter"]);
+    let review = skills.iter().find(|s| s.id == "code-review").unwrap();
+    assert_eq!(review.description, "Reviews code our way.");
+
+    let result = runtime
+        .execute_skill(
+            "greeter",
+            ExecutionOptions {
+                timeout_ms: Some(5000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.output["greeting"], "hi");
+
+    let mut plain = OpenSkillRuntime::from_directory(temp_dir.path());
+    let skills = plain.discover_skills().unwrap();
+    assert!(skills.iter().all(|s| s.id == "code-review"));
+}
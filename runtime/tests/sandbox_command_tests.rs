@@ -3,6 +3,8 @@
 //! Tests for the run_sandboxed_command API.
 //! Verifies permission controls, timeout enforcement, and security restrictions.
 
+#![cfg_attr(not(target_os = "macos"), allow(unused_imports))]
+
 use openskills_runtime::{run_sandboxed_command, CommandPermissions, SandboxMode};
 use std::fs;
 use tempfile::TempDir;
@@ -481,3 +483,25 @@ fn test_sandbox_disabled_runs_without_os_sandbox() {
         "Disabled sandbox mode should read /etc/passwd without OS sandbox"
     );
 }
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_allowed_programs_limit_what_children_exec() {
+    use openskills_runtime::command_programs;
+
+    let temp_dir = TempDir::new().unwrap();
+    let permissions = CommandPermissions {
+        allow_process: true,
+        allowed_programs: command_programs("cat notes.txt"),
+        ..Default::default()
+    };
+    fs::write(temp_dir.path().join("notes.txt"), "allowed").unwrap();
+
+    let allowed = run_sandboxed_command("cat notes.txt", temp_dir.path(), permissions.clone()).unwrap();
+    assert_eq!(allowed.exit_code, 0, "stderr: {}", allowed.stderr);
+    assert_eq!(allowed.stdout, "allowed");
+
+    let denied = run_sandboxed_command("ls", temp_dir.path(), permissions).unwrap();
+    assert_ne!(denied.exit_code, 0);
+    assert!(denied.stdout.is_empty());
+}
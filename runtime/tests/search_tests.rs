@@ -0,0 +1,128 @@
+//! `search_skills`: fuzzy matching and the pluggable embedder.
+
+use openskills_runtime::{Embedder, OpenSkillRuntime, RuntimeError, SearchOptions};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn create_skill(root: &Path, name: &str, description: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: {description}\n---\nInstructions.\n"),
+    )
+    .unwrap();
+}
+
+fn runtime_with_skills() -> (TempDir, OpenSkillRuntime) {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path(), "code-review", "Reviews pull requests for bugs and style.");
+    create_skill(temp.path(), "pdf-digest", "Extracts text from PDF files and condenses documents.");
+    create_skill(temp.path(), "release-notes", "Writes release notes from the git log.");
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path());
+    runtime.discover_skills().unwrap();
+    (temp, runtime)
+}
+
+fn ids(matches: &[openskills_runtime::SkillMatch]) -> Vec<&str> {
+    matches.iter().map(|m| m.skill.id.as_str()).collect()
+}
+
+#[test]
+fn test_fuzzy_search_ranks_names_above_descriptions() {
+    let (_temp, runtime) = runtime_with_skills();
+
+    let exact = runtime.search_skills("code-review").unwrap();
+    assert_eq!(ids(&exact), ["code-review"]);
+    assert_eq!(exact[0].score, 1.0);
+    assert!(exact[0].semantic_score.is_none());
+
+    // Typos and prefixes still find the skill.
+    assert_eq!(ids(&runtime.search_skills("reveiw code").unwrap())[0], "code-review");
+    assert_eq!(ids(&runtime.search_skills("rel").unwrap()), ["release-notes"]);
+
+    // "git" only appears in a description.
+    let by_description = runtime.search_skills("git").unwrap();
+    assert_eq!(ids(&by_description), ["release-notes"]);
+    assert!(by_description[0].score < 1.0);
+
+    assert!(runtime.search_skills("kubernetes").unwrap().is_empty());
+    assert!(runtime.search_skills("  ").unwrap().is_empty());
+
+    let limited = runtime
+        .search_skills_with("notes review pdf", &SearchOptions { limit: Some(2), min_score: 0.0 })
+        .unwrap();
+    assert_eq!(limited.len(), 2);
+    assert!(limited[0].score >= limited[1].score);
+}
+
+/// Embeds text as counts of a few concept words, standing in for a model.
+struct ConceptEmbedder {
+    calls: AtomicUsize,
+    embedded: AtomicUsize,
+}
+
+impl Embedder for ConceptEmbedder {
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.embedded.fetch_add(texts.len(), Ordering::SeqCst);
+        let concepts = [
+            &["pdf", "paper", "document", "documents", "summarize", "condenses"][..],
+            &["review", "reviews", "bugs", "critique"][..],
+            &["release", "changelog", "git"][..],
+        ];
+        Ok(texts
+            .iter()
+            .map(|text| {
+                let text = text.to_lowercase();
+                concepts
+                    .iter()
+                    .map(|words| words.iter().filter(|w| text.contains(*w)).count() as f32)
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn test_embedder_finds_skills_by_meaning() {
+    let (_temp, runtime) = runtime_with_skills();
+    let embedder = Arc::new(ConceptEmbedder {
+        calls: AtomicUsize::new(0),
+        embedded: AtomicUsize::new(0),
+    });
+    let runtime = runtime.with_embedder(embedder.clone());
+
+    // No word of the query appears in pdf-digest, but the meaning matches.
+    let matches = runtime.search_skills("summarize a paper").unwrap();
+    assert_eq!(ids(&matches)[0], "pdf-digest");
+    assert!(matches[0].semantic_score.unwrap() > 0.9);
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 4);
+
+    // Skill embeddings are cached; only the query is embedded again.
+    let matches = runtime.search_skills("critique my change").unwrap();
+    assert_eq!(ids(&matches)[0], "code-review");
+    assert_eq!(embedder.calls.load(Ordering::SeqCst), 2);
+    assert_eq!(embedder.embedded.load(Ordering::SeqCst), 5);
+}
+
+struct BrokenEmbedder;
+
+impl Embedder for BrokenEmbedder {
+    fn embed(&self, _texts: &[String]) -> Result<Vec<Vec<f32>>, RuntimeError> {
+        Ok(Vec::new())
+    }
+}
+
+#[test]
+fn test_embedder_errors_are_reported() {
+    let (_temp, runtime) = runtime_with_skills();
+    let runtime = runtime.with_embedder(Arc::new(BrokenEmbedder));
+    assert!(matches!(
+        runtime.search_skills("review"),
+        Err(RuntimeError::InvalidConfig(_))
+    ));
+}
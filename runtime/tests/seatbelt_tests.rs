@@ -1,3 +1,5 @@
+#![cfg_attr(not(target_os = "macos"), allow(unused_imports))]
+
 use openskills_runtime::{OpenSkillRuntime, ExecutionOptions, RuntimeExecutionStatus};
 use serde_json::json;
 use std::fs;
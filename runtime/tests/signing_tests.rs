@@ -0,0 +1,120 @@
+//! Signature enforcement when the runtime has trusted keys.
+
+use base64::Engine;
+use openskills_runtime::{
+    skill_digest, ExecutionOptions, OpenSkillRuntime, RuntimeError, RuntimeEvent,
+    RuntimeEventHandler, SignatureMode, SignatureStatus, TrustedKey, SIGSTORE_SIGNATURE_FILE,
+};
+use p256::ecdsa::signature::Signer;
+use p256::pkcs8::EncodePublicKey;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+fn create_skill(root: &Path, name: &str) -> std::path::PathBuf {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: Test skill {name}.\n---\nFollow the steps.\n"),
+    )
+    .unwrap();
+    dir
+}
+
+fn signing_key() -> (p256::ecdsa::SigningKey, TrustedKey) {
+    let signing = p256::ecdsa::SigningKey::from_slice(&[5; 32]).unwrap();
+    let pem = signing
+        .verifying_key()
+        .to_public_key_pem(p256::pkcs8::LineEnding::LF)
+        .unwrap();
+    (signing, TrustedKey::parse(&pem).unwrap())
+}
+
+/// Sign like `cosign sign-blob --key cosign.key digest.txt --output-signature SKILL.md.sig`.
+fn sign(dir: &Path, signing: &p256::ecdsa::SigningKey) {
+    let digest = skill_digest(dir).unwrap();
+    let signature: p256::ecdsa::Signature = signing.sign(digest.as_bytes());
+    fs::write(
+        dir.join(SIGSTORE_SIGNATURE_FILE),
+        base64::engine::general_purpose::STANDARD.encode(signature.to_der()),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_trusted_keys_refuse_unsigned_and_tampered_skills() {
+    let temp = TempDir::new().unwrap();
+    let (signing, key) = signing_key();
+    let signed = create_skill(temp.path(), "signed");
+    sign(&signed, &signing);
+    create_skill(temp.path(), "unsigned");
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path()).with_trusted_keys(vec![key.clone()]);
+    runtime.discover_skills().unwrap();
+
+    assert_eq!(
+        runtime.verify_skill_signature("signed").unwrap(),
+        SignatureStatus::Verified { key_id: key.id() }
+    );
+    runtime.activate_skill("signed").unwrap();
+
+    assert_eq!(
+        runtime.verify_skill_signature("unsigned").unwrap(),
+        SignatureStatus::Unsigned
+    );
+    let err = runtime.activate_skill("unsigned").unwrap_err();
+    assert!(matches!(err, RuntimeError::SignatureError(_)), "{err}");
+    let err = runtime
+        .execute_skill("unsigned", ExecutionOptions::default())
+        .unwrap_err();
+    assert!(matches!(err, RuntimeError::SignatureError(_)), "{err}");
+
+    fs::write(signed.join("helper.sh"), "echo injected\n").unwrap();
+    assert!(matches!(
+        runtime.verify_skill_signature("signed").unwrap(),
+        SignatureStatus::Invalid(_)
+    ));
+    assert!(matches!(
+        runtime.activate_skill("signed"),
+        Err(RuntimeError::SignatureError(_))
+    ));
+}
+
+#[test]
+fn test_warn_mode_allows_unsigned_skills_with_event() {
+    struct Collector(Arc<Mutex<Vec<RuntimeEvent>>>);
+    impl RuntimeEventHandler for Collector {
+        fn on_event(&self, event: &RuntimeEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path(), "unsigned");
+    let (_, key) = signing_key();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path())
+        .with_trusted_keys(vec![key])
+        .with_signature_mode(SignatureMode::Warn)
+        .with_event_handler(Box::new(Collector(events.clone())));
+    runtime.discover_skills().unwrap();
+
+    runtime.activate_skill("unsigned").unwrap();
+    let events = events.lock().unwrap();
+    assert!(
+        events.iter().any(|e| matches!(e, RuntimeEvent::SignatureRejected { skill_id, reason }
+            if skill_id == "unsigned" && reason == "skill is not signed")),
+        "{events:?}"
+    );
+}
+
+#[test]
+fn test_no_trusted_keys_skips_verification() {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path(), "unsigned");
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path());
+    runtime.discover_skills().unwrap();
+    runtime.activate_skill("unsigned").unwrap();
+}
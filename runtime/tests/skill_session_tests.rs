@@ -275,3 +275,36 @@ fn test_fork_test_skill() {
     assert!(result.output["summary"].as_str().is_some());
     assert!(result.audit.permissions_used.contains(&"Read".to_string()));
 }
+
+#[test]
+fn test_skill_session_copies_workspace_files() {
+    let skills = tempfile::TempDir::new().unwrap();
+    let workspace = tempfile::TempDir::new().unwrap();
+    let skill_dir = skills.path().join("report-writer");
+    std::fs::create_dir_all(skill_dir.join("templates")).unwrap();
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: report-writer\ndescription: Fills in the report template.\nworkspace-files:\n  - from: templates/report.md\n    to: report.md\n---\nEdit report.md in the workspace.\n",
+    )
+    .unwrap();
+    std::fs::write(skill_dir.join("templates/report.md"), "# Title\n").unwrap();
+
+    let mut runtime =
+        OpenSkillRuntime::from_directory(skills.path()).with_workspace_dir(workspace.path());
+    runtime.discover_skills().unwrap();
+    let session = runtime
+        .start_skill_session("report-writer", None, None)
+        .expect("start skill session");
+
+    let copied = workspace.path().join("report.md");
+    assert_eq!(std::fs::read_to_string(&copied).unwrap(), "# Title\n");
+    assert_eq!(session.workspace_files().len(), 1);
+    assert_eq!(session.workspace_files()[0].dest, "report.md");
+    assert_eq!(runtime.get_import_audit().len(), 1);
+
+    // The next session keeps the agent's edits
+    std::fs::write(&copied, "# Q3 Report\n").unwrap();
+    let session = runtime.start_skill_session("report-writer", None, None).unwrap();
+    assert!(session.workspace_files().is_empty());
+    assert_eq!(std::fs::read_to_string(&copied).unwrap(), "# Q3 Report\n");
+}
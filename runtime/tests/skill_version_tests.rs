@@ -0,0 +1,129 @@
+//! Several versions of one skill loaded side by side, selected by
+//! `id@constraint` references.
+
+use openskills_runtime::{ExecutionTarget, OpenSkillRuntime, RuntimeError};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Create `dir_name/` with a `scripts/version.sh` printing `output`.
+fn create_skill(root: &Path, dir_name: &str, frontmatter_version: Option<&str>, output: &str) {
+    let dir = root.join(dir_name);
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    let version_line = frontmatter_version
+        .map(|v| format!("version: {v}\n"))
+        .unwrap_or_default();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: greet\ndescription: Greets.\n{version_line}---\nInstructions for {output}.\n"),
+    )
+    .unwrap();
+    fs::write(dir.join("scripts/version.sh"), format!("echo {output}\n")).unwrap();
+}
+
+fn runtime_with_versions() -> (TempDir, OpenSkillRuntime) {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path(), "greet@1.0.0", None, "v1");
+    create_skill(temp.path(), "greet@1.4.0", Some("\"1.4.0\""), "v1.4");
+    create_skill(temp.path(), "greet@2.1.0", None, "v2");
+    create_skill(temp.path(), "greet@3.0.0-beta.1", None, "v3-beta");
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path());
+    runtime.discover_skills().unwrap();
+    (temp, runtime)
+}
+
+fn run_version_script(runtime: &mut OpenSkillRuntime, reference: &str) -> (String, String) {
+    let result = runtime
+        .run_skill_target(
+            reference,
+            ExecutionTarget::Script {
+                path: "scripts/version.sh".to_string(),
+                args: Vec::new(),
+            },
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(result.audit.skill_id, "greet");
+    (result.stdout.trim().to_string(), result.audit.version)
+}
+
+#[test]
+fn test_versions_coexist_and_bare_id_selects_highest_stable() {
+    let (_temp, runtime) = runtime_with_versions();
+
+    let skills = runtime.list_skills();
+    assert_eq!(skills.len(), 1);
+    assert_eq!(skills[0].id, "greet");
+    assert_eq!(skills[0].version, "2.1.0");
+
+    let versions: Vec<String> = runtime
+        .list_skill_versions("greet")
+        .into_iter()
+        .map(|d| d.version)
+        .collect();
+    assert_eq!(versions, ["1.0.0", "1.4.0", "2.1.0", "3.0.0-beta.1"]);
+
+    let loaded = runtime.activate_skill("greet").unwrap();
+    assert_eq!(loaded.instructions, "Instructions for v2.");
+    assert_eq!(loaded.manifest.version.as_deref(), Some("2.1.0"));
+}
+
+#[test]
+fn test_constraints_select_matching_version() {
+    let (_temp, mut runtime) = runtime_with_versions();
+
+    assert_eq!(
+        runtime.activate_skill("greet@^1").unwrap().instructions,
+        "Instructions for v1.4."
+    );
+    assert_eq!(
+        run_version_script(&mut runtime, "greet@1.0.0"),
+        ("v1".to_string(), "1.0.0".to_string())
+    );
+    assert_eq!(
+        run_version_script(&mut runtime, "greet@>=1.1, <2"),
+        ("v1.4".to_string(), "1.4.0".to_string())
+    );
+    assert_eq!(
+        run_version_script(&mut runtime, "greet@>=3.0.0-beta"),
+        ("v3-beta".to_string(), "3.0.0-beta.1".to_string())
+    );
+    assert_eq!(
+        run_version_script(&mut runtime, "greet"),
+        ("v2".to_string(), "2.1.0".to_string())
+    );
+}
+
+#[test]
+fn test_unmatched_and_invalid_constraints() {
+    let (_temp, runtime) = runtime_with_versions();
+
+    let err = runtime.activate_skill("greet@^4").unwrap_err();
+    assert!(matches!(err, RuntimeError::SkillNotFound(_)), "{err}");
+    assert!(err.to_string().contains("available versions: 1.0.0, 1.4.0"), "{err}");
+
+    let err = runtime.activate_skill("greet@not-a-version").unwrap_err();
+    assert!(matches!(err, RuntimeError::InvalidVersion(_)), "{err}");
+}
+
+#[test]
+fn test_frontmatter_version_and_unversioned_skills() {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path(), "greet", Some("2.1"), "v2");
+    let other = temp.path().join("plain");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(other.join("SKILL.md"), "---\nname: plain\ndescription: No version.\n---\nBody.\n").unwrap();
+    // A directory suffix that contradicts the frontmatter is a loading error.
+    create_skill(temp.path(), "greet@9.9.9", Some("1.0.0"), "bad");
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp.path());
+    runtime.discover_skills().unwrap();
+
+    assert_eq!(runtime.activate_skill("greet@2.1.0").unwrap().instructions, "Instructions for v2.");
+    let plain = runtime.list_skill_versions("plain");
+    assert_eq!(plain[0].version, "0.0.0");
+    assert!(runtime.get_loading_errors().contains_key("greet@9.9.9"));
+    assert_eq!(runtime.list_skill_versions("greet").len(), 1);
+}
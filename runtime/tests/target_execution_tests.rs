@@ -3,7 +3,10 @@
 //! Tests for runSkillTarget (script/WASM execution).
 //! Verifies auto-detection of execution type, argument passing, and workspace handling.
 
-use openskills_runtime::{OpenSkillRuntime, ExecutionTarget};
+use openskills_runtime::{
+    ExecutionOptions, ExecutionTarget, OpenSkillRuntime, OutputConverter, RuntimeExecutionStatus,
+    SkillKind,
+};
 use std::fs;
 use tempfile::TempDir;
 
@@ -417,6 +420,55 @@ description: Test path traversal protection.
     }
 }
 
+// =============================================================================
+// Instruction-Only Skills
+// =============================================================================
+
+#[test]
+fn test_instruction_only_skill() {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("guide");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: guide\ndescription: Instructions only.\n---\n# Steps\nFollow these steps.\n",
+    )
+    .unwrap();
+    create_skill_with_script(&temp_dir, "runner", "script.sh", "#!/bin/bash\necho hi\n");
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let kinds: Vec<_> = runtime
+        .list_skills()
+        .into_iter()
+        .map(|s| (s.id.clone(), s.skill_kind()))
+        .collect();
+    assert!(kinds.contains(&("guide".to_string(), SkillKind::InstructionOnly)));
+    assert!(kinds.contains(&("runner".to_string(), SkillKind::Script)));
+
+    let result = runtime
+        .execute_skill("guide", ExecutionOptions::default())
+        .expect("instruction-only skills are not an error");
+    assert!(matches!(
+        result.audit.exit_status,
+        RuntimeExecutionStatus::InstructionOnly
+    ));
+    assert_eq!(result.output["status"], "instruction_only");
+    assert!(result.output["instructions"]
+        .as_str()
+        .unwrap()
+        .contains("Follow these steps."));
+
+    let result = runtime
+        .run_skill_target("guide", ExecutionTarget::Auto, None, None, None)
+        .unwrap();
+    assert!(matches!(
+        result.audit.exit_status,
+        RuntimeExecutionStatus::InstructionOnly
+    ));
+}
+
 // =============================================================================
 // Skill Not Found
 // =============================================================================
@@ -493,6 +545,423 @@ description: Script in subdirectory.
     }
 }
 
+// =============================================================================
+// Streaming Events
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_run_skill_target_with_events() {
+    use openskills_runtime::{execution_event_channel, ExecutionEvent};
+
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "stream-skill",
+        "script.sh",
+        "#!/bin/bash\necho first\necho oops >&2\necho second\n",
+    );
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let (events, mut receiver) = execution_event_channel();
+    let target = ExecutionTarget::Path {
+        path: "script.sh".to_string(),
+        args: vec![],
+    };
+    let result = runtime
+        .run_skill_target_with_events("stream-skill", target, Some(10000), None, None, events)
+        .unwrap();
+
+    let mut received = Vec::new();
+    while let Ok(event) = receiver.try_recv() {
+        received.push(event);
+    }
+    assert!(matches!(received.first(), Some(ExecutionEvent::Started { .. })));
+    assert!(matches!(
+        received.last(),
+        Some(ExecutionEvent::Finished { status: RuntimeExecutionStatus::Success, .. })
+    ));
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    for event in &received {
+        match event {
+            ExecutionEvent::StdoutChunk(bytes) => stdout.extend_from_slice(bytes),
+            ExecutionEvent::StderrChunk(bytes) => stderr.extend_from_slice(bytes),
+            _ => {}
+        }
+    }
+    assert_eq!(String::from_utf8(stdout).unwrap(), "first\nsecond\n");
+    assert_eq!(result.stdout, "first\nsecond\n");
+    assert!(String::from_utf8(stderr).unwrap().contains("oops"));
+}
+
+// =============================================================================
+// Dry Run
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_execute_skill_dry_run() {
+    use openskills_runtime::ExecutionPlan;
+
+    let temp_dir = TempDir::new().unwrap();
+    let marker = temp_dir.path().join("ran");
+    create_skill_with_script(
+        &temp_dir,
+        "review-skill",
+        "script.sh",
+        &format!("#!/bin/bash
+touch {}
+", marker.display()),
+    );
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(5000),
+        dry_run: true,
+        ..Default::default()
+    };
+    let result = runtime.execute_skill("review-skill", options).unwrap();
+
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::DryRun));
+    assert!(!marker.exists(), "a dry run must not execute the script");
+    let plan: ExecutionPlan = serde_json::from_value(result.output).unwrap();
+    assert_eq!(plan.mode, "native");
+    assert_eq!(plan.entrypoint.as_deref(), Some("script.sh"));
+    assert_eq!(plan.command[0], "/bin/bash");
+    assert!(plan.command[1].ends_with("script.sh"));
+    assert_eq!(plan.timeout_ms, 5000);
+    assert!(plan.backend.is_some());
+    let sandbox = plan.sandbox.expect("native scripts run sandboxed by default");
+    assert!(!sandbox.allow_network);
+}
+
+// =============================================================================
+// Retry
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_execute_skill_retries_failed_attempt() {
+    use openskills_runtime::{
+        AuditSink, Backoff, RetryPolicy, RuntimeAuditRecord, SandboxOverrides,
+    };
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    struct Attempts(Arc<Mutex<Vec<(u32, RuntimeExecutionStatus)>>>);
+    impl AuditSink for Attempts {
+        fn record(&self, record: &RuntimeAuditRecord) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((record.attempt, record.exit_status.clone()));
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    let state_dir = TempDir::new().unwrap();
+    let marker = state_dir.path().join("tried");
+    create_skill_with_script(
+        &temp_dir,
+        "flaky-skill",
+        "script.sh",
+        &format!(
+            "#!/bin/bash
+if [ -f {marker} ]; then echo '{{\"ok\": true}}'; exit 0; fi
+touch {marker}
+exit 1
+",
+            marker = marker.display()
+        ),
+    );
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_audit_sink(Box::new(Attempts(attempts.clone())));
+    runtime.discover_skills().unwrap();
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(5000),
+        sandbox_overrides: SandboxOverrides {
+            write_paths: vec![state_dir.path().to_path_buf()],
+            ..Default::default()
+        },
+        retry: Some(RetryPolicy {
+            max_attempts: 3,
+            backoff: Backoff::Fixed(Duration::from_millis(10)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let result = runtime.execute_skill("flaky-skill", options).unwrap();
+
+    assert!(matches!(
+        result.audit.exit_status,
+        RuntimeExecutionStatus::Success
+    ));
+    assert_eq!(result.audit.attempt, 2);
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 2, "each attempt is audited");
+    assert_eq!(attempts[0].0, 1);
+    assert!(matches!(attempts[0].1, RuntimeExecutionStatus::Failed(_)));
+    assert_eq!(attempts[1].0, 2);
+}
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_pending_execution_runs_without_the_runtime() {
+    use openskills_runtime::{Backoff, ExecutionStep, RetryPolicy, SandboxOverrides};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let temp_dir = TempDir::new().unwrap();
+    let state_dir = TempDir::new().unwrap();
+    let marker = state_dir.path().join("tried");
+    create_skill_with_script(
+        &temp_dir,
+        "flaky-skill",
+        "script.sh",
+        &format!(
+            "#!/bin/bash
+if [ -f {marker} ]; then echo '{{\"ok\": true}}'; exit 0; fi
+touch {marker}
+exit 1
+",
+            marker = marker.display()
+        ),
+    );
+    let runtime = Arc::new(Mutex::new(OpenSkillRuntime::from_directory(temp_dir.path())));
+    runtime.lock().unwrap().discover_skills().unwrap();
+
+    let options = ExecutionOptions {
+        timeout_ms: Some(5000),
+        sandbox_overrides: SandboxOverrides {
+            write_paths: vec![state_dir.path().to_path_buf()],
+            ..Default::default()
+        },
+        retry: Some(RetryPolicy {
+            max_attempts: 3,
+            backoff: Backoff::Fixed(Duration::from_millis(10)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut pending = runtime.lock().unwrap().begin_execution("flaky-skill", options).unwrap();
+    assert_eq!(pending.skill_id(), "flaky-skill");
+    let mut attempts = 0;
+    let result = loop {
+        attempts += 1;
+        // The run holds no lock: the runtime stays usable from other threads.
+        let worker = std::thread::spawn(move || {
+            pending.run();
+            pending
+        });
+        assert_eq!(runtime.lock().unwrap().list_skills().len(), 1);
+        let done = worker.join().unwrap();
+        let step = runtime.lock().unwrap().finish_pending_execution(done);
+        match step {
+            ExecutionStep::Finished(result) => break result.unwrap(),
+            ExecutionStep::Retry { delay, next } => {
+                std::thread::sleep(delay);
+                pending = runtime.lock().unwrap().begin_next_attempt(next).unwrap();
+            }
+        }
+    };
+    assert_eq!(attempts, 2);
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success));
+    assert_eq!(result.audit.attempt, 2);
+    assert_eq!(result.output["ok"], true);
+}
+
+// =============================================================================
+// Trace Context
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_execute_skill_propagates_traceparent() {
+    use openskills_runtime::RuntimeError;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "traced-skill",
+        "script.sh",
+        "#!/bin/bash\necho \"{\\\"traceparent\\\": \\\"$TRACEPARENT\\\"}\"\n",
+    );
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let options = ExecutionOptions {
+        timeout_ms: Some(5000),
+        traceparent: Some(traceparent.to_string()),
+        ..Default::default()
+    };
+    let result = runtime.execute_skill("traced-skill", options).unwrap();
+
+    assert_eq!(result.output["traceparent"], traceparent);
+    assert_eq!(result.audit.traceparent.as_deref(), Some(traceparent));
+
+    let options = ExecutionOptions {
+        traceparent: Some("00-not-a-trace-01".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        runtime.execute_skill("traced-skill", options),
+        Err(RuntimeError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_audit_log_records_executions_for_usage_stats() {
+    use openskills_runtime::{read_audit_log, skill_usage, AuditLog};
+
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(&temp_dir, "ok-skill", "script.sh", "#!/bin/bash\necho '{}'\n");
+    create_skill_with_script(&temp_dir, "broken-skill", "script.sh", "#!/bin/bash\nexit 3\n");
+    let log_path = temp_dir.path().join("state/audit.jsonl");
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_audit_sink(Box::new(AuditLog::open(&log_path).unwrap()));
+    runtime.discover_skills().unwrap();
+
+    for skill_id in ["ok-skill", "ok-skill", "broken-skill"] {
+        let options = ExecutionOptions {
+            timeout_ms: Some(5000),
+            ..Default::default()
+        };
+        runtime.execute_skill(skill_id, options).unwrap();
+    }
+
+    let entries = read_audit_log(&log_path).unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[2].status, "failed");
+    let usage = skill_usage(&entries);
+    assert_eq!(usage[0].skill_id, "ok-skill");
+    assert_eq!((usage[0].executions, usage[0].failures), (2, 0));
+    assert_eq!(usage[1].skill_id, "broken-skill");
+    assert_eq!(usage[1].failure_rate, 1.0);
+}
+
+// =============================================================================
+// Script Sessions
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_script_session_streams_stdin_and_stdout() {
+    use openskills_runtime::RuntimeError;
+    use std::time::Duration;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "repl-skill",
+        "repl.sh",
+        r#"#!/bin/bash
+while read -r line; do
+  echo "echo: $line"
+done
+echo "bye"
+"#,
+    );
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let mut session = runtime
+        .start_script_session(
+            "repl-skill",
+            ExecutionTarget::Path {
+                path: "repl.sh".to_string(),
+                args: vec![],
+            },
+            Some(10_000),
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(matches!(
+        session.read_line(Duration::from_millis(50)),
+        Err(RuntimeError::Timeout)
+    ));
+    for message in ["first", "second"] {
+        session.write_line(message).unwrap();
+        let reply = session.read_line(Duration::from_secs(5)).unwrap();
+        assert_eq!(reply.as_deref(), Some(format!("echo: {message}").as_str()));
+    }
+    assert!(session.is_running());
+
+    let result = runtime.finish_script_session(session).unwrap();
+    assert!(matches!(
+        result.audit.exit_status,
+        RuntimeExecutionStatus::Success
+    ));
+    assert!(result.stdout.contains("bye"));
+}
+
+// =============================================================================
+// Execution Journal
+// =============================================================================
+
+#[test]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn test_execution_journal_recovers_aborted_executions() {
+    use openskills_runtime::{ExecutionJournal, JournalEntry};
+
+    let temp_dir = TempDir::new().unwrap();
+    let journal_dir = TempDir::new().unwrap();
+    create_skill_with_script(&temp_dir, "journal-skill", "run.sh", "#!/bin/bash\necho done\n");
+    // Left behind by a runtime that no longer runs.
+    let entry = JournalEntry {
+        skill_id: "journal-skill".to_string(),
+        version: "1.0.0".to_string(),
+        target: "run.sh".to_string(),
+        input_hash: String::new(),
+        start_time_ms: 1_000,
+        session_id: "session-1".to_string(),
+        runtime_pid: u32::MAX,
+        runtime_started: Some("0".to_string()),
+        pid: None,
+        pid_started: None,
+    };
+    fs::write(
+        journal_dir.path().join("crashed.json"),
+        serde_json::to_vec(&entry).unwrap(),
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_execution_journal(journal_dir.path());
+    runtime.discover_skills().unwrap();
+    let aborted = runtime.recover_aborted_executions();
+    assert_eq!(aborted.len(), 1);
+    assert_eq!(aborted[0].skill_id, "journal-skill");
+    assert!(matches!(
+        &aborted[0].exit_status,
+        RuntimeExecutionStatus::Failed(reason) if reason == "aborted"
+    ));
+    assert!(runtime.recover_aborted_executions().is_empty());
+
+    // Finished executions leave no entry behind.
+    let result = runtime
+        .run_skill_target(
+            "journal-skill",
+            ExecutionTarget::Path {
+                path: "run.sh".to_string(),
+                args: vec![],
+            },
+            Some(10_000),
+            None,
+            None,
+        )
+        .unwrap();
+    assert!(result.stdout.contains("done"));
+    assert!(ExecutionJournal::new(journal_dir.path()).entries().is_empty());
+}
+
 // =============================================================================
 // Default Execution Target
 // =============================================================================
@@ -504,3 +973,283 @@ fn test_execution_target_default() {
     // Verify default is Auto
     assert!(matches!(target, ExecutionTarget::Auto));
 }
+
+// =============================================================================
+// Entry-Point Precedence
+// =============================================================================
+
+#[test]
+fn test_entrypoint_precedence_and_explain() {
+    use openskills_runtime::{EntrypointConfig, EntrypointPrecedence};
+
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(&temp_dir, "picker", "script.sh", "#!/bin/bash\necho script\n");
+    let skill_dir = temp_dir.path().join("picker");
+    fs::write(skill_dir.join("skill.wasm"), b"\0asm").unwrap();
+    fs::create_dir_all(skill_dir.join("tools")).unwrap();
+    fs::write(skill_dir.join("tools/convert.py"), "print('convert')\n").unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    // Default: WASM before scripts.
+    let resolution = runtime.explain_entrypoint("picker").unwrap();
+    assert_eq!(resolution.selected.as_deref(), Some("skill.wasm"));
+
+    runtime.set_entrypoint_config(
+        EntrypointConfig::default().with_precedence(EntrypointPrecedence::ScriptFirst),
+    );
+    let resolution = runtime.explain_entrypoint("picker").unwrap();
+    assert_eq!(resolution.selected.as_deref(), Some("script.sh"));
+    assert!(resolution
+        .trace
+        .contains(&"script candidate script.py: no match".to_string()));
+
+    // SKILL.md `entrypoint:` wins over the config.
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: picker\ndescription: Picks a file.\nentrypoint: tools/convert.py\n---\n",
+    )
+    .unwrap();
+    runtime.discover_skills().unwrap();
+    let resolution = runtime.explain_entrypoint("picker").unwrap();
+    assert_eq!(resolution.selected.as_deref(), Some("tools/convert.py"));
+    assert_eq!(
+        resolution.trace,
+        vec!["SKILL.md entrypoint: tools/convert.py (selected)"]
+    );
+}
+
+#[test]
+fn test_manifest_entrypoint_args_and_load_validation() {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("echoer");
+    fs::create_dir_all(skill_dir.join("bin")).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: echoer\ndescription: Echoes its args.\nentrypoint:\n  path: bin/run.sh\n  args: [\"--mode\", \"fast\"]\n---\n",
+    )
+    .unwrap();
+    fs::write(skill_dir.join("bin/run.sh"), "#!/bin/bash\necho \"args: $*\"\n").unwrap();
+    // A conventional name that auto-detection would otherwise pick.
+    fs::write(skill_dir.join("script.sh"), "#!/bin/bash\necho wrong\n").unwrap();
+
+    let bad_dir = temp_dir.path().join("broken");
+    fs::create_dir_all(&bad_dir).unwrap();
+    fs::write(
+        bad_dir.join("SKILL.md"),
+        "---\nname: broken\ndescription: Missing entry point.\nentrypoint: run.py\n---\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    // Invalid entry points are rejected when the skill is loaded.
+    assert!(runtime.get_loading_errors()["broken"].contains("entrypoint 'run.py' not found"));
+
+    let resolution = runtime.explain_entrypoint("echoer").unwrap();
+    assert_eq!(resolution.selected.as_deref(), Some("bin/run.sh"));
+    assert_eq!(resolution.args, vec!["--mode", "fast"]);
+
+    let result = runtime
+        .run_skill_target("echoer", ExecutionTarget::Auto, Some(10000), None, None)
+        .unwrap();
+    assert_eq!(result.stdout.trim(), "args: --mode fast", "stderr: {}", result.stderr);
+}
+
+// =============================================================================
+// Output Schema
+// =============================================================================
+
+#[test]
+fn test_output_schema_violation() {
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("checker");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: checker\ndescription: Checks a document.\noutput-schema:\n  type: object\n  required: [valid]\n  properties:\n    valid: { type: boolean }\n---\n",
+    )
+    .unwrap();
+    fs::write(
+        skill_dir.join("check.sh"),
+        "#!/bin/bash\nif [ \"$1\" = bad ]; then echo '{\"valid\": \"yes\"}'; else echo '{\"valid\": true}'; fi\n",
+    )
+    .unwrap();
+    let bad_dir = temp_dir.path().join("bad-schema");
+    fs::create_dir_all(&bad_dir).unwrap();
+    fs::write(
+        bad_dir.join("SKILL.md"),
+        "---\nname: bad-schema\ndescription: Invalid schema.\noutput-schema:\n  type: 12\n---\n",
+    )
+    .unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    assert!(runtime.get_loading_errors()["bad-schema"].contains("Invalid output-schema"));
+
+    let mut run = |args: Vec<String>| {
+        runtime
+            .run_skill_target(
+                "checker",
+                ExecutionTarget::Path {
+                    path: "check.sh".to_string(),
+                    args,
+                },
+                Some(10_000),
+                None,
+                None,
+            )
+            .unwrap()
+    };
+    let result = run(vec![]);
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+
+    let result = run(vec!["bad".to_string()]);
+    match &result.audit.exit_status {
+        RuntimeExecutionStatus::SchemaViolation(violations) => {
+            assert_eq!(violations, r#"/valid: "yes" is not of type "boolean""#)
+        }
+        other => panic!("expected a schema violation, got {:?}", other),
+    }
+    assert_eq!(result.output["valid"], "yes");
+}
+
+// =============================================================================
+// Produced Artifacts
+// =============================================================================
+
+#[test]
+#[cfg(unix)]
+fn test_execution_result_lists_produced_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    fs::write(workspace.path().join("input.txt"), "untouched").unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "report",
+        "report.sh",
+        "#!/bin/bash\nmkdir -p \"$SKILL_WORKSPACE/out\"\necho '{\"ok\": true}' > \"$SKILL_WORKSPACE/out/report.json\"\necho done\n",
+    );
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let run = |runtime: &mut OpenSkillRuntime| {
+        runtime
+            .run_skill_target(
+                "report",
+                ExecutionTarget::Path {
+                    path: "report.sh".to_string(),
+                    args: vec![],
+                },
+                Some(10_000),
+                None,
+                Some(workspace.path().to_path_buf()),
+            )
+            .unwrap()
+    };
+
+    let result = run(&mut runtime);
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+    assert_eq!(result.artifacts.len(), 1, "{:?}", result.artifacts);
+    let artifact = &result.artifacts[0];
+    assert!(artifact.path.ends_with("out/report.json"));
+    assert_eq!(artifact.size, 13);
+    assert_eq!(artifact.mime, "application/json");
+    assert_eq!(artifact.sha256.len(), 64);
+
+    runtime.set_artifact_collection(false);
+    assert!(run(&mut runtime).artifacts.is_empty());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_output_converters_convert_produced_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "notes",
+        "notes.sh",
+        "#!/bin/bash\nmkdir -p \"$SKILL_WORKSPACE/out\"\necho '# Notes' > \"$SKILL_WORKSPACE/out/notes.md\"\necho 'a,b' > \"$SKILL_WORKSPACE/out/table.csv\"\n",
+    );
+    let skill_md = temp_dir.path().join("notes/SKILL.md");
+    let manifest = fs::read_to_string(&skill_md).unwrap().replacen(
+        "description: Test skill with script.\n",
+        "description: Test skill with script.\noutput-converters:\n  - from: md\n    to: html\n    run: to_html.sh\n",
+        1,
+    );
+    fs::write(&skill_md, manifest).unwrap();
+    fs::write(
+        temp_dir.path().join("notes/to_html.sh"),
+        "#!/bin/bash\ncd \"$SKILL_WORKSPACE\"\n{ echo '<pre>'; cat \"$1\"; echo '</pre>'; } > \"$2\"\n",
+    )
+    .unwrap();
+    create_skill_with_script(
+        &temp_dir,
+        "tables",
+        "csv2json.sh",
+        "#!/bin/bash\ncd \"$SKILL_WORKSPACE\"\necho \"[\\\"$(cat \"$1\")\\\", \\\"$3\\\"]\" > \"$2\"\n",
+    );
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_output_converter(
+        OutputConverter::new("text/csv", "json", "tables", "csv2json.sh").with_args(["header"]),
+    );
+    runtime.discover_skills().unwrap();
+    let result = runtime
+        .run_skill_target(
+            "notes",
+            ExecutionTarget::Path {
+                path: "notes.sh".to_string(),
+                args: vec![],
+            },
+            Some(10_000),
+            None,
+            Some(workspace.path().to_path_buf()),
+        )
+        .unwrap();
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+
+    let names: Vec<_> = result
+        .artifacts
+        .iter()
+        .map(|a| a.path.strip_prefix(workspace.path()).unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, ["out/notes.md", "out/table.csv", "out/notes.html", "out/table.json"]);
+    let html = &result.artifacts[2];
+    assert_eq!(html.mime, "text/html");
+    assert_eq!(html.converted_from.as_deref(), Some(result.artifacts[0].path.as_path()));
+    assert_eq!(
+        fs::read_to_string(&html.path).unwrap(),
+        "<pre>\n# Notes\n</pre>\n"
+    );
+    assert_eq!(fs::read_to_string(&result.artifacts[3].path).unwrap(), "[\"a,b\", \"header\"]\n");
+    assert!(result.artifacts[..2].iter().all(|a| a.converted_from.is_none()));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_run_skill_example_runs_named_example() {
+    let temp_dir = TempDir::new().unwrap();
+    create_skill_with_script(&temp_dir, "greeter", "greet.sh", "#!/bin/bash\nread input\necho \"$input\"\n");
+    let skill_md = temp_dir.path().join("greeter/SKILL.md");
+    let manifest = fs::read_to_string(&skill_md).unwrap().replacen(
+        "description: Test skill with script.\n",
+        "description: Test skill with script.\nexamples:\n  - Say hello\n  - name: hello\n    target: greet.sh\n    input: {name: World}\n",
+        1,
+    );
+    fs::write(&skill_md, manifest).unwrap();
+
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let result = runtime.run_skill_example("greeter", "hello").unwrap();
+    assert!(matches!(result.audit.exit_status, RuntimeExecutionStatus::Success), "stderr: {}", result.stderr);
+    assert_eq!(result.output["name"], "World");
+
+    let err = runtime.run_skill_example("greeter", "goodbye").unwrap_err();
+    assert!(err.to_string().contains("examples: hello"), "{}", err);
+    let card = runtime.get_skill_card("greeter").unwrap();
+    assert_eq!(card.examples[1].name.as_deref(), Some("hello"));
+    assert_eq!(card.examples[1].prompt, "hello");
+}
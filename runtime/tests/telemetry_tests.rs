@@ -0,0 +1,90 @@
+//! OTLP export of execution spans (`otel` feature).
+#![cfg(all(any(target_os = "macos", target_os = "linux"), feature = "otel"))]
+
+use openskills_runtime::{init_otlp_tracing, ExecutionOptions, OpenSkillRuntime};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Accept OTLP/HTTP exports, sending each request line and body to the channel.
+fn collector() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").unwrap();
+            if sender.send((request_line, body)).is_err() {
+                break;
+            }
+        }
+    });
+    (url, receiver)
+}
+
+#[test]
+fn test_execution_spans_are_exported_and_continue_the_callers_trace() {
+    let (endpoint, exports) = collector();
+    let tracing = init_otlp_tracing(Some(&endpoint), "telemetry-test").unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let skill_dir = temp_dir.path().join("traced-skill");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: traced-skill\ndescription: Echoes its trace context.\n---\nRun the script.\n",
+    )
+    .unwrap();
+    let script = skill_dir.join("script.sh");
+    fs::write(&script, "#!/bin/bash\necho \"{\\\"traceparent\\\": \\\"$TRACEPARENT\\\"}\"\n").unwrap();
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let trace_id = "4bf92f3577b34da6a3ce929d0e0e4736";
+    let caller = format!("00-{trace_id}-00f067aa0ba902b7-01");
+    let options = ExecutionOptions {
+        timeout_ms: Some(5000),
+        traceparent: Some(caller.clone()),
+        ..Default::default()
+    };
+    let result = runtime.execute_skill("traced-skill", options).unwrap();
+
+    // The skill sees the execution span, in the caller's trace.
+    let seen = result.output["traceparent"].as_str().unwrap();
+    assert!(seen.starts_with(&format!("00-{trace_id}-")), "{seen}");
+    assert_ne!(seen, caller);
+    assert_eq!(result.audit.traceparent.as_deref(), Some(caller.as_str()));
+
+    tracing.flush().unwrap();
+    let (request_line, body) = exports.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert!(request_line.starts_with("POST /v1/traces "), "{request_line}");
+    let contains = |needle: &[u8]| body.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(b"openskills.execute"));
+    assert!(contains(b"traced-skill"));
+    assert!(contains(&hex::decode(trace_id).unwrap()));
+}
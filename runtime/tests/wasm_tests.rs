@@ -1,6 +1,8 @@
 use openskills_runtime::{OpenSkillRuntime, ExecutionOptions, RuntimeError};
 use serde_json::json;
+use std::fs;
 use std::path::PathBuf;
+use tempfile::TempDir;
 
 fn get_examples_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -65,3 +67,586 @@ fn test_wasm_execution_basic() {
         }
     }
 }
+
+/// `openskills:skill` component that echoes its input, or returns
+/// `err("empty input")` for `{}`. Written in the text format, which
+/// wasmtime accepts in place of a binary.
+const ECHO_SKILL_WAT: &str = r#"(component
+  (core module $m
+    (memory (export "memory") 1)
+    (global $bump (mut i32) (i32.const 1024))
+    (data (i32.const 16) "echo0.1.0empty input")
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ret i32)
+      (local.set $ret (global.get $bump))
+      (global.set $bump (i32.add (global.get $bump) (local.get 3)))
+      (local.get $ret))
+    (func (export "run") (param $ptr i32) (param $len i32) (result i32)
+      (if (i32.eq (local.get $len) (i32.const 2))
+        (then
+          (i32.store (i32.const 0) (i32.const 1))
+          (i32.store (i32.const 4) (i32.const 25))
+          (i32.store (i32.const 8) (i32.const 11)))
+        (else
+          (i32.store (i32.const 0) (i32.const 0))
+          (i32.store (i32.const 4) (local.get $ptr))
+          (i32.store (i32.const 8) (local.get $len))))
+      (i32.const 0))
+    (func (export "metadata") (result i32)
+      (i32.store (i32.const 32) (i32.const 16))
+      (i32.store (i32.const 36) (i32.const 4))
+      (i32.store (i32.const 40) (i32.const 20))
+      (i32.store (i32.const 44) (i32.const 5))
+      (i32.const 32)))
+  (core instance $i (instantiate $m))
+  (type $info (record (field "name" string) (field "version" string)))
+  (export $info-export "skill-info" (type $info))
+  (func $run (param "input" string) (result (result string (error string)))
+    (canon lift (core func $i "run") (memory $i "memory") (realloc (func $i "realloc"))))
+  (func $metadata (result $info-export)
+    (canon lift (core func $i "metadata") (memory $i "memory")))
+  (export "run" (func $run))
+  (export "metadata" (func $metadata)))
+"#;
+
+fn create_wasm_skill(temp_dir: &TempDir, name: &str, component: &str) {
+    let skill_dir = temp_dir.path().join(name);
+    fs::create_dir_all(skill_dir.join("wasm")).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: WASM interface test skill.\n---\n# Instructions\nRun it.\n"),
+    )
+    .unwrap();
+    fs::write(skill_dir.join("wasm/skill.wasm"), component).unwrap();
+}
+
+#[test]
+fn test_wasm_skill_world_component() {
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let input = json!({ "query": "hello" });
+    let result = runtime
+        .execute_skill(
+            "echo",
+            ExecutionOptions {
+                timeout_ms: Some(5000),
+                input: Some(input.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.output, input);
+    assert!(result.stdout.is_empty());
+
+    // `err` from `run` fails the execution with the component's message.
+    let result = runtime
+        .execute_skill(
+            "echo",
+            ExecutionOptions {
+                timeout_ms: Some(5000),
+                input: Some(json!({})),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.output["error"], "empty input");
+}
+
+#[test]
+fn test_wasm_incompatible_component_rejected() {
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "empty", "(component)");
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    let err = runtime
+        .execute_skill(
+            "empty",
+            ExecutionOptions {
+                timeout_ms: Some(5000),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    match err {
+        RuntimeError::WasmError(msg) => assert!(msg.contains("Incompatible WASM component"), "{msg}"),
+        other => panic!("expected WasmError, got {other:?}"),
+    }
+}
+
+/// `openskills:skill` component whose `run` never returns.
+const SPIN_SKILL_WAT: &str = r#"(component
+  (core module $m
+    (memory (export "memory") 1)
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32) (i32.const 1024))
+    (func (export "run") (param i32 i32) (result i32)
+      (loop $spin (br $spin))
+      (i32.const 0))
+    (func (export "metadata") (result i32) (i32.const 0)))
+  (core instance $i (instantiate $m))
+  (type $info (record (field "name" string) (field "version" string)))
+  (export $info-export "skill-info" (type $info))
+  (func $run (param "input" string) (result (result string (error string)))
+    (canon lift (core func $i "run") (memory $i "memory") (realloc (func $i "realloc"))))
+  (func $metadata (result $info-export)
+    (canon lift (core func $i "metadata") (memory $i "memory")))
+  (export "run" (func $run))
+  (export "metadata" (func $metadata)))
+"#;
+
+#[test]
+fn test_wasm_timeout_on_shared_engine() {
+    use openskills_runtime::{ExecutionTarget, RuntimeExecutionStatus, WasmEngineConfig};
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "spin", SPIN_SKILL_WAT);
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_wasm_engine_config(WasmEngineConfig {
+            max_instances: 2,
+            module_cache: false,
+            ..Default::default()
+        });
+    runtime.discover_skills().unwrap();
+
+    // Each execution gets its own deadline on the shared engine: a timed-out
+    // run neither leaks into the next one nor poisons the engine.
+    for _ in 0..2 {
+        let started = Instant::now();
+        let result = runtime
+            .run_skill_target("spin", ExecutionTarget::Auto, Some(200), None, None)
+            .unwrap();
+        assert!(
+            matches!(result.audit.exit_status, RuntimeExecutionStatus::Timeout),
+            "{:?}: {}",
+            result.audit.exit_status,
+            result.output
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let result = runtime
+            .run_skill_target("echo", ExecutionTarget::Auto, Some(10000), Some(json!({ "n": 1 })), None)
+            .unwrap();
+        assert_eq!(result.output, json!({ "n": 1 }));
+    }
+}
+
+#[test]
+fn test_wasm_cancel_active_execution() {
+    use openskills_runtime::{ExecutionTarget, RuntimeExecutionStatus};
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "spin", SPIN_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    assert!(runtime.active_executions().is_empty());
+
+    let monitor = runtime.execution_monitor();
+    let session_id = runtime.get_session_id().to_string();
+    let canceller = std::thread::spawn(move || loop {
+        if let Some(execution) = monitor.active_executions().pop() {
+            assert_eq!(execution.skill_id, "spin");
+            assert_eq!(execution.target, "auto");
+            assert_eq!(execution.session_id, session_id);
+            assert!(monitor.cancel(execution.id));
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    });
+
+    let started = Instant::now();
+    let result = runtime
+        .run_skill_target("spin", ExecutionTarget::Auto, Some(30000), None, None)
+        .unwrap();
+    canceller.join().unwrap();
+    assert!(
+        matches!(&result.audit.exit_status, RuntimeExecutionStatus::Failed(reason) if reason == "cancelled"),
+        "{:?}",
+        result.audit.exit_status
+    );
+    assert!(started.elapsed() < Duration::from_secs(10));
+    assert!(runtime.active_executions().is_empty());
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_wasm_async_execution_cancellation() {
+    use openskills_runtime::{CancellationHandle, ExecutionTarget, RuntimeExecutionStatus};
+    use std::time::Duration;
+
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "spin", SPIN_SKILL_WAT);
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+    let tokio = tokio::runtime::Runtime::new().unwrap();
+
+    let result = tokio
+        .block_on(assert_send(runtime.execute_skill_async(
+            "echo",
+            ExecutionOptions {
+                input: Some(json!({ "n": 1 })),
+                ..Default::default()
+            },
+            CancellationHandle::default(),
+        )))
+        .unwrap();
+    assert_eq!(result.output, json!({ "n": 1 }));
+
+    let cancellation = CancellationHandle::default();
+    let canceller = {
+        let cancellation = cancellation.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            cancellation.cancel();
+        })
+    };
+    let result = tokio
+        .block_on(runtime.run_skill_target_async(
+            "spin",
+            ExecutionTarget::Auto,
+            Some(30000),
+            None,
+            None,
+            cancellation,
+        ))
+        .unwrap();
+    canceller.join().unwrap();
+    assert!(
+        matches!(&result.audit.exit_status, RuntimeExecutionStatus::Failed(reason) if reason == "cancelled"),
+        "{:?}",
+        result.audit.exit_status
+    );
+    assert!(runtime.active_executions().is_empty());
+}
+
+#[test]
+fn test_wasm_runtime_events() {
+    use openskills_runtime::{RuntimeEvent, RuntimeEventHandler, RuntimeExecutionStatus};
+    use std::sync::{Arc, Mutex};
+
+    struct Collector(Arc<Mutex<Vec<RuntimeEvent>>>);
+    impl RuntimeEventHandler for Collector {
+        fn on_event(&self, event: &RuntimeEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path())
+        .with_event_handler(Box::new(Collector(events.clone())));
+    runtime.discover_skills().unwrap();
+    runtime.activate_skill("echo").unwrap();
+    runtime
+        .execute_skill(
+            "echo",
+            ExecutionOptions {
+                timeout_ms: Some(5000),
+                input: Some(json!({ "query": "hello" })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 4, "{events:?}");
+    assert!(matches!(events[0], RuntimeEvent::DiscoveryCompleted { skills: 1, errors: 0 }));
+    assert!(matches!(&events[1], RuntimeEvent::SkillActivated { skill_id } if skill_id == "echo"));
+    let RuntimeEvent::ExecutionStarted { execution_id: started, skill_id, target } = &events[2] else {
+        panic!("expected ExecutionStarted, got {:?}", events[2]);
+    };
+    assert_eq!((skill_id.as_str(), target.as_str()), ("echo", "auto"));
+    let RuntimeEvent::ExecutionFinished { execution_id: finished, status, .. } = &events[3] else {
+        panic!("expected ExecutionFinished, got {:?}", events[3]);
+    };
+    assert_eq!(started, finished);
+    assert!(matches!(status, RuntimeExecutionStatus::Success), "{status:?}");
+}
+
+#[test]
+fn test_wasm_execution_history_suggestions() {
+    use openskills_runtime::ExecutionTarget;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "spin", SPIN_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_auto_limits(true);
+    runtime.discover_skills().unwrap();
+    assert_eq!(runtime.suggest_limits("spin").samples, 0);
+
+    for _ in 0..2 {
+        runtime
+            .run_skill_target("spin", ExecutionTarget::Auto, Some(200), None, None)
+            .unwrap();
+    }
+    // Timing out at 200ms suggests 400ms, which the 30s default already covers.
+    let suggestion = runtime.suggest_limits("spin");
+    assert_eq!(suggestion.skill_id, "spin");
+    assert_eq!(suggestion.samples, 2);
+    assert_eq!(suggestion.timeout_ms, None);
+    assert_eq!(suggestion.memory_mb, None);
+}
+
+#[test]
+fn test_wasm_precompile_skill() {
+    use openskills_runtime::WasmEngineConfig;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let instructions = temp_dir.path().join("notes");
+    fs::create_dir_all(&instructions).unwrap();
+    fs::write(
+        instructions.join("SKILL.md"),
+        "---\nname: notes\ndescription: Instructions only.\n---\n# Notes\n",
+    )
+    .unwrap();
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_wasm_engine_config(
+        WasmEngineConfig {
+            module_cache: false,
+            shared: false,
+            ..Default::default()
+        },
+    );
+
+    let phase_names = |timings: &openskills_runtime::SetupTimings| {
+        timings.phases.iter().map(|p| p.name.clone()).collect::<Vec<_>>()
+    };
+    let compiled = runtime.precompile_skill("echo").unwrap();
+    assert_eq!(compiled.backend, "wasm");
+    assert_eq!(phase_names(&compiled), vec!["engine", "component_compiled"]);
+    assert!(runtime.precompile_skill("notes").unwrap().phases.is_empty());
+
+    // The first execution starts from the compiled component.
+    let result = runtime
+        .execute_skill(
+            "echo",
+            ExecutionOptions {
+                input: Some(json!({ "n": 1 })),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.output, json!({ "n": 1 }));
+    assert_eq!(
+        phase_names(&result.setup_timings),
+        vec!["component_reused", "context", "instantiate"]
+    );
+}
+
+#[test]
+fn test_wasm_fuel_exhausted() {
+    use openskills_runtime::RuntimeExecutionStatus;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "spin", SPIN_SKILL_WAT);
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path());
+    runtime.discover_skills().unwrap();
+
+    // The fuel budget stops the loop long before the timeout would.
+    let result = runtime
+        .execute_skill(
+            "spin",
+            ExecutionOptions {
+                timeout_ms: Some(60000),
+                max_fuel: Some(1_000_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    match &result.audit.exit_status {
+        RuntimeExecutionStatus::Failed(msg) => assert_eq!(msg, "fuel exhausted"),
+        other => panic!("expected fuel exhaustion, got {other:?}"),
+    }
+    assert_eq!(result.audit.fuel_consumed, Some(1_000_000));
+
+    let result = runtime
+        .execute_skill(
+            "echo",
+            ExecutionOptions {
+                input: Some(json!({ "n": 1 })),
+                max_fuel: Some(1_000_000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.output, json!({ "n": 1 }));
+    let consumed = result.audit.fuel_consumed.unwrap();
+    assert!(consumed > 0 && consumed < 1_000_000, "{consumed}");
+}
+
+#[test]
+fn test_wasm_setup_timings_and_metrics() {
+    use openskills_runtime::WasmEngineConfig;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_wasm_skill(&temp_dir, "echo", ECHO_SKILL_WAT);
+    // A private engine, so the first execution here is the one that creates it.
+    let mut runtime = OpenSkillRuntime::from_directory(temp_dir.path()).with_wasm_engine_config(
+        WasmEngineConfig {
+            module_cache: false,
+            shared: false,
+            ..Default::default()
+        },
+    );
+    runtime.discover_skills().unwrap();
+
+    let phases = |runtime: &mut OpenSkillRuntime| {
+        let result = runtime
+            .execute_skill(
+                "echo",
+                ExecutionOptions {
+                    input: Some(json!({ "n": 1 })),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.setup_timings.backend, "wasm");
+        result
+            .setup_timings
+            .phases
+            .iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<_>>()
+    };
+    // Only the first execution creates the engine and compiles.
+    assert_eq!(
+        phases(&mut runtime),
+        vec!["engine", "component_compiled", "context", "instantiate"]
+    );
+    assert_eq!(
+        phases(&mut runtime),
+        vec!["component_reused", "context", "instantiate"]
+    );
+
+    let metrics = runtime.sandbox_metrics();
+    let instantiate = metrics
+        .iter()
+        .find(|m| m.backend == "wasm" && m.phase == "instantiate")
+        .unwrap();
+    assert_eq!(instantiate.count, 2);
+    assert!(instantiate.max <= instantiate.total);
+
+    runtime.reset_sandbox_metrics();
+    assert!(runtime.sandbox_metrics().is_empty());
+}
+
+/// `openskills:skill` component whose `run` writes `"hi"` to `out/hello.txt`
+/// through the host API, reads it back and returns it. Host errors become
+/// `err`.
+const HOST_API_SKILL_WAT: &str = r#"(component
+  (import "openskills:skill/host@0.1.0" (instance $host
+    (export "read-workspace-file"
+      (func (param "path" string) (result (result (list u8) (error string)))))
+    (export "write-workspace-file"
+      (func (param "path" string) (param "contents" (list u8)) (result (result (error string)))))))
+  (core module $libc
+    (memory (export "memory") 1)
+    (global $bump (mut i32) (i32.const 1024))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $ret i32)
+      (local.set $ret (global.get $bump))
+      (global.set $bump (i32.add (global.get $bump) (local.get 3)))
+      (local.get $ret)))
+  (core instance $libc (instantiate $libc))
+  (core func $write (canon lower (func $host "write-workspace-file")
+    (memory $libc "memory") (realloc (func $libc "realloc"))))
+  (core func $read (canon lower (func $host "read-workspace-file")
+    (memory $libc "memory") (realloc (func $libc "realloc"))))
+  (core module $m
+    (import "libc" "memory" (memory 1))
+    (import "host" "write" (func $write (param i32 i32 i32 i32 i32)))
+    (import "host" "read" (func $read (param i32 i32 i32)))
+    (data (i32.const 16) "host0.1.0")
+    (data (i32.const 100) "out/hello.txt")
+    (data (i32.const 120) "\"hi\"")
+    ;; Return `ok`/`err` with the string at the return area `$area`.
+    (func $finish (param $area i32) (result i32)
+      (i32.store (i32.const 0) (i32.load8_u (local.get $area)))
+      (i32.store (i32.const 4) (i32.load (i32.add (local.get $area) (i32.const 4))))
+      (i32.store (i32.const 8) (i32.load (i32.add (local.get $area) (i32.const 8))))
+      (i32.const 0))
+    (func (export "run") (param i32 i32) (result i32)
+      (call $write (i32.const 100) (i32.const 13) (i32.const 120) (i32.const 4) (i32.const 200))
+      (if (i32.load8_u (i32.const 200))
+        (then (return (call $finish (i32.const 200)))))
+      (call $read (i32.const 100) (i32.const 13) (i32.const 220))
+      (call $finish (i32.const 220)))
+    (func (export "metadata") (result i32)
+      (i32.store (i32.const 32) (i32.const 16))
+      (i32.store (i32.const 36) (i32.const 4))
+      (i32.store (i32.const 40) (i32.const 20))
+      (i32.store (i32.const 44) (i32.const 5))
+      (i32.const 32)))
+  (core instance $i (instantiate $m
+    (with "libc" (instance $libc))
+    (with "host" (instance (export "write" (func $write)) (export "read" (func $read))))))
+  (type $info (record (field "name" string) (field "version" string)))
+  (export $info-export "skill-info" (type $info))
+  (func $run (param "input" string) (result (result string (error string)))
+    (canon lift (core func $i "run") (memory $libc "memory") (realloc (func $libc "realloc"))))
+  (func $metadata (result $info-export)
+    (canon lift (core func $i "metadata") (memory $libc "memory")))
+  (export "run" (func $run))
+  (export "metadata" (func $metadata)))
+"#;
+
+#[test]
+fn test_wasm_host_api_workspace_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let workspace = TempDir::new().unwrap();
+    for (name, tools) in [("writer", "Read, Write"), ("reader", "Read")] {
+        let skill_dir = temp_dir.path().join(name);
+        fs::create_dir_all(skill_dir.join("wasm")).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: Host API test skill.\nallowed-tools: {tools}\n---\n# Instructions\nRun it.\n"),
+        )
+        .unwrap();
+        fs::write(skill_dir.join("wasm/skill.wasm"), HOST_API_SKILL_WAT).unwrap();
+    }
+    let mut runtime =
+        OpenSkillRuntime::from_directory(temp_dir.path()).with_workspace_dir(workspace.path());
+    runtime.discover_skills().unwrap();
+    let options = || ExecutionOptions {
+        timeout_ms: Some(5000),
+        ..Default::default()
+    };
+
+    let result = runtime.execute_skill("writer", options()).unwrap();
+    assert_eq!(result.output, json!("hi"));
+    assert_eq!(
+        fs::read_to_string(workspace.path().join("out/hello.txt")).unwrap(),
+        "\"hi\""
+    );
+    let calls: Vec<_> = result
+        .audit
+        .host_calls
+        .iter()
+        .map(|c| (c.function.as_str(), c.target.as_str(), c.allowed))
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("write-workspace-file", "out/hello.txt", true),
+            ("read-workspace-file", "out/hello.txt", true),
+        ]
+    );
+
+    // Without a write tool the call is refused and still audited.
+    let result = runtime.execute_skill("reader", options()).unwrap();
+    let error = result.output["error"].as_str().unwrap();
+    assert!(error.contains("requires Write"), "{error}");
+    assert_eq!(result.audit.host_calls.len(), 1);
+    assert!(!result.audit.host_calls[0].allowed);
+}
@@ -0,0 +1,103 @@
+//! Hot reload: `reload_skills` and, with the `watch` feature, `watch_skills`.
+
+use openskills_runtime::{
+    OpenSkillRuntime, RuntimeEvent, RuntimeEventHandler, SkillChange, SkillChangeKind,
+};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tempfile::TempDir;
+
+fn create_skill(root: &Path, name: &str, body: &str) {
+    let dir = root.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: Test skill.\n---\n{body}\n"),
+    )
+    .unwrap();
+}
+
+fn kinds(changes: &[SkillChange]) -> Vec<(&str, SkillChangeKind)> {
+    changes.iter().map(|c| (c.skill_id.as_str(), c.kind)).collect()
+}
+
+struct Recorder(Arc<Mutex<Vec<RuntimeEvent>>>);
+
+impl RuntimeEventHandler for Recorder {
+    fn on_event(&self, event: &RuntimeEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+#[test]
+fn test_reload_skills_reports_changes() {
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha", "Alpha.");
+    create_skill(skills.path(), "beta", "Beta.");
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut runtime = OpenSkillRuntime::from_directory(skills.path())
+        .with_event_handler(Box::new(Recorder(events.clone())));
+    runtime.discover_skills().unwrap();
+    assert!(runtime.reload_skills().unwrap().is_empty());
+
+    create_skill(skills.path(), "alpha", "Alpha, edited.");
+    fs::remove_dir_all(skills.path().join("beta")).unwrap();
+    create_skill(skills.path(), "gamma", "Gamma.");
+    let changes = runtime.reload_skills().unwrap();
+    assert_eq!(
+        kinds(&changes),
+        [
+            ("alpha", SkillChangeKind::Modified),
+            ("beta", SkillChangeKind::Removed),
+            ("gamma", SkillChangeKind::Added),
+        ]
+    );
+    assert_eq!(changes[1].path, skills.path().join("beta"));
+
+    let changed: Vec<String> = events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|e| match e {
+            RuntimeEvent::SkillChanged { skill_id, .. } => Some(skill_id.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(changed, ["alpha", "beta", "gamma"]);
+    assert!(runtime.get_skill_root("gamma").is_ok());
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn test_watch_skills_picks_up_edits() {
+    use std::time::Duration;
+
+    let skills = TempDir::new().unwrap();
+    create_skill(skills.path(), "alpha", "Alpha.");
+    let mut runtime = OpenSkillRuntime::from_directory(skills.path());
+    runtime.discover_skills().unwrap();
+    let runtime = Arc::new(Mutex::new(runtime));
+    let watcher = OpenSkillRuntime::watch_skills(&runtime).unwrap();
+
+    create_skill(skills.path(), "beta", "Beta.");
+    let change = watcher.changes().recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!((change.skill_id.as_str(), change.kind), ("beta", SkillChangeKind::Added));
+    assert!(runtime.lock().unwrap().get_skill_root("beta").is_ok());
+
+    fs::write(
+        skills.path().join("alpha").join("SKILL.md"),
+        "---\nname: alpha\ndescription: Edited.\n---\nAlpha.\n",
+    )
+    .unwrap();
+    let change = watcher.changes().recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!((change.skill_id.as_str(), change.kind), ("alpha", SkillChangeKind::Modified));
+    let descriptions: Vec<String> = runtime
+        .lock()
+        .unwrap()
+        .list_skills()
+        .into_iter()
+        .map(|s| s.description)
+        .collect();
+    assert!(descriptions.contains(&"Edited.".to_string()));
+}